@@ -0,0 +1,677 @@
+use anyhow::{Context as _, Result};
+use db::kvp::KEY_VALUE_STORE;
+use editor::{Editor, SelectionEffects, scroll::Autoscroll};
+use gpui::{
+    Action, AnyElement, App, AsyncWindowContext, ClickEvent, Context, DismissEvent, Entity,
+    EventEmitter, FocusHandle, Focusable, IntoElement, MouseDownEvent, Pixels, Point, Render,
+    SharedString, Subscription, Task, UniformListScrollHandle, WeakEntity, Window, anchored,
+    deferred, div, px, uniform_list,
+};
+use language::{Anchor, Buffer, ToPoint as _};
+use project::{Fs, Project, TypeHierarchyDirection, TypeHierarchyItem};
+use serde::{Deserialize, Serialize};
+use settings::{RegisterSetting, Settings};
+use std::{collections::HashMap, ops::Range, sync::Arc};
+use ui::{
+    Color, ContextMenu, IconButton, IconName, IconSize, Label, LabelSize, ListItem,
+    ListItemSpacing, ScrollAxes, Scrollbars, Tooltip, WithScrollbar, prelude::*,
+};
+use util::ResultExt;
+use workspace::{
+    Workspace,
+    dock::{DockPosition, Panel, PanelEvent},
+};
+use zed_actions::type_hierarchy::{ShowSubtypes, ShowSupertypes, ToggleFocus};
+
+const TYPE_HIERARCHY_PANEL_KEY: &str = "TypeHierarchyPanel";
+
+#[derive(Debug, RegisterSetting)]
+pub struct TypeHierarchyPanelSettings {
+    pub button: bool,
+    pub dock: DockPosition,
+    pub default_width: Pixels,
+}
+
+impl Settings for TypeHierarchyPanelSettings {
+    fn from_settings(content: &settings::SettingsContent) -> Self {
+        let panel = content.type_hierarchy_panel.as_ref().unwrap();
+        Self {
+            button: panel.button.unwrap(),
+            dock: panel.dock.unwrap().into(),
+            default_width: panel.default_width.map(px).unwrap(),
+        }
+    }
+}
+
+pub(crate) fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace
+            .register_action(|workspace, _: &ToggleFocus, window, cx| {
+                workspace.toggle_panel_focus::<TypeHierarchyPanel>(window, cx);
+            })
+            .register_action(|workspace, _: &ShowSupertypes, window, cx| {
+                show_type_hierarchy(workspace, TypeHierarchyDirection::Supertypes, window, cx);
+            })
+            .register_action(|workspace, _: &ShowSubtypes, window, cx| {
+                show_type_hierarchy(workspace, TypeHierarchyDirection::Subtypes, window, cx);
+            });
+    })
+    .detach();
+}
+
+/// Shows the type hierarchy of the type at the cursor of the active editor.
+fn show_type_hierarchy(
+    workspace: &mut Workspace,
+    direction: TypeHierarchyDirection,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+        return;
+    };
+    let Some((buffer, position)) = editor.update(cx, |editor, cx| {
+        let head = editor.selections.newest_anchor().head();
+        editor.buffer().read(cx).text_anchor_for_position(head, cx)
+    }) else {
+        return;
+    };
+    let Some(panel) = workspace.focus_panel::<TypeHierarchyPanel>(window, cx) else {
+        return;
+    };
+    panel.update(cx, |panel, cx| panel.show(buffer, position, direction, cx));
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedTypeHierarchyPanel {
+    width: Option<Pixels>,
+}
+
+/// A type of the tree, whose supertypes or subtypes are loaded when it is
+/// first expanded.
+struct TypeHierarchyEntry {
+    item: TypeHierarchyItem,
+    depth: usize,
+    children: Option<Vec<usize>>,
+    is_expanded: bool,
+}
+
+pub struct TypeHierarchyPanel {
+    project: Entity<Project>,
+    fs: Arc<dyn Fs>,
+    workspace: WeakEntity<Workspace>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    width: Option<Pixels>,
+    direction: TypeHierarchyDirection,
+    /// Where the hierarchy was requested, to request it again when the
+    /// direction changes.
+    origin: Option<(Entity<Buffer>, Anchor)>,
+    entries: Vec<TypeHierarchyEntry>,
+    visible_entries: Vec<usize>,
+    selected_entry: Option<usize>,
+    load_task: Task<()>,
+    expand_tasks: HashMap<usize, Task<()>>,
+    /// Shown instead of the tree when it is empty.
+    message: Option<SharedString>,
+    context_menu: Option<(Entity<ContextMenu>, Point<Pixels>, Subscription)>,
+    pending_serialization: Task<Option<()>>,
+}
+
+impl TypeHierarchyPanel {
+    pub fn new(workspace: &Workspace, cx: &mut Context<Self>) -> Self {
+        let project = workspace.project().clone();
+        Self {
+            fs: project.read(cx).fs().clone(),
+            project,
+            workspace: workspace.weak_handle(),
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::default(),
+            width: None,
+            direction: TypeHierarchyDirection::Subtypes,
+            origin: None,
+            entries: Vec::new(),
+            visible_entries: Vec::new(),
+            selected_entry: None,
+            load_task: Task::ready(()),
+            expand_tasks: HashMap::default(),
+            message: None,
+            context_menu: None,
+            pending_serialization: Task::ready(None),
+        }
+    }
+
+    /// Shows the hierarchy of the type at `position`.
+    pub fn show(
+        &mut self,
+        buffer: Entity<Buffer>,
+        position: Anchor,
+        direction: TypeHierarchyDirection,
+        cx: &mut Context<Self>,
+    ) {
+        self.origin = Some((buffer, position));
+        self.direction = direction;
+        self.refresh(cx);
+    }
+
+    fn refresh(&mut self, cx: &mut Context<Self>) {
+        let Some((buffer, position)) = self.origin.clone() else {
+            return;
+        };
+        let direction = self.direction;
+        self.entries.clear();
+        self.visible_entries.clear();
+        self.selected_entry = None;
+        self.expand_tasks.clear();
+        self.message = Some("Loading…".into());
+        cx.notify();
+
+        let hierarchy = self.project.update(cx, |project, cx| {
+            project.type_hierarchy(&buffer, position, direction, cx)
+        });
+        self.load_task = cx.spawn(async move |this, cx| {
+            let hierarchy = hierarchy.await;
+            this.update(cx, |this, cx| {
+                match hierarchy {
+                    Ok(Some(hierarchy)) => {
+                        this.message = None;
+                        this.entries.push(TypeHierarchyEntry {
+                            item: hierarchy.item,
+                            depth: 0,
+                            children: None,
+                            is_expanded: true,
+                        });
+                        this.push_types(0, hierarchy.types);
+                        this.selected_entry = Some(0);
+                    }
+                    Ok(None) => {
+                        this.message = Some("No type hierarchy at the cursor".into());
+                    }
+                    Err(error) => {
+                        log::error!("Failed to load the type hierarchy: {error:#}");
+                        this.message = Some("Failed to load the type hierarchy".into());
+                    }
+                }
+                this.update_visible_entries();
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    fn toggle_direction(&mut self, cx: &mut Context<Self>) {
+        self.direction = match self.direction {
+            TypeHierarchyDirection::Supertypes => TypeHierarchyDirection::Subtypes,
+            TypeHierarchyDirection::Subtypes => TypeHierarchyDirection::Supertypes,
+        };
+        self.refresh(cx);
+    }
+
+    fn push_types(&mut self, parent: usize, types: Vec<TypeHierarchyItem>) {
+        let depth = self.entries[parent].depth + 1;
+        let mut children = Vec::with_capacity(types.len());
+        for item in types {
+            children.push(self.entries.len());
+            self.entries.push(TypeHierarchyEntry {
+                item,
+                depth,
+                children: None,
+                is_expanded: false,
+            });
+        }
+        self.entries[parent].children = Some(children);
+    }
+
+    fn toggle_expanded(&mut self, index: usize, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.get_mut(index) else {
+            return;
+        };
+        entry.is_expanded = !entry.is_expanded;
+        if entry.is_expanded && entry.children.is_none() {
+            self.load_types(index, cx);
+        }
+        self.update_visible_entries();
+        cx.notify();
+    }
+
+    /// Requests the supertypes or subtypes of an entry's type, at the start
+    /// of its name.
+    fn load_types(&mut self, index: usize, cx: &mut Context<Self>) {
+        if self.expand_tasks.contains_key(&index) {
+            return;
+        }
+        let item = &self.entries[index].item;
+        let buffer = item.location.buffer.clone();
+        let position = item.selection_range.start;
+        let direction = self.direction;
+        let hierarchy = self.project.update(cx, |project, cx| {
+            project.type_hierarchy(&buffer, position, direction, cx)
+        });
+        let task = cx.spawn(async move |this, cx| {
+            let types = hierarchy
+                .await
+                .log_err()
+                .flatten()
+                .map(|hierarchy| hierarchy.types)
+                .unwrap_or_default();
+            this.update(cx, |this, cx| {
+                this.expand_tasks.remove(&index);
+                this.push_types(index, types);
+                this.update_visible_entries();
+                cx.notify();
+            })
+            .ok();
+        });
+        self.expand_tasks.insert(index, task);
+    }
+
+    fn update_visible_entries(&mut self) {
+        self.visible_entries.clear();
+        let mut stack = if self.entries.is_empty() {
+            Vec::new()
+        } else {
+            vec![0]
+        };
+        while let Some(index) = stack.pop() {
+            self.visible_entries.push(index);
+            let entry = &self.entries[index];
+            if entry.is_expanded
+                && let Some(children) = &entry.children
+            {
+                stack.extend(children.iter().rev());
+            }
+        }
+    }
+
+    fn go_to_type(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.get(index) else {
+            return;
+        };
+        let buffer = entry.item.location.buffer.clone();
+        let position = entry.item.selection_range.start.to_point(buffer.read(cx));
+        self.workspace
+            .update(cx, |workspace, cx| {
+                let pane = workspace.active_pane().clone();
+                let editor = workspace
+                    .open_project_item::<Editor>(pane, buffer, true, true, true, true, window, cx);
+                editor.update(cx, |editor, cx| {
+                    editor.change_selections(
+                        SelectionEffects::scroll(Autoscroll::center()),
+                        window,
+                        cx,
+                        |s| s.select_ranges([position..position]),
+                    );
+                });
+            })
+            .ok();
+    }
+
+    fn serialization_key(workspace: &Workspace) -> Option<String> {
+        workspace
+            .database_id()
+            .map(|id| i64::from(id).to_string())
+            .or(workspace.session_id())
+            .map(|id| format!("{}-{:?}", TYPE_HIERARCHY_PANEL_KEY, id))
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let Some(serialization_key) = self
+            .workspace
+            .read_with(cx, |workspace, _| Self::serialization_key(workspace))
+            .ok()
+            .flatten()
+        else {
+            return;
+        };
+        let width = self.width;
+        self.pending_serialization = cx.background_spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        serialization_key,
+                        serde_json::to_string(&SerializedTypeHierarchyPanel { width })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    fn on_entry_click(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        self.selected_entry = Some(index);
+        self.go_to_type(index, window, cx);
+        cx.notify();
+    }
+
+    fn render_tree(&mut self, cx: &mut Context<Self>) -> impl IntoElement {
+        uniform_list(
+            "type_hierarchy_tree",
+            self.visible_entries.len(),
+            cx.processor(|this, range: Range<usize>, window, cx| {
+                range
+                    .filter_map(|ix| {
+                        let index = *this.visible_entries.get(ix)?;
+                        Some(this.render_entry(index, window, cx))
+                    })
+                    .collect()
+            }),
+        )
+        .track_scroll(&self.scroll_handle)
+        .size_full()
+    }
+
+    fn render_entry(
+        &self,
+        index: usize,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let entry = &self.entries[index];
+        let is_selected = self.selected_entry == Some(index);
+        let has_children = entry
+            .children
+            .as_ref()
+            .is_none_or(|children| !children.is_empty());
+        let path = entry
+            .item
+            .location
+            .buffer
+            .read(cx)
+            .file()
+            .map(|file| file.file_name(cx).to_string());
+        let detail = entry.item.detail.clone().or(path);
+
+        ListItem::new(index)
+            .spacing(ListItemSpacing::Sparse)
+            .toggle_state(is_selected)
+            .on_click(cx.listener(move |this, _: &ClickEvent, window, cx| {
+                this.on_entry_click(index, window, cx)
+            }))
+            .on_secondary_mouse_down(cx.listener(
+                move |this, event: &MouseDownEvent, window, cx| {
+                    this.deploy_context_menu(event.position, index, window, cx)
+                },
+            ))
+            .child(
+                h_flex()
+                    .gap_1()
+                    .items_center()
+                    .pl(px(entry.depth as f32 * 16.0))
+                    .when(has_children, |this| {
+                        this.child(
+                            IconButton::new(
+                                ("type-hierarchy-disclosure", index),
+                                if entry.is_expanded {
+                                    IconName::ChevronDown
+                                } else {
+                                    IconName::ChevronRight
+                                },
+                            )
+                            .icon_size(IconSize::Small)
+                            .icon_color(Color::Muted)
+                            .on_click(
+                                cx.listener(move |this, _, _, cx| this.toggle_expanded(index, cx)),
+                            ),
+                        )
+                    })
+                    .when(!has_children, |this| this.child(div().w(px(16.0))))
+                    .child(Label::new(entry.item.name.clone()).size(LabelSize::Small))
+                    .when_some(detail, |this, detail| {
+                        this.child(
+                            Label::new(detail)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted)
+                                .truncate(),
+                        )
+                    }),
+            )
+            .into_any_element()
+    }
+
+    fn deploy_context_menu(
+        &mut self,
+        position: Point<Pixels>,
+        index: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let focus_handle = self.focus_handle.clone();
+        let panel_entity = cx.entity();
+        self.selected_entry = Some(index);
+
+        let context_menu = ContextMenu::build(window, cx, move |menu, window, _| {
+            menu.context(focus_handle.clone())
+                .entry(
+                    "Go to Type",
+                    None,
+                    window.handler_for(&panel_entity, move |this, window, cx| {
+                        this.go_to_type(index, window, cx)
+                    }),
+                )
+                .entry(
+                    "Show Supertypes",
+                    None,
+                    window.handler_for(&panel_entity, move |this, _, cx| {
+                        this.show_entry(index, TypeHierarchyDirection::Supertypes, cx)
+                    }),
+                )
+                .entry(
+                    "Show Subtypes",
+                    None,
+                    window.handler_for(&panel_entity, move |this, _, cx| {
+                        this.show_entry(index, TypeHierarchyDirection::Subtypes, cx)
+                    }),
+                )
+        });
+
+        window.focus(&context_menu.focus_handle(cx));
+        let subscription = cx.subscribe(&context_menu, |this, _, _: &DismissEvent, cx| {
+            this.context_menu.take();
+            cx.notify();
+        });
+
+        self.context_menu = Some((context_menu, position, subscription));
+        cx.notify();
+    }
+
+    /// Makes an entry the root of the tree.
+    fn show_entry(
+        &mut self,
+        index: usize,
+        direction: TypeHierarchyDirection,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(entry) = self.entries.get(index) else {
+            return;
+        };
+        let buffer = entry.item.location.buffer.clone();
+        let position = entry.item.selection_range.start;
+        self.show(buffer, position, direction, cx);
+    }
+
+    fn render_toolbar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let (title, toggle_tooltip) = match self.direction {
+            TypeHierarchyDirection::Supertypes => ("Supertypes", "Show Subtypes"),
+            TypeHierarchyDirection::Subtypes => ("Subtypes", "Show Supertypes"),
+        };
+        h_flex()
+            .px_2()
+            .py_1()
+            .gap_1()
+            .justify_between()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(Label::new(title).size(LabelSize::Small).color(Color::Muted))
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        IconButton::new("toggle-type-direction", IconName::ArrowRightLeft)
+                            .icon_size(IconSize::Small)
+                            .tooltip(Tooltip::text(toggle_tooltip))
+                            .on_click(cx.listener(|this, _, _, cx| this.toggle_direction(cx))),
+                    )
+                    .child(
+                        IconButton::new("refresh-type-hierarchy", IconName::RotateCw)
+                            .icon_size(IconSize::Small)
+                            .tooltip(Tooltip::text("Refresh"))
+                            .disabled(self.origin.is_none())
+                            .on_click(cx.listener(|this, _, _, cx| this.refresh(cx))),
+                    ),
+            )
+    }
+}
+
+impl Render for TypeHierarchyPanel {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let content = if self.entries.is_empty() {
+            let message = self.message.clone().unwrap_or_else(|| {
+                "Show the type hierarchy of a type from the editor's context menu".into()
+            });
+            v_flex()
+                .id("empty-type_hierarchy_panel")
+                .p_4()
+                .size_full()
+                .items_center()
+                .justify_center()
+                .child(
+                    Label::new(message)
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+                .into_any_element()
+        } else {
+            self.render_tree(cx)
+                .custom_scrollbars(
+                    Scrollbars::default()
+                        .tracked_scroll_handle(&self.scroll_handle)
+                        .with_track_along(
+                            ScrollAxes::Horizontal,
+                            cx.theme().colors().panel_background,
+                        )
+                        .notify_content(),
+                    window,
+                    cx,
+                )
+                .size_full()
+                .into_any_element()
+        };
+
+        v_flex()
+            .id("type_hierarchy_panel")
+            .key_context("TypeHierarchyPanel")
+            .size_full()
+            .track_focus(&self.focus_handle)
+            .child(self.render_toolbar(cx))
+            .child(content)
+            .children(self.context_menu.as_ref().map(|(menu, position, _)| {
+                deferred(
+                    anchored()
+                        .position(*position)
+                        .anchor(gpui::Corner::TopLeft)
+                        .child(menu.clone()),
+                )
+                .with_priority(3)
+            }))
+    }
+}
+
+impl EventEmitter<PanelEvent> for TypeHierarchyPanel {}
+
+impl Panel for TypeHierarchyPanel {
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        TypeHierarchyPanelSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file(self.fs.clone(), cx, move |settings, _| {
+            settings.type_hierarchy_panel.get_or_insert_default().dock = Some(position.into())
+        });
+    }
+
+    fn size(&self, _: &Window, cx: &App) -> Pixels {
+        self.width
+            .unwrap_or_else(|| TypeHierarchyPanelSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.width = size;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
+        TypeHierarchyPanelSettings::get_global(cx)
+            .button
+            .then_some(IconName::Blocks)
+    }
+
+    fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<&'static str> {
+        Some("Type Hierarchy")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn persistent_name() -> &'static str {
+        "Type Hierarchy"
+    }
+
+    fn panel_key() -> &'static str {
+        TYPE_HIERARCHY_PANEL_KEY
+    }
+
+    fn starts_open(&self, _: &Window, _: &App) -> bool {
+        false
+    }
+
+    fn activation_priority(&self) -> u32 {
+        13
+    }
+}
+
+impl Focusable for TypeHierarchyPanel {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl TypeHierarchyPanel {
+    pub async fn load(
+        workspace: WeakEntity<Workspace>,
+        mut cx: AsyncWindowContext,
+    ) -> Result<Entity<Self>> {
+        let serialized_panel = match workspace
+            .read_with(&cx, |workspace, _| Self::serialization_key(workspace))
+            .ok()
+            .flatten()
+        {
+            Some(serialization_key) => cx
+                .background_spawn(async move { KEY_VALUE_STORE.read_kvp(&serialization_key) })
+                .await
+                .context("loading type hierarchy panel")
+                .log_err()
+                .flatten()
+                .map(|panel| serde_json::from_str::<SerializedTypeHierarchyPanel>(&panel))
+                .transpose()
+                .log_err()
+                .flatten(),
+            None => None,
+        };
+
+        workspace.update(&mut cx, |workspace, cx| {
+            cx.new(|cx| {
+                let mut panel = TypeHierarchyPanel::new(workspace, cx);
+                if let Some(serialized_panel) = serialized_panel {
+                    panel.width = serialized_panel.width.map(|px| px.round());
+                }
+                panel
+            })
+        })
+    }
+}
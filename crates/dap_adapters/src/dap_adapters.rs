@@ -3,6 +3,7 @@ mod dotnet;
 mod gdb;
 mod go;
 mod javascript;
+mod netcoredbg;
 mod python;
 
 #[cfg(test)]
@@ -24,6 +25,7 @@ use gdb::GdbDebugAdapter;
 use go::GoDebugAdapter;
 use gpui::{App, BorrowAppContext};
 use javascript::JsDebugAdapter;
+use netcoredbg::NetCoreDbgDebugAdapter;
 use python::PythonDebugAdapter;
 use serde_json::json;
 use task::{DebugScenario, ZedDebugConfig};
@@ -32,6 +34,7 @@ pub fn init(cx: &mut App) {
     cx.update_default_global(|registry: &mut DapRegistry, _cx| {
         registry.add_adapter(Arc::from(CodeLldbDebugAdapter::default()));
         registry.add_adapter(Arc::from(DotNetDebugAdapter::default()));
+        registry.add_adapter(Arc::from(NetCoreDbgDebugAdapter::default()));
         registry.add_adapter(Arc::from(PythonDebugAdapter::default()));
         registry.add_adapter(Arc::from(JsDebugAdapter::default()));
         registry.add_adapter(Arc::from(GoDebugAdapter::default()));
@@ -56,7 +56,8 @@ impl DotNetDebugAdapter {
         // vsdbg not found
         bail!(
             "vsdbg not found. Please install .NET SDK or download vsdbg manually.\n\
-             To install: https://github.com/microsoft/vscode-csharp or dotnet install tool"
+             To install: https://github.com/microsoft/vscode-csharp or dotnet install tool\n\
+             Alternatively, debug with the open source netcoredbg adapter."
         )
     }
 
@@ -93,6 +94,10 @@ impl DotNetDebugAdapter {
 
         let mut configuration = task_definition.config.clone();
 
+        if request == StartDebuggingRequestArgumentsRequest::Attach {
+            normalize_process_id(&mut configuration)?;
+        }
+
         // Set console if not provided
         if configuration.get("console").is_none() {
             configuration["console"] = Value::String("integratedTerminal".to_string());
@@ -107,6 +112,23 @@ impl DotNetDebugAdapter {
     }
 }
 
+/// Turns the `processId` of an attach request into a number, as process
+/// pickers substitute process ids into strings such as `"$ZED_PICK_DOTNET_PID"`.
+pub(crate) fn normalize_process_id(configuration: &mut Value) -> Result<()> {
+    match configuration.get("processId") {
+        Some(Value::Number(_)) => {}
+        Some(Value::String(process_id)) => {
+            let process_id = process_id
+                .trim()
+                .parse::<u32>()
+                .map_err(|_| anyhow::anyhow!("'processId' is not a process id: {process_id}"))?;
+            configuration["processId"] = process_id.into();
+        }
+        _ => bail!("'processId' is required for attach requests"),
+    }
+    Ok(())
+}
+
 #[async_trait(?Send)]
 impl DebugAdapter for DotNetDebugAdapter {
     fn name(&self) -> DebugAdapterName {
@@ -114,11 +136,21 @@ impl DebugAdapter for DotNetDebugAdapter {
     }
 
     async fn config_from_zed_format(&self, zed_scenario: task::ZedDebugConfig) -> Result<task::DebugScenario> {
+        let mut config = serde_json::to_value(&zed_scenario.request)?;
+        // vsdbg names the process to attach to `processId`
+        if let dap::DebugRequest::Attach(attach) = &zed_scenario.request {
+            if let Some(object) = config.as_object_mut() {
+                object.remove("process_id");
+            }
+            if let Some(process_id) = attach.process_id {
+                config["processId"] = process_id.into();
+            }
+        }
         Ok(task::DebugScenario {
             adapter: zed_scenario.adapter,
             label: zed_scenario.label,
             build: None,
-            config: serde_json::to_value(&zed_scenario.request)?,
+            config,
             tcp_connection: None,
         })
     }
@@ -167,7 +199,7 @@ impl DebugAdapter for DotNetDebugAdapter {
                 },
                 "processId": {
                     "type": ["string", "integer"],
-                    "description": "Process ID to attach to (for attach requests)"
+                    "description": "Process ID to attach to (for attach requests), or `$ZED_PICK_DOTNET_PID` to pick one of the running .NET processes"
                 }
             }
         })
@@ -0,0 +1,383 @@
+use anyhow::{Result, bail};
+use async_trait::async_trait;
+use collections::HashMap;
+use dap::{StartDebuggingRequestArguments, adapters::DebugTaskDefinition};
+use gpui::AsyncApp;
+use paths::debug_adapters_dir;
+use std::{ffi::OsStr, path::PathBuf};
+use task::{DebugScenario, ZedDebugConfig};
+
+use crate::*;
+
+/// Samsung's open source debugger for .NET, an alternative to vsdbg, whose
+/// license only allows its use in Microsoft's IDEs.
+#[derive(Default)]
+pub(crate) struct NetCoreDbgDebugAdapter;
+
+impl NetCoreDbgDebugAdapter {
+    const ADAPTER_NAME: &'static str = "netcoredbg";
+    const BINARY_NAME: &'static str = if cfg!(windows) {
+        "netcoredbg.exe"
+    } else {
+        "netcoredbg"
+    };
+
+    /// Finds netcoredbg: at the path given by the scenario or the `dap`
+    /// settings, on the `PATH`, or in the debug adapters directory, where
+    /// release archives unpack into a `netcoredbg` directory of their own.
+    async fn find_binary(
+        &self,
+        delegate: &Arc<dyn DapDelegate>,
+        config: &DebugTaskDefinition,
+        user_installed_path: Option<PathBuf>,
+    ) -> Result<PathBuf> {
+        if let Some(path) = config
+            .config
+            .get("netcoredbg_path")
+            .and_then(|path| path.as_str())
+        {
+            return Ok(PathBuf::from(path));
+        }
+        if let Some(path) = user_installed_path.filter(|path| path.exists()) {
+            return Ok(path);
+        }
+        if let Some(path) = delegate.which(OsStr::new(Self::BINARY_NAME)).await {
+            return Ok(path);
+        }
+        let cache_dir = debug_adapters_dir().join(Self::ADAPTER_NAME);
+        if let Some(path) = [
+            cache_dir.join(Self::BINARY_NAME),
+            cache_dir.join("netcoredbg").join(Self::BINARY_NAME),
+        ]
+        .into_iter()
+        .find(|path| path.exists())
+        {
+            return Ok(path);
+        }
+        bail!(
+            "netcoredbg not found. Download a release from https://github.com/Samsung/netcoredbg/releases \
+             and unpack it into {}, or set the path to netcoredbg in the `dap` settings.",
+            cache_dir.display()
+        )
+    }
+}
+
+/// Ensures that netcoredbg speaks the Debug Adapter Protocol.
+fn ensure_vscode_interpreter(mut args: Vec<String>) -> Vec<String> {
+    if !args
+        .iter()
+        .any(|arg| arg.trim().starts_with("--interpreter="))
+    {
+        args.insert(0, "--interpreter=vscode".to_string());
+    }
+    args
+}
+
+#[async_trait(?Send)]
+impl DebugAdapter for NetCoreDbgDebugAdapter {
+    fn name(&self) -> DebugAdapterName {
+        DebugAdapterName(Self::ADAPTER_NAME.into())
+    }
+
+    async fn config_from_zed_format(&self, zed_scenario: ZedDebugConfig) -> Result<DebugScenario> {
+        let mut obj = serde_json::Map::default();
+        obj.insert("type".into(), "coreclr".into());
+
+        match &zed_scenario.request {
+            dap::DebugRequest::Attach(attach) => {
+                obj.insert("request".into(), "attach".into());
+                obj.insert("processId".into(), attach.process_id.into());
+            }
+
+            dap::DebugRequest::Launch(launch) => {
+                obj.insert("request".into(), "launch".into());
+                // netcoredbg starts programs itself, so framework-dependent
+                // assemblies run through the dotnet host
+                let mut args = launch.args.clone();
+                if launch.program.ends_with(".dll") {
+                    obj.insert("program".into(), "dotnet".into());
+                    args.insert(0, launch.program.clone());
+                } else {
+                    obj.insert("program".into(), launch.program.clone().into());
+                }
+                if !args.is_empty() {
+                    obj.insert("args".into(), args.into());
+                }
+                if !launch.env.is_empty() {
+                    obj.insert("env".into(), launch.env_json());
+                }
+                if let Some(cwd) = launch.cwd.as_ref() {
+                    obj.insert("cwd".into(), cwd.to_string_lossy().into_owned().into());
+                }
+                if let Some(stop_on_entry) = zed_scenario.stop_on_entry {
+                    obj.insert("stopAtEntry".into(), stop_on_entry.into());
+                }
+            }
+        }
+
+        Ok(DebugScenario {
+            adapter: zed_scenario.adapter,
+            label: zed_scenario.label,
+            build: None,
+            config: serde_json::Value::Object(obj),
+            tcp_connection: None,
+        })
+    }
+
+    fn dap_schema(&self) -> serde_json::Value {
+        json!({
+            "oneOf": [
+                {
+                    "allOf": [
+                        {
+                            "type": "object",
+                            "required": ["request"],
+                            "properties": {
+                                "request": {
+                                    "type": "string",
+                                    "enum": ["launch"],
+                                    "description": "Request to launch a new .NET process"
+                                }
+                            }
+                        },
+                        {
+                            "type": "object",
+                            "properties": {
+                                "program": {
+                                    "type": "string",
+                                    "description": "The program to debug: an executable, or `dotnet` with the assembly as its first argument. An assembly path is run through `dotnet`."
+                                },
+                                "args": {
+                                    "type": "array",
+                                    "items": {
+                                        "type": "string"
+                                    },
+                                    "description": "Command line arguments passed to the program.",
+                                    "default": []
+                                },
+                                "cwd": {
+                                    "type": "string",
+                                    "description": "Working directory of the program."
+                                },
+                                "env": {
+                                    "type": "object",
+                                    "description": "Environment variables for the program. Each key is the name of an environment variable; each value is the value of that variable."
+                                },
+                                "stopAtEntry": {
+                                    "type": "boolean",
+                                    "description": "Stop at the entry point of the program.",
+                                    "default": false
+                                }
+                            },
+                            "required": ["program"]
+                        }
+                    ]
+                },
+                {
+                    "allOf": [
+                        {
+                            "type": "object",
+                            "required": ["request"],
+                            "properties": {
+                                "request": {
+                                    "type": "string",
+                                    "enum": ["attach"],
+                                    "description": "Request to attach to a running .NET process"
+                                }
+                            }
+                        },
+                        {
+                            "type": "object",
+                            "properties": {
+                                "processId": {
+                                    "type": ["integer", "string"],
+                                    "description": "The id of the process to attach to, or `$ZED_PICK_DOTNET_PID` to pick one of the running .NET processes."
+                                }
+                            },
+                            "required": ["processId"]
+                        }
+                    ]
+                }
+            ],
+            "properties": {
+                "justMyCode": {
+                    "type": "boolean",
+                    "description": "Only step through and break in user code.",
+                    "default": true
+                },
+                "enableStepFiltering": {
+                    "type": "boolean",
+                    "description": "Step over properties and operators.",
+                    "default": true
+                },
+                "netcoredbg_path": {
+                    "type": "string",
+                    "description": "Path to the netcoredbg executable, if the one found otherwise is not desirable."
+                },
+                "netcoredbg_args": {
+                    "type": "array",
+                    "items": {
+                        "type": "string"
+                    },
+                    "description": "Additional arguments given to netcoredbg, such as `--engineLogging=<file>`. `--interpreter=vscode` is always added.",
+                    "default": []
+                }
+            }
+        })
+    }
+
+    async fn get_binary(
+        &self,
+        delegate: &Arc<dyn DapDelegate>,
+        config: &DebugTaskDefinition,
+        user_installed_path: Option<PathBuf>,
+        user_args: Option<Vec<String>>,
+        user_env: Option<HashMap<String, String>>,
+        _: &mut AsyncApp,
+    ) -> Result<DebugAdapterBinary> {
+        let netcoredbg_path = self
+            .find_binary(delegate, config, user_installed_path)
+            .await?;
+
+        let netcoredbg_args = config
+            .config
+            .get("netcoredbg_args")
+            .and_then(|args| args.as_array())
+            .map(|args| {
+                args.iter()
+                    .filter_map(|arg| arg.as_str().map(|arg| arg.to_string()))
+                    .collect::<Vec<_>>()
+            })
+            .or(user_args)
+            .unwrap_or_default();
+
+        let request = self.request_kind(&config.config).await?;
+        if request == dap::StartDebuggingRequestArgumentsRequest::Launch
+            && config.config.get("program").is_none()
+        {
+            bail!("'program' is required for launch requests");
+        }
+
+        let mut configuration = config.config.clone();
+        if request == dap::StartDebuggingRequestArgumentsRequest::Attach {
+            crate::dotnet::normalize_process_id(&mut configuration)?;
+        }
+        if let Some(configuration) = configuration.as_object_mut() {
+            configuration.remove("netcoredbg_path");
+            configuration.remove("netcoredbg_args");
+            configuration
+                .entry("cwd")
+                .or_insert_with(|| delegate.worktree_root_path().to_string_lossy().into());
+        }
+
+        Ok(DebugAdapterBinary {
+            command: Some(netcoredbg_path.to_string_lossy().into_owned()),
+            arguments: ensure_vscode_interpreter(netcoredbg_args),
+            envs: user_env.unwrap_or_default(),
+            cwd: Some(delegate.worktree_root_path().to_path_buf()),
+            connection: None,
+            request_args: StartDebuggingRequestArguments {
+                request,
+                configuration,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use task::LaunchRequest;
+
+    #[gpui::test]
+    async fn test_netcoredbg_launch(cx: &mut gpui::TestAppContext) {
+        let adapter = NetCoreDbgDebugAdapter;
+        let scenario = adapter
+            .config_from_zed_format(ZedDebugConfig {
+                label: "App".into(),
+                adapter: NetCoreDbgDebugAdapter::ADAPTER_NAME.into(),
+                request: dap::DebugRequest::Launch(LaunchRequest {
+                    program: "/src/App/bin/Debug/net8.0/App.dll".to_string(),
+                    cwd: Some(PathBuf::from("/src/App")),
+                    args: vec!["--verbose".to_string()],
+                    env: Default::default(),
+                }),
+                stop_on_entry: Some(true),
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            scenario.config,
+            json!({
+                "type": "coreclr",
+                "request": "launch",
+                "program": "dotnet",
+                "args": ["/src/App/bin/Debug/net8.0/App.dll", "--verbose"],
+                "cwd": "/src/App",
+                "stopAtEntry": true
+            })
+        );
+
+        let mut config = scenario.config;
+        config["netcoredbg_path"] = "/opt/netcoredbg/netcoredbg".into();
+        config["netcoredbg_args"] = json!(["--engineLogging=/tmp/netcoredbg.log"]);
+        let binary = adapter
+            .get_binary(
+                &test_mocks::MockDelegate::new(),
+                &DebugTaskDefinition {
+                    label: "App".into(),
+                    adapter: NetCoreDbgDebugAdapter::ADAPTER_NAME.into(),
+                    config,
+                    tcp_connection: None,
+                },
+                None,
+                None,
+                None,
+                &mut cx.to_async(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            binary.command.as_deref(),
+            Some("/opt/netcoredbg/netcoredbg")
+        );
+        assert_eq!(
+            binary.arguments,
+            [
+                "--interpreter=vscode",
+                "--engineLogging=/tmp/netcoredbg.log"
+            ]
+        );
+        assert_eq!(
+            binary.request_args.request,
+            dap::StartDebuggingRequestArgumentsRequest::Launch
+        );
+        assert_eq!(
+            binary.request_args.configuration.get("netcoredbg_path"),
+            None
+        );
+    }
+
+    #[gpui::test]
+    async fn test_netcoredbg_attach() {
+        let scenario = NetCoreDbgDebugAdapter
+            .config_from_zed_format(ZedDebugConfig {
+                label: "Tests".into(),
+                adapter: NetCoreDbgDebugAdapter::ADAPTER_NAME.into(),
+                request: dap::DebugRequest::Attach(task::AttachRequest {
+                    process_id: Some(4242),
+                }),
+                stop_on_entry: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            scenario.config,
+            json!({
+                "type": "coreclr",
+                "request": "attach",
+                "processId": 4242
+            })
+        );
+    }
+}
@@ -23,6 +23,8 @@ pub(super) struct Candidate {
     pub(super) pid: u32,
     pub(super) name: SharedString,
     pub(super) command: Vec<String>,
+    /// The .NET assembly the process runs, when only .NET processes are listed.
+    pub(super) assembly: Option<SharedString>,
 }
 
 pub(crate) enum ModalIntent {
@@ -71,7 +73,36 @@ impl AttachModal {
         cx: &mut Context<Self>,
     ) -> Self {
         let processes_task = get_processes_for_project(&project, cx);
+        Self::with_processes_task(processes_task, intent, workspace, modal, window, cx)
+    }
+
+    /// An attach modal only listing processes running .NET, with the
+    /// assemblies they run.
+    pub(crate) fn new_dotnet(
+        intent: ModalIntent,
+        workspace: WeakEntity<Workspace>,
+        project: Entity<Project>,
+        modal: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let processes_task = get_dotnet_processes_for_project(&project, cx);
+        let modal = Self::with_processes_task(processes_task, intent, workspace, modal, window, cx);
+        modal.picker.update(cx, |picker, _| {
+            picker.delegate.placeholder_text =
+                Arc::from("Select the .NET process you want to attach the debugger to");
+        });
+        modal
+    }
 
+    fn with_processes_task(
+        processes_task: Task<Arc<[Candidate]>>,
+        intent: ModalIntent,
+        workspace: WeakEntity<Workspace>,
+        modal: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
         let modal = Self::with_processes(workspace, Arc::new([]), modal, intent, window, cx);
 
         cx.spawn_in(window, async move |this, cx| {
@@ -180,10 +211,15 @@ impl PickerDelegate for AttachModalDelegate {
                         StringMatchCandidate::new(
                             id,
                             format!(
-                                "{} {} {}",
+                                "{} {} {}{}",
                                 candidate.command.join(" "),
                                 candidate.pid,
-                                candidate.name
+                                candidate.name,
+                                candidate
+                                    .assembly
+                                    .as_ref()
+                                    .map(|assembly| format!(" {assembly}"))
+                                    .unwrap_or_default()
                             )
                             .as_str(),
                         )
@@ -324,7 +360,11 @@ impl PickerDelegate for AttachModalDelegate {
                 .child(
                     v_flex()
                         .items_start()
-                        .child(Label::new(format!("{} {}", candidate.name, candidate.pid)))
+                        .child(Label::new(format!(
+                            "{} {}",
+                            candidate.assembly.as_ref().unwrap_or(&candidate.name),
+                            candidate.pid
+                        )))
                         .child(
                             div()
                                 .id(format!("process-entry-{ix}-command"))
@@ -379,6 +419,7 @@ fn get_processes_for_project(project: &Entity<Project>, cx: &mut App) -> Task<Ar
                     pid: p.pid,
                     name: p.name.into(),
                     command: p.command,
+                    assembly: None,
                 })
                 .collect();
 
@@ -404,6 +445,7 @@ fn get_processes_for_project(project: &Entity<Project>, cx: &mut App) -> Task<Ar
                         .iter()
                         .map(|s| s.to_string_lossy().into_owned())
                         .collect::<Vec<_>>(),
+                    assembly: None,
                 }
             })
             .collect();
@@ -413,6 +455,31 @@ fn get_processes_for_project(project: &Entity<Project>, cx: &mut App) -> Task<Ar
     }
 }
 
+/// The processes running .NET. Remote processes can't be inspected, so only
+/// those running an assembly through the `dotnet` host are found there.
+fn get_dotnet_processes_for_project(
+    project: &Entity<Project>,
+    cx: &mut App,
+) -> Task<Arc<[Candidate]>> {
+    let is_remote = project.read(cx).remote_client().is_some();
+    let processes_task = get_processes_for_project(project, cx);
+    cx.background_spawn(async move {
+        let processes = processes_task.await;
+        let dotnet_process_ids = (!is_remote).then(dotnet::processes::dotnet_process_ids);
+        processes
+            .iter()
+            .filter(|candidate| match &dotnet_process_ids {
+                Some(process_ids) => process_ids.contains(&candidate.pid),
+                None => dotnet::processes::is_dotnet_host_command(&candidate.command),
+            })
+            .map(|candidate| Candidate {
+                assembly: dotnet::processes::assembly_name(&candidate.command).map(Into::into),
+                ..candidate.clone()
+            })
+            .collect()
+    })
+}
+
 #[cfg(test)]
 pub(crate) fn set_candidates(
     modal: &AttachModal,
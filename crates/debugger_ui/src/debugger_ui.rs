@@ -1,11 +1,15 @@
 use std::any::TypeId;
 
+use attach_modal::{AttachModal, ModalIntent};
 use debugger_panel::DebugPanel;
 use editor::{Editor, MultiBufferOffsetUtf16};
 use gpui::{Action, App, DispatchPhase, EntityInputHandler, actions};
 use new_process_modal::{NewProcessModal, NewProcessMode};
 use onboarding_modal::DebuggerOnboardingModal;
-use project::debugger::{self, breakpoint_store::SourceBreakpoint, session::ThreadStatus};
+use project::{
+    debugger::{self, breakpoint_store::SourceBreakpoint, session::ThreadStatus},
+    dotnet_startup_projects::dotnet_debug_adapter,
+};
 use schemars::JsonSchema;
 use serde::Deserialize;
 use session::DebugSession;
@@ -34,6 +38,8 @@ actions!(
     [
         /// Starts a new debugging session.
         Start,
+        /// Attaches the debugger to a running .NET process.
+        AttachToDotNetProcess,
         /// Continues execution until the next breakpoint.
         Continue,
         /// Detaches the debugger from the running process.
@@ -124,6 +130,28 @@ pub fn init(cx: &mut App) {
             .register_action(|workspace: &mut Workspace, _: &Start, window, cx| {
                 NewProcessModal::show(workspace, window, NewProcessMode::Debug, None, cx);
             })
+            .register_action(
+                |workspace: &mut Workspace, _: &AttachToDotNetProcess, window, cx| {
+                    let project = workspace.project().clone();
+                    let definition = task::ZedDebugConfig {
+                        label: "Attach to .NET process".into(),
+                        adapter: dotnet_debug_adapter(project.read(cx), None, cx),
+                        request: dap::DebugRequest::Attach(task::AttachRequest::default()),
+                        stop_on_entry: None,
+                    };
+                    let workspace_handle = cx.weak_entity();
+                    workspace.toggle_modal(window, cx, |window, cx| {
+                        AttachModal::new_dotnet(
+                            ModalIntent::AttachToProcess(definition),
+                            workspace_handle,
+                            project,
+                            true,
+                            window,
+                            cx,
+                        )
+                    });
+                },
+            )
             .register_action(|workspace: &mut Workspace, _: &Rerun, window, cx| {
                 let Some(debug_panel) = workspace.panel::<DebugPanel>(cx) else {
                     return;
@@ -65,6 +65,8 @@ use workspace::{
 
 static PROCESS_ID_PLACEHOLDER: LazyLock<String> =
     LazyLock::new(|| task::VariableName::PickProcessId.template_value());
+static DOTNET_PROCESS_ID_PLACEHOLDER: LazyLock<String> =
+    LazyLock::new(|| task::VariableName::PickDotNetProcessId.template_value());
 
 pub struct RunningState {
     session: Entity<Session>,
@@ -686,8 +688,10 @@ impl RunningState {
                 });
             }
             serde_json::Value::String(s) => {
-                if s.contains(PROCESS_ID_PLACEHOLDER.as_str()) {
-                    *s = s.replace(PROCESS_ID_PLACEHOLDER.as_str(), &process_id.to_string());
+                for placeholder in [&*PROCESS_ID_PLACEHOLDER, &*DOTNET_PROCESS_ID_PLACEHOLDER] {
+                    if s.contains(placeholder.as_str()) {
+                        *s = s.replace(placeholder.as_str(), &process_id.to_string());
+                    }
                 }
             }
             _ => {}
@@ -996,26 +1000,27 @@ impl RunningState {
             Self::relativize_paths(None, &mut config, &task_context);
             Self::substitute_variables_in_config(&mut config, &task_context);
 
-            if Self::contains_substring(&config, PROCESS_ID_PLACEHOLDER.as_str()) || label.as_ref().contains(PROCESS_ID_PLACEHOLDER.as_str()) {
+            let pick_process = Self::contains_substring(&config, PROCESS_ID_PLACEHOLDER.as_str()) || label.as_ref().contains(PROCESS_ID_PLACEHOLDER.as_str());
+            let pick_dotnet_process = Self::contains_substring(&config, DOTNET_PROCESS_ID_PLACEHOLDER.as_str()) || label.as_ref().contains(DOTNET_PROCESS_ID_PLACEHOLDER.as_str());
+            if pick_process || pick_dotnet_process {
                 let (tx, rx) = futures::channel::oneshot::channel::<Option<i32>>();
 
                 let weak_workspace_clone = weak_workspace.clone();
                 weak_workspace.update_in(cx, |workspace, window, cx| {
                     let project = workspace.project().clone();
                     workspace.toggle_modal(window, cx, |window, cx| {
-                        AttachModal::new(
-                            ModalIntent::ResolveProcessId(Some(tx)),
-                            weak_workspace_clone,
-                            project,
-                            true,
-                            window,
-                            cx,
-                        )
+                        let intent = ModalIntent::ResolveProcessId(Some(tx));
+                        if pick_process {
+                            AttachModal::new(intent, weak_workspace_clone, project, true, window, cx)
+                        } else {
+                            AttachModal::new_dotnet(intent, weak_workspace_clone, project, true, window, cx)
+                        }
                     });
                 }).ok();
 
                 let Some(process_id) = rx.await.ok().flatten() else {
-                    bail!("No process selected with config that contains {}", PROCESS_ID_PLACEHOLDER.as_str())
+                    let placeholder = if pick_process { &*PROCESS_ID_PLACEHOLDER } else { &*DOTNET_PROCESS_ID_PLACEHOLDER };
+                    bail!("No process selected with config that contains {}", placeholder.as_str())
                 };
 
                 Self::substitute_process_id_in_config(&mut config, process_id);
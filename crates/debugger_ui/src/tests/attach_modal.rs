@@ -107,16 +107,19 @@ async fn test_show_attach_modal_and_select_process(
                             pid: 0,
                             name: "fake-binary-1".into(),
                             command: vec![],
+                            assembly: None,
                         },
                         Candidate {
                             pid: 3,
                             name: "real-binary-1".into(),
                             command: vec![],
+                            assembly: None,
                         },
                         Candidate {
                             pid: 1,
                             name: "fake-binary-2".into(),
                             command: vec![],
+                            assembly: None,
                         },
                     ]
                     .into_iter()
@@ -254,16 +257,19 @@ async fn test_attach_with_pick_pid_variable(executor: BackgroundExecutor, cx: &m
                             pid: 10,
                             name: "process-1".into(),
                             command: vec![],
+                            assembly: None,
                         },
                         Candidate {
                             pid: 42,
                             name: "target-process".into(),
                             command: vec![],
+                            assembly: None,
                         },
                         Candidate {
                             pid: 99,
                             name: "process-3".into(),
                             command: vec![],
+                            assembly: None,
                         },
                     ]
                     .into_iter()
@@ -309,3 +315,130 @@ async fn test_attach_with_pick_pid_variable(executor: BackgroundExecutor, cx: &m
         })
         .unwrap();
 }
+
+#[gpui::test]
+async fn test_attach_with_pick_dotnet_pid_variable(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "Program.cs": "Console.WriteLine(\"Hello\");",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let _initialize_subscription =
+        project::debugger::test::intercept_debug_sessions(cx, |client| {
+            client.on_request::<dap::requests::Attach, _>(move |_, args| {
+                assert_eq!(
+                    args.raw["process_id"], "4242",
+                    "verify process id has been replaced"
+                );
+
+                Ok(())
+            });
+        });
+
+    let pick_pid_placeholder = task::VariableName::PickDotNetProcessId.template_value();
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.start_debug_session(
+                DebugTaskDefinition {
+                    adapter: FakeAdapter::ADAPTER_NAME.into(),
+                    label: "attach to .NET".into(),
+                    config: json!({
+                        "request": "attach",
+                        "process_id": pick_pid_placeholder,
+                    }),
+                    tcp_connection: None,
+                }
+                .to_scenario(),
+                task::TaskContext::default(),
+                None,
+                None,
+                window,
+                cx,
+            )
+        })
+        .unwrap();
+
+    cx.run_until_parked();
+
+    let attach_modal = workspace
+        .update(cx, |workspace, _window, cx| {
+            workspace.active_modal::<AttachModal>(cx)
+        })
+        .unwrap()
+        .expect("Attach modal should open when config contains ZED_PICK_DOTNET_PID");
+
+    workspace
+        .update(cx, |_, window, cx| {
+            attach_modal.update(cx, |modal, cx| {
+                attach_modal::set_candidates(
+                    modal,
+                    vec![
+                        Candidate {
+                            pid: 17,
+                            name: "dotnet".into(),
+                            command: vec![],
+                            assembly: Some("Worker".into()),
+                        },
+                        Candidate {
+                            pid: 4242,
+                            name: "dotnet".into(),
+                            command: vec![],
+                            assembly: Some("Api".into()),
+                        },
+                    ]
+                    .into_iter()
+                    .collect(),
+                    window,
+                    cx,
+                )
+            })
+        })
+        .unwrap();
+
+    cx.run_until_parked();
+
+    workspace
+        .update(cx, |_, window, cx| {
+            attach_modal.update(cx, |modal, cx| {
+                modal.picker.update(cx, |picker, cx| {
+                    picker.set_query("Api", window, cx);
+                })
+            })
+        })
+        .unwrap();
+
+    cx.run_until_parked();
+
+    workspace
+        .update(cx, |_, _, cx| {
+            let names = attach_modal.update(cx, |modal, cx| attach_modal::process_names(modal, cx));
+            assert_eq!(names, [" 4242 dotnet Api"]);
+        })
+        .unwrap();
+
+    cx.dispatch_action(Confirm);
+    cx.run_until_parked();
+
+    workspace
+        .update(cx, |workspace, _window, cx| {
+            assert!(
+                workspace.active_modal::<AttachModal>(cx).is_none(),
+                "Attach modal should be dismissed after selection"
+            );
+        })
+        .unwrap();
+}
@@ -0,0 +1,868 @@
+//! Dependencies of a project and the project-to-project reference graph.
+//!
+//! [`project_dependencies`] classifies the reference items of an evaluated
+//! project the way IDEs group them under a project's "Dependencies" node.
+//! [`ProjectGraph`] links the projects of a solution through their
+//! `<ProjectReference>` items to compute build orders and find cycles.
+
+use crate::{
+    msbuild::{ProjectItem, ProjectModel, relative_path, resolve_path},
+    xml_edit::{escape, insert_after, insert_child, line_ending, removal_range, remove_ranges},
+};
+use anyhow::{Context as _, Result, bail};
+use collections::{BTreeMap, HashSet};
+use std::{
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum DependencyKind {
+    /// A `<ProjectReference>`.
+    Project,
+    /// A `<PackageReference>`.
+    Package,
+    /// A `<FrameworkReference>`, e.g. `Microsoft.AspNetCore.App`.
+    Framework,
+    /// A plain `<Reference>` to an assembly.
+    Assembly,
+    /// An `<Analyzer>`, or a project referenced with `OutputItemType="Analyzer"`.
+    Analyzer,
+}
+
+impl DependencyKind {
+    pub const ALL: [Self; 5] = [
+        Self::Project,
+        Self::Package,
+        Self::Framework,
+        Self::Assembly,
+        Self::Analyzer,
+    ];
+
+    /// The name of the group listing dependencies of this kind.
+    pub fn group_label(&self) -> &'static str {
+        match self {
+            Self::Project => "Projects",
+            Self::Package => "Packages",
+            Self::Framework => "Frameworks",
+            Self::Assembly => "Assemblies",
+            Self::Analyzer => "Analyzers",
+        }
+    }
+}
+
+/// A single dependency declared by a project.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dependency {
+    pub kind: DependencyKind,
+    /// The evaluated `Include` of the item.
+    pub include: String,
+    /// The display name: a package id, project name or assembly name.
+    pub name: String,
+    pub version: Option<String>,
+    /// Absolute path of a referenced project, assembly or analyzer, if known.
+    pub path: Option<PathBuf>,
+    /// Whether the version comes from a `<PackageVersion>` in `Directory.Packages.props`.
+    pub is_central_version: bool,
+}
+
+/// Returns the dependencies of a project, in declaration order.
+pub fn project_dependencies(project: &ProjectModel) -> Vec<Dependency> {
+    dependencies_from_items(project.directory(), &project.items)
+}
+
+fn dependencies_from_items(directory: &Path, items: &[ProjectItem]) -> Vec<Dependency> {
+    let central_version = |package_id: &str| {
+        items
+            .iter()
+            .rfind(|item| {
+                item.item_type.eq_ignore_ascii_case("PackageVersion")
+                    && item.include.eq_ignore_ascii_case(package_id)
+            })
+            .and_then(|item| item.metadata("Version"))
+            .map(str::to_string)
+    };
+    items
+        .iter()
+        .filter_map(|item| {
+            let item_type = item.item_type.to_ascii_lowercase();
+            let dependency = match item_type.as_str() {
+                "projectreference" => {
+                    let path = resolve_path(directory, &item.include);
+                    let is_analyzer = item
+                        .metadata("OutputItemType")
+                        .is_some_and(|output| output.eq_ignore_ascii_case("Analyzer"));
+                    Dependency {
+                        kind: if is_analyzer {
+                            DependencyKind::Analyzer
+                        } else {
+                            DependencyKind::Project
+                        },
+                        include: item.include.clone(),
+                        name: file_stem(&path),
+                        version: None,
+                        path: Some(path),
+                        is_central_version: false,
+                    }
+                }
+                "packagereference" => {
+                    let (version, is_central_version) = match item
+                        .metadata("VersionOverride")
+                        .or_else(|| item.metadata("Version"))
+                    {
+                        Some(version) => (Some(version.to_string()), false),
+                        None => {
+                            let version = central_version(&item.include);
+                            let is_central_version = version.is_some();
+                            (version, is_central_version)
+                        }
+                    };
+                    Dependency {
+                        kind: DependencyKind::Package,
+                        include: item.include.clone(),
+                        name: item.include.clone(),
+                        version,
+                        path: None,
+                        is_central_version,
+                    }
+                }
+                "frameworkreference" => Dependency {
+                    kind: DependencyKind::Framework,
+                    include: item.include.clone(),
+                    name: item.include.clone(),
+                    version: None,
+                    path: None,
+                    is_central_version: false,
+                },
+                "reference" => {
+                    // `Include` may be a full assembly name: `Name, Version=1.0.0.0, ...`.
+                    let mut parts = item.include.split(',').map(str::trim);
+                    let name = parts.next().unwrap_or_default().to_string();
+                    let version = parts
+                        .find_map(|part| part.strip_prefix("Version="))
+                        .map(str::to_string);
+                    Dependency {
+                        kind: DependencyKind::Assembly,
+                        include: item.include.clone(),
+                        name,
+                        version,
+                        path: item
+                            .metadata("HintPath")
+                            .map(|hint_path| resolve_path(directory, hint_path)),
+                        is_central_version: false,
+                    }
+                }
+                "analyzer" => {
+                    let path = resolve_path(directory, &item.include);
+                    Dependency {
+                        kind: DependencyKind::Analyzer,
+                        include: item.include.clone(),
+                        name: file_stem(&path),
+                        version: None,
+                        path: Some(path),
+                        is_central_version: false,
+                    }
+                }
+                _ => return None,
+            };
+            Some(dependency)
+        })
+        .collect()
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// The graph of project-to-project references between the projects of a solution.
+///
+/// Projects are identified by their absolute, normalized paths.
+#[derive(Debug, Clone, Default)]
+pub struct ProjectGraph {
+    references: BTreeMap<PathBuf, Vec<PathBuf>>,
+}
+
+/// The projects to build, in an order where each project comes after the
+/// projects it references.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BuildOrder {
+    pub projects: Vec<PathBuf>,
+    /// Reference cycles found among the projects. When non-empty, `projects`
+    /// is only a best-effort order and MSBuild will refuse to build them.
+    pub cycles: Vec<Vec<PathBuf>>,
+}
+
+impl ProjectGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a project with the absolute paths of the projects it references.
+    pub fn add_project(&mut self, path: PathBuf, references: impl IntoIterator<Item = PathBuf>) {
+        let existing = self.references.entry(path).or_default();
+        for reference in references {
+            if !existing.contains(&reference) {
+                existing.push(reference);
+            }
+        }
+    }
+
+    pub fn projects(&self) -> impl Iterator<Item = &Path> {
+        self.references.keys().map(PathBuf::as_path)
+    }
+
+    /// The projects directly referenced by `project`.
+    pub fn references(&self, project: &Path) -> &[PathBuf] {
+        self.references
+            .get(project)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// The projects directly referencing `project`.
+    pub fn dependents(&self, project: &Path) -> Vec<&Path> {
+        self.references
+            .iter()
+            .filter(|(_, references)| references.iter().any(|reference| reference == project))
+            .map(|(path, _)| path.as_path())
+            .collect()
+    }
+
+    /// Whether `project` references `target`, directly or transitively.
+    pub fn depends_on(&self, project: &Path, target: &Path) -> bool {
+        let mut visited = HashSet::default();
+        let mut stack = vec![project];
+        while let Some(current) = stack.pop() {
+            for reference in self.references(current) {
+                if reference == target {
+                    return true;
+                }
+                if visited.insert(reference.as_path()) {
+                    stack.push(reference);
+                }
+            }
+        }
+        false
+    }
+
+    /// Whether adding a reference from `project` to `reference` would introduce a cycle.
+    pub fn would_create_cycle(&self, project: &Path, reference: &Path) -> bool {
+        project == reference || self.depends_on(reference, project)
+    }
+
+    /// The build order of `roots` and everything they reference.
+    pub fn build_order<'a>(&self, roots: impl IntoIterator<Item = &'a Path>) -> BuildOrder {
+        let mut order = BuildOrder::default();
+        let mut visited = HashSet::default();
+        let mut stack = Vec::new();
+        for root in roots {
+            self.visit(root, &mut visited, &mut stack, &mut order);
+        }
+        order
+    }
+
+    /// The build order of every project in the graph, e.g. for building a whole solution.
+    pub fn solution_build_order(&self) -> BuildOrder {
+        self.build_order(self.projects())
+    }
+
+    fn visit<'a>(
+        &'a self,
+        project: &'a Path,
+        visited: &mut HashSet<&'a Path>,
+        stack: &mut Vec<&'a Path>,
+        order: &mut BuildOrder,
+    ) {
+        if let Some(start) = stack.iter().position(|entry| *entry == project) {
+            let cycle = stack[start..]
+                .iter()
+                .map(|path| path.to_path_buf())
+                .collect::<Vec<_>>();
+            if !order
+                .cycles
+                .iter()
+                .any(|existing| is_same_cycle(existing, &cycle))
+            {
+                order.cycles.push(cycle);
+            }
+            return;
+        }
+        if !visited.insert(project) {
+            return;
+        }
+        stack.push(project);
+        for reference in self.references(project) {
+            self.visit(reference, visited, stack, order);
+        }
+        stack.pop();
+        order.projects.push(project.to_path_buf());
+    }
+}
+
+/// Whether two cycles contain the same projects in the same rotation-independent order.
+fn is_same_cycle(a: &[PathBuf], b: &[PathBuf]) -> bool {
+    a.len() == b.len()
+        && (0..a.len()).any(|offset| (0..a.len()).all(|ix| a[(ix + offset) % a.len()] == b[ix]))
+}
+
+/// Adds a `<ProjectReference>` to `reference_path` to the text of the project
+/// file at `project_path`.
+pub fn add_project_reference(
+    content: &str,
+    project_path: &Path,
+    reference_path: &Path,
+) -> Result<String> {
+    let document = parse_project(content, project_path)?;
+    let directory = project_path.parent().unwrap_or(Path::new(""));
+    if !find_project_references(&document, directory, reference_path).is_empty() {
+        bail!(
+            "{} already references {}",
+            file_stem(project_path),
+            file_stem(reference_path)
+        );
+    }
+
+    let include = relative_path(directory, reference_path)
+        .to_string_lossy()
+        .replace('/', "\\");
+    let element = format!("<ProjectReference Include=\"{}\" />", escape(&include));
+    Ok(insert_item(
+        content,
+        &document,
+        "ProjectReference",
+        &element,
+    ))
+}
+
+/// Removes every `<ProjectReference>` to `reference_path` from the text of the
+/// project file at `project_path`, along with item groups left empty.
+pub fn remove_project_reference(
+    content: &str,
+    project_path: &Path,
+    reference_path: &Path,
+) -> Result<String> {
+    let document = parse_project(content, project_path)?;
+    let directory = project_path.parent().unwrap_or(Path::new(""));
+    let references = find_project_references(&document, directory, reference_path);
+    if references.is_empty() {
+        bail!(
+            "{} does not reference {}",
+            file_stem(project_path),
+            file_stem(reference_path)
+        );
+    }
+    Ok(remove_items(content, &references))
+}
+
+/// Adds a `<PackageReference>` to the text of the project file at `project_path`.
+///
+/// `version` is `None` for projects using Central Package Management, whose
+/// versions live in `Directory.Packages.props`.
+pub fn add_package_reference(
+    content: &str,
+    project_path: &Path,
+    package_id: &str,
+    version: Option<&str>,
+) -> Result<String> {
+    let document = parse_project(content, project_path)?;
+    if !find_items(&document, "PackageReference", package_id).is_empty() {
+        bail!(
+            "{} already references package {package_id}",
+            file_stem(project_path)
+        );
+    }
+    let element = match version {
+        Some(version) => format!(
+            "<PackageReference Include=\"{}\" Version=\"{}\" />",
+            escape(package_id),
+            escape(version)
+        ),
+        None => format!("<PackageReference Include=\"{}\" />", escape(package_id)),
+    };
+    Ok(insert_item(
+        content,
+        &document,
+        "PackageReference",
+        &element,
+    ))
+}
+
+/// Removes the `<PackageReference>` to `package_id` from the text of the
+/// project file at `project_path`.
+pub fn remove_package_reference(
+    content: &str,
+    project_path: &Path,
+    package_id: &str,
+) -> Result<String> {
+    let document = parse_project(content, project_path)?;
+    let references = find_items(&document, "PackageReference", package_id);
+    if references.is_empty() {
+        bail!(
+            "{} does not reference package {package_id}",
+            file_stem(project_path)
+        );
+    }
+    Ok(remove_items(content, &references))
+}
+
+/// Changes the version of the `<PackageReference>` to `package_id` in the text
+/// of the project file at `project_path`.
+///
+/// A `VersionOverride` takes precedence over `Version`, since that is the
+/// version a centrally managed project actually uses.
+pub fn set_package_reference_version(
+    content: &str,
+    project_path: &Path,
+    package_id: &str,
+    version: &str,
+) -> Result<String> {
+    let document = parse_project(content, project_path)?;
+    let references = find_items(&document, "PackageReference", package_id);
+    if references.is_empty() {
+        bail!(
+            "{} does not reference package {package_id}",
+            file_stem(project_path)
+        );
+    }
+    let mut text = content.to_string();
+    for reference in references.iter().rev() {
+        let range = version_range(reference).with_context(|| {
+            format!(
+                "the version of {package_id} is not set in {}",
+                file_stem(project_path)
+            )
+        })?;
+        text.replace_range(range, &escape(version));
+    }
+    Ok(text)
+}
+
+/// The byte range of the version value of a `PackageReference` or `PackageVersion` item.
+pub(crate) fn version_range(item: &roxmltree::Node) -> Option<Range<usize>> {
+    for name in ["VersionOverride", "Version"] {
+        if let Some(attribute) = item
+            .attributes()
+            .find(|attribute| attribute.name().eq_ignore_ascii_case(name))
+        {
+            return Some(attribute.range_value());
+        }
+        if let Some(text) = item
+            .children()
+            .find(|child| child.tag_name().name().eq_ignore_ascii_case(name))
+            .and_then(|child| child.first_child())
+            .filter(|child| child.is_text())
+        {
+            return Some(text.range());
+        }
+    }
+    None
+}
+
+pub(crate) fn parse_project<'input>(
+    content: &'input str,
+    path: &Path,
+) -> Result<roxmltree::Document<'input>> {
+    roxmltree::Document::parse(content)
+        .with_context(|| format!("parsing project file {}", path.display()))
+}
+
+/// Items of the given type declared directly in the project file whose
+/// `Include` matches `include`, ignoring case.
+pub(crate) fn find_items<'a, 'input>(
+    document: &'a roxmltree::Document<'input>,
+    item_type: &str,
+    include: &str,
+) -> Vec<roxmltree::Node<'a, 'input>> {
+    item_elements(document, item_type)
+        .filter(|item| {
+            item.attribute("Include")
+                .is_some_and(|value| value.trim().eq_ignore_ascii_case(include))
+        })
+        .collect()
+}
+
+fn item_elements<'a, 'input>(
+    document: &'a roxmltree::Document<'input>,
+    item_type: &str,
+) -> impl Iterator<Item = roxmltree::Node<'a, 'input>> {
+    document
+        .root_element()
+        .children()
+        .filter(|child| child.has_tag_name("ItemGroup"))
+        .flat_map(|group| group.children())
+        .filter(move |child| child.tag_name().name() == item_type)
+}
+
+fn find_project_references<'a, 'input>(
+    document: &'a roxmltree::Document<'input>,
+    directory: &Path,
+    reference_path: &Path,
+) -> Vec<roxmltree::Node<'a, 'input>> {
+    let reference_path = resolve_path(Path::new(""), &reference_path.to_string_lossy());
+    item_elements(document, "ProjectReference")
+        .filter(|item| {
+            item.attribute("Include")
+                .is_some_and(|include| resolve_path(directory, include) == reference_path)
+        })
+        .collect()
+}
+
+/// Inserts an item element into the first unconditional item group that already
+/// contains items of the same type, or into a new item group.
+pub(crate) fn insert_item(
+    content: &str,
+    document: &roxmltree::Document,
+    item_type: &str,
+    element: &str,
+) -> String {
+    let root = document.root_element();
+    let existing_group = root
+        .children()
+        .filter(|child| child.has_tag_name("ItemGroup") && child.attribute("Condition").is_none())
+        .find(|group| group.children().any(|child| child.has_tag_name(item_type)));
+    let (range, replacement) = if let Some(item_group) = existing_group {
+        insert_child(content, item_group, element)
+    } else {
+        let newline = line_ending(content);
+        let item_group = format!("<ItemGroup>{newline}  {element}{newline}</ItemGroup>");
+        let last_group = root
+            .children()
+            .rfind(|child| child.has_tag_name("ItemGroup") || child.has_tag_name("PropertyGroup"));
+        match last_group {
+            Some(last_group) => {
+                let (range, replacement) = insert_after(content, last_group, &item_group);
+                // Keep a blank line between groups, as project templates do.
+                (range, format!("{newline}{replacement}"))
+            }
+            None => insert_child(content, root, &item_group),
+        }
+    };
+
+    let mut text = content.to_string();
+    text.replace_range(range, &replacement);
+    text
+}
+
+/// Removes item elements, along with the item groups they leave empty.
+pub(crate) fn remove_items(content: &str, items: &[roxmltree::Node]) -> String {
+    let ranges = items
+        .iter()
+        .map(|item| {
+            let empty_item_group = item.parent_element().filter(|group| {
+                group
+                    .children()
+                    .filter(|child| child.is_element())
+                    .all(|child| items.contains(&child))
+            });
+            match empty_item_group {
+                Some(item_group) => {
+                    // Also drop the blank line separating the group from the previous one.
+                    let range = removal_range(content, item_group.range());
+                    let before = &content[..range.start];
+                    let blank_line = before
+                        .strip_suffix('\n')
+                        .map(|before| before.strip_suffix('\r').unwrap_or(before))
+                        .filter(|before| before.ends_with('\n'));
+                    match blank_line {
+                        Some(before) => before.len()..range.end,
+                        None => range,
+                    }
+                }
+                None => removal_range(content, item.range()),
+            }
+        })
+        .collect();
+    let mut text = content.to_string();
+    remove_ranges(&mut text, ranges);
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn item(item_type: &str, include: &str, metadata: &[(&str, &str)]) -> ProjectItem {
+        ProjectItem {
+            item_type: item_type.to_string(),
+            include: include.to_string(),
+            exclude: Vec::new(),
+            metadata: metadata
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        }
+    }
+
+    fn path(path: &str) -> PathBuf {
+        PathBuf::from(path)
+    }
+
+    #[test]
+    fn test_project_dependencies() {
+        let items = [
+            item("Compile", "Program.cs", &[]),
+            item("ProjectReference", "..\\Lib\\Lib.csproj", &[]),
+            item(
+                "ProjectReference",
+                "..\\Generators\\Generators.csproj",
+                &[
+                    ("OutputItemType", "Analyzer"),
+                    ("ReferenceOutputAssembly", "false"),
+                ],
+            ),
+            item("PackageReference", "Serilog", &[("Version", "3.1.1")]),
+            item("FrameworkReference", "Microsoft.AspNetCore.App", &[]),
+            item(
+                "Reference",
+                "Legacy, Version=1.2.0.0, Culture=neutral",
+                &[("HintPath", "..\\libs\\Legacy.dll")],
+            ),
+            item("Analyzer", "..\\tools\\Rules.dll", &[]),
+        ];
+        let dependencies = dependencies_from_items(Path::new("/repo/App"), &items);
+        let summary = dependencies
+            .iter()
+            .map(|dependency| {
+                (
+                    dependency.kind,
+                    dependency.name.as_str(),
+                    dependency.version.as_deref(),
+                    dependency.path.clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    DependencyKind::Project,
+                    "Lib",
+                    None,
+                    Some(path("/repo/Lib/Lib.csproj"))
+                ),
+                (
+                    DependencyKind::Analyzer,
+                    "Generators",
+                    None,
+                    Some(path("/repo/Generators/Generators.csproj"))
+                ),
+                (DependencyKind::Package, "Serilog", Some("3.1.1"), None),
+                (
+                    DependencyKind::Framework,
+                    "Microsoft.AspNetCore.App",
+                    None,
+                    None
+                ),
+                (
+                    DependencyKind::Assembly,
+                    "Legacy",
+                    Some("1.2.0.0"),
+                    Some(path("/repo/libs/Legacy.dll"))
+                ),
+                (
+                    DependencyKind::Analyzer,
+                    "Rules",
+                    None,
+                    Some(path("/repo/tools/Rules.dll"))
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_order() {
+        let mut graph = ProjectGraph::new();
+        graph.add_project(path("/app"), [path("/lib"), path("/core")]);
+        graph.add_project(path("/lib"), [path("/core")]);
+        graph.add_project(path("/core"), []);
+        graph.add_project(path("/tests"), [path("/app")]);
+
+        assert_eq!(
+            graph.build_order([Path::new("/app")]),
+            BuildOrder {
+                projects: vec![path("/core"), path("/lib"), path("/app")],
+                cycles: Vec::new(),
+            }
+        );
+        assert_eq!(
+            graph.solution_build_order().projects,
+            vec![path("/core"), path("/lib"), path("/app"), path("/tests")]
+        );
+        assert_eq!(
+            graph.dependents(Path::new("/core")),
+            vec![Path::new("/app"), Path::new("/lib")]
+        );
+        assert!(graph.depends_on(Path::new("/tests"), Path::new("/core")));
+        assert!(!graph.depends_on(Path::new("/core"), Path::new("/tests")));
+        assert!(graph.would_create_cycle(Path::new("/core"), Path::new("/app")));
+        assert!(graph.would_create_cycle(Path::new("/app"), Path::new("/app")));
+        assert!(!graph.would_create_cycle(Path::new("/tests"), Path::new("/lib")));
+    }
+
+    #[test]
+    fn test_build_order_cycles() {
+        let mut graph = ProjectGraph::new();
+        graph.add_project(path("/a"), [path("/b")]);
+        graph.add_project(path("/b"), [path("/c")]);
+        graph.add_project(path("/c"), [path("/a")]);
+        graph.add_project(path("/d"), [path("/d")]);
+
+        let order = graph.solution_build_order();
+        assert_eq!(
+            order.cycles,
+            vec![vec![path("/a"), path("/b"), path("/c")], vec![path("/d")]]
+        );
+        assert_eq!(order.projects.len(), 4);
+        assert!(
+            graph
+                .build_order([Path::new("/b")])
+                .cycles
+                .iter()
+                .all(|cycle| is_same_cycle(cycle, &[path("/a"), path("/b"), path("/c")]))
+        );
+    }
+
+    #[test]
+    fn test_edit_project_references() {
+        let project_path = Path::new("/repo/src/App/App.csproj");
+        let lib = Path::new("/repo/src/Lib/Lib.csproj");
+        let shared = Path::new("/repo/shared/Shared.csproj");
+        let original = r#"<Project Sdk="Microsoft.NET.Sdk">
+
+  <PropertyGroup>
+    <TargetFramework>net8.0</TargetFramework>
+  </PropertyGroup>
+
+</Project>
+"#;
+
+        let with_lib = add_project_reference(original, project_path, lib).unwrap();
+        assert_eq!(
+            with_lib,
+            r#"<Project Sdk="Microsoft.NET.Sdk">
+
+  <PropertyGroup>
+    <TargetFramework>net8.0</TargetFramework>
+  </PropertyGroup>
+
+  <ItemGroup>
+    <ProjectReference Include="..\Lib\Lib.csproj" />
+  </ItemGroup>
+
+</Project>
+"#
+        );
+        assert!(add_project_reference(&with_lib, project_path, lib).is_err());
+
+        let with_both = add_project_reference(&with_lib, project_path, shared).unwrap();
+        assert_eq!(
+            with_both,
+            r#"<Project Sdk="Microsoft.NET.Sdk">
+
+  <PropertyGroup>
+    <TargetFramework>net8.0</TargetFramework>
+  </PropertyGroup>
+
+  <ItemGroup>
+    <ProjectReference Include="..\Lib\Lib.csproj" />
+    <ProjectReference Include="..\..\shared\Shared.csproj" />
+  </ItemGroup>
+
+</Project>
+"#
+        );
+
+        let without_lib = remove_project_reference(&with_both, project_path, lib).unwrap();
+        assert_eq!(
+            without_lib,
+            r#"<Project Sdk="Microsoft.NET.Sdk">
+
+  <PropertyGroup>
+    <TargetFramework>net8.0</TargetFramework>
+  </PropertyGroup>
+
+  <ItemGroup>
+    <ProjectReference Include="..\..\shared\Shared.csproj" />
+  </ItemGroup>
+
+</Project>
+"#
+        );
+        assert_eq!(
+            remove_project_reference(&without_lib, project_path, shared).unwrap(),
+            r#"<Project Sdk="Microsoft.NET.Sdk">
+
+  <PropertyGroup>
+    <TargetFramework>net8.0</TargetFramework>
+  </PropertyGroup>
+
+</Project>
+"#
+        );
+        assert!(remove_project_reference(original, project_path, lib).is_err());
+    }
+
+    #[test]
+    fn test_edit_package_references() {
+        let project_path = Path::new("/repo/App/App.csproj");
+        let original = r#"<Project Sdk="Microsoft.NET.Sdk">
+
+  <ItemGroup>
+    <PackageReference Include="Serilog" Version="3.0.0" />
+    <PackageReference Include="Polly">
+      <Version>7.2.4</Version>
+    </PackageReference>
+    <PackageReference Include="xunit" VersionOverride="2.9.0" />
+  </ItemGroup>
+
+</Project>
+"#;
+
+        let updated = ["serilog", "Polly", "xunit"]
+            .into_iter()
+            .zip(["3.1.1", "8.4.1", "2.9.2"])
+            .try_fold(original.to_string(), |content, (package_id, version)| {
+                set_package_reference_version(&content, project_path, package_id, version)
+            })
+            .unwrap();
+        let with_central =
+            add_package_reference(&updated, project_path, "Humanizer", None).unwrap();
+        assert_eq!(
+            with_central,
+            r#"<Project Sdk="Microsoft.NET.Sdk">
+
+  <ItemGroup>
+    <PackageReference Include="Serilog" Version="3.1.1" />
+    <PackageReference Include="Polly">
+      <Version>8.4.1</Version>
+    </PackageReference>
+    <PackageReference Include="xunit" VersionOverride="2.9.2" />
+    <PackageReference Include="Humanizer" />
+  </ItemGroup>
+
+</Project>
+"#
+        );
+        assert!(
+            add_package_reference(&with_central, project_path, "SERILOG", Some("1.0")).is_err()
+        );
+
+        let removed = ["Serilog", "Polly", "xunit", "Humanizer"]
+            .into_iter()
+            .try_fold(with_central, |content, package_id| {
+                remove_package_reference(&content, project_path, package_id)
+            })
+            .unwrap();
+        assert_eq!(
+            removed,
+            r#"<Project Sdk="Microsoft.NET.Sdk">
+
+</Project>
+"#
+        );
+        assert!(set_package_reference_version(&removed, project_path, "Serilog", "1.0").is_err());
+    }
+}
@@ -0,0 +1,313 @@
+//! Diagnostics reported by MSBuild.
+//!
+//! `dotnet build` reports compiler errors and warnings in MSBuild's canonical
+//! message format, one per line:
+//!
+//! ```text
+//! /src/App/Program.cs(12,17): error CS1002: ; expected [/src/App/App.csproj]
+//! /src/App/App.csproj : warning NU1603: Newtonsoft.Json 13.0.1 was not found. [/src/App/App.csproj]
+//! CSC : error CS5001: Program does not contain a static 'Main' method [/src/App/App.csproj]
+//! ```
+//!
+//! This module turns that output into diagnostics, so that a build can report
+//! its errors without a language server.
+
+use std::path::{Path, PathBuf};
+
+/// MSBuild arguments that make the console output parseable by
+/// [`parse_build_output`]: absolute paths, one line per diagnostic and no
+/// summary repeating them.
+pub const CONSOLE_LOGGER_ARGS: &[&str] =
+    &["-p:GenerateFullPaths=true", "-clp:NoSummary;ForceNoAlign"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BuildDiagnosticSeverity {
+    Error,
+    Warning,
+    Info,
+}
+
+/// A zero-based position. Columns count UTF-16 code units, as the C# compiler
+/// reports them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BuildPosition {
+    pub line: u32,
+    pub column: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BuildDiagnostic {
+    /// The file the diagnostic is about. Diagnostics of tools such as `CSC`
+    /// that name no file are reported on the project file.
+    pub path: PathBuf,
+    pub start: BuildPosition,
+    /// Equal to `start` when MSBuild reported no end position.
+    pub end: BuildPosition,
+    pub severity: BuildDiagnosticSeverity,
+    /// The diagnostic code, such as `CS1002` or `NU1603`.
+    pub code: Option<String>,
+    pub message: String,
+    /// The project that was being built, when MSBuild named it.
+    pub project: Option<PathBuf>,
+}
+
+/// MSBuild arguments that write the errors and warnings of a build to
+/// `log_path` in the format read by [`parse_build_output`], leaving the
+/// console output as it is.
+pub fn file_logger_args(log_path: &Path) -> Vec<String> {
+    vec![
+        "-p:GenerateFullPaths=true".to_string(),
+        format!(
+            "-flp:LogFile={};Verbosity=quiet;NoSummary;ForceNoAlign;Encoding=UTF-8",
+            log_path.display()
+        ),
+    ]
+}
+
+/// Parses the diagnostics from MSBuild's output, skipping any other lines.
+///
+/// Relative paths are resolved against the project directory or, if MSBuild
+/// named no project, against `working_dir`. Diagnostics reported more than
+/// once, such as by every target framework of a project, are kept once.
+pub fn parse_build_output(output: &str, working_dir: &Path) -> Vec<BuildDiagnostic> {
+    let mut diagnostics = Vec::<BuildDiagnostic>::new();
+    for line in output.lines() {
+        if let Some(diagnostic) = parse_diagnostic_line(line, working_dir)
+            && !diagnostics.iter().any(|existing| {
+                existing.path == diagnostic.path
+                    && existing.start == diagnostic.start
+                    && existing.code == diagnostic.code
+                    && existing.message == diagnostic.message
+            })
+        {
+            diagnostics.push(diagnostic);
+        }
+    }
+    diagnostics
+}
+
+fn parse_diagnostic_line(line: &str, working_dir: &Path) -> Option<BuildDiagnostic> {
+    let line = line.trim();
+    let (line, project) = split_project_suffix(line);
+    let project = project.map(|project| working_dir.join(project));
+
+    // `origin : [subcategory] category code : text`, where the origin may
+    // contain colons itself, as in `C:\src\Program.cs(1,1)`
+    let (origin, severity, code, message) = line.match_indices(':').find_map(|(colon, _)| {
+        let (severity, code, message) = parse_category(&line[colon + 1..])?;
+        Some((line[..colon].trim(), severity, code, message))
+    })?;
+
+    let (origin, start, end) = split_position(origin);
+    let path = if is_file_origin(origin) {
+        let base_dir = project
+            .as_deref()
+            .and_then(Path::parent)
+            .unwrap_or(working_dir);
+        base_dir.join(origin)
+    } else {
+        project.clone()?
+    };
+
+    Some(BuildDiagnostic {
+        path,
+        start,
+        end,
+        severity,
+        code,
+        message,
+        project,
+    })
+}
+
+/// Splits the ` [/src/App/App.csproj::TargetFramework=net8.0]` suffix naming
+/// the project being built off a line.
+fn split_project_suffix(line: &str) -> (&str, Option<&str>) {
+    if let Some(rest) = line.strip_suffix(']')
+        && let Some(open) = rest.rfind(" [")
+    {
+        let project = &rest[open + 2..];
+        let project = project.split("::").next().unwrap_or(project);
+        if !project.is_empty() {
+            return (rest[..open].trim_end(), Some(project));
+        }
+    }
+    (line, None)
+}
+
+/// Parses `[subcategory] category code : text`.
+fn parse_category(rest: &str) -> Option<(BuildDiagnosticSeverity, Option<String>, String)> {
+    let (head, message) = rest.split_once(':')?;
+    let words = head.split_whitespace().collect::<Vec<_>>();
+    let category_ix = words.iter().rposition(|word| severity(word).is_some())?;
+    // Only the code may follow the category
+    if words.len() > category_ix + 2 {
+        return None;
+    }
+    let severity = severity(words[category_ix])?;
+    let code = words.get(category_ix + 1).map(|code| code.to_string());
+    Some((severity, code, message.trim().to_string()))
+}
+
+fn severity(category: &str) -> Option<BuildDiagnosticSeverity> {
+    if category.eq_ignore_ascii_case("error") {
+        Some(BuildDiagnosticSeverity::Error)
+    } else if category.eq_ignore_ascii_case("warning") {
+        Some(BuildDiagnosticSeverity::Warning)
+    } else if category.eq_ignore_ascii_case("info") || category.eq_ignore_ascii_case("message") {
+        Some(BuildDiagnosticSeverity::Info)
+    } else {
+        None
+    }
+}
+
+/// Splits a trailing `(line)`, `(line-line)`, `(line,col)`, `(line,col-col)`
+/// or `(line,col,line,col)` off an origin. MSBuild positions are one-based.
+fn split_position(origin: &str) -> (&str, BuildPosition, BuildPosition) {
+    let parsed = origin.strip_suffix(')').and_then(|rest| {
+        let open = rest.rfind('(')?;
+        let numbers = rest[open + 1..]
+            .split([',', '-'])
+            .map(|number| number.trim().parse::<u32>().ok())
+            .collect::<Option<Vec<_>>>()?;
+        let position = |line: u32, column: u32| BuildPosition {
+            line: line.saturating_sub(1),
+            column: column.saturating_sub(1),
+        };
+        let is_range = rest[open + 1..].contains('-');
+        let (start, end) = match (numbers.as_slice(), is_range) {
+            ([line], _) => (position(*line, 1), position(*line, 1)),
+            ([line, end_line], true) => (position(*line, 1), position(*end_line, 1)),
+            ([line, column], false) => (position(*line, *column), position(*line, *column)),
+            ([line, column, end_column], true) => {
+                (position(*line, *column), position(*line, *end_column))
+            }
+            ([line, column, end_line, end_column], false) => {
+                (position(*line, *column), position(*end_line, *end_column))
+            }
+            _ => return None,
+        };
+        Some((rest[..open].trim_end(), start, end))
+    });
+    parsed.unwrap_or((origin, BuildPosition::default(), BuildPosition::default()))
+}
+
+/// Tools report diagnostics under their own name, such as `CSC` or `MSBUILD`,
+/// rather than a file.
+fn is_file_origin(origin: &str) -> bool {
+    origin.contains(['/', '\\', '.'])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_build_output() {
+        let output = "\
+  Determining projects to restore...
+/src/App/App.csproj : warning NU1603: App depends on Newtonsoft.Json (>= 13.0.0) but Newtonsoft.Json 13.0.0 was not found. [/src/App/App.csproj]
+/src/Lib/Util.cs(3,5,3,12): warning CS0168: The variable 'unused' is declared but never used [/src/Lib/Lib.csproj::TargetFramework=net8.0]
+/src/Lib/Util.cs(3,5,3,12): warning CS0168: The variable 'unused' is declared but never used [/src/Lib/Lib.csproj::TargetFramework=net9.0]
+Models/Order.cs(12,17): error CS1002: ; expected [/src/App/App.csproj]
+C:\\src\\App\\Views.cs(4): error CS0246: The type or namespace name 'Foo' could not be found [C:\\src\\App\\App.csproj]
+CSC : error CS5001: Program does not contain a static 'Main' method suitable for an entry point [/src/App/App.csproj]
+MSBUILD : error MSB1009: Project file does not exist.
+  App -> /src/App/bin/Debug/net8.0/App.dll
+Build FAILED.
+";
+        let diagnostics = parse_build_output(output, Path::new("/src"));
+        let summary = diagnostics
+            .iter()
+            .map(|diagnostic| {
+                (
+                    diagnostic.path.to_string_lossy().into_owned(),
+                    (diagnostic.start.line, diagnostic.start.column),
+                    (diagnostic.end.line, diagnostic.end.column),
+                    diagnostic.severity,
+                    diagnostic.code.as_deref(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            [
+                (
+                    "/src/App/App.csproj".to_string(),
+                    (0, 0),
+                    (0, 0),
+                    BuildDiagnosticSeverity::Warning,
+                    Some("NU1603")
+                ),
+                (
+                    "/src/Lib/Util.cs".to_string(),
+                    (2, 4),
+                    (2, 11),
+                    BuildDiagnosticSeverity::Warning,
+                    Some("CS0168")
+                ),
+                (
+                    "/src/App/Models/Order.cs".to_string(),
+                    (11, 16),
+                    (11, 16),
+                    BuildDiagnosticSeverity::Error,
+                    Some("CS1002")
+                ),
+                (
+                    Path::new("/src")
+                        .join("C:\\src\\App\\Views.cs")
+                        .to_string_lossy()
+                        .into_owned(),
+                    (3, 0),
+                    (3, 0),
+                    BuildDiagnosticSeverity::Error,
+                    Some("CS0246")
+                ),
+                (
+                    "/src/App/App.csproj".to_string(),
+                    (0, 0),
+                    (0, 0),
+                    BuildDiagnosticSeverity::Error,
+                    Some("CS5001")
+                ),
+            ]
+        );
+        assert_eq!(diagnostics[2].message, "; expected");
+        assert_eq!(
+            diagnostics[1].project.as_deref(),
+            Some(Path::new("/src/Lib/Lib.csproj"))
+        );
+    }
+
+    #[test]
+    fn test_split_position() {
+        assert_eq!(
+            split_position("a.cs(10,2-8)"),
+            (
+                "a.cs",
+                BuildPosition { line: 9, column: 1 },
+                BuildPosition { line: 9, column: 7 }
+            )
+        );
+        assert_eq!(
+            split_position("a.cs(10-12)"),
+            (
+                "a.cs",
+                BuildPosition { line: 9, column: 0 },
+                BuildPosition {
+                    line: 11,
+                    column: 0
+                }
+            )
+        );
+        assert_eq!(
+            split_position("a (copy).cs"),
+            (
+                "a (copy).cs",
+                BuildPosition::default(),
+                BuildPosition::default()
+            )
+        );
+    }
+}
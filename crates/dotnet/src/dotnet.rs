@@ -0,0 +1,43 @@
+//! Shared models for .NET projects and solutions.
+//!
+//! These types are consumed by the C# context provider, the solution explorer
+//! and the .NET debug locator, so that all of them agree on where a project
+//! lives, what it targets and where its build output ends up.
+
+pub mod dependencies;
+pub mod diagnostics;
+pub mod launch_settings;
+pub mod msbuild;
+pub mod nuget;
+pub mod processes;
+pub mod project_items;
+pub mod solution;
+pub mod startup;
+pub mod templates;
+pub mod testing;
+pub mod watch;
+mod xml_edit;
+
+pub use dependencies::{
+    BuildOrder, Dependency, DependencyKind, ProjectGraph, add_package_reference,
+    add_project_reference, project_dependencies, remove_package_reference,
+    remove_project_reference, set_package_reference_version,
+};
+pub use diagnostics::{BuildDiagnostic, BuildDiagnosticSeverity, BuildPosition};
+pub use launch_settings::{LaunchProfile, LaunchSettings};
+pub use msbuild::{
+    EvaluationOptions, MsBuildProject, OutputType, ProjectFiles, ProjectItem, ProjectModel,
+    ProjectTarget, RealProjectFiles, find_project_file,
+};
+pub use nuget::{
+    NuGetVersion, OutdatedPackage, PackageSearchResult, PackageSource, PackageSourceKind,
+    VersionConflict,
+};
+pub use project_items::{ProjectFile, ProjectItemNode, project_item_tree};
+pub use solution::{
+    ProjectConfigurationMapping, SolutionConfiguration, SolutionFile, SolutionFolder,
+    SolutionFormat, SolutionProject,
+};
+pub use startup::{StartupAction, StartupProject, StartupProjects};
+pub use templates::{ProjectTemplate, parse_template_list};
+pub use testing::{TestMethod, TestOutcome, TestResult};
@@ -0,0 +1,391 @@
+//! Launch profiles from `Properties/launchSettings.json`.
+//!
+//! `dotnet run` applies a launch profile to the process it starts: its command
+//! line arguments, environment variables and application URL. This module reads
+//! the profiles and resolves them the same way, so that a debugger can launch a
+//! project exactly as `dotnet run --launch-profile <name>` would.
+
+use anyhow::{Context as _, Result};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Where launch settings live, relative to the project directory. Visual Basic
+/// projects keep them in `My Project`.
+const LAUNCH_SETTINGS_PATHS: &[&str] = &[
+    "Properties/launchSettings.json",
+    "My Project/launchSettings.json",
+];
+
+/// The profile kind that `dotnet run` can launch. Other kinds, such as
+/// `IISExpress` or `Docker`, need Visual Studio tooling.
+const PROJECT_COMMAND: &str = "Project";
+
+/// The launch settings of a project.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LaunchSettings {
+    /// Absolute path to the `launchSettings.json` file.
+    pub path: PathBuf,
+    /// Profiles in the order of the file.
+    pub profiles: Vec<LaunchProfile>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LaunchProfile {
+    pub name: String,
+    /// `Project`, `Executable`, `IISExpress`, `Docker`, …
+    pub command_name: String,
+    pub command_line_args: Option<String>,
+    pub environment_variables: Vec<(String, String)>,
+    /// The URLs an ASP.NET Core app listens on, separated by semicolons.
+    pub application_url: Option<String>,
+    pub working_directory: Option<String>,
+    pub launch_browser: bool,
+    pub launch_url: Option<String>,
+}
+
+impl LaunchSettings {
+    /// Loads the launch settings of the project in `project_dir`, if it has any.
+    pub fn load(project_dir: &Path) -> Result<Option<Self>> {
+        let Some(path) = LAUNCH_SETTINGS_PATHS
+            .iter()
+            .map(|path| project_dir.join(path))
+            .find(|path| path.is_file())
+        else {
+            return Ok(None);
+        };
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("reading {}", path.display()))?;
+        Self::parse(&path, &content).map(Some)
+    }
+
+    pub fn parse(path: &Path, content: &str) -> Result<Self> {
+        #[derive(Deserialize)]
+        struct File {
+            #[serde(default)]
+            profiles: serde_json::Map<String, serde_json::Value>,
+        }
+
+        #[derive(Deserialize, Default)]
+        #[serde(default, rename_all = "camelCase")]
+        struct Profile {
+            command_name: Option<String>,
+            command_line_args: Option<String>,
+            environment_variables: serde_json::Map<String, serde_json::Value>,
+            application_url: Option<String>,
+            working_directory: Option<String>,
+            launch_browser: bool,
+            launch_url: Option<String>,
+        }
+
+        let file: File = serde_json::from_str(content.trim_start_matches('\u{feff}'))
+            .with_context(|| format!("parsing {}", path.display()))?;
+        let profiles = file
+            .profiles
+            .into_iter()
+            .filter_map(|(name, profile)| {
+                let profile = serde_json::from_value::<Profile>(profile)
+                    .inspect_err(|error| {
+                        log::warn!(
+                            "Invalid launch profile {name} in {}: {error}",
+                            path.display()
+                        )
+                    })
+                    .ok()?;
+                Some(LaunchProfile {
+                    name,
+                    command_name: profile
+                        .command_name
+                        .unwrap_or_else(|| PROJECT_COMMAND.to_string()),
+                    command_line_args: profile.command_line_args,
+                    environment_variables: profile
+                        .environment_variables
+                        .into_iter()
+                        .map(|(name, value)| match value {
+                            serde_json::Value::String(value) => (name, value),
+                            value => (name, value.to_string()),
+                        })
+                        .collect(),
+                    application_url: profile.application_url,
+                    working_directory: profile.working_directory,
+                    launch_browser: profile.launch_browser,
+                    launch_url: profile.launch_url,
+                })
+            })
+            .collect();
+        Ok(Self {
+            path: path.to_path_buf(),
+            profiles,
+        })
+    }
+
+    pub fn profile(&self, name: &str) -> Option<&LaunchProfile> {
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+
+    /// Profiles that `dotnet run` can launch.
+    pub fn project_profiles(&self) -> impl Iterator<Item = &LaunchProfile> {
+        self.profiles.iter().filter(|profile| profile.is_project())
+    }
+
+    /// The profile `dotnet run` uses without `--launch-profile`: the first one
+    /// it can launch.
+    pub fn default_profile(&self) -> Option<&LaunchProfile> {
+        self.project_profiles().next()
+    }
+}
+
+impl LaunchProfile {
+    pub fn is_project(&self) -> bool {
+        self.command_name == PROJECT_COMMAND
+    }
+
+    /// The program arguments of the profile. Like `dotnet run`, callers should
+    /// only use them when no arguments were given on the command line.
+    pub fn arguments(&self) -> Vec<String> {
+        self.command_line_args
+            .as_deref()
+            .map(split_command_line)
+            .unwrap_or_default()
+    }
+
+    /// The environment of the launched process, with `%NAME%` references to
+    /// variables of `environment` expanded.
+    ///
+    /// As with `dotnet run`, the application URL is passed as `ASPNETCORE_URLS`
+    /// unless the profile sets that variable itself.
+    pub fn environment(
+        &self,
+        environment: impl Fn(&str) -> Option<String>,
+    ) -> Vec<(String, String)> {
+        let mut variables = self
+            .environment_variables
+            .iter()
+            .map(|(name, value)| {
+                (
+                    name.clone(),
+                    expand_environment_variables(value, &environment),
+                )
+            })
+            .collect::<Vec<_>>();
+        if let Some(url) = &self.application_url
+            && !variables
+                .iter()
+                .any(|(name, _)| name.eq_ignore_ascii_case("ASPNETCORE_URLS"))
+        {
+            variables.push(("ASPNETCORE_URLS".to_string(), url.clone()));
+        }
+        variables
+    }
+
+    /// The directory to launch in: the profile's working directory, resolved
+    /// against the project directory, or the project directory itself.
+    pub fn working_directory(&self, project_dir: &Path) -> PathBuf {
+        match self.working_directory.as_deref() {
+            Some(directory) if !directory.trim().is_empty() => {
+                let directory = directory
+                    .replace('\\', "/")
+                    .replace("$(ProjectDir)", &format!("{}/", project_dir.display()));
+                project_dir.join(directory)
+            }
+            _ => project_dir.to_path_buf(),
+        }
+    }
+
+    /// A short description for pickers: the application URL or the arguments.
+    pub fn summary(&self) -> Option<&str> {
+        self.application_url
+            .as_deref()
+            .or(self.command_line_args.as_deref())
+            .filter(|summary| !summary.is_empty())
+    }
+}
+
+/// Expands `%NAME%` references the way .NET's `Environment.ExpandEnvironmentVariables`
+/// does, leaving unknown variables as they are.
+fn expand_environment_variables(
+    value: &str,
+    environment: impl Fn(&str) -> Option<String>,
+) -> String {
+    let mut result = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find('%') {
+        let Some(length) = rest[start + 1..].find('%') else {
+            break;
+        };
+        let name = &rest[start + 1..start + 1 + length];
+        result.push_str(&rest[..start]);
+        match environment(name).filter(|_| !name.is_empty()) {
+            Some(expanded) => {
+                result.push_str(&expanded);
+                rest = &rest[start + length + 2..];
+            }
+            None => {
+                // The closing `%` may open the next reference
+                result.push('%');
+                result.push_str(name);
+                rest = &rest[start + 1 + length..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Splits a command line into arguments the way the .NET runtime does on
+/// Windows: whitespace separates arguments, double quotes group them and a
+/// backslash escapes a double quote.
+pub fn split_command_line(command_line: &str) -> Vec<String> {
+    let mut arguments = Vec::new();
+    let mut current = String::new();
+    let mut in_argument = false;
+    let mut in_quotes = false;
+    let mut chars = command_line.chars().peekable();
+    while let Some(char) = chars.next() {
+        match char {
+            '\\' if chars.peek() == Some(&'"') => {
+                current.push('"');
+                chars.next();
+                in_argument = true;
+            }
+            '"' => {
+                in_quotes = !in_quotes;
+                in_argument = true;
+            }
+            char if char.is_whitespace() && !in_quotes => {
+                if in_argument {
+                    arguments.push(std::mem::take(&mut current));
+                    in_argument = false;
+                }
+            }
+            char => {
+                current.push(char);
+                in_argument = true;
+            }
+        }
+    }
+    if in_argument {
+        arguments.push(current);
+    }
+    arguments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const LAUNCH_SETTINGS: &str = r#"{
+  "$schema": "https://json.schemastore.org/launchsettings.json",
+  "profiles": {
+    "https": {
+      "commandName": "Project",
+      "dotnetRunMessages": true,
+      "launchBrowser": true,
+      "launchUrl": "swagger",
+      "applicationUrl": "https://localhost:7001;http://localhost:5001",
+      "environmentVariables": {
+        "ASPNETCORE_ENVIRONMENT": "Development",
+        "DATA_DIR": "%HOME%/data"
+      }
+    },
+    "IIS Express": {
+      "commandName": "IISExpress",
+      "launchBrowser": true
+    },
+    "worker": {
+      "commandName": "Project",
+      "commandLineArgs": "--queue \"orders high\" --verbose",
+      "workingDirectory": "$(ProjectDir)..\\data",
+      "environmentVariables": {
+        "ASPNETCORE_URLS": "http://*:8080"
+      }
+    }
+  }
+}"#;
+
+    #[test]
+    fn test_parse_launch_settings() {
+        let settings = LaunchSettings::parse(
+            Path::new("/src/Api/Properties/launchSettings.json"),
+            LAUNCH_SETTINGS,
+        )
+        .unwrap();
+        assert_eq!(
+            settings
+                .profiles
+                .iter()
+                .map(|profile| profile.name.as_str())
+                .collect::<Vec<_>>(),
+            ["https", "IIS Express", "worker"]
+        );
+        assert_eq!(
+            settings
+                .project_profiles()
+                .map(|profile| profile.name.as_str())
+                .collect::<Vec<_>>(),
+            ["https", "worker"]
+        );
+
+        let https = settings.default_profile().unwrap();
+        assert_eq!(https.name, "https");
+        assert!(https.launch_browser);
+        assert_eq!(https.launch_url.as_deref(), Some("swagger"));
+        assert_eq!(https.arguments(), Vec::<String>::new());
+        assert_eq!(
+            https.environment(|name| (name == "HOME").then(|| "/home/me".to_string())),
+            [
+                (
+                    "ASPNETCORE_ENVIRONMENT".to_string(),
+                    "Development".to_string()
+                ),
+                ("DATA_DIR".to_string(), "/home/me/data".to_string()),
+                (
+                    "ASPNETCORE_URLS".to_string(),
+                    "https://localhost:7001;http://localhost:5001".to_string()
+                ),
+            ]
+        );
+        assert_eq!(
+            https.working_directory(Path::new("/src/Api")),
+            Path::new("/src/Api")
+        );
+        assert_eq!(
+            https.summary(),
+            Some("https://localhost:7001;http://localhost:5001")
+        );
+
+        let worker = settings.profile("worker").unwrap();
+        assert_eq!(worker.arguments(), ["--queue", "orders high", "--verbose"]);
+        // The profile's own URLs win over the application URL
+        assert_eq!(
+            worker.environment(|_| None),
+            [("ASPNETCORE_URLS".to_string(), "http://*:8080".to_string())]
+        );
+        assert_eq!(
+            worker.working_directory(Path::new("/src/Worker")),
+            Path::new("/src/Worker/../data")
+        );
+    }
+
+    #[test]
+    fn test_expand_environment_variables() {
+        let environment = |name: &str| (name == "USER").then(|| "me".to_string());
+        assert_eq!(
+            expand_environment_variables("%USER%:%MISSING%:%USER%", environment),
+            "me:%MISSING%:me"
+        );
+        assert_eq!(
+            expand_environment_variables("100% of %USER%", environment),
+            "100% of me"
+        );
+        assert_eq!(expand_environment_variables("%%", environment), "%%");
+    }
+
+    #[test]
+    fn test_split_command_line() {
+        assert_eq!(
+            split_command_line(r#"  run "C:\Program Files\app" --name \"quoted\" """#),
+            ["run", r"C:\Program Files\app", "--name", "\"quoted\"", ""]
+        );
+    }
+}
@@ -0,0 +1,2023 @@
+//! A small MSBuild evaluator for `.csproj`-style project files.
+//!
+//! This is not a full MSBuild implementation: targets and tasks are never run.
+//! It evaluates what is needed to answer "what does this project build and
+//! where does the output go": properties (with conditions, imports of
+//! `Directory.Build.props`/`Directory.Build.targets`, `Choose` blocks and a few
+//! common property functions) and items, with the default items of SDK-style
+//! projects and their wildcards expanded on request.
+
+use anyhow::{Context as _, Result, anyhow, bail};
+use collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+
+const PROJECT_FILE_EXTENSIONS: &[&str] = &["csproj", "fsproj", "vbproj"];
+
+/// File access used while evaluating a project, so that callers can evaluate
+/// projects from a real file system or from in-memory contents.
+pub trait ProjectFiles {
+    fn read(&self, path: &Path) -> Option<String>;
+    fn exists(&self, path: &Path) -> bool;
+    fn is_dir(&self, path: &Path) -> bool;
+    /// The files and directories in a directory.
+    fn read_dir(&self, path: &Path) -> Vec<PathBuf>;
+}
+
+/// [`ProjectFiles`] backed by [`std::fs`].
+pub struct RealProjectFiles;
+
+impl ProjectFiles for RealProjectFiles {
+    fn read(&self, path: &Path) -> Option<String> {
+        std::fs::read_to_string(path).ok()
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        path.is_dir()
+    }
+
+    fn read_dir(&self, path: &Path) -> Vec<PathBuf> {
+        std::fs::read_dir(path)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+            .collect()
+    }
+}
+
+/// Global properties a project is evaluated with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvaluationOptions {
+    /// The build configuration, e.g. `Debug` or `Release`.
+    pub configuration: String,
+    /// The build platform, e.g. `AnyCPU` or `x64`. Uses the project default when unset.
+    pub platform: Option<String>,
+    /// The target framework of an inner build of a multi-targeted project.
+    pub target_framework: Option<String>,
+    /// Additional global properties, as passed to `dotnet build -p:Name=Value`.
+    pub global_properties: Vec<(String, String)>,
+    /// Whether to expand wildcards in item includes into the files they match,
+    /// and to add the default items of SDK-style projects, such as
+    /// `<Compile Include="**/*.cs" />`. Otherwise includes are kept as written.
+    pub expand_wildcards: bool,
+}
+
+impl Default for EvaluationOptions {
+    fn default() -> Self {
+        Self {
+            configuration: "Debug".to_string(),
+            platform: None,
+            target_framework: None,
+            global_properties: Vec::new(),
+            expand_wildcards: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputType {
+    Library,
+    Exe,
+    WinExe,
+    Module,
+}
+
+impl OutputType {
+    pub fn parse(value: &str) -> Self {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "exe" => Self::Exe,
+            "winexe" => Self::WinExe,
+            "module" => Self::Module,
+            _ => Self::Library,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Library => "Library",
+            Self::Exe => "Exe",
+            Self::WinExe => "WinExe",
+            Self::Module => "Module",
+        }
+    }
+
+    pub fn is_executable(&self) -> bool {
+        matches!(self, Self::Exe | Self::WinExe)
+    }
+}
+
+/// An evaluated item, e.g. `<PackageReference Include="Serilog" Version="3.1.1" />`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectItem {
+    /// The item type, i.e. the element name (`PackageReference`, `Compile`, ...).
+    pub item_type: String,
+    /// The evaluated `Include` value of a single item.
+    pub include: String,
+    /// The evaluated `Exclude` patterns.
+    pub exclude: Vec<String>,
+    /// Item metadata from attributes and child elements, in declaration order.
+    pub metadata: Vec<(String, String)>,
+}
+
+impl ProjectItem {
+    pub fn metadata(&self, name: &str) -> Option<&str> {
+        self.metadata
+            .iter()
+            .rev()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn set_metadata(&mut self, name: &str, value: String) {
+        if let Some((_, existing)) = self
+            .metadata
+            .iter_mut()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        {
+            *existing = value;
+        } else {
+            self.metadata.push((name.to_string(), value));
+        }
+    }
+}
+
+/// The result of evaluating a single project file with a fixed set of global properties.
+#[derive(Debug, Clone)]
+pub struct MsBuildProject {
+    /// Absolute path to the project file.
+    pub path: PathBuf,
+    /// All files imported during evaluation, in import order.
+    pub imports: Vec<PathBuf>,
+    /// Evaluated items, in declaration order.
+    pub items: Vec<ProjectItem>,
+    properties: HashMap<String, String>,
+}
+
+impl MsBuildProject {
+    pub fn evaluate(
+        path: &Path,
+        options: &EvaluationOptions,
+        files: &dyn ProjectFiles,
+    ) -> Result<Self> {
+        Evaluator::new(path, options, files).evaluate()
+    }
+
+    /// Returns the value of a property, or `None` if it is unset or empty.
+    pub fn property(&self, name: &str) -> Option<&str> {
+        self.properties
+            .get(&name.to_ascii_lowercase())
+            .map(|value| value.as_str())
+            .filter(|value| !value.is_empty())
+    }
+
+    pub fn directory(&self) -> &Path {
+        self.path.parent().unwrap_or(Path::new(""))
+    }
+
+    pub fn items_of_type<'a>(
+        &'a self,
+        item_type: &'a str,
+    ) -> impl Iterator<Item = &'a ProjectItem> {
+        self.items
+            .iter()
+            .filter(move |item| item.item_type.eq_ignore_ascii_case(item_type))
+    }
+
+    fn is_true(&self, name: &str) -> bool {
+        self.property(name)
+            .is_some_and(|value| value.eq_ignore_ascii_case("true"))
+    }
+}
+
+/// Build output of a project for a single target framework.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectTarget {
+    /// The target framework moniker, e.g. `net8.0`. `None` for legacy projects
+    /// that only specify `TargetFrameworkVersion`.
+    pub target_framework: Option<String>,
+    pub assembly_name: String,
+    pub output_type: OutputType,
+    /// Absolute output directory, e.g. `<project>/bin/Debug/net8.0`.
+    pub output_directory: PathBuf,
+    /// Absolute path of the main output assembly.
+    pub output_assembly: PathBuf,
+}
+
+/// A structured view of a project, with one [`ProjectTarget`] per target framework.
+#[derive(Debug, Clone)]
+pub struct ProjectModel {
+    /// Absolute path to the project file.
+    pub path: PathBuf,
+    /// The project name, i.e. the project file name without its extension.
+    pub name: String,
+    pub assembly_name: String,
+    pub root_namespace: String,
+    pub output_type: OutputType,
+    pub configuration: String,
+    pub platform: String,
+    pub is_test_project: bool,
+    /// Whether package versions come from `Directory.Packages.props` (Central Package Management).
+    pub manages_package_versions_centrally: bool,
+    pub targets: Vec<ProjectTarget>,
+    /// Items of the outer (framework-independent) evaluation.
+    pub items: Vec<ProjectItem>,
+    pub imports: Vec<PathBuf>,
+}
+
+impl ProjectModel {
+    /// Evaluates a project and, for multi-targeted projects, each of its inner builds.
+    pub fn load(
+        path: &Path,
+        options: &EvaluationOptions,
+        files: &dyn ProjectFiles,
+    ) -> Result<Self> {
+        let outer_options = EvaluationOptions {
+            target_framework: None,
+            ..options.clone()
+        };
+        let outer = MsBuildProject::evaluate(path, &outer_options, files)?;
+
+        let target_frameworks = outer
+            .property("TargetFrameworks")
+            .map(split_list)
+            .unwrap_or_default();
+        let targets = if target_frameworks.is_empty() {
+            vec![project_target(&outer)]
+        } else {
+            target_frameworks
+                .into_iter()
+                .map(|target_framework| {
+                    let inner_options = EvaluationOptions {
+                        target_framework: Some(target_framework),
+                        // Items come from the outer evaluation
+                        expand_wildcards: false,
+                        ..options.clone()
+                    };
+                    let inner = MsBuildProject::evaluate(path, &inner_options, files)?;
+                    Ok(project_target(&inner))
+                })
+                .collect::<Result<Vec<_>>>()?
+        };
+
+        let name = outer
+            .property("MSBuildProjectName")
+            .unwrap_or_default()
+            .to_string();
+        let is_test_project = outer.is_true("IsTestProject")
+            || outer
+                .items_of_type("PackageReference")
+                .any(|item| item.include.eq_ignore_ascii_case("Microsoft.NET.Test.Sdk"));
+
+        Ok(Self {
+            path: outer.path.clone(),
+            assembly_name: outer.property("AssemblyName").unwrap_or(&name).to_string(),
+            root_namespace: outer.property("RootNamespace").unwrap_or(&name).to_string(),
+            output_type: OutputType::parse(outer.property("OutputType").unwrap_or_default()),
+            configuration: outer
+                .property("Configuration")
+                .unwrap_or("Debug")
+                .to_string(),
+            platform: outer.property("Platform").unwrap_or("AnyCPU").to_string(),
+            is_test_project,
+            manages_package_versions_centrally: outer.is_true("ManagePackageVersionsCentrally"),
+            targets,
+            items: outer.items,
+            imports: outer.imports,
+            name,
+        })
+    }
+
+    pub fn directory(&self) -> &Path {
+        self.path.parent().unwrap_or(Path::new(""))
+    }
+
+    pub fn target_frameworks(&self) -> impl Iterator<Item = &str> {
+        self.targets
+            .iter()
+            .filter_map(|target| target.target_framework.as_deref())
+    }
+
+    /// Returns the target for the given framework, or the first target when
+    /// no framework is requested.
+    pub fn target(&self, target_framework: Option<&str>) -> Option<&ProjectTarget> {
+        match target_framework {
+            Some(target_framework) => self.targets.iter().find(|target| {
+                target
+                    .target_framework
+                    .as_deref()
+                    .is_some_and(|framework| framework.eq_ignore_ascii_case(target_framework))
+            }),
+            None => self.targets.first(),
+        }
+    }
+
+    pub fn is_executable(&self) -> bool {
+        self.output_type.is_executable()
+    }
+
+    /// The imported `Directory.Packages.props` holding central package versions, if any.
+    pub fn central_packages_file(&self) -> Option<&Path> {
+        self.imports
+            .iter()
+            .find(|import| {
+                import
+                    .file_name()
+                    .is_some_and(|name| name.eq_ignore_ascii_case("Directory.Packages.props"))
+            })
+            .map(PathBuf::as_path)
+    }
+
+    pub fn items_of_type<'a>(
+        &'a self,
+        item_type: &'a str,
+    ) -> impl Iterator<Item = &'a ProjectItem> {
+        self.items
+            .iter()
+            .filter(move |item| item.item_type.eq_ignore_ascii_case(item_type))
+    }
+}
+
+fn project_target(project: &MsBuildProject) -> ProjectTarget {
+    let target_framework = project.property("TargetFramework").map(str::to_string);
+    let assembly_name = project
+        .property("AssemblyName")
+        .or_else(|| project.property("MSBuildProjectName"))
+        .unwrap_or_default()
+        .to_string();
+    let output_type = OutputType::parse(project.property("OutputType").unwrap_or_default());
+    let output_directory = resolve_path(
+        project.directory(),
+        project.property("OutputPath").unwrap_or_default(),
+    );
+    let extension = project
+        .property("TargetExt")
+        .map(str::to_string)
+        .unwrap_or_else(|| {
+            let is_framework = match target_framework.as_deref() {
+                Some(target_framework) => is_net_framework_moniker(target_framework),
+                None => project.property("TargetFrameworkVersion").is_some(),
+            };
+            if output_type.is_executable() && is_framework {
+                ".exe".to_string()
+            } else {
+                ".dll".to_string()
+            }
+        });
+    let output_assembly = output_directory.join(format!("{assembly_name}{extension}"));
+    ProjectTarget {
+        target_framework,
+        assembly_name,
+        output_type,
+        output_directory,
+        output_assembly,
+    }
+}
+
+/// Whether a target framework moniker such as `net48` refers to .NET Framework
+/// (as opposed to `net8.0`, `netcoreapp3.1` or `netstandard2.0`).
+fn is_net_framework_moniker(moniker: &str) -> bool {
+    let moniker = moniker.to_ascii_lowercase();
+    moniker
+        .strip_prefix("net")
+        .is_some_and(|version| !version.is_empty() && version.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Finds the project file closest to `start`, walking up the directory tree.
+///
+/// When a directory contains several project files, the one named after the
+/// directory wins.
+pub fn find_project_file(start: &Path) -> Option<PathBuf> {
+    for directory in start.ancestors() {
+        let Ok(entries) = std::fs::read_dir(directory) else {
+            continue;
+        };
+        let mut candidates = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.is_file()
+                    && path
+                        .extension()
+                        .and_then(|extension| extension.to_str())
+                        .is_some_and(|extension| PROJECT_FILE_EXTENSIONS.contains(&extension))
+            })
+            .collect::<Vec<_>>();
+        if candidates.is_empty() {
+            continue;
+        }
+        candidates.sort();
+        let directory_name = directory.file_name();
+        let preferred = candidates
+            .iter()
+            .position(|path| path.file_stem() == directory_name)
+            .unwrap_or(0);
+        return Some(candidates.swap_remove(preferred));
+    }
+    None
+}
+
+/// Splits an MSBuild list (`a;b; c`) into its non-empty elements.
+pub(crate) fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(';')
+        .map(str::trim)
+        .filter(|element| !element.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Resolves an MSBuild path (which may use `\` separators) against a base directory.
+pub(crate) fn resolve_path(base: &Path, value: &str) -> PathBuf {
+    let value = value.trim().replace('\\', "/");
+    normalize_path(&base.join(value))
+}
+
+/// Expresses `path` relative to the directory `base`, using `..` where needed.
+pub(crate) fn relative_path(base: &Path, path: &Path) -> PathBuf {
+    let base = resolve_path(base, "");
+    let path = resolve_path(Path::new(""), &path.to_string_lossy());
+    let base = base.components().collect::<Vec<_>>();
+    let components = path.components().collect::<Vec<_>>();
+    let common = base
+        .iter()
+        .zip(&components)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let mut relative = PathBuf::new();
+    for _ in common..base.len() {
+        relative.push("..");
+    }
+    for component in &components[common..] {
+        relative.push(component);
+    }
+    relative
+}
+
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push(component);
+                }
+            }
+            _ => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+struct PendingItemGroup {
+    file: PathBuf,
+    condition: Option<String>,
+    items: Vec<PendingItem>,
+}
+
+struct PendingItem {
+    item_type: String,
+    condition: Option<String>,
+    include: Option<String>,
+    exclude: Option<String>,
+    remove: Option<String>,
+    update: Option<String>,
+    metadata: Vec<PendingMetadata>,
+}
+
+struct PendingMetadata {
+    name: String,
+    value: String,
+    condition: Option<String>,
+}
+
+struct Evaluator<'a> {
+    path: PathBuf,
+    files: &'a dyn ProjectFiles,
+    properties: HashMap<String, String>,
+    global_properties: HashSet<String>,
+    item_groups: Vec<PendingItemGroup>,
+    imports: Vec<PathBuf>,
+    import_stack: Vec<PathBuf>,
+    this_file: PathBuf,
+    expand_wildcards: bool,
+}
+
+impl<'a> Evaluator<'a> {
+    fn new(path: &Path, options: &EvaluationOptions, files: &'a dyn ProjectFiles) -> Self {
+        let path = normalize_path(path);
+        let mut evaluator = Self {
+            this_file: path.clone(),
+            path,
+            files,
+            properties: HashMap::default(),
+            global_properties: HashSet::default(),
+            item_groups: Vec::new(),
+            imports: Vec::new(),
+            import_stack: Vec::new(),
+            expand_wildcards: options.expand_wildcards,
+        };
+
+        let directory = evaluator
+            .path
+            .parent()
+            .unwrap_or(Path::new(""))
+            .to_path_buf();
+        let file_name = file_name(&evaluator.path);
+        let name = evaluator
+            .path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let extension = evaluator
+            .path
+            .extension()
+            .map(|extension| format!(".{}", extension.to_string_lossy()))
+            .unwrap_or_default();
+        evaluator.set_reserved("MSBuildProjectFullPath", path_string(&evaluator.path));
+        evaluator.set_reserved("MSBuildProjectDirectory", path_string(&directory));
+        evaluator.set_reserved("MSBuildProjectFile", file_name);
+        evaluator.set_reserved("MSBuildProjectName", name);
+        evaluator.set_reserved("MSBuildProjectExtension", extension);
+
+        evaluator.set_global("Configuration", options.configuration.clone());
+        if let Some(platform) = &options.platform {
+            evaluator.set_global("Platform", platform.clone());
+        }
+        if let Some(target_framework) = &options.target_framework {
+            evaluator.set_global("TargetFramework", target_framework.clone());
+        }
+        for (name, value) in &options.global_properties {
+            evaluator.set_global(name, value.clone());
+        }
+        evaluator
+    }
+
+    fn evaluate(mut self) -> Result<MsBuildProject> {
+        let content = self
+            .files
+            .read(&self.path)
+            .with_context(|| format!("reading project file {}", self.path.display()))?;
+        let document = roxmltree::Document::parse(&content)
+            .with_context(|| format!("parsing project file {}", self.path.display()))?;
+        let root = document.root_element();
+        if root.tag_name().name() != "Project" {
+            bail!("{} is not an MSBuild project file", self.path.display());
+        }
+        let project_directory = self.path.parent().unwrap_or(Path::new("")).to_path_buf();
+        self.import_stack.push(self.path.clone());
+
+        // Properties a project sees before its own body, as set up by `Sdk.props`.
+        if !self.is_false("ImportDirectoryBuildProps") {
+            self.import_file_above(&project_directory, "Directory.Build.props");
+        }
+        // Central package versions, imported by the NuGet props of the SDK.
+        if !self.is_false("ImportDirectoryPackagesProps") {
+            self.import_file_above(&project_directory, "Directory.Packages.props");
+        }
+        self.set_default("Configuration", "Debug");
+        self.set_default("Platform", "AnyCPU");
+        self.set_default("BaseOutputPath", "bin\\");
+        self.set_default("BaseIntermediateOutputPath", "obj\\");
+        if self.property("OutputPath").is_empty() {
+            let output_path = if self.property("Platform").eq_ignore_ascii_case("AnyCPU") {
+                self.expand("$(BaseOutputPath)$(Configuration)\\")
+            } else {
+                self.expand("$(BaseOutputPath)$(Platform)\\$(Configuration)\\")
+            };
+            self.set_property("OutputPath", output_path);
+        }
+        let sdks = project_sdks(root);
+        if !sdks.is_empty() {
+            self.import_sdk_defaults(&sdks);
+        }
+
+        self.evaluate_children(root)?;
+
+        // Defaults applied after the project body, as done by `Sdk.targets`.
+        let project_name = self.property("MSBuildProjectName");
+        self.set_default("AssemblyName", &project_name);
+        let assembly_name = self.property("AssemblyName");
+        self.set_default("RootNamespace", &assembly_name);
+        self.set_default("OutputType", "Library");
+        let target_framework = self.property("TargetFramework");
+        if !target_framework.is_empty() && !self.is_false("AppendTargetFrameworkToOutputPath") {
+            let output_path = self.expand("$(OutputPath)$(TargetFramework)\\");
+            self.set_property("OutputPath", output_path);
+        }
+        let runtime_identifier = self.property("RuntimeIdentifier");
+        if !runtime_identifier.is_empty() && !self.is_false("AppendRuntimeIdentifierToOutputPath") {
+            let output_path = self.expand("$(OutputPath)$(RuntimeIdentifier)\\");
+            self.set_property("OutputPath", output_path);
+        }
+        if !self.is_false("ImportDirectoryBuildTargets") {
+            self.import_file_above(&project_directory, "Directory.Build.targets");
+        }
+
+        let items = self.evaluate_items();
+        Ok(MsBuildProject {
+            path: self.path,
+            imports: self.imports,
+            items,
+            properties: self.properties,
+        })
+    }
+
+    /// Properties and default items of SDK-style projects, as set up by the
+    /// `Sdk.props` of the .NET SDK.
+    fn import_sdk_defaults(&mut self, sdks: &[String]) {
+        let language_extension = match self
+            .property("MSBuildProjectExtension")
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            ".vbproj" => ".vb",
+            ".fsproj" => ".fs",
+            _ => ".cs",
+        };
+        self.set_default("DefaultLanguageSourceExtension", language_extension);
+        self.set_default("EnableDefaultItems", "true");
+        // F# compiles files in the order the project lists them
+        let compile_items = if language_extension == ".fs" {
+            "false"
+        } else {
+            "true"
+        };
+        self.set_default("EnableDefaultCompileItems", compile_items);
+        self.set_default("EnableDefaultEmbeddedResourceItems", "true");
+        self.set_default("EnableDefaultNoneItems", "true");
+        let item_excludes = self.expand(
+            "$(BaseOutputPath)/**;$(BaseIntermediateOutputPath)/**;**/*.user;**/*.*proj;**/*.sln;**/*.vssscc",
+        );
+        self.set_default("DefaultItemExcludes", &item_excludes);
+        self.set_default("DefaultExcludesInProjectFolder", "**/.*/**");
+
+        let is_web = sdks.iter().any(|sdk| {
+            [
+                "Microsoft.NET.Sdk.Web",
+                "Microsoft.NET.Sdk.Razor",
+                "Microsoft.NET.Sdk.BlazorWebAssembly",
+            ]
+            .iter()
+            .any(|web_sdk| sdk.eq_ignore_ascii_case(web_sdk))
+        });
+        if is_web {
+            self.set_default("EnableDefaultContentItems", "true");
+        }
+        if !self.expand_wildcards {
+            return;
+        }
+
+        let exclude = "$(DefaultItemExcludes);$(DefaultExcludesInProjectFolder)";
+        let source_files = "**/*$(DefaultLanguageSourceExtension)";
+        let content_files = "wwwroot/**;**/*.config;**/*.json;**/*.cshtml;**/*.razor";
+        let mut groups = vec![
+            (
+                "EnableDefaultCompileItems",
+                vec![default_item(
+                    "Compile",
+                    Some(source_files),
+                    Some(exclude),
+                    None,
+                )],
+            ),
+            (
+                "EnableDefaultEmbeddedResourceItems",
+                vec![default_item(
+                    "EmbeddedResource",
+                    Some("**/*.resx"),
+                    Some(exclude),
+                    None,
+                )],
+            ),
+            (
+                "EnableDefaultNoneItems",
+                vec![
+                    default_item("None", Some("**/*"), Some(exclude), None),
+                    default_item("None", None, None, Some(source_files)),
+                    default_item("None", None, None, Some("**/*.resx")),
+                ],
+            ),
+        ];
+        if is_web {
+            groups.push((
+                "EnableDefaultContentItems",
+                vec![
+                    default_item("Content", Some(content_files), Some(exclude), None),
+                    default_item("None", None, None, Some(content_files)),
+                ],
+            ));
+        }
+        self.item_groups
+            .extend(groups.into_iter().map(|(switch, items)| PendingItemGroup {
+                file: self.path.clone(),
+                condition: Some(format!(
+                    "'$(EnableDefaultItems)' == 'true' and '$({switch})' == 'true'"
+                )),
+                items,
+            }));
+    }
+
+    fn evaluate_children(&mut self, node: roxmltree::Node) -> Result<()> {
+        for child in node.children().filter(|child| child.is_element()) {
+            match child.tag_name().name() {
+                "PropertyGroup" => {
+                    if self.condition(&child) {
+                        self.evaluate_property_group(child);
+                    }
+                }
+                "ItemGroup" => {
+                    let group = self.pending_item_group(child);
+                    self.item_groups.push(group);
+                }
+                "Import" => {
+                    if self.condition(&child) {
+                        self.evaluate_import(child);
+                    }
+                }
+                "ImportGroup" => {
+                    if self.condition(&child) {
+                        for import in child
+                            .children()
+                            .filter(|import| import.has_tag_name("Import"))
+                        {
+                            if self.condition(&import) {
+                                self.evaluate_import(import);
+                            }
+                        }
+                    }
+                }
+                "Choose" => {
+                    let when = child
+                        .children()
+                        .filter(|branch| branch.has_tag_name("When"))
+                        .find(|branch| self.condition(branch));
+                    let branch = when.or_else(|| {
+                        child
+                            .children()
+                            .find(|branch| branch.has_tag_name("Otherwise"))
+                    });
+                    if let Some(branch) = branch {
+                        self.evaluate_children(branch)?;
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn evaluate_property_group(&mut self, group: roxmltree::Node) {
+        for property in group.children().filter(|property| property.is_element()) {
+            if !self.condition(&property) {
+                continue;
+            }
+            let name = property.tag_name().name();
+            let value = self.expand(element_text(&property).trim());
+            self.set_property(name, value);
+        }
+    }
+
+    fn evaluate_import(&mut self, import: roxmltree::Node) {
+        let Some(project) = import.attribute("Project") else {
+            return;
+        };
+        if import.attribute("Sdk").is_some() {
+            // SDK imports live in the .NET SDK installation, which we do not evaluate.
+            return;
+        }
+        let project = self.expand(project);
+        if project.contains('*') || project.contains('?') {
+            log::debug!("skipping wildcard import {project}");
+            return;
+        }
+        let path = resolve_path(&self.this_file_directory(), &project);
+        if !self.files.exists(&path) {
+            log::debug!("skipping missing import {}", path.display());
+            return;
+        }
+        self.import_file(&path);
+    }
+
+    /// Imports the first file named `file_name` found in `directory` or one of its ancestors.
+    fn import_file_above(&mut self, directory: &Path, file_name: &str) {
+        if let Some(path) = file_above(self.files, directory, file_name) {
+            self.import_file(&path);
+        }
+    }
+
+    fn import_file(&mut self, path: &Path) {
+        if self.import_stack.iter().any(|imported| imported == path) {
+            log::warn!("skipping circular import of {}", path.display());
+            return;
+        }
+        let Some(content) = self.files.read(path) else {
+            return;
+        };
+        let document = match roxmltree::Document::parse(&content) {
+            Ok(document) => document,
+            Err(error) => {
+                log::warn!("failed to parse imported file {}: {error}", path.display());
+                return;
+            }
+        };
+
+        self.imports.push(path.to_path_buf());
+        self.import_stack.push(path.to_path_buf());
+        let previous_file = std::mem::replace(&mut self.this_file, path.to_path_buf());
+        if let Err(error) = self.evaluate_children(document.root_element()) {
+            log::warn!(
+                "failed to evaluate imported file {}: {error}",
+                path.display()
+            );
+        }
+        self.this_file = previous_file;
+        self.import_stack.pop();
+    }
+
+    fn pending_item_group(&self, group: roxmltree::Node) -> PendingItemGroup {
+        let items =
+            group
+                .children()
+                .filter(|item| item.is_element())
+                .map(|item| {
+                    let mut metadata = item
+                        .attributes()
+                        .filter(|attribute| {
+                            !matches!(
+                                attribute.name(),
+                                "Include" | "Exclude" | "Remove" | "Update" | "Condition"
+                            )
+                        })
+                        .map(|attribute| PendingMetadata {
+                            name: attribute.name().to_string(),
+                            value: attribute.value().to_string(),
+                            condition: None,
+                        })
+                        .collect::<Vec<_>>();
+                    metadata.extend(item.children().filter(|child| child.is_element()).map(
+                        |child| PendingMetadata {
+                            name: child.tag_name().name().to_string(),
+                            value: element_text(&child).trim().to_string(),
+                            condition: child.attribute("Condition").map(str::to_string),
+                        },
+                    ));
+                    PendingItem {
+                        item_type: item.tag_name().name().to_string(),
+                        condition: item.attribute("Condition").map(str::to_string),
+                        include: item.attribute("Include").map(str::to_string),
+                        exclude: item.attribute("Exclude").map(str::to_string),
+                        remove: item.attribute("Remove").map(str::to_string),
+                        update: item.attribute("Update").map(str::to_string),
+                        metadata,
+                    }
+                })
+                .collect();
+        PendingItemGroup {
+            file: self.this_file.clone(),
+            condition: group.attribute("Condition").map(str::to_string),
+            items,
+        }
+    }
+
+    /// Evaluates items once all properties are known, as MSBuild does.
+    fn evaluate_items(&mut self) -> Vec<ProjectItem> {
+        let mut items: Vec<ProjectItem> = Vec::new();
+        for group in std::mem::take(&mut self.item_groups) {
+            self.this_file = group.file.clone();
+            if !self.evaluate_condition_logged(group.condition.as_deref()) {
+                continue;
+            }
+            for pending in &group.items {
+                if !self.evaluate_condition_logged(pending.condition.as_deref()) {
+                    continue;
+                }
+                let metadata = pending
+                    .metadata
+                    .iter()
+                    .filter(|metadata| {
+                        self.evaluate_condition_logged(metadata.condition.as_deref())
+                    })
+                    .map(|metadata| (metadata.name.clone(), self.expand(&metadata.value)))
+                    .collect::<Vec<_>>();
+
+                if let Some(include) = &pending.include {
+                    let exclude = pending
+                        .exclude
+                        .as_deref()
+                        .map(|exclude| split_list(&self.expand(exclude)))
+                        .unwrap_or_default();
+                    for include in split_list(&self.expand(include)) {
+                        for (include, recursive_dir) in self.expand_include(&include, &exclude) {
+                            let mut metadata = metadata.clone();
+                            if let Some(recursive_dir) = recursive_dir {
+                                metadata.push(("RecursiveDir".to_string(), recursive_dir));
+                            }
+                            items.push(ProjectItem {
+                                item_type: pending.item_type.clone(),
+                                include,
+                                exclude: exclude.clone(),
+                                metadata,
+                            });
+                        }
+                    }
+                } else if let Some(remove) = &pending.remove {
+                    let removed = split_list(&self.expand(remove));
+                    items.retain(|item| {
+                        !item.item_type.eq_ignore_ascii_case(&pending.item_type)
+                            || !removed
+                                .iter()
+                                .any(|removed| item_spec_refers_to(removed, &item.include))
+                    });
+                } else if let Some(update) = &pending.update {
+                    let updated = split_list(&self.expand(update));
+                    for item in items.iter_mut().filter(|item| {
+                        item.item_type.eq_ignore_ascii_case(&pending.item_type)
+                            && updated
+                                .iter()
+                                .any(|updated| item_spec_refers_to(updated, &item.include))
+                    }) {
+                        for (name, value) in &metadata {
+                            item.set_metadata(name, value.clone());
+                        }
+                    }
+                }
+            }
+        }
+        self.this_file = self.path.clone();
+        items
+    }
+
+    /// The items an include stands for when wildcards are expanded: the
+    /// matching files, relative to the project directory, along with the
+    /// directories `**` matched, as `%(RecursiveDir)`.
+    fn expand_include(&self, include: &str, exclude: &[String]) -> Vec<(String, Option<String>)> {
+        if !self.expand_wildcards {
+            return vec![(include.to_string(), None)];
+        }
+        let pattern = normalize_item_spec(include);
+        let excludes = exclude
+            .iter()
+            .map(|exclude| normalize_item_spec(exclude))
+            .collect::<Vec<_>>();
+        let is_excluded = |path: &str| {
+            excludes
+                .iter()
+                .any(|exclude| matches_item_spec(exclude, path))
+        };
+        if !has_wildcards(&pattern) {
+            return if is_excluded(&pattern) {
+                Vec::new()
+            } else {
+                vec![(include.to_string(), None)]
+            };
+        }
+
+        let segments = pattern.split('/').collect::<Vec<_>>();
+        let fixed = segments
+            .iter()
+            .take_while(|segment| !has_wildcards(segment))
+            .count();
+        let base = segments[..fixed].join("/");
+        let is_recursive = segments[fixed..].contains(&"**");
+        // Without `**`, files can't be deeper than the pattern
+        let max_depth = if is_recursive {
+            usize::MAX
+        } else {
+            segments.len() - fixed - 1
+        };
+        // Excluded directories, such as `bin/**`, are not listed at all
+        let is_excluded_directory = |path: &str| {
+            excludes.iter().any(|exclude| {
+                exclude
+                    .strip_suffix("/**")
+                    .is_some_and(|directory| matches_item_spec(directory, path))
+            })
+        };
+
+        let project_directory = self.path.parent().unwrap_or(Path::new(""));
+        let mut files = Vec::new();
+        let mut directories = vec![(resolve_path(project_directory, &base), base.clone(), 0)];
+        while let Some((directory, relative, depth)) = directories.pop() {
+            let mut entries = self.files.read_dir(&directory);
+            entries.sort();
+            for entry in entries {
+                let Some(name) = entry.file_name().map(|name| name.to_string_lossy()) else {
+                    continue;
+                };
+                let path = if relative.is_empty() {
+                    name.into_owned()
+                } else {
+                    format!("{relative}/{name}")
+                };
+                if self.files.is_dir(&entry) {
+                    if depth < max_depth && !is_excluded_directory(&path) {
+                        directories.push((entry, path, depth + 1));
+                    }
+                } else if matches_item_spec(&pattern, &path) && !is_excluded(&path) {
+                    let recursive_dir = is_recursive.then(|| {
+                        let within_base = path[base.len()..].trim_start_matches('/');
+                        match within_base.rfind('/') {
+                            Some(end) => within_base[..=end].to_string(),
+                            None => String::new(),
+                        }
+                    });
+                    files.push((path, recursive_dir));
+                }
+            }
+        }
+        files.sort();
+        files
+    }
+
+    fn condition(&self, node: &roxmltree::Node) -> bool {
+        self.evaluate_condition_logged(node.attribute("Condition"))
+    }
+
+    fn evaluate_condition_logged(&self, condition: Option<&str>) -> bool {
+        let Some(condition) = condition else {
+            return true;
+        };
+        match self.evaluate_condition(condition) {
+            Ok(result) => result,
+            Err(error) => {
+                log::warn!(
+                    "failed to evaluate condition {condition:?} in {}: {error}",
+                    self.this_file.display()
+                );
+                false
+            }
+        }
+    }
+
+    fn evaluate_condition(&self, condition: &str) -> Result<bool> {
+        if condition.trim().is_empty() {
+            return Ok(true);
+        }
+        let tokens = self.tokenize_condition(condition)?;
+        let mut parser = ConditionParser {
+            evaluator: self,
+            tokens,
+            position: 0,
+        };
+        let result = parser.parse_or()?;
+        if parser.position != parser.tokens.len() {
+            bail!("unexpected trailing tokens");
+        }
+        Ok(result)
+    }
+
+    fn tokenize_condition(&self, condition: &str) -> Result<Vec<Token>> {
+        let mut tokens = Vec::new();
+        let chars = condition.char_indices().collect::<Vec<_>>();
+        let mut ix = 0;
+        while ix < chars.len() {
+            let (offset, c) = chars[ix];
+            let next = chars.get(ix + 1).map(|(_, c)| *c);
+            match c {
+                c if c.is_whitespace() => ix += 1,
+                '\'' => {
+                    let end = chars[ix + 1..]
+                        .iter()
+                        .position(|(_, c)| *c == '\'')
+                        .map(|end| ix + 1 + end)
+                        .ok_or_else(|| anyhow!("unterminated string"))?;
+                    let start = offset + 1;
+                    let end_offset = chars[end].0;
+                    tokens.push(Token::String(self.expand(&condition[start..end_offset])));
+                    ix = end + 1;
+                }
+                '(' => {
+                    tokens.push(Token::OpenParen);
+                    ix += 1;
+                }
+                ')' => {
+                    tokens.push(Token::CloseParen);
+                    ix += 1;
+                }
+                ',' => {
+                    tokens.push(Token::Comma);
+                    ix += 1;
+                }
+                '=' | '!' | '<' | '>' => {
+                    let operator = match (c, next) {
+                        ('=', Some('=')) => Operator::Equal,
+                        ('!', Some('=')) => Operator::NotEqual,
+                        ('<', Some('=')) => Operator::LessOrEqual,
+                        ('>', Some('=')) => Operator::GreaterOrEqual,
+                        ('<', _) => Operator::Less,
+                        ('>', _) => Operator::Greater,
+                        ('!', _) => {
+                            tokens.push(Token::Not);
+                            ix += 1;
+                            continue;
+                        }
+                        _ => bail!("unexpected character {c:?}"),
+                    };
+                    tokens.push(Token::Operator(operator));
+                    ix += if next == Some('=') { 2 } else { 1 };
+                }
+                '$' if next == Some('(') => {
+                    let end = matching_paren(condition, offset + 1)
+                        .ok_or_else(|| anyhow!("unterminated property reference"))?;
+                    tokens.push(Token::String(self.expand(&condition[offset..=end])));
+                    while ix < chars.len() && chars[ix].0 <= end {
+                        ix += 1;
+                    }
+                }
+                c if c.is_alphanumeric() || c == '_' || c == '.' || c == '-' => {
+                    let start = offset;
+                    while ix < chars.len() {
+                        let c = chars[ix].1;
+                        if c.is_alphanumeric() || c == '_' || c == '.' || c == '-' {
+                            ix += 1;
+                        } else {
+                            break;
+                        }
+                    }
+                    let end = chars.get(ix).map_or(condition.len(), |(offset, _)| *offset);
+                    tokens.push(Token::Word(condition[start..end].to_string()));
+                }
+                c => bail!("unexpected character {c:?}"),
+            }
+        }
+        Ok(tokens)
+    }
+
+    /// Expands `$(Property)` references and supported property functions.
+    fn expand(&self, value: &str) -> String {
+        let mut result = String::with_capacity(value.len());
+        let mut rest = value;
+        while let Some(start) = rest.find("$(") {
+            result.push_str(&rest[..start]);
+            let Some(end) = matching_paren(rest, start + 1) else {
+                result.push_str(&rest[start..]);
+                return result;
+            };
+            let inner = rest[start + 2..end].trim();
+            result.push_str(&self.expand_reference(inner));
+            rest = &rest[end + 1..];
+        }
+        result.push_str(rest);
+        result
+    }
+
+    fn expand_reference(&self, reference: &str) -> String {
+        if let Some(function) = reference.strip_prefix('[') {
+            return self.expand_static_function(function).unwrap_or_else(|| {
+                log::debug!("unsupported property function $({reference})");
+                String::new()
+            });
+        }
+        if reference
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+        {
+            return self.property(reference);
+        }
+        log::debug!("unsupported property expression $({reference})");
+        String::new()
+    }
+
+    /// Expands static property functions such as `[MSBuild]::GetPathOfFileAbove(...)`.
+    fn expand_static_function(&self, function: &str) -> Option<String> {
+        let (class, call) = function.split_once("]::")?;
+        let open = call.find('(')?;
+        let close = call.rfind(')')?;
+        let name = call[..open].trim();
+        let arguments = split_arguments(&call[open + 1..close])
+            .into_iter()
+            .map(|argument| self.expand(argument.trim().trim_matches('\'')))
+            .collect::<Vec<_>>();
+        let this_directory = self.this_file_directory();
+
+        match (class.to_ascii_lowercase().as_str(), name) {
+            ("msbuild", "GetDirectoryNameOfFileAbove") => {
+                let start = resolve_path(&this_directory, arguments.first()?);
+                let path = file_above(self.files, &start, arguments.get(1)?)?;
+                Some(path_string(path.parent()?))
+            }
+            ("msbuild", "GetPathOfFileAbove") => {
+                let start = arguments
+                    .get(1)
+                    .map(|start| resolve_path(&this_directory, start))
+                    .unwrap_or(this_directory);
+                file_above(self.files, &start, arguments.first()?).map(|path| path_string(&path))
+            }
+            ("msbuild", "NormalizePath") | ("system.io.path", "Combine") => {
+                let mut path = PathBuf::new();
+                for argument in &arguments {
+                    path = resolve_path(&path, argument);
+                }
+                Some(path_string(&path))
+            }
+            ("msbuild", "NormalizeDirectory") => {
+                let mut path = PathBuf::new();
+                for argument in &arguments {
+                    path = resolve_path(&path, argument);
+                }
+                let mut path = path_string(&path);
+                if !path.ends_with(std::path::MAIN_SEPARATOR) {
+                    path.push(std::path::MAIN_SEPARATOR);
+                }
+                Some(path)
+            }
+            _ => None,
+        }
+    }
+
+    fn this_file_directory(&self) -> PathBuf {
+        self.this_file
+            .parent()
+            .unwrap_or(Path::new(""))
+            .to_path_buf()
+    }
+
+    fn property(&self, name: &str) -> String {
+        let key = name.to_ascii_lowercase();
+        match key.as_str() {
+            "msbuildthisfile" => file_name(&self.this_file),
+            "msbuildthisfilename" => self
+                .this_file
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            "msbuildthisfilefullpath" => path_string(&self.this_file),
+            "msbuildthisfiledirectory" => {
+                let mut directory = path_string(&self.this_file_directory());
+                directory.push(std::path::MAIN_SEPARATOR);
+                directory
+            }
+            _ => self.properties.get(&key).cloned().unwrap_or_default(),
+        }
+    }
+
+    fn is_false(&self, name: &str) -> bool {
+        self.property(name).eq_ignore_ascii_case("false")
+    }
+
+    fn set_property(&mut self, name: &str, value: String) {
+        let key = name.to_ascii_lowercase();
+        if self.global_properties.contains(&key) {
+            return;
+        }
+        self.properties.insert(key, value);
+    }
+
+    fn set_default(&mut self, name: &str, value: &str) {
+        if self.property(name).is_empty() {
+            self.set_property(name, value.to_string());
+        }
+    }
+
+    fn set_global(&mut self, name: &str, value: String) {
+        let key = name.to_ascii_lowercase();
+        self.properties.insert(key.clone(), value);
+        self.global_properties.insert(key);
+    }
+
+    fn set_reserved(&mut self, name: &str, value: String) {
+        self.set_global(name, value);
+    }
+}
+
+/// The SDKs of an SDK-style project, from its `Sdk` attribute, `<Sdk>`
+/// elements and imports of SDK files.
+fn project_sdks(root: roxmltree::Node) -> Vec<String> {
+    let mut sdks = root.attribute("Sdk").map(split_list).unwrap_or_default();
+    for child in root.children().filter(|child| child.is_element()) {
+        let sdk = match child.tag_name().name() {
+            "Sdk" => child.attribute("Name"),
+            "Import" => child.attribute("Sdk"),
+            _ => None,
+        };
+        sdks.extend(sdk.map(str::to_string));
+    }
+    // Drop versions, as in `Microsoft.Build.NoTargets/3.7.0`
+    for sdk in &mut sdks {
+        if let Some(end) = sdk.find('/') {
+            sdk.truncate(end);
+        }
+    }
+    sdks
+}
+
+fn default_item(
+    item_type: &str,
+    include: Option<&str>,
+    exclude: Option<&str>,
+    remove: Option<&str>,
+) -> PendingItem {
+    PendingItem {
+        item_type: item_type.to_string(),
+        condition: None,
+        include: include.map(str::to_string),
+        exclude: exclude.map(str::to_string),
+        remove: remove.map(str::to_string),
+        update: None,
+        metadata: Vec::new(),
+    }
+}
+
+/// An item spec with `/` separators and without redundant ones, so that
+/// `bin\/**` and `./bin/**` compare equal to `bin/**`.
+fn normalize_item_spec(spec: &str) -> String {
+    let spec = spec.trim().replace('\\', "/");
+    let spec = spec.trim_start_matches("./");
+    let mut normalized = String::with_capacity(spec.len());
+    for c in spec.chars() {
+        if c != '/' || !normalized.ends_with('/') {
+            normalized.push(c);
+        }
+    }
+    normalized.trim_end_matches('/').to_string()
+}
+
+fn has_wildcards(spec: &str) -> bool {
+    spec.contains(['*', '?'])
+}
+
+/// Whether the item spec of a `Remove` or `Update` refers to an item.
+fn item_spec_refers_to(spec: &str, include: &str) -> bool {
+    let spec = normalize_item_spec(spec);
+    let include = normalize_item_spec(include);
+    if has_wildcards(&spec) {
+        matches_item_spec(&spec, &include)
+    } else {
+        spec.eq_ignore_ascii_case(&include)
+    }
+}
+
+/// Whether a path matches an item spec, where `**` matches any number of
+/// directories, and `*` and `?` match within a name. Both use `/` separators.
+fn matches_item_spec(spec: &str, path: &str) -> bool {
+    let spec = spec.split('/').collect::<Vec<_>>();
+    let path = path.split('/').collect::<Vec<_>>();
+    matches_segments(&spec, &path)
+}
+
+fn matches_segments(spec: &[&str], path: &[&str]) -> bool {
+    match spec.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => {
+            (0..=path.len()).any(|skipped| matches_segments(rest, &path[skipped..]))
+        }
+        Some((segment, rest)) => path.split_first().is_some_and(|(name, path)| {
+            let segment = segment.chars().collect::<Vec<_>>();
+            let name = name.chars().collect::<Vec<_>>();
+            matches_name(&segment, &name) && matches_segments(rest, path)
+        }),
+    }
+}
+
+fn matches_name(spec: &[char], name: &[char]) -> bool {
+    match spec.split_first() {
+        None => name.is_empty(),
+        Some(('*', rest)) => (0..=name.len()).any(|skipped| matches_name(rest, &name[skipped..])),
+        Some((c, rest)) => name.split_first().is_some_and(|(first, name)| {
+            (*c == '?' || first.eq_ignore_ascii_case(c)) && matches_name(rest, name)
+        }),
+    }
+}
+
+fn file_above(files: &dyn ProjectFiles, start: &Path, file_name: &str) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|directory| directory.join(file_name))
+        .find(|path| files.exists(path))
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn path_string(path: &Path) -> String {
+    path.to_string_lossy().into_owned()
+}
+
+fn element_text<'a>(node: &roxmltree::Node<'a, '_>) -> &'a str {
+    node.text().unwrap_or_default()
+}
+
+/// Returns the byte offset of the parenthesis closing the one at `open`.
+fn matching_paren(text: &str, open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut in_quotes = false;
+    for (offset, c) in text[open..].char_indices() {
+        match c {
+            '\'' => in_quotes = !in_quotes,
+            '(' if !in_quotes => depth += 1,
+            ')' if !in_quotes => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(open + offset);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Splits property function arguments on top-level commas.
+fn split_arguments(arguments: &str) -> Vec<&str> {
+    let mut result = Vec::new();
+    let mut depth = 0usize;
+    let mut in_quotes = false;
+    let mut start = 0;
+    for (offset, c) in arguments.char_indices() {
+        match c {
+            '\'' => in_quotes = !in_quotes,
+            '(' if !in_quotes => depth += 1,
+            ')' if !in_quotes => depth = depth.saturating_sub(1),
+            ',' if !in_quotes && depth == 0 => {
+                result.push(&arguments[start..offset]);
+                start = offset + 1;
+            }
+            _ => {}
+        }
+    }
+    if !arguments[start..].trim().is_empty() {
+        result.push(&arguments[start..]);
+    }
+    result
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    String(String),
+    Word(String),
+    Operator(Operator),
+    Not,
+    OpenParen,
+    CloseParen,
+    Comma,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operator {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+struct ConditionParser<'a, 'b> {
+    evaluator: &'a Evaluator<'b>,
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl ConditionParser<'_, '_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword))
+    }
+
+    fn parse_or(&mut self) -> Result<bool> {
+        let mut result = self.parse_and()?;
+        while self.peek_keyword("or") {
+            self.position += 1;
+            let rhs = self.parse_and()?;
+            result = result || rhs;
+        }
+        Ok(result)
+    }
+
+    fn parse_and(&mut self) -> Result<bool> {
+        let mut result = self.parse_unary()?;
+        while self.peek_keyword("and") {
+            self.position += 1;
+            let rhs = self.parse_unary()?;
+            result = result && rhs;
+        }
+        Ok(result)
+    }
+
+    fn parse_unary(&mut self) -> Result<bool> {
+        if self.peek() == Some(&Token::Not) {
+            self.position += 1;
+            return Ok(!self.parse_unary()?);
+        }
+        self.parse_comparison()
+    }
+
+    fn parse_comparison(&mut self) -> Result<bool> {
+        if self.peek() == Some(&Token::OpenParen) {
+            self.position += 1;
+            let result = self.parse_or()?;
+            if self.next() != Some(Token::CloseParen) {
+                bail!("expected ')'");
+            }
+            return Ok(result);
+        }
+
+        if let Some(Token::Word(word)) = self.peek().cloned()
+            && self.tokens.get(self.position + 1) == Some(&Token::OpenParen)
+        {
+            self.position += 2;
+            let mut arguments = Vec::new();
+            loop {
+                match self.next() {
+                    Some(Token::CloseParen) => break,
+                    Some(Token::Comma) => {}
+                    Some(Token::String(value)) | Some(Token::Word(value)) => arguments.push(value),
+                    _ => bail!("malformed arguments to {word}"),
+                }
+            }
+            return self.call_function(&word, &arguments);
+        }
+
+        let lhs = self.parse_operand()?;
+        let Some(Token::Operator(operator)) = self.peek().cloned() else {
+            return parse_bool(&lhs);
+        };
+        self.position += 1;
+        let rhs = self.parse_operand()?;
+        compare(&lhs, operator, &rhs)
+    }
+
+    fn parse_operand(&mut self) -> Result<String> {
+        match self.next() {
+            Some(Token::String(value)) | Some(Token::Word(value)) => Ok(value),
+            token => bail!("expected operand, found {token:?}"),
+        }
+    }
+
+    fn call_function(&self, name: &str, arguments: &[String]) -> Result<bool> {
+        let argument = arguments.first().map(String::as_str).unwrap_or_default();
+        match name.to_ascii_lowercase().as_str() {
+            "exists" => {
+                if argument.trim().is_empty() {
+                    return Ok(false);
+                }
+                let path = resolve_path(&self.evaluator.this_file_directory(), argument);
+                Ok(self.evaluator.files.exists(&path))
+            }
+            "hastrailingslash" => Ok(argument.ends_with('/') || argument.ends_with('\\')),
+            _ => bail!("unsupported condition function {name}"),
+        }
+    }
+}
+
+fn parse_bool(value: &str) -> Result<bool> {
+    match value.to_ascii_lowercase().as_str() {
+        "true" | "on" | "yes" => Ok(true),
+        "false" | "off" | "no" => Ok(false),
+        _ => bail!("expected a boolean, found {value:?}"),
+    }
+}
+
+fn compare(lhs: &str, operator: Operator, rhs: &str) -> Result<bool> {
+    match operator {
+        Operator::Equal => Ok(lhs.eq_ignore_ascii_case(rhs)),
+        Operator::NotEqual => Ok(!lhs.eq_ignore_ascii_case(rhs)),
+        _ => {
+            let lhs = parse_number(lhs)?;
+            let rhs = parse_number(rhs)?;
+            Ok(match operator {
+                Operator::Less => lhs < rhs,
+                Operator::LessOrEqual => lhs <= rhs,
+                Operator::Greater => lhs > rhs,
+                Operator::GreaterOrEqual => lhs >= rhs,
+                Operator::Equal | Operator::NotEqual => unreachable!(),
+            })
+        }
+    }
+}
+
+fn parse_number(value: &str) -> Result<f64> {
+    value
+        .trim()
+        .parse::<f64>()
+        .with_context(|| format!("expected a number, found {value:?}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[derive(Default)]
+    struct FakeProjectFiles(HashMap<PathBuf, String>);
+
+    impl FakeProjectFiles {
+        fn with(mut self, path: &str, content: &str) -> Self {
+            self.0.insert(PathBuf::from(path), content.to_string());
+            self
+        }
+    }
+
+    impl ProjectFiles for FakeProjectFiles {
+        fn read(&self, path: &Path) -> Option<String> {
+            self.0.get(path).cloned()
+        }
+
+        fn exists(&self, path: &Path) -> bool {
+            self.0.keys().any(|file| file.starts_with(path))
+        }
+
+        fn is_dir(&self, path: &Path) -> bool {
+            self.0
+                .keys()
+                .any(|file| file != path && file.starts_with(path))
+        }
+
+        fn read_dir(&self, path: &Path) -> Vec<PathBuf> {
+            let mut entries = self
+                .0
+                .keys()
+                .filter_map(|file| {
+                    let child = file.strip_prefix(path).ok()?.components().next()?;
+                    Some(path.join(child))
+                })
+                .collect::<Vec<_>>();
+            entries.sort();
+            entries.dedup();
+            entries
+        }
+    }
+
+    #[test]
+    fn test_sdk_project_defaults() {
+        let files = FakeProjectFiles::default().with(
+            "/src/App/App.csproj",
+            r#"<Project Sdk="Microsoft.NET.Sdk">
+                <PropertyGroup>
+                    <OutputType>Exe</OutputType>
+                    <TargetFramework>net8.0</TargetFramework>
+                </PropertyGroup>
+            </Project>"#,
+        );
+        let model = ProjectModel::load(
+            Path::new("/src/App/App.csproj"),
+            &EvaluationOptions::default(),
+            &files,
+        )
+        .unwrap();
+
+        assert_eq!(model.name, "App");
+        assert_eq!(model.assembly_name, "App");
+        assert_eq!(model.output_type, OutputType::Exe);
+        assert_eq!(
+            model.targets,
+            vec![ProjectTarget {
+                target_framework: Some("net8.0".into()),
+                assembly_name: "App".into(),
+                output_type: OutputType::Exe,
+                output_directory: PathBuf::from("/src/App/bin/Debug/net8.0"),
+                output_assembly: PathBuf::from("/src/App/bin/Debug/net8.0/App.dll"),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_multi_targeting_with_conditions() {
+        let files = FakeProjectFiles::default().with(
+            "/src/Lib/Lib.csproj",
+            r#"<Project Sdk="Microsoft.NET.Sdk">
+                <PropertyGroup>
+                    <TargetFrameworks>net8.0;net48</TargetFrameworks>
+                    <AssemblyName>Contoso.Lib</AssemblyName>
+                    <OutputType>Exe</OutputType>
+                </PropertyGroup>
+                <PropertyGroup Condition="'$(Configuration)|$(Platform)' == 'Release|AnyCPU'">
+                    <OutputPath>..\artifacts\</OutputPath>
+                </PropertyGroup>
+                <PropertyGroup Condition=" '$(TargetFramework)' == 'net48' ">
+                    <AssemblyName>Contoso.Lib.Legacy</AssemblyName>
+                </PropertyGroup>
+            </Project>"#,
+        );
+        let options = EvaluationOptions {
+            configuration: "Release".into(),
+            ..EvaluationOptions::default()
+        };
+        let model = ProjectModel::load(Path::new("/src/Lib/Lib.csproj"), &options, &files).unwrap();
+
+        assert_eq!(
+            model.target_frameworks().collect::<Vec<_>>(),
+            vec!["net8.0", "net48"]
+        );
+        assert_eq!(
+            model.target(Some("net8.0")).unwrap().output_assembly,
+            PathBuf::from("/src/artifacts/net8.0/Contoso.Lib.dll")
+        );
+        assert_eq!(
+            model.target(Some("NET48")).unwrap().output_assembly,
+            PathBuf::from("/src/artifacts/net48/Contoso.Lib.Legacy.exe")
+        );
+    }
+
+    #[test]
+    fn test_directory_build_imports() {
+        let files = FakeProjectFiles::default()
+            .with(
+                "/repo/Directory.Build.props",
+                r#"<Project>
+                    <PropertyGroup>
+                        <BaseOutputPath>$(MSBuildThisFileDirectory)out\$(MSBuildProjectName)\</BaseOutputPath>
+                        <TargetFramework>net6.0</TargetFramework>
+                    </PropertyGroup>
+                </Project>"#,
+            )
+            .with(
+                "/repo/src/Directory.Build.props",
+                r#"<Project>
+                    <Import Project="$([MSBuild]::GetPathOfFileAbove('Directory.Build.props', '$(MSBuildThisFileDirectory)../'))" />
+                    <PropertyGroup>
+                        <RootNamespace>Contoso.$(MSBuildProjectName)</RootNamespace>
+                    </PropertyGroup>
+                </Project>"#,
+            )
+            .with(
+                "/repo/src/Directory.Build.targets",
+                r#"<Project>
+                    <ItemGroup>
+                        <PackageReference Include="StyleCop.Analyzers" Version="1.1.118" PrivateAssets="all" />
+                    </ItemGroup>
+                </Project>"#,
+            )
+            .with(
+                "/repo/src/Api/Api.csproj",
+                r#"<Project Sdk="Microsoft.NET.Sdk.Web">
+                    <ItemGroup>
+                        <PackageReference Include="Serilog" Version="3.1.1" />
+                        <PackageReference Include="Unused" Version="1.0.0" />
+                        <PackageReference Remove="Unused" />
+                        <PackageReference Update="Serilog">
+                            <PrivateAssets>all</PrivateAssets>
+                        </PackageReference>
+                        <ProjectReference Include="..\Core\Core.csproj" Condition="Exists('..\Core\Core.csproj')" />
+                    </ItemGroup>
+                </Project>"#,
+            );
+        let model = ProjectModel::load(
+            Path::new("/repo/src/Api/Api.csproj"),
+            &EvaluationOptions::default(),
+            &files,
+        )
+        .unwrap();
+
+        assert_eq!(
+            model.imports,
+            vec![
+                PathBuf::from("/repo/src/Directory.Build.props"),
+                PathBuf::from("/repo/Directory.Build.props"),
+                PathBuf::from("/repo/src/Directory.Build.targets"),
+            ]
+        );
+        assert_eq!(model.root_namespace, "Contoso.Api");
+        assert_eq!(
+            model.target(None).unwrap().output_directory,
+            PathBuf::from("/repo/out/Api/Debug/net6.0")
+        );
+        let packages = model
+            .items_of_type("PackageReference")
+            .map(|item| (item.include.as_str(), item.metadata("PrivateAssets")))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            packages,
+            vec![
+                ("Serilog", Some("all")),
+                ("StyleCop.Analyzers", Some("all"))
+            ]
+        );
+        assert_eq!(model.items_of_type("ProjectReference").count(), 0);
+    }
+
+    #[test]
+    fn test_default_items_and_wildcards() {
+        let files = FakeProjectFiles::default()
+            .with(
+                "/src/Api/Api.csproj",
+                r#"<Project Sdk="Microsoft.NET.Sdk.Web">
+                    <ItemGroup>
+                        <Compile Remove="Legacy\**" />
+                        <Compile Include="..\Shared\**\*.cs" LinkBase="Shared" />
+                        <None Update="appsettings.*.json" CopyToOutputDirectory="PreserveNewest" />
+                        <None Include="README.md" Exclude="*.md" />
+                    </ItemGroup>
+                </Project>"#,
+            )
+            .with("/src/Api/Program.cs", "")
+            .with("/src/Api/Legacy/Old.cs", "")
+            .with("/src/Api/Controllers/HomeController.cs", "")
+            .with("/src/Api/Resources/Strings.resx", "")
+            .with("/src/Api/appsettings.json", "")
+            .with("/src/Api/Notes/todo.txt", "")
+            .with("/src/Api/wwwroot/css/site.css", "")
+            .with("/src/Api/bin/Debug/net8.0/Api.cs", "")
+            .with("/src/Api/obj/Api.AssemblyInfo.cs", "")
+            .with("/src/Api/.vs/Api.cs", "")
+            .with("/src/Shared/Models/User.cs", "");
+        let options = EvaluationOptions {
+            expand_wildcards: true,
+            ..EvaluationOptions::default()
+        };
+        let model = ProjectModel::load(Path::new("/src/Api/Api.csproj"), &options, &files).unwrap();
+
+        let items = |item_type| {
+            model
+                .items_of_type(item_type)
+                .map(|item| item.include.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            items("Compile"),
+            vec![
+                "Controllers/HomeController.cs",
+                "Program.cs",
+                "../Shared/Models/User.cs",
+            ]
+        );
+        assert_eq!(items("EmbeddedResource"), vec!["Resources/Strings.resx"]);
+        assert_eq!(items("None"), vec!["Notes/todo.txt"]);
+        assert_eq!(
+            items("Content"),
+            vec!["wwwroot/css/site.css", "appsettings.json"]
+        );
+        let shared = model
+            .items_of_type("Compile")
+            .find(|item| item.include.starts_with(".."))
+            .unwrap();
+        assert_eq!(shared.metadata("RecursiveDir"), Some("Models/"));
+
+        // Without expansion, only the items of the project itself are there
+        let model = ProjectModel::load(
+            Path::new("/src/Api/Api.csproj"),
+            &EvaluationOptions::default(),
+            &files,
+        )
+        .unwrap();
+        assert_eq!(
+            model
+                .items
+                .iter()
+                .map(|item| item.include.as_str())
+                .collect::<Vec<_>>(),
+            vec!["..\\Shared\\**\\*.cs", "README.md"]
+        );
+    }
+
+    #[test]
+    fn test_conditions() {
+        let files = FakeProjectFiles::default().with("/p/Existing.props", "<Project />");
+        let options = EvaluationOptions {
+            platform: Some("x64".into()),
+            global_properties: vec![("Flag".into(), "true".into())],
+            ..EvaluationOptions::default()
+        };
+        let evaluator = Evaluator::new(Path::new("/p/P.csproj"), &options, &files);
+
+        for (condition, expected) in [
+            ("'$(Configuration)' == 'debug'", true),
+            ("'$(Configuration)|$(Platform)' != 'Debug|x64'", false),
+            ("$(Flag)", true),
+            ("!$(Flag) or '$(Missing)' == ''", true),
+            (
+                "('$(Platform)' == 'x86' or '$(Platform)' == 'x64') and Exists('Existing.props')",
+                true,
+            ),
+            ("Exists('$(Missing)')", false),
+            ("'10' > '9' and '1.5' <= '1.5'", true),
+            ("HasTrailingSlash('$(MSBuildThisFileDirectory)')", true),
+        ] {
+            assert_eq!(
+                evaluator.evaluate_condition(condition).unwrap(),
+                expected,
+                "{condition}"
+            );
+        }
+        assert!(evaluator.evaluate_condition("'a' ==").is_err());
+    }
+
+    #[test]
+    fn test_global_properties_are_not_overridden() {
+        let files = FakeProjectFiles::default().with(
+            "/p/P.csproj",
+            r#"<Project>
+                <PropertyGroup>
+                    <Configuration>Debug</Configuration>
+                    <TargetFrameworkVersion>v4.7.2</TargetFrameworkVersion>
+                    <OutputType>WinExe</OutputType>
+                </PropertyGroup>
+                <Choose>
+                    <When Condition="'$(Configuration)' == 'Release'">
+                        <PropertyGroup><DefineConstants>RELEASE</DefineConstants></PropertyGroup>
+                    </When>
+                    <Otherwise>
+                        <PropertyGroup><DefineConstants>DEBUG</DefineConstants></PropertyGroup>
+                    </Otherwise>
+                </Choose>
+            </Project>"#,
+        );
+        let options = EvaluationOptions {
+            configuration: "Release".into(),
+            ..EvaluationOptions::default()
+        };
+        let project = MsBuildProject::evaluate(Path::new("/p/P.csproj"), &options, &files).unwrap();
+
+        assert_eq!(project.property("Configuration"), Some("Release"));
+        assert_eq!(project.property("DefineConstants"), Some("RELEASE"));
+        let model = ProjectModel::load(Path::new("/p/P.csproj"), &options, &files).unwrap();
+        assert_eq!(
+            model.target(None).unwrap().output_assembly,
+            PathBuf::from("/p/bin/Release/P.exe")
+        );
+    }
+
+    #[test]
+    fn test_central_package_management() {
+        let files = FakeProjectFiles::default()
+            .with(
+                "/repo/Directory.Packages.props",
+                r#"<Project>
+                    <PropertyGroup>
+                        <ManagePackageVersionsCentrally>true</ManagePackageVersionsCentrally>
+                    </PropertyGroup>
+                    <ItemGroup>
+                        <PackageVersion Include="Serilog" Version="3.1.1" />
+                        <PackageVersion Include="Polly" Version="8.4.1" />
+                    </ItemGroup>
+                </Project>"#,
+            )
+            .with(
+                "/repo/src/App/App.csproj",
+                r#"<Project Sdk="Microsoft.NET.Sdk">
+                    <ItemGroup>
+                        <PackageReference Include="Serilog" />
+                        <PackageReference Include="Polly" VersionOverride="7.2.4" />
+                    </ItemGroup>
+                </Project>"#,
+            )
+            // Like the SDK, the opt-out only works before Directory.Packages.props is imported.
+            .with(
+                "/repo/src/Legacy/Directory.Build.props",
+                r#"<Project>
+                    <PropertyGroup>
+                        <ImportDirectoryPackagesProps>false</ImportDirectoryPackagesProps>
+                    </PropertyGroup>
+                </Project>"#,
+            )
+            .with(
+                "/repo/src/Legacy/Legacy.csproj",
+                r#"<Project Sdk="Microsoft.NET.Sdk" />"#,
+            );
+
+        let model = ProjectModel::load(
+            Path::new("/repo/src/App/App.csproj"),
+            &EvaluationOptions::default(),
+            &files,
+        )
+        .unwrap();
+        assert!(model.manages_package_versions_centrally);
+        assert_eq!(
+            model.central_packages_file(),
+            Some(Path::new("/repo/Directory.Packages.props"))
+        );
+        let packages = crate::project_dependencies(&model)
+            .into_iter()
+            .map(|dependency| {
+                (
+                    dependency.include,
+                    dependency.version,
+                    dependency.is_central_version,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            packages,
+            vec![
+                ("Serilog".to_string(), Some("3.1.1".to_string()), true),
+                ("Polly".to_string(), Some("7.2.4".to_string()), false),
+            ]
+        );
+
+        let legacy = ProjectModel::load(
+            Path::new("/repo/src/Legacy/Legacy.csproj"),
+            &EvaluationOptions::default(),
+            &files,
+        )
+        .unwrap();
+        assert!(!legacy.manages_package_versions_centrally);
+        assert_eq!(legacy.central_packages_file(), None);
+    }
+}
@@ -0,0 +1,1144 @@
+//! NuGet package sources, versions and Central Package Management.
+//!
+//! Package sources are read from the `nuget.config` files that apply to a
+//! directory. Local folder feeds are searched directly on disk; for NuGet v3
+//! feeds this module builds the request URLs and parses the responses, leaving
+//! the transport to the caller. A v3 feed may also be a local `index.json`
+//! whose flat container is a directory, which is handy for offline use.
+
+use crate::{
+    dependencies::{
+        Dependency, DependencyKind, find_items, insert_item, parse_project, version_range,
+    },
+    msbuild::resolve_path,
+    xml_edit::escape,
+};
+use anyhow::{Context as _, Result, bail};
+use collections::{BTreeMap, HashSet};
+use serde::Deserialize;
+use std::{
+    cmp::Ordering,
+    fmt,
+    path::{Path, PathBuf},
+};
+
+pub const NUGET_ORG_SOURCE: &str = "https://api.nuget.org/v3/index.json";
+
+/// A NuGet package version, e.g. `1.2.3` or `2.0.0-preview.1`.
+///
+/// Versions compare by their numeric parts, then by their prerelease labels,
+/// with release versions ordered after their prereleases. Build metadata after
+/// `+` is ignored.
+#[derive(Debug, Clone)]
+pub struct NuGetVersion {
+    numbers: [u64; 4],
+    prerelease: Vec<String>,
+    original: String,
+}
+
+impl NuGetVersion {
+    pub fn parse(version: &str) -> Option<Self> {
+        let original = version.trim();
+        let without_metadata = original.split('+').next()?;
+        let (numbers_part, prerelease) = match without_metadata.split_once('-') {
+            Some((numbers, prerelease)) => (
+                numbers,
+                prerelease
+                    .split('.')
+                    .map(str::to_string)
+                    .collect::<Vec<_>>(),
+            ),
+            None => (without_metadata, Vec::new()),
+        };
+        if prerelease.iter().any(|label| label.is_empty()) {
+            return None;
+        }
+        let parts = numbers_part.split('.').collect::<Vec<_>>();
+        if parts.is_empty() || parts.len() > 4 {
+            return None;
+        }
+        let mut numbers = [0; 4];
+        for (number, part) in numbers.iter_mut().zip(&parts) {
+            *number = part.parse().ok()?;
+        }
+        Some(Self {
+            numbers,
+            prerelease,
+            original: original.to_string(),
+        })
+    }
+
+    pub fn is_prerelease(&self) -> bool {
+        !self.prerelease.is_empty()
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.original
+    }
+}
+
+impl fmt::Display for NuGetVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.original)
+    }
+}
+
+impl PartialEq for NuGetVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for NuGetVersion {}
+
+impl PartialOrd for NuGetVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for NuGetVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.numbers.cmp(&other.numbers).then_with(|| {
+            match (self.is_prerelease(), other.is_prerelease()) {
+                (false, false) => Ordering::Equal,
+                (false, true) => Ordering::Greater,
+                (true, false) => Ordering::Less,
+                (true, true) => compare_prerelease(&self.prerelease, &other.prerelease),
+            }
+        })
+    }
+}
+
+fn compare_prerelease(a: &[String], b: &[String]) -> Ordering {
+    for (a, b) in a.iter().zip(b) {
+        let ordering = match (a.parse::<u64>(), b.parse::<u64>()) {
+            (Ok(a), Ok(b)) => a.cmp(&b),
+            (Ok(_), Err(_)) => Ordering::Less,
+            (Err(_), Ok(_)) => Ordering::Greater,
+            (Err(_), Err(_)) => a.to_ascii_lowercase().cmp(&b.to_ascii_lowercase()),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    a.len().cmp(&b.len())
+}
+
+/// A package source from a `nuget.config` file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackageSource {
+    /// The key of the source, e.g. `nuget.org`.
+    pub name: String,
+    pub kind: PackageSourceKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PackageSourceKind {
+    /// A directory of `.nupkg` files, either flat or in `<id>/<version>/` folders.
+    LocalFolder(PathBuf),
+    /// The service index of a NuGet v3 feed: an `http(s)://` URL, or the path of
+    /// an `index.json` on disk.
+    ServiceIndex(String),
+}
+
+impl PackageSourceKind {
+    fn parse(value: &str, config_directory: &Path) -> Self {
+        let value = value.trim();
+        if value.starts_with("http://") || value.starts_with("https://") {
+            return Self::ServiceIndex(value.to_string());
+        }
+        let path = value.strip_prefix("file://").unwrap_or(value);
+        let path = resolve_path(config_directory, path);
+        if path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("json"))
+        {
+            Self::ServiceIndex(path.to_string_lossy().into_owned())
+        } else {
+            Self::LocalFolder(path)
+        }
+    }
+}
+
+/// The package source settings of a single `nuget.config` file.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NuGetConfig {
+    pub sources: Vec<PackageSource>,
+    /// Whether the file clears the sources of the configs applied before it.
+    pub clear: bool,
+    /// Keys listed in `<disabledPackageSources>`.
+    pub disabled: Vec<String>,
+}
+
+/// Parses the `packageSources` of a `nuget.config` file in `directory`.
+pub fn parse_nuget_config(content: &str, directory: &Path) -> Result<NuGetConfig> {
+    let document = roxmltree::Document::parse(content).context("parsing nuget.config")?;
+    let mut config = NuGetConfig::default();
+    let section = |name: &str| {
+        document
+            .root_element()
+            .children()
+            .find(move |child| child.tag_name().name().eq_ignore_ascii_case(name))
+    };
+    if let Some(sources) = section("packageSources") {
+        for child in sources.children().filter(|child| child.is_element()) {
+            match child.tag_name().name() {
+                "clear" => {
+                    config.clear = true;
+                    config.sources.clear();
+                }
+                "add" => {
+                    let (Some(key), Some(value)) =
+                        (child.attribute("key"), child.attribute("value"))
+                    else {
+                        continue;
+                    };
+                    config
+                        .sources
+                        .retain(|source| !source.name.eq_ignore_ascii_case(key));
+                    config.sources.push(PackageSource {
+                        name: key.to_string(),
+                        kind: PackageSourceKind::parse(value, directory),
+                    });
+                }
+                "remove" => {
+                    if let Some(key) = child.attribute("key") {
+                        config
+                            .sources
+                            .retain(|source| !source.name.eq_ignore_ascii_case(key));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    if let Some(disabled) = section("disabledPackageSources") {
+        config.disabled = disabled
+            .children()
+            .filter(|child| {
+                child.has_tag_name("add")
+                    && child
+                        .attribute("value")
+                        .is_some_and(|value| value.eq_ignore_ascii_case("true"))
+            })
+            .filter_map(|child| child.attribute("key").map(str::to_string))
+            .collect();
+    }
+    Ok(config)
+}
+
+/// Combines configs the way NuGet does, from the lowest priority (the user
+/// config) to the highest (the config closest to the project).
+///
+/// Falls back to nuget.org when no config defines or clears any source.
+pub fn merge_nuget_configs(configs: impl IntoIterator<Item = NuGetConfig>) -> Vec<PackageSource> {
+    let mut sources = Vec::<PackageSource>::new();
+    let mut disabled = HashSet::default();
+    let mut any_config = false;
+    for config in configs {
+        any_config |= config.clear || !config.sources.is_empty();
+        if config.clear {
+            sources.clear();
+        }
+        for source in config.sources {
+            sources.retain(|existing| !existing.name.eq_ignore_ascii_case(&source.name));
+            sources.push(source);
+        }
+        disabled.extend(
+            config
+                .disabled
+                .into_iter()
+                .map(|key| key.to_ascii_lowercase()),
+        );
+    }
+    if !any_config {
+        sources.push(PackageSource {
+            name: "nuget.org".to_string(),
+            kind: PackageSourceKind::ServiceIndex(NUGET_ORG_SOURCE.to_string()),
+        });
+    }
+    sources.retain(|source| !disabled.contains(&source.name.to_ascii_lowercase()));
+    sources
+}
+
+/// The package sources that apply to projects in `directory`: those of the
+/// user-level `NuGet.Config` and of every `nuget.config` above `directory`.
+pub fn package_sources(directory: &Path) -> Vec<PackageSource> {
+    let mut config_paths = directory
+        .ancestors()
+        .filter_map(|ancestor| {
+            let entries = std::fs::read_dir(ancestor).ok()?;
+            entries.flatten().map(|entry| entry.path()).find(|path| {
+                path.file_name()
+                    .is_some_and(|name| name.eq_ignore_ascii_case("nuget.config"))
+            })
+        })
+        .collect::<Vec<_>>();
+    config_paths.extend(user_nuget_config());
+    config_paths.reverse();
+
+    let configs = config_paths.into_iter().filter_map(|path| {
+        let content = std::fs::read_to_string(&path).ok()?;
+        let directory = path.parent().unwrap_or(Path::new(""));
+        parse_nuget_config(&content, directory)
+            .inspect_err(|error| log::warn!("{}: {error:#}", path.display()))
+            .ok()
+    });
+    merge_nuget_configs(configs)
+}
+
+fn user_nuget_config() -> Option<PathBuf> {
+    let path = if cfg!(windows) {
+        PathBuf::from(std::env::var_os("APPDATA")?).join("NuGet/NuGet.Config")
+    } else {
+        PathBuf::from(std::env::var_os("HOME")?).join(".nuget/NuGet/NuGet.Config")
+    };
+    path.exists().then_some(path)
+}
+
+/// A package found in a package source.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackageSearchResult {
+    pub id: String,
+    pub description: Option<String>,
+    /// Available versions, newest first.
+    pub versions: Vec<NuGetVersion>,
+    /// The name of the source the package was found in.
+    pub source: String,
+}
+
+impl PackageSearchResult {
+    pub fn latest_version(&self, include_prerelease: bool) -> Option<&NuGetVersion> {
+        self.versions
+            .iter()
+            .find(|version| include_prerelease || !version.is_prerelease())
+    }
+}
+
+/// Merges search results from several sources, keeping the first source a
+/// package was found in and the union of its versions.
+pub fn merge_search_results(
+    results: impl IntoIterator<Item = PackageSearchResult>,
+) -> Vec<PackageSearchResult> {
+    let mut merged = Vec::<PackageSearchResult>::new();
+    for result in results {
+        match merged
+            .iter_mut()
+            .find(|existing| existing.id.eq_ignore_ascii_case(&result.id))
+        {
+            Some(existing) => {
+                for version in result.versions {
+                    if !existing.versions.contains(&version) {
+                        existing.versions.push(version);
+                    }
+                }
+                existing.versions.sort_by(|a, b| b.cmp(a));
+                if existing.description.is_none() {
+                    existing.description = result.description;
+                }
+            }
+            None => merged.push(result),
+        }
+    }
+    merged
+}
+
+fn matches_query(id: &str, query: &str) -> bool {
+    id.to_ascii_lowercase()
+        .contains(&query.trim().to_ascii_lowercase())
+}
+
+fn sorted_versions(
+    versions: impl IntoIterator<Item = NuGetVersion>,
+    include_prerelease: bool,
+) -> Vec<NuGetVersion> {
+    let mut versions = versions
+        .into_iter()
+        .filter(|version| include_prerelease || !version.is_prerelease())
+        .collect::<Vec<_>>();
+    versions.sort_by(|a, b| b.cmp(a));
+    versions.dedup();
+    versions
+}
+
+/// Searches a local folder feed for packages whose id contains `query`.
+///
+/// Both layouts produced by NuGet are supported: `.nupkg` files directly in the
+/// folder (`Id.1.0.0.nupkg`) and `<id>/<version>/` sub-folders.
+pub fn search_local_folder(
+    source: &str,
+    folder: &Path,
+    query: &str,
+    include_prerelease: bool,
+) -> Result<Vec<PackageSearchResult>> {
+    let mut packages = BTreeMap::<String, (String, Vec<NuGetVersion>)>::new();
+    let entries = std::fs::read_dir(folder)
+        .with_context(|| format!("reading package source {}", folder.display()))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            let Some(directory_name) = path.file_name().map(|name| name.to_string_lossy()) else {
+                continue;
+            };
+            let Ok(version_dirs) = std::fs::read_dir(&path) else {
+                continue;
+            };
+            for version_dir in version_dirs.flatten() {
+                let version_path = version_dir.path();
+                let Some(version) = version_path
+                    .file_name()
+                    .and_then(|name| NuGetVersion::parse(&name.to_string_lossy()))
+                else {
+                    continue;
+                };
+                // Take the original casing of the id from the package file, if present.
+                let id = std::fs::read_dir(&version_path)
+                    .ok()
+                    .and_then(|files| {
+                        files.flatten().find_map(|file| {
+                            parse_package_file_name(&file.file_name().to_string_lossy())
+                                .map(|(id, _)| id)
+                        })
+                    })
+                    .unwrap_or_else(|| directory_name.to_string());
+                let entry = packages
+                    .entry(id.to_ascii_lowercase())
+                    .or_insert_with(|| (id, Vec::new()));
+                entry.1.push(version);
+            }
+        } else if let Some((id, version)) =
+            parse_package_file_name(&entry.file_name().to_string_lossy())
+        {
+            let entry = packages
+                .entry(id.to_ascii_lowercase())
+                .or_insert_with(|| (id, Vec::new()));
+            entry.1.push(version);
+        }
+    }
+
+    Ok(packages
+        .into_values()
+        .filter(|(id, _)| matches_query(id, query))
+        .filter_map(|(id, versions)| {
+            let versions = sorted_versions(versions, include_prerelease);
+            (!versions.is_empty()).then(|| PackageSearchResult {
+                id,
+                description: None,
+                versions,
+                source: source.to_string(),
+            })
+        })
+        .collect())
+}
+
+/// Splits a package file name such as `Serilog.Sinks.Console.5.0.1.nupkg` into
+/// its id and version.
+fn parse_package_file_name(file_name: &str) -> Option<(String, NuGetVersion)> {
+    let stem = file_name
+        .strip_suffix(".nupkg")
+        .filter(|stem| !stem.ends_with(".symbols"))?;
+    // The version starts at the first dot-separated segment that is a number.
+    stem.match_indices('.').find_map(|(ix, _)| {
+        let version = &stem[ix + 1..];
+        if !version.starts_with(|c: char| c.is_ascii_digit()) {
+            return None;
+        }
+        Some((stem[..ix].to_string(), NuGetVersion::parse(version)?))
+    })
+}
+
+/// The resources of a NuGet v3 feed used for searching and listing versions.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ServiceIndex {
+    /// Base URL of the `SearchQueryService` resource.
+    pub search: Option<String>,
+    /// Base URL of the `PackageBaseAddress/3.0.0` (flat container) resource.
+    pub package_base_address: Option<String>,
+}
+
+impl ServiceIndex {
+    /// Parses a service index. Relative resource URLs of a local `index.json`
+    /// are resolved against the directory of `index_location`.
+    pub fn parse(json: &[u8], index_location: &str) -> Result<Self> {
+        #[derive(Deserialize)]
+        struct Index {
+            resources: Vec<Resource>,
+        }
+        #[derive(Deserialize)]
+        struct Resource {
+            #[serde(rename = "@id")]
+            id: String,
+            #[serde(rename = "@type")]
+            kind: ResourceType,
+        }
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum ResourceType {
+            One(String),
+            Many(Vec<String>),
+        }
+
+        let index: Index = serde_json::from_slice(json).context("parsing service index")?;
+        let resource = |prefix: &str| {
+            index.resources.iter().find_map(|resource| {
+                let is_match = match &resource.kind {
+                    ResourceType::One(kind) => kind.starts_with(prefix),
+                    ResourceType::Many(kinds) => kinds.iter().any(|kind| kind.starts_with(prefix)),
+                };
+                is_match.then(|| resolve_resource(&resource.id, index_location))
+            })
+        };
+        Ok(Self {
+            search: resource("SearchQueryService"),
+            package_base_address: resource("PackageBaseAddress/3.0.0"),
+        })
+    }
+}
+
+fn resolve_resource(id: &str, index_location: &str) -> String {
+    if id.contains("://") {
+        return id.strip_prefix("file://").unwrap_or(id).to_string();
+    }
+    let index_directory = Path::new(index_location).parent().unwrap_or(Path::new(""));
+    resolve_path(index_directory, id)
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Whether a resource location refers to the local file system rather than to a URL.
+pub fn is_local_resource(location: &str) -> bool {
+    !location.starts_with("http://") && !location.starts_with("https://")
+}
+
+/// The URL of a search request against a `SearchQueryService` resource.
+pub fn search_url(search: &str, query: &str, include_prerelease: bool, take: usize) -> String {
+    format!(
+        "{search}?q={}&prerelease={include_prerelease}&semVerLevel=2.0.0&take={take}",
+        encode_query_component(query)
+    )
+}
+
+fn encode_query_component(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+/// Parses the response of a `SearchQueryService` request.
+pub fn parse_search_response(
+    json: &[u8],
+    source: &str,
+    include_prerelease: bool,
+) -> Result<Vec<PackageSearchResult>> {
+    #[derive(Deserialize)]
+    struct Response {
+        data: Vec<Package>,
+    }
+    #[derive(Deserialize)]
+    struct Package {
+        id: String,
+        #[serde(default)]
+        description: Option<String>,
+        #[serde(default)]
+        version: Option<String>,
+        #[serde(default)]
+        versions: Vec<Version>,
+    }
+    #[derive(Deserialize)]
+    struct Version {
+        version: String,
+    }
+
+    let response: Response = serde_json::from_slice(json).context("parsing search results")?;
+    Ok(response
+        .data
+        .into_iter()
+        .map(|package| {
+            let versions = package
+                .versions
+                .iter()
+                .map(|version| version.version.as_str())
+                .chain(package.version.as_deref())
+                .filter_map(NuGetVersion::parse);
+            PackageSearchResult {
+                id: package.id,
+                description: package.description.filter(|text| !text.is_empty()),
+                versions: sorted_versions(versions, include_prerelease),
+                source: source.to_string(),
+            }
+        })
+        .collect())
+}
+
+/// The location of the version list of a package in a flat container.
+pub fn versions_url(package_base_address: &str, package_id: &str) -> String {
+    format!(
+        "{}/{}/index.json",
+        package_base_address.trim_end_matches('/'),
+        package_id.to_ascii_lowercase()
+    )
+}
+
+/// Parses the version list of a package in a flat container.
+pub fn parse_versions_response(json: &[u8], include_prerelease: bool) -> Result<Vec<NuGetVersion>> {
+    #[derive(Deserialize)]
+    struct Response {
+        versions: Vec<String>,
+    }
+    let response: Response = serde_json::from_slice(json).context("parsing package versions")?;
+    Ok(sorted_versions(
+        response
+            .versions
+            .iter()
+            .filter_map(|version| NuGetVersion::parse(version)),
+        include_prerelease,
+    ))
+}
+
+/// Searches a flat container on disk, as used by a local stand-in for a v3 feed:
+/// one directory per lowercase package id, each holding an `index.json` with
+/// the package versions.
+pub fn search_local_flat_container(
+    source: &str,
+    directory: &Path,
+    query: &str,
+    include_prerelease: bool,
+) -> Result<Vec<PackageSearchResult>> {
+    let entries = std::fs::read_dir(directory)
+        .with_context(|| format!("reading package source {}", directory.display()))?;
+    let mut results = entries
+        .flatten()
+        .filter_map(|entry| {
+            let id = entry.file_name().to_string_lossy().into_owned();
+            if !matches_query(&id, query) {
+                return None;
+            }
+            let json = std::fs::read(entry.path().join("index.json")).ok()?;
+            let versions = parse_versions_response(&json, include_prerelease).ok()?;
+            (!versions.is_empty()).then(|| PackageSearchResult {
+                id,
+                description: None,
+                versions,
+                source: source.to_string(),
+            })
+        })
+        .collect::<Vec<_>>();
+    results.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(results)
+}
+
+/// A top-level package with a newer version available, as reported by
+/// `dotnet list package --outdated --format json`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutdatedPackage {
+    pub project_path: PathBuf,
+    pub target_framework: String,
+    pub id: String,
+    pub requested_version: Option<String>,
+    pub resolved_version: String,
+    pub latest_version: String,
+}
+
+/// Parses the JSON output of `dotnet list package --outdated --format json`.
+pub fn parse_outdated_packages(json: &str) -> Result<Vec<OutdatedPackage>> {
+    #[derive(Deserialize)]
+    struct Report {
+        #[serde(default)]
+        projects: Vec<Project>,
+    }
+    #[derive(Deserialize)]
+    struct Project {
+        path: PathBuf,
+        #[serde(default)]
+        frameworks: Vec<Framework>,
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Framework {
+        framework: String,
+        #[serde(default)]
+        top_level_packages: Vec<Package>,
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Package {
+        id: String,
+        #[serde(default)]
+        requested_version: Option<String>,
+        resolved_version: String,
+        #[serde(default)]
+        latest_version: Option<String>,
+    }
+
+    let report: Report =
+        serde_json::from_str(json).context("parsing `dotnet list package` output")?;
+    let mut outdated = Vec::new();
+    for project in report.projects {
+        for framework in project.frameworks {
+            for package in framework.top_level_packages {
+                // Packages without a newer version report "Not found at the sources".
+                let Some(latest_version) = package
+                    .latest_version
+                    .filter(|latest| NuGetVersion::parse(latest).is_some())
+                else {
+                    continue;
+                };
+                outdated.push(OutdatedPackage {
+                    project_path: project.path.clone(),
+                    target_framework: framework.framework.clone(),
+                    id: package.id,
+                    requested_version: package.requested_version,
+                    resolved_version: package.resolved_version,
+                    latest_version,
+                });
+            }
+        }
+    }
+    Ok(outdated)
+}
+
+/// A package referenced with different versions by the projects of a solution.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VersionConflict {
+    pub package_id: String,
+    /// The projects referencing the package with the version each one uses.
+    pub projects: Vec<(String, String)>,
+    /// The highest version in use, which the projects are consolidated to.
+    pub highest_version: String,
+}
+
+/// Finds packages referenced with more than one version across projects.
+///
+/// Centrally managed versions are skipped, since they cannot diverge.
+pub fn version_conflicts<'a>(
+    projects: impl IntoIterator<Item = (&'a str, &'a [Dependency])>,
+) -> Vec<VersionConflict> {
+    let mut by_package = BTreeMap::<String, (String, Vec<(String, String)>)>::new();
+    for (project, dependencies) in projects {
+        for dependency in dependencies {
+            if dependency.kind != DependencyKind::Package || dependency.is_central_version {
+                continue;
+            }
+            let Some(version) = &dependency.version else {
+                continue;
+            };
+            by_package
+                .entry(dependency.include.to_ascii_lowercase())
+                .or_insert_with(|| (dependency.include.clone(), Vec::new()))
+                .1
+                .push((project.to_string(), version.clone()));
+        }
+    }
+    by_package
+        .into_values()
+        .filter_map(|(package_id, projects)| {
+            let highest_version = projects
+                .iter()
+                .filter_map(|(_, version)| NuGetVersion::parse(version))
+                .max()?;
+            projects
+                .iter()
+                .any(|(_, version)| NuGetVersion::parse(version).as_ref() != Some(&highest_version))
+                .then(|| VersionConflict {
+                    package_id,
+                    projects,
+                    highest_version: highest_version.to_string(),
+                })
+        })
+        .collect()
+}
+
+/// Sets the version of a package in the text of a `Directory.Packages.props`
+/// file, adding a `<PackageVersion>` item if the package has none yet.
+pub fn set_central_package_version(
+    content: &str,
+    props_path: &Path,
+    package_id: &str,
+    version: &str,
+) -> Result<String> {
+    let document = parse_project(content, props_path)?;
+    let items = find_items(&document, "PackageVersion", package_id);
+    if items.is_empty() {
+        let element = format!(
+            "<PackageVersion Include=\"{}\" Version=\"{}\" />",
+            escape(package_id),
+            escape(version)
+        );
+        return Ok(insert_item(content, &document, "PackageVersion", &element));
+    }
+    let mut text = content.to_string();
+    for item in items.iter().rev() {
+        let Some(range) = version_range(item) else {
+            bail!("the PackageVersion of {package_id} has no version");
+        };
+        text.replace_range(range, &escape(version));
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn version(version: &str) -> NuGetVersion {
+        NuGetVersion::parse(version).unwrap()
+    }
+
+    fn versions(result: &PackageSearchResult) -> Vec<&str> {
+        result.versions.iter().map(NuGetVersion::as_str).collect()
+    }
+
+    #[test]
+    fn test_version_ordering() {
+        let mut versions = [
+            "2.0.0",
+            "1.10.0",
+            "1.2",
+            "2.0.0-rc.1",
+            "2.0.0-beta.11",
+            "2.0.0-beta.2",
+            "2.0.0-beta",
+            "1.2.0.1",
+        ]
+        .map(version);
+        versions.sort();
+        assert_eq!(
+            versions
+                .iter()
+                .map(NuGetVersion::as_str)
+                .collect::<Vec<_>>(),
+            [
+                "1.2",
+                "1.2.0.1",
+                "1.10.0",
+                "2.0.0-beta",
+                "2.0.0-beta.2",
+                "2.0.0-beta.11",
+                "2.0.0-rc.1",
+                "2.0.0"
+            ]
+        );
+        assert_eq!(version("1.0.0+build.5"), version("1.0"));
+        assert!(version("2.0.0-rc.1").is_prerelease());
+        assert_eq!(NuGetVersion::parse("[1.0,2.0)"), None);
+        assert_eq!(NuGetVersion::parse("1.*"), None);
+    }
+
+    #[test]
+    fn test_nuget_config_sources() {
+        let user = parse_nuget_config(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<configuration>
+  <packageSources>
+    <add key="nuget.org" value="https://api.nuget.org/v3/index.json" protocolVersion="3" />
+    <add key="company" value="https://nuget.example.com/v3/index.json" />
+  </packageSources>
+</configuration>"#,
+            Path::new("/home/me/.nuget/NuGet"),
+        )
+        .unwrap();
+        let repo = parse_nuget_config(
+            r#"<configuration>
+  <packageSources>
+    <add key="local" value="packages" />
+    <add key="offline-v3" value="file://feeds/v3/index.json" />
+  </packageSources>
+  <disabledPackageSources>
+    <add key="company" value="true" />
+  </disabledPackageSources>
+</configuration>"#,
+            Path::new("/repo"),
+        )
+        .unwrap();
+        assert_eq!(
+            merge_nuget_configs([user.clone(), repo.clone()]),
+            vec![
+                PackageSource {
+                    name: "nuget.org".into(),
+                    kind: PackageSourceKind::ServiceIndex(NUGET_ORG_SOURCE.into()),
+                },
+                PackageSource {
+                    name: "local".into(),
+                    kind: PackageSourceKind::LocalFolder(PathBuf::from("/repo/packages")),
+                },
+                PackageSource {
+                    name: "offline-v3".into(),
+                    kind: PackageSourceKind::ServiceIndex("/repo/feeds/v3/index.json".into()),
+                },
+            ]
+        );
+
+        let isolated = parse_nuget_config(
+            r#"<configuration>
+  <packageSources>
+    <clear />
+    <add key="local" value="./packages" />
+  </packageSources>
+</configuration>"#,
+            Path::new("/repo/src"),
+        )
+        .unwrap();
+        assert_eq!(
+            merge_nuget_configs([user, repo, isolated]),
+            vec![PackageSource {
+                name: "local".into(),
+                kind: PackageSourceKind::LocalFolder(PathBuf::from("/repo/src/packages")),
+            }]
+        );
+        assert_eq!(
+            merge_nuget_configs([]),
+            vec![PackageSource {
+                name: "nuget.org".into(),
+                kind: PackageSourceKind::ServiceIndex(NUGET_ORG_SOURCE.into()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_search_local_folder() {
+        let feed = tempfile::tempdir().unwrap();
+        let root = feed.path();
+        for file in [
+            "Serilog.3.1.1.nupkg",
+            "Serilog.4.0.0-dev.2.nupkg",
+            "Serilog.Sinks.Console.5.0.1.nupkg",
+            "Serilog.Sinks.Console.5.0.1.symbols.nupkg",
+            "readme.txt",
+        ] {
+            std::fs::write(root.join(file), "").unwrap();
+        }
+        std::fs::create_dir_all(root.join("newtonsoft.json/13.0.3")).unwrap();
+        std::fs::write(
+            root.join("newtonsoft.json/13.0.3/Newtonsoft.Json.13.0.3.nupkg"),
+            "",
+        )
+        .unwrap();
+
+        let results = search_local_folder("local", root, "serilog", false).unwrap();
+        assert_eq!(
+            results
+                .iter()
+                .map(|result| (result.id.as_str(), versions(result)))
+                .collect::<Vec<_>>(),
+            vec![
+                ("Serilog", vec!["3.1.1"]),
+                ("Serilog.Sinks.Console", vec!["5.0.1"])
+            ]
+        );
+        let results = search_local_folder("local", root, "SERILOG", true).unwrap();
+        assert_eq!(versions(&results[0]), vec!["4.0.0-dev.2", "3.1.1"]);
+        assert_eq!(
+            results[0].latest_version(false).map(NuGetVersion::as_str),
+            Some("3.1.1")
+        );
+
+        let results = search_local_folder("local", root, "json", false).unwrap();
+        assert_eq!(results[0].id, "Newtonsoft.Json");
+        assert_eq!(versions(&results[0]), vec!["13.0.3"]);
+    }
+
+    #[test]
+    fn test_local_v3_feed() {
+        let feed = tempfile::tempdir().unwrap();
+        let root = feed.path();
+        let index = br#"{
+            "version": "3.0.0",
+            "resources": [
+                { "@id": "flat/", "@type": "PackageBaseAddress/3.0.0" },
+                { "@id": "https://search.example.com/query", "@type": ["SearchQueryService", "SearchQueryService/3.0.0-rc"] }
+            ]
+        }"#;
+        let index_path = root.join("index.json").to_string_lossy().into_owned();
+        let service_index = ServiceIndex::parse(index, &index_path).unwrap();
+        let flat = root.join("flat");
+        assert_eq!(
+            service_index,
+            ServiceIndex {
+                search: Some("https://search.example.com/query".into()),
+                package_base_address: Some(flat.to_string_lossy().into_owned()),
+            }
+        );
+
+        std::fs::create_dir_all(flat.join("polly")).unwrap();
+        std::fs::write(
+            flat.join("polly/index.json"),
+            r#"{ "versions": ["7.2.4", "8.0.0-beta.1", "8.4.1"] }"#,
+        )
+        .unwrap();
+        let results = search_local_flat_container("offline", &flat, "pol", false).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "polly");
+        assert_eq!(versions(&results[0]), vec!["8.4.1", "7.2.4"]);
+        assert!(
+            search_local_flat_container("offline", &flat, "serilog", false)
+                .unwrap()
+                .is_empty()
+        );
+
+        assert_eq!(
+            versions_url(
+                service_index.package_base_address.as_deref().unwrap(),
+                "Polly"
+            ),
+            format!("{}/polly/index.json", flat.to_string_lossy())
+        );
+    }
+
+    #[test]
+    fn test_search_response() {
+        assert_eq!(
+            search_url(
+                "https://search.example.com/query",
+                "serilog sinks",
+                true,
+                20
+            ),
+            "https://search.example.com/query?q=serilog%20sinks&prerelease=true&semVerLevel=2.0.0&take=20"
+        );
+        let response = br#"{
+            "totalHits": 1,
+            "data": [{
+                "id": "Serilog",
+                "version": "4.0.0",
+                "description": "Simple .NET logging with fully-structured events",
+                "versions": [
+                    { "version": "3.1.1", "downloads": 1 },
+                    { "version": "4.0.0-dev.2", "downloads": 1 },
+                    { "version": "4.0.0", "downloads": 1 }
+                ]
+            }]
+        }"#;
+        let results = parse_search_response(response, "nuget.org", false).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "Serilog");
+        assert_eq!(results[0].source, "nuget.org");
+        assert_eq!(versions(&results[0]), vec!["4.0.0", "3.1.1"]);
+
+        let merged = merge_search_results([
+            results[0].clone(),
+            PackageSearchResult {
+                id: "serilog".into(),
+                description: None,
+                versions: vec![version("4.1.0"), version("3.1.1")],
+                source: "local".into(),
+            },
+        ]);
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].source, "nuget.org");
+        assert_eq!(versions(&merged[0]), vec!["4.1.0", "4.0.0", "3.1.1"]);
+    }
+
+    #[test]
+    fn test_parse_outdated_packages() {
+        let json = r#"{
+  "version": 1,
+  "parameters": "--outdated",
+  "sources": ["https://api.nuget.org/v3/index.json"],
+  "projects": [
+    {
+      "path": "/repo/src/App/App.csproj",
+      "frameworks": [
+        {
+          "framework": "net8.0",
+          "topLevelPackages": [
+            {
+              "id": "Serilog",
+              "requestedVersion": "3.0.0",
+              "resolvedVersion": "3.0.0",
+              "latestVersion": "3.1.1"
+            },
+            {
+              "id": "Internal.Tools",
+              "requestedVersion": "1.0.0",
+              "resolvedVersion": "1.0.0",
+              "latestVersion": "Not found at the sources"
+            }
+          ]
+        }
+      ]
+    },
+    { "path": "/repo/src/Empty/Empty.csproj" }
+  ]
+}"#;
+        assert_eq!(
+            parse_outdated_packages(json).unwrap(),
+            vec![OutdatedPackage {
+                project_path: PathBuf::from("/repo/src/App/App.csproj"),
+                target_framework: "net8.0".into(),
+                id: "Serilog".into(),
+                requested_version: Some("3.0.0".into()),
+                resolved_version: "3.0.0".into(),
+                latest_version: "3.1.1".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_version_conflicts() {
+        let package = |id: &str, version: &str, is_central_version: bool| Dependency {
+            kind: DependencyKind::Package,
+            include: id.into(),
+            name: id.into(),
+            version: Some(version.into()),
+            path: None,
+            is_central_version,
+        };
+        let app = vec![
+            package("Serilog", "3.1.1", false),
+            package("Polly", "8.0.0", true),
+        ];
+        let tests = vec![
+            package("serilog", "2.12.0", false),
+            package("Polly", "7.2.4", true),
+            package("xunit", "2.9.0", false),
+        ];
+        assert_eq!(
+            version_conflicts([("App", app.as_slice()), ("Tests", tests.as_slice())]),
+            vec![VersionConflict {
+                package_id: "Serilog".into(),
+                projects: vec![
+                    ("App".into(), "3.1.1".into()),
+                    ("Tests".into(), "2.12.0".into())
+                ],
+                highest_version: "3.1.1".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_set_central_package_version() {
+        let props_path = Path::new("/repo/Directory.Packages.props");
+        let props = r#"<Project>
+  <PropertyGroup>
+    <ManagePackageVersionsCentrally>true</ManagePackageVersionsCentrally>
+  </PropertyGroup>
+  <ItemGroup>
+    <PackageVersion Include="Serilog" Version="3.0.0" />
+  </ItemGroup>
+</Project>
+"#;
+        let updated = set_central_package_version(props, props_path, "serilog", "3.1.1").unwrap();
+        let added = set_central_package_version(&updated, props_path, "Polly", "8.4.1").unwrap();
+        assert_eq!(
+            added,
+            r#"<Project>
+  <PropertyGroup>
+    <ManagePackageVersionsCentrally>true</ManagePackageVersionsCentrally>
+  </PropertyGroup>
+  <ItemGroup>
+    <PackageVersion Include="Serilog" Version="3.1.1" />
+    <PackageVersion Include="Polly" Version="8.4.1" />
+  </ItemGroup>
+</Project>
+"#
+        );
+    }
+}
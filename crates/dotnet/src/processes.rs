@@ -0,0 +1,181 @@
+//! Running .NET processes, for attaching a debugger to them.
+//!
+//! Every process hosting the CoreCLR runtime opens a diagnostic channel, unless
+//! diagnostics are disabled, and maps `libcoreclr`. A process runs either
+//! through the `dotnet` host, with the assembly as an argument, or through an
+//! apphost executable named after the assembly.
+
+use collections::HashSet;
+use std::path::Path;
+
+/// Prefix of the diagnostic channels the .NET runtime opens in every process,
+/// which `dotnet-trace ps` and `dotnet-counters ps` enumerate.
+const DIAGNOSTIC_CHANNEL_PREFIX: &str = "dotnet-diagnostic-";
+
+/// The id of the process owning a .NET diagnostic channel: a
+/// `dotnet-diagnostic-{pid}-{key}-socket` socket in the temporary directory
+/// on Unix, or a `dotnet-diagnostic-{pid}` named pipe on Windows.
+pub fn diagnostic_channel_process_id(name: &str) -> Option<u32> {
+    name.strip_prefix(DIAGNOSTIC_CHANNEL_PREFIX)?
+        .split('-')
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// Whether a `/proc/{pid}/maps` listing contains the CoreCLR runtime.
+pub fn maps_contain_coreclr(maps: &str) -> bool {
+    maps.lines().any(|line| {
+        line.split_whitespace()
+            .nth(5)
+            .is_some_and(|path| matches!(file_name(path), "libcoreclr.so" | "libcoreclr.dylib"))
+    })
+}
+
+/// The ids of the local processes running .NET, found through their
+/// diagnostic channels and, on Linux, the libraries they have mapped, which
+/// also finds processes started with diagnostics disabled.
+pub fn dotnet_process_ids() -> HashSet<u32> {
+    let channel_dir = if cfg!(windows) {
+        Path::new(r"\\.\pipe\").to_path_buf()
+    } else {
+        std::env::temp_dir()
+    };
+    let mut pids = std::fs::read_dir(channel_dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| diagnostic_channel_process_id(entry.ok()?.file_name().to_str()?))
+        .collect::<HashSet<_>>();
+
+    if cfg!(target_os = "linux") {
+        let processes = std::fs::read_dir("/proc").into_iter().flatten();
+        pids.extend(processes.filter_map(|entry| {
+            let entry = entry.ok()?;
+            let pid = entry.file_name().to_str()?.parse::<u32>().ok()?;
+            // processes of other users can't be read, nor debugged
+            let maps = std::fs::read_to_string(entry.path().join("maps")).ok()?;
+            maps_contain_coreclr(&maps).then_some(pid)
+        }));
+    }
+    pids
+}
+
+/// Whether a command line runs a .NET assembly through the `dotnet` host.
+///
+/// Used where the process itself can't be inspected, such as on remote
+/// hosts, so apphost executables are not recognized.
+pub fn is_dotnet_host_command(command: &[String]) -> bool {
+    command
+        .first()
+        .is_some_and(|program| is_dotnet_host(program))
+        && command.iter().skip(1).any(|arg| is_assembly(arg))
+}
+
+/// The name of the assembly a .NET process runs: the first assembly passed
+/// to the `dotnet` host, or the name of an apphost executable.
+pub fn assembly_name(command: &[String]) -> Option<String> {
+    let program = command.first()?;
+    if is_dotnet_host(program) {
+        let assembly = command.iter().skip(1).find(|arg| is_assembly(arg))?;
+        Some(file_stem(assembly).to_string())
+    } else {
+        Some(file_stem(program).to_string()).filter(|name| !name.is_empty())
+    }
+}
+
+fn is_dotnet_host(program: &str) -> bool {
+    file_stem(program).eq_ignore_ascii_case("dotnet")
+}
+
+fn is_assembly(arg: &str) -> bool {
+    !arg.starts_with('-')
+        && Path::new(arg)
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("dll"))
+}
+
+/// The last component of a path, whether it uses Unix or Windows separators,
+/// as command lines of remote processes may use either.
+fn file_name(path: &str) -> &str {
+    path.rsplit(['/', '\\']).next().unwrap_or(path)
+}
+
+fn file_stem(path: &str) -> &str {
+    let name = file_name(path);
+    match name.rsplit_once('.') {
+        Some((stem, extension))
+            if !stem.is_empty()
+                && ["dll", "exe"]
+                    .iter()
+                    .any(|known| extension.eq_ignore_ascii_case(known)) =>
+        {
+            stem
+        }
+        _ => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_diagnostic_channel_process_id() {
+        assert_eq!(
+            diagnostic_channel_process_id("dotnet-diagnostic-4242-1834563-socket"),
+            Some(4242)
+        );
+        assert_eq!(
+            diagnostic_channel_process_id("dotnet-diagnostic-17"),
+            Some(17)
+        );
+        assert_eq!(
+            diagnostic_channel_process_id("dotnet-diagnostic-socket"),
+            None
+        );
+        assert_eq!(
+            diagnostic_channel_process_id("clr-debug-pipe-4242-in"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_maps_contain_coreclr() {
+        let maps = "\
+55d7c8a4e000-55d7c8a56000 r--p 00000000 08:01 1048602 /usr/share/dotnet/dotnet
+7f3a2c000000-7f3a2c021000 rw-p 00000000 00:00 0
+7f3a2d1f0000-7f3a2d2c4000 r--p 00000000 08:01 1053350 /usr/share/dotnet/shared/Microsoft.NETCore.App/8.0.8/libcoreclr.so
+";
+        assert!(maps_contain_coreclr(maps));
+        assert!(!maps_contain_coreclr(
+            "7f3a2d1f0000-7f3a2d2c4000 r--p 00000000 08:01 1053350 /usr/lib/libc.so.6\n"
+        ));
+    }
+
+    #[test]
+    fn test_assembly_name() {
+        let hosted = command(&[
+            "/usr/share/dotnet/dotnet",
+            "exec",
+            "--runtimeconfig",
+            "bin/Debug/net8.0/Api.runtimeconfig.json",
+            "bin/Debug/net8.0/Api.dll",
+            "--urls",
+            "http://localhost:5000",
+        ]);
+        assert!(is_dotnet_host_command(&hosted));
+        assert_eq!(assembly_name(&hosted).as_deref(), Some("Api"));
+
+        let apphost = command(&[r"C:\src\Worker\bin\Debug\net8.0\Worker.exe"]);
+        assert!(!is_dotnet_host_command(&apphost));
+        assert_eq!(assembly_name(&apphost).as_deref(), Some("Worker"));
+
+        let sdk = command(&["dotnet", "build"]);
+        assert!(!is_dotnet_host_command(&sdk));
+        assert_eq!(assembly_name(&sdk), None);
+    }
+}
@@ -0,0 +1,629 @@
+//! The files of a project, arranged as Visual Studio shows them.
+//!
+//! Files are the items of a project evaluated with its wildcards expanded, so
+//! `Remove` and `Exclude` patterns apply as they do in a build. Files outside
+//! of the project directory show where their `Link` metadata puts them, and
+//! related files nest under each other: `Foo.Designer.cs` under `Foo.cs`,
+//! `appsettings.Development.json` under `appsettings.json` and
+//! `Index.razor.cs` under `Index.razor`.
+
+use crate::msbuild::{ProjectModel, relative_path, resolve_path};
+use anyhow::{Context as _, Result};
+use collections::{BTreeMap, HashMap, HashSet};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// Item types of the files of a project, in the order they take precedence
+/// when a file is included as several of them.
+const FILE_ITEM_TYPES: &[&str] = &[
+    "Compile",
+    "Content",
+    "EmbeddedResource",
+    "None",
+    "AdditionalFiles",
+    "Page",
+    "ApplicationDefinition",
+    "Resource",
+];
+
+/// Extensions of files that nest under a file with the same name and another
+/// extension, such as `site.css` under `site.scss`.
+const GENERATED_EXTENSIONS: &[(&str, &[&str])] = &[
+    ("js", &["ts", "tsx", "coffee"]),
+    ("css", &["scss", "sass", "less"]),
+];
+
+/// An item of a project that is a file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectFile {
+    /// Absolute path of the file.
+    pub path: PathBuf,
+    /// Where the file shows in the project, relative to the project directory.
+    pub display_path: PathBuf,
+    pub item_type: String,
+    /// Whether the file is outside of the project directory.
+    pub is_linked: bool,
+    /// The file this one nests under, from its `DependentUpon` metadata.
+    pub dependent_upon: Option<PathBuf>,
+}
+
+impl ProjectFile {
+    pub fn name(&self) -> String {
+        file_name(&self.display_path)
+    }
+}
+
+/// A folder or file of a project, with its children.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProjectItemNode {
+    Folder {
+        /// Relative to the project directory.
+        path: PathBuf,
+        children: Vec<ProjectItemNode>,
+    },
+    /// A file with the files nested under it.
+    File {
+        file: ProjectFile,
+        children: Vec<ProjectItemNode>,
+    },
+}
+
+impl ProjectItemNode {
+    pub fn name(&self) -> String {
+        match self {
+            Self::Folder { path, .. } => file_name(path),
+            Self::File { file, .. } => file.name(),
+        }
+    }
+
+    pub fn children(&self) -> &[ProjectItemNode] {
+        match self {
+            Self::Folder { children, .. } | Self::File { children, .. } => children,
+        }
+    }
+}
+
+/// The files of a project evaluated with
+/// [`EvaluationOptions::expand_wildcards`](crate::EvaluationOptions::expand_wildcards).
+pub fn project_files(project: &ProjectModel) -> Vec<ProjectFile> {
+    let directory = project.directory();
+    let mut seen = HashSet::default();
+    let mut files = Vec::new();
+    for item_type in FILE_ITEM_TYPES {
+        for item in project.items_of_type(item_type) {
+            let path = resolve_path(directory, &item.include);
+            if !seen.insert(path.clone()) {
+                continue;
+            }
+            let is_linked = !path.starts_with(directory);
+            let name = file_name(&path);
+            let display_path = match item.metadata("Link") {
+                Some(link) => resolve_path(Path::new(""), link),
+                None if is_linked => {
+                    let link_base = item.metadata("LinkBase").unwrap_or_default();
+                    let recursive_dir = item.metadata("RecursiveDir").unwrap_or_default();
+                    resolve_path(
+                        Path::new(""),
+                        &format!("{link_base}/{recursive_dir}/{name}"),
+                    )
+                }
+                None => relative_path(directory, &path),
+            };
+            let dependent_upon = item.metadata("DependentUpon").map(|dependent_upon| {
+                resolve_path(path.parent().unwrap_or(directory), dependent_upon)
+            });
+            files.push(ProjectFile {
+                path,
+                display_path,
+                item_type: item.item_type.clone(),
+                is_linked,
+                dependent_upon,
+            });
+        }
+    }
+    files
+}
+
+/// The folders and files of a project, with related files nested, sorted with
+/// folders first.
+pub fn project_item_tree(project: &ProjectModel) -> Vec<ProjectItemNode> {
+    let folders = project
+        .items_of_type("Folder")
+        .map(|item| resolve_path(Path::new(""), &item.include))
+        .collect::<Vec<_>>();
+    item_tree(project_files(project), &folders)
+}
+
+#[derive(Default)]
+struct FolderBuilder {
+    /// Keyed by lowercase name
+    folders: BTreeMap<String, (PathBuf, FolderBuilder)>,
+    files: Vec<usize>,
+}
+
+impl FolderBuilder {
+    fn folder(&mut self, path: &Path) -> &mut FolderBuilder {
+        let mut folder = self;
+        let mut folder_path = PathBuf::new();
+        for component in path.components() {
+            folder_path.push(component);
+            let key = component.as_os_str().to_string_lossy().to_lowercase();
+            folder = &mut folder
+                .folders
+                .entry(key)
+                .or_insert_with(|| (folder_path.clone(), FolderBuilder::default()))
+                .1;
+        }
+        folder
+    }
+
+    fn build(
+        self,
+        files: &[ProjectFile],
+        nested: &HashMap<usize, Vec<usize>>,
+    ) -> Vec<ProjectItemNode> {
+        let mut nodes = self
+            .folders
+            .into_values()
+            .map(|(path, folder)| ProjectItemNode::Folder {
+                path,
+                children: folder.build(files, nested),
+            })
+            .collect::<Vec<_>>();
+        nodes.extend(file_nodes(self.files, files, nested));
+        nodes
+    }
+}
+
+fn file_nodes(
+    mut indices: Vec<usize>,
+    files: &[ProjectFile],
+    nested: &HashMap<usize, Vec<usize>>,
+) -> Vec<ProjectItemNode> {
+    indices.sort_by_key(|&ix| files[ix].name().to_lowercase());
+    indices
+        .into_iter()
+        .map(|ix| ProjectItemNode::File {
+            file: files[ix].clone(),
+            children: file_nodes(nested.get(&ix).cloned().unwrap_or_default(), files, nested),
+        })
+        .collect()
+}
+
+fn item_tree(files: Vec<ProjectFile>, folders: &[PathBuf]) -> Vec<ProjectItemNode> {
+    // Files by folder and lowercase name, to find the files others nest under
+    let mut by_name = HashMap::default();
+    for (ix, file) in files.iter().enumerate() {
+        let folder = file.display_path.parent().unwrap_or(Path::new(""));
+        by_name.insert((folder.to_path_buf(), file.name().to_lowercase()), ix);
+    }
+    let mut parents = HashMap::default();
+    for (ix, file) in files.iter().enumerate() {
+        let folder = file.display_path.parent().unwrap_or(Path::new(""));
+        let parent = match &file.dependent_upon {
+            Some(dependent_upon) => files.iter().position(|file| file.path == *dependent_upon),
+            None => nesting_parent(&file.name(), |name| {
+                by_name.contains_key(&(folder.to_path_buf(), name.to_lowercase()))
+            })
+            .and_then(|name| {
+                by_name
+                    .get(&(folder.to_path_buf(), name.to_lowercase()))
+                    .copied()
+            }),
+        };
+        let Some(parent) = parent else {
+            continue;
+        };
+        // Skip parents that would nest under this file, such as with `DependentUpon` cycles
+        let mut ancestor = Some(parent);
+        while let Some(current) = ancestor {
+            if current == ix {
+                break;
+            }
+            ancestor = parents.get(&current).copied();
+        }
+        if ancestor.is_none() {
+            parents.insert(ix, parent);
+        }
+    }
+
+    let mut root = FolderBuilder::default();
+    for folder in folders {
+        root.folder(folder);
+    }
+    let mut nested = HashMap::<usize, Vec<usize>>::default();
+    for (ix, file) in files.iter().enumerate() {
+        match parents.get(&ix) {
+            Some(parent) => nested.entry(*parent).or_default().push(ix),
+            None => {
+                let folder = file.display_path.parent().unwrap_or(Path::new(""));
+                root.folder(folder).files.push(ix);
+            }
+        }
+    }
+    root.build(&files, &nested)
+}
+
+/// The name of the file that a file named `name` nests under, given which
+/// names the other files of its folder have, following the default file
+/// nesting rules of Visual Studio.
+pub fn nesting_parent(name: &str, exists: impl Fn(&str) -> bool) -> Option<String> {
+    let parts = name.split('.').collect::<Vec<_>>();
+    if parts.len() < 2 || parts[0].is_empty() {
+        return None;
+    }
+    // An added extension: `Index.razor.cs` under `Index.razor`
+    let without_extension = parts[..parts.len() - 1].join(".");
+    if exists(&without_extension) {
+        return Some(without_extension);
+    }
+    // A path segment: `appsettings.Development.json` under `appsettings.json`
+    let extension = parts[parts.len() - 1];
+    for end in (1..parts.len() - 1).rev() {
+        let candidate = format!("{}.{extension}", parts[..end].join("."));
+        if exists(&candidate) {
+            return Some(candidate);
+        }
+    }
+    // Another extension: `site.css` under `site.scss`
+    let (_, sources) = GENERATED_EXTENSIONS
+        .iter()
+        .find(|(generated, _)| generated.eq_ignore_ascii_case(extension))?;
+    sources
+        .iter()
+        .map(|source| format!("{without_extension}.{source}"))
+        .find(|candidate| exists(candidate))
+}
+
+/// The new name of a file nested under a renamed file, keeping what it adds
+/// to the name of its parent: when `Index.razor` becomes `Home.razor`,
+/// `Index.razor.cs` becomes `Home.razor.cs`, and when `appsettings.json`
+/// becomes `settings.json`, `appsettings.Development.json` becomes
+/// `settings.Development.json`.
+pub fn renamed_nested_file(old_parent: &str, new_parent: &str, name: &str) -> Option<String> {
+    if let Some(rest) = strip_prefix_ignore_case(name, old_parent)
+        && rest.starts_with('.')
+    {
+        return Some(format!("{new_parent}{rest}"));
+    }
+    let (old_stem, _) = old_parent.rsplit_once('.')?;
+    let (new_stem, _) = new_parent.rsplit_once('.')?;
+    let rest = strip_prefix_ignore_case(name, old_stem)?;
+    rest.starts_with('.').then(|| format!("{new_stem}{rest}"))
+}
+
+/// Updates the items of a project file that refer to renamed or moved files
+/// and folders, given as absolute paths, such as
+/// `<None Update="appsettings.json" />` and `DependentUpon` metadata.
+/// Items with wildcards are left alone.
+pub fn rename_item_references(
+    content: &str,
+    project_directory: &Path,
+    renames: &[(PathBuf, PathBuf)],
+) -> Result<String> {
+    let document = roxmltree::Document::parse(content).context("parsing project file")?;
+    let renamed = |path: &Path| {
+        renames.iter().find_map(|(old_path, new_path)| {
+            let rest = path.strip_prefix(old_path).ok()?;
+            Some(if rest.as_os_str().is_empty() {
+                new_path.clone()
+            } else {
+                new_path.join(rest)
+            })
+        })
+    };
+    // Keeps the separators of the project file
+    let item_spec = |directory: &Path, path: &Path, value: &str| {
+        let relative = relative_path(directory, path)
+            .to_string_lossy()
+            .into_owned();
+        if value.contains('\\') {
+            relative.replace('/', "\\")
+        } else {
+            relative.replace('\\', "/")
+        }
+    };
+    let is_path = |value: &str| !value.contains(['*', '?', '$', '@', '%', ';']);
+
+    let mut edits: Vec<(Range<usize>, String)> = Vec::new();
+    let items = document
+        .descendants()
+        .filter(|node| node.has_tag_name("ItemGroup"))
+        .flat_map(|group| group.children().filter(|item| item.is_element()));
+    for item in items {
+        let mut item_path = None;
+        for attribute in item.attributes() {
+            if !matches!(attribute.name(), "Include" | "Update" | "Remove")
+                || !is_path(attribute.value())
+            {
+                continue;
+            }
+            let path = resolve_path(project_directory, attribute.value());
+            if let Some(new_path) = renamed(&path) {
+                let value = item_spec(project_directory, &new_path, attribute.value());
+                edits.push((attribute.range_value(), value));
+            }
+            item_path.get_or_insert(path);
+        }
+
+        // `DependentUpon` is relative to the folder of the item, which may move too
+        let Some(item_path) = item_path else {
+            continue;
+        };
+        let directory = item_path.parent().unwrap_or(project_directory);
+        let new_item_path = renamed(&item_path).unwrap_or_else(|| item_path.clone());
+        let new_directory = new_item_path.parent().unwrap_or(project_directory);
+        let dependent_upon = item
+            .attributes()
+            .filter(|attribute| attribute.name() == "DependentUpon")
+            .map(|attribute| (attribute.range_value(), attribute.value()))
+            .chain(
+                item.children()
+                    .filter(|child| child.has_tag_name("DependentUpon"))
+                    .filter_map(|metadata| metadata.first_child())
+                    .filter(|text| text.is_text())
+                    .map(|text| (text.range(), text.text().unwrap_or_default())),
+            );
+        for (range, value) in dependent_upon {
+            let value = value.trim();
+            if !is_path(value) {
+                continue;
+            }
+            let path = resolve_path(directory, value);
+            let new_path = renamed(&path).unwrap_or(path);
+            let new_value = item_spec(new_directory, &new_path, value);
+            if new_value != value {
+                edits.push((range, new_value));
+            }
+        }
+    }
+
+    let mut content = content.to_string();
+    edits.sort_by_key(|(range, _)| range.start);
+    for (range, value) in edits.into_iter().rev() {
+        content.replace_range(range, &crate::xml_edit::escape(&value));
+    }
+    Ok(content)
+}
+
+fn strip_prefix_ignore_case<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = text.get(..prefix.len())?;
+    head.eq_ignore_ascii_case(prefix)
+        .then(|| &text[prefix.len()..])
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn file(path: &str, display_path: &str) -> ProjectFile {
+        ProjectFile {
+            path: PathBuf::from(path),
+            display_path: PathBuf::from(display_path),
+            item_type: "Compile".to_string(),
+            is_linked: !path.starts_with("/src/App/"),
+            dependent_upon: None,
+        }
+    }
+
+    /// Renders a tree as indented names, folders ending with `/`.
+    fn outline(nodes: &[ProjectItemNode], depth: usize, lines: &mut Vec<String>) {
+        for node in nodes {
+            let suffix = if matches!(node, ProjectItemNode::Folder { .. }) {
+                "/"
+            } else {
+                ""
+            };
+            lines.push(format!("{}{}{suffix}", "  ".repeat(depth), node.name()));
+            outline(node.children(), depth + 1, lines);
+        }
+    }
+
+    #[test]
+    fn test_nesting_parent() {
+        let names = [
+            "Foo.cs",
+            "Index.razor",
+            "appsettings.json",
+            "site.scss",
+            "app.js",
+        ];
+        let exists = |name: &str| names.iter().any(|known| known.eq_ignore_ascii_case(name));
+
+        assert_eq!(
+            nesting_parent("Foo.Designer.cs", exists).as_deref(),
+            Some("Foo.cs")
+        );
+        assert_eq!(
+            nesting_parent("Index.razor.cs", exists).as_deref(),
+            Some("Index.razor")
+        );
+        assert_eq!(
+            nesting_parent("Index.razor.css", exists).as_deref(),
+            Some("Index.razor")
+        );
+        assert_eq!(
+            nesting_parent("appsettings.Development.json", exists).as_deref(),
+            Some("appsettings.json")
+        );
+        assert_eq!(
+            nesting_parent("site.css", exists).as_deref(),
+            Some("site.scss")
+        );
+        assert_eq!(
+            nesting_parent("app.min.js", exists).as_deref(),
+            Some("app.js")
+        );
+        assert_eq!(
+            nesting_parent("app.js.map", exists).as_deref(),
+            Some("app.js")
+        );
+        assert_eq!(nesting_parent("Foo.cs", exists), None);
+        assert_eq!(nesting_parent("Bar.Designer.cs", exists), None);
+        assert_eq!(nesting_parent(".editorconfig", exists), None);
+    }
+
+    #[test]
+    fn test_item_tree() {
+        let mut designer = file("/src/App/Forms/Main.Designer.cs", "Forms/Main.Designer.cs");
+        designer.dependent_upon = None;
+        let mut resources = file("/src/App/Forms/Strings.resx", "Forms/Strings.resx");
+        resources.dependent_upon = Some(PathBuf::from("/src/App/Forms/Main.cs"));
+        let files = vec![
+            file("/src/App/Program.cs", "Program.cs"),
+            file("/src/App/appsettings.json", "appsettings.json"),
+            file(
+                "/src/App/appsettings.Development.json",
+                "appsettings.Development.json",
+            ),
+            file("/src/App/Forms/Main.cs", "Forms/Main.cs"),
+            designer,
+            resources,
+            file("/src/Shared/Models/User.cs", "Shared/Models/User.cs"),
+        ];
+        let tree = item_tree(files, &[PathBuf::from("Migrations")]);
+
+        let mut lines = Vec::new();
+        outline(&tree, 0, &mut lines);
+        assert_eq!(
+            lines,
+            [
+                "Forms/",
+                "  Main.cs",
+                "    Main.Designer.cs",
+                "    Strings.resx",
+                "Migrations/",
+                "Shared/",
+                "  Models/",
+                "    User.cs",
+                "appsettings.json",
+                "  appsettings.Development.json",
+                "Program.cs",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_project_files_with_links() {
+        let directory = tempfile::tempdir().unwrap();
+        let root = directory.path();
+        std::fs::create_dir_all(root.join("App/Views")).unwrap();
+        std::fs::create_dir_all(root.join("Shared/Models")).unwrap();
+        std::fs::write(
+            root.join("App/App.csproj"),
+            r#"<Project Sdk="Microsoft.NET.Sdk">
+                <ItemGroup>
+                    <Compile Include="..\Shared\Models\*.cs" LinkBase="Shared" />
+                    <Compile Include="..\Shared\Version.cs" Link="Properties\Version.cs" />
+                    <Compile Update="Views\Main.Generated.cs" DependentUpon="Main.cs" />
+                </ItemGroup>
+            </Project>"#,
+        )
+        .unwrap();
+        for file in [
+            "App/Views/Main.cs",
+            "App/Views/Main.Generated.cs",
+            "Shared/Models/User.cs",
+            "Shared/Version.cs",
+        ] {
+            std::fs::write(root.join(file), "").unwrap();
+        }
+        let options = crate::EvaluationOptions {
+            expand_wildcards: true,
+            ..crate::EvaluationOptions::default()
+        };
+        let project = ProjectModel::load(
+            &root.join("App/App.csproj"),
+            &options,
+            &crate::RealProjectFiles,
+        )
+        .unwrap();
+
+        let files = project_files(&project)
+            .into_iter()
+            .map(|file| {
+                (
+                    file.display_path,
+                    file.is_linked,
+                    file.dependent_upon.is_some(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            files,
+            vec![
+                (PathBuf::from("Views/Main.Generated.cs"), false, true),
+                (PathBuf::from("Views/Main.cs"), false, false),
+                (PathBuf::from("Shared/User.cs"), true, false),
+                (PathBuf::from("Properties/Version.cs"), true, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_renames() {
+        assert_eq!(
+            renamed_nested_file("Index.razor", "Home.razor", "Index.razor.cs").as_deref(),
+            Some("Home.razor.cs")
+        );
+        assert_eq!(
+            renamed_nested_file(
+                "appsettings.json",
+                "settings.json",
+                "appsettings.Development.json"
+            )
+            .as_deref(),
+            Some("settings.Development.json")
+        );
+        assert_eq!(
+            renamed_nested_file("Main.cs", "Shell.cs", "Strings.resx"),
+            None
+        );
+
+        let content = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <ItemGroup>
+    <None Update="appsettings.json" CopyToOutputDirectory="PreserveNewest" />
+    <Compile Update="Forms\Main.Designer.cs">
+      <DependentUpon>Main.cs</DependentUpon>
+    </Compile>
+    <Compile Remove="Forms\**" />
+  </ItemGroup>
+</Project>
+"#;
+        let renamed = rename_item_references(
+            content,
+            Path::new("/src/App"),
+            &[
+                (
+                    PathBuf::from("/src/App/appsettings.json"),
+                    PathBuf::from("/src/App/settings.json"),
+                ),
+                (
+                    PathBuf::from("/src/App/Forms/Main.cs"),
+                    PathBuf::from("/src/App/Main.cs"),
+                ),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            renamed,
+            r#"<Project Sdk="Microsoft.NET.Sdk">
+  <ItemGroup>
+    <None Update="settings.json" CopyToOutputDirectory="PreserveNewest" />
+    <Compile Update="Forms\Main.Designer.cs">
+      <DependentUpon>../Main.cs</DependentUpon>
+    </Compile>
+    <Compile Remove="Forms\**" />
+  </ItemGroup>
+</Project>
+"#
+        );
+    }
+}
@@ -0,0 +1,1911 @@
+//! Reading and editing of `.sln` and `.slnx` solution files.
+//!
+//! Both formats are parsed into the same [`SolutionFile`] model. Edits are
+//! applied to the original text and the model is re-parsed afterwards, so
+//! that saving a solution only changes the lines touched by the edit.
+
+use crate::msbuild::{EvaluationOptions, relative_path, resolve_path};
+use anyhow::{Context as _, Result, anyhow, bail};
+use std::{
+    fmt,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+/// Project type GUID of solution folders.
+pub const SOLUTION_FOLDER_TYPE_GUID: &str = "2150E333-8FDC-42A3-9474-1A3956D46DE8";
+/// Project type GUID written by `dotnet sln add` for C# projects.
+pub const CSHARP_PROJECT_TYPE_GUID: &str = "FAE04EC0-301F-11D3-BA7A-00C04FC2CCAE";
+/// Project type GUID written by `dotnet sln add` for F# projects.
+pub const FSHARP_PROJECT_TYPE_GUID: &str = "F2A71F9B-5D33-465A-A702-920D77279786";
+/// Project type GUID written by `dotnet sln add` for Visual Basic projects.
+pub const VISUAL_BASIC_PROJECT_TYPE_GUID: &str = "F184B08F-C81C-45F6-A57F-5ABD9991F28F";
+
+const BOM: char = '\u{feff}';
+const ANY_CPU: &str = "Any CPU";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolutionFormat {
+    /// The classic text format.
+    Sln,
+    /// The XML format introduced with Visual Studio 17.10.
+    Slnx,
+}
+
+/// A `Configuration|Platform` pair, e.g. `Debug|Any CPU`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SolutionConfiguration {
+    pub configuration: String,
+    pub platform: String,
+}
+
+impl SolutionConfiguration {
+    pub fn new(configuration: impl Into<String>, platform: impl Into<String>) -> Self {
+        Self {
+            configuration: configuration.into(),
+            platform: platform.into(),
+        }
+    }
+
+    /// Parses a `Configuration|Platform` pair.
+    pub fn parse(value: &str) -> Option<Self> {
+        let (configuration, platform) = value.trim().split_once('|')?;
+        Some(Self::new(configuration.trim(), platform.trim()))
+    }
+
+    /// The platform as MSBuild expects it, i.e. `AnyCPU` instead of `Any CPU`.
+    pub fn msbuild_platform(&self) -> &str {
+        if self.platform.eq_ignore_ascii_case(ANY_CPU) {
+            "AnyCPU"
+        } else {
+            &self.platform
+        }
+    }
+
+    fn matches(&self, other: &Self) -> bool {
+        self.configuration
+            .eq_ignore_ascii_case(&other.configuration)
+            && self.platform.eq_ignore_ascii_case(&other.platform)
+    }
+}
+
+impl fmt::Display for SolutionConfiguration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}|{}", self.configuration, self.platform)
+    }
+}
+
+/// How a project is built in a given solution configuration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectConfigurationMapping {
+    pub solution: SolutionConfiguration,
+    pub project: SolutionConfiguration,
+    pub build: bool,
+    pub deploy: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolutionProject {
+    /// The project GUID in `.sln` files. In `.slnx` files, the `Id` attribute
+    /// if present and the project path otherwise.
+    pub id: String,
+    pub name: String,
+    /// Path to the project file relative to the solution directory, using `/` separators.
+    pub path: PathBuf,
+    pub type_guid: Option<String>,
+    /// The id of the solution folder containing this project.
+    pub parent: Option<String>,
+    pub configurations: Vec<ProjectConfigurationMapping>,
+    /// Ids of projects that must be built before this one.
+    pub dependencies: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolutionFolder {
+    /// The folder GUID in `.sln` files, the full folder path (`/src/tests/`) in `.slnx` files.
+    pub id: String,
+    pub name: String,
+    /// The id of the solution folder containing this folder.
+    pub parent: Option<String>,
+    /// Solution items (loose files) in this folder, relative to the solution directory.
+    pub items: Vec<PathBuf>,
+}
+
+/// A parsed `.sln` or `.slnx` file.
+#[derive(Debug, Clone)]
+pub struct SolutionFile {
+    /// Absolute path to the solution file.
+    pub path: PathBuf,
+    pub format: SolutionFormat,
+    pub projects: Vec<SolutionProject>,
+    pub folders: Vec<SolutionFolder>,
+    pub configurations: Vec<SolutionConfiguration>,
+    source: Source,
+}
+
+#[derive(Debug, Clone)]
+struct Source {
+    bom: bool,
+    text: String,
+}
+
+struct ParsedSolution {
+    projects: Vec<SolutionProject>,
+    folders: Vec<SolutionFolder>,
+    configurations: Vec<SolutionConfiguration>,
+}
+
+impl SolutionFile {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("reading solution file {}", path.display()))?;
+        Self::parse(path, &content)
+    }
+
+    /// Parses a solution file. The format is inferred from the content.
+    pub fn parse(path: &Path, content: &str) -> Result<Self> {
+        let bom = content.starts_with(BOM);
+        let text = content.trim_start_matches(BOM);
+        let format = if text.trim_start().starts_with('<') {
+            SolutionFormat::Slnx
+        } else {
+            SolutionFormat::Sln
+        };
+        let mut solution = Self {
+            path: path.to_path_buf(),
+            format,
+            projects: Vec::new(),
+            folders: Vec::new(),
+            configurations: Vec::new(),
+            source: Source {
+                bom,
+                text: text.to_string(),
+            },
+        };
+        solution.reparse()?;
+        Ok(solution)
+    }
+
+    fn reparse(&mut self) -> Result<()> {
+        let parsed = match self.format {
+            SolutionFormat::Sln => sln::parse(&self.source.text),
+            SolutionFormat::Slnx => slnx::parse(&self.source.text),
+        }
+        .with_context(|| format!("parsing solution file {}", self.path.display()))?;
+        self.projects = parsed.projects;
+        self.folders = parsed.folders;
+        self.configurations = parsed.configurations;
+        Ok(())
+    }
+
+    /// The solution file contents, including any edits.
+    pub fn to_text(&self) -> String {
+        let mut text = String::with_capacity(self.source.text.len() + BOM.len_utf8());
+        if self.source.bom {
+            text.push(BOM);
+        }
+        text.push_str(&self.source.text);
+        text
+    }
+
+    pub fn directory(&self) -> &Path {
+        self.path.parent().unwrap_or(Path::new(""))
+    }
+
+    pub fn name(&self) -> &str {
+        self.path
+            .file_stem()
+            .and_then(|name| name.to_str())
+            .unwrap_or("Solution")
+    }
+
+    /// Absolute path to a project file.
+    pub fn project_abs_path(&self, project: &SolutionProject) -> PathBuf {
+        resolve_path(self.directory(), &project.path.to_string_lossy())
+    }
+
+    /// Expresses an absolute path relative to the solution directory, the way
+    /// paths are stored in the solution file.
+    pub fn relative_path(&self, path: &Path) -> PathBuf {
+        relative_path(self.directory(), path)
+    }
+
+    pub fn project(&self, name: &str) -> Option<&SolutionProject> {
+        self.projects.iter().find(|project| project.name == name)
+    }
+
+    pub fn project_by_id(&self, id: &str) -> Option<&SolutionProject> {
+        self.projects
+            .iter()
+            .find(|project| project.id.eq_ignore_ascii_case(id))
+    }
+
+    /// Finds a project by its path relative to the solution directory.
+    pub fn project_by_path(&self, path: &Path) -> Option<&SolutionProject> {
+        let path = normalize_separators(&path.to_string_lossy());
+        self.projects
+            .iter()
+            .find(|project| project.path.to_string_lossy().eq_ignore_ascii_case(&path))
+    }
+
+    pub fn folder_by_id(&self, id: &str) -> Option<&SolutionFolder> {
+        self.folders
+            .iter()
+            .find(|folder| folder.id.eq_ignore_ascii_case(id))
+    }
+
+    /// Folders directly inside `parent`, or at the root when `parent` is `None`.
+    pub fn child_folders<'a>(
+        &'a self,
+        parent: Option<&'a str>,
+    ) -> impl Iterator<Item = &'a SolutionFolder> {
+        self.folders
+            .iter()
+            .filter(move |folder| same_id(folder.parent.as_deref(), parent))
+    }
+
+    /// Projects directly inside `parent`, or at the root when `parent` is `None`.
+    pub fn child_projects<'a>(
+        &'a self,
+        parent: Option<&'a str>,
+    ) -> impl Iterator<Item = &'a SolutionProject> {
+        self.projects
+            .iter()
+            .filter(move |project| same_id(project.parent.as_deref(), parent))
+    }
+
+    /// How `project_id` is built in the given solution configuration, if at all.
+    pub fn project_configuration(
+        &self,
+        project_id: &str,
+        configuration: &SolutionConfiguration,
+    ) -> Option<&ProjectConfigurationMapping> {
+        self.project_by_id(project_id)?
+            .configurations
+            .iter()
+            .find(|mapping| mapping.solution.matches(configuration))
+    }
+
+    /// The configuration selected when none was chosen yet: the first `Debug`
+    /// configuration, as Visual Studio does for a freshly opened solution.
+    pub fn default_configuration(&self) -> Option<&SolutionConfiguration> {
+        self.configurations
+            .iter()
+            .find(|configuration| configuration.configuration.eq_ignore_ascii_case("Debug"))
+            .or_else(|| self.configurations.first())
+    }
+
+    /// How to evaluate a project when the solution is built in `configuration`.
+    ///
+    /// Projects without a mapping for the configuration use its name and their
+    /// default platform.
+    pub fn evaluation_options(
+        &self,
+        project: &SolutionProject,
+        configuration: &SolutionConfiguration,
+    ) -> EvaluationOptions {
+        match self.project_configuration(&project.id, configuration) {
+            Some(mapping) => EvaluationOptions {
+                configuration: mapping.project.configuration.clone(),
+                platform: Some(mapping.project.msbuild_platform().to_string()),
+                ..EvaluationOptions::default()
+            },
+            None => EvaluationOptions {
+                configuration: configuration.configuration.clone(),
+                ..EvaluationOptions::default()
+            },
+        }
+    }
+
+    /// Adds an existing project file, given relative to the solution directory.
+    /// Returns the id of the new project.
+    pub fn add_project(&mut self, relative_path: &Path, parent: Option<&str>) -> Result<String> {
+        let path = normalize_separators(&relative_path.to_string_lossy());
+        if self
+            .projects
+            .iter()
+            .any(|project| project.path.to_string_lossy().eq_ignore_ascii_case(&path))
+        {
+            bail!("{path} is already part of the solution");
+        }
+        let parent = self.existing_folder_id(parent)?;
+        let id = match self.format {
+            SolutionFormat::Sln => sln::add_project(self, &path, parent.as_deref()),
+            SolutionFormat::Slnx => slnx::add_project(self, &path, parent.as_deref()),
+        }?;
+        self.reparse()?;
+        Ok(id)
+    }
+
+    /// Removes a project from the solution. The project file itself is left untouched.
+    pub fn remove_project(&mut self, id: &str) -> Result<()> {
+        let project = self
+            .project_by_id(id)
+            .cloned()
+            .with_context(|| format!("no project with id {id}"))?;
+        match self.format {
+            SolutionFormat::Sln => sln::remove_project(self, &project),
+            SolutionFormat::Slnx => slnx::remove_project(self, &project),
+        }?;
+        self.reparse()
+    }
+
+    /// Changes the name and path of a project, e.g. after its file was renamed.
+    pub fn rename_project(&mut self, id: &str, name: &str, relative_path: &Path) -> Result<()> {
+        let project = self
+            .project_by_id(id)
+            .cloned()
+            .with_context(|| format!("no project with id {id}"))?;
+        let path = normalize_separators(&relative_path.to_string_lossy());
+        match self.format {
+            SolutionFormat::Sln => sln::rename_project(self, &project, name, &path),
+            SolutionFormat::Slnx => slnx::rename_project(self, &project, &path),
+        }?;
+        self.reparse()
+    }
+
+    /// Adds a solution folder. Returns the id of the new folder.
+    pub fn add_folder(&mut self, name: &str, parent: Option<&str>) -> Result<String> {
+        let name = name.trim();
+        if name.is_empty() || name.contains(['/', '\\']) {
+            bail!("invalid solution folder name {name:?}");
+        }
+        let parent = self.existing_folder_id(parent)?;
+        if self
+            .child_folders(parent.as_deref())
+            .any(|folder| folder.name.eq_ignore_ascii_case(name))
+        {
+            bail!("a solution folder named {name} already exists");
+        }
+        let id = match self.format {
+            SolutionFormat::Sln => sln::add_folder(self, name, parent.as_deref()),
+            SolutionFormat::Slnx => slnx::add_folder(self, name, parent.as_deref()),
+        }?;
+        self.reparse()?;
+        Ok(id)
+    }
+
+    /// Removes a solution folder together with everything nested in it.
+    pub fn remove_folder(&mut self, id: &str) -> Result<()> {
+        let folder = self
+            .folder_by_id(id)
+            .cloned()
+            .with_context(|| format!("no solution folder with id {id}"))?;
+        match self.format {
+            SolutionFormat::Sln => sln::remove_folder(self, &folder),
+            SolutionFormat::Slnx => slnx::remove_folder(self, &folder),
+        }?;
+        self.reparse()
+    }
+
+    pub fn rename_folder(&mut self, id: &str, name: &str) -> Result<()> {
+        let folder = self
+            .folder_by_id(id)
+            .cloned()
+            .with_context(|| format!("no solution folder with id {id}"))?;
+        let name = name.trim();
+        if name.is_empty() || name.contains(['/', '\\']) {
+            bail!("invalid solution folder name {name:?}");
+        }
+        match self.format {
+            SolutionFormat::Sln => sln::rename_folder(self, &folder, name),
+            SolutionFormat::Slnx => slnx::rename_folder(self, &folder, name),
+        }?;
+        self.reparse()
+    }
+
+    /// Moves a project or solution folder into `parent`, or to the root when `parent` is `None`.
+    pub fn move_item(&mut self, id: &str, parent: Option<&str>) -> Result<()> {
+        let parent = self.existing_folder_id(parent)?;
+        if let Some(parent) = parent.as_deref()
+            && (parent.eq_ignore_ascii_case(id) || self.is_descendant_folder(parent, id))
+        {
+            bail!("cannot move a solution folder into itself");
+        }
+        match self.format {
+            SolutionFormat::Sln => sln::move_item(self, id, parent.as_deref()),
+            SolutionFormat::Slnx => slnx::move_item(self, id, parent.as_deref()),
+        }?;
+        self.reparse()
+    }
+
+    fn existing_folder_id(&self, id: Option<&str>) -> Result<Option<String>> {
+        id.map(|id| {
+            self.folder_by_id(id)
+                .map(|folder| folder.id.clone())
+                .with_context(|| format!("no solution folder with id {id}"))
+        })
+        .transpose()
+    }
+
+    /// Whether `folder` is nested (at any depth) inside `ancestor`.
+    fn is_descendant_folder(&self, folder: &str, ancestor: &str) -> bool {
+        let mut current = self.folder_by_id(folder).and_then(|f| f.parent.clone());
+        while let Some(id) = current {
+            if id.eq_ignore_ascii_case(ancestor) {
+                return true;
+            }
+            current = self.folder_by_id(&id).and_then(|f| f.parent.clone());
+        }
+        false
+    }
+
+    /// Ids of all folders and projects nested (at any depth) inside `folder`.
+    fn descendants(&self, folder: &str) -> (Vec<SolutionFolder>, Vec<SolutionProject>) {
+        let folders = self
+            .folders
+            .iter()
+            .filter(|candidate| self.is_descendant_folder(&candidate.id, folder))
+            .cloned()
+            .collect::<Vec<_>>();
+        let projects = self
+            .projects
+            .iter()
+            .filter(|project| {
+                project.parent.as_deref().is_some_and(|parent| {
+                    parent.eq_ignore_ascii_case(folder) || self.is_descendant_folder(parent, folder)
+                })
+            })
+            .cloned()
+            .collect();
+        (folders, projects)
+    }
+}
+
+fn same_id(a: Option<&str>, b: Option<&str>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+fn normalize_separators(path: &str) -> String {
+    path.replace('\\', "/")
+}
+
+fn project_type_guid(path: &str) -> &'static str {
+    let extension = Path::new(path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    match extension.as_str() {
+        "fsproj" => FSHARP_PROJECT_TYPE_GUID,
+        "vbproj" => VISUAL_BASIC_PROJECT_TYPE_GUID,
+        _ => CSHARP_PROJECT_TYPE_GUID,
+    }
+}
+
+fn file_stem(path: &str) -> String {
+    Path::new(path)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn new_guid() -> String {
+    uuid::Uuid::new_v4().to_string().to_ascii_uppercase()
+}
+
+/// Replaces `range` in `text` with `replacement`.
+fn splice(text: &mut String, range: Range<usize>, replacement: &str) {
+    text.replace_range(range, replacement);
+}
+
+mod sln {
+    use super::*;
+
+    /// A `Project(...) = ...` / `EndProject` block.
+    struct ProjectBlock {
+        lines: Range<usize>,
+        type_guid: String,
+        name: String,
+        path: String,
+        id: String,
+    }
+
+    /// A `GlobalSection(...)` / `EndGlobalSection` block.
+    struct GlobalSection {
+        name: String,
+        lines: Range<usize>,
+    }
+
+    struct Lines<'a> {
+        lines: Vec<&'a str>,
+        line_ending: &'static str,
+    }
+
+    impl<'a> Lines<'a> {
+        fn new(text: &'a str) -> Self {
+            let line_ending = if text.contains("\r\n") { "\r\n" } else { "\n" };
+            Self {
+                lines: text.split_inclusive('\n').collect(),
+                line_ending,
+            }
+        }
+
+        fn trimmed(&self, ix: usize) -> &'a str {
+            self.lines[ix].trim()
+        }
+
+        /// Byte offset at which line `ix` starts.
+        fn offset(&self, ix: usize) -> usize {
+            self.lines[..ix].iter().map(|line| line.len()).sum()
+        }
+
+        fn byte_range(&self, lines: Range<usize>) -> Range<usize> {
+            self.offset(lines.start)..self.offset(lines.end)
+        }
+
+        fn project_blocks(&self) -> Vec<ProjectBlock> {
+            let mut blocks = Vec::new();
+            let mut ix = 0;
+            while ix < self.lines.len() {
+                let line = self.trimmed(ix);
+                if line.starts_with("Project(")
+                    && let Some((type_guid, name, path, id)) = parse_project_line(line)
+                {
+                    let start = ix;
+                    while ix < self.lines.len() && self.trimmed(ix) != "EndProject" {
+                        ix += 1;
+                    }
+                    blocks.push(ProjectBlock {
+                        lines: start..(ix + 1).min(self.lines.len()),
+                        type_guid,
+                        name,
+                        path,
+                        id,
+                    });
+                }
+                ix += 1;
+            }
+            blocks
+        }
+
+        fn global_sections(&self) -> Vec<GlobalSection> {
+            let mut sections = Vec::new();
+            let mut ix = 0;
+            while ix < self.lines.len() {
+                let line = self.trimmed(ix);
+                if let Some(rest) = line.strip_prefix("GlobalSection(")
+                    && let Some(end) = rest.find(')')
+                {
+                    let start = ix;
+                    while ix < self.lines.len() && self.trimmed(ix) != "EndGlobalSection" {
+                        ix += 1;
+                    }
+                    sections.push(GlobalSection {
+                        name: rest[..end].to_string(),
+                        lines: start..(ix + 1).min(self.lines.len()),
+                    });
+                }
+                ix += 1;
+            }
+            sections
+        }
+
+        fn global_section(&self, name: &str) -> Option<GlobalSection> {
+            self.global_sections()
+                .into_iter()
+                .find(|section| section.name == name)
+        }
+
+        /// Entries (`key = value`) of a section, with their line index.
+        fn section_entries(&self, section: &GlobalSection) -> Vec<(usize, &'a str, &'a str)> {
+            (section.lines.start + 1..section.lines.end.saturating_sub(1))
+                .filter_map(|ix| {
+                    let (key, value) = self.trimmed(ix).split_once('=')?;
+                    Some((ix, key.trim(), value.trim()))
+                })
+                .collect()
+        }
+
+        fn line_index(&self, content: &str) -> Option<usize> {
+            (0..self.lines.len()).find(|ix| self.trimmed(*ix) == content)
+        }
+    }
+
+    /// Parses `Project("{type}") = "name", "path", "{id}"`.
+    fn parse_project_line(line: &str) -> Option<(String, String, String, String)> {
+        let rest = line.strip_prefix("Project(\"")?;
+        let (type_guid, rest) = rest.split_once("\")")?;
+        let rest = rest.trim_start().strip_prefix('=')?;
+        let mut values = Vec::new();
+        let mut remaining = rest;
+        for _ in 0..3 {
+            let start = remaining.find('"')? + 1;
+            let end = start + remaining[start..].find('"')?;
+            values.push(remaining[start..end].to_string());
+            remaining = &remaining[end + 1..];
+        }
+        let id = values.pop()?;
+        let path = values.pop()?;
+        let name = values.pop()?;
+        Some((guid(type_guid), name, path, guid(&id)))
+    }
+
+    fn guid(value: &str) -> String {
+        value
+            .trim()
+            .trim_start_matches('{')
+            .trim_end_matches('}')
+            .to_ascii_uppercase()
+    }
+
+    fn project_line(type_guid: &str, name: &str, path: &str, id: &str) -> String {
+        format!("Project(\"{{{type_guid}}}\") = \"{name}\", \"{path}\", \"{{{id}}}\"")
+    }
+
+    pub(super) fn parse(text: &str) -> Result<ParsedSolution> {
+        let lines = Lines::new(text);
+        if !lines
+            .lines
+            .iter()
+            .take(3)
+            .any(|line| line.contains("Microsoft Visual Studio Solution File"))
+        {
+            bail!("missing solution file header");
+        }
+
+        let mut projects = Vec::new();
+        let mut folders = Vec::new();
+        for block in lines.project_blocks() {
+            let mut items = Vec::new();
+            let mut dependencies = Vec::new();
+            let mut section = None;
+            for ix in block.lines.clone() {
+                let line = lines.trimmed(ix);
+                if let Some(rest) = line.strip_prefix("ProjectSection(") {
+                    section = rest.split_once(')').map(|(name, _)| name.to_string());
+                } else if line == "EndProjectSection" {
+                    section = None;
+                } else if let Some((key, _)) = line.split_once('=') {
+                    match section.as_deref() {
+                        Some("SolutionItems") => {
+                            items.push(PathBuf::from(normalize_separators(key.trim())))
+                        }
+                        Some("ProjectDependencies") => dependencies.push(guid(key)),
+                        _ => {}
+                    }
+                }
+            }
+
+            if block.type_guid == SOLUTION_FOLDER_TYPE_GUID {
+                folders.push(SolutionFolder {
+                    id: block.id,
+                    name: block.name,
+                    parent: None,
+                    items,
+                });
+            } else {
+                projects.push(SolutionProject {
+                    id: block.id,
+                    name: block.name,
+                    path: PathBuf::from(normalize_separators(&block.path)),
+                    type_guid: Some(block.type_guid),
+                    parent: None,
+                    configurations: Vec::new(),
+                    dependencies,
+                });
+            }
+        }
+
+        let mut configurations = Vec::new();
+        if let Some(section) = lines.global_section("SolutionConfigurationPlatforms") {
+            for (_, key, _) in lines.section_entries(&section) {
+                if let Some(configuration) = SolutionConfiguration::parse(key)
+                    && !configurations.contains(&configuration)
+                {
+                    configurations.push(configuration);
+                }
+            }
+        }
+
+        if let Some(section) = lines.global_section("ProjectConfigurationPlatforms") {
+            for (_, key, value) in lines.section_entries(&section) {
+                let Some((id, rest)) = key.split_once('.') else {
+                    continue;
+                };
+                let Some(project) = projects.iter_mut().find(|project| project.id == guid(id))
+                else {
+                    continue;
+                };
+                let (solution, kind) = if let Some(solution) = rest.strip_suffix(".ActiveCfg") {
+                    (solution, "ActiveCfg")
+                } else if let Some(solution) = rest.strip_suffix(".Build.0") {
+                    (solution, "Build")
+                } else if let Some(solution) = rest.strip_suffix(".Deploy.0") {
+                    (solution, "Deploy")
+                } else {
+                    continue;
+                };
+                let Some(solution) = SolutionConfiguration::parse(solution) else {
+                    continue;
+                };
+                let ix = match project
+                    .configurations
+                    .iter()
+                    .position(|mapping| mapping.solution == solution)
+                {
+                    Some(ix) => ix,
+                    None => {
+                        project.configurations.push(ProjectConfigurationMapping {
+                            project: solution.clone(),
+                            solution,
+                            build: false,
+                            deploy: false,
+                        });
+                        project.configurations.len() - 1
+                    }
+                };
+                let mapping = &mut project.configurations[ix];
+                match kind {
+                    "ActiveCfg" => {
+                        if let Some(configuration) = SolutionConfiguration::parse(value) {
+                            mapping.project = configuration;
+                        }
+                    }
+                    "Build" => mapping.build = true,
+                    _ => mapping.deploy = true,
+                }
+            }
+        }
+
+        if let Some(section) = lines.global_section("NestedProjects") {
+            for (_, child, parent) in lines.section_entries(&section) {
+                let (child, parent) = (guid(child), guid(parent));
+                if let Some(project) = projects.iter_mut().find(|project| project.id == child) {
+                    project.parent = Some(parent);
+                } else if let Some(folder) = folders.iter_mut().find(|folder| folder.id == child) {
+                    folder.parent = Some(parent);
+                }
+            }
+        }
+
+        Ok(ParsedSolution {
+            projects,
+            folders,
+            configurations,
+        })
+    }
+
+    /// Inserts lines at `line_ix` (before the line currently at that index).
+    fn insert_lines(solution: &mut SolutionFile, line_ix: usize, new_lines: &[String]) {
+        let text = &solution.source.text;
+        let lines = Lines::new(text);
+        let mut offset = lines.offset(line_ix.min(lines.lines.len()));
+        let line_ending = lines.line_ending;
+        let mut insertion = String::new();
+        if line_ix >= lines.lines.len() && !text.is_empty() && !text.ends_with('\n') {
+            insertion.push_str(line_ending);
+            offset = text.len();
+        }
+        for line in new_lines {
+            insertion.push_str(line);
+            insertion.push_str(line_ending);
+        }
+        solution.source.text.insert_str(offset, &insertion);
+    }
+
+    /// Removes the given line indices.
+    fn remove_lines(solution: &mut SolutionFile, mut line_indices: Vec<usize>) {
+        line_indices.sort_unstable();
+        line_indices.dedup();
+        let lines = Lines::new(&solution.source.text);
+        let ranges = line_indices
+            .into_iter()
+            .rev()
+            .map(|ix| lines.byte_range(ix..ix + 1))
+            .collect::<Vec<_>>();
+        for range in ranges {
+            splice(&mut solution.source.text, range, "");
+        }
+    }
+
+    fn replace_line(solution: &mut SolutionFile, line_ix: usize, content: &str) {
+        let lines = Lines::new(&solution.source.text);
+        let line = lines.lines[line_ix];
+        let indent = &line[..line.len() - line.trim_start().len()];
+        let ending = &line[line.trim_end().len()..];
+        let replacement = format!("{indent}{content}{ending}");
+        let range = lines.byte_range(line_ix..line_ix + 1);
+        splice(&mut solution.source.text, range, &replacement);
+    }
+
+    /// Finds a global section, creating it (empty) if necessary. Returns the
+    /// line index of its `EndGlobalSection`.
+    fn ensure_global_section(solution: &mut SolutionFile, name: &str, timing: &str) -> usize {
+        if let Some(section) = Lines::new(&solution.source.text).global_section(name) {
+            return section.lines.end - 1;
+        }
+
+        let lines = Lines::new(&solution.source.text);
+        let global = match lines.line_index("Global") {
+            Some(global) => global,
+            None => {
+                let end = lines.lines.len();
+                insert_lines(solution, end, &["Global".into(), "EndGlobal".into()]);
+                end
+            }
+        };
+        // Keep the sections in the order Visual Studio writes them.
+        let order = [
+            "SolutionConfigurationPlatforms",
+            "ProjectConfigurationPlatforms",
+            "SolutionProperties",
+            "NestedProjects",
+        ];
+        let rank = order.iter().position(|section| *section == name);
+        let lines = Lines::new(&solution.source.text);
+        let insert_at = lines
+            .global_sections()
+            .into_iter()
+            .filter(|section| {
+                let section_rank = order.iter().position(|name| *name == section.name);
+                matches!((section_rank, rank), (Some(section_rank), Some(rank)) if section_rank < rank)
+            })
+            .map(|section| section.lines.end)
+            .max()
+            .unwrap_or(global + 1);
+        insert_lines(
+            solution,
+            insert_at,
+            &[
+                format!("\tGlobalSection({name}) = {timing}"),
+                "\tEndGlobalSection".into(),
+            ],
+        );
+        insert_at + 1
+    }
+
+    fn remove_section_if_empty(solution: &mut SolutionFile, name: &str) {
+        let lines = Lines::new(&solution.source.text);
+        if let Some(section) = lines.global_section(name)
+            && lines.section_entries(&section).is_empty()
+        {
+            remove_lines(solution, section.lines.collect());
+        }
+    }
+
+    fn set_parent(solution: &mut SolutionFile, id: &str, parent: Option<&str>) {
+        let lines = Lines::new(&solution.source.text);
+        if let Some(section) = lines.global_section("NestedProjects") {
+            let existing = lines
+                .section_entries(&section)
+                .into_iter()
+                .filter(|(_, child, _)| guid(child) == id)
+                .map(|(ix, _, _)| ix)
+                .collect();
+            remove_lines(solution, existing);
+        }
+        match parent {
+            Some(parent) => {
+                let end = ensure_global_section(solution, "NestedProjects", "preSolution");
+                insert_lines(solution, end, &[format!("\t\t{{{id}}} = {{{parent}}}")]);
+            }
+            None => remove_section_if_empty(solution, "NestedProjects"),
+        }
+    }
+
+    fn insert_project_block(solution: &mut SolutionFile, lines_to_insert: &[String]) {
+        let lines = Lines::new(&solution.source.text);
+        let insert_at = lines
+            .line_index("Global")
+            .or_else(|| lines.project_blocks().last().map(|block| block.lines.end))
+            .unwrap_or(lines.lines.len());
+        insert_lines(solution, insert_at, lines_to_insert);
+    }
+
+    pub(super) fn add_project(
+        solution: &mut SolutionFile,
+        path: &str,
+        parent: Option<&str>,
+    ) -> Result<String> {
+        let id = new_guid();
+        let name = file_stem(path);
+        let sln_path = path.replace('/', "\\");
+        insert_project_block(
+            solution,
+            &[
+                project_line(project_type_guid(path), &name, &sln_path, &id),
+                "EndProject".into(),
+            ],
+        );
+
+        if solution.configurations.is_empty() {
+            let end =
+                ensure_global_section(solution, "SolutionConfigurationPlatforms", "preSolution");
+            insert_lines(
+                solution,
+                end,
+                &[
+                    "\t\tDebug|Any CPU = Debug|Any CPU".into(),
+                    "\t\tRelease|Any CPU = Release|Any CPU".into(),
+                ],
+            );
+            solution.configurations = vec![
+                SolutionConfiguration::new("Debug", ANY_CPU),
+                SolutionConfiguration::new("Release", ANY_CPU),
+            ];
+        }
+        let mapping_lines = solution
+            .configurations
+            .iter()
+            .flat_map(|configuration| {
+                let project_configuration = format!("{}|{ANY_CPU}", configuration.configuration);
+                [
+                    format!("\t\t{{{id}}}.{configuration}.ActiveCfg = {project_configuration}"),
+                    format!("\t\t{{{id}}}.{configuration}.Build.0 = {project_configuration}"),
+                ]
+            })
+            .collect::<Vec<_>>();
+        let end = ensure_global_section(solution, "ProjectConfigurationPlatforms", "postSolution");
+        insert_lines(solution, end, &mapping_lines);
+
+        if parent.is_some() {
+            set_parent(solution, &id, parent);
+        }
+        Ok(id)
+    }
+
+    /// Removes the blocks and global section entries of the given ids.
+    fn remove_ids(solution: &mut SolutionFile, ids: &[String]) {
+        let lines = Lines::new(&solution.source.text);
+        let mut removed_lines = Vec::new();
+        for block in lines.project_blocks() {
+            if ids.contains(&block.id) {
+                removed_lines.extend(block.lines.clone());
+            } else {
+                // Drop build dependencies on removed projects.
+                removed_lines.extend(block.lines.clone().filter(|ix| {
+                    lines
+                        .trimmed(*ix)
+                        .split_once('=')
+                        .is_some_and(|(key, value)| {
+                            key.trim().starts_with('{')
+                                && ids.contains(&guid(key))
+                                && guid(key) == guid(value)
+                        })
+                }));
+            }
+        }
+        if let Some(section) = lines.global_section("ProjectConfigurationPlatforms") {
+            removed_lines.extend(
+                lines
+                    .section_entries(&section)
+                    .into_iter()
+                    .filter(|(_, key, _)| {
+                        key.split_once('.')
+                            .is_some_and(|(id, _)| ids.contains(&guid(id)))
+                    })
+                    .map(|(ix, _, _)| ix),
+            );
+        }
+        if let Some(section) = lines.global_section("NestedProjects") {
+            removed_lines.extend(
+                lines
+                    .section_entries(&section)
+                    .into_iter()
+                    .filter(|(_, child, parent)| {
+                        ids.contains(&guid(child)) || ids.contains(&guid(parent))
+                    })
+                    .map(|(ix, _, _)| ix),
+            );
+        }
+        remove_lines(solution, removed_lines);
+        remove_section_if_empty(solution, "ProjectConfigurationPlatforms");
+        remove_section_if_empty(solution, "NestedProjects");
+    }
+
+    pub(super) fn remove_project(
+        solution: &mut SolutionFile,
+        project: &SolutionProject,
+    ) -> Result<()> {
+        remove_ids(solution, std::slice::from_ref(&project.id));
+        Ok(())
+    }
+
+    pub(super) fn rename_project(
+        solution: &mut SolutionFile,
+        project: &SolutionProject,
+        name: &str,
+        path: &str,
+    ) -> Result<()> {
+        let block = find_block(solution, &project.id)?;
+        let content = project_line(&block.type_guid, name, &path.replace('/', "\\"), &block.id);
+        replace_line(solution, block.lines.start, &content);
+        Ok(())
+    }
+
+    pub(super) fn add_folder(
+        solution: &mut SolutionFile,
+        name: &str,
+        parent: Option<&str>,
+    ) -> Result<String> {
+        let id = new_guid();
+        insert_project_block(
+            solution,
+            &[
+                project_line(SOLUTION_FOLDER_TYPE_GUID, name, name, &id),
+                "EndProject".into(),
+            ],
+        );
+        if parent.is_some() {
+            set_parent(solution, &id, parent);
+        }
+        Ok(id)
+    }
+
+    pub(super) fn remove_folder(
+        solution: &mut SolutionFile,
+        folder: &SolutionFolder,
+    ) -> Result<()> {
+        let (folders, projects) = solution.descendants(&folder.id);
+        let ids = std::iter::once(folder.id.clone())
+            .chain(folders.into_iter().map(|folder| folder.id))
+            .chain(projects.into_iter().map(|project| project.id))
+            .collect::<Vec<_>>();
+        remove_ids(solution, &ids);
+        Ok(())
+    }
+
+    pub(super) fn rename_folder(
+        solution: &mut SolutionFile,
+        folder: &SolutionFolder,
+        name: &str,
+    ) -> Result<()> {
+        let block = find_block(solution, &folder.id)?;
+        let content = project_line(SOLUTION_FOLDER_TYPE_GUID, name, name, &block.id);
+        replace_line(solution, block.lines.start, &content);
+        Ok(())
+    }
+
+    pub(super) fn move_item(
+        solution: &mut SolutionFile,
+        id: &str,
+        parent: Option<&str>,
+    ) -> Result<()> {
+        let block = find_block(solution, id)?;
+        set_parent(solution, &block.id, parent);
+        Ok(())
+    }
+
+    fn find_block(solution: &SolutionFile, id: &str) -> Result<ProjectBlock> {
+        let id = guid(id);
+        Lines::new(&solution.source.text)
+            .project_blocks()
+            .into_iter()
+            .find(|block| block.id == id)
+            .with_context(|| format!("no project with id {id}"))
+    }
+}
+
+mod slnx {
+    use super::*;
+    use crate::xml_edit::{
+        escape, indent_of, insert_after, insert_child, line_ending, line_start, reindent,
+        removal_range, remove_ranges,
+    };
+
+    pub(super) fn parse(text: &str) -> Result<ParsedSolution> {
+        let document = roxmltree::Document::parse(text)?;
+        let root = document.root_element();
+        if !root.has_tag_name("Solution") {
+            bail!("expected a <Solution> root element");
+        }
+
+        let mut build_types = Vec::new();
+        let mut platforms = Vec::new();
+        if let Some(configurations) = root
+            .children()
+            .find(|child| child.has_tag_name("Configurations"))
+        {
+            for child in configurations.children().filter(|child| child.is_element()) {
+                let Some(name) = child.attribute("Name") else {
+                    continue;
+                };
+                match child.tag_name().name() {
+                    "BuildType" => build_types.push(name.to_string()),
+                    "Platform" => platforms.push(name.to_string()),
+                    _ => {}
+                }
+            }
+        }
+        if build_types.is_empty() {
+            build_types = vec!["Debug".to_string(), "Release".to_string()];
+        }
+        if platforms.is_empty() {
+            platforms = vec![ANY_CPU.to_string()];
+        }
+        let configurations = build_types
+            .iter()
+            .flat_map(|build_type| {
+                platforms
+                    .iter()
+                    .map(move |platform| SolutionConfiguration::new(build_type, platform))
+            })
+            .collect::<Vec<_>>();
+
+        let mut folders = Vec::new();
+        let mut projects = Vec::new();
+        for child in root.children().filter(|child| child.is_element()) {
+            match child.tag_name().name() {
+                "Folder" => {
+                    let Some(id) = child.attribute("Name") else {
+                        continue;
+                    };
+                    let id = folder_path(id);
+                    let (parent, name) = split_folder_path(&id);
+                    let items = child
+                        .children()
+                        .filter(|item| item.has_tag_name("File"))
+                        .filter_map(|item| item.attribute("Path"))
+                        .map(|path| PathBuf::from(normalize_separators(path)))
+                        .collect();
+                    for project in child.children().filter(|item| item.has_tag_name("Project")) {
+                        if let Some(project) =
+                            parse_project(project, Some(id.clone()), &configurations)
+                        {
+                            projects.push(project);
+                        }
+                    }
+                    folders.push(SolutionFolder {
+                        id: id.clone(),
+                        name,
+                        parent,
+                        items,
+                    });
+                }
+                "Project" => {
+                    if let Some(project) = parse_project(child, None, &configurations) {
+                        projects.push(project);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // Folders only reference their parents by path, which may not be declared.
+        let folder_ids = folders
+            .iter()
+            .map(|folder| folder.id.clone())
+            .collect::<Vec<_>>();
+        for folder in &mut folders {
+            if folder
+                .parent
+                .as_ref()
+                .is_some_and(|parent| !folder_ids.contains(parent))
+            {
+                folder.parent = None;
+            }
+        }
+        // Build dependencies reference project paths; resolve them to ids.
+        let ids_by_path = projects
+            .iter()
+            .map(|project| (project.path.clone(), project.id.clone()))
+            .collect::<Vec<_>>();
+        for project in &mut projects {
+            for dependency in &mut project.dependencies {
+                if let Some((_, id)) = ids_by_path
+                    .iter()
+                    .find(|(path, _)| path.to_string_lossy().eq_ignore_ascii_case(dependency))
+                {
+                    *dependency = id.clone();
+                }
+            }
+        }
+
+        Ok(ParsedSolution {
+            projects,
+            folders,
+            configurations,
+        })
+    }
+
+    fn parse_project(
+        node: roxmltree::Node,
+        parent: Option<String>,
+        configurations: &[SolutionConfiguration],
+    ) -> Option<SolutionProject> {
+        let path = normalize_separators(node.attribute("Path")?);
+        let name = node
+            .attribute("DisplayName")
+            .map(str::to_string)
+            .unwrap_or_else(|| file_stem(&path));
+        let id = node
+            .attribute("Id")
+            .map(|id| id.trim_matches(['{', '}']).to_ascii_uppercase())
+            .unwrap_or_else(|| path.clone());
+
+        let rules = node
+            .children()
+            .filter(|child| child.is_element())
+            .map(|child| {
+                (
+                    child.tag_name().name(),
+                    child.attribute("Solution").unwrap_or("*|*"),
+                    child.attribute("Project").unwrap_or_default(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let configurations = configurations
+            .iter()
+            .map(|solution| {
+                let mut mapping = ProjectConfigurationMapping {
+                    solution: solution.clone(),
+                    project: solution.clone(),
+                    build: true,
+                    deploy: false,
+                };
+                for (kind, pattern, value) in &rules {
+                    if !pattern_matches(pattern, solution) {
+                        continue;
+                    }
+                    match *kind {
+                        "BuildType" => mapping.project.configuration = value.to_string(),
+                        "Platform" => mapping.project.platform = value.to_string(),
+                        "Build" => mapping.build = !value.eq_ignore_ascii_case("false"),
+                        "Deploy" => mapping.deploy = !value.eq_ignore_ascii_case("false"),
+                        _ => {}
+                    }
+                }
+                mapping
+            })
+            .collect();
+        let dependencies = node
+            .children()
+            .filter(|child| child.has_tag_name("BuildDependency"))
+            .filter_map(|child| child.attribute("Project"))
+            .map(normalize_separators)
+            .collect();
+
+        Some(SolutionProject {
+            id,
+            name,
+            type_guid: node
+                .attribute("Type")
+                .map(|type_guid| type_guid.trim_matches(['{', '}']).to_ascii_uppercase()),
+            path: PathBuf::from(path),
+            parent,
+            configurations,
+            dependencies,
+        })
+    }
+
+    /// Matches `Debug|*`, `*|x64`, `Release` and similar solution configuration patterns.
+    fn pattern_matches(pattern: &str, configuration: &SolutionConfiguration) -> bool {
+        let (build_type, platform) = pattern.split_once('|').unwrap_or((pattern, "*"));
+        let matches = |pattern: &str, value: &str| {
+            let pattern = pattern.trim();
+            pattern == "*" || pattern.is_empty() || pattern.eq_ignore_ascii_case(value)
+        };
+        matches(build_type, &configuration.configuration)
+            && matches(platform, &configuration.platform)
+    }
+
+    /// Normalizes a folder name to the `/a/b/` form.
+    fn folder_path(name: &str) -> String {
+        let trimmed = name.trim().trim_matches('/');
+        format!("/{trimmed}/")
+    }
+
+    fn split_folder_path(path: &str) -> (Option<String>, String) {
+        let trimmed = path.trim_matches('/');
+        match trimmed.rsplit_once('/') {
+            Some((parent, name)) => (Some(format!("/{parent}/")), name.to_string()),
+            None => (None, trimmed.to_string()),
+        }
+    }
+
+    fn find_folder<'a, 'input>(
+        document: &'a roxmltree::Document<'input>,
+        id: &str,
+    ) -> Option<roxmltree::Node<'a, 'input>> {
+        document.root_element().children().find(|child| {
+            child.has_tag_name("Folder")
+                && child
+                    .attribute("Name")
+                    .is_some_and(|name| folder_path(name).eq_ignore_ascii_case(id))
+        })
+    }
+
+    fn find_project<'a, 'input>(
+        document: &'a roxmltree::Document<'input>,
+        project: &SolutionProject,
+    ) -> Option<roxmltree::Node<'a, 'input>> {
+        document.descendants().find(|node| {
+            node.has_tag_name("Project")
+                && node.parent_element().is_some_and(|parent| {
+                    parent.has_tag_name("Solution") || parent.has_tag_name("Folder")
+                })
+                && node.attribute("Path").is_some_and(|path| {
+                    normalize_separators(path).eq_ignore_ascii_case(&project.path.to_string_lossy())
+                })
+        })
+    }
+
+    /// An edit inserting a project element into `parent`, or after the last
+    /// root-level project when `parent` is `None`.
+    fn insert_project(
+        text: &str,
+        document: &roxmltree::Document,
+        parent: Option<&str>,
+        element: &str,
+    ) -> Result<(Range<usize>, String)> {
+        let root = document.root_element();
+        Ok(match parent {
+            Some(parent) => {
+                let folder = find_folder(document, parent)
+                    .with_context(|| format!("no solution folder {parent}"))?;
+                insert_child(text, folder, element)
+            }
+            None => match root.children().rfind(|child| child.has_tag_name("Project")) {
+                Some(last) => insert_after(text, last, element),
+                None => insert_child(text, root, element),
+            },
+        })
+    }
+
+    pub(super) fn add_project(
+        solution: &mut SolutionFile,
+        path: &str,
+        parent: Option<&str>,
+    ) -> Result<String> {
+        let element = format!("<Project Path=\"{}\" />", escape(path));
+        let text = solution.source.text.clone();
+        let document = roxmltree::Document::parse(&text)?;
+        let (range, replacement) = insert_project(&text, &document, parent, &element)?;
+        splice(&mut solution.source.text, range, &replacement);
+        Ok(path.to_string())
+    }
+
+    pub(super) fn remove_project(
+        solution: &mut SolutionFile,
+        project: &SolutionProject,
+    ) -> Result<()> {
+        let text = solution.source.text.clone();
+        let document = roxmltree::Document::parse(&text)?;
+        let node = find_project(&document, project)
+            .with_context(|| format!("project {} not found", project.path.display()))?;
+        let mut ranges = vec![removal_range(&text, node.range())];
+        let path = project.path.to_string_lossy();
+        ranges.extend(
+            document
+                .descendants()
+                .filter(|node| {
+                    node.has_tag_name("BuildDependency")
+                        && node.attribute("Project").is_some_and(|dependency| {
+                            normalize_separators(dependency).eq_ignore_ascii_case(&path)
+                        })
+                })
+                .map(|node| removal_range(&text, node.range())),
+        );
+        remove_ranges(&mut solution.source.text, ranges);
+        Ok(())
+    }
+
+    pub(super) fn rename_project(
+        solution: &mut SolutionFile,
+        project: &SolutionProject,
+        path: &str,
+    ) -> Result<()> {
+        let text = solution.source.text.clone();
+        let document = roxmltree::Document::parse(&text)?;
+        let node = find_project(&document, project)
+            .with_context(|| format!("project {} not found", project.path.display()))?;
+        let attribute = node
+            .attributes()
+            .find(|attribute| attribute.name() == "Path")
+            .ok_or_else(|| anyhow!("project without a Path attribute"))?;
+        splice(
+            &mut solution.source.text,
+            attribute.range_value(),
+            &escape(path),
+        );
+        Ok(())
+    }
+
+    pub(super) fn add_folder(
+        solution: &mut SolutionFile,
+        name: &str,
+        parent: Option<&str>,
+    ) -> Result<String> {
+        let id = match parent {
+            Some(parent) => format!("{parent}{name}/"),
+            None => format!("/{name}/"),
+        };
+        let element = format!("<Folder Name=\"{}\" />", escape(&id));
+        let text = solution.source.text.clone();
+        let document = roxmltree::Document::parse(&text)?;
+        let root = document.root_element();
+        let newline = line_ending(&text);
+        // Folders are written before root-level projects, after the configurations.
+        let previous = root
+            .children()
+            .rfind(|child| child.has_tag_name("Folder") || child.has_tag_name("Configurations"));
+        let first_project = root.children().find(|child| child.has_tag_name("Project"));
+        let (range, replacement) = match (previous, first_project) {
+            (Some(previous), _) => insert_after(&text, previous, &element),
+            (None, Some(project)) => {
+                let indent = indent_of(&text, project.range().start);
+                let start = line_start(&text, project.range().start);
+                (start..start, format!("{indent}{element}{newline}"))
+            }
+            (None, None) => insert_child(&text, root, &element),
+        };
+        splice(&mut solution.source.text, range, &replacement);
+        Ok(id)
+    }
+
+    pub(super) fn remove_folder(
+        solution: &mut SolutionFile,
+        folder: &SolutionFolder,
+    ) -> Result<()> {
+        let text = solution.source.text.clone();
+        let document = roxmltree::Document::parse(&text)?;
+        let prefix = folder.id.to_ascii_lowercase();
+        let ranges = document
+            .root_element()
+            .children()
+            .filter(|child| {
+                child.has_tag_name("Folder")
+                    && child.attribute("Name").is_some_and(|name| {
+                        folder_path(name).to_ascii_lowercase().starts_with(&prefix)
+                    })
+            })
+            .map(|child| removal_range(&text, child.range()))
+            .collect();
+        remove_ranges(&mut solution.source.text, ranges);
+        Ok(())
+    }
+
+    /// Replaces the `old` folder path prefix with `new` in all folder names.
+    fn replace_folder_prefix(solution: &mut SolutionFile, old: &str, new: &str) -> Result<()> {
+        let text = solution.source.text.clone();
+        let document = roxmltree::Document::parse(&text)?;
+        let old_lowercase = old.to_ascii_lowercase();
+        let mut edits = document
+            .root_element()
+            .children()
+            .filter(|child| child.has_tag_name("Folder"))
+            .filter_map(|child| {
+                let attribute = child
+                    .attributes()
+                    .find(|attribute| attribute.name() == "Name")?;
+                let name = folder_path(attribute.value());
+                if !name.to_ascii_lowercase().starts_with(&old_lowercase) {
+                    return None;
+                }
+                let renamed = format!("{new}{}", &name[old.len()..]);
+                Some((attribute.range_value(), escape(&renamed)))
+            })
+            .collect::<Vec<_>>();
+        edits.sort_by_key(|(range, _)| range.start);
+        for (range, value) in edits.into_iter().rev() {
+            splice(&mut solution.source.text, range, &value);
+        }
+        Ok(())
+    }
+
+    pub(super) fn rename_folder(
+        solution: &mut SolutionFile,
+        folder: &SolutionFolder,
+        name: &str,
+    ) -> Result<()> {
+        let renamed = match &folder.parent {
+            Some(parent) => format!("{parent}{name}/"),
+            None => format!("/{name}/"),
+        };
+        replace_folder_prefix(solution, &folder.id, &renamed)
+    }
+
+    pub(super) fn move_item(
+        solution: &mut SolutionFile,
+        id: &str,
+        parent: Option<&str>,
+    ) -> Result<()> {
+        if let Some(folder) = solution.folder_by_id(id).cloned() {
+            let moved = match parent {
+                Some(parent) => format!("{parent}{}/", folder.name),
+                None => format!("/{}/", folder.name),
+            };
+            return replace_folder_prefix(solution, &folder.id, &moved);
+        }
+
+        let project = solution
+            .project_by_id(id)
+            .cloned()
+            .with_context(|| format!("no project with id {id}"))?;
+        let text = solution.source.text.clone();
+        let document = roxmltree::Document::parse(&text)?;
+        let node = find_project(&document, &project)
+            .with_context(|| format!("project {} not found", project.path.display()))?;
+        let element = reindent(&text, node.range());
+        remove_ranges(
+            &mut solution.source.text,
+            vec![removal_range(&text, node.range())],
+        );
+
+        let text = solution.source.text.clone();
+        let document = roxmltree::Document::parse(&text)?;
+        let (range, replacement) = insert_project(&text, &document, parent, &element)?;
+        splice(&mut solution.source.text, range, &replacement);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const SLN: &str = "\u{feff}
+Microsoft Visual Studio Solution File, Format Version 12.00\r
+# Visual Studio Version 17\r
+VisualStudioVersion = 17.0.31903.59\r
+MinimumVisualStudioVersion = 10.0.40219.1\r
+Project(\"{2150E333-8FDC-42A3-9474-1A3956D46DE8}\") = \"src\", \"src\", \"{A0000000-0000-0000-0000-000000000001}\"\r
+EndProject\r
+Project(\"{FAE04EC0-301F-11D3-BA7A-00C04FC2CCAE}\") = \"Api\", \"src\\Api\\Api.csproj\", \"{B0000000-0000-0000-0000-000000000001}\"\r
+\tProjectSection(ProjectDependencies) = postProject\r
+\t\t{B0000000-0000-0000-0000-000000000002} = {B0000000-0000-0000-0000-000000000002}\r
+\tEndProjectSection\r
+EndProject\r
+Project(\"{9A19103F-16F7-4668-BE54-9A1E7A4F7556}\") = \"Core\", \"src\\Core\\Core.csproj\", \"{b0000000-0000-0000-0000-000000000002}\"\r
+EndProject\r
+Project(\"{2150E333-8FDC-42A3-9474-1A3956D46DE8}\") = \"Solution Items\", \"Solution Items\", \"{A0000000-0000-0000-0000-000000000002}\"\r
+\tProjectSection(SolutionItems) = preProject\r
+\t\t.editorconfig = .editorconfig\r
+\tEndProjectSection\r
+EndProject\r
+Global\r
+\tGlobalSection(SolutionConfigurationPlatforms) = preSolution\r
+\t\tDebug|Any CPU = Debug|Any CPU\r
+\t\tRelease|Any CPU = Release|Any CPU\r
+\tEndGlobalSection\r
+\tGlobalSection(ProjectConfigurationPlatforms) = postSolution\r
+\t\t{B0000000-0000-0000-0000-000000000001}.Debug|Any CPU.ActiveCfg = Debug|Any CPU\r
+\t\t{B0000000-0000-0000-0000-000000000001}.Debug|Any CPU.Build.0 = Debug|Any CPU\r
+\t\t{B0000000-0000-0000-0000-000000000001}.Release|Any CPU.ActiveCfg = Release|x64\r
+\t\t{B0000000-0000-0000-0000-000000000001}.Release|Any CPU.Build.0 = Release|x64\r
+\t\t{B0000000-0000-0000-0000-000000000002}.Debug|Any CPU.ActiveCfg = Debug|Any CPU\r
+\t\t{B0000000-0000-0000-0000-000000000002}.Release|Any CPU.ActiveCfg = Release|Any CPU\r
+\tEndGlobalSection\r
+\tGlobalSection(SolutionProperties) = preSolution\r
+\t\tHideSolutionNode = FALSE\r
+\tEndGlobalSection\r
+\tGlobalSection(NestedProjects) = preSolution\r
+\t\t{B0000000-0000-0000-0000-000000000001} = {A0000000-0000-0000-0000-000000000001}\r
+\t\t{B0000000-0000-0000-0000-000000000002} = {A0000000-0000-0000-0000-000000000001}\r
+\tEndGlobalSection\r
+EndGlobal\r
+";
+
+    const SLNX: &str = r#"<Solution>
+  <Configurations>
+    <Platform Name="Any CPU" />
+    <Platform Name="x64" />
+  </Configurations>
+  <Folder Name="/Solution Items/">
+    <File Path="Directory.Build.props" />
+  </Folder>
+  <Folder Name="/src/">
+    <Project Path="src/Api/Api.csproj">
+      <BuildDependency Project="src/Core/Core.csproj" />
+      <Platform Solution="*|x64" Project="x64" />
+    </Project>
+    <Project Path="src/Core/Core.csproj" Id="{c0000000-0000-0000-0000-000000000001}" />
+  </Folder>
+  <Folder Name="/src/tests/" />
+  <Project Path="tools/Tool.fsproj">
+    <Build Solution="Release|*" Project="false" />
+  </Project>
+</Solution>
+"#;
+
+    fn sln() -> SolutionFile {
+        SolutionFile::parse(Path::new("/repo/App.sln"), SLN).unwrap()
+    }
+
+    fn slnx() -> SolutionFile {
+        SolutionFile::parse(Path::new("/repo/App.slnx"), SLNX).unwrap()
+    }
+
+    #[test]
+    fn test_parse_sln() {
+        let solution = sln();
+        assert_eq!(solution.format, SolutionFormat::Sln);
+        assert_eq!(solution.to_text(), SLN);
+        assert_eq!(
+            solution.configurations,
+            vec![
+                SolutionConfiguration::new("Debug", "Any CPU"),
+                SolutionConfiguration::new("Release", "Any CPU"),
+            ]
+        );
+
+        let api = solution.project("Api").unwrap();
+        assert_eq!(api.path, PathBuf::from("src/Api/Api.csproj"));
+        assert_eq!(
+            api.parent.as_deref(),
+            Some("A0000000-0000-0000-0000-000000000001")
+        );
+        assert_eq!(
+            api.dependencies,
+            vec!["B0000000-0000-0000-0000-000000000002"]
+        );
+        assert_eq!(
+            solution
+                .project_configuration(&api.id, &SolutionConfiguration::new("Release", "Any CPU")),
+            Some(&ProjectConfigurationMapping {
+                solution: SolutionConfiguration::new("Release", "Any CPU"),
+                project: SolutionConfiguration::new("Release", "x64"),
+                build: true,
+                deploy: false,
+            })
+        );
+        let core = solution
+            .project_by_id("b0000000-0000-0000-0000-000000000002")
+            .unwrap();
+        assert!(
+            !solution
+                .project_configuration(&core.id, &SolutionConfiguration::new("Debug", "Any CPU"))
+                .unwrap()
+                .build
+        );
+
+        let release = SolutionConfiguration::new("Release", "Any CPU");
+        assert_eq!(
+            solution.default_configuration(),
+            Some(&SolutionConfiguration::new("Debug", "Any CPU"))
+        );
+        assert_eq!(
+            solution.evaluation_options(api, &release),
+            EvaluationOptions {
+                configuration: "Release".into(),
+                platform: Some("x64".into()),
+                ..EvaluationOptions::default()
+            }
+        );
+        assert_eq!(
+            solution
+                .evaluation_options(core, &release)
+                .platform
+                .as_deref(),
+            Some("AnyCPU")
+        );
+        assert_eq!(
+            solution.evaluation_options(core, &SolutionConfiguration::new("Staging", "Any CPU")),
+            EvaluationOptions {
+                configuration: "Staging".into(),
+                ..EvaluationOptions::default()
+            }
+        );
+
+        let items = solution
+            .folder_by_id("A0000000-0000-0000-0000-000000000002")
+            .unwrap();
+        assert_eq!(items.name, "Solution Items");
+        assert_eq!(items.items, vec![PathBuf::from(".editorconfig")]);
+        assert_eq!(
+            solution
+                .child_projects(Some("A0000000-0000-0000-0000-000000000001"))
+                .map(|project| project.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Api", "Core"]
+        );
+    }
+
+    #[test]
+    fn test_edit_sln() {
+        let mut solution = sln();
+        let folder = solution
+            .add_folder("tests", Some("A0000000-0000-0000-0000-000000000001"))
+            .unwrap();
+        let project = solution
+            .add_project(Path::new("tests/Api.Tests/Api.Tests.csproj"), Some(&folder))
+            .unwrap();
+        let text = solution.to_text();
+        assert!(text.contains(&format!(
+            "Project(\"{{FAE04EC0-301F-11D3-BA7A-00C04FC2CCAE}}\") = \"Api.Tests\", \"tests\\Api.Tests\\Api.Tests.csproj\", \"{{{project}}}\"\r\nEndProject\r\nGlobal\r\n"
+        )));
+        assert!(text.contains(&format!(
+            "\t\t{{{project}}}.Release|Any CPU.Build.0 = Release|Any CPU\r\n\tEndGlobalSection\r\n\tGlobalSection(SolutionProperties)"
+        )));
+        assert!(text.contains(&format!(
+            "\t\t{{{project}}} = {{{folder}}}\r\n\tEndGlobalSection\r\nEndGlobal"
+        )));
+        assert_eq!(
+            solution.project("Api.Tests").unwrap().parent.as_deref(),
+            Some(folder.as_str())
+        );
+
+        solution.remove_folder(&folder).unwrap();
+        assert_eq!(solution.to_text(), SLN);
+
+        solution
+            .move_item("B0000000-0000-0000-0000-000000000002", None)
+            .unwrap();
+        solution
+            .rename_folder("A0000000-0000-0000-0000-000000000001", "source")
+            .unwrap();
+        solution
+            .remove_project("B0000000-0000-0000-0000-000000000001")
+            .unwrap();
+        let expected = SLN
+            .replace("\"src\", \"src\"", "\"source\", \"source\"")
+            .replace(
+                "Project(\"{FAE04EC0-301F-11D3-BA7A-00C04FC2CCAE}\") = \"Api\", \"src\\Api\\Api.csproj\", \"{B0000000-0000-0000-0000-000000000001}\"\r\n\tProjectSection(ProjectDependencies) = postProject\r\n\t\t{B0000000-0000-0000-0000-000000000002} = {B0000000-0000-0000-0000-000000000002}\r\n\tEndProjectSection\r\nEndProject\r\n",
+                "",
+            )
+            .replace("\t\t{B0000000-0000-0000-0000-000000000001}.Debug|Any CPU.ActiveCfg = Debug|Any CPU\r\n\t\t{B0000000-0000-0000-0000-000000000001}.Debug|Any CPU.Build.0 = Debug|Any CPU\r\n\t\t{B0000000-0000-0000-0000-000000000001}.Release|Any CPU.ActiveCfg = Release|x64\r\n\t\t{B0000000-0000-0000-0000-000000000001}.Release|Any CPU.Build.0 = Release|x64\r\n", "")
+            .replace("\tGlobalSection(NestedProjects) = preSolution\r\n\t\t{B0000000-0000-0000-0000-000000000001} = {A0000000-0000-0000-0000-000000000001}\r\n\t\t{B0000000-0000-0000-0000-000000000002} = {A0000000-0000-0000-0000-000000000001}\r\n\tEndGlobalSection\r\n", "");
+        assert_eq!(solution.to_text(), expected);
+    }
+
+    #[test]
+    fn test_relative_path() {
+        let solution = sln();
+        assert_eq!(
+            solution.relative_path(Path::new("/repo/src/Api/Api.csproj")),
+            PathBuf::from("src/Api/Api.csproj")
+        );
+        assert_eq!(
+            solution.relative_path(Path::new("/shared/Lib/../Lib/Lib.csproj")),
+            PathBuf::from("../shared/Lib/Lib.csproj")
+        );
+        let project = SolutionProject {
+            path: PathBuf::from("../shared/Lib/Lib.csproj"),
+            ..solution.project("Api").unwrap().clone()
+        };
+        assert_eq!(
+            solution.project_abs_path(&project),
+            PathBuf::from("/shared/Lib/Lib.csproj")
+        );
+    }
+
+    #[test]
+    fn test_add_project_to_empty_sln() {
+        let text = "\nMicrosoft Visual Studio Solution File, Format Version 12.00\n# Visual Studio Version 17\nGlobal\n\tGlobalSection(SolutionProperties) = preSolution\n\t\tHideSolutionNode = FALSE\n\tEndGlobalSection\nEndGlobal\n";
+        let mut solution = SolutionFile::parse(Path::new("/repo/Empty.sln"), text).unwrap();
+        let id = solution
+            .add_project(Path::new("App/App.csproj"), None)
+            .unwrap();
+        let folder = solution.add_folder("src", None).unwrap();
+        solution.move_item(&id, Some(&folder)).unwrap();
+
+        assert_eq!(
+            solution.to_text(),
+            format!(
+                "\nMicrosoft Visual Studio Solution File, Format Version 12.00\n# Visual Studio Version 17\n\
+                Project(\"{{FAE04EC0-301F-11D3-BA7A-00C04FC2CCAE}}\") = \"App\", \"App\\App.csproj\", \"{{{id}}}\"\nEndProject\n\
+                Project(\"{{2150E333-8FDC-42A3-9474-1A3956D46DE8}}\") = \"src\", \"src\", \"{{{folder}}}\"\nEndProject\n\
+                Global\n\
+                \tGlobalSection(SolutionConfigurationPlatforms) = preSolution\n\
+                \t\tDebug|Any CPU = Debug|Any CPU\n\
+                \t\tRelease|Any CPU = Release|Any CPU\n\
+                \tEndGlobalSection\n\
+                \tGlobalSection(ProjectConfigurationPlatforms) = postSolution\n\
+                \t\t{{{id}}}.Debug|Any CPU.ActiveCfg = Debug|Any CPU\n\
+                \t\t{{{id}}}.Debug|Any CPU.Build.0 = Debug|Any CPU\n\
+                \t\t{{{id}}}.Release|Any CPU.ActiveCfg = Release|Any CPU\n\
+                \t\t{{{id}}}.Release|Any CPU.Build.0 = Release|Any CPU\n\
+                \tEndGlobalSection\n\
+                \tGlobalSection(SolutionProperties) = preSolution\n\
+                \t\tHideSolutionNode = FALSE\n\
+                \tEndGlobalSection\n\
+                \tGlobalSection(NestedProjects) = preSolution\n\
+                \t\t{{{id}}} = {{{folder}}}\n\
+                \tEndGlobalSection\n\
+                EndGlobal\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_slnx() {
+        let solution = slnx();
+        assert_eq!(solution.format, SolutionFormat::Slnx);
+        assert_eq!(solution.to_text(), SLNX);
+        assert_eq!(
+            solution.configurations,
+            vec![
+                SolutionConfiguration::new("Debug", "Any CPU"),
+                SolutionConfiguration::new("Debug", "x64"),
+                SolutionConfiguration::new("Release", "Any CPU"),
+                SolutionConfiguration::new("Release", "x64"),
+            ]
+        );
+        assert_eq!(
+            solution
+                .folders
+                .iter()
+                .map(|folder| (
+                    folder.id.as_str(),
+                    folder.name.as_str(),
+                    folder.parent.as_deref()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                ("/Solution Items/", "Solution Items", None),
+                ("/src/", "src", None),
+                ("/src/tests/", "tests", Some("/src/")),
+            ]
+        );
+
+        let api = solution.project("Api").unwrap();
+        assert_eq!(api.id, "src/Api/Api.csproj");
+        assert_eq!(api.parent.as_deref(), Some("/src/"));
+        assert_eq!(
+            api.dependencies,
+            vec!["C0000000-0000-0000-0000-000000000001"]
+        );
+        assert_eq!(
+            solution
+                .project_configuration(&api.id, &SolutionConfiguration::new("Debug", "x64"))
+                .unwrap()
+                .project,
+            SolutionConfiguration::new("Debug", "x64")
+        );
+        let tool = solution.project("Tool").unwrap();
+        assert_eq!(tool.parent, None);
+        assert!(
+            !solution
+                .project_configuration(&tool.id, &SolutionConfiguration::new("Release", "x64"))
+                .unwrap()
+                .build
+        );
+    }
+
+    #[test]
+    fn test_edit_slnx() {
+        let mut solution = slnx();
+        let project = solution
+            .add_project(Path::new("src\\Worker\\Worker.csproj"), Some("/src/tests/"))
+            .unwrap();
+        assert_eq!(project, "src/Worker/Worker.csproj");
+        solution
+            .add_project(Path::new("Cli/Cli.csproj"), None)
+            .unwrap();
+        solution.add_folder("docs", None).unwrap();
+        assert_eq!(
+            solution.to_text(),
+            SLNX.replace(
+                "  <Folder Name=\"/src/tests/\" />\n",
+                "  <Folder Name=\"/src/tests/\">\n    <Project Path=\"src/Worker/Worker.csproj\" />\n  </Folder>\n  <Folder Name=\"/docs/\" />\n",
+            )
+            .replace(
+                "  </Project>\n</Solution>",
+                "  </Project>\n  <Project Path=\"Cli/Cli.csproj\" />\n</Solution>",
+            )
+        );
+
+        solution.move_item("src/Api/Api.csproj", None).unwrap();
+        solution.rename_folder("/src/", "source").unwrap();
+        solution
+            .remove_project("C0000000-0000-0000-0000-000000000001")
+            .unwrap();
+        assert_eq!(solution.project("Api").unwrap().parent, None);
+        assert!(solution.to_text().contains(
+            "  <Project Path=\"Cli/Cli.csproj\" />\n  <Project Path=\"src/Api/Api.csproj\">\n    <Platform Solution=\"*|x64\" Project=\"x64\" />\n  </Project>\n</Solution>"
+        ));
+        assert!(
+            solution
+                .to_text()
+                .contains("<Folder Name=\"/source/tests/\">")
+        );
+
+        solution.remove_folder("/source/").unwrap();
+        assert!(solution.folder_by_id("/source/tests/").is_none());
+        assert_eq!(
+            solution
+                .projects
+                .iter()
+                .map(|project| project.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Tool", "Cli", "Api"]
+        );
+        assert!(solution.move_item("/docs/", Some("/docs/")).is_err());
+    }
+}
@@ -0,0 +1,191 @@
+//! Startup projects of a solution.
+//!
+//! Like Visual Studio's "multiple startup projects", each project of a solution
+//! can be started with or without debugging when the solution is started.
+//! Visual Studio keeps this choice in its per-user `.suo` file; here it is a
+//! serializable value that callers persist alongside their workspace.
+
+use crate::solution::{SolutionFile, SolutionProject};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// What starting the solution does with a project.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupAction {
+    #[default]
+    None,
+    Start,
+    StartWithoutDebugging,
+}
+
+impl StartupAction {
+    pub const ALL: [Self; 3] = [Self::Start, Self::StartWithoutDebugging, Self::None];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::None => "None",
+            Self::Start => "Start",
+            Self::StartWithoutDebugging => "Start Without Debugging",
+        }
+    }
+}
+
+/// A project started with the solution.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StartupProject {
+    /// Path to the project file relative to the solution directory, as in
+    /// [`SolutionProject::path`].
+    pub path: PathBuf,
+    pub action: StartupAction,
+}
+
+/// The projects started with a solution, in launch order.
+///
+/// Projects with [`StartupAction::None`] are not stored.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct StartupProjects(Vec<StartupProject>);
+
+impl StartupProjects {
+    /// A single project started with debugging, Visual Studio's "Set as
+    /// Startup Project".
+    pub fn single(path: impl Into<PathBuf>) -> Self {
+        Self(vec![StartupProject {
+            path: path.into(),
+            action: StartupAction::Start,
+        }])
+    }
+
+    /// The startup project chosen when none was chosen yet: the first project
+    /// that `is_launchable` accepts, such as the first executable that is not
+    /// a test project.
+    pub fn default_for(
+        solution: &SolutionFile,
+        is_launchable: impl Fn(&SolutionProject) -> bool,
+    ) -> Self {
+        solution
+            .projects
+            .iter()
+            .find(|project| is_launchable(project))
+            .map(|project| Self::single(&project.path))
+            .unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &StartupProject> {
+        self.0.iter()
+    }
+
+    pub fn action(&self, path: &Path) -> StartupAction {
+        self.0
+            .iter()
+            .find(|project| same_path(&project.path, path))
+            .map_or(StartupAction::None, |project| project.action)
+    }
+
+    /// Changes what starting the solution does with a project. Projects newly
+    /// started are launched after the others.
+    pub fn set_action(&mut self, path: &Path, action: StartupAction) {
+        let existing = self
+            .0
+            .iter()
+            .position(|project| same_path(&project.path, path));
+        match (existing, action) {
+            (Some(ix), StartupAction::None) => {
+                self.0.remove(ix);
+            }
+            (Some(ix), action) => self.0[ix].action = action,
+            (None, StartupAction::None) => {}
+            (None, action) => self.0.push(StartupProject {
+                path: path.to_path_buf(),
+                action,
+            }),
+        }
+    }
+
+    /// Drops projects that are no longer part of `solution`.
+    pub fn retain_solution_projects(&mut self, solution: &SolutionFile) {
+        self.0
+            .retain(|startup| solution.project_by_path(&startup.path).is_some());
+    }
+
+    /// The project a debugger attaches to when only one can be debugged: the
+    /// first one started with debugging.
+    pub fn debug_target(&self) -> Option<&StartupProject> {
+        self.0
+            .iter()
+            .find(|project| project.action == StartupAction::Start)
+    }
+}
+
+/// Solution files are commonly edited on Windows, so project paths compare
+/// case-insensitively.
+fn same_path(a: &Path, b: &Path) -> bool {
+    a.to_string_lossy()
+        .eq_ignore_ascii_case(&b.to_string_lossy())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const SLNX: &str = r#"<Solution>
+  <Project Path="src/Lib/Lib.csproj" />
+  <Project Path="src/Api/Api.csproj" />
+  <Project Path="src/Worker/Worker.csproj" />
+  <Project Path="tests/Api.Tests/Api.Tests.csproj" />
+</Solution>
+"#;
+
+    #[test]
+    fn test_startup_projects() {
+        let solution = SolutionFile::parse(Path::new("/repo/App.slnx"), SLNX).unwrap();
+
+        let mut startup = StartupProjects::default_for(&solution, |project| {
+            project.name != "Lib" && !project.name.ends_with(".Tests")
+        });
+        assert_eq!(startup, StartupProjects::single("src/Api/Api.csproj"));
+
+        startup.set_action(
+            Path::new("src/Worker/Worker.csproj"),
+            StartupAction::StartWithoutDebugging,
+        );
+        startup.set_action(Path::new("src/worker/worker.csproj"), StartupAction::Start);
+        assert_eq!(
+            startup.action(Path::new("src/Worker/Worker.csproj")),
+            StartupAction::Start
+        );
+        assert_eq!(
+            startup.action(Path::new("src/Lib/Lib.csproj")),
+            StartupAction::None
+        );
+
+        startup.set_action(Path::new("src/Api/Api.csproj"), StartupAction::None);
+        assert_eq!(
+            startup.debug_target().map(|project| project.path.as_path()),
+            Some(Path::new("src/Worker/Worker.csproj"))
+        );
+
+        let json = serde_json::to_string(&startup).unwrap();
+        assert_eq!(
+            json,
+            r#"[{"path":"src/Worker/Worker.csproj","action":"start"}]"#
+        );
+        assert_eq!(
+            serde_json::from_str::<StartupProjects>(&json).unwrap(),
+            startup
+        );
+
+        let mut solution = solution;
+        let worker = solution.projects[2].id.clone();
+        solution.remove_project(&worker).unwrap();
+        startup.retain_solution_projects(&solution);
+        assert!(startup.is_empty());
+        assert_eq!(startup.debug_target(), None);
+    }
+}
@@ -0,0 +1,173 @@
+//! Parsing of the template table printed by `dotnet new list`.
+
+/// A template installed for `dotnet new`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectTemplate {
+    pub name: String,
+    /// Short names accepted by `dotnet new`, e.g. `console`.
+    pub short_names: Vec<String>,
+    pub languages: Vec<String>,
+    /// The language used when `--language` is not passed (shown in brackets).
+    pub default_language: Option<String>,
+    pub tags: Vec<String>,
+}
+
+impl ProjectTemplate {
+    pub fn short_name(&self) -> &str {
+        self.short_names
+            .first()
+            .map(String::as_str)
+            .unwrap_or(&self.name)
+    }
+
+    pub fn is_test_project(&self) -> bool {
+        self.tags.iter().any(|tag| tag.eq_ignore_ascii_case("Test"))
+    }
+}
+
+/// Parses the output of `dotnet new list`.
+///
+/// Column boundaries are taken from the dashed line below the header, since
+/// template names and tags may themselves contain single spaces.
+pub fn parse_template_list(output: &str) -> Vec<ProjectTemplate> {
+    let lines = output.lines().collect::<Vec<_>>();
+    let Some(separator_ix) = lines.iter().position(|line| {
+        let line = line.trim();
+        line.starts_with("---") && line.chars().all(|c| c == '-' || c.is_whitespace())
+    }) else {
+        return Vec::new();
+    };
+    let Some(header) = separator_ix
+        .checked_sub(1)
+        .and_then(|ix| lines.get(ix))
+        .map(|line| line.chars().collect::<Vec<_>>())
+    else {
+        return Vec::new();
+    };
+
+    let separator = lines[separator_ix].chars().collect::<Vec<_>>();
+    let mut columns = Vec::new();
+    let mut start = None;
+    for (ix, c) in separator.iter().enumerate() {
+        match (c, start) {
+            ('-', None) => start = Some(ix),
+            ('-', Some(_)) => {}
+            (_, Some(column_start)) => {
+                columns.push(column_start..ix);
+                start = None;
+            }
+            (_, None) => {}
+        }
+    }
+    if let Some(column_start) = start {
+        columns.push(column_start..separator.len());
+    }
+
+    let cell = |row: &[char], column: usize| -> String {
+        let start = columns[column].start.min(row.len());
+        let end = columns
+            .get(column + 1)
+            .map_or(row.len(), |next| next.start.min(row.len()));
+        row[start..end]
+            .iter()
+            .collect::<String>()
+            .trim()
+            .to_string()
+    };
+    let column_named =
+        |name: &str| (0..columns.len()).find(|ix| cell(&header, *ix).eq_ignore_ascii_case(name));
+    let (Some(name_column), Some(short_name_column)) =
+        (column_named("Template Name"), column_named("Short Name"))
+    else {
+        return Vec::new();
+    };
+    let language_column = column_named("Language");
+    let tags_column = column_named("Tags");
+
+    lines[separator_ix + 1..]
+        .iter()
+        .map(|line| line.chars().collect::<Vec<_>>())
+        .take_while(|row| !row.iter().all(|c| c.is_whitespace()))
+        .filter_map(|row| {
+            let name = cell(&row, name_column);
+            let short_names = split(&cell(&row, short_name_column), ',');
+            if name.is_empty() || short_names.is_empty() {
+                return None;
+            }
+            let languages_cell = language_column
+                .map(|column| cell(&row, column))
+                .unwrap_or_default();
+            let default_language = split(&languages_cell, ',')
+                .into_iter()
+                .find(|language| language.starts_with('['))
+                .map(|language| language.trim_matches(['[', ']']).to_string());
+            Some(ProjectTemplate {
+                name,
+                short_names,
+                languages: split(&languages_cell, ',')
+                    .into_iter()
+                    .map(|language| language.trim_matches(['[', ']']).to_string())
+                    .collect(),
+                default_language,
+                tags: tags_column
+                    .map(|column| split(&cell(&row, column), '/'))
+                    .unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+fn split(value: &str, separator: char) -> Vec<String> {
+    value
+        .split(separator)
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_template_list() {
+        let output = "\
+These templates matched your input: --type='project'
+
+Template Name                                 Short Name                  Language    Tags
+--------------------------------------------  --------------------------  ----------  -------------------------
+ASP.NET Core Web API                          webapi                      [C#],F#     Web/Web API/API/Service
+Class Library                                 classlib                    [C#],F#,VB  Common/Library
+Console App                                   console                     [C#],F#,VB  Common/Console
+NUnit 3 Test Project                          nunit                       [C#],F#,VB  Test/NUnit/Desktop/Web
+Windows Forms App                             winforms                    [C#],VB     Common/WinForms
+dotnet local tool manifest file               tool-manifest,toolmanifest              Config
+
+";
+        let templates = parse_template_list(output);
+        assert_eq!(templates.len(), 6);
+        assert_eq!(
+            templates[0],
+            ProjectTemplate {
+                name: "ASP.NET Core Web API".into(),
+                short_names: vec!["webapi".into()],
+                languages: vec!["C#".into(), "F#".into()],
+                default_language: Some("C#".into()),
+                tags: vec![
+                    "Web".into(),
+                    "Web API".into(),
+                    "API".into(),
+                    "Service".into()
+                ],
+            }
+        );
+        assert!(templates[3].is_test_project());
+        assert_eq!(templates[5].short_name(), "tool-manifest");
+        assert_eq!(templates[5].languages, Vec::<String>::new());
+        assert_eq!(templates[5].default_language, None);
+
+        assert_eq!(parse_template_list("No templates found."), Vec::new());
+    }
+}
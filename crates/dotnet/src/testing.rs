@@ -0,0 +1,590 @@
+//! Tests of .NET test projects, as `dotnet test` sees them.
+//!
+//! `dotnet test` runs tests through VSTest, whatever the framework: xUnit,
+//! NUnit or MSTest. VSTest names each test by its fully qualified name,
+//! `Namespace.Class.Method`, which is what its `--filter` option matches and
+//! what its TRX logger reports results for:
+//!
+//! ```text
+//! $ dotnet test --list-tests
+//! The following Tests are available:
+//!     Shop.Tests.OrderTests.Total_IncludesTax
+//!     Shop.Tests.OrderTests.Discount(percent: 10)
+//! ```
+//!
+//! This module discovers tests, from that list or from the attributes in the
+//! source, selects them for a run and reads back their results.
+
+use anyhow::{Context as _, Result};
+use std::{path::Path, time::Duration};
+
+/// Makes the test host wait for a debugger after printing its process id,
+/// which [`parse_test_host_process_id`] reads from the output of `dotnet test`.
+pub const VSTEST_HOST_DEBUG: &str = "VSTEST_HOST_DEBUG";
+
+/// The line of `dotnet test --list-tests` after which the tests are listed.
+const TEST_LIST_HEADER: &str = "The following Tests are available:";
+
+/// Attributes marking test methods in xUnit, NUnit and MSTest.
+const TEST_ATTRIBUTES: &[&str] = &[
+    "Fact",
+    "Theory",
+    "SkippableFact",
+    "SkippableTheory",
+    "Test",
+    "TestCase",
+    "TestCaseSource",
+    "TestMethod",
+    "DataTestMethod",
+];
+
+/// A test method declared in a source file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TestMethod {
+    /// The fully qualified name of the declaring class. Nested classes are
+    /// separated by `+`, as in VSTest's names.
+    pub class_name: String,
+    pub method: String,
+    /// The zero-based line of the method declaration.
+    pub line: u32,
+}
+
+impl TestMethod {
+    pub fn fully_qualified_name(&self) -> String {
+        format!("{}.{}", self.class_name, self.method)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TestOutcome {
+    Passed,
+    Skipped,
+    Failed,
+}
+
+/// The result of a test in a TRX report.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TestResult {
+    /// `Namespace.Class.Method`. Every case of a parameterized test has the
+    /// same name.
+    pub fully_qualified_name: String,
+    /// The name of the test case, which includes the arguments of a
+    /// parameterized test.
+    pub display_name: String,
+    pub outcome: TestOutcome,
+    pub duration: Option<Duration>,
+    pub message: Option<String>,
+    pub stack_trace: Option<String>,
+}
+
+/// Parses the fully qualified names printed by `dotnet test --list-tests`.
+///
+/// The arguments of parameterized test cases are dropped, so that each test
+/// method is listed once.
+pub fn parse_test_list(output: &str) -> Vec<String> {
+    let mut tests = Vec::<String>::new();
+    let mut in_list = false;
+    for line in output.lines() {
+        if line.trim() == TEST_LIST_HEADER {
+            in_list = true;
+            continue;
+        }
+        // Tests are indented, the lines of the next project's build are not
+        if !in_list || !line.starts_with(char::is_whitespace) {
+            in_list = false;
+            continue;
+        }
+        let name = strip_test_arguments(line.trim());
+        if !name.is_empty() && !tests.iter().any(|test| test == name) {
+            tests.push(name.to_string());
+        }
+    }
+    tests
+}
+
+/// Splits a fully qualified test name into its class and method names.
+pub fn split_test_name(fully_qualified_name: &str) -> Option<(&str, &str)> {
+    strip_test_arguments(fully_qualified_name).rsplit_once('.')
+}
+
+fn strip_test_arguments(name: &str) -> &str {
+    name.split_once('(')
+        .map_or(name, |(name, _)| name)
+        .trim_end()
+}
+
+/// Finds the test methods declared in C# source.
+///
+/// This is a line-based scan for test attributes, not a parser: it is meant to
+/// show tests before a project is built, and to place results in an editor.
+pub fn find_test_methods(source: &str) -> Vec<TestMethod> {
+    enum Scope {
+        Namespace(String),
+        Class(String),
+        Other,
+    }
+
+    let mut tests = Vec::new();
+    let mut file_namespace = None::<String>;
+    let mut scopes = Vec::<Scope>::new();
+    let mut pending_scope = None::<Scope>;
+    let mut pending_test = false;
+    let mut in_block_comment = false;
+    for (line_ix, line) in source.lines().enumerate() {
+        let code = strip_comments_and_strings(line, &mut in_block_comment);
+        let (attributes, declaration) = split_attributes(code.trim());
+        if attributes
+            .iter()
+            .any(|attribute| TEST_ATTRIBUTES.contains(attribute))
+        {
+            pending_test = true;
+        }
+
+        if let Some(namespace) = declared_name(declaration, &["namespace"]) {
+            if declaration.ends_with(';') {
+                file_namespace = Some(namespace.to_string());
+            } else {
+                pending_scope = Some(Scope::Namespace(namespace.to_string()));
+            }
+        } else if let Some(class) = declared_name(declaration, &["class", "record", "struct"]) {
+            pending_scope = Some(Scope::Class(class.to_string()));
+            pending_test = false;
+        } else if pending_test && let Some(method) = declared_method(declaration) {
+            pending_test = false;
+            let namespaces = file_namespace
+                .iter()
+                .map(String::as_str)
+                .chain(scopes.iter().filter_map(|scope| match scope {
+                    Scope::Namespace(namespace) => Some(namespace.as_str()),
+                    _ => None,
+                }))
+                .collect::<Vec<_>>();
+            let classes = scopes
+                .iter()
+                .filter_map(|scope| match scope {
+                    Scope::Class(class) => Some(class.as_str()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            if !classes.is_empty() {
+                let mut class_name = namespaces.join(".");
+                if !class_name.is_empty() {
+                    class_name.push('.');
+                }
+                class_name.push_str(&classes.join("+"));
+                tests.push(TestMethod {
+                    class_name,
+                    method: method.to_string(),
+                    line: line_ix as u32,
+                });
+            }
+        }
+
+        for char in code.chars() {
+            match char {
+                '{' => scopes.push(pending_scope.take().unwrap_or(Scope::Other)),
+                '}' => {
+                    scopes.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+    tests
+}
+
+/// Blanks out comments and the contents of string and character literals, so
+/// that braces inside them are not counted.
+fn strip_comments_and_strings(line: &str, in_block_comment: &mut bool) -> String {
+    let mut code = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    let mut quote = None;
+    while let Some(char) = chars.next() {
+        if *in_block_comment {
+            if char == '*' && chars.peek() == Some(&'/') {
+                chars.next();
+                *in_block_comment = false;
+            }
+            continue;
+        }
+        match (quote, char) {
+            (Some(_), '\\') => {
+                chars.next();
+            }
+            (Some(open), char) if char == open => {
+                quote = None;
+                code.push(char);
+            }
+            (Some(_), _) => {}
+            (None, '"' | '\'') => {
+                quote = Some(char);
+                code.push(char);
+            }
+            (None, '/') if chars.peek() == Some(&'/') => break,
+            (None, '/') if chars.peek() == Some(&'*') => {
+                chars.next();
+                *in_block_comment = true;
+            }
+            (None, char) => code.push(char),
+        }
+    }
+    code
+}
+
+/// Splits the attribute names off the start of a line, as in
+/// `[Fact, Trait("Category", "Unit")] public void Test()`.
+fn split_attributes(mut line: &str) -> (Vec<&str>, &str) {
+    let mut attributes = Vec::new();
+    while let Some(rest) = line.strip_prefix('[') {
+        let Some(close) = rest.find(']') else {
+            break;
+        };
+        for attribute in rest[..close].split(',') {
+            let name = attribute
+                .trim()
+                .split(|char: char| !is_identifier_char(char) && char != '.')
+                .next()
+                .unwrap_or_default();
+            let name = name.rsplit('.').next().unwrap_or(name);
+            attributes.push(name.strip_suffix("Attribute").unwrap_or(name));
+        }
+        line = rest[close + 1..].trim_start();
+    }
+    (attributes, line)
+}
+
+/// The name declared after one of `keywords`, as in `public sealed class Name<T>`.
+fn declared_name<'a>(declaration: &'a str, keywords: &[&str]) -> Option<&'a str> {
+    let mut words = declaration.split_whitespace();
+    while let Some(word) = words.next() {
+        if keywords.contains(&word) {
+            let name = words.next()?;
+            let end = name
+                .find(|char: char| !is_identifier_char(char) && char != '.')
+                .unwrap_or(name.len());
+            return Some(&name[..end]).filter(|name| !name.is_empty());
+        }
+        // Constraints such as `where T : class` declare nothing
+        if word == "where" || word.contains('(') {
+            return None;
+        }
+    }
+    None
+}
+
+/// The name of the method declared on a line, as in
+/// `public async Task Total_IncludesTax()`.
+fn declared_method(declaration: &str) -> Option<&str> {
+    let (signature, _) = declaration.split_once('(')?;
+    let signature = signature.trim_end();
+    // Skip generic parameters, as in `Parse<T>`
+    let signature = match signature.strip_suffix('>') {
+        Some(signature) => &signature[..signature.rfind('<')?],
+        None => signature,
+    };
+    let start = signature
+        .rfind(|char: char| !is_identifier_char(char))
+        .map_or(0, |ix| ix + 1);
+    let name = &signature[start..];
+    // A method has a return type before its name
+    (!name.is_empty() && start > 0).then_some(name)
+}
+
+fn is_identifier_char(char: char) -> bool {
+    char.is_alphanumeric() || char == '_'
+}
+
+/// A VSTest filter running exactly the tests with the given fully qualified
+/// names.
+pub fn test_name_filter<'a>(names: impl IntoIterator<Item = &'a str>) -> String {
+    names
+        .into_iter()
+        .map(|name| format!("FullyQualifiedName={}", escape_filter_value(name)))
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
+/// A VSTest filter running the tests of a namespace or class.
+pub fn test_prefix_filter(prefix: &str) -> String {
+    format!("FullyQualifiedName~{}.", escape_filter_value(prefix))
+}
+
+fn escape_filter_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for char in value.chars() {
+        if matches!(char, '\\' | '(' | ')' | '&' | '|' | '=' | '!' | '~') {
+            escaped.push('\\');
+        }
+        escaped.push(char);
+    }
+    escaped
+}
+
+/// `dotnet test` arguments that write a TRX report of the run to `report_path`.
+pub fn trx_logger_args(report_path: &Path) -> Vec<String> {
+    vec![
+        "--logger".to_string(),
+        format!("trx;LogFileName={}", report_path.display()),
+    ]
+}
+
+/// Reads the id of the test host process from a line of `dotnet test` output
+/// when [`VSTEST_HOST_DEBUG`] is set:
+///
+/// ```text
+/// Process Id: 4242, Name: testhost
+/// ```
+pub fn parse_test_host_process_id(line: &str) -> Option<u32> {
+    let (_, rest) = line.split_once("Process Id:")?;
+    let rest = rest.trim_start();
+    let end = rest
+        .find(|char: char| !char.is_ascii_digit())
+        .unwrap_or(rest.len());
+    rest[..end].parse().ok()
+}
+
+/// Parses the results of a TRX report, written by `dotnet test --logger trx`.
+pub fn parse_trx(content: &str) -> Result<Vec<TestResult>> {
+    let document = roxmltree::Document::parse(content.trim_start_matches('\u{feff}'))
+        .context("parsing TRX report")?;
+    let test_method = |test_id: &str| {
+        document
+            .descendants()
+            .filter(|node| node.has_tag_name("UnitTest"))
+            .find(|node| node.attribute("id") == Some(test_id))?
+            .children()
+            .find(|node| node.has_tag_name("TestMethod"))
+    };
+    let child_text = |node: roxmltree::Node, path: &[&str]| {
+        let mut node = node;
+        for name in path {
+            node = node.children().find(|child| child.has_tag_name(*name))?;
+        }
+        node.text()
+            .map(str::trim)
+            .filter(|text| !text.is_empty())
+            .map(str::to_string)
+    };
+
+    let results = document
+        .descendants()
+        .filter(|node| node.has_tag_name("UnitTestResult"))
+        .filter_map(|result| {
+            let display_name = result.attribute("testName")?.to_string();
+            let method = result.attribute("testId").and_then(test_method);
+            // MSTest qualifies the class with its assembly
+            let fully_qualified_name = method
+                .and_then(|method| {
+                    let class_name = method.attribute("className")?;
+                    let class_name = class_name.split(',').next()?.trim();
+                    Some(format!("{class_name}.{}", method.attribute("name")?))
+                })
+                .unwrap_or_else(|| strip_test_arguments(&display_name).to_string());
+            let outcome = match result.attribute("outcome") {
+                Some("Passed") => TestOutcome::Passed,
+                Some("NotExecuted" | "Inconclusive" | "Pending" | "Disconnected") => {
+                    TestOutcome::Skipped
+                }
+                _ => TestOutcome::Failed,
+            };
+            Some(TestResult {
+                fully_qualified_name,
+                display_name,
+                outcome,
+                duration: result.attribute("duration").and_then(parse_duration),
+                message: child_text(result, &["Output", "ErrorInfo", "Message"]),
+                stack_trace: child_text(result, &["Output", "ErrorInfo", "StackTrace"]),
+            })
+        })
+        .collect();
+    Ok(results)
+}
+
+/// Parses a TRX duration, `hh:mm:ss.fffffff`.
+fn parse_duration(duration: &str) -> Option<Duration> {
+    let mut parts = duration.splitn(3, ':');
+    let hours = parts.next()?.parse::<u64>().ok()?;
+    let minutes = parts.next()?.parse::<u64>().ok()?;
+    let seconds = parts.next()?.parse::<f64>().ok()?;
+    Some(Duration::from_secs(hours * 3600 + minutes * 60) + Duration::from_secs_f64(seconds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_test_list() {
+        let output = "\
+  Determining projects to restore...
+  Shop.Tests -> /src/Shop.Tests/bin/Debug/net8.0/Shop.Tests.dll
+Test run for /src/Shop.Tests/bin/Debug/net8.0/Shop.Tests.dll (.NETCoreApp,Version=v8.0)
+The following Tests are available:
+    Shop.Tests.OrderTests.Total_IncludesTax
+    Shop.Tests.OrderTests.Discount(percent: 10)
+    Shop.Tests.OrderTests.Discount(percent: 20)
+    Shop.Tests.Nested.Outer+Inner.Works
+Test run for /src/Shop.Tests/bin/Debug/net9.0/Shop.Tests.dll (.NETCoreApp,Version=v9.0)
+The following Tests are available:
+    Shop.Tests.OrderTests.Total_IncludesTax
+";
+        assert_eq!(
+            parse_test_list(output),
+            [
+                "Shop.Tests.OrderTests.Total_IncludesTax",
+                "Shop.Tests.OrderTests.Discount",
+                "Shop.Tests.Nested.Outer+Inner.Works",
+            ]
+        );
+        assert_eq!(
+            split_test_name("Shop.Tests.OrderTests.Discount(percent: 1.5)"),
+            Some(("Shop.Tests.OrderTests", "Discount"))
+        );
+    }
+
+    #[test]
+    fn test_find_test_methods() {
+        let source = r#"
+using Xunit;
+
+namespace Shop.Tests
+{
+    public class OrderTests
+    {
+        private const string Brace = "{";
+
+        [Fact]
+        public void Total_IncludesTax()
+        {
+            Assert.Equal(110, new Order(100).Total);
+        }
+
+        [Theory]
+        [InlineData(10)]
+        public async Task Discount(int percent) { }
+
+        // [Fact] public void Commented() { }
+        public void Helper() { }
+
+        public class Refunds
+        {
+            [Fact, Trait("Category", "Slow")] public void Full() { }
+        }
+    }
+}
+
+namespace Shop.Tests.Other
+{
+    [TestClass]
+    public sealed class Generic<T> where T : class
+    {
+        [TestMethod]
+        [Timeout(100)]
+        public void Works<TValue>() { }
+    }
+}
+"#;
+        let tests = find_test_methods(source)
+            .into_iter()
+            .map(|test| (test.fully_qualified_name(), test.line))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            tests,
+            [
+                ("Shop.Tests.OrderTests.Total_IncludesTax".to_string(), 10),
+                ("Shop.Tests.OrderTests.Discount".to_string(), 17),
+                ("Shop.Tests.OrderTests+Refunds.Full".to_string(), 24),
+                ("Shop.Tests.Other.Generic.Works".to_string(), 36),
+            ]
+        );
+
+        let file_scoped =
+            "namespace Shop.Tests;\n\npublic class A\n{\n    [Test]\n    public void B() { }\n}\n";
+        assert_eq!(
+            find_test_methods(file_scoped),
+            [TestMethod {
+                class_name: "Shop.Tests.A".to_string(),
+                method: "B".to_string(),
+                line: 5,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_filters() {
+        assert_eq!(
+            test_name_filter(["Shop.OrderTests.Total", "Shop.OrderTests.A=B"]),
+            "FullyQualifiedName=Shop.OrderTests.Total|FullyQualifiedName=Shop.OrderTests.A\\=B"
+        );
+        assert_eq!(
+            test_prefix_filter("Shop.OrderTests"),
+            "FullyQualifiedName~Shop.OrderTests."
+        );
+        assert_eq!(
+            parse_test_host_process_id("Process Id: 4242, Name: testhost"),
+            Some(4242)
+        );
+        assert_eq!(
+            parse_test_host_process_id("Host debugging is enabled."),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_trx() {
+        let trx = r#"<?xml version="1.0" encoding="utf-8"?>
+<TestRun id="1" name="run" xmlns="http://microsoft.com/schemas/VisualStudio/TeamTest/2010">
+  <Results>
+    <UnitTestResult executionId="e1" testId="t1" testName="Shop.Tests.OrderTests.Total_IncludesTax" duration="00:00:00.0123000" outcome="Passed" />
+    <UnitTestResult executionId="e2" testId="t2" testName="Discount(10)" duration="00:00:01.5000000" outcome="Failed">
+      <Output>
+        <ErrorInfo>
+          <Message>Assert.Equal() Failure
+Expected: 90</Message>
+          <StackTrace>   at Shop.Tests.OrderTests.Discount(Int32 percent) in /src/OrderTests.cs:line 19</StackTrace>
+        </ErrorInfo>
+      </Output>
+    </UnitTestResult>
+    <UnitTestResult executionId="e3" testId="t3" testName="Ignored" outcome="NotExecuted" />
+  </Results>
+  <TestDefinitions>
+    <UnitTest name="Total_IncludesTax" id="t1">
+      <TestMethod codeBase="Shop.Tests.dll" className="Shop.Tests.OrderTests" name="Total_IncludesTax" />
+    </UnitTest>
+    <UnitTest name="Discount" id="t2">
+      <TestMethod codeBase="Shop.Tests.dll" className="Shop.Tests.OrderTests, Shop.Tests, Version=1.0.0.0" name="Discount" />
+    </UnitTest>
+  </TestDefinitions>
+</TestRun>"#;
+        let results = parse_trx(trx).unwrap();
+        assert_eq!(
+            results
+                .iter()
+                .map(|result| (
+                    result.fully_qualified_name.as_str(),
+                    result.outcome,
+                    result.duration
+                ))
+                .collect::<Vec<_>>(),
+            [
+                (
+                    "Shop.Tests.OrderTests.Total_IncludesTax",
+                    TestOutcome::Passed,
+                    Some(Duration::from_micros(12300))
+                ),
+                (
+                    "Shop.Tests.OrderTests.Discount",
+                    TestOutcome::Failed,
+                    Some(Duration::from_millis(1500))
+                ),
+                ("Ignored", TestOutcome::Skipped, None),
+            ]
+        );
+        assert_eq!(
+            results[1].message.as_deref(),
+            Some("Assert.Equal() Failure\nExpected: 90")
+        );
+        assert!(results[1].stack_trace.as_ref().unwrap().contains("line 19"));
+    }
+}
@@ -0,0 +1,322 @@
+//! Sessions of `dotnet watch`, as its console output reports them.
+//!
+//! `dotnet watch` builds and runs a project, then applies edits to the running
+//! app with hot reload, or rebuilds and restarts it when an edit can't be
+//! applied, a "rude edit". Its own messages start with `dotnet watch`, usually
+//! followed by an emoji, and are interleaved with the output of the builds and
+//! of the app:
+//!
+//! ```text
+//! dotnet watch 🔥 Hot reload enabled. For a list of supported edits, see https://aka.ms/dotnet/hot-reload.
+//! dotnet watch 🔧 Building...
+//! /src/Api/Program.cs(12,17): error CS1002: ; expected [/src/Api/Api.csproj]
+//! dotnet watch ❌ Build failed
+//! dotnet watch ⏳ Waiting for a file to change before restarting dotnet...
+//! ```
+
+use crate::diagnostics::{BuildDiagnostic, parse_build_output};
+use std::path::{Path, PathBuf};
+
+const MESSAGE_PREFIX: &str = "dotnet watch";
+
+/// Environment variables for a `dotnet watch` whose output is read rather than
+/// shown in a terminal.
+pub const WATCH_ENV: &[(&str, &str)] = &[("DOTNET_WATCH_SUPPRESS_EMOJIS", "1")];
+
+/// The state of a watched project.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WatchStatus {
+    /// Building the project, initially or to restart it.
+    Building,
+    /// The app runs, with no changes applied yet.
+    Running,
+    /// Applying changed files to the running app.
+    Applying,
+    /// The latest changes were hot reloaded into the running app.
+    HotReloaded,
+    /// The latest changes can't be hot reloaded, and the app must restart to
+    /// apply them.
+    RudeEdit,
+    /// The build failed, and the app waits for a change to rebuild.
+    BuildFailed,
+    /// The app exited, and `dotnet watch` waits for a change to restart it.
+    WaitingForChanges,
+    /// `dotnet watch` itself exited.
+    Exited,
+}
+
+impl WatchStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Building => "Building",
+            Self::Running => "Running",
+            Self::Applying => "Applying changes",
+            Self::HotReloaded => "Hot reloaded",
+            Self::RudeEdit => "Restart required",
+            Self::BuildFailed => "Build failed",
+            Self::WaitingForChanges => "Waiting for changes",
+            Self::Exited => "Exited",
+        }
+    }
+
+    /// Whether the app is not running as edited.
+    pub fn is_error(self) -> bool {
+        matches!(self, Self::RudeEdit | Self::BuildFailed)
+    }
+}
+
+/// A message of `dotnet watch`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WatchEvent {
+    /// The project is building.
+    Building,
+    /// The app started.
+    Started,
+    /// Files changed.
+    FileChanged,
+    /// Changes were hot reloaded.
+    HotReloadSucceeded,
+    /// Changes can't be hot reloaded.
+    RudeEdit,
+    /// `dotnet watch` asks whether to restart the app after a rude edit, and
+    /// waits for a [`RestartAnswer`] on its standard input.
+    RestartPrompt,
+    /// The build failed.
+    BuildFailed,
+    /// `dotnet watch` waits for a file to change before building again.
+    WaitingForChanges,
+    /// The app exited.
+    AppExited,
+    /// `dotnet watch` is shutting down.
+    ShuttingDown,
+    /// Any other message, such as a diagnostic of a hot reload.
+    Message(String),
+}
+
+impl WatchEvent {
+    /// The status of the project after this event, if it changes it.
+    pub fn status(&self) -> Option<WatchStatus> {
+        match self {
+            Self::Building => Some(WatchStatus::Building),
+            Self::Started => Some(WatchStatus::Running),
+            Self::FileChanged => Some(WatchStatus::Applying),
+            Self::HotReloadSucceeded => Some(WatchStatus::HotReloaded),
+            Self::RudeEdit | Self::RestartPrompt => Some(WatchStatus::RudeEdit),
+            Self::BuildFailed => Some(WatchStatus::BuildFailed),
+            Self::AppExited => Some(WatchStatus::WaitingForChanges),
+            Self::ShuttingDown => Some(WatchStatus::Exited),
+            Self::WaitingForChanges => None,
+            Self::Message(_) => None,
+        }
+    }
+}
+
+/// The answers `dotnet watch` accepts when asking whether to restart the app.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RestartAnswer {
+    Yes,
+    No,
+    /// Restart now, and whenever changes can't be hot reloaded again.
+    Always,
+    /// Don't restart, now or after later rude edits.
+    Never,
+}
+
+impl RestartAnswer {
+    /// The key that gives this answer.
+    pub fn key(self) -> char {
+        match self {
+            Self::Yes => 'y',
+            Self::No => 'n',
+            Self::Always => 'a',
+            Self::Never => 'v',
+        }
+    }
+}
+
+/// Parses a line of output, returning `None` for lines that are not messages
+/// of `dotnet watch`, such as the output of builds and of the app.
+pub fn parse_watch_line(line: &str) -> Option<WatchEvent> {
+    let message = line.trim().strip_prefix(MESSAGE_PREFIX)?;
+    // Skip the emoji, or the `:` replacing it when emojis are suppressed
+    let message = message
+        .trim_start_matches(|c: char| !c.is_alphanumeric() && c != '[' && c != '/')
+        .trim_end();
+    let lowercase = message.to_lowercase();
+    let event = if lowercase.starts_with("building") || lowercase.starts_with("restarting") {
+        WatchEvent::Building
+    } else if lowercase == "started" || lowercase.starts_with("started ") {
+        WatchEvent::Started
+    } else if lowercase.starts_with("file changed") || lowercase.starts_with("files changed") {
+        WatchEvent::FileChanged
+    } else if lowercase.contains("do you want to restart") {
+        WatchEvent::RestartPrompt
+    } else if lowercase.contains("rude edit") || lowercase.starts_with("unable to apply hot reload")
+    {
+        WatchEvent::RudeEdit
+    } else if lowercase.starts_with("hot reload")
+        && (lowercase.contains("succeeded") || lowercase.contains("handled"))
+    {
+        WatchEvent::HotReloadSucceeded
+    } else if lowercase.starts_with("build failed") {
+        WatchEvent::BuildFailed
+    } else if lowercase.starts_with("waiting for a file to change") {
+        WatchEvent::WaitingForChanges
+    } else if lowercase.starts_with("exited") {
+        WatchEvent::AppExited
+    } else if lowercase.starts_with("shutdown requested") {
+        WatchEvent::ShuttingDown
+    } else {
+        WatchEvent::Message(message.to_string())
+    };
+    Some(event)
+}
+
+/// The output of a `dotnet watch` session, from which the diagnostics of each
+/// build and hot reload are parsed when it finishes.
+pub struct WatchOutput {
+    working_dir: PathBuf,
+    /// The output since the current build or hot reload started.
+    pending: String,
+}
+
+impl WatchOutput {
+    pub fn new(working_dir: &Path) -> Self {
+        Self {
+            working_dir: working_dir.to_path_buf(),
+            pending: String::new(),
+        }
+    }
+
+    /// Adds a line of output, returning its event, if any, and the
+    /// diagnostics of the build or hot reload it finishes.
+    pub fn push_line(&mut self, line: &str) -> (Option<WatchEvent>, Option<Vec<BuildDiagnostic>>) {
+        let event = parse_watch_line(line);
+        let finished = match &event {
+            Some(WatchEvent::Building | WatchEvent::FileChanged) => {
+                self.pending.clear();
+                false
+            }
+            Some(
+                WatchEvent::Started
+                | WatchEvent::HotReloadSucceeded
+                | WatchEvent::RudeEdit
+                | WatchEvent::BuildFailed,
+            ) => true,
+            // Hot reload reports compiler errors as messages of its own
+            Some(WatchEvent::Message(message)) => {
+                self.pending.push_str(message);
+                self.pending.push('\n');
+                false
+            }
+            Some(_) => false,
+            None => {
+                self.pending.push_str(line);
+                self.pending.push('\n');
+                false
+            }
+        };
+        let diagnostics = finished.then(|| {
+            let diagnostics = parse_build_output(&self.pending, &self.working_dir);
+            self.pending.clear();
+            diagnostics
+        });
+        (event, diagnostics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::BuildDiagnosticSeverity;
+
+    #[test]
+    fn test_parse_watch_line() {
+        assert_eq!(
+            parse_watch_line("dotnet watch 🔧 Building..."),
+            Some(WatchEvent::Building)
+        );
+        assert_eq!(
+            parse_watch_line("dotnet watch : Started"),
+            Some(WatchEvent::Started)
+        );
+        assert_eq!(
+            parse_watch_line("dotnet watch ⌚ File changed: ./Program.cs."),
+            Some(WatchEvent::FileChanged)
+        );
+        assert_eq!(
+            parse_watch_line("dotnet watch 🔥 Hot reload of changes succeeded."),
+            Some(WatchEvent::HotReloadSucceeded)
+        );
+        assert_eq!(
+            parse_watch_line("dotnet watch ⌚ Unable to apply hot reload because of a rude edit."),
+            Some(WatchEvent::RudeEdit)
+        );
+        assert_eq!(
+            parse_watch_line(
+                "dotnet watch ⌚ Do you want to restart your app - Yes (y) / No (n) / Always (a) / Never (v)?"
+            ),
+            Some(WatchEvent::RestartPrompt)
+        );
+        assert_eq!(
+            parse_watch_line("dotnet watch ❌ Build failed"),
+            Some(WatchEvent::BuildFailed)
+        );
+        assert_eq!(
+            parse_watch_line(
+                "dotnet watch ⏳ Waiting for a file to change before restarting dotnet..."
+            ),
+            Some(WatchEvent::WaitingForChanges)
+        );
+        assert_eq!(
+            parse_watch_line(
+                "dotnet watch 🛑 Shutdown requested. Press Ctrl+C again to force exit."
+            ),
+            Some(WatchEvent::ShuttingDown)
+        );
+        assert_eq!(
+            parse_watch_line("dotnet watch ❌ Exited with error code 1"),
+            Some(WatchEvent::AppExited)
+        );
+        assert_eq!(
+            parse_watch_line("dotnet watch 🔥 Hot reload enabled."),
+            Some(WatchEvent::Message("Hot reload enabled.".to_string()))
+        );
+        assert_eq!(
+            parse_watch_line("info: Microsoft.Hosting.Lifetime[14]"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_watch_output_diagnostics() {
+        let mut output = WatchOutput::new(Path::new("/src/Api"));
+        let mut push = |line: &str| output.push_line(line).1;
+
+        assert_eq!(push("dotnet watch 🔧 Building..."), None);
+        assert_eq!(
+            push("/src/Api/Program.cs(12,17): error CS1002: ; expected [/src/Api/Api.csproj]"),
+            None
+        );
+        let diagnostics = push("dotnet watch ❌ Build failed").unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].path, Path::new("/src/Api/Program.cs"));
+        assert_eq!(diagnostics[0].severity, BuildDiagnosticSeverity::Error);
+
+        assert_eq!(push("dotnet watch ⌚ File changed: ./Program.cs."), None);
+        assert_eq!(push("dotnet watch 🔧 Building..."), None);
+        assert_eq!(push("dotnet watch 🚀 Started"), Some(Vec::new()));
+
+        assert_eq!(push("dotnet watch ⌚ File changed: ./Program.cs."), None);
+        assert_eq!(
+            push(
+                "dotnet watch ❌ /src/Api/Program.cs(3,1): error CS0103: The name 'x' does not exist in the current context"
+            ),
+            None
+        );
+        let diagnostics =
+            push("dotnet watch ⌚ Unable to apply hot reload because of a rude edit.").unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code.as_deref(), Some("CS0103"));
+    }
+}
@@ -0,0 +1,151 @@
+//! Helpers for editing XML files in place.
+//!
+//! Edits are computed from the byte ranges of parsed [`roxmltree`] nodes and
+//! applied to the original text, so formatting and comments elsewhere in the
+//! file are preserved.
+
+use std::ops::Range;
+
+const DEFAULT_INDENT: &str = "  ";
+
+pub(crate) fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+pub(crate) fn line_start(text: &str, offset: usize) -> usize {
+    text[..offset].rfind('\n').map_or(0, |ix| ix + 1)
+}
+
+pub(crate) fn indent_of(text: &str, offset: usize) -> String {
+    let start = line_start(text, offset);
+    text[start..offset]
+        .chars()
+        .take_while(|c| c.is_whitespace())
+        .collect()
+}
+
+pub(crate) fn line_ending(text: &str) -> &'static str {
+    if text.contains("\r\n") { "\r\n" } else { "\n" }
+}
+
+/// The byte range of an element, extended to cover its whole line when
+/// nothing else is on that line.
+pub(crate) fn removal_range(text: &str, range: Range<usize>) -> Range<usize> {
+    let start = line_start(text, range.start);
+    let end_of_line = text[range.end..]
+        .find('\n')
+        .map_or(text.len(), |ix| range.end + ix + 1);
+    if text[start..range.start].trim().is_empty() && text[range.end..end_of_line].trim().is_empty()
+    {
+        start..end_of_line
+    } else {
+        range
+    }
+}
+
+/// Indents all but the first line of `element` with `indent`.
+fn indent_element(element: &str, indent: &str) -> String {
+    element
+        .split_inclusive('\n')
+        .enumerate()
+        .map(|(ix, line)| {
+            if ix == 0 {
+                line.to_string()
+            } else {
+                format!("{indent}{line}")
+            }
+        })
+        .collect()
+}
+
+/// An edit inserting `element` on its own line after `sibling`.
+pub(crate) fn insert_after(
+    text: &str,
+    sibling: roxmltree::Node,
+    element: &str,
+) -> (Range<usize>, String) {
+    let indent = indent_of(text, sibling.range().start);
+    let newline = line_ending(text);
+    let end = sibling.range().end;
+    (
+        end..end,
+        format!("{newline}{indent}{}", indent_element(element, &indent)),
+    )
+}
+
+/// An edit inserting `element` as the last child of `parent`.
+pub(crate) fn insert_child(
+    text: &str,
+    parent: roxmltree::Node,
+    element: &str,
+) -> (Range<usize>, String) {
+    if let Some(last) = parent.children().rfind(|child| child.is_element()) {
+        return insert_after(text, last, element);
+    }
+    let newline = line_ending(text);
+    let range = parent.range();
+    let parent_indent = indent_of(text, range.start);
+    let child_indent = format!("{parent_indent}{DEFAULT_INDENT}");
+    let element = indent_element(element, &child_indent);
+    if text[..range.end].ends_with("/>") {
+        // Expand a self-closing element, e.g. `<Folder Name="/a/" />`.
+        let tag = text[range.start..range.end - 2].trim_end();
+        let name = parent.tag_name().name();
+        return (
+            range,
+            format!("{tag}>{newline}{child_indent}{element}{newline}{parent_indent}</{name}>"),
+        );
+    }
+    let close = text[..range.end].rfind("</").unwrap_or(range.end);
+    let close_line = line_start(text, close);
+    if text[close_line..close].trim().is_empty() {
+        (
+            close_line..close_line,
+            format!("{child_indent}{element}{newline}"),
+        )
+    } else {
+        (
+            close..close,
+            format!("{newline}{child_indent}{element}{newline}{parent_indent}"),
+        )
+    }
+}
+
+pub(crate) fn remove_ranges(text: &mut String, mut ranges: Vec<Range<usize>>) {
+    ranges.sort_by_key(|range| range.start);
+    // Merge nested or overlapping ranges so no byte is removed twice.
+    ranges.dedup_by(|next, previous| {
+        if next.start < previous.end {
+            previous.end = previous.end.max(next.end);
+            true
+        } else {
+            false
+        }
+    });
+    for range in ranges.into_iter().rev() {
+        text.replace_range(range, "");
+    }
+}
+
+/// Returns an element's text with the indentation of its inner lines made
+/// relative to the element itself, so it can be re-inserted elsewhere.
+pub(crate) fn reindent(text: &str, range: Range<usize>) -> String {
+    let indent = indent_of(text, range.start);
+    text[range]
+        .split_inclusive('\n')
+        .enumerate()
+        .map(|(ix, line)| {
+            if ix == 0 {
+                line.to_string()
+            } else {
+                line.strip_prefix(indent.as_str())
+                    .unwrap_or(line)
+                    .to_string()
+            }
+        })
+        .collect()
+}
@@ -49,6 +49,7 @@ use workspace::notifications::{ErrorMessagePrompt, NotificationId, show_app_noti
 
 mod cursor_excerpt;
 mod license_detection;
+mod lsp_edit_prediction_delegate;
 pub mod mercury;
 mod onboarding_modal;
 pub mod open_ai_response;
@@ -66,6 +67,7 @@ pub mod zeta2;
 mod edit_prediction_tests;
 
 use crate::license_detection::LicenseDetectionWatcher;
+pub use crate::lsp_edit_prediction_delegate::LspEditPredictionDelegate;
 use crate::mercury::Mercury;
 use crate::onboarding_modal::ZedPredictModal;
 pub use crate::prediction::EditPrediction;
@@ -7,6 +7,7 @@ use cloud_llm_client::{
     EditPredictionRejectReason, EditPredictionRejection, PredictEditsBody, PredictEditsResponse,
     RejectEditPredictionsBody,
 };
+use edit_prediction_types::EditPredictionDelegate as _;
 use futures::{
     AsyncReadExt, StreamExt,
     channel::{mpsc, oneshot},
@@ -1657,6 +1658,110 @@ async fn test_no_data_collection_for_events_in_uncollectable_buffers(cx: &mut Te
     );
 }
 
+#[gpui::test]
+async fn test_lsp_inline_completions(cx: &mut TestAppContext) {
+    init_test(cx);
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/root"),
+        json!({
+            "main.rs": "fn main() {\n    pri\n}\n",
+        }),
+    )
+    .await;
+    let project = Project::test(fs, [path!("/root").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(language::rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        language::FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                inline_completion_provider: Some(lsp::OneOf::Left(true)),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/root/main.rs"), cx)
+        })
+        .await
+        .unwrap();
+    let fake_server = fake_servers.next().await.unwrap();
+    fake_server.set_request_handler::<lsp::request::InlineCompletionRequest, _, _>(
+        |params, _| async move {
+            assert_eq!(
+                params.text_document_position.position,
+                lsp::Position::new(1, 7)
+            );
+            assert_eq!(
+                params.context.trigger_kind,
+                lsp::InlineCompletionTriggerKind::Invoked
+            );
+            Ok(Some(lsp::InlineCompletionResponse::Array(vec![
+                lsp::InlineCompletionItem {
+                    insert_text: "println!(\"${1:hi}\")".into(),
+                    filter_text: None,
+                    range: Some(lsp::Range::new(
+                        lsp::Position::new(1, 4),
+                        lsp::Position::new(1, 7),
+                    )),
+                    command: None,
+                    insert_text_format: Some(lsp::InsertTextFormat::SNIPPET),
+                },
+                lsp::InlineCompletionItem {
+                    insert_text: "nt!()".into(),
+                    filter_text: None,
+                    range: None,
+                    command: None,
+                    insert_text_format: None,
+                },
+            ])))
+        },
+    );
+
+    let delegate = cx.new(|_| LspEditPredictionDelegate::new(project.clone()));
+    let cursor = buffer.read_with(cx, |buffer, _| buffer.anchor_before(Point::new(1, 7)));
+    assert!(cx.read(|cx| delegate.read(cx).is_enabled(&buffer, cursor, cx)));
+
+    delegate.update(cx, |delegate, cx| {
+        delegate.refresh(buffer.clone(), cursor, false, cx)
+    });
+    cx.run_until_parked();
+
+    let suggested_edits = |cx: &mut TestAppContext| {
+        delegate.update(cx, |delegate, cx| {
+            let Some(edit_prediction_types::EditPrediction::Local { edits, .. }) =
+                delegate.suggest(&buffer, cursor, cx)
+            else {
+                panic!("expected a local edit prediction");
+            };
+            let buffer = buffer.read(cx);
+            edits
+                .into_iter()
+                .map(|(range, text)| (range.to_offset(buffer), text.to_string()))
+                .collect::<Vec<_>>()
+        })
+    };
+    assert_eq!(
+        suggested_edits(cx),
+        vec![(19..19, "ntln!(\"hi\")".to_string())]
+    );
+
+    delegate.update(cx, |delegate, cx| {
+        delegate.cycle(
+            buffer.clone(),
+            cursor,
+            edit_prediction_types::Direction::Next,
+            cx,
+        )
+    });
+    assert_eq!(suggested_edits(cx), vec![(19..19, "nt!()".to_string())]);
+}
+
 fn init_test(cx: &mut TestAppContext) {
     cx.update(|cx| {
         let settings_store = SettingsStore::test(cx);
@@ -0,0 +1,200 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use edit_prediction_types::{Direction, EditPrediction, EditPredictionDelegate};
+use gpui::{App, Context, Entity, EntityId, Task};
+use language::{Buffer, OffsetRangeExt as _, ToOffset as _};
+use project::{InlineCompletion, InlineCompletionTrigger, Project};
+
+pub const LSP_EDIT_PREDICTION_DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(75);
+
+/// Predicts edits with the `textDocument/inlineCompletion` requests of the language servers
+/// of the buffer, so that any server providing inline completions can be used as a provider.
+pub struct LspEditPredictionDelegate {
+    project: Entity<Project>,
+    buffer_id: Option<EntityId>,
+    completions: Vec<InlineCompletion>,
+    active_completion_index: usize,
+    pending_refresh: Option<Task<Result<()>>>,
+}
+
+impl LspEditPredictionDelegate {
+    pub fn new(project: Entity<Project>) -> Self {
+        Self {
+            project,
+            buffer_id: None,
+            completions: Vec::new(),
+            active_completion_index: 0,
+            pending_refresh: None,
+        }
+    }
+
+    fn active_completion(&self) -> Option<&InlineCompletion> {
+        self.completions.get(self.active_completion_index)
+    }
+}
+
+impl EditPredictionDelegate for LspEditPredictionDelegate {
+    fn name() -> &'static str {
+        "language-server"
+    }
+
+    fn display_name() -> &'static str {
+        "Language Server"
+    }
+
+    fn show_predictions_in_menu() -> bool {
+        true
+    }
+
+    fn show_tab_accept_marker() -> bool {
+        true
+    }
+
+    fn supports_jump_to_edit() -> bool {
+        false
+    }
+
+    fn is_refreshing(&self, _cx: &App) -> bool {
+        self.pending_refresh.is_some() && self.completions.is_empty()
+    }
+
+    fn is_enabled(
+        &self,
+        buffer: &Entity<Buffer>,
+        _cursor_position: language::Anchor,
+        cx: &App,
+    ) -> bool {
+        self.project
+            .read(cx)
+            .lsp_store()
+            .read(cx)
+            .has_inline_completion_provider(buffer, cx)
+    }
+
+    fn refresh(
+        &mut self,
+        buffer: Entity<Buffer>,
+        cursor_position: language::Anchor,
+        debounce: bool,
+        cx: &mut Context<Self>,
+    ) {
+        let project = self.project.clone();
+        self.pending_refresh = Some(cx.spawn(async move |this, cx| {
+            let trigger = if debounce {
+                cx.background_executor()
+                    .timer(LSP_EDIT_PREDICTION_DEBOUNCE_TIMEOUT)
+                    .await;
+                InlineCompletionTrigger::Automatic
+            } else {
+                InlineCompletionTrigger::Invoked
+            };
+
+            let completions = project
+                .update(cx, |project, cx| {
+                    project.inline_completions(&buffer, cursor_position, trigger, cx)
+                })?
+                .await?;
+
+            this.update(cx, |this, cx| {
+                this.pending_refresh = None;
+                this.active_completion_index = 0;
+                this.buffer_id = Some(buffer.entity_id());
+                this.completions.clear();
+                for completion in completions {
+                    if !this.completions.contains(&completion) {
+                        this.completions.push(completion);
+                    }
+                }
+                cx.notify();
+            })?;
+
+            Ok(())
+        }));
+    }
+
+    fn cycle(
+        &mut self,
+        _buffer: Entity<Buffer>,
+        _cursor_position: language::Anchor,
+        direction: Direction,
+        cx: &mut Context<Self>,
+    ) {
+        if self.completions.is_empty() {
+            self.active_completion_index = 0;
+            return;
+        }
+        self.active_completion_index = match direction {
+            Direction::Prev => {
+                (self.active_completion_index + self.completions.len() - 1) % self.completions.len()
+            }
+            Direction::Next => (self.active_completion_index + 1) % self.completions.len(),
+        };
+        cx.notify();
+    }
+
+    fn accept(&mut self, _cx: &mut Context<Self>) {
+        self.completions.clear();
+        self.active_completion_index = 0;
+    }
+
+    fn discard(&mut self, _cx: &mut Context<Self>) {
+        self.completions.clear();
+        self.active_completion_index = 0;
+    }
+
+    fn suggest(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        cursor_position: language::Anchor,
+        cx: &mut Context<Self>,
+    ) -> Option<EditPrediction> {
+        let buffer_id = buffer.entity_id();
+        let buffer = buffer.read(cx);
+        let completion = self.active_completion()?;
+        if Some(buffer_id) != self.buffer_id
+            || !completion.range.start.is_valid(buffer)
+            || !completion.range.end.is_valid(buffer)
+        {
+            return None;
+        }
+
+        // Servers usually replace the word being typed, so only keep the text that differs.
+        let mut completion_range = completion.range.to_offset(buffer);
+        let prefix_len = common_prefix(
+            buffer.chars_for_range(completion_range.clone()),
+            completion.text.chars(),
+        );
+        completion_range.start += prefix_len;
+        let suffix_len = common_prefix(
+            buffer.reversed_chars_for_range(completion_range.clone()),
+            completion.text[prefix_len..].chars().rev(),
+        );
+        completion_range.end = completion_range.end.saturating_sub(suffix_len);
+
+        if completion_range.is_empty()
+            && completion_range.start == cursor_position.to_offset(buffer)
+        {
+            let completion_text = &completion.text[prefix_len..completion.text.len() - suffix_len];
+            if completion_text.trim().is_empty() {
+                None
+            } else {
+                let position = cursor_position.bias_right(buffer);
+                Some(EditPrediction::Local {
+                    id: None,
+                    edits: vec![(position..position, completion_text.into())],
+                    edit_preview: None,
+                })
+            }
+        } else {
+            None
+        }
+    }
+}
+
+fn common_prefix<T1: Iterator<Item = char>, T2: Iterator<Item = char>>(a: T1, b: T2) -> usize {
+    a.zip(b)
+        .take_while(|(a, b)| a == b)
+        .map(|(a, _)| a.len_utf8())
+        .sum()
+}
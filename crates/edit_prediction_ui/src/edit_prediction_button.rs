@@ -478,6 +478,36 @@ impl Render for EditPredictionButton {
                 div().child(popover_menu.into_any_element())
             }
 
+            EditPredictionProvider::LanguageServer => {
+                let enabled = self.editor_enabled.unwrap_or(true);
+                let this = cx.weak_entity();
+
+                div().child(
+                    PopoverMenu::new("language-server")
+                        .menu(move |window, cx| {
+                            this.update(cx, |this, cx| {
+                                this.build_language_server_context_menu(window, cx)
+                            })
+                            .ok()
+                        })
+                        .anchor(Corner::BottomRight)
+                        .trigger_with_tooltip(
+                            IconButton::new("language-server-icon", IconName::Server)
+                                .shape(IconButtonShape::Square)
+                                .when(!enabled, |this| {
+                                    this.indicator(Indicator::dot().color(Color::Ignored))
+                                        .indicator_border_color(Some(
+                                            cx.theme().colors().status_bar_background,
+                                        ))
+                                }),
+                            move |_window, cx| {
+                                Tooltip::for_action("Language Server", &ToggleMenu, cx)
+                            },
+                        )
+                        .with_handle(self.popover_menu_handle.clone()),
+                )
+            }
+
             EditPredictionProvider::None => div().hidden(),
         }
     }
@@ -537,6 +567,8 @@ impl EditPredictionButton {
             providers.push(EditPredictionProvider::Codestral);
         }
 
+        providers.push(EditPredictionProvider::LanguageServer);
+
         if cx.has_flag::<SweepFeatureFlag>() {
             providers.push(EditPredictionProvider::Experimental(
                 EXPERIMENTAL_SWEEP_EDIT_PREDICTION_PROVIDER_NAME,
@@ -615,6 +647,13 @@ impl EditPredictionButton {
                                 set_completion_provider(fs.clone(), cx, provider);
                             }),
                     ),
+                    EditPredictionProvider::LanguageServer => menu.item(
+                        ContextMenuEntry::new("Language Server")
+                            .toggleable(IconPosition::Start, is_current)
+                            .handler(move |_, cx| {
+                                set_completion_provider(fs.clone(), cx, provider);
+                            }),
+                    ),
                     EditPredictionProvider::Experimental(
                         EXPERIMENTAL_SWEEP_EDIT_PREDICTION_PROVIDER_NAME,
                     ) => {
@@ -838,6 +877,7 @@ impl EditPredictionButton {
                 | EditPredictionProvider::Copilot
                 | EditPredictionProvider::Supermaven
                 | EditPredictionProvider::Codestral
+                | EditPredictionProvider::LanguageServer
         ) {
             menu = menu
                 .separator()
@@ -1094,6 +1134,17 @@ impl EditPredictionButton {
         })
     }
 
+    fn build_language_server_context_menu(
+        &self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Entity<ContextMenu> {
+        ContextMenu::build(window, cx, |menu, window, cx| {
+            let menu = self.build_language_settings_menu(menu, window, cx);
+            self.add_provider_switching_section(menu, EditPredictionProvider::LanguageServer, cx)
+        })
+    }
+
     fn build_edit_prediction_context_menu(
         &self,
         provider: EditPredictionProvider,
@@ -389,6 +389,8 @@ actions!(
         ClearFlycheck,
         /// Confirms the rename operation.
         ConfirmRename,
+        /// Confirms the rename operation, previewing its edits before applying them.
+        ConfirmRenameWithPreview,
         /// Confirms completion by inserting at cursor.
         ConfirmCompletionInsert,
         /// Confirms completion by replacing existing text.
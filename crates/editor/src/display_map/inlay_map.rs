@@ -324,7 +324,9 @@ impl<'a> Iterator for InlayChunks<'a> {
                         }
                     }),
                     InlayId::Hint(_) => self.highlight_styles.inlay_hint,
-                    InlayId::DebuggerValue(_) => self.highlight_styles.inlay_hint,
+                    InlayId::DebuggerValue(_) | InlayId::TestResult(_) => {
+                        self.highlight_styles.inlay_hint
+                    }
                     InlayId::Color(_) => {
                         if let InlayContent::Color(color) = inlay.content {
                             renderer = Some(ChunkRenderer {
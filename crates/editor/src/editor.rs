@@ -19,6 +19,7 @@ pub mod code_context_menus;
 pub mod display_map;
 mod editor_settings;
 mod element;
+mod folding_ranges;
 mod git;
 mod highlight_matching_bracket;
 mod hover_links;
@@ -36,8 +37,10 @@ mod persistence;
 mod rust_analyzer_ext;
 pub mod scroll;
 mod selections_collection;
+mod semantic_tokens;
 mod split;
 pub mod tasks;
+mod workspace_edit_preview;
 
 #[cfg(test)]
 mod code_completion_tests;
@@ -72,6 +75,7 @@ pub use multi_buffer::{
 };
 pub use split::SplittableEditor;
 pub use text::Bias;
+pub use workspace_edit_preview::WorkspaceEditPreview;
 
 use ::git::{
     Restore,
@@ -95,6 +99,7 @@ use display_map::*;
 use edit_prediction_types::{EditPredictionDelegate, EditPredictionDelegateHandle};
 use editor_settings::{GoToDefinitionFallback, Minimap as MinimapSettings};
 use element::{AcceptEditPredictionBinding, LineWithInvisibles, PositionMap, layout_line};
+use folding_ranges::FoldingRangesData;
 use futures::{
     FutureExt, StreamExt as _,
     future::{self, Shared, join},
@@ -124,8 +129,8 @@ use language::{
     IndentSize, Language, LanguageName, LanguageRegistry, OffsetRangeExt, OutlineItem, Point,
     Runnable, Selection, SelectionGoal, TextObject, TransactionId, TreeSitterOptions, WordsQuery,
     language_settings::{
-        self, LanguageSettings, LspInsertMode, RewrapBehavior, WordsCompletionMode,
-        all_language_settings, language_settings,
+        self, LanguageSettings, LspInsertMode, RewrapBehavior, SyntaxRangesProvider,
+        WordsCompletionMode, all_language_settings, language_settings,
     },
     point_from_lsp, point_to_lsp, text_diff_with_options,
 };
@@ -147,7 +152,7 @@ use project::{
     BreakpointWithPosition, CodeAction, Completion, CompletionDisplayOptions, CompletionIntent,
     CompletionResponse, CompletionSource, DisableAiSettings, DocumentHighlight, InlayHint, InlayId,
     InvalidationStrategy, Location, LocationLink, LspAction, PrepareRenameResponse, Project,
-    ProjectItem, ProjectPath, ProjectTransaction, TaskSourceKind,
+    ProjectItem, ProjectPath, ProjectTransaction, ResolvedWorkspaceEdit, TaskSourceKind,
     debugger::{
         breakpoint_store::{
             Breakpoint, BreakpointEditAction, BreakpointSessionState, BreakpointState,
@@ -166,6 +171,7 @@ use rand::seq::SliceRandom;
 use rpc::{ErrorCode, ErrorExt, proto::PeerId};
 use scroll::{Autoscroll, OngoingScroll, ScrollAnchor, ScrollManager};
 use selections_collection::{MutableSelectionsCollection, SelectionsCollection};
+use semantic_tokens::SemanticTokensData;
 use serde::{Deserialize, Serialize};
 use settings::{
     GitGutterSetting, RelativeLineNumbers, Settings, SettingsLocation, SettingsStore,
@@ -1045,6 +1051,7 @@ pub struct Editor {
     autoclose_regions: Vec<AutocloseRegion>,
     snippet_stack: InvalidationStack<SnippetState>,
     select_syntax_node_history: SelectSyntaxNodeHistory,
+    select_larger_syntax_node_task: Task<()>,
     ime_transaction: Option<TransactionId>,
     pub diagnostics_max_severity: DiagnosticSeverity,
     active_diagnostics: ActiveDiagnostic,
@@ -1198,11 +1205,17 @@ pub struct Editor {
     hide_mouse_mode: HideMouseMode,
     pub change_list: ChangeList,
     inline_value_cache: InlineValueCache,
+    /// Test results shown next to test methods
+    test_result_inlays: Vec<InlayId>,
 
     selection_drag_state: SelectionDragState,
     colors: Option<LspColorData>,
     post_scroll_update: Task<()>,
     refresh_colors_task: Task<()>,
+    semantic_tokens: SemanticTokensData,
+    refresh_semantic_tokens_task: Task<()>,
+    folding_ranges: FoldingRangesData,
+    refresh_folding_ranges_task: Task<()>,
     inlay_hints: Option<LspInlayHintData>,
     folding_newlines: Task<()>,
     select_next_is_case_sensitive: Option<bool>,
@@ -1973,6 +1986,9 @@ impl Editor {
                     project::Event::RefreshCodeLens => {
                         // we always query lens with actions, without storing them, always refreshing them
                     }
+                    project::Event::RefreshSemanticTokens => {
+                        editor.refresh_semantic_tokens(None, cx);
+                    }
                     project::Event::RefreshInlayHints {
                         server_id,
                         request_id,
@@ -2219,6 +2235,7 @@ impl Editor {
             autoclose_regions: Vec::new(),
             snippet_stack: InvalidationStack::default(),
             select_syntax_node_history: SelectSyntaxNodeHistory::default(),
+            select_larger_syntax_node_task: Task::ready(()),
             ime_transaction: None,
             active_diagnostics: ActiveDiagnostic::None,
             show_inline_diagnostics: ProjectSettings::get_global(cx).diagnostics.inline.enabled,
@@ -2307,6 +2324,7 @@ impl Editor {
             diagnostics_enabled: full_mode,
             word_completions_enabled: full_mode,
             inline_value_cache: InlineValueCache::new(inlay_hint_settings.show_value_hints),
+            test_result_inlays: Vec::new(),
             gutter_hovered: false,
             pixel_position_of_newest_cursor: None,
             last_bounds: None,
@@ -2377,6 +2395,10 @@ impl Editor {
             pull_diagnostics_background_task: Task::ready(()),
             colors: None,
             refresh_colors_task: Task::ready(()),
+            semantic_tokens: SemanticTokensData::default(),
+            refresh_semantic_tokens_task: Task::ready(()),
+            folding_ranges: FoldingRangesData::default(),
+            refresh_folding_ranges_task: Task::ready(()),
             inlay_hints: None,
             next_color_inlay_id: 0,
             post_scroll_update: Task::ready(()),
@@ -2455,6 +2477,8 @@ impl Editor {
                                 .update_in(cx, |editor, window, cx| {
                                     editor.register_visible_buffers(cx);
                                     editor.refresh_colors_for_visible_range(None, window, cx);
+                                    editor.refresh_semantic_tokens(None, cx);
+                                    editor.refresh_folding_ranges(None, cx);
                                     editor.refresh_inlay_hints(
                                         InlayHintRefreshReason::NewLinesShown,
                                         cx,
@@ -15873,9 +15897,9 @@ impl Editor {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let Some(visible_row_count) = self.visible_row_count() else {
+        if self.visible_row_count().is_none() {
             return;
-        };
+        }
         let old_selections: Box<[_]> = self
             .selections
             .all::<MultiBufferOffset>(&self.display_snapshot(cx))
@@ -15886,11 +15910,54 @@ impl Editor {
 
         self.hide_mouse_cursor(HideMouseCursorOrigin::MovementAction, cx);
 
+        if let Some(lsp_selections) = self.larger_lsp_selections(&old_selections, cx) {
+            self.select_larger_syntax_node_task = cx.spawn_in(window, async move |editor, cx| {
+                let new_selections = lsp_selections.await;
+                editor
+                    .update_in(cx, |editor, window, cx| {
+                        let current_selections = editor
+                            .selections
+                            .all::<MultiBufferOffset>(&editor.display_snapshot(cx));
+                        if *current_selections != *old_selections {
+                            return;
+                        }
+                        // Expand through tree-sitter when no language server provides the ranges.
+                        let new_selections = match new_selections {
+                            Some(new_selections) => Some(new_selections),
+                            None => editor.larger_syntax_node_selections(&old_selections, cx),
+                        };
+                        if let Some(new_selections) = new_selections
+                            && *new_selections != *old_selections
+                        {
+                            editor.select_larger_selections(
+                                old_selections,
+                                new_selections,
+                                window,
+                                cx,
+                            );
+                        }
+                    })
+                    .ok();
+            });
+            return;
+        }
+
+        if let Some(new_selections) = self.larger_syntax_node_selections(&old_selections, cx) {
+            self.select_larger_selections(old_selections, new_selections, window, cx);
+        }
+    }
+
+    /// The selections expanded to their enclosing syntax nodes, or `None` if none expanded.
+    fn larger_syntax_node_selections(
+        &mut self,
+        old_selections: &[Selection<MultiBufferOffset>],
+        cx: &mut Context<Self>,
+    ) -> Option<Vec<Selection<MultiBufferOffset>>> {
         let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
         let buffer = self.buffer.read(cx).snapshot(cx);
 
         let mut selected_larger_node = false;
-        let mut new_selections = old_selections
+        let new_selections = old_selections
             .iter()
             .map(|selection| {
                 let old_range = selection.start..selection.end;
@@ -15941,10 +16008,117 @@ impl Editor {
             })
             .collect::<Vec<_>>();
 
-        if !selected_larger_node {
-            return; // don't put this call in the history
+        selected_larger_node.then_some(new_selections)
+    }
+
+    /// The selections expanded through the selection ranges of the language servers, when all
+    /// of them are in buffers whose language settings ask for them, or `None` otherwise.
+    /// Resolves to `None` when no language server provides selection ranges.
+    fn larger_lsp_selections(
+        &mut self,
+        old_selections: &[Selection<MultiBufferOffset>],
+        cx: &mut Context<Self>,
+    ) -> Option<Task<Option<Vec<Selection<MultiBufferOffset>>>>> {
+        let project = self.project.clone()?;
+        let multi_buffer = self.buffer.read(cx);
+        let snapshot = multi_buffer.snapshot(cx);
+
+        let mut buffer_selections =
+            HashMap::<BufferId, (Entity<Buffer>, Vec<(usize, ExcerptId, Range<usize>)>)>::default();
+        for (ix, selection) in old_selections.iter().enumerate() {
+            let [(buffer_snapshot, buffer_range, excerpt_id)] = snapshot
+                .range_to_buffer_ranges(selection.start..selection.end)
+                .try_into()
+                .ok()?;
+            let settings = language_settings(
+                buffer_snapshot.language().map(|language| language.name()),
+                buffer_snapshot.file(),
+                cx,
+            );
+            if settings.selection_ranges != SyntaxRangesProvider::LanguageServer {
+                return None;
+            }
+            let buffer = multi_buffer.buffer(buffer_snapshot.remote_id())?;
+            buffer_selections
+                .entry(buffer_snapshot.remote_id())
+                .or_insert_with(|| (buffer, Vec::new()))
+                .1
+                .push((ix, excerpt_id, buffer_range.start.0..buffer_range.end.0));
         }
 
+        let tasks = buffer_selections
+            .into_values()
+            .map(|(buffer, selections)| {
+                let selection_ranges = project.update(cx, |project, cx| {
+                    project.selection_ranges(
+                        &buffer,
+                        selections.iter().map(|(_, _, range)| range.start),
+                        cx,
+                    )
+                });
+                async move { (buffer, selections, selection_ranges.await) }
+            })
+            .collect::<Vec<_>>();
+        let old_selections = old_selections.to_vec();
+        Some(cx.spawn(async move |editor, cx| {
+            let buffer_selection_ranges = future::join_all(tasks).await;
+            editor
+                .update(cx, |editor, cx| {
+                    let snapshot = editor.buffer.read(cx).snapshot(cx);
+                    let mut new_selections = old_selections;
+                    let mut any_ranges = false;
+                    for (buffer, selections, selection_ranges) in buffer_selection_ranges {
+                        let selection_ranges = match selection_ranges {
+                            Ok(selection_ranges) => selection_ranges,
+                            Err(e) => {
+                                log::error!("Failed to retrieve selection ranges: {e}");
+                                continue;
+                            }
+                        };
+                        any_ranges |= !selection_ranges.is_empty();
+                        let buffer = buffer.read(cx);
+                        for ((ix, excerpt_id, old_range), ranges) in
+                            selections.into_iter().zip(selection_ranges)
+                        {
+                            // The innermost range that strictly contains the selection.
+                            let new_range = ranges
+                                .into_iter()
+                                .filter(|range| {
+                                    let range = range.to_offset(buffer);
+                                    range != old_range
+                                        && range.start <= old_range.start
+                                        && old_range.end <= range.end
+                                })
+                                .find_map(|range| {
+                                    snapshot.anchor_range_in_excerpt(excerpt_id, range)
+                                });
+                            if let Some(new_range) = new_range {
+                                let selection = &mut new_selections[ix];
+                                selection.start = new_range.start.to_offset(&snapshot);
+                                selection.end = new_range.end.to_offset(&snapshot);
+                                selection.goal = SelectionGoal::None;
+                            }
+                        }
+                    }
+                    any_ranges.then_some(new_selections)
+                })
+                .ok()
+                .flatten()
+        }))
+    }
+
+    fn select_larger_selections(
+        &mut self,
+        old_selections: Box<[Selection<MultiBufferOffset>]>,
+        mut new_selections: Vec<Selection<MultiBufferOffset>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(visible_row_count) = self.visible_row_count() else {
+            return;
+        };
+        let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
+
         // scroll based on transformation done to the last selection created by the user
         let (last_old, last_new) = old_selections
             .last()
@@ -15959,13 +16133,11 @@ impl Editor {
             should_newest_selection_be_reversed
         };
 
-        if selected_larger_node {
-            self.select_syntax_node_history.disable_clearing = true;
-            self.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
-                s.select(new_selections.clone());
-            });
-            self.select_syntax_node_history.disable_clearing = false;
-        }
+        self.select_syntax_node_history.disable_clearing = true;
+        self.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+            s.select(new_selections.clone());
+        });
+        self.select_syntax_node_history.disable_clearing = false;
 
         let start_row = last_new.start.to_display_point(&display_map).row().0;
         let end_row = last_new.end.to_display_point(&display_map).row().0;
@@ -18169,6 +18341,94 @@ impl Editor {
         }))
     }
 
+    /// Like [`Editor::confirm_rename`], but opens the edits of the rename in a
+    /// [`WorkspaceEditPreview`], where they can be reviewed before applying them.
+    pub fn confirm_rename_with_preview(
+        &mut self,
+        _: &ConfirmRenameWithPreview,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Task<Result<()>>> {
+        let semantics_provider = self.semantics_provider.clone()?;
+        let project = self.project.clone()?;
+        let rename = self.take_rename(false, window, cx)?;
+        let workspace = self.workspace()?.downgrade();
+        let (buffer, start) = self
+            .buffer
+            .read(cx)
+            .text_anchor_for_position(rename.range.start, cx)?;
+        let (end_buffer, _) = self
+            .buffer
+            .read(cx)
+            .text_anchor_for_position(rename.range.end, cx)?;
+        if buffer != end_buffer {
+            return None;
+        }
+
+        let old_name = rename.old_name;
+        let new_name = rename.editor.read(cx).text(cx);
+        let title = format!("Rename: {} → {}", old_name, new_name);
+
+        // Edits can only be resolved without applying them in local projects,
+        // so others rename right away.
+        let Some(rename_edits) =
+            semantics_provider.rename_edits(&buffer, start, new_name.clone(), cx)
+        else {
+            let rename = semantics_provider.perform_rename(&buffer, start, new_name, cx)?;
+            return Some(cx.spawn_in(window, async move |editor, cx| {
+                let project_transaction = rename.await?;
+                Self::open_project_transaction(&editor, workspace, project_transaction, title, cx)
+                    .await?;
+                editor.update(cx, |editor, cx| {
+                    editor.refresh_document_highlights(cx);
+                })?;
+                Ok(())
+            }));
+        };
+
+        Some(cx.spawn_in(window, async move |editor, cx| {
+            let edit = match rename_edits.await? {
+                Some(edit) => edit,
+                None => {
+                    let rename = cx.update(|_, cx| {
+                        semantics_provider.perform_rename(&buffer, start, new_name, cx)
+                    })?;
+                    let Some(rename) = rename else {
+                        return Ok(());
+                    };
+                    let project_transaction = rename.await?;
+                    return Self::open_project_transaction(
+                        &editor,
+                        workspace,
+                        project_transaction,
+                        title,
+                        cx,
+                    )
+                    .await;
+                }
+            };
+            if edit.is_empty() {
+                return Ok(());
+            }
+
+            workspace.update_in(cx, |workspace, window, cx| {
+                let preview = cx.new(|cx| {
+                    WorkspaceEditPreview::new(
+                        title,
+                        edit,
+                        editor.clone(),
+                        cx.entity().downgrade(),
+                        project,
+                        window,
+                        cx,
+                    )
+                });
+                workspace.add_item_to_active_pane(Box::new(preview), None, true, window, cx);
+            })?;
+            Ok(())
+        }))
+    }
+
     fn take_rename(
         &mut self,
         moving_cursor: bool,
@@ -22243,6 +22503,12 @@ impl Editor {
                 self.colorize_brackets(true, cx);
             }
 
+            if language_settings_changed {
+                self.refresh_semantic_tokens(None, cx);
+                self.refresh_folding_ranges(None, cx);
+            }
+            self.refresh_semantic_token_styles(language_settings_changed, cx);
+
             if let Some(inlay_splice) = self.colors.as_mut().and_then(|colors| {
                 colors.render_mode_updated(EditorSettings::get_global(cx).lsp_document_colors)
             }) {
@@ -23156,6 +23422,8 @@ impl Editor {
     ) {
         self.pull_diagnostics(for_buffer, window, cx);
         self.refresh_colors_for_visible_range(for_buffer, window, cx);
+        self.refresh_semantic_tokens(for_buffer, cx);
+        self.refresh_folding_ranges(for_buffer, cx);
     }
 
     fn register_visible_buffers(&mut self, cx: &mut Context<Self>) {
@@ -23994,6 +24262,16 @@ pub trait SemanticsProvider {
         new_name: String,
         cx: &mut App,
     ) -> Option<Task<Result<ProjectTransaction>>>;
+
+    /// Resolves the edits of a rename without applying them. Returns `None`
+    /// when they can't be resolved without applying them.
+    fn rename_edits(
+        &self,
+        buffer: &Entity<Buffer>,
+        position: text::Anchor,
+        new_name: String,
+        cx: &mut App,
+    ) -> Option<Task<Result<Option<ResolvedWorkspaceEdit>>>>;
 }
 
 pub trait CompletionProvider {
@@ -24578,6 +24856,18 @@ impl SemanticsProvider for Entity<Project> {
             project.perform_rename(buffer.clone(), position, new_name, cx)
         }))
     }
+
+    fn rename_edits(
+        &self,
+        buffer: &Entity<Buffer>,
+        position: text::Anchor,
+        new_name: String,
+        cx: &mut App,
+    ) -> Option<Task<Result<Option<ResolvedWorkspaceEdit>>>> {
+        Some(self.update(cx, |project, cx| {
+            project.rename_edits(buffer.clone(), position, new_name, cx)
+        }))
+    }
 }
 
 fn consume_contiguous_rows(
@@ -9670,6 +9670,96 @@ async fn test_select_larger_smaller_syntax_node_for_string(cx: &mut TestAppConte
     });
 }
 
+#[gpui::test]
+async fn test_select_larger_smaller_syntax_node_with_lsp_selection_ranges(cx: &mut TestAppContext) {
+    init_test(cx, |settings| {
+        settings.defaults.selection_ranges = Some(SyntaxRangesProvider::LanguageServer);
+    });
+
+    let mut cx = EditorLspTestContext::new_rust(
+        lsp::ServerCapabilities {
+            selection_range_provider: Some(lsp::SelectionRangeProviderCapability::Simple(true)),
+            ..Default::default()
+        },
+        cx,
+    )
+    .await;
+    cx.set_state(indoc! {"
+        fn main() {
+            let x = vec![1, ˇ2];
+        }
+    "});
+
+    cx.lsp
+        .set_request_handler::<lsp::request::SelectionRangeRequest, _, _>(|params, _| async move {
+            let range = |start_line, start_character, end_line, end_character| lsp::Range {
+                start: lsp::Position::new(start_line, start_character),
+                end: lsp::Position::new(end_line, end_character),
+            };
+            let ranges = [
+                range(1, 20, 1, 21),
+                range(1, 17, 1, 21),
+                range(1, 12, 1, 22),
+                range(1, 4, 1, 23),
+                range(0, 0, 2, 1),
+            ];
+            Ok(Some(
+                params
+                    .positions
+                    .iter()
+                    .map(|_| {
+                        ranges.iter().rev().fold(None, |parent, range| {
+                            Some(lsp::SelectionRange {
+                                range: *range,
+                                parent: parent.map(Box::new),
+                            })
+                        })
+                    })
+                    .collect::<Option<Vec<_>>>()
+                    .unwrap(),
+            ))
+        });
+
+    cx.update_editor(|editor, window, cx| {
+        editor.select_larger_syntax_node(&SelectLargerSyntaxNode, window, cx);
+    });
+    cx.run_until_parked();
+    cx.assert_editor_state(indoc! {"
+        fn main() {
+            let x = vec![1, «2ˇ»];
+        }
+    "});
+
+    cx.update_editor(|editor, window, cx| {
+        editor.select_larger_syntax_node(&SelectLargerSyntaxNode, window, cx);
+    });
+    cx.run_until_parked();
+    cx.assert_editor_state(indoc! {"
+        fn main() {
+            let x = vec![«ˇ1, 2»];
+        }
+    "});
+
+    cx.update_editor(|editor, window, cx| {
+        editor.select_larger_syntax_node(&SelectLargerSyntaxNode, window, cx);
+    });
+    cx.run_until_parked();
+    cx.assert_editor_state(indoc! {"
+        fn main() {
+            let x = «ˇvec![1, 2]»;
+        }
+    "});
+
+    cx.update_editor(|editor, window, cx| {
+        editor.select_smaller_syntax_node(&SelectSmallerSyntaxNode, window, cx);
+    });
+    cx.assert_editor_state(indoc! {"
+        fn main() {
+            let x = vec![«ˇ1, 2»];
+        }
+    "});
+}
+
 #[gpui::test]
 async fn test_unwrap_syntax_nodes(cx: &mut gpui::TestAppContext) {
     init_test(cx, |_| {});
@@ -9854,6 +9944,58 @@ async fn test_fold_function_bodies(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_fold_lsp_folding_ranges(cx: &mut TestAppContext) {
+    init_test(cx, |settings| {
+        settings.defaults.folding_ranges = Some(SyntaxRangesProvider::LanguageServer);
+    });
+
+    let mut cx = EditorLspTestContext::new_rust(
+        lsp::ServerCapabilities {
+            folding_range_provider: Some(lsp::FoldingRangeProviderCapability::Simple(true)),
+            ..Default::default()
+        },
+        cx,
+    )
+    .await;
+    cx.lsp
+        .set_request_handler::<lsp::request::FoldingRangeRequest, _, _>(|_, _| async move {
+            Ok(Some(vec![lsp::FoldingRange {
+                start_line: 1,
+                start_character: None,
+                end_line: 4,
+                end_character: None,
+                kind: Some(lsp::FoldingRangeKind::Region),
+                collapsed_text: None,
+            }]))
+        });
+    cx.set_state(indoc! {"
+        fn main() {
+            // #region setup
+            let x = 1;
+            let y = 2;
+            // #endregion
+            ˇx + y;
+        }
+    "});
+    cx.executor().advance_clock(Duration::from_millis(200));
+    cx.run_until_parked();
+
+    cx.update_editor(|editor, window, cx| {
+        editor.fold_at(MultiBufferRow(1), window, cx);
+        assert_eq!(
+            editor.display_text(cx),
+            indoc! {"
+                fn main() {
+                    // #region setup⋯
+                    x + y;
+                }
+            "},
+            "the region the indentation can't fold is folded as the language server reports it"
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_autoindent(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
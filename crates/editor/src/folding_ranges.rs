@@ -0,0 +1,155 @@
+//! Folding ranges of language servers, offered as creases in the languages
+//! that take their folds from the language server.
+
+use std::{cmp::Reverse, ops::Range, time::Duration};
+
+use crate::{
+    Editor,
+    display_map::{Crease, CreaseId},
+};
+use collections::HashMap;
+use futures::future::join_all;
+use gpui::Context;
+use itertools::Itertools;
+use language::{
+    OffsetRangeExt as _, Point,
+    language_settings::{self, SyntaxRangesProvider},
+};
+use text::BufferId;
+
+const FETCH_FOLDING_RANGES_DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(150);
+
+#[derive(Debug, Default)]
+pub(super) struct FoldingRangesData {
+    buffer_creases: HashMap<BufferId, Vec<CreaseId>>,
+}
+
+impl Editor {
+    /// Fetches the folding ranges of the visible buffers that take their folds
+    /// from the language server, and replaces their creases with them.
+    pub(super) fn refresh_folding_ranges(
+        &mut self,
+        buffer_id: Option<BufferId>,
+        cx: &mut Context<Self>,
+    ) {
+        if self.ignore_lsp_data() || self.project.is_none() {
+            return;
+        }
+
+        self.refresh_folding_ranges_task = cx.spawn(async move |editor, cx| {
+            cx.background_executor()
+                .timer(FETCH_FOLDING_RANGES_DEBOUNCE_TIMEOUT)
+                .await;
+            let Ok(folding_ranges_tasks) = editor.update(cx, |editor, cx| {
+                let Some(project) = editor.project.clone() else {
+                    return Vec::new();
+                };
+                let visible_buffers = editor
+                    .visible_excerpts(true, cx)
+                    .into_values()
+                    .map(|(buffer, ..)| buffer)
+                    .filter(|editor_buffer| {
+                        let editor_buffer_id = editor_buffer.read(cx).remote_id();
+                        buffer_id.is_none_or(|buffer_id| buffer_id == editor_buffer_id)
+                            && editor.registered_buffers.contains_key(&editor_buffer_id)
+                    })
+                    .unique_by(|buffer| buffer.read(cx).remote_id())
+                    .collect::<Vec<_>>();
+
+                let mut disabled_buffers = Vec::new();
+                let mut tasks = Vec::new();
+                for buffer in visible_buffers {
+                    let (buffer_id, provider) = {
+                        let buffer = buffer.read(cx);
+                        let provider = language_settings::language_settings(
+                            buffer.language().map(|language| language.name()),
+                            buffer.file(),
+                            cx,
+                        )
+                        .folding_ranges;
+                        (buffer.remote_id(), provider)
+                    };
+                    if provider == SyntaxRangesProvider::LanguageServer {
+                        let folding_ranges =
+                            project.update(cx, |project, cx| project.folding_ranges(&buffer, cx));
+                        tasks.push(async move { (buffer_id, folding_ranges.await) });
+                    } else {
+                        disabled_buffers.push(buffer_id);
+                    }
+                }
+
+                for buffer_id in disabled_buffers {
+                    if let Some(crease_ids) =
+                        editor.folding_ranges.buffer_creases.remove(&buffer_id)
+                    {
+                        editor.remove_creases(crease_ids, cx);
+                    }
+                }
+                tasks
+            }) else {
+                return;
+            };
+            if folding_ranges_tasks.is_empty() {
+                return;
+            }
+
+            let all_folding_ranges = join_all(folding_ranges_tasks).await;
+            editor
+                .update(cx, |editor, cx| {
+                    for (buffer_id, folding_ranges) in all_folding_ranges {
+                        match folding_ranges {
+                            Ok(folding_ranges) => {
+                                editor.insert_folding_ranges(buffer_id, folding_ranges, cx)
+                            }
+                            Err(e) => log::error!("Failed to retrieve folding ranges: {e}"),
+                        }
+                    }
+                })
+                .ok();
+        });
+    }
+
+    fn insert_folding_ranges(
+        &mut self,
+        buffer_id: BufferId,
+        folding_ranges: Vec<Range<text::Anchor>>,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(crease_ids) = self.folding_ranges.buffer_creases.remove(&buffer_id) {
+            self.remove_creases(crease_ids, cx);
+        }
+
+        let multi_buffer = self.buffer.read(cx);
+        let Some(buffer) = multi_buffer.buffer(buffer_id) else {
+            return;
+        };
+        let buffer = buffer.read(cx);
+        let multi_buffer_snapshot = multi_buffer.snapshot(cx);
+        let excerpts = multi_buffer.excerpts_for_buffer(buffer_id, cx);
+        let placeholder = self.display_map.read(cx).fold_placeholder.clone();
+
+        // Only one crease can start on a row, so keep the outermost range of each row.
+        let creases = folding_ranges
+            .into_iter()
+            .map(|range| {
+                let point_range: Range<Point> = range.to_point(buffer);
+                (point_range, range)
+            })
+            .sorted_by_key(|(point_range, _)| (point_range.start.row, Reverse(point_range.end)))
+            .dedup_by(|(a, _), (b, _)| a.start.row == b.start.row)
+            .filter_map(|(_, range)| {
+                excerpts.iter().find_map(|(excerpt_id, _)| {
+                    multi_buffer_snapshot.anchor_range_in_excerpt(*excerpt_id, range.clone())
+                })
+            })
+            .map(|range| Crease::simple(range, placeholder.clone()))
+            .collect::<Vec<_>>();
+
+        let crease_ids = self.insert_creases(creases, cx);
+        if !crease_ids.is_empty() {
+            self.folding_ranges
+                .buffer_creases
+                .insert(buffer_id, crease_ids);
+        }
+    }
+}
@@ -23,6 +23,7 @@ use gpui::{Context, HighlightStyle, Hsla, Rgba, Task};
 use multi_buffer::Anchor;
 use project::{InlayHint, InlayId};
 use text::Rope;
+use util::post_inc;
 
 use crate::{Editor, hover_links::InlayHighlight};
 
@@ -104,6 +105,14 @@ impl Inlay {
         }
     }
 
+    pub fn test_result<T: Into<Rope>>(id: usize, position: Anchor, text: T) -> Self {
+        Self {
+            id: InlayId::TestResult(id),
+            position,
+            content: InlayContent::Text(text.into()),
+        }
+    }
+
     pub fn text(&self) -> &Rope {
         static COLOR_TEXT: OnceLock<Rope> = OnceLock::new();
         match &self.content {
@@ -168,6 +177,22 @@ impl Editor {
         cx.notify();
     }
 
+    /// Replaces the test results shown at the given positions, such as the end
+    /// of a test method's declaration.
+    pub fn set_test_results(&mut self, results: Vec<(Anchor, String)>, cx: &mut Context<Self>) {
+        let inlays = results
+            .into_iter()
+            .map(|(position, text)| {
+                Inlay::test_result(post_inc(&mut self.next_inlay_id), position, text)
+            })
+            .collect::<Vec<_>>();
+        let to_remove = std::mem::replace(
+            &mut self.test_result_inlays,
+            inlays.iter().map(|inlay| inlay.id).collect(),
+        );
+        self.splice_inlays(&to_remove, inlays, cx);
+    }
+
     pub fn inline_values_enabled(&self) -> bool {
         self.inline_value_cache.enabled
     }
@@ -235,10 +235,18 @@ pub fn deploy_context_menu(
                 .action("Go to Declaration", Box::new(GoToDeclaration))
                 .action("Go to Type Definition", Box::new(GoToTypeDefinition))
                 .action("Go to Implementation", Box::new(GoToImplementation))
+                .action(
+                    "Show Type Hierarchy",
+                    Box::new(zed_actions::type_hierarchy::ShowSubtypes),
+                )
                 .action(
                     "Find All References",
                     Box::new(FindAllReferences::default()),
                 )
+                .action(
+                    "Show Call Hierarchy",
+                    Box::new(zed_actions::call_hierarchy::ShowIncomingCalls),
+                )
                 .separator()
                 .action("Rename Symbol", Box::new(Rename))
                 .action("Format Buffer", Box::new(Format))
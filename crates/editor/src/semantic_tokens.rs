@@ -0,0 +1,255 @@
+//! Semantic tokens of language servers, styled with the theme syntax styles
+//! and layered over the tree-sitter highlights.
+
+use std::{ops::Range, sync::Arc, time::Duration};
+
+use crate::Editor;
+use collections::HashMap;
+use futures::future::join_all;
+use gpui::{Context, HighlightStyle};
+use itertools::Itertools;
+use language::language_settings;
+use lsp::LanguageServerId;
+use multi_buffer::Anchor;
+use project::SemanticTokens;
+use text::BufferId;
+use theme::SyntaxTheme;
+use ui::ActiveTheme;
+
+const FETCH_SEMANTIC_TOKENS_DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(150);
+
+struct SemanticTokenHighlight;
+
+#[derive(Debug, Default)]
+pub(super) struct SemanticTokensData {
+    buffer_tokens: HashMap<BufferId, BufferSemanticTokens>,
+    /// The theme the highlights were styled with.
+    syntax_theme: Option<Arc<SyntaxTheme>>,
+}
+
+#[derive(Debug)]
+struct BufferSemanticTokens {
+    buffer_version: clock::Global,
+    cache_version: Option<usize>,
+    servers: HashMap<LanguageServerId, SemanticTokens>,
+}
+
+impl Editor {
+    /// Fetches the semantic tokens of the visible buffers that have them enabled,
+    /// and highlights them once they change.
+    pub(super) fn refresh_semantic_tokens(
+        &mut self,
+        buffer_id: Option<BufferId>,
+        cx: &mut Context<Self>,
+    ) {
+        if self.ignore_lsp_data() || self.project.is_none() {
+            return;
+        }
+
+        self.refresh_semantic_tokens_task = cx.spawn(async move |editor, cx| {
+            cx.background_executor()
+                .timer(FETCH_SEMANTIC_TOKENS_DEBOUNCE_TIMEOUT)
+                .await;
+            let Ok(all_tokens_tasks) = editor.update(cx, |editor, cx| {
+                let Some(project) = editor.project.clone() else {
+                    return Vec::new();
+                };
+                let visible_buffers = editor
+                    .visible_excerpts(true, cx)
+                    .into_values()
+                    .map(|(buffer, ..)| buffer)
+                    .filter(|editor_buffer| {
+                        let editor_buffer_id = editor_buffer.read(cx).remote_id();
+                        buffer_id.is_none_or(|buffer_id| buffer_id == editor_buffer_id)
+                            && editor.registered_buffers.contains_key(&editor_buffer_id)
+                    })
+                    .unique_by(|buffer| buffer.read(cx).remote_id())
+                    .collect::<Vec<_>>();
+
+                let mut disabled_buffers = Vec::new();
+                let tasks = project.read(cx).lsp_store().update(cx, |lsp_store, cx| {
+                    visible_buffers
+                        .into_iter()
+                        .filter_map(|buffer| {
+                            let (buffer_id, buffer_version, enabled) = {
+                                let buffer = buffer.read(cx);
+                                let enabled = language_settings::language_settings(
+                                    buffer.language().map(|language| language.name()),
+                                    buffer.file(),
+                                    cx,
+                                )
+                                .semantic_tokens;
+                                (buffer.remote_id(), buffer.version(), enabled)
+                            };
+                            if !enabled {
+                                disabled_buffers.push(buffer_id);
+                                return None;
+                            }
+
+                            let known_cache_version = editor
+                                .semantic_tokens
+                                .buffer_tokens
+                                .get(&buffer_id)
+                                .filter(|tokens| tokens.buffer_version == buffer_version)
+                                .and_then(|tokens| tokens.cache_version);
+                            let tokens_task =
+                                lsp_store.semantic_tokens(known_cache_version, buffer, cx)?;
+                            Some(async move { (buffer_id, buffer_version, tokens_task.await) })
+                        })
+                        .collect::<Vec<_>>()
+                });
+
+                let mut removed_any = false;
+                for buffer_id in disabled_buffers {
+                    removed_any |= editor
+                        .semantic_tokens
+                        .buffer_tokens
+                        .remove(&buffer_id)
+                        .is_some();
+                }
+                if removed_any {
+                    editor.highlight_semantic_tokens(cx);
+                }
+                tasks
+            }) else {
+                return;
+            };
+            if all_tokens_tasks.is_empty() {
+                return;
+            }
+
+            let all_tokens = join_all(all_tokens_tasks).await;
+            editor
+                .update(cx, |editor, cx| {
+                    let mut updated = false;
+                    for (buffer_id, buffer_version, tokens) in all_tokens {
+                        match tokens {
+                            Ok(tokens) => {
+                                editor.semantic_tokens.buffer_tokens.insert(
+                                    buffer_id,
+                                    BufferSemanticTokens {
+                                        buffer_version,
+                                        cache_version: tokens.cache_version,
+                                        servers: tokens.servers,
+                                    },
+                                );
+                                updated = true;
+                            }
+                            Err(e) => log::error!("Failed to retrieve semantic tokens: {e}"),
+                        }
+                    }
+                    if updated {
+                        editor.highlight_semantic_tokens(cx);
+                    }
+                })
+                .ok();
+        });
+    }
+
+    /// Restyles the semantic token highlights, if the styles in the settings
+    /// or the theme changed since they were styled.
+    pub(super) fn refresh_semantic_token_styles(
+        &mut self,
+        language_settings_changed: bool,
+        cx: &mut Context<Self>,
+    ) {
+        let theme_changed = self
+            .semantic_tokens
+            .syntax_theme
+            .as_ref()
+            .is_some_and(|syntax_theme| !Arc::ptr_eq(syntax_theme, cx.theme().syntax()));
+        if language_settings_changed || theme_changed {
+            self.highlight_semantic_tokens(cx);
+        }
+    }
+
+    fn highlight_semantic_tokens(&mut self, cx: &mut Context<Self>) {
+        let syntax_theme = cx.theme().syntax().clone();
+        let multi_buffer = self.buffer.read(cx);
+        let multi_buffer_snapshot = multi_buffer.snapshot(cx);
+        self.semantic_tokens
+            .buffer_tokens
+            .retain(|buffer_id, _| multi_buffer.buffer(*buffer_id).is_some());
+
+        let mut style_keys = HashMap::<String, usize>::default();
+        let mut styles = Vec::<(HighlightStyle, Vec<Range<Anchor>>)>::new();
+        for (buffer_id, buffer_tokens) in &self.semantic_tokens.buffer_tokens {
+            let Some(buffer) = multi_buffer.buffer(*buffer_id) else {
+                continue;
+            };
+            let buffer = buffer.read(cx);
+            let language_settings = language_settings::language_settings(
+                buffer.language().map(|language| language.name()),
+                buffer.file(),
+                cx,
+            );
+            let excerpts = multi_buffer.excerpts_for_buffer(*buffer_id, cx);
+
+            for tokens in buffer_tokens.servers.values() {
+                let mut token_style_keys = HashMap::<(u32, u32), Option<usize>>::default();
+                for token in tokens.tokens.iter() {
+                    let style_key = *token_style_keys
+                        .entry((token.token_type, token.token_modifiers))
+                        .or_insert_with(|| {
+                            let token_type = tokens.legend.token_type(token)?;
+                            let style_name = tokens
+                                .legend
+                                .token_modifiers(token)
+                                .find_map(|modifier| {
+                                    language_settings
+                                        .semantic_token_styles
+                                        .get(&format!("{token_type}.{modifier}"))
+                                })
+                                .or_else(|| {
+                                    language_settings
+                                        .semantic_token_styles
+                                        .get(token_type.as_ref())
+                                })?;
+                            if let Some(&style_key) = style_keys.get(style_name) {
+                                return Some(style_key);
+                            }
+                            let style = syntax_style(&syntax_theme, style_name)?;
+                            let style_key = styles.len();
+                            styles.push((style, Vec::new()));
+                            style_keys.insert(style_name.clone(), style_key);
+                            Some(style_key)
+                        });
+                    let Some(style_key) = style_key else {
+                        continue;
+                    };
+                    let range = excerpts.iter().find_map(|(excerpt_id, _)| {
+                        multi_buffer_snapshot
+                            .anchor_range_in_excerpt(*excerpt_id, token.range.clone())
+                    });
+                    if let Some(range) = range {
+                        styles[style_key].1.push(range);
+                    }
+                }
+            }
+        }
+
+        self.clear_highlights::<SemanticTokenHighlight>(cx);
+        for (style_key, (style, mut ranges)) in styles.into_iter().enumerate() {
+            ranges.sort_by(|a, b| a.start.cmp(&b.start, &multi_buffer_snapshot));
+            self.highlight_text_key::<SemanticTokenHighlight>(style_key, ranges, style, false, cx);
+        }
+        self.semantic_tokens.syntax_theme = Some(syntax_theme);
+    }
+}
+
+/// The theme style of the name, or of its closest parent (`function` for `function.method`).
+/// An empty name has no style.
+fn syntax_style(syntax_theme: &SyntaxTheme, name: &str) -> Option<HighlightStyle> {
+    let mut name = name;
+    while !name.is_empty() {
+        if let Some((_, style)) = syntax_theme
+            .highlights
+            .iter()
+            .find(|(style_name, _)| style_name == name)
+        {
+            return Some(*style);
+        }
+        name = &name[..name.rfind('.').unwrap_or(0)];
+    }
+    None
+}
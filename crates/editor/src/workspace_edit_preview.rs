@@ -0,0 +1,546 @@
+//! A preview of the edits a language server makes across the workspace, such as
+//! those of a rename, where files and hunks can be left out before applying them.
+
+use std::{any::TypeId, ops::Range, sync::Arc};
+
+use anyhow::Result;
+use collections::HashSet;
+use gpui::{
+    AnyElement, App, Context, Entity, EventEmitter, FocusHandle, Focusable, Task, WeakEntity,
+    Window,
+};
+use language::{Buffer, Capability, OffsetRangeExt as _, Point, ToOffset as _, ToPoint as _};
+use multi_buffer::{Anchor, MultiBuffer, PathKey};
+use project::{Project, ResolvedWorkspaceEdit, WorkspaceEditOperation};
+use ui::{Checkbox, ToggleState, prelude::*};
+use util::paths::PathExt as _;
+use workspace::{
+    Item, Workspace,
+    item::{ItemEvent, TabContentParams},
+};
+
+use crate::{
+    Editor,
+    display_map::{BlockContext, BlockPlacement, BlockProperties, BlockStyle},
+    multibuffer_context_lines,
+};
+
+pub enum WorkspaceEditPreviewEvent {
+    Close,
+}
+
+pub struct WorkspaceEditPreview {
+    title: SharedString,
+    project: Entity<Project>,
+    workspace: WeakEntity<Workspace>,
+    source_editor: WeakEntity<Editor>,
+    edit: ResolvedWorkspaceEdit,
+    /// The file operations of the edit that are left out, by operation index.
+    excluded_operations: HashSet<usize>,
+    hunks: Vec<PreviewHunk>,
+    editor: Entity<Editor>,
+    apply_task: Option<Task<()>>,
+}
+
+/// Edits of a buffer on the same lines, included or left out together.
+struct PreviewHunk {
+    operation_ix: usize,
+    edit_range: Range<usize>,
+    /// The lines of the hunk, as they read with the edits applied.
+    new_text: SharedString,
+    /// The ranges the edits replace, in the preview editor.
+    old_ranges: Vec<Range<Anchor>>,
+    included: bool,
+}
+
+impl WorkspaceEditPreview {
+    pub fn new(
+        title: impl Into<SharedString>,
+        mut edit: ResolvedWorkspaceEdit,
+        source_editor: WeakEntity<Editor>,
+        workspace: WeakEntity<Workspace>,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let title = title.into();
+        let mut hunks = Vec::new();
+        let mut hunk_line_ranges = Vec::<(Entity<Buffer>, Vec<(usize, Range<Point>)>)>::new();
+        for (operation_ix, operation) in edit.operations.iter_mut().enumerate() {
+            let WorkspaceEditOperation::EditBuffer { buffer, edits } = operation else {
+                continue;
+            };
+            let snapshot = buffer.read(cx).snapshot();
+            edits.sort_by(|(a, _), (b, _)| a.start.cmp(&b.start, &snapshot));
+
+            let mut edit_ix = 0;
+            while edit_ix < edits.len() {
+                let hunk_start = edit_ix;
+                let start_row = edits[edit_ix].0.start.to_point(&snapshot).row;
+                let mut end_row = edits[edit_ix].0.end.to_point(&snapshot).row;
+                edit_ix += 1;
+                while let Some((range, _)) = edits.get(edit_ix)
+                    && range.start.to_point(&snapshot).row <= end_row
+                {
+                    end_row = end_row.max(range.end.to_point(&snapshot).row);
+                    edit_ix += 1;
+                }
+
+                let line_range =
+                    Point::new(start_row, 0)..Point::new(end_row, snapshot.line_len(end_row));
+                let mut new_text = String::new();
+                let mut old_offset = line_range.start.to_offset(&snapshot);
+                for (range, text) in &edits[hunk_start..edit_ix] {
+                    let range = range.to_offset(&snapshot);
+                    new_text
+                        .extend(snapshot.text_for_range(old_offset..range.start.max(old_offset)));
+                    new_text.push_str(text);
+                    old_offset = old_offset.max(range.end);
+                }
+                new_text.extend(
+                    snapshot.text_for_range(old_offset..line_range.end.to_offset(&snapshot)),
+                );
+
+                let buffer_ix = match hunk_line_ranges
+                    .iter()
+                    .position(|(hunk_buffer, _)| hunk_buffer == buffer)
+                {
+                    Some(buffer_ix) => buffer_ix,
+                    None => {
+                        hunk_line_ranges.push((buffer.clone(), Vec::new()));
+                        hunk_line_ranges.len() - 1
+                    }
+                };
+                hunk_line_ranges[buffer_ix]
+                    .1
+                    .push((hunks.len(), line_range));
+                hunks.push(PreviewHunk {
+                    operation_ix,
+                    edit_range: hunk_start..edit_ix,
+                    new_text: new_text.into(),
+                    old_ranges: Vec::new(),
+                    included: true,
+                });
+            }
+        }
+
+        let mut hunk_anchors = Vec::new();
+        let multibuffer = cx.new(|cx| {
+            let mut multibuffer =
+                MultiBuffer::new(Capability::ReadOnly).with_title(title.to_string());
+            for (buffer, mut line_ranges) in hunk_line_ranges {
+                line_ranges.sort_by_key(|(_, line_range)| line_range.start);
+                let (ranges, _) = multibuffer.set_excerpts_for_path(
+                    PathKey::for_buffer(&buffer, cx),
+                    buffer.clone(),
+                    line_ranges.iter().map(|(_, line_range)| line_range.clone()),
+                    multibuffer_context_lines(cx),
+                    cx,
+                );
+                let snapshot = multibuffer.snapshot(cx);
+                for ((hunk_ix, _), range) in line_ranges.into_iter().zip(ranges) {
+                    let hunk = &mut hunks[hunk_ix];
+                    if let WorkspaceEditOperation::EditBuffer { edits, .. } =
+                        &edit.operations[hunk.operation_ix]
+                    {
+                        hunk.old_ranges = edits[hunk.edit_range.clone()]
+                            .iter()
+                            .filter_map(|(old_range, _)| {
+                                snapshot.anchor_range_in_excerpt(
+                                    range.start.excerpt_id,
+                                    old_range.clone(),
+                                )
+                            })
+                            .collect();
+                    }
+                    hunk_anchors.push((hunk_ix, range.end));
+                }
+            }
+            multibuffer
+        });
+
+        let editor = cx.new(|cx| {
+            let mut editor =
+                Editor::for_multibuffer(multibuffer, Some(project.clone()), window, cx);
+            editor.disable_diagnostics(cx);
+            editor
+        });
+
+        let preview = cx.weak_entity();
+        editor.update(cx, |editor, cx| {
+            let blocks = hunk_anchors
+                .into_iter()
+                .map(|(hunk_ix, anchor)| {
+                    let new_text = hunks[hunk_ix].new_text.clone();
+                    BlockProperties {
+                        placement: BlockPlacement::Below(anchor),
+                        height: Some(new_text.lines().count().max(1) as u32),
+                        style: BlockStyle::Flex,
+                        render: Arc::new({
+                            let preview = preview.clone();
+                            move |cx: &mut BlockContext| {
+                                render_hunk_block(&preview, hunk_ix, &new_text, cx)
+                            }
+                        }),
+                        priority: 0,
+                    }
+                })
+                .collect::<Vec<_>>();
+            editor.insert_blocks(blocks, None, cx);
+        });
+
+        let mut this = Self {
+            title,
+            project,
+            workspace,
+            source_editor,
+            edit,
+            excluded_operations: HashSet::default(),
+            hunks,
+            editor,
+            apply_task: None,
+        };
+        this.update_highlights(cx);
+        this
+    }
+
+    fn toggle_hunk(&mut self, hunk_ix: usize, cx: &mut Context<Self>) {
+        if let Some(hunk) = self.hunks.get_mut(hunk_ix) {
+            hunk.included = !hunk.included;
+            self.update_highlights(cx);
+        }
+    }
+
+    fn toggle_operation(&mut self, operation_ix: usize, cx: &mut Context<Self>) {
+        if matches!(
+            self.edit.operations.get(operation_ix),
+            Some(WorkspaceEditOperation::EditBuffer { .. })
+        ) {
+            let included = !self
+                .hunks
+                .iter()
+                .filter(|hunk| hunk.operation_ix == operation_ix)
+                .all(|hunk| hunk.included);
+            for hunk in &mut self.hunks {
+                if hunk.operation_ix == operation_ix {
+                    hunk.included = included;
+                }
+            }
+        } else if !self.excluded_operations.remove(&operation_ix) {
+            self.excluded_operations.insert(operation_ix);
+        }
+        self.update_highlights(cx);
+    }
+
+    fn update_highlights(&mut self, cx: &mut Context<Self>) {
+        let ranges = self
+            .hunks
+            .iter()
+            .filter(|hunk| hunk.included)
+            .flat_map(|hunk| hunk.old_ranges.iter().cloned())
+            .collect::<Vec<_>>();
+        self.editor.update(cx, |editor, cx| {
+            editor.highlight_background::<Self>(
+                &ranges,
+                |_, theme| theme.status().deleted_background,
+                cx,
+            );
+            cx.notify();
+        });
+        cx.notify();
+    }
+
+    /// The part of the edit that is included in the preview.
+    fn included_edit(&self) -> ResolvedWorkspaceEdit {
+        let mut operations = Vec::new();
+        for (operation_ix, operation) in self.edit.operations.iter().enumerate() {
+            match operation {
+                WorkspaceEditOperation::EditBuffer { buffer, edits } => {
+                    let edits = self
+                        .hunks
+                        .iter()
+                        .filter(|hunk| hunk.operation_ix == operation_ix && hunk.included)
+                        .flat_map(|hunk| edits[hunk.edit_range.clone()].iter().cloned())
+                        .collect::<Vec<_>>();
+                    if !edits.is_empty() {
+                        operations.push(WorkspaceEditOperation::EditBuffer {
+                            buffer: buffer.clone(),
+                            edits,
+                        });
+                    }
+                }
+                operation => {
+                    if !self.excluded_operations.contains(&operation_ix) {
+                        operations.push(operation.clone());
+                    }
+                }
+            }
+        }
+        ResolvedWorkspaceEdit { operations }
+    }
+
+    /// Applies the included part of the edit, as a single transaction that can be undone
+    /// across all of the buffers it edits.
+    pub fn apply(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.apply_task.is_some() {
+            return;
+        }
+        let edit = self.included_edit();
+        let title = self.title.to_string();
+        let source_editor = self.source_editor.clone();
+        let workspace = self.workspace.clone();
+        let apply = self
+            .project
+            .update(cx, |project, cx| project.apply_workspace_edit(edit, cx));
+        self.apply_task = Some(cx.spawn_in(window, async move |this, cx| {
+            let result: Result<()> = async {
+                let transaction = apply.await?;
+                Editor::open_project_transaction(&source_editor, workspace, transaction, title, cx)
+                    .await
+            }
+            .await;
+            this.update(cx, |this, cx| {
+                this.apply_task = None;
+                match result {
+                    Ok(()) => cx.emit(WorkspaceEditPreviewEvent::Close),
+                    Err(error) => {
+                        log::error!("Failed to apply workspace edit: {error:#}");
+                        cx.notify();
+                    }
+                }
+            })
+            .ok();
+        }));
+        cx.notify();
+    }
+
+    pub fn discard(&mut self, cx: &mut Context<Self>) {
+        cx.emit(WorkspaceEditPreviewEvent::Close);
+    }
+
+    fn operation_label(&self, operation: &WorkspaceEditOperation, cx: &App) -> SharedString {
+        match operation {
+            WorkspaceEditOperation::CreateFile { abs_path, .. } => {
+                format!("Create {}", abs_path.compact().display()).into()
+            }
+            WorkspaceEditOperation::RenameFile {
+                old_abs_path,
+                new_abs_path,
+                ..
+            } => format!(
+                "Rename {} → {}",
+                old_abs_path.compact().display(),
+                new_abs_path.compact().display()
+            )
+            .into(),
+            WorkspaceEditOperation::DeleteFile { abs_path, .. } => {
+                format!("Delete {}", abs_path.compact().display()).into()
+            }
+            WorkspaceEditOperation::EditBuffer { buffer, .. } => buffer
+                .read(cx)
+                .file()
+                .map(|file| file.full_path(cx).compact().display().to_string())
+                .unwrap_or_else(|| "untitled".to_string())
+                .into(),
+        }
+    }
+
+    fn operation_state(&self, operation_ix: usize) -> ToggleState {
+        match self.edit.operations[operation_ix] {
+            WorkspaceEditOperation::EditBuffer { .. } => {
+                let (included, total) = self
+                    .hunks
+                    .iter()
+                    .filter(|hunk| hunk.operation_ix == operation_ix)
+                    .fold((0, 0), |(included, total), hunk| {
+                        (included + hunk.included as usize, total + 1)
+                    });
+                if included == total {
+                    ToggleState::Selected
+                } else if included == 0 {
+                    ToggleState::Unselected
+                } else {
+                    ToggleState::Indeterminate
+                }
+            }
+            _ => (!self.excluded_operations.contains(&operation_ix)).into(),
+        }
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let can_apply = self.apply_task.is_none()
+            && (self.hunks.iter().any(|hunk| hunk.included)
+                || (0..self.edit.operations.len()).any(|operation_ix| {
+                    !matches!(
+                        self.edit.operations[operation_ix],
+                        WorkspaceEditOperation::EditBuffer { .. }
+                    ) && !self.excluded_operations.contains(&operation_ix)
+                }));
+
+        v_flex()
+            .p_2()
+            .gap_1()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                h_flex()
+                    .justify_between()
+                    .child(Label::new(self.title.clone()))
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .child(Button::new("discard", "Discard").on_click(cx.listener(
+                                |this, _, _, cx| {
+                                    this.discard(cx);
+                                },
+                            )))
+                            .child(
+                                Button::new("apply", "Apply")
+                                    .style(ButtonStyle::Filled)
+                                    .disabled(!can_apply)
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.apply(window, cx);
+                                    })),
+                            ),
+                    ),
+            )
+            .child(
+                v_flex()
+                    .id("workspace-edit-operations")
+                    .max_h_48()
+                    .overflow_y_scroll()
+                    .children(self.edit.operations.iter().enumerate().map(
+                        |(operation_ix, operation)| {
+                            Checkbox::new(
+                                ("workspace-edit-operation", operation_ix),
+                                self.operation_state(operation_ix),
+                            )
+                            .label(self.operation_label(operation, cx))
+                            .on_click(cx.listener(
+                                move |this, _, _, cx| {
+                                    this.toggle_operation(operation_ix, cx);
+                                },
+                            ))
+                        },
+                    )),
+            )
+    }
+}
+
+fn render_hunk_block(
+    preview: &WeakEntity<WorkspaceEditPreview>,
+    hunk_ix: usize,
+    new_text: &SharedString,
+    cx: &mut BlockContext,
+) -> AnyElement {
+    let included = preview
+        .upgrade()
+        .is_some_and(|preview| preview.read(cx.app).hunks[hunk_ix].included);
+    let background = if included {
+        cx.theme().status().created_background
+    } else {
+        cx.theme().status().ignored_background
+    };
+    let preview = preview.clone();
+
+    h_flex()
+        .id(("workspace-edit-hunk", hunk_ix))
+        .block_mouse_except_scroll()
+        .w_full()
+        .bg(background)
+        .child(
+            div()
+                .w(cx.margins.gutter.full_width())
+                .flex()
+                .justify_end()
+                .pr_1()
+                .child(
+                    Checkbox::new(("workspace-edit-hunk-toggle", hunk_ix), included.into())
+                        .on_click(move |_, _, cx| {
+                            preview
+                                .update(cx, |preview, cx| preview.toggle_hunk(hunk_ix, cx))
+                                .ok();
+                        }),
+                ),
+        )
+        .child(
+            v_flex()
+                .font(cx.editor_style.text.font())
+                .text_color(if included {
+                    cx.theme().colors().text
+                } else {
+                    cx.theme().colors().text_muted
+                })
+                .children(
+                    new_text
+                        .lines()
+                        .map(|line| div().h(cx.line_height).child(line.to_string())),
+                ),
+        )
+        .into_any_element()
+}
+
+impl EventEmitter<WorkspaceEditPreviewEvent> for WorkspaceEditPreview {}
+
+impl Focusable for WorkspaceEditPreview {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+impl Item for WorkspaceEditPreview {
+    type Event = WorkspaceEditPreviewEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::Replace).color(Color::Muted))
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, cx: &App) -> AnyElement {
+        Label::new(self.tab_content_text(params.detail.unwrap_or_default(), cx))
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _detail: usize, _: &App) -> SharedString {
+        self.title.clone()
+    }
+
+    fn to_item_events(event: &WorkspaceEditPreviewEvent, mut f: impl FnMut(ItemEvent)) {
+        match event {
+            WorkspaceEditPreviewEvent::Close => f(ItemEvent::CloseItem),
+        }
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Workspace Edit Preview Opened")
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<gpui::AnyEntity> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.clone().into())
+        } else if type_id == TypeId::of::<Editor>() {
+            Some(self.editor.clone().into())
+        } else {
+            None
+        }
+    }
+}
+
+impl Render for WorkspaceEditPreview {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .size_full()
+            .child(self.render_header(cx))
+            .child(self.editor.clone())
+    }
+}
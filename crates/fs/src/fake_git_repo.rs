@@ -6,13 +6,16 @@ use git::{
     Oid, RunHook,
     blame::Blame,
     repository::{
-        AskPassDelegate, Branch, CommitDetails, CommitOptions, FetchOptions, GitRepository,
-        GitRepositoryCheckpoint, PushOptions, Remote, RepoPath, ResetMode, Worktree,
+        AskPassDelegate, Branch, CommitDetails, CommitLogEntry, CommitLogQuery, CommitOptions,
+        CommitSummary, FetchOptions, GitRepository, GitRepositoryCheckpoint, PushOptions,
+        RebaseControl, RebaseOutcome, RebaseTodoAction, RebaseTodoEntry, Remote,
+        RemoteCommandOutput, RepoPath, ResetMode, SequencerOperation, Tag, Worktree,
     },
     status::{
         DiffTreeType, FileStatus, GitStatus, StatusCode, TrackedStatus, TreeDiff, TreeDiffStatus,
         UnmergedStatus,
     },
+    submodule::{Submodule, SubmoduleOperation, SubmoduleStatus},
 };
 use gpui::{AsyncApp, BackgroundExecutor, SharedString, Task, TaskLabel};
 use ignore::gitignore::GitignoreBuilder;
@@ -20,6 +23,8 @@ use parking_lot::Mutex;
 use rope::Rope;
 use smol::future::FutureExt as _;
 use std::{
+    collections::VecDeque,
+    hash::{DefaultHasher, Hash, Hasher},
     path::PathBuf,
     sync::{Arc, LazyLock},
 };
@@ -52,8 +57,34 @@ pub struct FakeGitRepositoryState {
     pub branches: HashSet<String>,
     /// List of remotes, keys are names and values are URLs
     pub remotes: HashMap<String, String>,
+    /// List of tags, keys are names and values are the shas they point to
+    pub tags: HashMap<String, String>,
+    pub submodules: Vec<Submodule>,
+    /// The `git submodule` commands run so far, with the paths they were limited to.
+    pub submodule_operations: Vec<(SubmoduleOperation, Vec<RepoPath>)>,
     pub simulated_index_write_error_message: Option<String>,
     pub refs: HashMap<String, String>,
+    /// Commits reachable from `HEAD`, newest first. Operations that commit keep the `HEAD` ref
+    /// pointing to the first one.
+    pub commits: Vec<CommitSummary>,
+    /// Commits that aren't reachable from `HEAD`, like those of other branches.
+    pub other_commits: Vec<CommitSummary>,
+    /// Commits whose cherry-pick or revert stops on conflicts.
+    pub conflicting_commits: HashSet<String>,
+    pub rebase: Option<FakeRebase>,
+    /// The cherry-pick or revert that stopped on conflicts, with the commit it was applying.
+    pub sequencer: Option<(SequencerOperation, CommitSummary)>,
+    /// Tags pushed to remotes, as `(remote, tag)` pairs.
+    pub pushed_tags: Vec<(String, String)>,
+}
+
+/// An interactive rebase that stopped at a commit to edit.
+#[derive(Debug, Clone)]
+pub struct FakeRebase {
+    /// The commits of `HEAD` before the rebase, which aborting it restores.
+    pub original_commits: Vec<CommitSummary>,
+    /// The todo list entries that haven't been applied yet.
+    pub remaining_todo: VecDeque<RebaseTodoEntry>,
 }
 
 impl FakeGitRepositoryState {
@@ -71,7 +102,117 @@ impl FakeGitRepositoryState {
             merge_base_contents: Default::default(),
             oids: Default::default(),
             remotes: HashMap::default(),
+            tags: HashMap::default(),
+            submodules: Vec::new(),
+            submodule_operations: Vec::new(),
+            commits: Vec::new(),
+            other_commits: Vec::new(),
+            conflicting_commits: HashSet::default(),
+            rebase: None,
+            sequencer: None,
+            pushed_tags: Vec::new(),
+        }
+    }
+
+    /// Makes `commits` the history of `HEAD`, and points `HEAD` to the first of them.
+    pub fn set_commits(&mut self, commits: Vec<CommitSummary>) {
+        if let Some(head) = commits.first() {
+            self.refs.insert("HEAD".into(), head.sha.to_string());
+        }
+        self.commits = commits;
+    }
+
+    /// Commits on top of `HEAD`, with a SHA derived from the parent and the subject.
+    fn commit_on_head(&mut self, subject: SharedString) {
+        let parent = self.commits.first();
+        let mut hasher = DefaultHasher::new();
+        (parent.map(|commit| &commit.sha), &subject).hash(&mut hasher);
+        let commit = CommitSummary {
+            sha: format!("{:040x}", hasher.finish()).into(),
+            subject,
+            commit_timestamp: parent.map_or(0, |commit| commit.commit_timestamp + 1),
+            author_name: "Fake Author".into(),
+            has_parent: parent.is_some(),
+        };
+        let mut commits = vec![commit];
+        commits.append(&mut self.commits);
+        self.set_commits(commits);
+    }
+
+    /// Cherry-picks or reverts `commit`, unless it conflicts, which leaves the operation to be
+    /// continued.
+    fn apply_commit(&mut self, operation: SequencerOperation, commit: String) -> Result<()> {
+        if let Some((operation, _)) = &self.sequencer {
+            bail!("a {} is already in progress", operation.command());
         }
+        let sha = self
+            .refs
+            .get(&commit)
+            .map_or(commit.as_str(), String::as_str);
+        let commit = self
+            .commits
+            .iter()
+            .chain(&self.other_commits)
+            .find(|candidate| candidate.sha.as_ref() == sha)
+            .with_context(|| format!("unknown revision {commit}"))?
+            .clone();
+        if self.conflicting_commits.contains(commit.sha.as_ref()) {
+            let sha = commit.sha.clone();
+            self.refs
+                .insert(sequencer_head(operation).into(), sha.to_string());
+            self.sequencer = Some((operation, commit));
+            bail!("could not {} {sha}", operation.command());
+        }
+        self.commit_on_head(sequenced_subject(operation, &commit));
+        Ok(())
+    }
+
+    fn commit_index(&self, revision: &str) -> Result<usize> {
+        let sha = self.refs.get(revision).map_or(revision, String::as_str);
+        self.commits
+            .iter()
+            .position(|commit| commit.sha.as_ref() == sha)
+            .with_context(|| format!("unknown revision {revision}"))
+    }
+
+    /// Applies the remaining todo list of the rebase, until it's done or stops at an `edit`.
+    fn apply_rebase_todo(&mut self) -> Result<RebaseOutcome> {
+        let rebase = self.rebase.as_mut().context("no rebase in progress")?;
+        let mut commits = self.commits.clone();
+        let mut outcome = RebaseOutcome::Finished;
+        while let Some(entry) = rebase.remaining_todo.pop_front() {
+            let commit = rebase
+                .original_commits
+                .iter()
+                .find(|commit| commit.sha == entry.sha)
+                .with_context(|| format!("unknown commit {}", entry.sha))?
+                .clone();
+            match entry.action {
+                RebaseTodoAction::Pick | RebaseTodoAction::Edit => commits.insert(0, commit),
+                RebaseTodoAction::Reword => commits.insert(
+                    0,
+                    CommitSummary {
+                        subject: entry
+                            .message
+                            .as_ref()
+                            .and_then(|message| message.lines().next())
+                            .map_or(commit.subject.clone(), |subject| subject.to_string().into()),
+                        ..commit
+                    },
+                ),
+                // Squashed commits keep the message of the commit they're squashed into.
+                RebaseTodoAction::Squash | RebaseTodoAction::Fixup | RebaseTodoAction::Drop => {}
+            }
+            if entry.action == RebaseTodoAction::Edit {
+                outcome = RebaseOutcome::Paused;
+                break;
+            }
+        }
+        if outcome == RebaseOutcome::Finished {
+            self.rebase = None;
+        }
+        self.set_commits(commits);
+        Ok(outcome)
     }
 }
 
@@ -375,6 +516,10 @@ impl GitRepository for FakeGitRepository {
         async { Ok(git::stash::GitStash::default()) }.boxed()
     }
 
+    fn submodules(&self) -> BoxFuture<'_, Result<Vec<Submodule>>> {
+        self.with_state_async(false, |state| Ok(state.submodules.clone()))
+    }
+
     fn branches(&self) -> BoxFuture<'_, Result<Vec<Branch>>> {
         self.with_state_async(false, move |state| {
             let current_branch = &state.current_branch_name;
@@ -418,6 +563,17 @@ impl GitRepository for FakeGitRepository {
         })
     }
 
+    fn checkout_commit(
+        &self,
+        _commit: String,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, |state| {
+            state.current_branch_name = None;
+            Ok(())
+        })
+    }
+
     fn create_branch(
         &self,
         name: String,
@@ -480,6 +636,10 @@ impl GitRepository for FakeGitRepository {
         .boxed()
     }
 
+    fn commit_log(&self, _query: CommitLogQuery) -> BoxFuture<'_, Result<Vec<CommitLogEntry>>> {
+        async { Ok(Vec::new()) }.boxed()
+    }
+
     fn stage_paths(
         &self,
         paths: Vec<RepoPath>,
@@ -560,6 +720,64 @@ impl GitRepository for FakeGitRepository {
         unimplemented!()
     }
 
+    fn recent_commits(&self, limit: usize) -> BoxFuture<'_, Result<Vec<CommitSummary>>> {
+        self.with_state_async(false, move |state| {
+            Ok(state.commits.iter().take(limit).cloned().collect())
+        })
+    }
+
+    fn rebase_todo(&self, base: String) -> BoxFuture<'_, Result<Vec<RebaseTodoEntry>>> {
+        self.with_state_async(false, move |state| {
+            let base_ix = state.commit_index(&base)?;
+            Ok(state.commits[..base_ix]
+                .iter()
+                .rev()
+                .map(|commit| RebaseTodoEntry {
+                    action: RebaseTodoAction::Pick,
+                    sha: commit.sha.clone(),
+                    subject: commit.subject.clone(),
+                    message: None,
+                })
+                .collect())
+        })
+    }
+
+    fn rebase(
+        &self,
+        base: String,
+        todo: Vec<RebaseTodoEntry>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<RebaseOutcome>> {
+        self.with_state_async(true, move |state| {
+            if state.rebase.is_some() {
+                bail!("a rebase is already in progress");
+            }
+            let base_ix = state.commit_index(&base)?;
+            state.rebase = Some(FakeRebase {
+                original_commits: state.commits.clone(),
+                remaining_todo: todo.into(),
+            });
+            state.commits.drain(..base_ix);
+            state.apply_rebase_todo()
+        })
+    }
+
+    fn rebase_control(
+        &self,
+        control: RebaseControl,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<RebaseOutcome>> {
+        self.with_state_async(true, move |state| match control {
+            // The commit being edited has already been applied, so there's nothing to skip.
+            RebaseControl::Continue | RebaseControl::Skip => state.apply_rebase_todo(),
+            RebaseControl::Abort => {
+                let rebase = state.rebase.take().context("no rebase in progress")?;
+                state.set_commits(rebase.original_commits);
+                Ok(RebaseOutcome::Finished)
+            }
+        })
+    }
+
     fn commit(
         &self,
         _message: gpui::SharedString,
@@ -579,6 +797,119 @@ impl GitRepository for FakeGitRepository {
         unimplemented!()
     }
 
+    fn cherry_pick(
+        &self,
+        commit: String,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            state.apply_commit(SequencerOperation::CherryPick, commit)
+        })
+    }
+
+    fn revert(
+        &self,
+        commit: String,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            state.apply_commit(SequencerOperation::Revert, commit)
+        })
+    }
+
+    fn sequencer_control(
+        &self,
+        operation: SequencerOperation,
+        control: RebaseControl,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            let commit = match state.sequencer.take() {
+                Some((current_operation, commit)) if current_operation == operation => commit,
+                sequencer => {
+                    state.sequencer = sequencer;
+                    bail!("no {} in progress", operation.command());
+                }
+            };
+            state.refs.remove(sequencer_head(operation));
+            // The conflicts count as resolved once the operation goes on.
+            state.conflicting_commits.remove(commit.sha.as_ref());
+            if control == RebaseControl::Continue {
+                state.commit_on_head(sequenced_subject(operation, &commit));
+            }
+            Ok(())
+        })
+    }
+
+    fn tags(&self) -> BoxFuture<'_, Result<Vec<Tag>>> {
+        self.with_state_async(false, move |state| {
+            let mut tags = state
+                .tags
+                .iter()
+                .map(|(name, sha)| Tag {
+                    name: name.clone().into(),
+                    sha: sha.clone().into(),
+                    subject: SharedString::default(),
+                    creation_timestamp: 0,
+                })
+                .collect::<Vec<_>>();
+            tags.sort_by(|a, b| a.name.cmp(&b.name));
+            Ok(tags)
+        })
+    }
+
+    fn create_tag(
+        &self,
+        name: String,
+        target: Option<String>,
+        _message: Option<String>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            if state.tags.contains_key(&name) {
+                bail!("tag '{name}' already exists");
+            }
+            let sha = match target {
+                Some(target) => state.refs.get(&target).cloned().unwrap_or(target),
+                None => state.refs.get("HEAD").cloned().context("no HEAD")?,
+            };
+            state.tags.insert(name, sha);
+            Ok(())
+        })
+    }
+
+    fn delete_tag(&self, name: String) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            if state.tags.remove(&name).is_none() {
+                bail!("no such tag: {name}");
+            }
+            Ok(())
+        })
+    }
+
+    fn push_tag(
+        &self,
+        name: String,
+        remote: String,
+        _askpass: AskPassDelegate,
+        _env: Arc<HashMap<String, String>>,
+        _cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<git::repository::RemoteCommandOutput>> {
+        self.with_state_async(true, move |state| {
+            if !state.tags.contains_key(&name) {
+                bail!("no such tag: {name}");
+            }
+            if !state.remotes.contains_key(&remote) {
+                bail!("no such remote: {remote}");
+            }
+            state.pushed_tags.push((remote, name));
+            Ok(RemoteCommandOutput {
+                stdout: String::new(),
+                stderr: String::new(),
+            })
+        })
+    }
+
     fn push(
         &self,
         _branch: String,
@@ -613,6 +944,32 @@ impl GitRepository for FakeGitRepository {
         unimplemented!()
     }
 
+    fn submodule_operation(
+        &self,
+        operation: SubmoduleOperation,
+        paths: Vec<RepoPath>,
+        _askpass: AskPassDelegate,
+        _env: Arc<HashMap<String, String>>,
+        _cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<git::repository::RemoteCommandOutput>> {
+        self.with_state_async(true, move |state| {
+            if operation == SubmoduleOperation::Update {
+                for submodule in &mut state.submodules {
+                    if (paths.is_empty() || paths.contains(&submodule.path))
+                        && submodule.status != SubmoduleStatus::Conflicted
+                    {
+                        submodule.status = SubmoduleStatus::UpToDate;
+                    }
+                }
+            }
+            state.submodule_operations.push((operation, paths));
+            Ok(RemoteCommandOutput {
+                stdout: String::new(),
+                stderr: String::new(),
+            })
+        })
+    }
+
     fn get_all_remotes(&self) -> BoxFuture<'_, Result<Vec<Remote>>> {
         self.with_state_async(false, move |state| {
             let remotes = state
@@ -723,6 +1080,22 @@ impl GitRepository for FakeGitRepository {
     }
 }
 
+/// The ref git keeps pointing at the commit a stopped cherry-pick or revert was applying.
+fn sequencer_head(operation: SequencerOperation) -> &'static str {
+    match operation {
+        SequencerOperation::CherryPick => "CHERRY_PICK_HEAD",
+        SequencerOperation::Revert => "REVERT_HEAD",
+    }
+}
+
+/// The subject of the commit that cherry-picks or reverts `commit`.
+fn sequenced_subject(operation: SequencerOperation, commit: &CommitSummary) -> SharedString {
+    match operation {
+        SequencerOperation::CherryPick => commit.subject.clone(),
+        SequencerOperation::Revert => format!("Revert \"{}\"", commit.subject).into(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{FakeFs, Fs};
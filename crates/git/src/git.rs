@@ -5,6 +5,7 @@ mod remote;
 pub mod repository;
 pub mod stash;
 pub mod status;
+pub mod submodule;
 
 pub use crate::hosting_provider::*;
 pub use crate::remote::*;
@@ -66,6 +67,16 @@ actions!(
         TrashUntrackedFiles,
         /// Undoes the last commit, keeping changes in the working directory.
         Uncommit,
+        /// Shows the history of the whole repository as a graph of branches.
+        CommitGraph,
+        /// Rebases the current branch interactively onto a commit picked from its history.
+        InteractiveRebase,
+        /// Continues the paused rebase.
+        RebaseContinue,
+        /// Skips the commit the paused rebase stopped at.
+        RebaseSkip,
+        /// Aborts the paused rebase.
+        RebaseAbort,
         /// Pushes commits to the remote repository.
         Push,
         /// Pushes commits to a specific remote branch.
@@ -80,6 +91,12 @@ actions!(
         Fetch,
         /// Fetches changes from a specific remote.
         FetchFrom,
+        /// Registers the repository's submodules in its config.
+        InitSubmodules,
+        /// Checks out the commits recorded for the repository's submodules, cloning missing ones.
+        UpdateSubmodules,
+        /// Copies the URLs of the repository's submodules from `.gitmodules` into its config.
+        SyncSubmodules,
         /// Creates a new commit with staged changes.
         Commit,
         /// Amends the last commit with staged changes.
@@ -0,0 +1,216 @@
+use crate::{Oid, repository::RepoPath};
+use std::str::FromStr;
+use util::ResultExt as _;
+
+/// How the checkout of a submodule relates to the commit its parent repository records for it.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SubmoduleStatus {
+    /// The recorded commit is checked out.
+    UpToDate,
+    /// The submodule hasn't been cloned into the working tree yet.
+    Uninitialized,
+    /// A commit other than the recorded one is checked out.
+    OutOfDate,
+    /// The submodule has merge conflicts.
+    Conflicted,
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Submodule {
+    pub path: RepoPath,
+    pub status: SubmoduleStatus,
+    /// Whether the working tree of the submodule has changes of its own, including untracked files.
+    pub is_dirty: bool,
+    /// The commit checked out in the submodule, or the one recorded in the index while the
+    /// submodule is uninitialized.
+    pub sha: Oid,
+    /// The commit recorded for the submodule in the parent's `HEAD`, unless it hasn't been
+    /// committed yet.
+    pub head_sha: Option<Oid>,
+}
+
+impl Submodule {
+    /// The commits the parent's `HEAD` and the submodule point to, when the pointer has changed.
+    pub fn pointer_change(&self) -> Option<(Oid, Oid)> {
+        let head_sha = self.head_sha?;
+        (head_sha != self.sha).then_some((head_sha, self.sha))
+    }
+
+    /// Describes how the submodule differs from a clean checkout of its recorded commit, like
+    /// "out of date, dirty", or returns `None` when it doesn't.
+    pub fn state_description(&self) -> Option<String> {
+        let status = match self.status {
+            SubmoduleStatus::UpToDate => None,
+            SubmoduleStatus::Uninitialized => Some("uninitialized"),
+            SubmoduleStatus::OutOfDate => Some("out of date"),
+            SubmoduleStatus::Conflicted => Some("conflicted"),
+        };
+        let states = status
+            .into_iter()
+            .chain(self.is_dirty.then_some("dirty"))
+            .collect::<Vec<_>>();
+        (!states.is_empty()).then(|| states.join(", "))
+    }
+}
+
+/// What `git submodule` does to the submodules of a repository.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SubmoduleOperation {
+    /// Registers the submodules in the repository's config.
+    Init,
+    /// Clones missing submodules, and checks out the recorded commits.
+    Update,
+    /// Copies the submodule URLs from `.gitmodules` into the config.
+    Sync,
+}
+
+impl SubmoduleOperation {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Init => "init",
+            Self::Update => "update",
+            Self::Sync => "sync",
+        }
+    }
+}
+
+/// Parses the output of `git submodule status`, whose lines look like
+/// `+<sha> <path> (<description>)`.
+///
+/// Every submodule is assumed to have no local changes, and to have its commit in `HEAD`, until
+/// [`apply_submodule_changes`] says otherwise.
+pub(crate) fn parse_submodule_status(output: &str) -> Vec<Submodule> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut chars = line.chars();
+            let status = match chars.next()? {
+                ' ' => SubmoduleStatus::UpToDate,
+                '-' => SubmoduleStatus::Uninitialized,
+                '+' => SubmoduleStatus::OutOfDate,
+                'U' => SubmoduleStatus::Conflicted,
+                _ => return None,
+            };
+            let (sha, path) = chars.as_str().split_once(' ')?;
+            // Initialized submodules are followed by the `git describe` of their commit.
+            let path = if status == SubmoduleStatus::Uninitialized {
+                path
+            } else {
+                path.rsplit_once(" (").map_or(path, |(path, _)| path)
+            };
+            let sha = Oid::from_str(sha).log_err()?;
+            Some(Submodule {
+                path: RepoPath::new(path).log_err()?,
+                status,
+                is_dirty: false,
+                sha,
+                head_sha: Some(sha),
+            })
+        })
+        .collect()
+}
+
+/// Updates `submodules` from the `git status --porcelain=v2 -z` of their paths, which reports the
+/// submodules with local changes, or with a commit other than the one in `HEAD`.
+pub(crate) fn apply_submodule_changes(submodules: &mut [Submodule], output: &str) {
+    for record in output.split('\0') {
+        let fields = record.splitn(9, ' ').collect::<Vec<_>>();
+        let ["1", _, submodule_state, _, _, _, head_sha, _, path] = fields.as_slice() else {
+            continue;
+        };
+        let Some(submodule) = submodules
+            .iter_mut()
+            .find(|submodule| submodule.path.as_unix_str() == *path)
+        else {
+            continue;
+        };
+        // `S<c><m><u>`, where `m` and `u` flag modified and untracked files.
+        submodule.is_dirty = matches!(
+            submodule_state.as_bytes(),
+            [b'S', _, b'M', _] | [b'S', _, _, b'U']
+        );
+        if let Ok(head_sha) = Oid::from_str(head_sha) {
+            submodule.head_sha = (!head_sha.is_zero()).then_some(head_sha);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::repo_path;
+
+    const SHA_1: &str = "1111111111111111111111111111111111111111";
+    const SHA_2: &str = "2222222222222222222222222222222222222222";
+    const SHA_3: &str = "3333333333333333333333333333333333333333";
+
+    #[test]
+    fn test_parse_submodule_status() {
+        let output = format!(
+            " {SHA_1} libs/up-to-date (v1.0)\n\
+             -{SHA_2} libs/uninitialized\n\
+             +{SHA_3} libs/with (parens) (v1.0-2-g3333333)\n"
+        );
+        let submodules = parse_submodule_status(&output);
+        assert_eq!(
+            submodules
+                .iter()
+                .map(|submodule| (submodule.path.clone(), submodule.status, submodule.sha))
+                .collect::<Vec<_>>(),
+            [
+                (
+                    repo_path("libs/up-to-date"),
+                    SubmoduleStatus::UpToDate,
+                    SHA_1.parse().unwrap()
+                ),
+                (
+                    repo_path("libs/uninitialized"),
+                    SubmoduleStatus::Uninitialized,
+                    SHA_2.parse().unwrap()
+                ),
+                (
+                    repo_path("libs/with (parens)"),
+                    SubmoduleStatus::OutOfDate,
+                    SHA_3.parse().unwrap()
+                ),
+            ]
+        );
+        assert!(
+            submodules
+                .iter()
+                .all(|submodule| submodule.pointer_change().is_none())
+        );
+    }
+
+    #[test]
+    fn test_apply_submodule_changes() {
+        let mut submodules = parse_submodule_status(&format!(
+            " {SHA_1} dirty (v1.0)\n+{SHA_3} moved (heads/main)\n {SHA_2} new (heads/main)\n"
+        ));
+        let zero = "0000000000000000000000000000000000000000";
+        let output = format!(
+            "1 .M S.MU 160000 160000 160000 {SHA_1} {SHA_1} dirty\0\
+             1 .M SC.. 160000 160000 160000 {SHA_2} {SHA_2} moved\0\
+             1 A. S... 000000 160000 160000 {zero} {SHA_2} new\0"
+        );
+        apply_submodule_changes(&mut submodules, &output);
+
+        assert!(submodules[0].is_dirty);
+        assert_eq!(submodules[0].pointer_change(), None);
+        assert_eq!(submodules[0].state_description().as_deref(), Some("dirty"));
+
+        assert!(!submodules[1].is_dirty);
+        assert_eq!(
+            submodules[1].pointer_change(),
+            Some((SHA_2.parse().unwrap(), SHA_3.parse().unwrap()))
+        );
+        assert_eq!(
+            submodules[1].state_description().as_deref(),
+            Some("out of date")
+        );
+
+        assert_eq!(submodules[2].head_sha, None);
+        assert_eq!(submodules[2].pointer_change(), None);
+        assert_eq!(submodules[2].state_description(), None);
+    }
+}
@@ -0,0 +1,1271 @@
+use std::any::{Any, TypeId};
+use std::time::Duration;
+
+use anyhow::{Context as _, Result};
+use editor::{Editor, EditorEvent};
+use futures::channel::oneshot;
+use git::{
+    SHORT_SHA_LENGTH,
+    repository::{CommitLogEntry, CommitLogQuery, CommitRefKind, RepoPath, ResetMode},
+};
+use gpui::{
+    AnyElement, AnyEntity, App, Bounds, ClickEvent, ClipboardItem, ContentMask, Context, Corner,
+    DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, FontWeight, MouseButton,
+    MouseDownEvent, PathBuilder, Pixels, Point, PromptLevel, ScrollStrategy, Subscription, Task,
+    UniformListScrollHandle, WeakEntity, Window, anchored, canvas, deferred, point, size,
+    uniform_list,
+};
+use project::{
+    Project, ProjectPath,
+    git_store::{Repository, RepositoryEvent},
+};
+use time::OffsetDateTime;
+use ui::{Checkbox, Chip, ContextMenu, Divider, ToggleState, WithScrollbar, prelude::*};
+use workspace::{
+    Item, Workspace,
+    item::{ItemEvent, SaveOptions},
+};
+
+use crate::{
+    commit_view::{CommitOperation, CommitView, run_commit_operation},
+    git_panel::show_error_toast,
+    project_diff::ProjectDiff,
+};
+
+const PAGE_SIZE: usize = 200;
+/// The next page is loaded once the list is scrolled this close to the last loaded commit.
+const LOAD_MORE_THRESHOLD: usize = 50;
+const FILTER_DEBOUNCE: Duration = Duration::from_millis(300);
+const LANE_WIDTH: f32 = 14.;
+/// Lanes past this many are clipped, so that a wide graph doesn't push the commits out of view.
+const MAX_VISIBLE_LANES: usize = 16;
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(|workspace, _: &git::CommitGraph, window, cx| {
+        CommitGraphView::deploy(workspace, window, cx);
+    });
+}
+
+/// A line of the graph, drawn in the row of a commit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum GraphEdge {
+    /// A lane passing by the commit, from the top to the bottom of the row.
+    Pass { lane: usize, color: u32 },
+    /// A lane ending at the commit, coming from one of its children above.
+    Incoming { from_lane: usize, color: u32 },
+    /// A lane leaving the commit for one of its parents below.
+    Outgoing { to_lane: usize, color: u32 },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct GraphRow {
+    lane: usize,
+    color: u32,
+    edges: Vec<GraphEdge>,
+    lane_count: usize,
+}
+
+#[derive(Clone, Debug)]
+struct Lane {
+    /// The commit a later row will draw this lane into.
+    sha: SharedString,
+    color: u32,
+}
+
+/// Assigns commits to lanes, one row at a time, so that the graph can be extended page by page.
+/// Commits must be pushed children first, as `git log --topo-order` lists them.
+#[derive(Default)]
+struct GraphLayout {
+    lanes: Vec<Option<Lane>>,
+    next_color: u32,
+}
+
+impl GraphLayout {
+    fn push(&mut self, sha: &SharedString, parents: &[SharedString]) -> GraphRow {
+        let lane_count_above = self.lanes.len();
+        let mut edges = Vec::new();
+
+        let mut node = None;
+        for (ix, lane) in self.lanes.iter_mut().enumerate() {
+            if let Some(incoming) = lane.take_if(|lane| &lane.sha == sha) {
+                edges.push(GraphEdge::Incoming {
+                    from_lane: ix,
+                    color: incoming.color,
+                });
+                node.get_or_insert((ix, incoming.color));
+            }
+        }
+        let (node_lane, node_color) = match node {
+            Some(node) => node,
+            None => (self.free_lane(), self.next_color()),
+        };
+
+        for (ix, lane) in self.lanes.iter().enumerate() {
+            if let Some(lane) = lane {
+                edges.push(GraphEdge::Pass {
+                    lane: ix,
+                    color: lane.color,
+                });
+            }
+        }
+
+        for (parent_ix, parent) in parents.iter().enumerate() {
+            // The first parent continues the commit's lane, even when another lane already leads
+            // to it, and the lanes join at the parent's row. Other parents of a merge join the
+            // lane that already leads to them, if any.
+            let existing = self.lanes.iter().enumerate().find_map(|(ix, lane)| {
+                let lane = lane.as_ref().filter(|lane| &lane.sha == parent)?;
+                Some((ix, lane.color))
+            });
+            if parent_ix > 0
+                && let Some((ix, color)) = existing
+            {
+                edges.push(GraphEdge::Outgoing { to_lane: ix, color });
+                continue;
+            }
+
+            let (lane, color) = if parent_ix == 0 {
+                (node_lane, node_color)
+            } else {
+                (self.free_lane(), self.next_color())
+            };
+            if lane == self.lanes.len() {
+                self.lanes.push(None);
+            }
+            self.lanes[lane] = Some(Lane {
+                sha: parent.clone(),
+                color,
+            });
+            edges.push(GraphEdge::Outgoing {
+                to_lane: lane,
+                color,
+            });
+        }
+
+        let lane_count = lane_count_above.max(self.lanes.len()).max(node_lane + 1);
+        while self.lanes.last().is_some_and(Option::is_none) {
+            self.lanes.pop();
+        }
+
+        GraphRow {
+            lane: node_lane,
+            color: node_color,
+            edges,
+            lane_count,
+        }
+    }
+
+    fn free_lane(&self) -> usize {
+        self.lanes
+            .iter()
+            .position(Option::is_none)
+            .unwrap_or(self.lanes.len())
+    }
+
+    fn next_color(&mut self) -> u32 {
+        let color = self.next_color;
+        self.next_color += 1;
+        color
+    }
+}
+
+fn paint_graph_row(
+    row: &GraphRow,
+    is_head: bool,
+    bounds: Bounds<Pixels>,
+    window: &mut Window,
+    cx: &App,
+) {
+    let accents = cx.theme().accents();
+    let lane_x = |lane: usize| bounds.origin.x + px(LANE_WIDTH * (lane as f32 + 0.5));
+    let top = bounds.origin.y;
+    let bottom = bounds.origin.y + bounds.size.height;
+    let center = point(lane_x(row.lane), bounds.center().y);
+
+    window.with_content_mask(Some(ContentMask { bounds }), |window| {
+        for edge in &row.edges {
+            let mut path = PathBuilder::stroke(px(1.5));
+            let color = match *edge {
+                GraphEdge::Pass { lane, color } => {
+                    path.move_to(point(lane_x(lane), top));
+                    path.line_to(point(lane_x(lane), bottom));
+                    color
+                }
+                GraphEdge::Incoming { from_lane, color } => {
+                    let start = point(lane_x(from_lane), top);
+                    path.move_to(start);
+                    path.curve_to(center, point(start.x, center.y));
+                    color
+                }
+                GraphEdge::Outgoing { to_lane, color } => {
+                    let end = point(lane_x(to_lane), bottom);
+                    path.move_to(center);
+                    path.curve_to(end, point(end.x, center.y));
+                    color
+                }
+            };
+            if let Ok(path) = path.build() {
+                window.paint_path(path, accents.color_for_index(color));
+            }
+        }
+
+        let radius = if is_head { px(4.5) } else { px(3.5) };
+        let node = Bounds::centered_at(center, size(radius * 2., radius * 2.));
+        window
+            .paint_quad(gpui::fill(node, accents.color_for_index(row.color)).corner_radii(radius));
+    });
+}
+
+fn short_sha(sha: &str) -> &str {
+    sha.get(..SHORT_SHA_LENGTH).unwrap_or(sha)
+}
+
+fn short_range(revision_range: &str) -> String {
+    revision_range
+        .split("...")
+        .map(short_sha)
+        .collect::<Vec<_>>()
+        .join("...")
+}
+
+/// The history of the whole repository, drawn as a graph of branch lanes, and loaded a page at a
+/// time as it is scrolled.
+pub struct CommitGraphView {
+    repository: Entity<Repository>,
+    workspace: WeakEntity<Workspace>,
+    read_only: bool,
+    /// The range of commits shown instead of the whole history, like the commits a submodule
+    /// moved across.
+    revision_range: Option<SharedString>,
+    entries: Vec<CommitLogEntry>,
+    /// The graph rows of `entries`, empty while the log is filtered by author or message, whose
+    /// commits don't connect to each other.
+    rows: Vec<GraphRow>,
+    layout: GraphLayout,
+    lane_count: usize,
+    all_refs: bool,
+    author_editor: Entity<Editor>,
+    message_editor: Entity<Editor>,
+    path_editor: Entity<Editor>,
+    /// The name editor of a branch being created at a commit.
+    new_branch: Option<(SharedString, Entity<Editor>)>,
+    selected_ix: Option<usize>,
+    has_more: bool,
+    error: Option<SharedString>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    context_menu: Option<(Entity<ContextMenu>, Point<Pixels>, Subscription)>,
+    load_task: Option<Task<()>>,
+    filter_task: Option<Task<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl CommitGraphView {
+    pub fn deploy(workspace: &mut Workspace, window: &mut Window, cx: &mut Context<Workspace>) {
+        let Some(repository) = workspace.project().read(cx).active_repository(cx) else {
+            return;
+        };
+        Self::open(repository, None, workspace, window, cx);
+    }
+
+    /// Shows the commits between the two ends of `revision_range` in `repository`.
+    pub fn deploy_range(
+        repository: Entity<Repository>,
+        revision_range: SharedString,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        Self::open(repository, Some(revision_range), workspace, window, cx);
+    }
+
+    fn open(
+        repository: Entity<Repository>,
+        revision_range: Option<SharedString>,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let existing = workspace.items_of_type::<Self>(cx).find(|view| {
+            let view = view.read(cx);
+            view.repository == repository && view.revision_range == revision_range
+        });
+        if let Some(existing) = existing {
+            workspace.activate_item(&existing, true, true, window, cx);
+            return;
+        }
+
+        let read_only = workspace.project().read(cx).is_read_only(cx);
+        let workspace_handle = workspace.weak_handle();
+        let view = cx.new(|cx| {
+            Self::new(
+                repository,
+                revision_range,
+                workspace_handle,
+                read_only,
+                window,
+                cx,
+            )
+        });
+        workspace.add_item_to_active_pane(Box::new(view), None, true, window, cx);
+    }
+
+    fn new(
+        repository: Entity<Repository>,
+        revision_range: Option<SharedString>,
+        workspace: WeakEntity<Workspace>,
+        read_only: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let filter_editor = |placeholder: &str, window: &mut Window, cx: &mut Context<Self>| {
+            let editor = cx.new(|cx| {
+                let mut editor = Editor::single_line(window, cx);
+                editor.set_placeholder_text(placeholder, window, cx);
+                editor
+            });
+            let subscription = cx.subscribe(&editor, |this, _, event: &EditorEvent, cx| {
+                if let EditorEvent::BufferEdited = event {
+                    this.schedule_reload(cx);
+                }
+            });
+            (editor, subscription)
+        };
+        let (author_editor, author_subscription) = filter_editor("Author", window, cx);
+        let (message_editor, message_subscription) = filter_editor("Message", window, cx);
+        let (path_editor, path_subscription) = filter_editor("Path", window, cx);
+        let repository_subscription =
+            cx.subscribe(&repository, |this, _, event: &RepositoryEvent, cx| {
+                if let RepositoryEvent::BranchChanged = event {
+                    this.reload(cx);
+                }
+            });
+
+        let mut this = Self {
+            repository,
+            workspace,
+            read_only,
+            all_refs: revision_range.is_none(),
+            revision_range,
+            entries: Vec::new(),
+            rows: Vec::new(),
+            layout: GraphLayout::default(),
+            lane_count: 0,
+            author_editor,
+            message_editor,
+            path_editor,
+            new_branch: None,
+            selected_ix: None,
+            has_more: true,
+            error: None,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+            context_menu: None,
+            load_task: None,
+            filter_task: None,
+            _subscriptions: vec![
+                author_subscription,
+                message_subscription,
+                path_subscription,
+                repository_subscription,
+            ],
+        };
+        this.load_more(cx);
+        this
+    }
+
+    fn filter_text(editor: &Entity<Editor>, cx: &App) -> Option<String> {
+        let text = editor.read(cx).text(cx);
+        let text = text.trim();
+        (!text.is_empty()).then(|| text.to_string())
+    }
+
+    fn query(&self, cx: &App) -> Result<CommitLogQuery> {
+        let path = Self::filter_text(&self.path_editor, cx)
+            .map(|path| RepoPath::new(&path).with_context(|| format!("Invalid path {path:?}")))
+            .transpose()?;
+        Ok(CommitLogQuery {
+            skip: self.entries.len(),
+            limit: PAGE_SIZE,
+            all_refs: self.all_refs,
+            author: Self::filter_text(&self.author_editor, cx),
+            message: Self::filter_text(&self.message_editor, cx),
+            path,
+            revision_range: self.revision_range.as_ref().map(ToString::to_string),
+        })
+    }
+
+    fn schedule_reload(&mut self, cx: &mut Context<Self>) {
+        self.filter_task = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(FILTER_DEBOUNCE).await;
+            this.update(cx, |this, cx| this.reload(cx)).ok();
+        }));
+    }
+
+    /// Drops the loaded commits, and loads the first page of the log again.
+    fn reload(&mut self, cx: &mut Context<Self>) {
+        self.filter_task = None;
+        self.load_task = None;
+        self.entries.clear();
+        self.rows.clear();
+        self.layout = GraphLayout::default();
+        self.lane_count = 0;
+        self.selected_ix = None;
+        self.has_more = true;
+        self.error = None;
+        self.load_more(cx);
+    }
+
+    fn load_more(&mut self, cx: &mut Context<Self>) {
+        if self.load_task.is_some() || !self.has_more {
+            return;
+        }
+        let query = match self.query(cx) {
+            Ok(query) => query,
+            Err(error) => {
+                self.has_more = false;
+                self.error = Some(error.to_string().into());
+                cx.notify();
+                return;
+            }
+        };
+        let draw_graph = query.author.is_none() && query.message.is_none();
+        let log = self
+            .repository
+            .update(cx, |repository, _| repository.commit_log(query));
+
+        self.load_task = Some(cx.spawn(async move |this, cx| {
+            let result = log.await.map_err(anyhow::Error::from).and_then(|log| log);
+            this.update(cx, |this, cx| {
+                this.load_task = None;
+                match result {
+                    Ok(entries) => {
+                        this.has_more = entries.len() >= PAGE_SIZE;
+                        if draw_graph {
+                            for entry in &entries {
+                                let row = this.layout.push(&entry.sha, &entry.parents);
+                                this.lane_count = this.lane_count.max(row.lane_count);
+                                this.rows.push(row);
+                            }
+                        }
+                        this.entries.extend(entries);
+                    }
+                    Err(error) => {
+                        this.has_more = false;
+                        this.error = Some(error.to_string().into());
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        }));
+        cx.notify();
+    }
+
+    fn toggle_all_refs(&mut self, cx: &mut Context<Self>) {
+        self.all_refs = !self.all_refs;
+        self.reload(cx);
+    }
+
+    fn select_ix(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.selected_ix = Some(ix);
+        self.scroll_handle
+            .scroll_to_item(ix, ScrollStrategy::Nearest);
+        if ix + LOAD_MORE_THRESHOLD >= self.entries.len() {
+            self.load_more(cx);
+        }
+        cx.notify();
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let ix = self
+            .selected_ix
+            .map_or(0, |ix| (ix + 1).min(self.entries.len() - 1));
+        self.select_ix(ix, cx);
+    }
+
+    fn select_previous(
+        &mut self,
+        _: &menu::SelectPrevious,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let ix = self.selected_ix.map_or(0, |ix| ix.saturating_sub(1));
+        self.select_ix(ix, cx);
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some((sha, editor)) = &self.new_branch
+            && editor.focus_handle(cx).is_focused(window)
+        {
+            let name = editor.read(cx).text(cx).trim().replace(' ', "-");
+            if name.is_empty() {
+                return;
+            }
+            let job = self.repository.update(cx, |repository, _| {
+                repository.create_branch(name.clone(), Some(sha.to_string()))
+            });
+            report_errors(
+                format!("switch -c {name}").into(),
+                job,
+                self.workspace.clone(),
+                window,
+                cx,
+            );
+            self.new_branch = None;
+            window.focus(&self.focus_handle);
+            cx.notify();
+        } else if self.focus_handle.is_focused(window) {
+            if let Some(ix) = self.selected_ix {
+                self.open_commit(ix, window, cx);
+            }
+        } else {
+            // Apply the filters right away instead of waiting for the debounce.
+            self.reload(cx);
+            window.focus(&self.focus_handle);
+        }
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        self.new_branch = None;
+        window.focus(&self.focus_handle);
+        cx.notify();
+    }
+
+    fn open_commit(&self, ix: usize, window: &mut Window, cx: &mut App) {
+        let Some(entry) = self.entries.get(ix) else {
+            return;
+        };
+        CommitView::open(
+            entry.sha.to_string(),
+            self.repository.downgrade(),
+            self.workspace.clone(),
+            None,
+            None,
+            window,
+            cx,
+        );
+    }
+
+    fn start_new_branch(&mut self, sha: SharedString, window: &mut Window, cx: &mut Context<Self>) {
+        let editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text(
+                &format!("Name of the new branch at {}", short_sha(&sha)),
+                window,
+                cx,
+            );
+            editor
+        });
+        window.focus(&editor.focus_handle(cx));
+        self.new_branch = Some((sha, editor));
+        cx.notify();
+    }
+
+    fn deploy_entry_context_menu(
+        &mut self,
+        position: Point<Pixels>,
+        ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(entry) = self.entries.get(ix) else {
+            return;
+        };
+        let sha = entry.sha.clone();
+        let branches = entry
+            .refs
+            .iter()
+            .filter(|commit_ref| {
+                matches!(
+                    commit_ref.kind,
+                    CommitRefKind::LocalBranch | CommitRefKind::RemoteBranch
+                )
+            })
+            .map(|commit_ref| commit_ref.name.clone())
+            .collect::<Vec<_>>();
+        let repository = self.repository.clone();
+        let workspace = self.workspace.clone();
+        let read_only = self.read_only;
+        let this = cx.weak_entity();
+
+        let operation_handler = |operation| {
+            let sha = sha.clone();
+            let repository = repository.clone();
+            let workspace = workspace.clone();
+            move |window: &mut Window, cx: &mut App| {
+                run_commit_operation(
+                    operation,
+                    sha.clone(),
+                    repository.clone(),
+                    workspace.clone(),
+                    window,
+                    cx,
+                )
+            }
+        };
+        let reset_handler = |mode| {
+            let sha = sha.clone();
+            let repository = repository.clone();
+            let workspace = workspace.clone();
+            move |window: &mut Window, cx: &mut App| {
+                confirm_reset(
+                    sha.clone(),
+                    mode,
+                    repository.clone(),
+                    workspace.clone(),
+                    window,
+                    cx,
+                )
+            }
+        };
+        let cherry_pick = operation_handler(CommitOperation::CherryPick);
+        let revert = operation_handler(CommitOperation::Revert);
+        let soft_reset = reset_handler(ResetMode::Soft);
+        let mixed_reset = reset_handler(ResetMode::Mixed);
+
+        let context_menu = ContextMenu::build(window, cx, move |mut context_menu, _, _| {
+            if !read_only {
+                for branch in branches {
+                    let repository = repository.clone();
+                    let workspace = workspace.clone();
+                    context_menu = context_menu.entry(
+                        format!("Checkout {branch}"),
+                        None,
+                        move |window, cx| {
+                            let job = repository.update(cx, |repository, _| {
+                                repository.change_branch(branch.to_string())
+                            });
+                            report_errors(
+                                format!("switch {branch}").into(),
+                                job,
+                                workspace.clone(),
+                                window,
+                                cx,
+                            );
+                        },
+                    );
+                }
+                context_menu = context_menu
+                    .entry("Checkout Commit (Detached)", None, {
+                        let sha = sha.clone();
+                        let repository = repository.clone();
+                        let workspace = workspace.clone();
+                        move |window, cx| {
+                            let job = repository.update(cx, |repository, _| {
+                                repository.checkout_commit(sha.to_string())
+                            });
+                            report_errors(
+                                format!("switch --detach {}", short_sha(&sha)).into(),
+                                job,
+                                workspace.clone(),
+                                window,
+                                cx,
+                            );
+                        }
+                    })
+                    .entry("Create Branch Here…", None, {
+                        let sha = sha.clone();
+                        let this = this.clone();
+                        move |window, cx| {
+                            this.update(cx, |this, cx| {
+                                this.start_new_branch(sha.clone(), window, cx)
+                            })
+                            .ok();
+                        }
+                    })
+                    .separator()
+                    .entry("Reset Current Branch Here (Soft)", None, soft_reset)
+                    .entry("Reset Current Branch Here (Mixed)", None, mixed_reset)
+                    .separator()
+                    .entry("Cherry-Pick", None, cherry_pick)
+                    .entry("Revert", None, revert)
+                    .separator();
+            }
+            context_menu
+                .entry("Compare with Working Tree", None, {
+                    let sha = sha.clone();
+                    let workspace = workspace.clone();
+                    move |window, cx| {
+                        workspace
+                            .update(cx, |workspace, cx| {
+                                ProjectDiff::deploy_since_commit(workspace, sha.clone(), window, cx)
+                            })
+                            .ok();
+                    }
+                })
+                .entry("View Commit", None, {
+                    let this = this.clone();
+                    move |window, cx| {
+                        this.update(cx, |this, cx| this.open_commit(ix, window, cx))
+                            .ok();
+                    }
+                })
+                .entry("Copy SHA", None, {
+                    let sha = sha.clone();
+                    move |_, cx| cx.write_to_clipboard(ClipboardItem::new_string(sha.to_string()))
+                })
+        });
+        self.selected_ix = Some(ix);
+        self.set_context_menu(context_menu, position, window, cx);
+    }
+
+    fn set_context_menu(
+        &mut self,
+        context_menu: Entity<ContextMenu>,
+        position: Point<Pixels>,
+        window: &Window,
+        cx: &mut Context<Self>,
+    ) {
+        let subscription = cx.subscribe_in(
+            &context_menu,
+            window,
+            |this, _, _: &DismissEvent, window, cx| {
+                if this.context_menu.as_ref().is_some_and(|context_menu| {
+                    context_menu.0.focus_handle(cx).contains_focused(window, cx)
+                }) {
+                    cx.focus_self(window);
+                }
+                this.context_menu.take();
+                cx.notify();
+            },
+        );
+        self.context_menu = Some((context_menu, position, subscription));
+        cx.notify();
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let filter = |editor: &Entity<Editor>| {
+            div()
+                .w(rems(10.))
+                .px_1p5()
+                .py_0p5()
+                .rounded_sm()
+                .border_1()
+                .border_color(cx.theme().colors().border_variant)
+                .child(editor.clone())
+        };
+        let count = if self.has_more {
+            format!("{}+ commits", self.entries.len())
+        } else {
+            format!("{} commits", self.entries.len())
+        };
+
+        h_flex()
+            .h(rems_from_px(41.))
+            .pl_3()
+            .pr_2()
+            .gap_2()
+            .justify_between()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                h_flex()
+                    .gap_1p5()
+                    .child(filter(&self.author_editor))
+                    .child(filter(&self.message_editor))
+                    .child(filter(&self.path_editor))
+                    .map(|this| match &self.revision_range {
+                        Some(revision_range) => this.child(
+                            Label::new(short_range(revision_range))
+                                .size(LabelSize::Small)
+                                .color(Color::Muted)
+                                .buffer_font(cx),
+                        ),
+                        None => this.child(
+                            Checkbox::new("all-branches", ToggleState::from(self.all_refs))
+                                .label("All Branches")
+                                .label_size(LabelSize::Small)
+                                .on_click(cx.listener(|this, _, _, cx| this.toggle_all_refs(cx))),
+                        ),
+                    }),
+            )
+            .child(
+                h_flex()
+                    .gap_1p5()
+                    .child(Label::new(count).size(LabelSize::Small).color(Color::Muted))
+                    .child(Divider::vertical())
+                    .child(
+                        IconButton::new("refresh-commit-graph", IconName::ArrowCircle)
+                            .icon_size(IconSize::Small)
+                            .disabled(self.load_task.is_some())
+                            .on_click(cx.listener(|this, _, _, cx| this.reload(cx))),
+                    ),
+            )
+    }
+
+    fn render_new_branch(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let (sha, editor) = self.new_branch.as_ref()?;
+        Some(
+            h_flex()
+                .px_3()
+                .py_1()
+                .gap_2()
+                .border_b_1()
+                .border_color(cx.theme().colors().border_variant)
+                .child(Icon::new(IconName::GitBranchPlus).size(IconSize::Small))
+                .child(
+                    Label::new(format!("New branch at {}", short_sha(sha)))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+                .child(div().flex_1().child(editor.clone()))
+                .child(
+                    Button::new("cancel-new-branch", "Cancel")
+                        .label_size(LabelSize::Small)
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.cancel(&menu::Cancel, window, cx)
+                        })),
+                ),
+        )
+    }
+
+    fn render_entry(&self, ix: usize, cx: &mut Context<Self>) -> AnyElement {
+        let entry = &self.entries[ix];
+        let is_head = entry
+            .refs
+            .iter()
+            .any(|commit_ref| commit_ref.kind == CommitRefKind::Head);
+        let graph = self.rows.get(ix).cloned().map(|row| {
+            canvas(
+                |_, _, _| {},
+                move |bounds, _, window, cx| paint_graph_row(&row, is_head, bounds, window, cx),
+            )
+            .flex_none()
+            .h_full()
+            .w(px(
+                LANE_WIDTH * self.lane_count.min(MAX_VISIBLE_LANES) as f32
+            ))
+        });
+
+        let refs = entry.refs.iter().map(|commit_ref| {
+            let (label, color) = match commit_ref.kind {
+                CommitRefKind::Head => (commit_ref.name.clone(), Color::Accent),
+                CommitRefKind::LocalBranch => (commit_ref.name.clone(), Color::Default),
+                CommitRefKind::RemoteBranch => (commit_ref.name.clone(), Color::Muted),
+                CommitRefKind::Tag => (format!("tag: {}", commit_ref.name).into(), Color::Warning),
+            };
+            Chip::new(label).label_color(color)
+        });
+
+        let commit_time = OffsetDateTime::from_unix_timestamp(entry.commit_timestamp)
+            .unwrap_or_else(|_| OffsetDateTime::UNIX_EPOCH);
+        let relative_timestamp = time_format::format_localized_timestamp(
+            commit_time,
+            OffsetDateTime::now_utc(),
+            time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC),
+            time_format::TimestampFormat::Relative,
+        );
+        let colors = cx.theme().colors();
+
+        h_flex()
+            .id(("commit", ix))
+            .h_7()
+            .w_full()
+            .pl_2()
+            .pr_3()
+            .gap_2()
+            .cursor_pointer()
+            .when(self.selected_ix == Some(ix), |row| {
+                row.bg(colors.element_selected)
+            })
+            .hover(|row| row.bg(colors.element_hover))
+            .children(graph)
+            .child(
+                h_flex().min_w_0().flex_1().gap_1().children(refs).child(
+                    Label::new(entry.subject.clone())
+                        .size(LabelSize::Small)
+                        .when(is_head, |label| label.weight(FontWeight::SEMIBOLD))
+                        .truncate(),
+                ),
+            )
+            .child(
+                div().w(rems(9.)).flex_none().child(
+                    Label::new(entry.author_name.clone())
+                        .size(LabelSize::Small)
+                        .color(Color::Muted)
+                        .truncate(),
+                ),
+            )
+            .child(
+                div().w(rems(7.)).flex_none().child(
+                    Label::new(relative_timestamp)
+                        .size(LabelSize::Small)
+                        .color(Color::Muted)
+                        .truncate(),
+                ),
+            )
+            .child(
+                Label::new(short_sha(&entry.sha).to_string())
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+                    .buffer_font(cx),
+            )
+            .on_click(cx.listener(move |this, event: &ClickEvent, window, cx| {
+                this.selected_ix = Some(ix);
+                window.focus(&this.focus_handle);
+                if event.click_count() > 1 {
+                    this.open_commit(ix, window, cx);
+                }
+                cx.notify();
+            }))
+            .on_mouse_down(
+                MouseButton::Right,
+                cx.listener(move |this, event: &MouseDownEvent, window, cx| {
+                    this.deploy_entry_context_menu(event.position, ix, window, cx);
+                    cx.stop_propagation();
+                }),
+            )
+            .into_any_element()
+    }
+
+    fn render_empty_state(&self) -> impl IntoElement {
+        let message: SharedString = if let Some(error) = &self.error {
+            error.clone()
+        } else if self.load_task.is_some() {
+            "Loading commits…".into()
+        } else {
+            "No commits".into()
+        };
+        h_flex()
+            .flex_1()
+            .justify_center()
+            .child(Label::new(message).color(Color::Muted))
+    }
+}
+
+/// Resets the current branch to `sha`, once the user confirms it.
+fn confirm_reset(
+    sha: SharedString,
+    mode: ResetMode,
+    repository: Entity<Repository>,
+    workspace: WeakEntity<Workspace>,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let (mode_name, detail) = match mode {
+        ResetMode::Soft => ("soft", "Changes of later commits are kept staged."),
+        ResetMode::Mixed => ("mixed", "Changes of later commits are kept unstaged."),
+    };
+    let answer = window.prompt(
+        PromptLevel::Warning,
+        &format!("Reset the current branch to {}?", short_sha(&sha)),
+        Some(detail),
+        &["Reset", "Cancel"],
+        cx,
+    );
+    window
+        .spawn(cx, async move |cx| {
+            if answer.await != Ok(0) {
+                return anyhow::Ok(());
+            }
+            let job = repository.update(cx, |repository, cx| {
+                repository.reset(sha.to_string(), mode, cx)
+            })?;
+            cx.update(|window, cx| {
+                report_errors(
+                    format!("reset --{mode_name} {}", short_sha(&sha)).into(),
+                    job,
+                    workspace,
+                    window,
+                    cx,
+                )
+            })
+        })
+        .detach_and_log_err(cx);
+}
+
+/// Shows the error of a git job, if it fails, as a toast.
+fn report_errors(
+    action: SharedString,
+    job: oneshot::Receiver<Result<()>>,
+    workspace: WeakEntity<Workspace>,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    window
+        .spawn(cx, async move |cx| {
+            if let Err(error) = job.await?
+                && let Some(workspace) = workspace.upgrade()
+            {
+                cx.update(|_, cx| show_error_toast(workspace, action, error, cx))?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+}
+
+impl EventEmitter<ItemEvent> for CommitGraphView {}
+
+impl Focusable for CommitGraphView {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for CommitGraphView {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let entry_count = self.entries.len();
+        let list = if entry_count == 0 {
+            self.render_empty_state().into_any_element()
+        } else {
+            let view = cx.weak_entity();
+            v_flex()
+                .flex_1()
+                .size_full()
+                .child(
+                    uniform_list("commit-graph", entry_count, move |range, _window, cx| {
+                        let Some(view) = view.upgrade() else {
+                            return Vec::new();
+                        };
+                        view.update(cx, |this, cx| {
+                            if range.end + LOAD_MORE_THRESHOLD >= this.entries.len() {
+                                this.load_more(cx);
+                            }
+                            range.map(|ix| this.render_entry(ix, cx)).collect()
+                        })
+                    })
+                    .flex_1()
+                    .size_full()
+                    .track_scroll(&self.scroll_handle),
+                )
+                .vertical_scrollbar_for(&self.scroll_handle, window, cx)
+                .into_any_element()
+        };
+
+        v_flex()
+            .key_context("CommitGraph")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(self.render_header(cx))
+            .children(self.render_new_branch(cx))
+            .when_some(
+                self.error.clone().filter(|_| entry_count > 0),
+                |this, error| {
+                    this.child(
+                        h_flex()
+                            .px_3()
+                            .py_1()
+                            .child(Label::new(error).size(LabelSize::Small).color(Color::Error)),
+                    )
+                },
+            )
+            .child(list)
+            .children(self.context_menu.as_ref().map(|(menu, position, _)| {
+                deferred(
+                    anchored()
+                        .position(*position)
+                        .anchor(Corner::TopLeft)
+                        .child(menu.clone()),
+                )
+                .with_priority(1)
+            }))
+    }
+}
+
+impl Item for CommitGraphView {
+    type Event = ItemEvent;
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        match &self.revision_range {
+            Some(revision_range) => format!("Commits {}", short_range(revision_range)).into(),
+            None => "Commit Graph".into(),
+        }
+    }
+
+    fn tab_tooltip_text(&self, cx: &App) -> Option<SharedString> {
+        let work_directory = self.repository.read(cx).work_directory_abs_path.clone();
+        Some(format!("Git history of {}", work_directory.display()).into())
+    }
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::GitBranchAlt))
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("commit graph")
+    }
+
+    fn clone_on_split(
+        &self,
+        _workspace_id: Option<workspace::WorkspaceId>,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) -> Task<Option<Entity<Self>>> {
+        Task::ready(None)
+    }
+
+    fn navigate(&mut self, _: Box<dyn Any>, _window: &mut Window, _: &mut Context<Self>) -> bool {
+        false
+    }
+
+    fn can_save(&self, _: &App) -> bool {
+        false
+    }
+
+    fn save(
+        &mut self,
+        _options: SaveOptions,
+        _project: Entity<Project>,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        Task::ready(Ok(()))
+    }
+
+    fn save_as(
+        &mut self,
+        _project: Entity<Project>,
+        _path: ProjectPath,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        Task::ready(Ok(()))
+    }
+
+    fn is_dirty(&self, _: &App) -> bool {
+        false
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        _workspace: &mut Workspace,
+        window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) {
+        window.focus(&self.focus_handle);
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<AnyEntity> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.clone().into())
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout(commits: &[(&str, &[&str])]) -> Vec<GraphRow> {
+        let mut layout = GraphLayout::default();
+        commits
+            .iter()
+            .map(|(sha, parents)| {
+                let parents = parents
+                    .iter()
+                    .map(|parent| SharedString::from(parent.to_string()))
+                    .collect::<Vec<_>>();
+                layout.push(&SharedString::from(sha.to_string()), &parents)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_linear_history_stays_in_one_lane() {
+        let rows = layout(&[("c", &["b"]), ("b", &["a"]), ("a", &[])]);
+        assert!(rows.iter().all(|row| row.lane == 0 && row.lane_count == 1));
+        assert_eq!(
+            rows[1].edges,
+            [
+                GraphEdge::Incoming {
+                    from_lane: 0,
+                    color: 0
+                },
+                GraphEdge::Outgoing {
+                    to_lane: 0,
+                    color: 0
+                },
+            ]
+        );
+        assert_eq!(
+            rows[2].edges,
+            [GraphEdge::Incoming {
+                from_lane: 0,
+                color: 0
+            }]
+        );
+    }
+
+    #[test]
+    fn test_branch_and_merge() {
+        // m merges f into c; f and c branched off a.
+        let rows = layout(&[("m", &["c", "f"]), ("f", &["a"]), ("c", &["a"]), ("a", &[])]);
+
+        assert_eq!(rows[0].lane, 0);
+        assert_eq!(
+            rows[0].edges,
+            [
+                GraphEdge::Outgoing {
+                    to_lane: 0,
+                    color: 0
+                },
+                GraphEdge::Outgoing {
+                    to_lane: 1,
+                    color: 1
+                },
+            ]
+        );
+
+        // The merged branch continues in its own lane, next to the first parent's.
+        assert_eq!((rows[1].lane, rows[1].color), (1, 1));
+        assert!(
+            rows[1]
+                .edges
+                .contains(&GraphEdge::Pass { lane: 0, color: 0 })
+        );
+
+        // Both lanes lead to `a`, and join at its row.
+        assert_eq!(rows[2].lane, 0);
+        assert!(
+            rows[2]
+                .edges
+                .contains(&GraphEdge::Pass { lane: 1, color: 1 })
+        );
+        assert_eq!(rows[3].lane, 0);
+        assert!(rows[3].edges.contains(&GraphEdge::Incoming {
+            from_lane: 1,
+            color: 1
+        }));
+        assert_eq!(rows[3].lane_count, 2);
+    }
+
+    #[test]
+    fn test_unrelated_branch_tips_get_their_own_lanes() {
+        let mut layout = GraphLayout::default();
+        let row = layout.push(&"x".into(), &["a".into()]);
+        assert_eq!(row.lane, 0);
+        let row = layout.push(&"y".into(), &["b".into()]);
+        assert_eq!(row.lane, 1);
+
+        // Lanes carry over from one page to the next.
+        let row = layout.push(&"a".into(), &[]);
+        assert_eq!(row.lane, 0);
+        assert_eq!(row.lane_count, 2);
+        let row = layout.push(&"b".into(), &[]);
+        assert_eq!(row.lane, 1);
+        assert!(layout.lanes.is_empty());
+    }
+}
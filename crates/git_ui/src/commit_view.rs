@@ -2,12 +2,12 @@ use anyhow::{Context as _, Result};
 use buffer_diff::{BufferDiff, BufferDiffSnapshot};
 use editor::display_map::{BlockPlacement, BlockProperties, BlockStyle};
 use editor::{Editor, EditorEvent, ExcerptRange, MultiBuffer, multibuffer_context_lines};
-use git::repository::{CommitDetails, CommitDiff, RepoPath};
-use git::{GitHostingProviderRegistry, GitRemote, parse_git_remote_url};
+use git::repository::{CommitDetails, CommitDiff, RebaseControl, RepoPath, SequencerOperation};
+use git::{GitHostingProviderRegistry, GitRemote, SHORT_SHA_LENGTH, parse_git_remote_url};
 use gpui::{
-    AnyElement, App, AppContext as _, Asset, AsyncApp, AsyncWindowContext, Context, Element,
-    Entity, EventEmitter, FocusHandle, Focusable, InteractiveElement, IntoElement, ParentElement,
-    PromptLevel, Render, Styled, Task, WeakEntity, Window, actions,
+    Action as _, AnyElement, App, AppContext as _, Asset, AsyncApp, AsyncWindowContext, Context,
+    Element, Entity, EventEmitter, FocusHandle, Focusable, InteractiveElement, IntoElement,
+    ParentElement, PromptLevel, Render, Styled, Task, WeakEntity, Window, actions,
 };
 use language::{
     Anchor, Buffer, Capability, DiskState, File, LanguageRegistry, LineEnding, OffsetRangeExt as _,
@@ -33,9 +33,32 @@ use workspace::{
     searchable::SearchableItemHandle,
 };
 
-use crate::git_panel::GitPanel;
-
-actions!(git, [ApplyCurrentStash, PopCurrentStash, DropCurrentStash,]);
+use crate::git_panel::{GitPanel, show_error_toast};
+
+actions!(
+    git,
+    [
+        ApplyCurrentStash,
+        PopCurrentStash,
+        DropCurrentStash,
+        /// Applies the changes of the commit in the active commit view on top of the current branch.
+        CherryPickCurrentCommit,
+        /// Commits the inverse of the changes of the commit in the active commit view.
+        RevertCurrentCommit,
+        /// Continues the cherry-pick that stopped on conflicts.
+        CherryPickContinue,
+        /// Skips the commit the cherry-pick stopped at.
+        CherryPickSkip,
+        /// Aborts the cherry-pick that stopped on conflicts.
+        CherryPickAbort,
+        /// Continues the revert that stopped on conflicts.
+        RevertContinue,
+        /// Skips the commit the revert stopped at.
+        RevertSkip,
+        /// Aborts the revert that stopped on conflicts.
+        RevertAbort,
+    ]
+);
 
 pub fn init(cx: &mut App) {
     cx.observe_new(|workspace: &mut Workspace, _window, _cx| {
@@ -48,6 +71,60 @@ pub fn init(cx: &mut App) {
         workspace.register_action(|workspace, _: &PopCurrentStash, window, cx| {
             CommitView::pop_stash(workspace, window, cx);
         });
+        workspace.register_action(|workspace, _: &CherryPickCurrentCommit, window, cx| {
+            CommitView::commit_operation(workspace, CommitOperation::CherryPick, window, cx);
+        });
+        workspace.register_action(|workspace, _: &RevertCurrentCommit, window, cx| {
+            CommitView::commit_operation(workspace, CommitOperation::Revert, window, cx);
+        });
+        workspace.register_action(|workspace, _: &CherryPickContinue, _, cx| {
+            CommitView::sequencer_control(
+                workspace,
+                CommitOperation::CherryPick,
+                RebaseControl::Continue,
+                cx,
+            );
+        });
+        workspace.register_action(|workspace, _: &CherryPickSkip, _, cx| {
+            CommitView::sequencer_control(
+                workspace,
+                CommitOperation::CherryPick,
+                RebaseControl::Skip,
+                cx,
+            );
+        });
+        workspace.register_action(|workspace, _: &CherryPickAbort, _, cx| {
+            CommitView::sequencer_control(
+                workspace,
+                CommitOperation::CherryPick,
+                RebaseControl::Abort,
+                cx,
+            );
+        });
+        workspace.register_action(|workspace, _: &RevertContinue, _, cx| {
+            CommitView::sequencer_control(
+                workspace,
+                CommitOperation::Revert,
+                RebaseControl::Continue,
+                cx,
+            );
+        });
+        workspace.register_action(|workspace, _: &RevertSkip, _, cx| {
+            CommitView::sequencer_control(
+                workspace,
+                CommitOperation::Revert,
+                RebaseControl::Skip,
+                cx,
+            );
+        });
+        workspace.register_action(|workspace, _: &RevertAbort, _, cx| {
+            CommitView::sequencer_control(
+                workspace,
+                CommitOperation::Revert,
+                RebaseControl::Abort,
+                cx,
+            );
+        });
     })
     .detach();
 }
@@ -68,6 +145,90 @@ struct GitBlob {
     display_name: Arc<str>,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum CommitOperation {
+    CherryPick,
+    Revert,
+}
+
+impl CommitOperation {
+    fn label(self) -> &'static str {
+        match self {
+            CommitOperation::CherryPick => "Cherry-Pick",
+            CommitOperation::Revert => "Revert",
+        }
+    }
+
+    fn command(self) -> &'static str {
+        match self {
+            CommitOperation::CherryPick => "cherry-pick",
+            CommitOperation::Revert => "revert",
+        }
+    }
+
+    fn sequencer_operation(self) -> SequencerOperation {
+        match self {
+            CommitOperation::CherryPick => SequencerOperation::CherryPick,
+            CommitOperation::Revert => SequencerOperation::Revert,
+        }
+    }
+}
+
+/// Cherry-picks or reverts a commit on the current branch, once the user confirms it.
+pub(crate) fn run_commit_operation(
+    operation: CommitOperation,
+    sha: SharedString,
+    repository: Entity<Repository>,
+    workspace: WeakEntity<Workspace>,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let short_sha = sha.get(..SHORT_SHA_LENGTH).unwrap_or(&sha).to_string();
+    let prompt = match operation {
+        CommitOperation::CherryPick => {
+            format!("Cherry-pick commit {short_sha} onto the current branch?")
+        }
+        CommitOperation::Revert => format!("Commit a revert of {short_sha}?"),
+    };
+    let answer = window.prompt(
+        PromptLevel::Info,
+        &prompt,
+        None,
+        &[operation.label(), "Cancel"],
+        cx,
+    );
+
+    window
+        .spawn(cx, async move |cx| {
+            if answer.await != Ok(0) {
+                return anyhow::Ok(());
+            }
+
+            let result = repository
+                .update(cx, |repository, _| match operation {
+                    CommitOperation::CherryPick => repository.cherry_pick(sha.to_string()),
+                    CommitOperation::Revert => repository.revert(sha.to_string()),
+                })?
+                .await?;
+
+            // Conflicts also end up here, and stay in the working tree to be resolved.
+            if let Err(e) = result
+                && let Some(workspace) = workspace.upgrade()
+            {
+                cx.update(|_, cx| {
+                    show_error_toast(
+                        workspace,
+                        format!("{} {short_sha}", operation.command()),
+                        e,
+                        cx,
+                    )
+                })?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+}
+
 const COMMIT_MESSAGE_SORT_PREFIX: u64 = 0;
 const FILE_NAMESPACE_SORT_PREFIX: u64 = 1;
 
@@ -476,14 +637,54 @@ impl CommitView {
                                     .children(commit_diff_stat),
                             ),
                     )
-                    .children(github_url.map(|url| {
-                        Button::new("view_on_github", "View on GitHub")
-                            .icon(IconName::Github)
-                            .icon_color(Color::Muted)
-                            .icon_size(IconSize::Small)
-                            .icon_position(IconPosition::Start)
-                            .on_click(move |_, _, cx| cx.open_url(&url))
-                    })),
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .when(self.stash.is_none(), |this| {
+                                this.child(
+                                    Button::new("cherry_pick", "Cherry-Pick")
+                                        .icon(IconName::Copy)
+                                        .icon_color(Color::Muted)
+                                        .icon_size(IconSize::Small)
+                                        .icon_position(IconPosition::Start)
+                                        .tooltip(Tooltip::for_action_title(
+                                            "Apply This Commit on the Current Branch",
+                                            &CherryPickCurrentCommit,
+                                        ))
+                                        .on_click(|_, window, cx| {
+                                            window.dispatch_action(
+                                                CherryPickCurrentCommit.boxed_clone(),
+                                                cx,
+                                            )
+                                        }),
+                                )
+                                .child(
+                                    Button::new("revert", "Revert")
+                                        .icon(IconName::Undo)
+                                        .icon_color(Color::Muted)
+                                        .icon_size(IconSize::Small)
+                                        .icon_position(IconPosition::Start)
+                                        .tooltip(Tooltip::for_action_title(
+                                            "Commit the Inverse of This Commit",
+                                            &RevertCurrentCommit,
+                                        ))
+                                        .on_click(|_, window, cx| {
+                                            window.dispatch_action(
+                                                RevertCurrentCommit.boxed_clone(),
+                                                cx,
+                                            )
+                                        }),
+                                )
+                            })
+                            .children(github_url.map(|url| {
+                                Button::new("view_on_github", "View on GitHub")
+                                    .icon(IconName::Github)
+                                    .icon_color(Color::Muted)
+                                    .icon_size(IconSize::Small)
+                                    .icon_position(IconPosition::Start)
+                                    .on_click(move |_, _, cx| cx.open_url(&url))
+                            })),
+                    ),
             )
     }
 
@@ -568,6 +769,68 @@ impl CommitView {
         );
     }
 
+    fn commit_operation(
+        workspace: &mut Workspace,
+        operation: CommitOperation,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let Some(commit_view) = workspace.active_item_as::<CommitView>(cx) else {
+            return;
+        };
+        let commit_view = commit_view.read(cx);
+        if commit_view.stash.is_some() {
+            return;
+        }
+        run_commit_operation(
+            operation,
+            commit_view.commit.sha.clone(),
+            commit_view.repository.clone(),
+            workspace.weak_handle(),
+            window,
+            cx,
+        );
+    }
+
+    /// Continues, skips or aborts the cherry-pick or revert of the active repository.
+    fn sequencer_control(
+        workspace: &mut Workspace,
+        operation: CommitOperation,
+        control: RebaseControl,
+        cx: &mut Context<Workspace>,
+    ) {
+        let Some(repository) = workspace.project().read(cx).active_repository(cx) else {
+            return;
+        };
+        let task = repository.update(cx, |repository, _| {
+            repository.sequencer_control(operation.sequencer_operation(), control)
+        });
+        let workspace = cx.entity();
+        cx.spawn(async move |_, cx| {
+            if let Err(error) = task
+                .await
+                .map_err(anyhow::Error::from)
+                .and_then(|result| result)
+            {
+                let flag = match control {
+                    RebaseControl::Continue => "--continue",
+                    RebaseControl::Skip => "--skip",
+                    RebaseControl::Abort => "--abort",
+                };
+                cx.update(|cx| {
+                    show_error_toast(
+                        workspace,
+                        format!("{} {flag}", operation.command()),
+                        error,
+                        cx,
+                    )
+                })
+                .ok();
+            }
+        })
+        .detach();
+    }
+
     fn stash_action<AsyncFn>(
         workspace: &mut Workspace,
         str_action: &str,
@@ -3,8 +3,9 @@ use futures::Future;
 use git::repository::{FileHistory, FileHistoryEntry, RepoPath};
 use git::{GitHostingProviderRegistry, GitRemote, parse_git_remote_url};
 use gpui::{
-    AnyElement, AnyEntity, App, Asset, Context, Entity, EventEmitter, FocusHandle, Focusable,
-    IntoElement, Render, Task, UniformListScrollHandle, WeakEntity, Window, actions, uniform_list,
+    AnyElement, AnyEntity, App, Asset, Context, Corner, DismissEvent, Entity, EventEmitter,
+    FocusHandle, Focusable, IntoElement, MouseDownEvent, Pixels, Point, Render, Subscription, Task,
+    UniformListScrollHandle, WeakEntity, Window, actions, anchored, deferred, uniform_list,
 };
 use project::{
     Project, ProjectPath,
@@ -13,14 +14,14 @@ use project::{
 use std::any::{Any, TypeId};
 
 use time::OffsetDateTime;
-use ui::{Avatar, Chip, Divider, ListItem, WithScrollbar, prelude::*};
+use ui::{Avatar, Chip, ContextMenu, Divider, ListItem, WithScrollbar, prelude::*};
 use util::ResultExt;
 use workspace::{
     Item, Workspace,
     item::{ItemEvent, SaveOptions},
 };
 
-use crate::commit_view::CommitView;
+use crate::commit_view::{CommitOperation, CommitView, run_commit_operation};
 
 actions!(git, [ViewCommitFromHistory, LoadMoreHistory]);
 
@@ -45,6 +46,7 @@ pub struct FileHistoryView {
     focus_handle: FocusHandle,
     loading_more: bool,
     has_more: bool,
+    context_menu: Option<(Entity<ContextMenu>, Point<Pixels>, Subscription)>,
 }
 
 impl FileHistoryView {
@@ -142,6 +144,7 @@ impl FileHistoryView {
             focus_handle,
             loading_more: false,
             has_more,
+            context_menu: None,
         }
     }
 
@@ -191,6 +194,86 @@ impl FileHistoryView {
         task.detach();
     }
 
+    fn deploy_entry_context_menu(
+        &mut self,
+        position: Point<Pixels>,
+        ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(entry) = self.history.entries.get(ix) else {
+            return;
+        };
+        let Some(repo) = self.repository.upgrade() else {
+            return;
+        };
+        let sha = entry.sha.clone();
+        let workspace = self.workspace.clone();
+        let file_path = self.history.path.clone();
+
+        let operation_handler = |operation| {
+            let sha = sha.clone();
+            let repo = repo.clone();
+            let workspace = workspace.clone();
+            move |window: &mut Window, cx: &mut App| {
+                run_commit_operation(
+                    operation,
+                    sha.clone(),
+                    repo.clone(),
+                    workspace.clone(),
+                    window,
+                    cx,
+                )
+            }
+        };
+        let cherry_pick = operation_handler(CommitOperation::CherryPick);
+        let revert = operation_handler(CommitOperation::Revert);
+
+        let context_menu = ContextMenu::build(window, cx, move |context_menu, _, _| {
+            context_menu
+                .entry("View Commit", None, move |window, cx| {
+                    CommitView::open(
+                        sha.to_string(),
+                        repo.downgrade(),
+                        workspace.clone(),
+                        None,
+                        Some(file_path.clone()),
+                        window,
+                        cx,
+                    );
+                })
+                .separator()
+                .entry("Cherry-Pick", None, cherry_pick)
+                .entry("Revert", None, revert)
+        });
+        self.selected_entry = Some(ix);
+        self.set_context_menu(context_menu, position, window, cx);
+    }
+
+    fn set_context_menu(
+        &mut self,
+        context_menu: Entity<ContextMenu>,
+        position: Point<Pixels>,
+        window: &Window,
+        cx: &mut Context<Self>,
+    ) {
+        let subscription = cx.subscribe_in(
+            &context_menu,
+            window,
+            |this, _, _: &DismissEvent, window, cx| {
+                if this.context_menu.as_ref().is_some_and(|context_menu| {
+                    context_menu.0.focus_handle(cx).contains_focused(window, cx)
+                }) {
+                    cx.focus_self(window);
+                }
+                this.context_menu.take();
+                cx.notify();
+            },
+        );
+        self.context_menu = Some((context_menu, position, subscription));
+        cx.notify();
+    }
+
     fn render_commit_avatar(
         &self,
         sha: &SharedString,
@@ -297,6 +380,12 @@ impl FileHistoryView {
                             ),
                     ),
             )
+            .on_secondary_mouse_down(cx.listener(
+                move |this, event: &MouseDownEvent, window, cx| {
+                    this.deploy_entry_context_menu(event.position, ix, window, cx);
+                    cx.stop_propagation();
+                },
+            ))
             .on_click(cx.listener(move |this, _, window, cx| {
                 this.selected_entry = Some(ix);
                 cx.notify();
@@ -452,6 +541,15 @@ impl Render for FileHistoryView {
                     })
                     .vertical_scrollbar_for(&self.scroll_handle, window, cx),
             )
+            .children(self.context_menu.as_ref().map(|(menu, position, _)| {
+                deferred(
+                    anchored()
+                        .position(*position)
+                        .anchor(Corner::TopLeft)
+                        .child(menu.clone()),
+                )
+                .with_priority(1)
+            }))
     }
 }
 
@@ -1,4 +1,5 @@
 use crate::askpass_modal::AskPassModal;
+use crate::commit_graph::CommitGraphView;
 use crate::commit_modal::CommitModal;
 use crate::commit_tooltip::CommitTooltip;
 use crate::commit_view::CommitView;
@@ -28,10 +29,11 @@ use git::repository::{
 };
 use git::stash::GitStash;
 use git::status::StageStatus;
+use git::submodule::{Submodule, SubmoduleOperation, SubmoduleStatus};
 use git::{Amend, Signoff, ToggleStaged, repository::RepoPath, status::FileStatus};
 use git::{
-    ExpandCommitEditor, RestoreTrackedFiles, StageAll, StashAll, StashApply, StashPop,
-    TrashUntrackedFiles, UnstageAll,
+    ExpandCommitEditor, InitSubmodules, RestoreTrackedFiles, StageAll, StashAll, StashApply,
+    StashPop, SyncSubmodules, TrashUntrackedFiles, UnstageAll, UpdateSubmodules,
 };
 use gpui::{
     Action, AsyncApp, AsyncWindowContext, Bounds, ClickEvent, Corner, DismissEvent, Entity,
@@ -127,6 +129,8 @@ struct GitMenuState {
     has_new_changes: bool,
     sort_by_path: bool,
     has_stash_items: bool,
+    /// Whether the repository has submodules, and the user can run git commands on them.
+    can_run_submodule_operations: bool,
     tree_view: bool,
 }
 
@@ -157,6 +161,12 @@ fn git_panel_context_menu(
             )
             .action_disabled_when(!state.has_stash_items, "Stash Pop", StashPop.boxed_clone())
             .action("View Stash", zed_actions::git::ViewStash.boxed_clone())
+            .when(state.can_run_submodule_operations, |this| {
+                this.separator()
+                    .action("Initialize Submodules", InitSubmodules.boxed_clone())
+                    .action("Update Submodules", UpdateSubmodules.boxed_clone())
+                    .action("Sync Submodule URLs", SyncSubmodules.boxed_clone())
+            })
             .separator()
             .action("Open Diff", project_diff::Diff.boxed_clone())
             .separator()
@@ -194,6 +204,18 @@ fn git_panel_context_menu(
     })
 }
 
+/// Summarizes a submodule entry, like "1a2b3c4 → 5d6e7f8 · dirty".
+fn submodule_summary(submodule: &Submodule) -> Option<String> {
+    let pointer_change = submodule.pointer_change().map(|(old_sha, new_sha)| {
+        format!("{} → {}", old_sha.display_short(), new_sha.display_short())
+    });
+    let parts = pointer_change
+        .into_iter()
+        .chain(submodule.state_description())
+        .collect::<Vec<_>>();
+    (!parts.is_empty()).then(|| parts.join(" · "))
+}
+
 const GIT_PANEL_KEY: &str = "GitPanel";
 
 const UPDATE_DEBOUNCE: Duration = Duration::from_millis(50);
@@ -748,7 +770,8 @@ impl GitPanel {
                         _,
                         RepositoryEvent::StatusesChanged
                         | RepositoryEvent::BranchChanged
-                        | RepositoryEvent::MergeHeadsChanged,
+                        | RepositoryEvent::MergeHeadsChanged
+                        | RepositoryEvent::SubmodulesChanged,
                         true,
                     )
                     | GitStoreEvent::RepositoryAdded
@@ -1056,6 +1079,11 @@ impl GitPanel {
             let workspace = self.workspace.upgrade()?;
             let git_repo = self.active_repository.as_ref()?;
 
+            if let Some(submodule) = git_repo.read(cx).submodule(&entry.repo_path).cloned() {
+                self.open_submodule(&submodule, window, cx);
+                return Some(());
+            }
+
             if let Some(project_diff) = workspace.read(cx).active_item_as::<ProjectDiff>(cx)
                 && let Some(project_path) = project_diff.read(cx).active_path(cx)
                 && Some(&entry.repo_path)
@@ -1080,6 +1108,61 @@ impl GitPanel {
         });
     }
 
+    /// Opens the commits between the recorded and checked out commits of a submodule, or switches
+    /// to the submodule's repository when its pointer hasn't moved.
+    fn open_submodule(
+        &mut self,
+        submodule: &Submodule,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(repo) = self.active_repository.as_ref() else {
+            return;
+        };
+        let submodule_repo = self
+            .project
+            .read(cx)
+            .git_store()
+            .read(cx)
+            .submodule_repository(repo, &submodule.path, cx);
+        let Some(submodule_repo) = submodule_repo else {
+            self.show_error_toast(
+                "open submodule",
+                anyhow::anyhow!(
+                    "Submodule {} is not initialized",
+                    submodule.path.as_unix_str()
+                ),
+                cx,
+            );
+            return;
+        };
+
+        match submodule.pointer_change() {
+            Some((old_sha, new_sha)) => {
+                self.workspace
+                    .update(cx, |workspace, cx| {
+                        CommitGraphView::deploy_range(
+                            submodule_repo,
+                            format!("{old_sha}...{new_sha}").into(),
+                            workspace,
+                            window,
+                            cx,
+                        );
+                    })
+                    .ok();
+            }
+            None => {
+                submodule_repo.update(cx, |repo, cx| repo.set_as_active_repository(cx));
+            }
+        }
+    }
+
+    fn has_submodules(&self, cx: &App) -> bool {
+        self.active_repository
+            .as_ref()
+            .is_some_and(|repo| !repo.read(cx).submodules.is_empty())
+    }
+
     fn file_history(&mut self, _: &git::FileHistory, window: &mut Window, cx: &mut Context<Self>) {
         maybe!({
             let entry = self.entries.get(self.selected_entry?)?.status_entry()?;
@@ -2903,6 +2986,96 @@ impl GitPanel {
         .detach_and_log_err(cx);
     }
 
+    pub(crate) fn push_tag(
+        &mut self,
+        tag: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.can_push_and_pull(cx) {
+            return;
+        }
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        // Without a branch there is no push remote to default to, so let the user pick one.
+        let select_remote = repo.read(cx).branch.is_none();
+        let remote = self.get_remote(select_remote, true, window, cx);
+
+        cx.spawn_in(window, async move |this, cx| {
+            let remote = match remote.await {
+                Ok(Some(remote)) => remote,
+                Ok(None) => {
+                    return Ok(());
+                }
+                Err(e) => {
+                    log::error!("Failed to get current remote: {}", e);
+                    this.update(cx, |this, cx| this.show_error_toast("push", e, cx))
+                        .ok();
+                    return Ok(());
+                }
+            };
+
+            let askpass_delegate = this.update_in(cx, |this, window, cx| {
+                this.askpass_delegate(format!("git push {}", remote.name), window, cx)
+            })?;
+
+            let push = repo.update(cx, |repo, cx| {
+                repo.push_tag(tag.clone(), remote.name.clone(), askpass_delegate, cx)
+            })?;
+
+            let remote_output = push.await?;
+
+            let action = RemoteAction::Push(tag, remote);
+            this.update(cx, |this, cx| match remote_output {
+                Ok(remote_message) => this.show_remote_output(action, remote_message, cx),
+                Err(e) => {
+                    log::error!("Error while pushing tag {:?}", e);
+                    this.show_error_toast(action.name(), e, cx)
+                }
+            })?;
+
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    pub(crate) fn submodule_operation(
+        &mut self,
+        operation: SubmoduleOperation,
+        paths: Vec<RepoPath>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.can_push_and_pull(cx) {
+            return;
+        }
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        let askpass_delegate =
+            self.askpass_delegate(format!("git submodule {}", operation.name()), window, cx);
+        let update = repo.update(cx, |repo, cx| {
+            repo.submodule_operation(operation, paths, askpass_delegate, cx)
+        });
+
+        cx.spawn(async move |this, cx| {
+            let remote_output = update.await?;
+
+            let action = RemoteAction::Submodule(operation);
+            this.update(cx, |this, cx| match remote_output {
+                Ok(remote_message) => this.show_remote_output(action, remote_message, cx),
+                Err(e) => {
+                    log::error!("Error while running git submodule {:?}", e);
+                    this.show_error_toast(action.name(), e, cx)
+                }
+            })?;
+
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
     fn askpass_delegate(
         &self,
         operation: impl Into<SharedString>,
@@ -3655,6 +3828,8 @@ impl GitPanel {
                         has_new_changes,
                         sort_by_path: GitPanelSettings::get_global(cx).sort_by_path,
                         has_stash_items,
+                        can_run_submodule_operations: self.has_submodules(cx)
+                            && self.can_push_and_pull(cx),
                         tree_view: GitPanelSettings::get_global(cx).tree_view,
                     },
                     window,
@@ -4581,6 +4756,12 @@ impl GitPanel {
         } else {
             "Restore File"
         };
+        let submodule = self
+            .active_repository
+            .as_ref()
+            .and_then(|repo| repo.read(cx).submodule(&entry.repo_path).cloned());
+        let can_run_submodule_operations = self.can_push_and_pull(cx);
+        let git_panel = cx.weak_entity();
         let context_menu = ContextMenu::build(window, cx, |context_menu, _, _| {
             let is_created = entry.status.is_created();
             context_menu
@@ -4593,10 +4774,48 @@ impl GitPanel {
                     git::AddToGitignore.boxed_clone(),
                 )
                 .separator()
-                .action("Open Diff", Confirm.boxed_clone())
-                .action("Open File", SecondaryConfirm.boxed_clone())
+                .map(|this| match &submodule {
+                    Some(submodule) if submodule.pointer_change().is_some() => {
+                        this.action("Open Commit Range", Confirm.boxed_clone())
+                    }
+                    Some(_) => this.action("Open Submodule Repository", Confirm.boxed_clone()),
+                    None => this
+                        .action("Open Diff", Confirm.boxed_clone())
+                        .action("Open File", SecondaryConfirm.boxed_clone()),
+                })
                 .separator()
                 .action_disabled_when(is_created, "View File History", Box::new(git::FileHistory))
+                .when_some(
+                    submodule.filter(|_| can_run_submodule_operations),
+                    |menu, submodule| {
+                        let run = |operation: SubmoduleOperation| {
+                            let git_panel = git_panel.clone();
+                            let path = submodule.path.clone();
+                            move |window: &mut Window, cx: &mut App| {
+                                git_panel
+                                    .update(cx, |git_panel, cx| {
+                                        git_panel.submodule_operation(
+                                            operation,
+                                            vec![path.clone()],
+                                            window,
+                                            cx,
+                                        )
+                                    })
+                                    .ok();
+                            }
+                        };
+                        menu.separator()
+                            .when(submodule.status == SubmoduleStatus::Uninitialized, |menu| {
+                                menu.entry(
+                                    "Initialize Submodule",
+                                    None,
+                                    run(SubmoduleOperation::Init),
+                                )
+                            })
+                            .entry("Update Submodule", None, run(SubmoduleOperation::Update))
+                            .entry("Sync Submodule URL", None, run(SubmoduleOperation::Sync))
+                    },
+                )
         });
         self.selected_entry = Some(ix);
         self.set_context_menu(context_menu, position, window, cx);
@@ -4617,6 +4836,7 @@ impl GitPanel {
                 has_new_changes: self.new_count > 0,
                 sort_by_path: GitPanelSettings::get_global(cx).sort_by_path,
                 has_stash_items: self.stash_entries.entries.len() > 0,
+                can_run_submodule_operations: self.has_submodules(cx) && self.can_push_and_pull(cx),
                 tree_view: GitPanelSettings::get_global(cx).tree_view,
             },
             window,
@@ -4757,6 +4977,8 @@ impl GitPanel {
             })
             .child(git_status_icon(status));
 
+        let submodule_summary = repo.submodule(&entry.repo_path).and_then(submodule_summary);
+
         name_row = if tree_view {
             name_row.child(
                 self.entry_label(display_name, label_color)
@@ -4777,6 +4999,14 @@ impl GitPanel {
                 )
             }))
         };
+        name_row = name_row.when_some(submodule_summary, |this, summary| {
+            this.child(
+                Label::new(summary)
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+                    .single_line(),
+            )
+        });
 
         h_flex()
             .id(id)
@@ -14,7 +14,7 @@ use editor::{
     scroll::Autoscroll,
 };
 use git::{
-    Commit, StageAll, StageAndNext, ToggleStaged, UnstageAll, UnstageAndNext,
+    Commit, SHORT_SHA_LENGTH, StageAll, StageAndNext, ToggleStaged, UnstageAll, UnstageAndNext,
     repository::{Branch, RepoPath, Upstream, UpstreamTracking, UpstreamTrackingStatus},
     status::FileStatus,
 };
@@ -171,6 +171,32 @@ impl ProjectDiff {
         }
     }
 
+    /// Opens the changes between `sha` and the working tree.
+    pub fn deploy_since_commit(
+        workspace: &mut Workspace,
+        sha: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        telemetry::event!("Git Commit Diff Opened");
+        let diff_base = DiffBase::Commit { sha };
+        let existing = workspace
+            .items_of_type::<Self>(cx)
+            .find(|item| item.read(cx).diff_base(cx) == &diff_base);
+        if let Some(existing) = existing {
+            workspace.activate_item(&existing, true, true, window, cx);
+            return;
+        }
+
+        let project = workspace.project().clone();
+        let workspace_handle = cx.entity();
+        let branch_diff =
+            cx.new(|cx| branch_diff::BranchDiff::new(diff_base, project.clone(), window, cx));
+        let project_diff =
+            cx.new(|cx| Self::new_impl(branch_diff, project, workspace_handle, window, cx));
+        workspace.add_item_to_active_pane(Box::new(project_diff), None, true, window, cx);
+    }
+
     pub fn autoscroll(&self, cx: &mut Context<Self>) {
         self.editor.update(cx, |editor, cx| {
             editor.primary_editor().update(cx, |editor, cx| {
@@ -254,7 +280,7 @@ impl ProjectDiff {
                                 workspace: workspace.downgrade(),
                             });
                         }
-                        DiffBase::Merge { .. } => {
+                        DiffBase::Merge { .. } | DiffBase::Commit { .. } => {
                             editor.register_addon(BranchDiffAddon {
                                 branch_diff: branch_diff.clone(),
                             });
@@ -533,7 +559,7 @@ impl ProjectDiff {
                 multibuffer_context_lines(cx),
                 cx,
             );
-            if self.branch_diff.read(cx).diff_base().is_merge_base() {
+            if self.branch_diff.read(cx).diff_base().is_commit_base() {
                 multibuffer.add_diff(diff.clone(), cx);
             }
             (was_empty, is_newly_added)
@@ -720,6 +746,9 @@ impl Item for ProjectDiff {
         match self.branch_diff.read(cx).diff_base() {
             DiffBase::Head => "Uncommitted Changes".into(),
             DiffBase::Merge { base_ref } => format!("Changes since {}", base_ref).into(),
+            DiffBase::Commit { sha } => {
+                format!("Changes since {}", &sha[..sha.len().min(SHORT_SHA_LENGTH)]).into()
+            }
         }
     }
 
@@ -0,0 +1,802 @@
+use std::any::{Any, TypeId};
+
+use anyhow::Result;
+use editor::Editor;
+use futures::channel::oneshot;
+use git::{
+    SHORT_SHA_LENGTH,
+    repository::{CommitSummary, RebaseControl, RebaseOutcome, RebaseTodoAction, RebaseTodoEntry},
+};
+use gpui::{
+    Action, AnyElement, AnyEntity, App, Context, Entity, EventEmitter, FocusHandle, Focusable,
+    ScrollHandle, Subscription, Task, WeakEntity, Window, actions,
+};
+use project::{Project, ProjectPath, git_store::Repository};
+use ui::{ContextMenu, PopoverMenu, Tooltip, prelude::*};
+use workspace::{
+    Item, Workspace,
+    item::{ItemEvent, SaveOptions},
+    notifications::DetachAndPromptErr,
+};
+
+use crate::{git_panel::show_error_toast, picker_prompt, project_diff::Diff};
+
+actions!(
+    git,
+    [
+        /// Moves the selected commit of the rebase todo list up.
+        MoveRebaseEntryUp,
+        /// Moves the selected commit of the rebase todo list down.
+        MoveRebaseEntryDown,
+    ]
+);
+
+/// How many commits are offered as the base of an interactive rebase.
+const BASE_COMMIT_LIMIT: usize = 200;
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(|workspace, _: &git::InteractiveRebase, window, cx| {
+        RebaseView::deploy(workspace, window, cx);
+    });
+    workspace.register_action(|workspace, _: &git::RebaseContinue, window, cx| {
+        RebaseView::control_in_workspace(workspace, RebaseControl::Continue, window, cx);
+    });
+    workspace.register_action(|workspace, _: &git::RebaseSkip, window, cx| {
+        RebaseView::control_in_workspace(workspace, RebaseControl::Skip, window, cx);
+    });
+    workspace.register_action(|workspace, _: &git::RebaseAbort, window, cx| {
+        RebaseView::control_in_workspace(workspace, RebaseControl::Abort, window, cx);
+    });
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum RebaseState {
+    Editing,
+    Running,
+    Paused,
+}
+
+struct RebaseViewEntry {
+    entry: RebaseTodoEntry,
+    /// The editor for the new message of a reworded commit.
+    message_editor: Option<Entity<Editor>>,
+}
+
+/// The todo list of an interactive rebase, where commits are reordered and given an action
+/// before the rebase runs, and where a paused rebase is continued, skipped or aborted.
+pub struct RebaseView {
+    repository: Entity<Repository>,
+    base: CommitSummary,
+    entries: Vec<RebaseViewEntry>,
+    selected_ix: usize,
+    state: RebaseState,
+    error: Option<SharedString>,
+    focus_handle: FocusHandle,
+    scroll_handle: ScrollHandle,
+    pending_task: Option<Task<()>>,
+    _repository_subscription: Subscription,
+}
+
+impl RebaseView {
+    /// Asks for the commit to rebase onto, and opens the todo list of the rebase.
+    pub fn deploy(workspace: &mut Workspace, window: &mut Window, cx: &mut Context<Workspace>) {
+        let Some(repository) = workspace.project().read(cx).active_repository(cx) else {
+            return;
+        };
+        let recent_commits = repository.update(cx, |repository, _| {
+            repository.recent_commits(BASE_COMMIT_LIMIT)
+        });
+        let workspace_handle = workspace.weak_handle();
+        cx.spawn_in(window, async move |_, cx| {
+            // The newest commit has nothing on top of it to rebase.
+            let commits = recent_commits
+                .await??
+                .into_iter()
+                .skip(1)
+                .collect::<Vec<_>>();
+            let options = commits
+                .iter()
+                .map(|commit| format!("{} {}", short_sha(&commit.sha), commit.subject).into())
+                .collect();
+            let base_ix = cx
+                .update(|window, cx| {
+                    picker_prompt::prompt(
+                        "Rebase onto commit…",
+                        options,
+                        workspace_handle.clone(),
+                        window,
+                        cx,
+                    )
+                })?
+                .await;
+            let Some(base) = base_ix.and_then(|ix| commits.get(ix).cloned()) else {
+                return Ok(());
+            };
+
+            let todo = repository
+                .update(cx, |repository, _| {
+                    repository.rebase_todo(base.sha.to_string())
+                })?
+                .await??;
+            anyhow::ensure!(
+                !todo.is_empty(),
+                "There are no commits to rebase onto {}",
+                short_sha(&base.sha)
+            );
+
+            workspace_handle.update_in(cx, |workspace, window, cx| {
+                let view = cx.new(|cx| RebaseView::new(repository, base, todo, window, cx));
+                workspace.add_item_to_active_pane(Box::new(view), None, true, window, cx);
+            })?;
+            anyhow::Ok(())
+        })
+        .detach_and_prompt_err(
+            "Failed to start interactive rebase",
+            window,
+            cx,
+            |_, _, _| None,
+        );
+    }
+
+    /// Continues, skips or aborts the rebase of the active repository, through its todo list
+    /// when one is open.
+    fn control_in_workspace(
+        workspace: &mut Workspace,
+        control: RebaseControl,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        if let Some(view) = workspace.items_of_type::<RebaseView>(cx).next() {
+            view.update(cx, |view, cx| view.control(control, window, cx));
+            return;
+        }
+
+        let Some(repository) = workspace.project().read(cx).active_repository(cx) else {
+            return;
+        };
+        let task = repository.update(cx, |repository, _| repository.rebase_control(control));
+        let workspace = cx.entity();
+        cx.spawn(async move |_, cx| {
+            if let Err(error) = task
+                .await
+                .map_err(anyhow::Error::from)
+                .and_then(|result| result)
+            {
+                cx.update(|cx| show_error_toast(workspace, control_name(control), error, cx))
+                    .ok();
+            }
+        })
+        .detach();
+    }
+
+    fn new(
+        repository: Entity<Repository>,
+        base: CommitSummary,
+        todo: Vec<RebaseTodoEntry>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let _repository_subscription = cx.observe(&repository, |_, _, cx| cx.notify());
+        Self {
+            repository,
+            base,
+            entries: todo
+                .into_iter()
+                .map(|entry| RebaseViewEntry {
+                    entry,
+                    message_editor: None,
+                })
+                .collect(),
+            selected_ix: 0,
+            state: RebaseState::Editing,
+            error: None,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: ScrollHandle::new(),
+            pending_task: None,
+            _repository_subscription,
+        }
+    }
+
+    fn set_action(
+        &mut self,
+        ix: usize,
+        action: RebaseTodoAction,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.state != RebaseState::Editing {
+            return;
+        }
+        let Some(view_entry) = self.entries.get_mut(ix) else {
+            return;
+        };
+        view_entry.entry.action = action;
+        if action == RebaseTodoAction::Reword && view_entry.message_editor.is_none() {
+            let subject = view_entry.entry.subject.clone();
+            let editor = cx.new(|cx| {
+                let mut editor = Editor::auto_height(1, 8, window, cx);
+                editor.set_text(subject.clone(), window, cx);
+                editor
+            });
+            view_entry.message_editor = Some(editor.clone());
+
+            // Start from the full message of the commit, unless it was already edited.
+            let show = self.repository.update(cx, |repository, _| {
+                repository.show(view_entry.entry.sha.to_string())
+            });
+            cx.spawn_in(window, async move |_, cx| {
+                let Ok(Ok(details)) = show.await else {
+                    return;
+                };
+                editor
+                    .update_in(cx, |editor, window, cx| {
+                        if editor.text(cx) == subject.as_ref() {
+                            editor.set_text(details.message.trim(), window, cx);
+                        }
+                    })
+                    .ok();
+            })
+            .detach();
+        }
+        self.selected_ix = ix;
+        cx.notify();
+    }
+
+    fn move_entry(&mut self, ix: usize, up: bool, cx: &mut Context<Self>) {
+        if self.state != RebaseState::Editing {
+            return;
+        }
+        let target_ix = if up {
+            ix.checked_sub(1)
+        } else {
+            Some(ix + 1).filter(|ix| *ix < self.entries.len())
+        };
+        if let Some(target_ix) = target_ix {
+            self.entries.swap(ix, target_ix);
+            self.selected_ix = target_ix;
+            self.scroll_handle.scroll_to_item(target_ix);
+            cx.notify();
+        }
+    }
+
+    fn move_selected_up(&mut self, _: &MoveRebaseEntryUp, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_entry(self.selected_ix, true, cx);
+    }
+
+    fn move_selected_down(
+        &mut self,
+        _: &MoveRebaseEntryDown,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.move_entry(self.selected_ix, false, cx);
+    }
+
+    fn select_previous(
+        &mut self,
+        _: &menu::SelectPrevious,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.selected_ix = self.selected_ix.saturating_sub(1);
+        self.scroll_handle.scroll_to_item(self.selected_ix);
+        cx.notify();
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        self.selected_ix = (self.selected_ix + 1).min(self.entries.len().saturating_sub(1));
+        self.scroll_handle.scroll_to_item(self.selected_ix);
+        cx.notify();
+    }
+
+    /// The todo list as it was edited, with the new messages of reworded commits.
+    fn todo(&self, cx: &App) -> Vec<RebaseTodoEntry> {
+        self.entries
+            .iter()
+            .map(|view_entry| {
+                let mut entry = view_entry.entry.clone();
+                if entry.action == RebaseTodoAction::Reword
+                    && let Some(editor) = &view_entry.message_editor
+                {
+                    let message = editor.read(cx).text(cx);
+                    let message = message.trim();
+                    if !message.is_empty() {
+                        entry.message = Some(message.to_string().into());
+                    }
+                }
+                entry
+            })
+            .collect()
+    }
+
+    fn start(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.state != RebaseState::Editing || self.pending_task.is_some() {
+            return;
+        }
+        let todo = self.todo(cx);
+        let base = self.base.sha.to_string();
+        let task = self
+            .repository
+            .update(cx, |repository, _| repository.rebase(base, todo));
+        self.run(task, window, cx);
+    }
+
+    fn control(&mut self, control: RebaseControl, window: &mut Window, cx: &mut Context<Self>) {
+        if self.pending_task.is_some() {
+            return;
+        }
+        let task = self
+            .repository
+            .update(cx, |repository, _| repository.rebase_control(control));
+        self.run(task, window, cx);
+    }
+
+    fn run(
+        &mut self,
+        task: oneshot::Receiver<Result<RebaseOutcome>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let previous_state = self.state;
+        self.state = RebaseState::Running;
+        self.error = None;
+        self.pending_task = Some(cx.spawn_in(window, async move |this, cx| {
+            let outcome = task
+                .await
+                .map_err(anyhow::Error::from)
+                .and_then(|result| result);
+            this.update(cx, |this, cx| {
+                this.pending_task = None;
+                match outcome {
+                    Ok(RebaseOutcome::Paused) => this.state = RebaseState::Paused,
+                    Ok(RebaseOutcome::Finished) => cx.emit(ItemEvent::CloseItem),
+                    Err(error) => {
+                        this.state = previous_state;
+                        this.error = Some(format!("{error:#}").into());
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        }));
+        cx.notify();
+    }
+
+    fn render_entry(&self, ix: usize, cx: &mut Context<Self>) -> AnyElement {
+        let view_entry = &self.entries[ix];
+        let entry = &view_entry.entry;
+        let editable = self.state == RebaseState::Editing;
+        let dropped = entry.action == RebaseTodoAction::Drop;
+        let view = cx.weak_entity();
+
+        let action_menu = PopoverMenu::new(("rebase-action", ix))
+            .trigger(
+                Button::new(("rebase-action-trigger", ix), entry.action.as_str())
+                    .label_size(LabelSize::Small)
+                    .icon(IconName::ChevronDown)
+                    .icon_size(IconSize::XSmall)
+                    .icon_position(IconPosition::End)
+                    .disabled(!editable),
+            )
+            .menu(move |window, cx| {
+                let view = view.clone();
+                Some(ContextMenu::build(window, cx, move |mut menu, _, _| {
+                    for action in RebaseTodoAction::ALL {
+                        let view = view.clone();
+                        menu = menu.entry(action.as_str(), None, move |window, cx| {
+                            view.update(cx, |view, cx| view.set_action(ix, action, window, cx))
+                                .ok();
+                        });
+                    }
+                    menu
+                }))
+            });
+
+        v_flex()
+            .id(("rebase-entry", ix))
+            .w_full()
+            .px_2()
+            .py_1()
+            .gap_1()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .when(ix == self.selected_ix, |this| {
+                this.bg(cx.theme().colors().element_selected)
+            })
+            .on_click(cx.listener(move |this, _, _, cx| {
+                this.selected_ix = ix;
+                cx.notify();
+            }))
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(div().w(rems(5.)).flex_none().child(action_menu))
+                    .child(
+                        Label::new(short_sha(&entry.sha))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .buffer_font(cx),
+                    )
+                    .child(
+                        Label::new(entry.subject.clone())
+                            .size(LabelSize::Small)
+                            .color(if dropped {
+                                Color::Disabled
+                            } else {
+                                Color::Default
+                            })
+                            .when(dropped, |label| label.strikethrough())
+                            .truncate(),
+                    )
+                    .child(div().flex_1())
+                    .child(
+                        IconButton::new(("rebase-entry-up", ix), IconName::ArrowUp)
+                            .icon_size(IconSize::Small)
+                            .disabled(!editable || ix == 0)
+                            .tooltip(Tooltip::text("Move Up"))
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.move_entry(ix, true, cx);
+                            })),
+                    )
+                    .child(
+                        IconButton::new(("rebase-entry-down", ix), IconName::ArrowDown)
+                            .icon_size(IconSize::Small)
+                            .disabled(!editable || ix + 1 == self.entries.len())
+                            .tooltip(Tooltip::text("Move Down"))
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.move_entry(ix, false, cx);
+                            })),
+                    ),
+            )
+            .when_some(
+                view_entry
+                    .message_editor
+                    .clone()
+                    .filter(|_| entry.action == RebaseTodoAction::Reword),
+                |this, editor| {
+                    this.child(
+                        div()
+                            .ml(rems(5.5))
+                            .p_1()
+                            .border_1()
+                            .rounded_sm()
+                            .border_color(cx.theme().colors().border)
+                            .child(editor),
+                    )
+                },
+            )
+            .into_any_element()
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let title = format!(
+            "Rebase {} commits onto {} {}",
+            self.entries.len(),
+            short_sha(&self.base.sha),
+            self.base.subject
+        );
+        let controls =
+            match self.state {
+                RebaseState::Editing => h_flex().child(
+                    Button::new("start-rebase", "Start Rebase")
+                        .style(ButtonStyle::Filled)
+                        .on_click(cx.listener(|this, _, window, cx| this.start(window, cx))),
+                ),
+                RebaseState::Running => h_flex().child(
+                    Label::new("Rebasing…")
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                ),
+                RebaseState::Paused => h_flex()
+                    .gap_1()
+                    .child(
+                        Button::new("view-changes", "View Changes").on_click(|_, window, cx| {
+                            window.dispatch_action(Diff.boxed_clone(), cx);
+                        }),
+                    )
+                    .child(Button::new("abort-rebase", "Abort").on_click(cx.listener(
+                        |this, _, window, cx| this.control(RebaseControl::Abort, window, cx),
+                    )))
+                    .child(Button::new("skip-rebase", "Skip").on_click(cx.listener(
+                        |this, _, window, cx| this.control(RebaseControl::Skip, window, cx),
+                    )))
+                    .child(
+                        Button::new("continue-rebase", "Continue")
+                            .style(ButtonStyle::Filled)
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.control(RebaseControl::Continue, window, cx)
+                            })),
+                    ),
+            };
+
+        let conflict_count = self
+            .repository
+            .read(cx)
+            .snapshot()
+            .merge
+            .conflicted_paths
+            .iter()
+            .count();
+        let status = match (&self.error, self.state) {
+            (Some(error), _) => Some(Label::new(error.clone()).color(Color::Error)),
+            (None, RebaseState::Paused) if conflict_count > 0 => Some(
+                Label::new(format!(
+                    "The rebase stopped on conflicts in {conflict_count} files. \
+                     Resolve them, then continue."
+                ))
+                .color(Color::Warning),
+            ),
+            (None, RebaseState::Paused) => Some(
+                Label::new("The rebase stopped to edit a commit. Amend it, then continue.")
+                    .color(Color::Warning),
+            ),
+            _ => None,
+        };
+
+        v_flex()
+            .px_3()
+            .py_2()
+            .gap_1()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                h_flex()
+                    .justify_between()
+                    .gap_2()
+                    .child(Label::new(title).truncate())
+                    .child(controls),
+            )
+            .children(status.map(|status| status.size(LabelSize::Small)))
+    }
+}
+
+fn short_sha(sha: &str) -> &str {
+    &sha[..SHORT_SHA_LENGTH.min(sha.len())]
+}
+
+fn control_name(control: RebaseControl) -> &'static str {
+    match control {
+        RebaseControl::Continue => "rebase --continue",
+        RebaseControl::Skip => "rebase --skip",
+        RebaseControl::Abort => "rebase --abort",
+    }
+}
+
+impl EventEmitter<ItemEvent> for RebaseView {}
+
+impl Focusable for RebaseView {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for RebaseView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let entries = (0..self.entries.len())
+            .map(|ix| self.render_entry(ix, cx))
+            .collect::<Vec<_>>();
+
+        v_flex()
+            .key_context("RebaseView")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::move_selected_up))
+            .on_action(cx.listener(Self::move_selected_down))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::select_next))
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(self.render_header(cx))
+            .child(
+                v_flex()
+                    .id("rebase-todo")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .track_scroll(&self.scroll_handle)
+                    .children(entries),
+            )
+    }
+}
+
+impl Item for RebaseView {
+    type Event = ItemEvent;
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        "Interactive Rebase".into()
+    }
+
+    fn tab_tooltip_text(&self, _cx: &App) -> Option<SharedString> {
+        Some(
+            format!(
+                "Rebase onto {} {}",
+                short_sha(&self.base.sha),
+                self.base.subject
+            )
+            .into(),
+        )
+    }
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::GitBranch))
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("interactive rebase")
+    }
+
+    fn clone_on_split(
+        &self,
+        _workspace_id: Option<workspace::WorkspaceId>,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) -> Task<Option<Entity<Self>>> {
+        Task::ready(None)
+    }
+
+    fn navigate(&mut self, _: Box<dyn Any>, _window: &mut Window, _: &mut Context<Self>) -> bool {
+        false
+    }
+
+    fn can_save(&self, _: &App) -> bool {
+        false
+    }
+
+    fn save(
+        &mut self,
+        _options: SaveOptions,
+        _project: Entity<Project>,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        Task::ready(Ok(()))
+    }
+
+    fn save_as(
+        &mut self,
+        _project: Entity<Project>,
+        _path: ProjectPath,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        Task::ready(Ok(()))
+    }
+
+    fn is_dirty(&self, _: &App) -> bool {
+        false
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        _workspace: &mut Workspace,
+        window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) {
+        window.focus(&self.focus_handle);
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<AnyEntity> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.clone().into())
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use std::path::Path;
+    use theme::LoadThemes;
+    use util::path;
+
+    fn init_test(cx: &mut TestAppContext) {
+        zlog::init_test();
+
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(LoadThemes::JustBase, cx);
+            editor::init(cx);
+            crate::init(cx);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_rebase_view(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "a.txt": "A",
+            }),
+        )
+        .await;
+        let commits = ["Add c", "Add b", "Add a"]
+            .into_iter()
+            .enumerate()
+            .map(|(ix, subject)| CommitSummary {
+                sha: format!("{:040x}", 3 - ix).into(),
+                subject: subject.into(),
+                commit_timestamp: 3 - ix as i64,
+                author_name: "Fake Author".into(),
+                has_parent: ix < 2,
+            })
+            .collect::<Vec<_>>();
+        fs.with_git_state(Path::new(path!("/project/.git")), true, {
+            let commits = commits.clone();
+            move |state| state.set_commits(commits)
+        })
+        .unwrap();
+
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let workspace =
+            cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let cx = &mut VisualTestContext::from_window(*workspace, cx);
+        cx.run_until_parked();
+
+        let repository =
+            project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+        let base = commits[2].clone();
+        let todo = repository
+            .update(cx, |repository, _| {
+                repository.rebase_todo(base.sha.to_string())
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        let view = cx.new_window_entity(|window, cx| {
+            RebaseView::new(repository.clone(), base, todo, window, cx)
+        });
+
+        // Swap the commits, and stop at the first one to edit it.
+        view.update_in(cx, |view, window, cx| {
+            view.move_entry(0, false, cx);
+            view.set_action(0, RebaseTodoAction::Edit, window, cx);
+            assert_eq!(
+                view.todo(cx)
+                    .iter()
+                    .map(|entry| (entry.action, entry.subject.as_ref()))
+                    .collect::<Vec<_>>(),
+                [
+                    (RebaseTodoAction::Edit, "Add c"),
+                    (RebaseTodoAction::Pick, "Add b")
+                ]
+            );
+            view.start(window, cx);
+        });
+        cx.run_until_parked();
+        view.read_with(cx, |view, _| {
+            assert_eq!(view.state, RebaseState::Paused);
+            assert_eq!(view.error, None);
+        });
+
+        view.update_in(cx, |view, window, cx| {
+            view.control(RebaseControl::Continue, window, cx)
+        });
+        cx.run_until_parked();
+        let subjects = repository
+            .update(cx, |repository, _| repository.recent_commits(10))
+            .await
+            .unwrap()
+            .unwrap()
+            .into_iter()
+            .map(|commit| commit.subject.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(subjects, ["Add b", "Add c", "Add a"]);
+    }
+}
@@ -1,6 +1,7 @@
 use anyhow::Context as _;
 
 use git::repository::{Remote, RemoteCommandOutput};
+use git::submodule::SubmoduleOperation;
 use linkify::{LinkFinder, LinkKind};
 use ui::SharedString;
 use util::ResultExt as _;
@@ -10,6 +11,7 @@ pub enum RemoteAction {
     Fetch(Option<Remote>),
     Pull(Remote),
     Push(SharedString, Remote),
+    Submodule(SubmoduleOperation),
 }
 
 impl RemoteAction {
@@ -18,6 +20,9 @@ impl RemoteAction {
             RemoteAction::Fetch(_) => "fetch",
             RemoteAction::Pull(_) => "pull",
             RemoteAction::Push(_, _) => "push",
+            RemoteAction::Submodule(SubmoduleOperation::Init) => "submodule init",
+            RemoteAction::Submodule(SubmoduleOperation::Update) => "submodule update",
+            RemoteAction::Submodule(SubmoduleOperation::Sync) => "submodule sync",
         }
     }
 }
@@ -157,6 +162,24 @@ pub fn format_output(action: &RemoteAction, output: RemoteCommandOutput) -> Succ
                 style: style.unwrap_or(SuccessStyle::ToastWithLog { output }),
             }
         }
+        RemoteAction::Submodule(operation) => {
+            if output.stdout.is_empty() && output.stderr.is_empty() {
+                SuccessMessage {
+                    message: "Submodules: Already up to date".into(),
+                    style: SuccessStyle::Toast,
+                }
+            } else {
+                let message = match operation {
+                    SubmoduleOperation::Init => "Initialized submodules",
+                    SubmoduleOperation::Update => "Updated submodules",
+                    SubmoduleOperation::Sync => "Synchronized submodule URLs",
+                };
+                SuccessMessage {
+                    message: message.into(),
+                    style: SuccessStyle::ToastWithLog { output },
+                }
+            }
+        }
     }
 }
 
@@ -297,4 +320,29 @@ mod tests {
             panic!("Expected ToastWithLog variant");
         }
     }
+
+    #[test]
+    fn test_submodule_update() {
+        let action = RemoteAction::Submodule(SubmoduleOperation::Update);
+
+        let msg = format_output(
+            &action,
+            RemoteCommandOutput {
+                stdout: String::new(),
+                stderr: String::new(),
+            },
+        );
+        assert_eq!(msg.message, "Submodules: Already up to date");
+        assert!(matches!(msg.style, SuccessStyle::Toast));
+
+        let msg = format_output(
+            &action,
+            RemoteCommandOutput {
+                stdout: "Submodule path 'libs/sub': checked out 'e7833874c4f30587a95c467f211940ee1a9a57f1'\n".into(),
+                stderr: String::new(),
+            },
+        );
+        assert_eq!(msg.message, "Updated submodules");
+        assert!(matches!(msg.style, SuccessStyle::ToastWithLog { .. }));
+    }
 }
@@ -0,0 +1,561 @@
+use fuzzy::StringMatchCandidate;
+
+use git::repository::Tag;
+use gpui::{
+    Action, AnyElement, App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
+    InteractiveElement, IntoElement, ParentElement, Render, SharedString, Styled, Subscription,
+    Task, WeakEntity, Window, actions, rems,
+};
+use picker::{Picker, PickerDelegate};
+use project::git_store::Repository;
+use std::sync::Arc;
+use time::{OffsetDateTime, UtcOffset};
+use ui::{HighlightedLabel, KeyBinding, ListItem, ListItemSpacing, Tooltip, prelude::*};
+use util::ResultExt;
+use workspace::notifications::DetachAndPromptErr;
+use workspace::{ModalView, Workspace};
+
+use crate::commit_view::CommitView;
+use crate::git_panel::{GitPanel, show_error_toast};
+use crate::tag_picker;
+
+actions!(
+    tag_picker,
+    [
+        /// Deletes the selected git tag.
+        DeleteTag,
+        /// Pushes the selected git tag to a remote.
+        PushTag,
+    ]
+);
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(open);
+}
+
+pub fn open(
+    workspace: &mut Workspace,
+    _: &zed_actions::git::Tag,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let repository = workspace.project().read(cx).active_repository(cx);
+    let weak_workspace = workspace.weak_handle();
+    workspace.toggle_modal(window, cx, |window, cx| {
+        TagList::new(repository, weak_workspace, rems(34.), window, cx)
+    })
+}
+
+pub struct TagList {
+    width: Rems,
+    pub picker: Entity<Picker<TagListDelegate>>,
+    picker_focus_handle: FocusHandle,
+    _subscription: Subscription,
+}
+
+impl TagList {
+    fn new(
+        repository: Option<Entity<Repository>>,
+        workspace: WeakEntity<Workspace>,
+        width: Rems,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let tags_request = repository
+            .clone()
+            .map(|repository| repository.update(cx, |repository, _| repository.tags()));
+
+        cx.spawn_in(window, async move |this, cx| {
+            let tags = match tags_request {
+                Some(tags_request) => tags_request.await??,
+                None => Vec::new(),
+            };
+
+            this.update_in(cx, |this, window, cx| {
+                this.picker.update(cx, |picker, cx| {
+                    picker.delegate.all_tags = Some(tags);
+                    picker.refresh(window, cx);
+                })
+            })?;
+
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+
+        let delegate = TagListDelegate::new(repository, workspace, cx);
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+        let picker_focus_handle = picker.focus_handle(cx);
+        picker.update(cx, |picker, _| {
+            picker.delegate.focus_handle = picker_focus_handle.clone();
+        });
+
+        let _subscription = cx.subscribe(&picker, |_, _, _, cx| {
+            cx.emit(DismissEvent);
+        });
+
+        Self {
+            picker,
+            picker_focus_handle,
+            width,
+            _subscription,
+        }
+    }
+
+    fn handle_delete_tag(&mut self, _: &DeleteTag, window: &mut Window, cx: &mut Context<Self>) {
+        self.picker.update(cx, |picker, cx| {
+            picker
+                .delegate
+                .delete_tag_at(picker.delegate.selected_index(), window, cx);
+        });
+    }
+
+    fn handle_push_tag(&mut self, _: &PushTag, window: &mut Window, cx: &mut Context<Self>) {
+        self.picker.update(cx, |picker, cx| {
+            picker
+                .delegate
+                .push_tag_at(picker.delegate.selected_index(), window, cx);
+        });
+    }
+}
+
+impl ModalView for TagList {}
+impl EventEmitter<DismissEvent> for TagList {}
+impl Focusable for TagList {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.picker_focus_handle.clone()
+    }
+}
+
+impl Render for TagList {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("GitTagSelector")
+            .w(self.width)
+            .on_action(cx.listener(Self::handle_delete_tag))
+            .on_action(cx.listener(Self::handle_push_tag))
+            .child(self.picker.clone())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Entry {
+    Tag {
+        tag: Tag,
+        positions: Vec<usize>,
+        formatted_timestamp: String,
+    },
+    NewTag {
+        name: String,
+    },
+}
+
+impl Entry {
+    fn as_tag(&self) -> Option<&Tag> {
+        match self {
+            Entry::Tag { tag, .. } => Some(tag),
+            Entry::NewTag { .. } => None,
+        }
+    }
+}
+
+pub struct TagListDelegate {
+    matches: Vec<Entry>,
+    all_tags: Option<Vec<Tag>>,
+    repo: Option<Entity<Repository>>,
+    workspace: WeakEntity<Workspace>,
+    selected_index: usize,
+    focus_handle: FocusHandle,
+    timezone: UtcOffset,
+    /// Pushing asks for credentials, which can't be relayed to collaborators.
+    can_push: bool,
+}
+
+impl TagListDelegate {
+    fn new(
+        repo: Option<Entity<Repository>>,
+        workspace: WeakEntity<Workspace>,
+        cx: &mut Context<TagList>,
+    ) -> Self {
+        let can_push = workspace
+            .upgrade()
+            .is_some_and(|workspace| !workspace.read(cx).project().read(cx).is_via_collab());
+        Self {
+            matches: vec![],
+            all_tags: None,
+            repo,
+            workspace,
+            selected_index: 0,
+            focus_handle: cx.focus_handle(),
+            timezone: UtcOffset::current_local_offset().unwrap_or(UtcOffset::UTC),
+            can_push,
+        }
+    }
+
+    fn format_timestamp(timestamp: i64, timezone: UtcOffset) -> String {
+        let timestamp =
+            OffsetDateTime::from_unix_timestamp(timestamp).unwrap_or(OffsetDateTime::now_utc());
+        time_format::format_localized_timestamp(
+            timestamp,
+            OffsetDateTime::now_utc(),
+            timezone,
+            time_format::TimestampFormat::Relative,
+        )
+    }
+
+    fn create_tag(&self, name: String, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        cx.spawn(async move |_, cx| {
+            repo.update(cx, |repo, _| repo.create_tag(name, None, None))?
+                .await??;
+            anyhow::Ok(())
+        })
+        .detach_and_prompt_err("Failed to create tag", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+        cx.emit(DismissEvent);
+    }
+
+    fn delete_tag_at(&self, ix: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(tag) = self.matches.get(ix).and_then(Entry::as_tag).cloned() else {
+            return;
+        };
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        let workspace = self.workspace.clone();
+
+        cx.spawn_in(window, async move |picker, cx| {
+            let result = repo
+                .update(cx, |repo, _| repo.delete_tag(tag.name.to_string()))?
+                .await?;
+
+            if let Err(e) = result {
+                log::error!("Failed to delete tag: {}", e);
+                if let Some(workspace) = workspace.upgrade() {
+                    cx.update(|_window, cx| {
+                        show_error_toast(workspace, format!("tag -d {}", tag.name), e, cx)
+                    })?;
+                }
+                return Ok(());
+            }
+
+            picker.update_in(cx, |picker, _, cx| {
+                let delegate = &mut picker.delegate;
+                delegate
+                    .matches
+                    .retain(|entry| entry.as_tag().is_none_or(|t| t.name != tag.name));
+                if let Some(all_tags) = &mut delegate.all_tags {
+                    all_tags.retain(|t| t.name != tag.name);
+                }
+                if delegate.matches.is_empty() {
+                    delegate.selected_index = 0;
+                } else if delegate.selected_index >= delegate.matches.len() {
+                    delegate.selected_index = delegate.matches.len() - 1;
+                }
+                cx.notify();
+            })?;
+
+            anyhow::Ok(())
+        })
+        .detach();
+    }
+
+    fn push_tag_at(&self, ix: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        if !self.can_push {
+            return;
+        }
+        let Some(tag) = self.matches.get(ix).and_then(Entry::as_tag) else {
+            return;
+        };
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let Some(panel) = workspace.read(cx).panel::<GitPanel>(cx) else {
+            return;
+        };
+        let tag_name = tag.name.clone();
+        // Pushing may ask for credentials in a modal of its own, which replaces this one.
+        cx.emit(DismissEvent);
+        panel.update(cx, |panel, cx| panel.push_tag(tag_name, window, cx));
+    }
+
+    fn show_tag_at(&self, ix: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(tag) = self.matches.get(ix).and_then(Entry::as_tag) else {
+            return;
+        };
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        CommitView::open(
+            tag.sha.to_string(),
+            repo.downgrade(),
+            self.workspace.clone(),
+            None,
+            None,
+            window,
+            cx,
+        );
+        cx.emit(DismissEvent);
+    }
+}
+
+impl PickerDelegate for TagListDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Select or create a tag…".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let Some(all_tags) = self.all_tags.clone() else {
+            return Task::ready(());
+        };
+
+        let timezone = self.timezone;
+
+        cx.spawn_in(window, async move |picker, cx| {
+            let mut matches: Vec<Entry> = if query.is_empty() {
+                all_tags
+                    .into_iter()
+                    .map(|tag| {
+                        let formatted_timestamp =
+                            Self::format_timestamp(tag.creation_timestamp, timezone);
+                        Entry::Tag {
+                            tag,
+                            positions: Vec::new(),
+                            formatted_timestamp,
+                        }
+                    })
+                    .collect()
+            } else {
+                let candidates = all_tags
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, tag)| StringMatchCandidate::new(ix, &tag.name))
+                    .collect::<Vec<StringMatchCandidate>>();
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    true,
+                    true,
+                    10000,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+                .into_iter()
+                .map(|candidate| {
+                    let tag = all_tags[candidate.candidate_id].clone();
+                    let formatted_timestamp =
+                        Self::format_timestamp(tag.creation_timestamp, timezone);
+                    Entry::Tag {
+                        tag,
+                        positions: candidate.positions,
+                        formatted_timestamp,
+                    }
+                })
+                .collect()
+            };
+
+            let name = query.trim().replace(' ', "-");
+            if !name.is_empty()
+                && !matches
+                    .iter()
+                    .any(|entry| entry.as_tag().is_some_and(|tag| tag.name == name))
+            {
+                matches.push(Entry::NewTag { name });
+            }
+
+            picker
+                .update(cx, |picker, _| {
+                    let delegate = &mut picker.delegate;
+                    delegate.matches = matches;
+                    if delegate.matches.is_empty() {
+                        delegate.selected_index = 0;
+                    } else {
+                        delegate.selected_index =
+                            core::cmp::min(delegate.selected_index, delegate.matches.len() - 1);
+                    }
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        match self.matches.get(self.selected_index()) {
+            Some(Entry::Tag { .. }) => self.show_tag_at(self.selected_index(), window, cx),
+            Some(Entry::NewTag { name }) => self.create_tag(name.clone(), window, cx),
+            None => {}
+        }
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let entry = self.matches.get(ix)?;
+
+        let (icon, content, tooltip) = match entry {
+            Entry::NewTag { name } => (
+                IconName::Plus,
+                v_flex()
+                    .w_full()
+                    .child(
+                        Label::new(format!("Create Tag: \"{name}\"…"))
+                            .single_line()
+                            .truncate(),
+                    )
+                    .child(
+                        Label::new("Points to the current commit")
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    ),
+                None,
+            ),
+            Entry::Tag {
+                tag,
+                positions,
+                formatted_timestamp,
+            } => {
+                let short_sha = tag.sha.get(..7).unwrap_or(&tag.sha).to_string();
+                let details = h_flex()
+                    .gap_1p5()
+                    .w_full()
+                    .child(
+                        Label::new(short_sha)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .buffer_font(cx),
+                    )
+                    .child(
+                        Label::new(tag.subject.clone())
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .truncate(),
+                    )
+                    .child(
+                        Label::new(formatted_timestamp.clone())
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    );
+                (
+                    IconName::Hash,
+                    v_flex()
+                        .w_full()
+                        .child(
+                            HighlightedLabel::new(tag.name.to_string(), positions.clone())
+                                .single_line()
+                                .truncate(),
+                        )
+                        .child(details),
+                    Some(tag.name.clone()),
+                )
+            }
+        };
+
+        Some(
+            ListItem::new(format!("tag-{ix}"))
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(
+                    h_flex()
+                        .w_full()
+                        .gap_3()
+                        .child(Icon::new(icon).color(Color::Muted))
+                        .child(content),
+                )
+                .when_some(tooltip, |this, tooltip: SharedString| {
+                    this.tooltip(Tooltip::text(tooltip))
+                }),
+        )
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some("No tags found".into())
+    }
+
+    fn render_footer(&self, _: &mut Window, cx: &mut Context<Picker<Self>>) -> Option<AnyElement> {
+        let focus_handle = self.focus_handle.clone();
+        let has_tag = self
+            .matches
+            .get(self.selected_index)
+            .is_some_and(|entry| entry.as_tag().is_some());
+
+        Some(
+            h_flex()
+                .w_full()
+                .p_1p5()
+                .gap_0p5()
+                .justify_end()
+                .border_t_1()
+                .border_color(cx.theme().colors().border_variant)
+                .child(
+                    Button::new("delete-tag", "Delete")
+                        .disabled(!has_tag)
+                        .key_binding(
+                            KeyBinding::for_action_in(&tag_picker::DeleteTag, &focus_handle, cx)
+                                .map(|kb| kb.size(rems_from_px(12.))),
+                        )
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(tag_picker::DeleteTag.boxed_clone(), cx)
+                        }),
+                )
+                .when(self.can_push, |this| {
+                    this.child(
+                        Button::new("push-tag", "Push")
+                            .disabled(!has_tag)
+                            .key_binding(
+                                KeyBinding::for_action_in(&tag_picker::PushTag, &focus_handle, cx)
+                                    .map(|kb| kb.size(rems_from_px(12.))),
+                            )
+                            .on_click(|_, window, cx| {
+                                window.dispatch_action(tag_picker::PushTag.boxed_clone(), cx)
+                            }),
+                    )
+                })
+                .child(
+                    Button::new("confirm-tag", if has_tag { "View" } else { "Create" })
+                        .key_binding(
+                            KeyBinding::for_action_in(&menu::Confirm, &focus_handle, cx)
+                                .map(|kb| kb.size(rems_from_px(12.))),
+                        )
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(menu::Confirm.boxed_clone(), cx)
+                        }),
+                )
+                .into_any(),
+        )
+    }
+}
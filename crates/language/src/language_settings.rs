@@ -13,7 +13,7 @@ use itertools::{Either, Itertools};
 pub use settings::{
     CompletionSettingsContent, EditPredictionProvider, EditPredictionsMode, FormatOnSave,
     Formatter, FormatterList, InlayHintKind, LanguageSettingsContent, LspInsertMode,
-    RewrapBehavior, ShowWhitespaceSetting, SoftWrap, WordsCompletionMode,
+    RewrapBehavior, ShowWhitespaceSetting, SoftWrap, SyntaxRangesProvider, WordsCompletionMode,
 };
 use settings::{RegisterSetting, Settings, SettingsLocation, SettingsStore};
 use shellexpand;
@@ -162,6 +162,15 @@ pub struct LanguageSettings {
     pub word_diff_enabled: bool,
     /// Whether to use tree-sitter bracket queries to detect and colorize the brackets in the editor.
     pub colorize_brackets: bool,
+    /// Whether to highlight the semantic tokens of language servers over the tree-sitter highlights.
+    pub semantic_tokens: bool,
+    /// The theme syntax styles used for semantic tokens, keyed by token type,
+    /// or by token type and modifier.
+    pub semantic_token_styles: HashMap<String, String>,
+    /// Which ranges the editor folds.
+    pub folding_ranges: SyntaxRangesProvider,
+    /// Which ranges `editor::SelectLargerSyntaxNode` expands the selections through.
+    pub selection_ranges: SyntaxRangesProvider,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -596,6 +605,10 @@ impl settings::Settings for AllLanguageSettings {
                 show_completions_on_input: settings.show_completions_on_input.unwrap(),
                 show_completion_documentation: settings.show_completion_documentation.unwrap(),
                 colorize_brackets: settings.colorize_brackets.unwrap(),
+                semantic_tokens: settings.semantic_tokens.unwrap(),
+                semantic_token_styles: settings.semantic_token_styles.unwrap(),
+                folding_ranges: settings.folding_ranges.unwrap(),
+                selection_ranges: settings.selection_ranges.unwrap(),
                 completions: CompletionSettings {
                     words: completions.words.unwrap(),
                     words_min_length: completions.words_min_length.unwrap() as usize,
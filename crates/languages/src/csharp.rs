@@ -1,16 +1,34 @@
-use anyhow::Result;
+use anyhow::{Context as _, Result};
 use async_trait::async_trait;
 use collections::HashMap;
-use gpui::{App, SharedString, Task};
+use dotnet::{
+    LaunchSettings, ProjectModel, RealProjectFiles, find_project_file,
+    testing::{find_test_methods, test_name_filter},
+};
+use futures::StreamExt;
+use gpui::{App, AsyncApp, SharedString, Task};
+use http_client::github::latest_github_release;
 use language::{
-    ContextLocation, ContextProvider, LanguageToolchainStore, ManifestName,
-    ManifestProvider, ManifestQuery,
+    ContextLocation, ContextProvider, LanguageName, LanguageToolchainStore, LspAdapter,
+    LspAdapterDelegate, LspInstaller, ManifestName, ManifestProvider, ManifestQuery, ToPoint as _,
+    Toolchain,
 };
-use std::{hash::{Hash, Hasher}, path::{Path, PathBuf}, sync::Arc};
-use std::collections::hash_map::DefaultHasher;
-use task::{TaskTemplate, TaskTemplates, TaskVariables};
-use util::rel_path::RelPath;
+use lsp::{LanguageServerBinary, LanguageServerName};
+use project::dotnet_configurations::DotNetConfigurations;
+use smol::fs;
+use std::{
+    borrow::Cow,
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering::SeqCst},
+    },
+};
+use task::{TaskTemplate, TaskTemplates, TaskVariables, VariableName};
+use util::ResultExt;
 use util::paths::PathStyle;
+use util::rel_path::RelPath;
+use util::{fs::remove_matching, maybe};
 
 /// Manifest provider for .csproj files
 /// Detects .NET project files and returns their directory as the project root
@@ -104,6 +122,99 @@ impl ManifestProvider for SolutionManifestProvider {
     }
 }
 
+/// Manifest provider for the root of a .NET codebase: the directory of its
+/// solution, or of the project if there is no solution. C# files of all the
+/// projects of a solution thereby share one language server.
+pub struct DotNetManifestProvider;
+
+impl DotNetManifestProvider {
+    pub const NAME: &str = "dotnet";
+}
+
+impl ManifestProvider for DotNetManifestProvider {
+    fn name(&self) -> ManifestName {
+        SharedString::new_static(Self::NAME).into()
+    }
+
+    fn search(&self, query: ManifestQuery) -> Option<Arc<RelPath>> {
+        let ManifestQuery {
+            path,
+            depth,
+            delegate,
+        } = query;
+
+        SolutionManifestProvider
+            .search(ManifestQuery {
+                path: path.clone(),
+                depth,
+                delegate: delegate.clone(),
+            })
+            .or_else(|| {
+                CsprojManifestProvider.search(ManifestQuery {
+                    path,
+                    depth,
+                    delegate,
+                })
+            })
+    }
+}
+
+/// Absolute path of the project file (.csproj) closest to the current file
+pub const DOTNET_PROJECT_TASK_VARIABLE: VariableName =
+    VariableName::Custom(Cow::Borrowed("DOTNET_PROJECT"));
+
+/// Directory of the project file closest to the current file
+pub const DOTNET_PROJECT_DIR_TASK_VARIABLE: VariableName =
+    VariableName::Custom(Cow::Borrowed("DOTNET_PROJECT_DIR"));
+
+/// Name of the project, i.e. the project file name without extension
+pub const DOTNET_PROJECT_NAME_TASK_VARIABLE: VariableName =
+    VariableName::Custom(Cow::Borrowed("DOTNET_PROJECT_NAME"));
+
+/// Build configuration the project was evaluated with (e.g. Debug)
+pub const DOTNET_CONFIGURATION_TASK_VARIABLE: VariableName =
+    VariableName::Custom(Cow::Borrowed("DOTNET_CONFIGURATION"));
+
+/// Build platform the project was evaluated with (e.g. AnyCPU or x64)
+pub const DOTNET_PLATFORM_TASK_VARIABLE: VariableName =
+    VariableName::Custom(Cow::Borrowed("DOTNET_PLATFORM"));
+
+/// First target framework of the project (e.g. net8.0)
+pub const DOTNET_TARGET_FRAMEWORK_TASK_VARIABLE: VariableName =
+    VariableName::Custom(Cow::Borrowed("DOTNET_TARGET_FRAMEWORK"));
+
+/// All target frameworks of the project, separated by semicolons
+pub const DOTNET_TARGET_FRAMEWORKS_TASK_VARIABLE: VariableName =
+    VariableName::Custom(Cow::Borrowed("DOTNET_TARGET_FRAMEWORKS"));
+
+/// Evaluated `AssemblyName` of the project
+pub const DOTNET_ASSEMBLY_NAME_TASK_VARIABLE: VariableName =
+    VariableName::Custom(Cow::Borrowed("DOTNET_ASSEMBLY_NAME"));
+
+/// Evaluated `OutputType` of the project (Exe, WinExe, Library)
+pub const DOTNET_OUTPUT_TYPE_TASK_VARIABLE: VariableName =
+    VariableName::Custom(Cow::Borrowed("DOTNET_OUTPUT_TYPE"));
+
+/// Output directory for the first target framework
+pub const DOTNET_OUTPUT_DIR_TASK_VARIABLE: VariableName =
+    VariableName::Custom(Cow::Borrowed("DOTNET_OUTPUT_DIR"));
+
+/// Path of the main output assembly for the first target framework
+pub const DOTNET_OUTPUT_ASSEMBLY_TASK_VARIABLE: VariableName =
+    VariableName::Custom(Cow::Borrowed("DOTNET_OUTPUT_ASSEMBLY"));
+
+/// Fully qualified name of the test method at the cursor, as VSTest names it
+pub const DOTNET_TEST_NAME_TASK_VARIABLE: VariableName =
+    VariableName::Custom(Cow::Borrowed("DOTNET_TEST_NAME"));
+
+/// `dotnet test --filter` expression running only the test at the cursor
+pub const DOTNET_TEST_FILTER_TASK_VARIABLE: VariableName =
+    VariableName::Custom(Cow::Borrowed("DOTNET_TEST_FILTER"));
+
+/// The test method captured by `runnables.scm`
+const DOTNET_TEST_METHOD_CAPTURE: VariableName =
+    VariableName::Custom(Cow::Borrowed("_dotnet_test_method"));
+
 /// Context provider for C# projects
 /// Provides task variables and task templates for .NET builds
 pub(crate) struct CSharpContextProvider;
@@ -113,342 +224,652 @@ impl ContextProvider for CSharpContextProvider {
     fn build_context(
         &self,
         variables: &TaskVariables,
-        _location: ContextLocation<'_>,
+        location: ContextLocation<'_>,
         _project_env: Option<HashMap<String, String>>,
         _language_toolchain_store: Arc<dyn LanguageToolchainStore>,
-        _cx: &mut App,
+        cx: &mut App,
     ) -> Task<Result<TaskVariables>> {
-        // For now, just return the provided variables without modification
-        // A full implementation would parse .csproj files to extract actual values
-        Task::ready(Ok(variables.clone()))
+        // The file variable is the path on the machine the project lives on,
+        // which is also where this runs for remote projects
+        let file_path = variables.get(&VariableName::File).map(PathBuf::from);
+        // A test runnable captures the method name only, so its class and
+        // namespace are read from the declarations around it
+        let test_method = variables.get(&DOTNET_TEST_METHOD_CAPTURE).map(|method| {
+            let snapshot = location.file_location.buffer.read(cx).snapshot();
+            let row = location.file_location.range.start.to_point(&snapshot).row;
+            (method.to_string(), row, snapshot.text())
+        });
+        let mut variables = variables.clone();
+        // Evaluate the project in the solution configuration chosen in the solution
+        // explorer, which the task store passes along with the captured variables
+        let configurations = DotNetConfigurations::from_task_variables(&variables);
+
+        cx.background_spawn(async move {
+            let Some(project_path) = file_path
+                .as_deref()
+                .and_then(|path| path.parent())
+                .and_then(find_project_file)
+            else {
+                return Ok(variables);
+            };
+            let model = ProjectModel::load(
+                &project_path,
+                &configurations.evaluation_options(&project_path),
+                &RealProjectFiles,
+            )?;
+            variables.extend(project_task_variables(&model));
+            if let Some((method, row, text)) = test_method
+                && let Some(test) = find_test_methods(&text)
+                    .into_iter()
+                    .find(|test| test.line == row && test.method == method)
+            {
+                let name = test.fully_qualified_name();
+                variables.insert(
+                    DOTNET_TEST_FILTER_TASK_VARIABLE,
+                    test_name_filter([name.as_str()]),
+                );
+                variables.insert(DOTNET_TEST_NAME_TASK_VARIABLE, name);
+            }
+            Ok(variables)
+        })
     }
 
     fn associated_tasks(
         &self,
-        _file: Option<Arc<dyn language::File>>,
-        _cx: &App,
+        file: Option<Arc<dyn language::File>>,
+        cx: &App,
     ) -> Task<Option<TaskTemplates>> {
-        // Provide default task templates for common dotnet operations
-        let templates = TaskTemplates(vec![
-            TaskTemplate {
-                label: "dotnet: build".into(),
-                command: "dotnet".into(),
-                args: vec!["build".into()],
-                ..Default::default()
-            },
-            TaskTemplate {
-                label: "dotnet: clean".into(),
-                command: "dotnet".into(),
-                args: vec!["clean".into()],
-                ..Default::default()
-            },
-            TaskTemplate {
-                label: "dotnet: test".into(),
-                command: "dotnet".into(),
-                args: vec!["test".into()],
-                ..Default::default()
-            },
-            TaskTemplate {
-                label: "dotnet: run".into(),
-                command: "dotnet".into(),
-                args: vec!["run".into()],
-                ..Default::default()
-            },
-        ]);
-        Task::ready(Some(templates))
+        let local_abs_path = file.and_then(|file| Some(file.as_local()?.abs_path(cx)));
+        cx.background_spawn(async move {
+            // Without a project file, there are no project variables to
+            // resolve, so fall back to what `dotnet` finds in the worktree root
+            let Some(project_path) = local_abs_path
+                .as_deref()
+                .and_then(|path| path.parent())
+                .and_then(find_project_file)
+            else {
+                return Some(TaskTemplates(fallback_task_templates()));
+            };
+            // One run task per launch profile, which the .NET debug locator also
+            // offers as a debug scenario
+            let launch_settings = project_path
+                .parent()
+                .and_then(|directory| LaunchSettings::load(directory).log_err())
+                .flatten();
+            let profiles = launch_settings
+                .iter()
+                .flat_map(|settings| settings.project_profiles())
+                .map(|profile| profile.name.clone())
+                .collect::<Vec<_>>();
+            Some(TaskTemplates(project_task_templates(&profiles)))
+        })
     }
 }
 
-/// Represents a NuGet package reference in a project
-#[derive(Debug, Clone)]
-pub struct NuGetPackage {
-    /// Package ID (e.g., "Newtonsoft.Json")
-    pub id: String,
-    /// Package version (e.g., "13.0.1")
-    pub version: Option<String>,
-}
-
-/// Represents a project in a .NET solution
-#[derive(Debug, Clone)]
-pub struct SolutionProject {
-    /// Project name
-    pub name: String,
-    /// Path to .csproj file relative to solution directory
-    pub path: PathBuf,
-    /// Project GUID (unique identifier)
-    pub guid: String,
-    /// Project type GUID (e.g., C#, VB.NET, C++, etc.)
-    pub type_guid: String,
-    /// NuGet packages referenced by this project
-    pub packages: Vec<NuGetPackage>,
+/// Tasks for the project of the current file, referring to its [task variables](project_task_variables).
+fn project_task_templates(launch_profiles: &[String]) -> Vec<TaskTemplate> {
+    let project = DOTNET_PROJECT_TASK_VARIABLE.template_value_with_whitespace();
+    let project_dir = Some(DOTNET_PROJECT_DIR_TASK_VARIABLE.template_value());
+    let configuration = DOTNET_CONFIGURATION_TASK_VARIABLE.template_value();
+    let platform = format!(
+        "-p:Platform={}",
+        DOTNET_PLATFORM_TASK_VARIABLE.template_value()
+    );
+
+    let mut templates = vec![
+        TaskTemplate {
+            label: "dotnet: build".into(),
+            command: "dotnet".into(),
+            args: vec![
+                "build".into(),
+                project.clone(),
+                "--configuration".into(),
+                configuration.clone(),
+                platform.clone(),
+            ],
+            cwd: project_dir.clone(),
+            ..Default::default()
+        },
+        TaskTemplate {
+            label: "dotnet: clean".into(),
+            command: "dotnet".into(),
+            args: vec![
+                "clean".into(),
+                project.clone(),
+                "--configuration".into(),
+                configuration.clone(),
+                platform.clone(),
+            ],
+            cwd: project_dir.clone(),
+            ..Default::default()
+        },
+        TaskTemplate {
+            label: "dotnet: test".into(),
+            command: "dotnet".into(),
+            args: vec![
+                "test".into(),
+                project.clone(),
+                "--configuration".into(),
+                configuration.clone(),
+                platform.clone(),
+            ],
+            cwd: project_dir.clone(),
+            ..Default::default()
+        },
+        TaskTemplate {
+            label: format!(
+                "dotnet: test {}",
+                DOTNET_TEST_NAME_TASK_VARIABLE.template_value()
+            ),
+            command: "dotnet".into(),
+            args: vec![
+                "test".into(),
+                project.clone(),
+                "--configuration".into(),
+                configuration.clone(),
+                platform.clone(),
+                "--filter".into(),
+                DOTNET_TEST_FILTER_TASK_VARIABLE.template_value_with_whitespace(),
+            ],
+            cwd: project_dir.clone(),
+            tags: vec!["csharp-test".into()],
+            ..Default::default()
+        },
+    ];
+    let run_args = vec![
+        "run".into(),
+        "--project".into(),
+        project,
+        "--configuration".into(),
+        configuration,
+        platform,
+        "--framework".into(),
+        DOTNET_TARGET_FRAMEWORK_TASK_VARIABLE.template_value(),
+    ];
+    templates.push(TaskTemplate {
+        label: "dotnet: run".into(),
+        command: "dotnet".into(),
+        args: run_args.clone(),
+        cwd: project_dir.clone(),
+        ..Default::default()
+    });
+    templates.push(TaskTemplate {
+        label: format!(
+            "dotnet: run {}",
+            DOTNET_PROJECT_NAME_TASK_VARIABLE.template_value()
+        ),
+        command: "dotnet".into(),
+        args: run_args.clone(),
+        cwd: project_dir.clone(),
+        tags: vec!["csharp-main".into()],
+        ..Default::default()
+    });
+    for profile in launch_profiles {
+        let mut args = run_args.clone();
+        args.extend(["--launch-profile".into(), profile.clone()]);
+        templates.push(TaskTemplate {
+            label: format!("dotnet: run ({profile})"),
+            command: "dotnet".into(),
+            args,
+            cwd: project_dir.clone(),
+            ..Default::default()
+        });
+    }
+    templates
 }
 
-/// Represents a parsed .NET solution (.sln) file
-#[derive(Debug, Clone)]
-pub struct SolutionFile {
-    /// Path to the solution file
-    pub path: PathBuf,
-    /// All projects in the solution
-    pub projects: Vec<SolutionProject>,
-    /// Solution configurations (e.g., Debug, Release)
-    pub configurations: Vec<String>,
-    /// Startup project GUID (if specified)
-    pub startup_project: Option<String>,
+/// Tasks that don't need any task variables, for files outside of a project,
+/// or ones that can't be evaluated from here, like those of remote projects.
+fn fallback_task_templates() -> Vec<TaskTemplate> {
+    ["build", "clean", "test", "run"]
+        .into_iter()
+        .map(|command| TaskTemplate {
+            label: format!("dotnet: {command}"),
+            command: "dotnet".into(),
+            args: vec![command.into()],
+            ..Default::default()
+        })
+        .collect()
 }
 
-impl SolutionFile {
-    /// Parse a .NET solution file (.sln or .slnx format)
-    pub fn parse(content: &str, base_dir: &Path) -> Result<Self> {
-        // Check if it's XML format (.slnx) or text format (.sln)
-        if content.trim_start().starts_with("<?xml") || content.trim_start().starts_with("<Solution") {
-            Self::parse_slnx(content, base_dir)
-        } else {
-            Self::parse_sln(content, base_dir)
+/// Task variables describing an evaluated project.
+pub fn project_task_variables(model: &ProjectModel) -> TaskVariables {
+    let mut variables = TaskVariables::from_iter([
+        (
+            DOTNET_PROJECT_TASK_VARIABLE,
+            model.path.to_string_lossy().into_owned(),
+        ),
+        (
+            DOTNET_PROJECT_DIR_TASK_VARIABLE,
+            model.directory().to_string_lossy().into_owned(),
+        ),
+        (DOTNET_PROJECT_NAME_TASK_VARIABLE, model.name.clone()),
+        (
+            DOTNET_CONFIGURATION_TASK_VARIABLE,
+            model.configuration.clone(),
+        ),
+        (DOTNET_PLATFORM_TASK_VARIABLE, model.platform.clone()),
+        (
+            DOTNET_TARGET_FRAMEWORKS_TASK_VARIABLE,
+            model.target_frameworks().collect::<Vec<_>>().join(";"),
+        ),
+        (
+            DOTNET_ASSEMBLY_NAME_TASK_VARIABLE,
+            model.assembly_name.clone(),
+        ),
+        (
+            DOTNET_OUTPUT_TYPE_TASK_VARIABLE,
+            model.output_type.as_str().to_string(),
+        ),
+    ]);
+    if let Some(target) = model.target(None) {
+        if let Some(target_framework) = &target.target_framework {
+            variables.insert(
+                DOTNET_TARGET_FRAMEWORK_TASK_VARIABLE,
+                target_framework.clone(),
+            );
         }
+        variables.insert(
+            DOTNET_OUTPUT_DIR_TASK_VARIABLE,
+            target.output_directory.to_string_lossy().into_owned(),
+        );
+        variables.insert(
+            DOTNET_OUTPUT_ASSEMBLY_TASK_VARIABLE,
+            target.output_assembly.to_string_lossy().into_owned(),
+        );
     }
+    variables
+}
 
-    /// Parse a traditional .sln file (text format)
-    fn parse_sln(content: &str, base_dir: &Path) -> Result<Self> {
-        let mut projects = Vec::new();
-        let mut configurations = Vec::new();
-        let mut startup_project = None;
+/// Language server for C#, backed by csharp-ls.
+///
+/// csharp-ls loads the solution in its root directory, which
+/// [`DotNetManifestProvider`] finds, so that a solution gets one server.
+pub struct CSharpLspAdapter;
 
-        for line in content.lines() {
-            let line = line.trim();
+impl CSharpLspAdapter {
+    const SERVER_NAME: LanguageServerName = LanguageServerName::new_static("csharp-ls");
+}
 
-            // Parse project entries: Project("{type-guid}") = "name", "path", "{guid}"
-            if line.starts_with("Project(\"") {
-                if let Some(project) = parse_project_line(line) {
-                    projects.push(project);
-                }
-            }
+const BINARY: &str = if cfg!(target_os = "windows") {
+    "csharp-ls.exe"
+} else {
+    "csharp-ls"
+};
 
-            // Parse solution configurations
-            if line.starts_with("Debug|") || line.starts_with("Release|") {
-                if let Some(config) = line.split('|').next() {
-                    if !configurations.contains(&config.to_string()) {
-                        configurations.push(config.to_string());
-                    }
-                }
-            }
+impl LspInstaller for CSharpLspAdapter {
+    type BinaryVersion = String;
 
-            // Parse startup project configuration
-            if line.contains("StartupProject") {
-                if let Some(guid) = extract_guid(line) {
-                    startup_project = Some(guid);
-                }
+    async fn fetch_latest_server_version(
+        &self,
+        delegate: &dyn LspAdapterDelegate,
+        pre_release: bool,
+        cx: &mut AsyncApp,
+    ) -> Result<String> {
+        static DID_SHOW_NOTIFICATION: AtomicBool = AtomicBool::new(false);
+
+        const NOTIFICATION_MESSAGE: &str =
+            "Could not install the C# language server `csharp-ls`, because `dotnet` was not found.";
+
+        if delegate.which("dotnet".as_ref()).await.is_none() {
+            if DID_SHOW_NOTIFICATION
+                .compare_exchange(false, true, SeqCst, SeqCst)
+                .is_ok()
+            {
+                cx.update(|cx| {
+                    delegate.show_notification(NOTIFICATION_MESSAGE, cx);
+                })?
             }
+            anyhow::bail!(NOTIFICATION_MESSAGE);
         }
 
-        // Default to first executable project if no startup project specified
-        if startup_project.is_none() && !projects.is_empty() {
-            startup_project = Some(projects[0].guid.clone());
-        }
+        let release = latest_github_release(
+            "razzmatazz/csharp-language-server",
+            true,
+            pre_release,
+            delegate.http_client(),
+        )
+        .await?;
+        Ok(release.tag_name.trim_start_matches('v').to_string())
+    }
 
-        Ok(SolutionFile {
-            path: base_dir.join("solution.sln"),
-            projects,
-            configurations: if configurations.is_empty() {
-                vec!["Debug".to_string(), "Release".to_string()]
-            } else {
-                configurations
-            },
-            startup_project,
+    async fn check_if_user_installed(
+        &self,
+        delegate: &dyn LspAdapterDelegate,
+        _: Option<Toolchain>,
+        _: &AsyncApp,
+    ) -> Option<LanguageServerBinary> {
+        let path = delegate.which(Self::SERVER_NAME.as_ref()).await?;
+        Some(LanguageServerBinary {
+            path,
+            arguments: Vec::new(),
+            env: None,
         })
     }
 
-    /// Parse a .slnx file (XML format)
-    fn parse_slnx(content: &str, base_dir: &Path) -> Result<Self> {
-        let mut projects = Vec::new();
-        let mut startup_project = None;
-
-        // Simple XML parsing for .slnx format
-        // .slnx format structure:
-        // <Solution>
-        //   <Projects>
-        //     <Project Path="..." />
-        //   </Projects>
-        // </Solution>
-        
-        // Extract projects - look for <Project> tags
-        let mut remaining = content;
-        while let Some(project_start) = remaining.find("<Project") {
-            let project_end = remaining[project_start..].find(">").ok_or_else(|| {
-                anyhow::anyhow!("Invalid XML: unclosed Project tag")
-            })?;
-            
-            let project_tag = &remaining[project_start..project_start + project_end + 1];
-            
-            // Extract Path attribute
-            if let Some(path_start) = project_tag.find("Path=\"") {
-                let path_start = path_start + 6; // Skip "Path=\""
-                if let Some(path_end) = project_tag[path_start..].find('"') {
-                    let path_str = &project_tag[path_start..path_start + path_end];
-                    let path = PathBuf::from(path_str);
-                    
-                    // Extract name from path (filename without extension)
-                    let name = path
-                        .file_stem()
-                        .and_then(|s| s.to_str())
-                        .unwrap_or("Unknown")
-                        .to_string();
-                    
-                    // Generate a simple GUID for .slnx projects (we'll use a hash of the path)
-                    let mut hasher = DefaultHasher::new();
-                    path_str.hash(&mut hasher);
-                    let hash = hasher.finish();
-                    // Format as GUID: {8 hex}-{4 hex}-{4 hex}-{4 hex}-{12 hex}
-                    let guid = format!("{:08x}-{:04x}-{:04x}-{:04x}-{:012x}", 
-                        (hash >> 32) as u32, 
-                        ((hash >> 16) & 0xFFFF) as u16, 
-                        (hash & 0xFFFF) as u16,
-                        ((hash >> 48) & 0xFFFF) as u16,
-                        hash & 0xFFFFFFFFFFFF);
-                    
-                    projects.push(SolutionProject {
-                        name,
-                        path,
-                        guid,
-                        type_guid: "FAE04EC0-301F-11D3-BA7A-00C04FC2CCAE".to_string(), // C# project type GUID
-                        packages: Vec::new(), // Packages will be loaded separately
-                    });
-                }
-            }
-            
-            remaining = &remaining[project_start + project_end + 1..];
+    async fn fetch_server_binary(
+        &self,
+        version: String,
+        container_dir: PathBuf,
+        delegate: &dyn LspAdapterDelegate,
+    ) -> Result<LanguageServerBinary> {
+        let tool_dir = container_dir.join(format!("csharp-ls_{version}"));
+        let binary = LanguageServerBinary {
+            path: tool_dir.join(BINARY),
+            arguments: Vec::new(),
+            env: None,
+        };
+        if fs::metadata(&binary.path)
+            .await
+            .is_ok_and(|metadata| metadata.is_file())
+        {
+            return Ok(binary);
         }
 
-        // Default to first executable project if no startup project specified
-        if startup_project.is_none() && !projects.is_empty() {
-            startup_project = Some(projects[0].guid.clone());
+        // csharp-ls is distributed as a .NET tool, installed into a directory
+        // of its own rather than globally
+        let dotnet = delegate
+            .which("dotnet".as_ref())
+            .await
+            .unwrap_or("dotnet".into());
+        let install_output = util::command::new_smol_command(dotnet)
+            .args(["tool", "install", "csharp-ls", "--version", &version])
+            .arg("--tool-path")
+            .arg(&tool_dir)
+            .output()
+            .await?;
+        if !install_output.status.success() {
+            log::error!(
+                "failed to install csharp-ls via `dotnet tool install`. stdout: {:?}, stderr: {:?}",
+                String::from_utf8_lossy(&install_output.stdout),
+                String::from_utf8_lossy(&install_output.stderr)
+            );
+            anyhow::bail!(
+                "failed to install csharp-ls with `dotnet tool install`. Check logs for more information."
+            );
         }
+        remove_matching(&container_dir, |entry| entry != tool_dir).await;
 
-        Ok(SolutionFile {
-            path: base_dir.join("solution.slnx"),
-            projects,
-            configurations: vec!["Debug".to_string(), "Release".to_string()],
-            startup_project,
-        })
+        Ok(binary)
     }
 
-    /// Get a project by name
-    pub fn get_project(&self, name: &str) -> Option<&SolutionProject> {
-        self.projects.iter().find(|p| p.name == name)
-    }
+    async fn cached_server_binary(
+        &self,
+        container_dir: PathBuf,
+        _: &dyn LspAdapterDelegate,
+    ) -> Option<LanguageServerBinary> {
+        maybe!(async {
+            let mut last_binary_path = None;
+            let mut entries = fs::read_dir(&container_dir).await?;
+            while let Some(entry) = entries.next().await {
+                let entry = entry?;
+                let binary_path = entry.path().join(BINARY);
+                if entry.file_type().await?.is_dir()
+                    && entry
+                        .file_name()
+                        .to_str()
+                        .is_some_and(|name| name.starts_with("csharp-ls_"))
+                    && fs::metadata(&binary_path).await.is_ok()
+                {
+                    last_binary_path = Some(binary_path);
+                }
+            }
 
-    /// Get a project by GUID
-    pub fn get_project_by_guid(&self, guid: &str) -> Option<&SolutionProject> {
-        self.projects.iter().find(|p| p.guid == guid)
+            let path = last_binary_path.context("no cached binary")?;
+            anyhow::Ok(LanguageServerBinary {
+                path,
+                arguments: Vec::new(),
+                env: None,
+            })
+        })
+        .await
+        .log_err()
     }
+}
 
-    /// Get the startup project
-    pub fn get_startup_project(&self) -> Option<&SolutionProject> {
-        self.startup_project
-            .as_ref()
-            .and_then(|guid| self.get_project_by_guid(guid))
+#[async_trait(?Send)]
+impl LspAdapter for CSharpLspAdapter {
+    fn name(&self) -> LanguageServerName {
+        Self::SERVER_NAME
     }
 
-    /// Get all executable projects (likely to have a Main entry point)
-    pub fn get_executable_projects(&self) -> Vec<&SolutionProject> {
-        // Heuristic: projects with names not ending in "Tests" or containing "Test"
-        self.projects
-            .iter()
-            .filter(|p| !p.name.contains("Test"))
-            .collect()
+    fn language_ids(&self) -> HashMap<LanguageName, String> {
+        HashMap::from_iter([(LanguageName::new_static("CSharp"), "csharp".into())])
     }
 }
 
-/// Parse a project line from a .sln file
-/// Format: Project("{type-guid}") = "name", "path", "{guid}"
-fn parse_project_line(line: &str) -> Option<SolutionProject> {
-    // Extract type GUID
-    let type_guid = extract_guid(line)?;
-
-    // Extract project name and path
-    if let Some(after_equals) = line.split('=').nth(1) {
-        let parts: Vec<&str> = after_equals
-            .split(',')
-            .map(|s| s.trim().trim_matches('"'))
-            .collect();
-
-        if parts.len() >= 3 {
-            let name = parts[0].to_string();
-            let path = PathBuf::from(parts[1]);
-            let guid = parts[2].to_string();
-
-            return Some(SolutionProject {
-                name,
-                path,
-                guid,
-                type_guid,
-                packages: Vec::new(), // Packages will be loaded separately
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language;
+    use dotnet::{SolutionConfiguration, SolutionFile};
+    use gpui::{AppContext as _, TestAppContext};
+    use project::{
+        debugger::locators::dotnet::build_evaluation_options, toolchain_store::EmptyToolchainStore,
+    };
+    use serde_json::json;
+    use task::TaskContext;
+    use util::{rel_path::rel_path, test::TempTree};
+
+    #[gpui::test]
+    async fn test_csharp_tasks_without_project_file(cx: &mut TestAppContext) {
+        let tree = TempTree::new(json!({
+            "Scripts": { "Tool.cs": "class Tool {}" },
+            "App": {
+                "App.csproj": r#"<Project Sdk="Microsoft.NET.Sdk"></Project>"#,
+                "Program.cs": "class Program {}",
+            },
+        }));
+        let task_labels = async |path: &str, cx: &mut TestAppContext| {
+            let file: Arc<dyn language::File> = Arc::new(crate::TestFile {
+                path: rel_path(path).into(),
+                root_name: String::new(),
+                local_root: Some(tree.path().to_path_buf()),
             });
-        }
-    }
+            cx.update(|cx| CSharpContextProvider.associated_tasks(Some(file), cx))
+                .await
+                .unwrap()
+                .0
+                .into_iter()
+                .map(|template| template.label)
+                .collect::<Vec<_>>()
+        };
 
-    None
-}
+        // Tasks of a file outside of any project don't depend on project variables.
+        assert_eq!(
+            task_labels("Scripts/Tool.cs", cx).await,
+            [
+                "dotnet: build",
+                "dotnet: clean",
+                "dotnet: test",
+                "dotnet: run"
+            ]
+        );
+        assert!(task_labels("App/Program.cs", cx).await.contains(&format!(
+            "dotnet: run {}",
+            DOTNET_PROJECT_NAME_TASK_VARIABLE.template_value()
+        )));
+
+        // Building the context of such a file keeps the variables it was given.
+        let buffer = cx.new(|cx| crate::Buffer::local("class Tool {}", cx));
+        let location = crate::Location {
+            buffer,
+            range: text::Anchor::MIN..text::Anchor::MIN,
+        };
+        let variables = TaskVariables::from_iter([(
+            VariableName::File,
+            tree.path()
+                .join("Scripts/Tool.cs")
+                .to_string_lossy()
+                .into_owned(),
+        )]);
+        let context = cx
+            .update(|cx| {
+                CSharpContextProvider.build_context(
+                    &variables,
+                    ContextLocation {
+                        fs: None,
+                        worktree_root: Some(tree.path().to_path_buf()),
+                        file_location: &location,
+                    },
+                    None,
+                    Arc::new(EmptyToolchainStore),
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+        assert_eq!(context, variables);
+    }
 
-/// Extract a GUID from a string in the format {GUID}
-fn extract_guid(line: &str) -> Option<String> {
-    if let Some(start) = line.find('{') {
-        if let Some(end) = line[start..].find('}') {
-            let guid = &line[start + 1..start + end];
-            return Some(guid.to_string());
-        }
+    #[gpui::test]
+    async fn test_csharp_tasks_use_solution_configuration(cx: &mut TestAppContext) {
+        let tree = TempTree::new(json!({
+            "App.sln": "\
+Microsoft Visual Studio Solution File, Format Version 12.00
+Project(\"{FAE04EC0-301F-11D3-BA7A-00C04FC2CCAE}\") = \"App\", \"App\\App.csproj\", \"{B0000000-0000-0000-0000-000000000001}\"
+EndProject
+Global
+\tGlobalSection(SolutionConfigurationPlatforms) = preSolution
+\t\tDebug|Any CPU = Debug|Any CPU
+\t\tRelease|Any CPU = Release|Any CPU
+\tEndGlobalSection
+\tGlobalSection(ProjectConfigurationPlatforms) = postSolution
+\t\t{B0000000-0000-0000-0000-000000000001}.Debug|Any CPU.ActiveCfg = Debug|Any CPU
+\t\t{B0000000-0000-0000-0000-000000000001}.Release|Any CPU.ActiveCfg = Release|x64
+\tEndGlobalSection
+EndGlobal
+",
+            "App": {
+                "App.csproj": r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <OutputType>Exe</OutputType>
+    <TargetFramework>net8.0</TargetFramework>
+  </PropertyGroup>
+</Project>"#,
+                "Program.cs": "class Program {}",
+            },
+        }));
+
+        // Switch the solution to its release configuration, as the solution explorer does.
+        let solution = SolutionFile::load(&tree.path().join("App.sln")).unwrap();
+        let configurations = DotNetConfigurations::default();
+        configurations.set_solution_configuration(
+            &solution,
+            &SolutionConfiguration::new("Release", "Any CPU"),
+        );
+
+        // The task store passes the configurations along with the captured variables.
+        let mut variables = TaskVariables::from_iter([(
+            VariableName::File,
+            tree.path()
+                .join("App/Program.cs")
+                .to_string_lossy()
+                .into_owned(),
+        )]);
+        variables.extend(configurations.task_variables());
+        let buffer = cx.new(|cx| crate::Buffer::local("class Program {}", cx));
+        let location = crate::Location {
+            buffer,
+            range: text::Anchor::MIN..text::Anchor::MIN,
+        };
+        let context = cx
+            .update(|cx| {
+                CSharpContextProvider.build_context(
+                    &variables,
+                    ContextLocation {
+                        fs: None,
+                        worktree_root: Some(tree.path().to_path_buf()),
+                        file_location: &location,
+                    },
+                    None,
+                    Arc::new(EmptyToolchainStore),
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            context.get(&DOTNET_CONFIGURATION_TASK_VARIABLE),
+            Some("Release")
+        );
+        assert_eq!(context.get(&DOTNET_PLATFORM_TASK_VARIABLE), Some("x64"));
+
+        // The debug locator evaluates the project of the run task in that configuration.
+        let run_task = project_task_templates(&[])
+            .into_iter()
+            .find(|template| template.label == "dotnet: run")
+            .unwrap()
+            .resolve_task(
+                "test",
+                &TaskContext {
+                    task_variables: context,
+                    ..TaskContext::default()
+                },
+            )
+            .unwrap();
+        let options = build_evaluation_options(&run_task.resolved.args);
+        assert_eq!(options.configuration, "Release");
+        assert_eq!(options.platform.as_deref(), Some("x64"));
     }
-    None
+
+    #[gpui::test]
+    fn test_csharp_runnables(cx: &mut TestAppContext) {
+        let language = language("csharp", tree_sitter_c_sharp::LANGUAGE.into());
+        let text = r#"
+namespace Shop.Tests;
+
+public class OrderTests
+{
+    [Fact]
+    public void Total_IncludesTax() { }
+
+    [Xunit.Theory]
+    [InlineData(10)]
+    public void Discount(int percent) { }
+
+    public void Helper() { }
 }
 
-/// Parse a .csproj file to extract NuGet package references
-pub fn parse_csproj_packages(content: &str) -> Result<Vec<NuGetPackage>> {
-    let mut packages = Vec::new();
-    
-    // Simple XML parsing for PackageReference items
-    // Format: <PackageReference Include="PackageId" Version="1.0.0" />
-    // or: <PackageReference Include="PackageId" />
-    
-    let mut remaining = content;
-    while let Some(ref_start) = remaining.find("<PackageReference") {
-        let ref_end = remaining[ref_start..].find("/>")
-            .or_else(|| remaining[ref_start..].find("</PackageReference>"))
-            .ok_or_else(|| anyhow::anyhow!("Invalid XML: unclosed PackageReference tag"))?;
-        
-        let ref_tag = &remaining[ref_start..ref_start + ref_end + 2];
-        
-        // Extract Include attribute (package ID)
-        let package_id = if let Some(include_start) = ref_tag.find("Include=\"") {
-            let include_start = include_start + 9; // Skip "Include=\""
-            if let Some(include_end) = ref_tag[include_start..].find('"') {
-                Some(ref_tag[include_start..include_start + include_end].to_string())
-            } else {
-                None
-            }
-        } else {
-            None
-        };
-        
-        // Extract Version attribute (optional)
-        let package_version = if let Some(version_start) = ref_tag.find("Version=\"") {
-            let version_start = version_start + 9; // Skip "Version=\""
-            if let Some(version_end) = ref_tag[version_start..].find('"') {
-                Some(ref_tag[version_start..version_start + version_end].to_string())
-            } else {
-                None
-            }
-        } else {
-            None
-        };
-        
-        if let Some(id) = package_id {
-            packages.push(NuGetPackage {
-                id,
-                version: package_version,
-            });
-        }
-        
-        remaining = &remaining[ref_start + ref_end + 2..];
+public static class Program
+{
+    public static void Main(string[] args) { }
+}
+"#;
+
+        let buffer =
+            cx.new(|cx| crate::Buffer::local(text, cx).with_language(language.clone(), cx));
+        cx.executor().run_until_parked();
+
+        let runnables = buffer.update(cx, |buffer, _| {
+            let snapshot = buffer.snapshot();
+            snapshot
+                .runnable_ranges(0..text.len())
+                .map(|runnable| {
+                    (
+                        snapshot
+                            .text_for_range(runnable.run_range)
+                            .collect::<String>(),
+                        runnable
+                            .runnable
+                            .tags
+                            .iter()
+                            .map(|tag| tag.0.to_string())
+                            .collect::<Vec<_>>(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(
+            runnables,
+            [
+                (
+                    "Total_IncludesTax".to_string(),
+                    vec!["csharp-test".to_string()]
+                ),
+                ("Discount".to_string(), vec!["csharp-test".to_string()]),
+                ("Main".to_string(), vec!["csharp-main".to_string()]),
+            ]
+        );
     }
-    
-    Ok(packages)
 }
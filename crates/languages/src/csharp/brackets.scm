@@ -0,0 +1,5 @@
+("(" @open ")" @close)
+("[" @open "]" @close)
+("{" @open "}" @close)
+(("\"" @open "\"" @close) (#set! rainbow.exclude))
+((character_literal) @open @close (#set! rainbow.exclude))
@@ -0,0 +1,241 @@
+(identifier) @variable
+
+; Types
+
+(predefined_type) @type.builtin
+
+(implicit_type) @keyword
+
+(class_declaration
+  name: (identifier) @type)
+
+(struct_declaration
+  name: (identifier) @type)
+
+(record_declaration
+  name: (identifier) @type)
+
+(interface_declaration
+  name: (identifier) @type)
+
+(enum_declaration
+  name: (identifier) @type)
+
+(delegate_declaration
+  name: (identifier) @type)
+
+(generic_name
+  (identifier) @type)
+
+(type_argument_list
+  (identifier) @type)
+
+(base_list
+  (identifier) @type)
+
+(type_parameter
+  (identifier) @type)
+
+(_
+  type: (identifier) @type)
+
+(qualified_name
+  name: (identifier) @type)
+
+(namespace_declaration
+  name: [
+    (identifier) @namespace
+    (qualified_name
+      (identifier) @namespace)
+  ])
+
+(file_scoped_namespace_declaration
+  name: [
+    (identifier) @namespace
+    (qualified_name
+      (identifier) @namespace)
+  ])
+
+(enum_member_declaration
+  (identifier) @constant)
+
+; Functions
+
+(method_declaration
+  name: (identifier) @function.definition)
+
+(local_function_statement
+  name: (identifier) @function.definition)
+
+(constructor_declaration
+  name: (identifier) @constructor)
+
+(destructor_declaration
+  name: (identifier) @constructor)
+
+(invocation_expression
+  function: (identifier) @function)
+
+(invocation_expression
+  function: (member_access_expression
+    name: (identifier) @function.method))
+
+(invocation_expression
+  function: (member_access_expression
+    name: (generic_name
+      (identifier) @function.method)))
+
+; Members
+
+(property_declaration
+  name: (identifier) @property)
+
+(member_access_expression
+  name: (identifier) @property)
+
+(parameter
+  name: (identifier) @variable.parameter)
+
+(attribute
+  name: (identifier) @attribute)
+
+(attribute
+  name: (qualified_name) @attribute)
+
+; Literals
+
+[
+  (integer_literal)
+  (real_literal)
+] @number
+
+[
+  (string_literal)
+  (verbatim_string_literal)
+  (raw_string_literal)
+  (interpolated_string_expression)
+] @string
+
+(character_literal) @string
+
+(escape_sequence) @string.escape
+
+[
+  (boolean_literal)
+  (null_literal)
+] @constant.builtin
+
+(comment) @comment
+
+((comment) @comment.doc
+  (#match? @comment.doc "^///"))
+
+; Tokens
+
+[
+  ";"
+  "."
+  ","
+  ":"
+] @punctuation.delimiter
+
+[
+  "("
+  ")"
+  "["
+  "]"
+  "{"
+  "}"
+] @punctuation.bracket
+
+[
+  "="
+  "=="
+  "!="
+  "<"
+  "<="
+  ">"
+  ">="
+  "+"
+  "+="
+  "++"
+  "-"
+  "-="
+  "--"
+  "*"
+  "*="
+  "/"
+  "/="
+  "%"
+  "%="
+  "!"
+  "&&"
+  "||"
+  "&"
+  "|"
+  "^"
+  "~"
+  "<<"
+  ">>"
+  "?"
+  "??"
+  "??="
+  "=>"
+] @operator
+
+(modifier) @keyword
+
+[
+  "as"
+  "base"
+  "break"
+  "case"
+  "catch"
+  "checked"
+  "class"
+  "continue"
+  "default"
+  "delegate"
+  "do"
+  "else"
+  "enum"
+  "event"
+  "explicit"
+  "finally"
+  "for"
+  "foreach"
+  "get"
+  "goto"
+  "if"
+  "implicit"
+  "in"
+  "init"
+  "interface"
+  "is"
+  "lock"
+  "namespace"
+  "new"
+  "operator"
+  "out"
+  "params"
+  "record"
+  "ref"
+  "return"
+  "set"
+  "sizeof"
+  "stackalloc"
+  "struct"
+  "switch"
+  "this"
+  "throw"
+  "try"
+  "typeof"
+  "unchecked"
+  "using"
+  "when"
+  "where"
+  "while"
+  "with"
+  "yield"
+  "await"
+] @keyword
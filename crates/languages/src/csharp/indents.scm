@@ -0,0 +1,14 @@
+[
+  (member_access_expression)
+  (assignment_expression)
+  (if_statement)
+  (for_statement)
+  (foreach_statement)
+  (while_statement)
+  (do_statement)
+  (switch_expression)
+] @indent
+
+(_ "{" "}" @end) @indent
+(_ "(" ")" @end) @indent
+(_ "[" "]" @end) @indent
@@ -0,0 +1,73 @@
+(comment) @annotation
+
+(namespace_declaration
+    "namespace" @context
+    name: (_) @name) @item
+
+(file_scoped_namespace_declaration
+    "namespace" @context
+    name: (_) @name) @item
+
+(class_declaration
+    (modifier)* @context
+    "class" @context
+    name: (_) @name) @item
+
+(struct_declaration
+    (modifier)* @context
+    "struct" @context
+    name: (_) @name) @item
+
+(record_declaration
+    (modifier)* @context
+    "record" @context
+    name: (_) @name) @item
+
+(interface_declaration
+    (modifier)* @context
+    "interface" @context
+    name: (_) @name) @item
+
+(enum_declaration
+    (modifier)* @context
+    "enum" @context
+    name: (_) @name) @item
+
+(enum_member_declaration
+    name: (_) @name) @item
+
+(delegate_declaration
+    (modifier)* @context
+    "delegate" @context
+    name: (_) @name) @item
+
+(method_declaration
+    (modifier)* @context
+    name: (_) @name
+    parameters: (parameter_list
+        "(" @context
+        ")" @context)) @item
+
+(constructor_declaration
+    (modifier)* @context
+    name: (_) @name
+    parameters: (parameter_list
+        "(" @context
+        ")" @context)) @item
+
+(property_declaration
+    (modifier)* @context
+    name: (_) @name) @item
+
+(field_declaration
+    (modifier)* @context
+    (variable_declaration
+        (variable_declarator
+            name: (_) @name))) @item
+
+(event_field_declaration
+    (modifier)* @context
+    "event" @context
+    (variable_declaration
+        (variable_declarator
+            name: (_) @name))) @item
@@ -0,0 +1,20 @@
+; xUnit, NUnit and MSTest test methods
+(
+    (method_declaration
+        (attribute_list
+            (attribute
+                name: (_) @_attribute))
+        name: (identifier) @run @_dotnet_test_method
+        (#match? @_attribute "(^|\\.)(Fact|Theory|SkippableFact|SkippableTheory|Test|TestCase|TestCaseSource|TestMethod|DataTestMethod)(Attribute)?$"))
+    (#set! tag csharp-test)
+)
+
+; `static void Main`, the entry point of a program
+(
+    (method_declaration
+        (modifier) @_static
+        name: (identifier) @run @_name
+        (#eq? @_static "static")
+        (#eq? @_name "Main"))
+    (#set! tag csharp-main)
+)
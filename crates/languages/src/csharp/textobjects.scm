@@ -0,0 +1,59 @@
+(method_declaration
+    body: (block
+        "{"
+        (_)* @function.inside
+        "}")) @function.around
+
+(method_declaration
+    body: (arrow_expression_clause
+        (_) @function.inside)) @function.around
+
+(constructor_declaration
+    body: (block
+        "{"
+        (_)* @function.inside
+        "}")) @function.around
+
+(local_function_statement
+    body: (block
+        "{"
+        (_)* @function.inside
+        "}")) @function.around
+
+(lambda_expression
+    body: (block
+        "{"
+        (_)* @function.inside
+        "}")) @function.around
+
+(class_declaration
+    body: (declaration_list
+        "{"
+        (_)* @class.inside
+        "}")) @class.around
+
+(struct_declaration
+    body: (declaration_list
+        "{"
+        (_)* @class.inside
+        "}")) @class.around
+
+(record_declaration
+    body: (declaration_list
+        "{"
+        (_)* @class.inside
+        "}")) @class.around
+
+(interface_declaration
+    body: (declaration_list
+        "{"
+        (_)* @class.inside
+        "}")) @class.around
+
+(enum_declaration
+    body: (enum_member_declaration_list
+        "{"
+        [(_) ","?]* @class.inside
+        "}")) @class.around
+
+(comment)+ @comment.around
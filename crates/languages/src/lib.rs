@@ -1,10 +1,10 @@
 use anyhow::Context as _;
+use csharp::{CsprojManifestProvider, DotNetManifestProvider, SolutionManifestProvider};
 use gpui::{App, SharedString, UpdateGlobal};
 use node_runtime::NodeRuntime;
 use project::Fs;
 use python::PyprojectTomlManifestProvider;
 use rust::CargoManifestProvider;
-use csharp::{CsprojManifestProvider, SolutionManifestProvider};
 use rust_embed::RustEmbed;
 use settings::SettingsStore;
 use smol::stream::StreamExt;
@@ -66,6 +66,7 @@ pub fn init(languages: Arc<LanguageRegistry>, fs: Arc<dyn Fs>, node: NodeRuntime
         ("bash", tree_sitter_bash::LANGUAGE),
         ("c", tree_sitter_c::LANGUAGE),
         ("cpp", tree_sitter_cpp::LANGUAGE),
+        ("csharp", tree_sitter_c_sharp::LANGUAGE),
         ("css", tree_sitter_css::LANGUAGE),
         ("diff", tree_sitter_diff::LANGUAGE),
         ("go", tree_sitter_go::LANGUAGE),
@@ -81,11 +82,14 @@ pub fn init(languages: Arc<LanguageRegistry>, fs: Arc<dyn Fs>, node: NodeRuntime
         ("rust", tree_sitter_rust::LANGUAGE),
         ("tsx", tree_sitter_typescript::LANGUAGE_TSX),
         ("typescript", tree_sitter_typescript::LANGUAGE_TYPESCRIPT),
+        ("xml", tree_sitter_xml::LANGUAGE_XML),
         ("yaml", tree_sitter_yaml::LANGUAGE),
         ("gitcommit", tree_sitter_gitcommit::LANGUAGE),
     ]);
 
     let c_lsp_adapter = Arc::new(c::CLspAdapter);
+    let csharp_context_provider = Arc::new(csharp::CSharpContextProvider);
+    let csharp_lsp_adapter = Arc::new(csharp::CSharpLspAdapter);
     let css_lsp_adapter = Arc::new(css::CssLspAdapter::new(node.clone()));
     let eslint_adapter = Arc::new(eslint::EsLintLspAdapter::new(node.clone()));
     let go_context_provider = Arc::new(go::GoContextProvider);
@@ -127,6 +131,18 @@ pub fn init(languages: Arc<LanguageRegistry>, fs: Arc<dyn Fs>, node: NodeRuntime
             adapters: vec![c_lsp_adapter],
             ..Default::default()
         },
+        LanguageInfo {
+            name: "csharp",
+            adapters: vec![csharp_lsp_adapter],
+            context: Some(csharp_context_provider.clone()),
+            manifest_name: Some(SharedString::new_static(DotNetManifestProvider::NAME).into()),
+            ..Default::default()
+        },
+        LanguageInfo {
+            name: "msbuild",
+            context: Some(csharp_context_provider),
+            ..Default::default()
+        },
         LanguageInfo {
             name: "css",
             adapters: vec![css_lsp_adapter],
@@ -329,11 +345,12 @@ pub fn init(languages: Arc<LanguageRegistry>, fs: Arc<dyn Fs>, node: NodeRuntime
         anyhow::Ok(())
     })
     .detach();
-    let manifest_providers: [Arc<dyn ManifestProvider>; 4] = [
+    let manifest_providers: [Arc<dyn ManifestProvider>; 5] = [
         Arc::from(CargoManifestProvider),
         Arc::from(PyprojectTomlManifestProvider),
         Arc::from(CsprojManifestProvider),
         Arc::from(SolutionManifestProvider),
+        Arc::from(DotNetManifestProvider),
     ];
     for provider in manifest_providers {
         project::ManifestProvidersStore::global(cx).register(provider);
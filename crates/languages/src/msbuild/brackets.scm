@@ -0,0 +1,3 @@
+(STag "<" @open ">" @close)
+(ETag "</" @open ">" @close)
+(EmptyElemTag "<" @open "/>" @close)
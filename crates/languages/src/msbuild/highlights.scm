@@ -0,0 +1,37 @@
+(STag
+    (Name) @tag)
+
+(ETag
+    (Name) @tag)
+
+(EmptyElemTag
+    (Name) @tag)
+
+(Attribute
+    (Name) @attribute)
+
+(Attribute
+    (AttValue) @string)
+
+[
+    (EntityRef)
+    (CharRef)
+] @string.escape
+
+(XMLDecl
+    "xml" @keyword)
+
+(Comment) @comment
+
+(CDSect) @string
+
+[
+    "<"
+    ">"
+    "</"
+    "/>"
+    "<?"
+    "?>"
+] @punctuation.bracket
+
+"=" @operator
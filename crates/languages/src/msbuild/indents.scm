@@ -0,0 +1,3 @@
+(element
+    (STag)
+    (ETag) @end) @indent
@@ -0,0 +1,22 @@
+(Comment) @annotation
+
+; Property and item groups, targets and imports, the structure of a project
+(element
+    (STag
+        (Name) @name
+        (#any-of? @name "PropertyGroup" "ItemGroup" "ItemDefinitionGroup" "Choose" "When" "Otherwise" "ImportGroup"))) @item
+
+(element
+    (STag
+        (Name) @context
+        (Attribute
+            (Name) @_attribute
+            (AttValue) @name)
+        (#any-of? @_attribute "Include" "Update" "Remove" "Project" "Name"))) @item
+
+(EmptyElemTag
+    (Name) @context
+    (Attribute
+        (Name) @_attribute
+        (AttValue) @name)
+    (#any-of? @_attribute "Include" "Update" "Remove" "Project" "Name")) @item
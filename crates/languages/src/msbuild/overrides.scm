@@ -0,0 +1,2 @@
+(Comment) @comment.inclusive
+(AttValue) @string
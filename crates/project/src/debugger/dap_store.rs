@@ -7,6 +7,8 @@ use super::{
 use crate::{
     InlayHint, InlayHintLabel, ProjectEnvironment, ResolveState,
     debugger::session::SessionQuirks,
+    dotnet_build_diagnostics::DotNetBuildDiagnostics,
+    dotnet_startup_projects::DotNetStartupProjects,
     project_settings::{DapBinary, ProjectSettings},
     worktree_store::WorktreeStore,
 };
@@ -14,7 +16,7 @@ use anyhow::{Context as _, Result, anyhow};
 use async_trait::async_trait;
 use collections::HashMap;
 use dap::{
-    Capabilities, DapRegistry, DebugRequest, EvaluateArgumentsContext, StackFrameId,
+    Capabilities, DapLocator, DapRegistry, DebugRequest, EvaluateArgumentsContext, StackFrameId,
     adapters::{
         DapDelegate, DebugAdapterBinary, DebugAdapterName, DebugTaskDefinition, TcpArguments,
     },
@@ -78,6 +80,9 @@ pub struct LocalDapStore {
     http_client: Arc<dyn HttpClient>,
     environment: Entity<ProjectEnvironment>,
     toolchain_store: Arc<dyn LanguageToolchainStore>,
+    /// Locators that read the state of this project, used instead of the
+    /// global ones of the same name when running a build.
+    locators: HashMap<SharedString, Arc<dyn DapLocator>>,
     is_headless: bool,
 }
 
@@ -119,7 +124,12 @@ impl DapStore {
         ADD_LOCATORS.call_once(|| {
             let registry = DapRegistry::global(cx);
             registry.add_locator(Arc::new(locators::cargo::CargoLocator {}));
-            registry.add_locator(Arc::new(locators::dotnet::DotNetLocator));
+            // Creates scenarios only: each local project runs builds with a
+            // locator that knows its startup projects and shows its diagnostics
+            registry.add_locator(Arc::new(locators::dotnet::DotNetLocator {
+                startup_projects: DotNetStartupProjects::default(),
+                build_diagnostics: DotNetBuildDiagnostics::default(),
+            }));
             registry.add_locator(Arc::new(locators::go::GoLocator {}));
             registry.add_locator(Arc::new(locators::node::NodeLocator));
             registry.add_locator(Arc::new(locators::python::PythonLocator));
@@ -138,15 +148,22 @@ impl DapStore {
         toolchain_store: Arc<dyn LanguageToolchainStore>,
         worktree_store: Entity<WorktreeStore>,
         breakpoint_store: Entity<BreakpointStore>,
+        dotnet_startup_projects: DotNetStartupProjects,
+        dotnet_build_diagnostics: DotNetBuildDiagnostics,
         is_headless: bool,
         cx: &mut Context<Self>,
     ) -> Self {
+        let dotnet_locator: Arc<dyn DapLocator> = Arc::new(locators::dotnet::DotNetLocator {
+            startup_projects: dotnet_startup_projects,
+            build_diagnostics: dotnet_build_diagnostics,
+        });
         let mode = DapStoreMode::Local(LocalDapStore {
             fs: fs.clone(),
             environment,
             http_client,
             node_runtime,
             toolchain_store,
+            locators: HashMap::from_iter([(dotnet_locator.name(), dotnet_locator)]),
             is_headless,
         });
 
@@ -393,12 +410,16 @@ impl DapStore {
         cx: &mut Context<Self>,
     ) -> Task<Result<DebugRequest>> {
         match &self.mode {
-            DapStoreMode::Local(_) => {
+            DapStoreMode::Local(local) => {
                 // Pre-resolve args with existing environment.
-                let locators = DapRegistry::global(cx).locators();
-                let locator = locators.get(locator_name);
+                let locator = local.locators.get(locator_name).cloned().or_else(|| {
+                    DapRegistry::global(cx)
+                        .locators()
+                        .get(locator_name)
+                        .cloned()
+                });
 
-                if let Some(locator) = locator.cloned() {
+                if let Some(locator) = locator {
                     cx.background_spawn(async move {
                         let result = locator
                             .run(build_command.clone())
@@ -1,5 +1,5 @@
 pub(crate) mod cargo;
-pub(crate) mod dotnet;
+pub mod dotnet;
 pub(crate) mod go;
 pub(crate) mod node;
 pub(crate) mod python;
@@ -1,19 +1,39 @@
+use crate::{
+    dotnet_build_diagnostics::DotNetBuildDiagnostics,
+    dotnet_startup_projects::DotNetStartupProjects,
+};
 use anyhow::{Context as _, Result, bail};
 use async_trait::async_trait;
 use collections::FxHashMap;
 use dap::{DapLocator, DebugRequest, adapters::DebugAdapterName};
+use dotnet::{
+    BuildDiagnosticSeverity, EvaluationOptions, LaunchProfile, LaunchSettings, ProjectModel,
+    RealProjectFiles,
+    diagnostics::{CONSOLE_LOGGER_ARGS, parse_build_output},
+    find_project_file,
+    testing::{VSTEST_HOST_DEBUG, parse_test_host_process_id},
+};
 use gpui::SharedString;
 use serde_json::json;
-use smol::io::AsyncReadExt;
+use smol::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
 use smol::process::Stdio;
+use smol::stream::StreamExt;
 use std::path::{Path, PathBuf};
-use task::{BuildTaskDefinition, DebugScenario, LaunchRequest, ShellBuilder, SpawnInTerminal, TaskTemplate};
-use util::command::new_smol_command;
+use task::{
+    AttachRequest, BuildTaskDefinition, DebugScenario, LaunchRequest, ShellBuilder,
+    SpawnInTerminal, TaskTemplate,
+};
+use util::{ResultExt as _, command::new_smol_command};
 
 /// Debug locator for .NET projects
 /// Converts "dotnet run" tasks to debug configurations
 /// Parses build output to find the executable DLL path
-pub(crate) struct DotNetLocator;
+pub(crate) struct DotNetLocator {
+    /// Startup projects, for builds of a whole solution
+    pub(crate) startup_projects: DotNetStartupProjects,
+    /// Where the errors and warnings of the build are reported
+    pub(crate) build_diagnostics: DotNetBuildDiagnostics,
+}
 
 #[async_trait]
 impl DapLocator for DotNetLocator {
@@ -43,13 +63,20 @@ impl DapLocator for DotNetLocator {
                 // Convert "dotnet run" to "dotnet build"
                 // The locator's run() method will find the executable
                 *dotnet_action = "build".to_owned();
+                // `dotnet build` takes the project as a positional argument
+                if let Some(ix) = task_template.args.iter().position(|arg| arg == "--project") {
+                    task_template.args.remove(ix);
+                }
             }
             "test" => {
-                // Test debugging - build without running
-                // Could skip the build if --no-build is present
-                if !task_template.args.contains(&"--no-build".to_owned()) {
-                    // Tests typically don't need building separately
-                    return None;
+                // The build step lists the tests instead of running them, and
+                // run() starts a test host for the debugger to attach to
+                if !task_template
+                    .args
+                    .iter()
+                    .any(|arg| arg == "--list-tests" || arg == "-t")
+                {
+                    task_template.args.push("--list-tests".to_owned());
                 }
             }
             "build" => {
@@ -69,9 +96,9 @@ impl DapLocator for DotNetLocator {
                 task_template,
                 locator_name: Some(self.name()),
             }),
+            // No request: run() decides between launching and attaching
             config: json!({
-                "type": "coreclr",
-                "request": "launch"
+                "type": "coreclr"
             }),
             tcp_connection: None,
         })
@@ -82,23 +109,13 @@ impl DapLocator for DotNetLocator {
             .cwd
             .clone()
             .context("Working directory required for dotnet build")?;
+        if build_config.args.first().is_some_and(|arg| arg == "test") {
+            return start_test_host(&build_config, &cwd).await;
+        }
 
-        // Build the dotnet command with output path generation
+        let dotnet_args = locator_build_args(&build_config.args);
         let builder = ShellBuilder::new(&build_config.shell, cfg!(windows)).non_interactive();
-        let (program, mut args) = builder.build(
-            Some("dotnet".into()),
-            &build_config
-                .args
-                .iter()
-                .cloned()
-                .take_while(|arg| arg != "--")
-                .collect::<Vec<_>>(),
-        );
-
-        // Add flags to get full paths and verbose output
-        args.push("--no-restore".to_string());
-        args.push("/p:GenerateFullPaths=true".to_string());
-        args.push("-v:q".to_string()); // Quiet verbosity to reduce output noise
+        let (program, args) = builder.build(Some("dotnet".into()), &dotnet_args);
 
         log::info!("Running dotnet build: {} {:?}", program, args);
 
@@ -124,43 +141,352 @@ impl DapLocator for DotNetLocator {
 
         let status = child.status().await.context("Build process failed")?;
 
+        let diagnostics = parse_build_output(&stdout, &cwd);
+        let errors = diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == BuildDiagnosticSeverity::Error)
+            .map(|error| {
+                format!(
+                    "{}({},{}): {}",
+                    error.path.display(),
+                    error.start.line + 1,
+                    error.start.column + 1,
+                    error.message
+                )
+            })
+            .collect::<Vec<_>>();
+        self.build_diagnostics.publish(diagnostics);
+
         if !status.success() {
             bail!(
-                "dotnet build failed with exit code {:?}\nstderr: {}",
+                "dotnet build failed with exit code {:?}\n{}",
                 status.code(),
-                stderr
+                if errors.is_empty() {
+                    stderr
+                } else {
+                    errors.join("\n")
+                }
             );
         }
 
+        let build_args = DotNetBuildArgs::parse(&build_config.args);
+        let project_path = self.project_to_debug(&build_args, &cwd);
+        let project = project_path.as_deref().and_then(|project_path| {
+            let options = build_args.evaluation_options();
+            ProjectModel::load(project_path, &options, &RealProjectFiles)
+                .inspect_err(|e| log::warn!("Failed to evaluate {}: {e:#}", project_path.display()))
+                .ok()
+        });
+
         // Parse the output to find the built DLL path
-        let dll_path = find_dotnet_output_assembly(&stdout, &cwd)?;
+        let dll_path = find_dotnet_output_assembly(
+            &stdout,
+            &cwd,
+            project.as_ref(),
+            build_args.framework.as_deref(),
+        )?;
 
         log::info!("Found output assembly: {}", dll_path);
 
+        // Launch from the project directory, as `dotnet run` does
+        let project_dir = project
+            .as_ref()
+            .map(|project| project.directory().to_path_buf())
+            .unwrap_or(cwd);
+        let launch_profile = launch_profile(&project_dir, &build_args)?;
+
+        let mut env = build_config
+            .env
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect::<FxHashMap<_, _>>();
+        let mut args = build_args.program_args.clone();
+        let mut launch_cwd = project_dir.clone();
+        if let Some(profile) = &launch_profile {
+            log::info!("Applying launch profile {}", profile.name);
+            let profile_env = profile.environment(|name| {
+                build_config
+                    .env
+                    .get(name)
+                    .cloned()
+                    .or_else(|| std::env::var(name).ok())
+            });
+            env.extend(profile_env);
+            // Arguments given on the command line replace the profile's
+            if args.is_empty() {
+                args = profile.arguments();
+            }
+            launch_cwd = profile.working_directory(&project_dir);
+        }
+
         // Create the debug launch request
         let launch_request = LaunchRequest {
             program: dll_path,
-            cwd: Some(cwd),
-            args: vec![],
-            env: FxHashMap::default(),
+            cwd: Some(launch_cwd),
+            args,
+            env,
         };
 
         Ok(DebugRequest::Launch(launch_request))
     }
 }
 
+impl DotNetLocator {
+    /// The project file that a build run in `cwd` produces the program to
+    /// debug of: the one it names, or a startup project of the solution it
+    /// builds.
+    fn project_to_debug(&self, build_args: &DotNetBuildArgs, cwd: &Path) -> Option<PathBuf> {
+        match build_args.project.as_ref().map(|project| cwd.join(project)) {
+            // Building a solution: debug its startup project
+            Some(solution) if is_solution_file(&solution) => solution
+                .parent()
+                .and_then(|dir| self.startup_projects.debug_target(dir)),
+            Some(project) if project.is_file() => Some(project),
+            _ => {
+                let nearest = find_project_file(cwd);
+                if nearest.as_deref().and_then(Path::parent) == Some(cwd) {
+                    nearest
+                } else {
+                    // No project of its own: `cwd` may be a solution directory
+                    self.startup_projects.debug_target(cwd).or(nearest)
+                }
+            }
+        }
+    }
+}
+
+/// Runs the tests of a `dotnet test` task, which the build step has built,
+/// in a test host that waits for the debugger to attach to it.
+async fn start_test_host(build_config: &SpawnInTerminal, cwd: &Path) -> Result<DebugRequest> {
+    let mut dotnet_args = build_config
+        .args
+        .iter()
+        .filter(|arg| *arg != "--list-tests" && *arg != "-t")
+        .cloned()
+        .collect::<Vec<_>>();
+    if !dotnet_args.iter().any(|arg| arg == "--no-build") {
+        dotnet_args.push("--no-build".to_string());
+    }
+    let builder = ShellBuilder::new(&build_config.shell, cfg!(windows)).non_interactive();
+    let (program, args) = builder.build(Some("dotnet".into()), &dotnet_args);
+
+    log::info!("Starting test host: {} {:?}", program, args);
+
+    let mut child = new_smol_command(&program)
+        .args(&args)
+        .envs(build_config.env.iter().map(|(k, v)| (k.clone(), v.clone())))
+        .env(VSTEST_HOST_DEBUG, "1")
+        .current_dir(cwd)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .context("Failed to spawn dotnet test")?;
+    let stdout = child
+        .stdout
+        .take()
+        .context("Failed to read dotnet test output")?;
+
+    let mut lines = BufReader::new(stdout).lines();
+    let mut output = String::new();
+    let process_id = loop {
+        let Some(line) = lines.next().await else {
+            bail!("dotnet test exited before starting a test host\n{output}");
+        };
+        let line = line.context("Failed to read dotnet test output")?;
+        if let Some(process_id) = parse_test_host_process_id(&line) {
+            break process_id;
+        }
+        output.push_str(&line);
+        output.push('\n');
+    };
+    log::info!("Test host waiting for the debugger: process {process_id}");
+
+    // Keep draining the output while the tests run, so that they never block
+    // on a full pipe
+    smol::spawn(async move {
+        while lines.next().await.is_some() {}
+        child.status().await.log_err();
+    })
+    .detach();
+
+    Ok(DebugRequest::Attach(AttachRequest {
+        process_id: Some(process_id),
+    }))
+}
+
+/// The options the locator evaluates the project of a `dotnet build` or
+/// `dotnet run` command line with, to find the assembly to debug.
+pub fn build_evaluation_options(args: &[String]) -> EvaluationOptions {
+    DotNetBuildArgs::parse(args).evaluation_options()
+}
+
+/// The parts of a `dotnet build` or `dotnet run` command line that determine
+/// its output and how it is launched.
+#[derive(Debug, Default, PartialEq)]
+struct DotNetBuildArgs {
+    project: Option<PathBuf>,
+    configuration: Option<String>,
+    platform: Option<String>,
+    framework: Option<String>,
+    launch_profile: Option<String>,
+    no_launch_profile: bool,
+    /// Arguments for the program, after `--`
+    program_args: Vec<String>,
+}
+
+impl DotNetBuildArgs {
+    fn parse(args: &[String]) -> Self {
+        let mut result = Self::default();
+        let mut args = args.iter().skip(1).map(|arg| arg.trim_matches('"'));
+        while let Some(arg) = args.next() {
+            if arg == "--" {
+                result.program_args = args.map(str::to_string).collect();
+                break;
+            }
+            let (flag, inline_value) = match arg.split_once([':', '=']) {
+                Some((flag, value)) if flag.starts_with(['-', '/']) => (flag, Some(value)),
+                _ => (arg, None),
+            };
+            match flag {
+                "-c" | "--configuration" => {
+                    result.configuration = inline_value.or_else(|| args.next()).map(str::to_string);
+                }
+                "-f" | "--framework" => {
+                    result.framework = inline_value.or_else(|| args.next()).map(str::to_string);
+                }
+                "-lp" | "--launch-profile" => {
+                    result.launch_profile =
+                        inline_value.or_else(|| args.next()).map(str::to_string);
+                }
+                "--no-launch-profile" => result.no_launch_profile = true,
+                "-p" | "--property" | "/p" | "/property" => {
+                    let property = inline_value.or_else(|| args.next()).unwrap_or_default();
+                    if let Some((name, value)) = property.split_once('=')
+                        && name.eq_ignore_ascii_case("Platform")
+                    {
+                        result.platform = Some(value.trim_matches('"').to_string());
+                    }
+                }
+                "-o" | "--output" | "-r" | "--runtime" | "-a" | "--arch" | "--os" | "-v"
+                | "--verbosity" => {
+                    if inline_value.is_none() {
+                        args.next();
+                    }
+                }
+                // MSBuild switches such as `/p:Name=Value` are not projects
+                _ if !arg.starts_with('-')
+                    && !(arg.starts_with('/') && arg.contains(':'))
+                    && result.project.is_none() =>
+                {
+                    result.project = Some(PathBuf::from(arg));
+                }
+                _ => {}
+            }
+        }
+        result
+    }
+
+    /// The configuration and platform given on the command line, which
+    /// default to `Debug` and the project's own platform, as in `dotnet build`.
+    fn evaluation_options(&self) -> EvaluationOptions {
+        EvaluationOptions {
+            configuration: self
+                .configuration
+                .clone()
+                .unwrap_or_else(|| "Debug".to_string()),
+            platform: self.platform.clone(),
+            ..EvaluationOptions::default()
+        }
+    }
+}
+
+/// The arguments of a build or run task that `dotnet build` accepts: launch
+/// profile options and program arguments only apply to `dotnet run`.
+fn build_command_args(args: &[String]) -> Vec<String> {
+    let mut result = Vec::new();
+    let mut args = args.iter().take_while(|arg| *arg != "--");
+    while let Some(arg) = args.next() {
+        let (flag, inline_value) = match arg.trim_matches('"').split_once([':', '=']) {
+            Some((flag, value)) => (flag, Some(value)),
+            None => (arg.trim_matches('"'), None),
+        };
+        match flag {
+            "-lp" | "--launch-profile" => {
+                if inline_value.is_none() {
+                    args.next();
+                }
+            }
+            "--no-launch-profile" => {}
+            _ => result.push(arg.clone()),
+        }
+    }
+    result
+}
+
+/// The arguments of the build the locator runs to find the output assembly of
+/// a build or run task.
+fn locator_build_args(args: &[String]) -> Vec<String> {
+    let mut result = build_command_args(args);
+    // The build task ran in a terminal before, and restored packages already
+    if !result.iter().any(|arg| arg == "--no-restore") {
+        result.push("--no-restore".to_string());
+    }
+    // One line per error with its full path, for the build diagnostics.
+    // Minimal verbosity still lists the output assembly of each project.
+    result.push("-v:m".to_string());
+    result.extend(CONSOLE_LOGGER_ARGS.iter().map(|arg| arg.to_string()));
+    result
+}
+
+/// The launch profile `dotnet run` would apply: the one named on the command
+/// line, or the first one it can launch.
+fn launch_profile(
+    project_dir: &Path,
+    build_args: &DotNetBuildArgs,
+) -> Result<Option<LaunchProfile>> {
+    if build_args.no_launch_profile {
+        return Ok(None);
+    }
+    let settings = LaunchSettings::load(project_dir)?;
+    match (&build_args.launch_profile, settings) {
+        (Some(name), Some(settings)) => {
+            settings.profile(name).cloned().map(Some).with_context(|| {
+                format!(
+                    "Launch profile {name} not found in {}",
+                    settings.path.display()
+                )
+            })
+        }
+        (Some(name), None) => bail!(
+            "Launch profile {name} requested, but {} has no launch settings",
+            project_dir.display()
+        ),
+        (None, settings) => Ok(settings.and_then(|settings| settings.default_profile().cloned())),
+    }
+}
+
 /// Parse dotnet build output to find the compiled assembly path
 /// Dotnet outputs lines like: "MyApp -> /path/to/bin/Debug/net6.0/MyApp.dll"
-fn find_dotnet_output_assembly(output: &str, cwd: &std::path::Path) -> Result<String> {
+/// Falls back to the output path of the evaluated project
+fn find_dotnet_output_assembly(
+    output: &str,
+    cwd: &Path,
+    project: Option<&ProjectModel>,
+    framework: Option<&str>,
+) -> Result<String> {
     // Look for the pattern: "ProjectName -> /path/to/assembly"
     for line in output.lines() {
         if let Some(arrow_pos) = line.find("->") {
+            // A solution build lists every project, only take the one we debug
+            if project.is_some_and(|project| line[..arrow_pos].trim() != project.name) {
+                continue;
+            }
             let assembly_part = line[arrow_pos + 2..].trim();
 
             // Check if this looks like a .dll or .exe file
             if assembly_part.ends_with(".dll") || assembly_part.ends_with(".exe") {
                 // Make the path absolute if it's relative
-                let path = std::path::Path::new(assembly_part);
+                let path = Path::new(assembly_part);
                 let absolute_path = if path.is_absolute() {
                     path.to_path_buf()
                 } else {
@@ -174,30 +500,15 @@ fn find_dotnet_output_assembly(output: &str, cwd: &std::path::Path) -> Result<St
         }
     }
 
-    // Fallback: try to find a recently modified DLL in common output paths
-    let possible_output_dirs = vec![
-        cwd.join("bin/Debug"),
-        cwd.join("bin/Release"),
-        cwd.join("bin/Debug/net6.0"),
-        cwd.join("bin/Debug/net5.0"),
-        cwd.join("bin/Debug/net8.0"),
-        cwd.join("bin/Release/net6.0"),
-        cwd.join("bin/Release/net5.0"),
-        cwd.join("bin/Release/net8.0"),
-    ];
-
-    for dir in possible_output_dirs {
-        if let Ok(entries) = std::fs::read_dir(&dir) {
-            for entry in entries.flatten() {
-                if let Ok(metadata) = entry.metadata() {
-                    if let Ok(name) = entry.file_name().into_string() {
-                        if name.ends_with(".dll") && metadata.is_file() {
-                            return Ok(entry.path().to_string_lossy().to_string());
-                        }
-                    }
-                }
-            }
+    // Fallback: ask the project model where the build output goes
+    if let Some(target) = project.and_then(|project| project.target(framework)) {
+        if target.output_assembly.exists() {
+            return Ok(target.output_assembly.to_string_lossy().to_string());
         }
+        bail!(
+            "Expected build output {} does not exist",
+            target.output_assembly.display()
+        );
     }
 
     bail!(
@@ -207,75 +518,135 @@ fn find_dotnet_output_assembly(output: &str, cwd: &std::path::Path) -> Result<St
     )
 }
 
-/// Find the .sln file in or above the given directory
-fn find_solution_file(dir: &Path) -> Option<PathBuf> {
-    // Search current directory and parent directories for .sln files
-    let mut current = dir.to_path_buf();
-
-    loop {
-        // Check for any .sln file in current directory
-        if let Ok(entries) = std::fs::read_dir(&current) {
-            for entry in entries.flatten() {
-                if let Ok(name) = entry.file_name().into_string() {
-                    if name.ends_with(".sln") {
-                        return Some(entry.path());
-                    }
-                }
-            }
-        }
-
-        // Move up to parent directory
-        if !current.pop() {
-            break;
-        }
-    }
-
-    None
+fn is_solution_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == "sln" || extension == "slnx")
 }
 
-/// Try to find the startup project path from a solution file
-/// Returns the path to the startup project's directory
-fn find_startup_project_from_solution(solution_path: &Path, solution_dir: &Path) -> Option<PathBuf> {
-    // Read the solution file
-    let content = std::fs::read_to_string(solution_path).ok()?;
-
-    let mut first_exe_project = None;
-
-    // Parse solution file to find projects
-    for line in content.lines() {
-        let line = line.trim();
-
-        // Look for Project entries: Project("{type-guid}") = "name", "path", "{guid}"
-        if line.starts_with("Project(\"") {
-            if let Some(project_info) = extract_project_info(line) {
-                let project_path = solution_dir.join(&project_info.0);
-
-                // Check if this is likely an executable project (not test, not library by name heuristic)
-                if !project_info.1.contains("Test") && first_exe_project.is_none() {
-                    first_exe_project = Some(project_path.clone());
-                }
+#[cfg(test)]
+mod test {
+    use super::*;
+    use dotnet::{SolutionFile, StartupProjects};
+    use serde_json::json;
+    use util::test::TempTree;
+
+    #[test]
+    fn test_parse_build_args() {
+        let args = [
+            "build",
+            "\"/src/App/App.csproj\"",
+            "-c:Release",
+            "--framework",
+            "net8.0",
+            "-p:Platform=x64",
+            "/p:GenerateFullPaths=true",
+            "--launch-profile",
+            "https",
+            "--",
+            "extra",
+            "--verbose",
+        ]
+        .map(String::from);
+
+        assert_eq!(
+            DotNetBuildArgs::parse(&args),
+            DotNetBuildArgs {
+                project: Some(PathBuf::from("/src/App/App.csproj")),
+                configuration: Some("Release".into()),
+                platform: Some("x64".into()),
+                framework: Some("net8.0".into()),
+                launch_profile: Some("https".into()),
+                no_launch_profile: false,
+                program_args: vec!["extra".into(), "--verbose".into()],
             }
-        }
+        );
+        assert_eq!(
+            build_command_args(&args),
+            [
+                "build",
+                "\"/src/App/App.csproj\"",
+                "-c:Release",
+                "--framework",
+                "net8.0",
+                "-p:Platform=x64",
+                "/p:GenerateFullPaths=true",
+            ]
+        );
+        assert_eq!(
+            locator_build_args(&args),
+            [
+                "build",
+                "\"/src/App/App.csproj\"",
+                "-c:Release",
+                "--framework",
+                "net8.0",
+                "-p:Platform=x64",
+                "/p:GenerateFullPaths=true",
+                "--no-restore",
+                "-v:m",
+                "-p:GenerateFullPaths=true",
+                "-clp:NoSummary;ForceNoAlign",
+            ]
+        );
+        // A `--no-restore` of the task is not repeated
+        assert_eq!(
+            locator_build_args(&["build".to_string(), "--no-restore".to_string()])
+                .iter()
+                .filter(|arg| *arg == "--no-restore")
+                .count(),
+            1
+        );
     }
 
-    // Prefer the first non-test project found
-    first_exe_project
-}
+    #[test]
+    fn test_solution_build_debugs_startup_project() {
+        let tree = TempTree::new(json!({
+            "Shop.sln": "\
+Microsoft Visual Studio Solution File, Format Version 12.00
+Project(\"{FAE04EC0-301F-11D3-BA7A-00C04FC2CCAE}\") = \"Api\", \"Api\\Api.csproj\", \"{B0000000-0000-0000-0000-000000000001}\"
+EndProject
+Project(\"{FAE04EC0-301F-11D3-BA7A-00C04FC2CCAE}\") = \"Web\", \"Web\\Web.csproj\", \"{B0000000-0000-0000-0000-000000000002}\"
+EndProject
+",
+            "Api": { "Api.csproj": r#"<Project Sdk="Microsoft.NET.Sdk" />"# },
+            "Web": { "Web.csproj": r#"<Project Sdk="Microsoft.NET.Sdk.Web" />"# },
+        }));
+        let solution = SolutionFile::load(&tree.path().join("Shop.sln")).unwrap();
+        let locator = DotNetLocator {
+            startup_projects: DotNetStartupProjects::default(),
+            build_diagnostics: DotNetBuildDiagnostics::default(),
+        };
+        locator
+            .startup_projects
+            .set_startup_projects(&solution, &StartupProjects::single("Web/Web.csproj"));
+        let build = |args: &[&str]| {
+            DotNetBuildArgs::parse(&args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>())
+        };
 
-/// Extract project path and name from a Project line
-/// Format: Project("{type-guid}") = "name", "path", "{guid}"
-fn extract_project_info(line: &str) -> Option<(String, String)> {
-    if let Some(after_equals) = line.split('=').nth(1) {
-        let parts: Vec<&str> = after_equals
-            .split(',')
-            .map(|s| s.trim().trim_matches('"'))
-            .collect();
-
-        if parts.len() >= 2 {
-            let name = parts[0].to_string();
-            let path = parts[1].to_string();
-            return Some((path, name));
-        }
+        // Building the solution debugs its startup project
+        assert_eq!(
+            locator.project_to_debug(&build(&["build", "Shop.sln"]), tree.path()),
+            Some(tree.path().join("Web/Web.csproj"))
+        );
+        // So does building in the solution directory, which has no project of its own
+        assert_eq!(
+            locator.project_to_debug(&build(&["build"]), tree.path()),
+            Some(tree.path().join("Web/Web.csproj"))
+        );
+        // A project named on the command line is debugged itself
+        assert_eq!(
+            locator.project_to_debug(&build(&["build", "Api/Api.csproj"]), tree.path()),
+            Some(tree.path().join("Api/Api.csproj"))
+        );
+
+        // The startup projects of another project's solutions don't apply
+        let other_locator = DotNetLocator {
+            startup_projects: DotNetStartupProjects::default(),
+            build_diagnostics: DotNetBuildDiagnostics::default(),
+        };
+        assert_eq!(
+            other_locator.project_to_debug(&build(&["build", "Shop.sln"]), tree.path()),
+            None
+        );
     }
-    None
 }
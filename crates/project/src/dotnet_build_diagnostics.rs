@@ -0,0 +1,190 @@
+use crate::lsp_store::LspStore;
+use collections::{BTreeMap, HashSet};
+use dotnet::{
+    BuildDiagnostic, BuildDiagnosticSeverity, BuildPosition,
+    diagnostics::{file_logger_args, parse_build_output},
+};
+use gpui::{App, Context, Task, WeakEntity};
+use language::{Diagnostic, DiagnosticEntry, DiagnosticSourceKind, PointUtf16, Unclipped};
+use lsp::{DiagnosticSeverity, LanguageServerId, NumberOrString};
+use parking_lot::Mutex;
+use postage::{stream::Stream as _, watch};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use task::SpawnInTerminal;
+use util::ResultExt as _;
+
+/// The id under which build diagnostics are stored next to those of language
+/// servers. Language server ids count up from zero, so no server gets it.
+pub const DOTNET_BUILD_DIAGNOSTICS_ID: LanguageServerId = LanguageServerId(usize::MAX - 1);
+
+/// The source shown with build diagnostics.
+pub const DOTNET_BUILD_DIAGNOSTICS_SOURCE: &str = "dotnet build";
+
+/// `dotnet` commands that run an MSBuild build and accept its logger options.
+const MSBUILD_COMMANDS: &[&str] = &["build", "msbuild", "pack", "publish"];
+
+/// The errors and warnings of the latest .NET build of a project.
+///
+/// Each local [`Project`](crate::Project) has its own: builds run in its
+/// terminals and by its .NET debug locator publish their diagnostics there,
+/// and it shows them alongside language server diagnostics. Each build
+/// replaces the diagnostics of the previous one of the same project, like the
+/// error list of Visual Studio.
+#[derive(Clone)]
+pub struct DotNetBuildDiagnostics {
+    sender: Arc<Mutex<watch::Sender<Arc<[BuildDiagnostic]>>>>,
+    receiver: watch::Receiver<Arc<[BuildDiagnostic]>>,
+}
+
+impl Default for DotNetBuildDiagnostics {
+    fn default() -> Self {
+        let (sender, receiver) = watch::channel_with(Arc::from([]));
+        Self {
+            sender: Arc::new(Mutex::new(sender)),
+            receiver,
+        }
+    }
+}
+
+impl DotNetBuildDiagnostics {
+    /// Replaces the diagnostics of the previous build with those of a build
+    /// that just finished.
+    pub fn publish(&self, diagnostics: Vec<BuildDiagnostic>) {
+        *self.sender.lock().borrow_mut() = diagnostics.into();
+    }
+
+    /// Keeps the diagnostics of `lsp_store` up to date with the latest build,
+    /// for as long as it exists.
+    pub(crate) fn maintain_diagnostics(
+        &self,
+        lsp_store: WeakEntity<LspStore>,
+        cx: &mut App,
+    ) -> Task<()> {
+        let mut updates = self.receiver.clone();
+        cx.spawn(async move |cx| {
+            let mut shown_paths = HashSet::default();
+            while let Some(diagnostics) = updates.recv().await {
+                let updated = lsp_store.update(cx, |lsp_store, cx| {
+                    show_build_diagnostics(lsp_store, &diagnostics, &mut shown_paths, cx)
+                });
+                if updated.is_err() {
+                    break;
+                }
+            }
+        })
+    }
+}
+
+/// Replaces the build diagnostics shown in `lsp_store`. Files that had
+/// diagnostics in `shown_paths` but none now are cleared.
+fn show_build_diagnostics(
+    lsp_store: &mut LspStore,
+    diagnostics: &[BuildDiagnostic],
+    shown_paths: &mut HashSet<PathBuf>,
+    cx: &mut Context<LspStore>,
+) {
+    let worktree_store = lsp_store.worktree_store();
+    let mut diagnostics_by_path = shown_paths
+        .drain()
+        .map(|path| (path, Vec::new()))
+        .collect::<BTreeMap<_, _>>();
+    for diagnostic in diagnostics {
+        if worktree_store
+            .read(cx)
+            .find_worktree(&diagnostic.path, cx)
+            .is_none()
+        {
+            continue;
+        }
+        let entries = diagnostics_by_path
+            .entry(diagnostic.path.clone())
+            .or_default();
+        entries.push(diagnostic_entry(diagnostic, entries.len()));
+    }
+
+    for (path, entries) in diagnostics_by_path {
+        if !entries.is_empty() {
+            shown_paths.insert(path.clone());
+        }
+        lsp_store
+            .update_diagnostic_entries(DOTNET_BUILD_DIAGNOSTICS_ID, path, None, None, entries, cx)
+            .log_err();
+    }
+}
+
+fn diagnostic_entry(
+    diagnostic: &BuildDiagnostic,
+    group_id: usize,
+) -> DiagnosticEntry<Unclipped<PointUtf16>> {
+    let point =
+        |position: BuildPosition| Unclipped(PointUtf16::new(position.line, position.column));
+    DiagnosticEntry {
+        range: point(diagnostic.start)..point(diagnostic.end),
+        diagnostic: Diagnostic {
+            source: Some(DOTNET_BUILD_DIAGNOSTICS_SOURCE.to_string()),
+            source_kind: DiagnosticSourceKind::Other,
+            code: diagnostic.code.clone().map(NumberOrString::String),
+            severity: match diagnostic.severity {
+                BuildDiagnosticSeverity::Error => DiagnosticSeverity::ERROR,
+                BuildDiagnosticSeverity::Warning => DiagnosticSeverity::WARNING,
+                BuildDiagnosticSeverity::Info => DiagnosticSeverity::INFORMATION,
+            },
+            message: diagnostic.message.clone(),
+            group_id,
+            is_primary: true,
+            // Positions refer to the files as they were built, so that unsaved
+            // edits shift them
+            is_disk_based: true,
+            ..Diagnostic::default()
+        },
+    }
+}
+
+/// An MSBuild file logger added to a build run in a terminal, whose output
+/// cannot be read otherwise.
+pub struct DotNetBuildLog {
+    path: tempfile::TempPath,
+    working_dir: PathBuf,
+}
+
+impl DotNetBuildLog {
+    /// Makes `spawn_in_terminal` log its diagnostics to a file, if it runs a
+    /// `dotnet` build.
+    pub fn attach(spawn_in_terminal: &mut SpawnInTerminal) -> Option<Self> {
+        let command = spawn_in_terminal.command.as_deref()?;
+        let is_dotnet = Path::new(command)
+            .file_stem()
+            .is_some_and(|stem| stem == "dotnet");
+        let is_build = spawn_in_terminal
+            .args
+            .first()
+            .is_some_and(|subcommand| MSBUILD_COMMANDS.contains(&subcommand.as_str()));
+        if !is_dotnet || !is_build {
+            return None;
+        }
+
+        let path = tempfile::Builder::new()
+            .prefix("dotnet-build-")
+            .suffix(".log")
+            .tempfile()
+            .log_err()?
+            .into_temp_path();
+        spawn_in_terminal.args.extend(file_logger_args(&path));
+        Some(Self {
+            path,
+            working_dir: spawn_in_terminal.cwd.clone().unwrap_or_default(),
+        })
+    }
+
+    /// Publishes the diagnostics of the finished build and removes the log.
+    pub fn publish(self, build_diagnostics: &DotNetBuildDiagnostics) {
+        let Some(output) = std::fs::read_to_string(&self.path).log_err() else {
+            return;
+        };
+        let output = output.trim_start_matches('\u{feff}');
+        build_diagnostics.publish(parse_build_output(output, &self.working_dir));
+    }
+}
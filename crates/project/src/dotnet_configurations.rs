@@ -0,0 +1,124 @@
+use collections::HashMap;
+use dotnet::{EvaluationOptions, SolutionConfiguration, SolutionFile};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::{
+    borrow::Cow,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use task::{TaskVariables, VariableName};
+
+/// Carries the configurations of a project into the context providers of its
+/// tasks, which don't have access to the project itself.
+///
+/// Starts with `_`, so it's swept from the task context once it's built.
+const DOTNET_CONFIGURATIONS_TASK_VARIABLE: VariableName =
+    VariableName::Custom(Cow::Borrowed("_dotnet_configurations"));
+
+/// The build configuration of .NET projects in the active solution
+/// configuration, chosen in the solution explorer.
+///
+/// Each [`Project`](crate::Project) has its own. Task contexts read it to fill
+/// in `$DOTNET_CONFIGURATION` and `$DOTNET_PLATFORM`, which then flow into
+/// builds and debug launches.
+#[derive(Clone, Default)]
+pub struct DotNetConfigurations(Arc<RwLock<HashMap<PathBuf, EvaluationOptions>>>);
+
+#[derive(Serialize, Deserialize)]
+struct SerializedConfiguration {
+    configuration: String,
+    platform: Option<String>,
+}
+
+impl DotNetConfigurations {
+    /// Makes `configuration` the active configuration of every project in `solution`.
+    pub fn set_solution_configuration(
+        &self,
+        solution: &SolutionFile,
+        configuration: &SolutionConfiguration,
+    ) {
+        let mut projects = self.0.write();
+        for project in &solution.projects {
+            projects.insert(
+                solution.project_abs_path(project),
+                solution.evaluation_options(project, configuration),
+            );
+        }
+    }
+
+    /// How to evaluate the project at `project_path`, defaulting to `Debug`
+    /// for projects outside of any open solution.
+    pub fn evaluation_options(&self, project_path: &Path) -> EvaluationOptions {
+        self.0.read().get(project_path).cloned().unwrap_or_default()
+    }
+
+    /// The task variables that hand these configurations to the task context
+    /// providers, read back with [`Self::from_task_variables`].
+    pub fn task_variables(&self) -> TaskVariables {
+        let projects = self.0.read();
+        if projects.is_empty() {
+            return TaskVariables::default();
+        }
+        let serialized = projects
+            .iter()
+            .map(|(path, options)| {
+                (
+                    path.clone(),
+                    SerializedConfiguration {
+                        configuration: options.configuration.clone(),
+                        platform: options.platform.clone(),
+                    },
+                )
+            })
+            .collect::<HashMap<_, _>>();
+        match serde_json::to_string(&serialized) {
+            Ok(value) => TaskVariables::from_iter([(DOTNET_CONFIGURATIONS_TASK_VARIABLE, value)]),
+            Err(error) => {
+                log::error!("Failed to serialize .NET configurations: {error}");
+                TaskVariables::default()
+            }
+        }
+    }
+
+    /// The configurations passed along with [`Self::task_variables`], or
+    /// empty ones if `variables` has none.
+    pub fn from_task_variables(variables: &TaskVariables) -> Self {
+        let Some(value) = variables.get(&DOTNET_CONFIGURATIONS_TASK_VARIABLE) else {
+            return Self::default();
+        };
+        let serialized =
+            match serde_json::from_str::<HashMap<PathBuf, SerializedConfiguration>>(value) {
+                Ok(serialized) => serialized,
+                Err(error) => {
+                    log::error!("Failed to deserialize .NET configurations: {error}");
+                    return Self::default();
+                }
+            };
+        let projects = serialized
+            .into_iter()
+            .map(|(path, configuration)| {
+                (
+                    path,
+                    EvaluationOptions {
+                        configuration: configuration.configuration,
+                        platform: configuration.platform,
+                        ..EvaluationOptions::default()
+                    },
+                )
+            })
+            .collect();
+        Self(Arc::new(RwLock::new(projects)))
+    }
+
+    /// Adds these configurations to `variables`, unless they already carry
+    /// some, as when a collaborator asks for the context of a task.
+    pub(crate) fn add_task_variables(&self, variables: &mut TaskVariables) {
+        if variables
+            .get(&DOTNET_CONFIGURATIONS_TASK_VARIABLE)
+            .is_none()
+        {
+            variables.extend(self.task_variables());
+        }
+    }
+}
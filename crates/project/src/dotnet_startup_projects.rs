@@ -0,0 +1,73 @@
+use collections::HashMap;
+use dotnet::{SolutionFile, StartupAction, StartupProjects};
+use gpui::{App, SharedString};
+use language::{LanguageName, language_settings::AllLanguageSettings};
+use parking_lot::RwLock;
+use settings::SettingsLocation;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use util::rel_path::RelPath;
+
+use crate::Project;
+
+/// The debug adapter used for .NET when the settings name none.
+const DEFAULT_DOTNET_DEBUG_ADAPTER: &str = "vsdbg";
+
+/// The startup projects of open .NET solutions, chosen in the solution explorer.
+///
+/// Each [`Project`] has its own. Its .NET debug locator reads them to find the
+/// project to launch when a build task names none, such as `dotnet run` in
+/// the solution directory.
+#[derive(Clone, Default)]
+pub struct DotNetStartupProjects(Arc<RwLock<HashMap<PathBuf, Vec<(PathBuf, StartupAction)>>>>);
+
+impl DotNetStartupProjects {
+    /// Replaces the startup projects of `solution`.
+    pub fn set_startup_projects(&self, solution: &SolutionFile, startup: &StartupProjects) {
+        let projects = startup
+            .iter()
+            .filter_map(|startup| {
+                let project = solution.project_by_path(&startup.path)?;
+                Some((solution.project_abs_path(project), startup.action))
+            })
+            .collect();
+        self.0
+            .write()
+            .insert(solution.directory().to_path_buf(), projects);
+    }
+
+    /// The project file to debug for a build run in `dir`: the first project
+    /// started with debugging in the innermost solution containing `dir`.
+    pub fn debug_target(&self, dir: &Path) -> Option<PathBuf> {
+        let solutions = self.0.read();
+        let (_, projects) = solutions
+            .iter()
+            .filter(|(solution_dir, _)| dir.starts_with(solution_dir))
+            .max_by_key(|(solution_dir, _)| solution_dir.components().count())?;
+        projects
+            .iter()
+            .find(|(_, action)| *action == StartupAction::Start)
+            .map(|(path, _)| path.clone())
+    }
+}
+
+/// The debug adapter for .NET code at `path`: the first of the C# `debuggers`
+/// in the settings of its worktree, or vsdbg. Without a path, the user
+/// settings decide.
+pub fn dotnet_debug_adapter(project: &Project, path: Option<&Path>, cx: &App) -> SharedString {
+    let worktree_id = path
+        .and_then(|path| project.find_worktree(path, cx))
+        .map(|(worktree, _)| worktree.read(cx).id());
+    let location = worktree_id.map(|worktree_id| SettingsLocation {
+        worktree_id,
+        path: RelPath::empty(),
+    });
+    AllLanguageSettings::get(location, cx)
+        .language(location, Some(&LanguageName::new_static("CSharp")), cx)
+        .debuggers
+        .first()
+        .map(|adapter| SharedString::from(adapter.clone()))
+        .unwrap_or_else(|| DEFAULT_DOTNET_DEBUG_ADAPTER.into())
+}
@@ -29,15 +29,18 @@ use git::{
     blame::Blame,
     parse_git_remote_url,
     repository::{
-        Branch, CommitDetails, CommitDiff, CommitFile, CommitOptions, DiffType, FetchOptions,
-        GitRepository, GitRepositoryCheckpoint, PushOptions, Remote, RemoteCommandOutput, RepoPath,
-        ResetMode, UpstreamTrackingStatus, Worktree as GitWorktree,
+        Branch, CommitDetails, CommitDiff, CommitFile, CommitLogEntry, CommitLogQuery,
+        CommitOptions, CommitRef, CommitRefKind, DiffType, FetchOptions, GitRepository,
+        GitRepositoryCheckpoint, PushOptions, RebaseControl, RebaseOutcome, RebaseTodoAction,
+        RebaseTodoEntry, Remote, RemoteCommandOutput, RepoPath, ResetMode, SequencerOperation, Tag,
+        UpstreamTrackingStatus, Worktree as GitWorktree,
     },
     stash::{GitStash, StashEntry},
     status::{
         DiffTreeType, FileStatus, GitSummary, StatusCode, TrackedStatus, TreeDiff, TreeDiffStatus,
         UnmergedStatus, UnmergedStatusCode,
     },
+    submodule::{Submodule, SubmoduleOperation, SubmoduleStatus},
 };
 use gpui::{
     App, AppContext, AsyncApp, Context, Entity, EventEmitter, SharedString, Subscription, Task,
@@ -86,6 +89,11 @@ use worktree::{
 };
 use zeroize::Zeroize;
 
+/// The most commits a peer can ask for in one page of the commit log.
+const MAX_COMMIT_LOG_LIMIT: u64 = 1_000;
+/// The furthest into the commit log a peer can ask to start a page.
+const MAX_COMMIT_LOG_SKIP: u64 = 1_000_000;
+
 pub struct GitStore {
     state: GitStoreState,
     buffer_store: Entity<BufferStore>,
@@ -265,6 +273,7 @@ pub struct RepositorySnapshot {
     pub remote_origin_url: Option<String>,
     pub remote_upstream_url: Option<String>,
     pub stash_entries: GitStash,
+    pub submodules: Arc<[Submodule]>,
 }
 
 type JobId = u64;
@@ -366,6 +375,7 @@ pub enum RepositoryEvent {
     MergeHeadsChanged,
     BranchChanged,
     StashEntriesChanged,
+    SubmodulesChanged,
     PendingOpsChanged { pending_ops: SumTree<PendingOps> },
 }
 
@@ -491,6 +501,20 @@ impl GitStore {
         client.add_entity_request_handler(Self::handle_show);
         client.add_entity_request_handler(Self::handle_load_commit_diff);
         client.add_entity_request_handler(Self::handle_file_history);
+        client.add_entity_request_handler(Self::handle_recent_commits);
+        client.add_entity_request_handler(Self::handle_rebase_todo);
+        client.add_entity_request_handler(Self::handle_rebase);
+        client.add_entity_request_handler(Self::handle_rebase_control);
+        client.add_entity_request_handler(Self::handle_cherry_pick);
+        client.add_entity_request_handler(Self::handle_revert);
+        client.add_entity_request_handler(Self::handle_sequencer_control);
+        client.add_entity_request_handler(Self::handle_get_tags);
+        client.add_entity_request_handler(Self::handle_create_tag);
+        client.add_entity_request_handler(Self::handle_delete_tag);
+        client.add_entity_request_handler(Self::handle_push_tag);
+        client.add_entity_request_handler(Self::handle_commit_log);
+        client.add_entity_request_handler(Self::handle_checkout_commit);
+        client.add_entity_request_handler(Self::handle_submodule_operation);
         client.add_entity_request_handler(Self::handle_checkout_files);
         client.add_entity_request_handler(Self::handle_open_commit_message_buffer);
         client.add_entity_request_handler(Self::handle_set_index_text);
@@ -1638,6 +1662,51 @@ impl GitStore {
             .max_by_key(|(repo, _)| repo.read(cx).work_directory_abs_path.clone())
     }
 
+    /// Returns the submodule at `path`, along with the repository it's a submodule of.
+    pub fn submodule_for_project_path(
+        &self,
+        path: &ProjectPath,
+        cx: &App,
+    ) -> Option<(Entity<Repository>, Submodule)> {
+        let abs_path = self.worktree_store.read(cx).absolutize(path, cx)?;
+        self.submodule_for_abs_path(&abs_path, cx)
+    }
+
+    /// Returns the repository that `repository` is a submodule of, along with the submodule.
+    pub fn parent_repository(
+        &self,
+        repository: &Entity<Repository>,
+        cx: &App,
+    ) -> Option<(Entity<Repository>, Submodule)> {
+        self.submodule_for_abs_path(&repository.read(cx).work_directory_abs_path, cx)
+    }
+
+    fn submodule_for_abs_path(
+        &self,
+        abs_path: &Path,
+        cx: &App,
+    ) -> Option<(Entity<Repository>, Submodule)> {
+        self.repositories.values().find_map(|repo| {
+            let repo_path = repo.read(cx).abs_path_to_repo_path(abs_path)?;
+            let submodule = repo.read(cx).submodule(&repo_path)?.clone();
+            Some((repo.clone(), submodule))
+        })
+    }
+
+    /// Returns the repository checked out for a submodule of `repository`, if it's initialized.
+    pub fn submodule_repository(
+        &self,
+        repository: &Entity<Repository>,
+        path: &RepoPath,
+        cx: &App,
+    ) -> Option<Entity<Repository>> {
+        let abs_path = repository.read(cx).repo_path_to_abs_path(path);
+        self.repositories
+            .values()
+            .find(|repo| *repo.read(cx).work_directory_abs_path == *abs_path)
+            .cloned()
+    }
+
     pub fn git_init(
         &self,
         path: Arc<Path>,
@@ -2229,10 +2298,11 @@ impl GitStore {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
         let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
         let branch_name = envelope.payload.branch_name;
+        let base_branch = envelope.payload.base_branch;
 
         repository_handle
             .update(&mut cx, |repository_handle, _| {
-                repository_handle.create_branch(branch_name, None)
+                repository_handle.create_branch(branch_name, base_branch)
             })?
             .await??;
 
@@ -2413,6 +2483,359 @@ impl GitStore {
         })
     }
 
+    async fn handle_recent_commits(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRecentCommits>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitRecentCommitsResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let limit = envelope.payload.limit as usize;
+
+        let commits = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.recent_commits(limit)
+            })?
+            .await??;
+
+        Ok(proto::GitRecentCommitsResponse {
+            commits: commits
+                .into_iter()
+                .map(|commit| proto::GitRecentCommit {
+                    sha: commit.sha.to_string(),
+                    subject: commit.subject.to_string(),
+                    commit_timestamp: commit.commit_timestamp,
+                    author_name: commit.author_name.to_string(),
+                    has_parent: commit.has_parent,
+                })
+                .collect(),
+        })
+    }
+
+    async fn handle_rebase_todo(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRebaseTodo>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitRebaseTodoResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let base = envelope.payload.base;
+
+        let entries = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.rebase_todo(base)
+            })?
+            .await??;
+
+        Ok(proto::GitRebaseTodoResponse {
+            entries: entries.iter().map(rebase_todo_entry_to_proto).collect(),
+        })
+    }
+
+    async fn handle_rebase(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRebase>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitRebaseResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let base = envelope.payload.base;
+        let todo = envelope
+            .payload
+            .todo
+            .iter()
+            .map(proto_to_rebase_todo_entry)
+            .collect();
+
+        let outcome = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.rebase(base, todo)
+            })?
+            .await??;
+
+        Ok(proto::GitRebaseResponse {
+            paused: outcome == RebaseOutcome::Paused,
+        })
+    }
+
+    async fn handle_rebase_control(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRebaseControl>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitRebaseResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let control = match envelope.payload.control() {
+            proto::git_rebase_control::Control::Continue => RebaseControl::Continue,
+            proto::git_rebase_control::Control::Skip => RebaseControl::Skip,
+            proto::git_rebase_control::Control::Abort => RebaseControl::Abort,
+        };
+
+        let outcome = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.rebase_control(control)
+            })?
+            .await??;
+
+        Ok(proto::GitRebaseResponse {
+            paused: outcome == RebaseOutcome::Paused,
+        })
+    }
+
+    async fn handle_cherry_pick(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCherryPick>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let sha = envelope.payload.sha;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.cherry_pick(sha)
+            })?
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_revert(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRevert>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let sha = envelope.payload.sha;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.revert(sha)
+            })?
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_sequencer_control(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitSequencerControl>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let operation = match envelope.payload.operation() {
+            proto::git_sequencer_control::Operation::CherryPick => SequencerOperation::CherryPick,
+            proto::git_sequencer_control::Operation::Revert => SequencerOperation::Revert,
+        };
+        let control = match envelope.payload.control() {
+            proto::git_rebase_control::Control::Continue => RebaseControl::Continue,
+            proto::git_rebase_control::Control::Skip => RebaseControl::Skip,
+            proto::git_rebase_control::Control::Abort => RebaseControl::Abort,
+        };
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.sequencer_control(operation, control)
+            })?
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_get_tags(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitGetTags>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitGetTagsResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let tags = repository_handle
+            .update(&mut cx, |repository_handle, _| repository_handle.tags())?
+            .await??;
+
+        Ok(proto::GitGetTagsResponse {
+            tags: tags
+                .into_iter()
+                .map(|tag| proto::GitTag {
+                    name: tag.name.to_string(),
+                    sha: tag.sha.to_string(),
+                    subject: tag.subject.to_string(),
+                    creation_timestamp: tag.creation_timestamp,
+                })
+                .collect(),
+        })
+    }
+
+    async fn handle_create_tag(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCreateTag>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let name = envelope.payload.name;
+        let target = envelope.payload.target;
+        let message = envelope.payload.message;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.create_tag(name, target, message)
+            })?
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_delete_tag(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitDeleteTag>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let name = envelope.payload.name;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.delete_tag(name)
+            })?
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_push_tag(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitPushTag>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::RemoteMessageResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let askpass_id = envelope.payload.askpass_id;
+        let askpass = make_remote_delegate(
+            this,
+            envelope.payload.project_id,
+            repository_id,
+            askpass_id,
+            &mut cx,
+        );
+
+        let name = envelope.payload.name.into();
+        let remote_name = envelope.payload.remote_name.into();
+
+        let remote_output = repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.push_tag(name, remote_name, askpass, cx)
+            })?
+            .await??;
+        Ok(proto::RemoteMessageResponse {
+            stdout: remote_output.stdout,
+            stderr: remote_output.stderr,
+        })
+    }
+
+    async fn handle_commit_log(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCommitLog>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitCommitLogResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        anyhow::ensure!(
+            envelope.payload.limit <= MAX_COMMIT_LOG_LIMIT,
+            "commit log limit {} is over {MAX_COMMIT_LOG_LIMIT}",
+            envelope.payload.limit
+        );
+        anyhow::ensure!(
+            envelope.payload.skip <= MAX_COMMIT_LOG_SKIP,
+            "commit log skip {} is over {MAX_COMMIT_LOG_SKIP}",
+            envelope.payload.skip
+        );
+        let query = CommitLogQuery {
+            skip: envelope.payload.skip as usize,
+            limit: envelope.payload.limit as usize,
+            all_refs: envelope.payload.all_refs,
+            author: envelope.payload.author,
+            message: envelope.payload.message,
+            path: envelope
+                .payload
+                .path
+                .as_deref()
+                .map(RepoPath::from_proto)
+                .transpose()?,
+            revision_range: envelope.payload.revision_range,
+        };
+
+        let entries = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.commit_log(query)
+            })?
+            .await??;
+
+        Ok(proto::GitCommitLogResponse {
+            entries: entries.iter().map(commit_log_entry_to_proto).collect(),
+        })
+    }
+
+    async fn handle_checkout_commit(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCheckoutCommit>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.checkout_commit(envelope.payload.commit)
+            })?
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_submodule_operation(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitSubmoduleOperation>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::RemoteMessageResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let operation = match envelope.payload.operation() {
+            proto::git_submodule_operation::Operation::Init => SubmoduleOperation::Init,
+            proto::git_submodule_operation::Operation::Update => SubmoduleOperation::Update,
+            proto::git_submodule_operation::Operation::Sync => SubmoduleOperation::Sync,
+        };
+        let paths = envelope
+            .payload
+            .paths
+            .iter()
+            .map(|path| RepoPath::from_proto(path))
+            .collect::<Result<Vec<_>>>()?;
+
+        let askpass_id = envelope.payload.askpass_id;
+        let askpass = make_remote_delegate(
+            this,
+            envelope.payload.project_id,
+            repository_id,
+            askpass_id,
+            &mut cx,
+        );
+
+        let remote_output = repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.submodule_operation(operation, paths, askpass, cx)
+            })?
+            .await??;
+        Ok(proto::RemoteMessageResponse {
+            stdout: remote_output.stdout,
+            stderr: remote_output.stderr,
+        })
+    }
+
     async fn handle_reset(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::GitReset>,
@@ -3263,6 +3686,7 @@ impl RepositorySnapshot {
             remote_origin_url: None,
             remote_upstream_url: None,
             stash_entries: Default::default(),
+            submodules: Arc::default(),
             path_style,
         }
     }
@@ -3298,6 +3722,7 @@ impl RepositorySnapshot {
                 .collect(),
             remote_upstream_url: self.remote_upstream_url.clone(),
             remote_origin_url: self.remote_origin_url.clone(),
+            submodules: self.submodules.iter().map(submodule_to_proto).collect(),
         }
     }
 
@@ -3369,6 +3794,7 @@ impl RepositorySnapshot {
                 .collect(),
             remote_upstream_url: self.remote_upstream_url.clone(),
             remote_origin_url: self.remote_origin_url.clone(),
+            submodules: self.submodules.iter().map(submodule_to_proto).collect(),
         }
     }
 
@@ -3386,6 +3812,12 @@ impl RepositorySnapshot {
             .cloned()
     }
 
+    pub fn submodule(&self, path: &RepoPath) -> Option<&Submodule> {
+        self.submodules
+            .iter()
+            .find(|submodule| &submodule.path == path)
+    }
+
     pub fn abs_path_to_repo_path(&self, abs_path: &Path) -> Option<RepoPath> {
         Self::abs_path_to_repo_path_inner(&self.work_directory_abs_path, abs_path, self.path_style)
     }
@@ -3451,6 +3883,139 @@ pub fn proto_to_stash(entry: &proto::StashEntry) -> Result<StashEntry> {
     })
 }
 
+fn submodule_to_proto(submodule: &Submodule) -> proto::GitSubmodule {
+    let status = match submodule.status {
+        SubmoduleStatus::UpToDate => proto::git_submodule::Status::UpToDate,
+        SubmoduleStatus::Uninitialized => proto::git_submodule::Status::Uninitialized,
+        SubmoduleStatus::OutOfDate => proto::git_submodule::Status::OutOfDate,
+        SubmoduleStatus::Conflicted => proto::git_submodule::Status::Conflicted,
+    };
+    proto::GitSubmodule {
+        path: submodule.path.to_proto(),
+        status: status as i32,
+        is_dirty: submodule.is_dirty,
+        sha: submodule.sha.as_bytes().to_vec(),
+        head_sha: submodule.head_sha.map(|sha| sha.as_bytes().to_vec()),
+    }
+}
+
+fn proto_to_submodule(submodule: &proto::GitSubmodule) -> Result<Submodule> {
+    let status = match submodule.status() {
+        proto::git_submodule::Status::UpToDate => SubmoduleStatus::UpToDate,
+        proto::git_submodule::Status::Uninitialized => SubmoduleStatus::Uninitialized,
+        proto::git_submodule::Status::OutOfDate => SubmoduleStatus::OutOfDate,
+        proto::git_submodule::Status::Conflicted => SubmoduleStatus::Conflicted,
+    };
+    Ok(Submodule {
+        path: RepoPath::from_proto(&submodule.path)?,
+        status,
+        is_dirty: submodule.is_dirty,
+        sha: Oid::from_bytes(&submodule.sha)?,
+        head_sha: submodule
+            .head_sha
+            .as_deref()
+            .map(Oid::from_bytes)
+            .transpose()?,
+    })
+}
+
+fn rebase_todo_entry_to_proto(entry: &RebaseTodoEntry) -> proto::RebaseTodoEntry {
+    let action = match entry.action {
+        RebaseTodoAction::Pick => proto::rebase_todo_entry::Action::Pick,
+        RebaseTodoAction::Reword => proto::rebase_todo_entry::Action::Reword,
+        RebaseTodoAction::Edit => proto::rebase_todo_entry::Action::Edit,
+        RebaseTodoAction::Squash => proto::rebase_todo_entry::Action::Squash,
+        RebaseTodoAction::Fixup => proto::rebase_todo_entry::Action::Fixup,
+        RebaseTodoAction::Drop => proto::rebase_todo_entry::Action::Drop,
+    };
+    proto::RebaseTodoEntry {
+        action: action as i32,
+        sha: entry.sha.to_string(),
+        subject: entry.subject.to_string(),
+        message: entry.message.as_ref().map(ToString::to_string),
+    }
+}
+
+fn proto_to_rebase_todo_entry(entry: &proto::RebaseTodoEntry) -> RebaseTodoEntry {
+    let action = match entry.action() {
+        proto::rebase_todo_entry::Action::Pick => RebaseTodoAction::Pick,
+        proto::rebase_todo_entry::Action::Reword => RebaseTodoAction::Reword,
+        proto::rebase_todo_entry::Action::Edit => RebaseTodoAction::Edit,
+        proto::rebase_todo_entry::Action::Squash => RebaseTodoAction::Squash,
+        proto::rebase_todo_entry::Action::Fixup => RebaseTodoAction::Fixup,
+        proto::rebase_todo_entry::Action::Drop => RebaseTodoAction::Drop,
+    };
+    RebaseTodoEntry {
+        action,
+        sha: entry.sha.clone().into(),
+        subject: entry.subject.clone().into(),
+        message: entry.message.clone().map(SharedString::from),
+    }
+}
+
+fn rebase_outcome_from_proto(response: &proto::GitRebaseResponse) -> RebaseOutcome {
+    if response.paused {
+        RebaseOutcome::Paused
+    } else {
+        RebaseOutcome::Finished
+    }
+}
+
+fn commit_log_entry_to_proto(entry: &CommitLogEntry) -> proto::GitCommitLogEntry {
+    proto::GitCommitLogEntry {
+        sha: entry.sha.to_string(),
+        parents: entry.parents.iter().map(ToString::to_string).collect(),
+        subject: entry.subject.to_string(),
+        author_name: entry.author_name.to_string(),
+        author_email: entry.author_email.to_string(),
+        commit_timestamp: entry.commit_timestamp,
+        refs: entry
+            .refs
+            .iter()
+            .map(|commit_ref| {
+                let kind = match commit_ref.kind {
+                    CommitRefKind::Head => proto::git_commit_ref::Kind::Head,
+                    CommitRefKind::LocalBranch => proto::git_commit_ref::Kind::LocalBranch,
+                    CommitRefKind::RemoteBranch => proto::git_commit_ref::Kind::RemoteBranch,
+                    CommitRefKind::Tag => proto::git_commit_ref::Kind::Tag,
+                };
+                proto::GitCommitRef {
+                    kind: kind as i32,
+                    name: commit_ref.name.to_string(),
+                }
+            })
+            .collect(),
+    }
+}
+
+fn proto_to_commit_log_entry(entry: proto::GitCommitLogEntry) -> CommitLogEntry {
+    let refs = entry
+        .refs
+        .iter()
+        .map(|commit_ref| {
+            let kind = match commit_ref.kind() {
+                proto::git_commit_ref::Kind::Head => CommitRefKind::Head,
+                proto::git_commit_ref::Kind::LocalBranch => CommitRefKind::LocalBranch,
+                proto::git_commit_ref::Kind::RemoteBranch => CommitRefKind::RemoteBranch,
+                proto::git_commit_ref::Kind::Tag => CommitRefKind::Tag,
+            };
+            CommitRef {
+                kind,
+                name: commit_ref.name.clone().into(),
+            }
+        })
+        .collect();
+    CommitLogEntry {
+        sha: entry.sha.into(),
+        parents: entry.parents.into_iter().map(SharedString::from).collect(),
+        subject: entry.subject.into(),
+        author_name: entry.author_name.into(),
+        author_email: entry.author_email.into(),
+        commit_timestamp: entry.commit_timestamp,
+        refs,
+    }
+}
+
 impl MergeDetails {
     async fn load(
         backend: &Arc<dyn GitRepository>,
@@ -4168,6 +4733,503 @@ impl Repository {
         })
     }
 
+    pub fn recent_commits(
+        &mut self,
+        limit: usize,
+    ) -> oneshot::Receiver<Result<Vec<git::repository::CommitSummary>>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.recent_commits(limit).await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { client, project_id }) => {
+                    let response = client
+                        .request(proto::GitRecentCommits {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            limit: limit as u64,
+                        })
+                        .await?;
+                    Ok(response
+                        .commits
+                        .into_iter()
+                        .map(|commit| git::repository::CommitSummary {
+                            sha: commit.sha.into(),
+                            subject: commit.subject.into(),
+                            commit_timestamp: commit.commit_timestamp,
+                            author_name: commit.author_name.into(),
+                            has_parent: commit.has_parent,
+                        })
+                        .collect())
+                }
+            }
+        })
+    }
+
+    pub fn rebase_todo(&mut self, base: String) -> oneshot::Receiver<Result<Vec<RebaseTodoEntry>>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.rebase_todo(base).await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { client, project_id }) => {
+                    let response = client
+                        .request(proto::GitRebaseTodo {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            base,
+                        })
+                        .await?;
+                    Ok(response
+                        .entries
+                        .iter()
+                        .map(proto_to_rebase_todo_entry)
+                        .collect())
+                }
+            }
+        })
+    }
+
+    pub fn rebase(
+        &mut self,
+        base: String,
+        todo: Vec<RebaseTodoEntry>,
+    ) -> oneshot::Receiver<Result<RebaseOutcome>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git rebase --interactive {base}").into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.rebase(base, todo, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { client, project_id }) => {
+                        let response = client
+                            .request(proto::GitRebase {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                base,
+                                todo: todo.iter().map(rebase_todo_entry_to_proto).collect(),
+                            })
+                            .await?;
+                        Ok(rebase_outcome_from_proto(&response))
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn rebase_control(
+        &mut self,
+        control: RebaseControl,
+    ) -> oneshot::Receiver<Result<RebaseOutcome>> {
+        let id = self.id;
+        let status = match control {
+            RebaseControl::Continue => "git rebase --continue",
+            RebaseControl::Skip => "git rebase --skip",
+            RebaseControl::Abort => "git rebase --abort",
+        };
+        self.send_job(Some(status.into()), move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState {
+                    backend,
+                    environment,
+                    ..
+                }) => backend.rebase_control(control, environment).await,
+                RepositoryState::Remote(RemoteRepositoryState { client, project_id }) => {
+                    let control = match control {
+                        RebaseControl::Continue => proto::git_rebase_control::Control::Continue,
+                        RebaseControl::Skip => proto::git_rebase_control::Control::Skip,
+                        RebaseControl::Abort => proto::git_rebase_control::Control::Abort,
+                    };
+                    let response = client
+                        .request(proto::GitRebaseControl {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            control: control as i32,
+                        })
+                        .await?;
+                    Ok(rebase_outcome_from_proto(&response))
+                }
+            }
+        })
+    }
+
+    pub fn cherry_pick(&mut self, sha: String) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git cherry-pick {sha}").into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.cherry_pick(sha, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { client, project_id }) => {
+                        client
+                            .request(proto::GitCherryPick {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                sha,
+                            })
+                            .await?;
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn revert(&mut self, sha: String) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git revert {sha}").into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.revert(sha, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { client, project_id }) => {
+                        client
+                            .request(proto::GitRevert {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                sha,
+                            })
+                            .await?;
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    /// Continues, skips or aborts a cherry-pick or revert that stopped on conflicts.
+    pub fn sequencer_control(
+        &mut self,
+        operation: SequencerOperation,
+        control: RebaseControl,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        let flag = match control {
+            RebaseControl::Continue => "--continue",
+            RebaseControl::Skip => "--skip",
+            RebaseControl::Abort => "--abort",
+        };
+        self.send_job(
+            Some(format!("git {} {flag}", operation.command()).into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => {
+                        backend
+                            .sequencer_control(operation, control, environment)
+                            .await
+                    }
+                    RepositoryState::Remote(RemoteRepositoryState { client, project_id }) => {
+                        let operation = match operation {
+                            SequencerOperation::CherryPick => {
+                                proto::git_sequencer_control::Operation::CherryPick
+                            }
+                            SequencerOperation::Revert => {
+                                proto::git_sequencer_control::Operation::Revert
+                            }
+                        };
+                        let control = match control {
+                            RebaseControl::Continue => proto::git_rebase_control::Control::Continue,
+                            RebaseControl::Skip => proto::git_rebase_control::Control::Skip,
+                            RebaseControl::Abort => proto::git_rebase_control::Control::Abort,
+                        };
+                        client
+                            .request(proto::GitSequencerControl {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                operation: operation as i32,
+                                control: control as i32,
+                            })
+                            .await?;
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn tags(&mut self) -> oneshot::Receiver<Result<Vec<Tag>>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.tags().await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { client, project_id }) => {
+                    let response = client
+                        .request(proto::GitGetTags {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                        })
+                        .await?;
+                    Ok(response
+                        .tags
+                        .into_iter()
+                        .map(|tag| Tag {
+                            name: tag.name.into(),
+                            sha: tag.sha.into(),
+                            subject: tag.subject.into(),
+                            creation_timestamp: tag.creation_timestamp,
+                        })
+                        .collect())
+                }
+            }
+        })
+    }
+
+    pub fn create_tag(
+        &mut self,
+        name: String,
+        target: Option<String>,
+        message: Option<String>,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git tag {name}").into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.create_tag(name, target, message, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { client, project_id }) => {
+                        client
+                            .request(proto::GitCreateTag {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                name,
+                                target,
+                                message,
+                            })
+                            .await?;
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn delete_tag(&mut self, name: String) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git tag -d {name}").into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                        backend.delete_tag(name).await
+                    }
+                    RepositoryState::Remote(RemoteRepositoryState { client, project_id }) => {
+                        client
+                            .request(proto::GitDeleteTag {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                name,
+                            })
+                            .await?;
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn push_tag(
+        &mut self,
+        name: SharedString,
+        remote: SharedString,
+        askpass: AskPassDelegate,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
+        let askpass_delegates = self.askpass_delegates.clone();
+        let askpass_id = util::post_inc(&mut self.latest_askpass_id);
+        let id = self.id;
+
+        self.send_job(
+            Some(format!("git push {remote} {name}").into()),
+            move |git_repo, cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => {
+                        backend
+                            .push_tag(
+                                name.to_string(),
+                                remote.to_string(),
+                                askpass,
+                                environment.clone(),
+                                cx,
+                            )
+                            .await
+                    }
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        askpass_delegates.lock().insert(askpass_id, askpass);
+                        let _defer = util::defer(|| {
+                            let askpass_delegate = askpass_delegates.lock().remove(&askpass_id);
+                            debug_assert!(askpass_delegate.is_some());
+                        });
+                        let response = client
+                            .request(proto::GitPushTag {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                name: name.to_string(),
+                                remote_name: remote.to_string(),
+                                askpass_id,
+                            })
+                            .await
+                            .context("sending push tag request")?;
+
+                        Ok(RemoteCommandOutput {
+                            stdout: response.stdout,
+                            stderr: response.stderr,
+                        })
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn commit_log(
+        &mut self,
+        query: CommitLogQuery,
+    ) -> oneshot::Receiver<Result<Vec<CommitLogEntry>>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.commit_log(query).await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { client, project_id }) => {
+                    let response = client
+                        .request(proto::GitCommitLog {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            skip: query.skip as u64,
+                            limit: query.limit as u64,
+                            all_refs: query.all_refs,
+                            author: query.author,
+                            message: query.message,
+                            path: query.path.map(|path| path.to_proto()),
+                            revision_range: query.revision_range,
+                        })
+                        .await?;
+                    Ok(response
+                        .entries
+                        .into_iter()
+                        .map(proto_to_commit_log_entry)
+                        .collect())
+                }
+            }
+        })
+    }
+
+    pub fn checkout_commit(&mut self, commit: String) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git switch --detach {commit}").into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.checkout_commit(commit, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { client, project_id }) => {
+                        client
+                            .request(proto::GitCheckoutCommit {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                commit,
+                            })
+                            .await?;
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn submodule_operation(
+        &mut self,
+        operation: SubmoduleOperation,
+        paths: Vec<RepoPath>,
+        askpass: AskPassDelegate,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
+        let askpass_delegates = self.askpass_delegates.clone();
+        let askpass_id = util::post_inc(&mut self.latest_askpass_id);
+        let id = self.id;
+
+        self.send_job(
+            Some(format!("git submodule {}", operation.name()).into()),
+            move |git_repo, cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => {
+                        backend
+                            .submodule_operation(operation, paths, askpass, environment.clone(), cx)
+                            .await
+                    }
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        askpass_delegates.lock().insert(askpass_id, askpass);
+                        let _defer = util::defer(|| {
+                            let askpass_delegate = askpass_delegates.lock().remove(&askpass_id);
+                            debug_assert!(askpass_delegate.is_some());
+                        });
+                        let operation = match operation {
+                            SubmoduleOperation::Init => {
+                                proto::git_submodule_operation::Operation::Init
+                            }
+                            SubmoduleOperation::Update => {
+                                proto::git_submodule_operation::Operation::Update
+                            }
+                            SubmoduleOperation::Sync => {
+                                proto::git_submodule_operation::Operation::Sync
+                            }
+                        };
+                        let response = client
+                            .request(proto::GitSubmoduleOperation {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                operation: operation as i32,
+                                paths: paths.iter().map(|path| path.to_proto()).collect(),
+                                askpass_id,
+                            })
+                            .await
+                            .context("sending submodule request")?;
+
+                        Ok(RemoteCommandOutput {
+                            stdout: response.stdout,
+                            stderr: response.stderr,
+                        })
+                    }
+                }
+            },
+        )
+    }
+
     fn buffer_store(&self, cx: &App) -> Option<Entity<BufferStore>> {
         Some(self.git_store.upgrade()?.read(cx).buffer_store.clone())
     }
@@ -5227,6 +6289,7 @@ impl Repository {
                             project_id: project_id.0,
                             repository_id: id.to_proto(),
                             branch_name,
+                            base_branch,
                         })
                         .await?;
 
@@ -5397,6 +6460,15 @@ impl Repository {
             cx.emit(RepositoryEvent::StashEntriesChanged)
         }
         self.snapshot.stash_entries = new_stash_entries;
+        let new_submodules = update
+            .submodules
+            .iter()
+            .filter_map(|submodule| proto_to_submodule(submodule).log_err())
+            .collect::<Arc<[_]>>();
+        if self.snapshot.submodules != new_submodules {
+            cx.emit(RepositoryEvent::SubmodulesChanged)
+        }
+        self.snapshot.submodules = new_submodules;
         self.snapshot.remote_upstream_url = update.remote_upstream_url;
         self.snapshot.remote_origin_url = update.remote_origin_url;
 
@@ -5735,6 +6807,7 @@ impl Repository {
                 }
                 let statuses = backend.status(&paths).await?;
                 let stash_entries = backend.stash_entries().await?;
+                let submodules = Arc::<[Submodule]>::from(backend.submodules().await?);
 
                 let changed_path_statuses = cx
                     .background_spawn(async move {
@@ -5772,6 +6845,11 @@ impl Repository {
                         this.snapshot.stash_entries = stash_entries;
                     }
 
+                    if this.snapshot.submodules != submodules {
+                        cx.emit(RepositoryEvent::SubmodulesChanged);
+                        this.snapshot.submodules = submodules;
+                    }
+
                     if !changed_path_statuses.is_empty() {
                         cx.emit(RepositoryEvent::StatusesChanged);
                         this.snapshot
@@ -6111,6 +7189,7 @@ async fn compute_snapshot(
         )])
         .await?;
     let stash_entries = backend.stash_entries().await?;
+    let submodules = Arc::<[Submodule]>::from(backend.submodules().await?);
     let statuses_by_path = SumTree::from_iter(
         statuses
             .entries
@@ -6133,6 +7212,10 @@ async fn compute_snapshot(
         events.push(RepositoryEvent::StatusesChanged)
     }
 
+    if submodules != prev_snapshot.submodules {
+        events.push(RepositoryEvent::SubmodulesChanged)
+    }
+
     // Useful when branch is None in detached head state
     let head_commit = match backend.head_sha().await {
         Some(head_sha) => backend.show(head_sha).await.log_err(),
@@ -6158,6 +7241,7 @@ async fn compute_snapshot(
         remote_origin_url,
         remote_upstream_url,
         stash_entries,
+        submodules,
     };
 
     Ok((snapshot, events))
@@ -24,13 +24,24 @@ use crate::{
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum DiffBase {
     Head,
-    Merge { base_ref: SharedString },
+    Merge {
+        base_ref: SharedString,
+    },
+    /// Changes between a commit and the working tree.
+    Commit {
+        sha: SharedString,
+    },
 }
 
 impl DiffBase {
     pub fn is_merge_base(&self) -> bool {
         matches!(self, DiffBase::Merge { .. })
     }
+
+    /// Whether the diff is against a commit rather than against `HEAD`.
+    pub fn is_commit_base(&self) -> bool {
+        !matches!(self, DiffBase::Head)
+    }
 }
 
 pub struct BranchDiff {
@@ -224,22 +235,22 @@ impl BranchDiff {
         cx: &mut AsyncWindowContext,
     ) -> Result<()> {
         let task = this.update(cx, |this, cx| {
-            let DiffBase::Merge { base_ref } = this.diff_base.clone() else {
-                return None;
+            let diff_type = match this.diff_base.clone() {
+                DiffBase::Head => return None,
+                DiffBase::Merge { base_ref } => DiffTreeType::MergeBase {
+                    base: base_ref,
+                    head: "HEAD".into(),
+                },
+                DiffBase::Commit { sha } => DiffTreeType::Since {
+                    base: sha,
+                    head: "HEAD".into(),
+                },
             };
             let Some(repo) = this.repo.as_ref() else {
                 this.tree_diff.take();
                 return None;
             };
-            repo.update(cx, |repo, cx| {
-                Some(repo.diff_tree(
-                    DiffTreeType::MergeBase {
-                        base: base_ref,
-                        head: "HEAD".into(),
-                    },
-                    cx,
-                ))
-            })
+            repo.update(cx, |repo, cx| Some(repo.diff_tree(diff_type, cx)))
         })?;
         let Some(task) = task else { return Ok(()) };
 
@@ -267,6 +278,10 @@ impl BranchDiff {
 
             for item in repo.read(cx).cached_status() {
                 seen.insert(item.repo_path.clone());
+                // Submodules are directories, whose changes are browsed as a range of commits.
+                if repo.read(cx).submodule(&item.repo_path).is_some() {
+                    continue;
+                }
                 let branch_diff = self
                     .tree_diff
                     .as_ref()
@@ -298,7 +313,7 @@ impl BranchDiff {
             };
 
             for (path, branch_diff) in tree_diff.entries.iter() {
-                if seen.contains(&path) {
+                if seen.contains(&path) || repo.read(cx).submodule(path).is_some() {
                     continue;
                 }
 
@@ -1,11 +1,13 @@
 mod signature_help;
 
 use crate::{
-    CodeAction, CompletionSource, CoreCompletion, CoreCompletionResponse, DocumentColor,
-    DocumentHighlight, DocumentSymbol, Hover, HoverBlock, HoverBlockKind, InlayHint,
-    InlayHintLabel, InlayHintLabelPart, InlayHintLabelPartTooltip, InlayHintTooltip, Location,
-    LocationLink, LspAction, LspPullDiagnostics, MarkupContent, PrepareRenameResponse,
-    ProjectTransaction, PulledDiagnostics, ResolveState,
+    CallHierarchy, CallHierarchyCall, CallHierarchyDirection, CallHierarchyItem, CodeAction,
+    CompletionSource, CoreCompletion, CoreCompletionResponse, DocumentColor, DocumentHighlight,
+    DocumentSymbol, Hover, HoverBlock, HoverBlockKind, InlayHint, InlayHintLabel,
+    InlayHintLabelPart, InlayHintLabelPartTooltip, InlayHintTooltip, InlineCompletion,
+    InlineCompletionTrigger, Location, LocationLink, LspAction, LspPullDiagnostics, MarkupContent,
+    PrepareRenameResponse, ProjectTransaction, PulledDiagnostics, ResolveState, TypeHierarchy,
+    TypeHierarchyDirection, TypeHierarchyItem,
     lsp_store::{LocalLspStore, LspStore},
 };
 use anyhow::{Context as _, Result};
@@ -17,10 +19,13 @@ use futures::future;
 use gpui::{App, AsyncApp, Entity, SharedString, Task};
 use language::{
     Anchor, Bias, Buffer, BufferSnapshot, CachedLspAdapter, CharKind, CharScopeContext,
-    OffsetRangeExt, PointUtf16, ToOffset, ToPointUtf16, Transaction, Unclipped,
+    OffsetRangeExt, Point, PointUtf16, ToOffset, ToPoint, ToPointUtf16, Transaction, Unclipped,
     language_settings::{InlayHintKind, LanguageSettings, language_settings},
     point_from_lsp, point_to_lsp,
-    proto::{deserialize_anchor, deserialize_version, serialize_anchor, serialize_version},
+    proto::{
+        deserialize_anchor, deserialize_anchor_range, deserialize_version, serialize_anchor,
+        serialize_anchor_range, serialize_version,
+    },
     range_from_lsp, range_to_lsp,
 };
 use lsp::{
@@ -31,6 +36,7 @@ use lsp::{
 };
 use serde_json::Value;
 use signature_help::{lsp_to_proto_signature, proto_to_lsp_signature};
+use snippet::Snippet;
 use std::{
     cmp::Reverse, collections::hash_map, mem, ops::Range, path::Path, str::FromStr, sync::Arc,
 };
@@ -204,6 +210,38 @@ pub(crate) struct GetDocumentHighlights {
 #[derive(Debug, Copy, Clone)]
 pub(crate) struct GetDocumentSymbols;
 
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct GetInlineCompletions {
+    pub position: PointUtf16,
+    pub trigger: InlineCompletionTrigger,
+}
+
+/// Requests the ranges the language server folds, each starting at the end of its first line.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct GetFoldingRanges;
+
+/// Requests the selection ranges around each position, from the innermost to the outermost.
+#[derive(Debug, Clone)]
+pub(crate) struct GetSelectionRanges {
+    pub positions: Vec<PointUtf16>,
+}
+
+/// Prepares the call hierarchy of the symbol at a position, then requests
+/// its incoming or outgoing calls.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct GetCallHierarchy {
+    pub position: PointUtf16,
+    pub direction: CallHierarchyDirection,
+}
+
+/// Prepares the type hierarchy of the type at a position, then requests its
+/// supertypes or subtypes.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct GetTypeHierarchy {
+    pub position: PointUtf16,
+    pub direction: TypeHierarchyDirection,
+}
+
 #[derive(Clone, Debug)]
 pub(crate) struct GetSignatureHelp {
     pub position: PointUtf16,
@@ -1803,6 +1841,1015 @@ impl LspCommand for GetDocumentSymbols {
     }
 }
 
+#[async_trait(?Send)]
+impl LspCommand for GetCallHierarchy {
+    type Response = Option<CallHierarchy>;
+    type LspRequest = lsp::request::CallHierarchyPrepare;
+    type ProtoRequest = proto::GetCallHierarchy;
+
+    fn display_name(&self) -> &str {
+        "Get call hierarchy"
+    }
+
+    fn status(&self) -> Option<String> {
+        Some("Finding calls...".to_owned())
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        match capabilities.server_capabilities.call_hierarchy_provider {
+            Some(lsp::CallHierarchyServerCapability::Simple(supported)) => supported,
+            Some(lsp::CallHierarchyServerCapability::Options(_)) => true,
+            None => false,
+        }
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::CallHierarchyPrepareParams> {
+        Ok(lsp::CallHierarchyPrepareParams {
+            text_document_position_params: make_lsp_text_document_position(path, self.position)?,
+            work_done_progress_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        items: Option<Vec<lsp::CallHierarchyItem>>,
+        lsp_store: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        server_id: LanguageServerId,
+        mut cx: AsyncApp,
+    ) -> Result<Option<CallHierarchy>> {
+        // Several items are only returned for positions with several symbols,
+        // such as a macro expanding to functions, so only the first is shown.
+        let Some(lsp_item) = items.and_then(|items| items.into_iter().next()) else {
+            return Ok(None);
+        };
+        let (_, language_server) =
+            language_server_for_buffer(&lsp_store, &buffer, server_id, &mut cx)?;
+        let lsp_calls = match self.direction {
+            CallHierarchyDirection::Incoming => language_server
+                .request::<lsp::request::CallHierarchyIncomingCalls>(
+                    lsp::CallHierarchyIncomingCallsParams {
+                        item: lsp_item.clone(),
+                        work_done_progress_params: Default::default(),
+                        partial_result_params: Default::default(),
+                    },
+                )
+                .await
+                .into_response()?
+                .unwrap_or_default()
+                .into_iter()
+                .map(|call| (call.from, call.from_ranges))
+                .collect::<Vec<_>>(),
+            CallHierarchyDirection::Outgoing => language_server
+                .request::<lsp::request::CallHierarchyOutgoingCalls>(
+                    lsp::CallHierarchyOutgoingCallsParams {
+                        item: lsp_item.clone(),
+                        work_done_progress_params: Default::default(),
+                        partial_result_params: Default::default(),
+                    },
+                )
+                .await
+                .into_response()?
+                .unwrap_or_default()
+                .into_iter()
+                .map(|call| (call.to, call.from_ranges))
+                .collect::<Vec<_>>(),
+        };
+
+        let item = call_hierarchy_item_from_lsp(lsp_item, &lsp_store, server_id, &mut cx).await?;
+        let mut calls = Vec::with_capacity(lsp_calls.len());
+        for (lsp_call_item, from_ranges) in lsp_calls {
+            let call_item =
+                call_hierarchy_item_from_lsp(lsp_call_item, &lsp_store, server_id, &mut cx).await?;
+            let call_sites_buffer = match self.direction {
+                CallHierarchyDirection::Incoming => call_item.location.buffer.clone(),
+                CallHierarchyDirection::Outgoing => item.location.buffer.clone(),
+            };
+            let call_sites = call_sites_buffer.read_with(&cx, |buffer, _| {
+                from_ranges
+                    .into_iter()
+                    .map(|range| Location {
+                        buffer: call_sites_buffer.clone(),
+                        range: anchor_range_from_lsp(buffer, range),
+                    })
+                    .collect()
+            })?;
+            calls.push(CallHierarchyCall {
+                item: call_item,
+                call_sites,
+            });
+        }
+        Ok(Some(CallHierarchy { item, calls }))
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetCallHierarchy {
+        let direction = match self.direction {
+            CallHierarchyDirection::Incoming => proto::CallHierarchyDirection::Incoming,
+            CallHierarchyDirection::Outgoing => proto::CallHierarchyDirection::Outgoing,
+        };
+        proto::GetCallHierarchy {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            position: Some(serialize_anchor(&buffer.anchor_before(self.position))),
+            version: serialize_version(&buffer.version()),
+            direction: direction as i32,
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetCallHierarchy,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        let position = message
+            .position
+            .and_then(deserialize_anchor)
+            .context("invalid position")?;
+        let direction = match proto::CallHierarchyDirection::from_i32(message.direction) {
+            Some(proto::CallHierarchyDirection::Incoming) => CallHierarchyDirection::Incoming,
+            Some(proto::CallHierarchyDirection::Outgoing) => CallHierarchyDirection::Outgoing,
+            None => anyhow::bail!("invalid call hierarchy direction"),
+        };
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        Ok(Self {
+            position: buffer.read_with(&cx, |buffer, _| position.to_point_utf16(buffer))?,
+            direction,
+        })
+    }
+
+    fn response_to_proto(
+        response: Option<CallHierarchy>,
+        lsp_store: &mut LspStore,
+        peer_id: PeerId,
+        _: &clock::Global,
+        cx: &mut App,
+    ) -> proto::GetCallHierarchyResponse {
+        let Some(hierarchy) = response else {
+            return proto::GetCallHierarchyResponse::default();
+        };
+        let item = call_hierarchy_item_to_proto(hierarchy.item, lsp_store, peer_id, cx);
+        let mut calls = Vec::with_capacity(hierarchy.calls.len());
+        for call in hierarchy.calls {
+            let item = call_hierarchy_item_to_proto(call.item, lsp_store, peer_id, cx);
+            let call_sites = call
+                .call_sites
+                .into_iter()
+                .map(|location| location_to_proto(location, lsp_store, peer_id, cx))
+                .collect();
+            calls.push(proto::CallHierarchyCall {
+                item: Some(item),
+                call_sites,
+            });
+        }
+        proto::GetCallHierarchyResponse {
+            item: Some(item),
+            calls,
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetCallHierarchyResponse,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Option<CallHierarchy>> {
+        let Some(item) = message.item else {
+            return Ok(None);
+        };
+        let item = call_hierarchy_item_from_proto(item, &lsp_store, &mut cx).await?;
+        let mut calls = Vec::with_capacity(message.calls.len());
+        for call in message.calls {
+            let call_item = call.item.context("missing call hierarchy item")?;
+            let call_item = call_hierarchy_item_from_proto(call_item, &lsp_store, &mut cx).await?;
+            let mut call_sites = Vec::with_capacity(call.call_sites.len());
+            for location in call.call_sites {
+                call_sites.push(location_from_proto(location, &lsp_store, &mut cx).await?);
+            }
+            calls.push(CallHierarchyCall {
+                item: call_item,
+                call_sites,
+            });
+        }
+        Ok(Some(CallHierarchy { item, calls }))
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetCallHierarchy) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetTypeHierarchy {
+    type Response = Option<TypeHierarchy>;
+    type LspRequest = lsp::request::TypeHierarchyPrepare;
+    type ProtoRequest = proto::GetTypeHierarchy;
+
+    fn display_name(&self) -> &str {
+        "Get type hierarchy"
+    }
+
+    fn status(&self) -> Option<String> {
+        Some("Finding types...".to_owned())
+    }
+
+    // lsp-types has no `typeHierarchyProvider` in its server capabilities, so
+    // servers are asked regardless, and those without support reply with an error.
+    fn check_capabilities(&self, _: AdapterServerCapabilities) -> bool {
+        true
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::TypeHierarchyPrepareParams> {
+        Ok(lsp::TypeHierarchyPrepareParams {
+            text_document_position_params: make_lsp_text_document_position(path, self.position)?,
+            work_done_progress_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        items: Option<Vec<lsp::TypeHierarchyItem>>,
+        lsp_store: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        server_id: LanguageServerId,
+        mut cx: AsyncApp,
+    ) -> Result<Option<TypeHierarchy>> {
+        let Some(lsp_item) = items.and_then(|items| items.into_iter().next()) else {
+            return Ok(None);
+        };
+        let (_, language_server) =
+            language_server_for_buffer(&lsp_store, &buffer, server_id, &mut cx)?;
+        let lsp_types = match self.direction {
+            TypeHierarchyDirection::Supertypes => language_server
+                .request::<lsp::request::TypeHierarchySupertypes>(
+                    lsp::TypeHierarchySupertypesParams {
+                        item: lsp_item.clone(),
+                        work_done_progress_params: Default::default(),
+                        partial_result_params: Default::default(),
+                    },
+                )
+                .await
+                .into_response()?,
+            TypeHierarchyDirection::Subtypes => language_server
+                .request::<lsp::request::TypeHierarchySubtypes>(lsp::TypeHierarchySubtypesParams {
+                    item: lsp_item.clone(),
+                    work_done_progress_params: Default::default(),
+                    partial_result_params: Default::default(),
+                })
+                .await
+                .into_response()?,
+        };
+
+        let item = type_hierarchy_item_from_lsp(lsp_item, &lsp_store, server_id, &mut cx).await?;
+        let lsp_types = lsp_types.unwrap_or_default();
+        let mut types = Vec::with_capacity(lsp_types.len());
+        for lsp_type in lsp_types {
+            types.push(
+                type_hierarchy_item_from_lsp(lsp_type, &lsp_store, server_id, &mut cx).await?,
+            );
+        }
+        Ok(Some(TypeHierarchy { item, types }))
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetTypeHierarchy {
+        let direction = match self.direction {
+            TypeHierarchyDirection::Supertypes => proto::TypeHierarchyDirection::Supertypes,
+            TypeHierarchyDirection::Subtypes => proto::TypeHierarchyDirection::Subtypes,
+        };
+        proto::GetTypeHierarchy {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            position: Some(serialize_anchor(&buffer.anchor_before(self.position))),
+            version: serialize_version(&buffer.version()),
+            direction: direction as i32,
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetTypeHierarchy,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        let position = message
+            .position
+            .and_then(deserialize_anchor)
+            .context("invalid position")?;
+        let direction = match proto::TypeHierarchyDirection::from_i32(message.direction) {
+            Some(proto::TypeHierarchyDirection::Supertypes) => TypeHierarchyDirection::Supertypes,
+            Some(proto::TypeHierarchyDirection::Subtypes) => TypeHierarchyDirection::Subtypes,
+            None => anyhow::bail!("invalid type hierarchy direction"),
+        };
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        Ok(Self {
+            position: buffer.read_with(&cx, |buffer, _| position.to_point_utf16(buffer))?,
+            direction,
+        })
+    }
+
+    fn response_to_proto(
+        response: Option<TypeHierarchy>,
+        lsp_store: &mut LspStore,
+        peer_id: PeerId,
+        _: &clock::Global,
+        cx: &mut App,
+    ) -> proto::GetTypeHierarchyResponse {
+        let Some(hierarchy) = response else {
+            return proto::GetTypeHierarchyResponse::default();
+        };
+        proto::GetTypeHierarchyResponse {
+            item: Some(type_hierarchy_item_to_proto(
+                hierarchy.item,
+                lsp_store,
+                peer_id,
+                cx,
+            )),
+            types: hierarchy
+                .types
+                .into_iter()
+                .map(|item| type_hierarchy_item_to_proto(item, lsp_store, peer_id, cx))
+                .collect(),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetTypeHierarchyResponse,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Option<TypeHierarchy>> {
+        let Some(item) = message.item else {
+            return Ok(None);
+        };
+        let item = type_hierarchy_item_from_proto(item, &lsp_store, &mut cx).await?;
+        let mut types = Vec::with_capacity(message.types.len());
+        for item in message.types {
+            types.push(type_hierarchy_item_from_proto(item, &lsp_store, &mut cx).await?);
+        }
+        Ok(Some(TypeHierarchy { item, types }))
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetTypeHierarchy) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetFoldingRanges {
+    type Response = Vec<Range<Anchor>>;
+    type LspRequest = lsp::request::FoldingRangeRequest;
+    type ProtoRequest = proto::GetFoldingRanges;
+
+    fn display_name(&self) -> &str {
+        "Get folding ranges"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        capabilities
+            .server_capabilities
+            .folding_range_provider
+            .is_some_and(|capability| match capability {
+                lsp::FoldingRangeProviderCapability::Simple(supported) => supported,
+                _ => true,
+            })
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::FoldingRangeParams> {
+        Ok(lsp::FoldingRangeParams {
+            text_document: make_text_document_identifier(path)?,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        lsp_ranges: Option<Vec<lsp::FoldingRange>>,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        _: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<Range<Anchor>>> {
+        let lsp_ranges = lsp_ranges.unwrap_or_default();
+        buffer.read_with(&cx, |buffer, _| {
+            let max_row = buffer.max_point().row;
+            lsp_ranges
+                .into_iter()
+                .filter(|range| range.start_line < range.end_line && range.end_line <= max_row)
+                .map(|range| {
+                    // Like other editors, keep the first line visible whatever the start character.
+                    let start = Point::new(range.start_line, buffer.line_len(range.start_line));
+                    let end = match range.end_character {
+                        Some(end_character) => buffer
+                            .clip_point_utf16(
+                                Unclipped(PointUtf16::new(range.end_line, end_character)),
+                                Bias::Left,
+                            )
+                            .to_point(buffer),
+                        None => Point::new(range.end_line, buffer.line_len(range.end_line)),
+                    };
+                    buffer.anchor_after(start)..buffer.anchor_before(end)
+                })
+                .collect()
+        })
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetFoldingRanges {
+        proto::GetFoldingRanges {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetFoldingRanges,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        Ok(Self)
+    }
+
+    fn response_to_proto(
+        response: Vec<Range<Anchor>>,
+        _: &mut LspStore,
+        _: PeerId,
+        buffer_version: &clock::Global,
+        _: &mut App,
+    ) -> proto::GetFoldingRangesResponse {
+        proto::GetFoldingRangesResponse {
+            ranges: response.into_iter().map(serialize_anchor_range).collect(),
+            version: serialize_version(buffer_version),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetFoldingRangesResponse,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<Range<Anchor>>> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        message
+            .ranges
+            .into_iter()
+            .map(deserialize_anchor_range)
+            .collect()
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetFoldingRanges) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetSelectionRanges {
+    type Response = Vec<Vec<Range<Anchor>>>;
+    type LspRequest = lsp::request::SelectionRangeRequest;
+    type ProtoRequest = proto::GetSelectionRanges;
+
+    fn display_name(&self) -> &str {
+        "Get selection ranges"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        capabilities
+            .server_capabilities
+            .selection_range_provider
+            .is_some_and(|capability| match capability {
+                lsp::SelectionRangeProviderCapability::Simple(supported) => supported,
+                _ => true,
+            })
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::SelectionRangeParams> {
+        Ok(lsp::SelectionRangeParams {
+            text_document: make_text_document_identifier(path)?,
+            positions: self
+                .positions
+                .iter()
+                .map(|position| point_to_lsp(*position))
+                .collect(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        lsp_selection_ranges: Option<Vec<lsp::SelectionRange>>,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        _: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<Vec<Range<Anchor>>>> {
+        let lsp_selection_ranges = lsp_selection_ranges.unwrap_or_default();
+        buffer.read_with(&cx, |buffer, _| {
+            lsp_selection_ranges
+                .into_iter()
+                .map(|selection_range| {
+                    let mut ranges = Vec::new();
+                    let mut selection_range = Some(&selection_range);
+                    while let Some(range) = selection_range {
+                        ranges.push(anchor_range_from_lsp(buffer, range.range));
+                        selection_range = range.parent.as_deref();
+                    }
+                    ranges
+                })
+                .collect()
+        })
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetSelectionRanges {
+        proto::GetSelectionRanges {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            positions: self
+                .positions
+                .iter()
+                .map(|position| serialize_anchor(&buffer.anchor_before(*position)))
+                .collect(),
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetSelectionRanges,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        let positions = message
+            .positions
+            .into_iter()
+            .map(|position| deserialize_anchor(position).context("invalid position"))
+            .collect::<Result<Vec<_>>>()?;
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        Ok(Self {
+            positions: buffer.read_with(&cx, |buffer, _| {
+                positions
+                    .iter()
+                    .map(|position| position.to_point_utf16(buffer))
+                    .collect()
+            })?,
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<Vec<Range<Anchor>>>,
+        _: &mut LspStore,
+        _: PeerId,
+        buffer_version: &clock::Global,
+        _: &mut App,
+    ) -> proto::GetSelectionRangesResponse {
+        proto::GetSelectionRangesResponse {
+            selection_ranges: response
+                .into_iter()
+                .map(|ranges| proto::SelectionRanges {
+                    ranges: ranges.into_iter().map(serialize_anchor_range).collect(),
+                })
+                .collect(),
+            version: serialize_version(buffer_version),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetSelectionRangesResponse,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<Vec<Range<Anchor>>>> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        message
+            .selection_ranges
+            .into_iter()
+            .map(|selection_ranges| {
+                selection_ranges
+                    .ranges
+                    .into_iter()
+                    .map(deserialize_anchor_range)
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetSelectionRanges) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetInlineCompletions {
+    type Response = Vec<InlineCompletion>;
+    type LspRequest = lsp::request::InlineCompletionRequest;
+    type ProtoRequest = proto::GetInlineCompletions;
+
+    fn display_name(&self) -> &str {
+        "Get inline completions"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        capabilities
+            .server_capabilities
+            .inline_completion_provider
+            .is_some_and(|capability| match capability {
+                OneOf::Left(supported) => supported,
+                OneOf::Right(_options) => true,
+            })
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::InlineCompletionParams> {
+        Ok(lsp::InlineCompletionParams {
+            work_done_progress_params: Default::default(),
+            text_document_position: make_lsp_text_document_position(path, self.position)?,
+            context: lsp::InlineCompletionContext {
+                trigger_kind: match self.trigger {
+                    InlineCompletionTrigger::Invoked => lsp::InlineCompletionTriggerKind::Invoked,
+                    InlineCompletionTrigger::Automatic => {
+                        lsp::InlineCompletionTriggerKind::Automatic
+                    }
+                },
+                selected_completion_info: None,
+            },
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Option<lsp::InlineCompletionResponse>,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        _: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<InlineCompletion>> {
+        let items = match message {
+            Some(lsp::InlineCompletionResponse::Array(items)) => items,
+            Some(lsp::InlineCompletionResponse::List(list)) => list.items,
+            None => return Ok(Vec::new()),
+        };
+        buffer.read_with(&cx, |buffer, _| {
+            let position =
+                buffer.anchor_after(buffer.clip_point_utf16(Unclipped(self.position), Bias::Left));
+            items
+                .into_iter()
+                .map(|item| {
+                    let text = if item.insert_text_format == Some(lsp::InsertTextFormat::SNIPPET) {
+                        Snippet::parse(&item.insert_text)
+                            .map(|snippet| snippet.text)
+                            .unwrap_or(item.insert_text)
+                    } else {
+                        item.insert_text
+                    };
+                    let range = match item.range {
+                        Some(range) => anchor_range_from_lsp(buffer, range),
+                        None => position..position,
+                    };
+                    InlineCompletion { range, text }
+                })
+                .collect()
+        })
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetInlineCompletions {
+        proto::GetInlineCompletions {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            position: Some(serialize_anchor(&buffer.anchor_before(self.position))),
+            invoked: self.trigger == InlineCompletionTrigger::Invoked,
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetInlineCompletions,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        let position = message
+            .position
+            .and_then(deserialize_anchor)
+            .context("invalid position")?;
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        Ok(Self {
+            position: buffer.read_with(&cx, |buffer, _| position.to_point_utf16(buffer))?,
+            trigger: if message.invoked {
+                InlineCompletionTrigger::Invoked
+            } else {
+                InlineCompletionTrigger::Automatic
+            },
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<InlineCompletion>,
+        _: &mut LspStore,
+        _: PeerId,
+        buffer_version: &clock::Global,
+        _: &mut App,
+    ) -> proto::GetInlineCompletionsResponse {
+        proto::GetInlineCompletionsResponse {
+            completions: response
+                .into_iter()
+                .map(|completion| proto::InlineCompletion {
+                    range: Some(serialize_anchor_range(completion.range)),
+                    text: completion.text,
+                })
+                .collect(),
+            version: serialize_version(buffer_version),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetInlineCompletionsResponse,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<InlineCompletion>> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        message
+            .completions
+            .into_iter()
+            .map(|completion| {
+                Ok(InlineCompletion {
+                    range: deserialize_anchor_range(completion.range.context("invalid range")?)?,
+                    text: completion.text,
+                })
+            })
+            .collect()
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetInlineCompletions) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+fn anchor_range_from_lsp(buffer: &Buffer, range: lsp::Range) -> Range<Anchor> {
+    let start = buffer.clip_point_utf16(point_from_lsp(range.start), Bias::Left);
+    let end = buffer.clip_point_utf16(point_from_lsp(range.end), Bias::Left);
+    buffer.anchor_after(start)..buffer.anchor_before(end)
+}
+
+async fn call_hierarchy_item_from_lsp(
+    item: lsp::CallHierarchyItem,
+    lsp_store: &Entity<LspStore>,
+    server_id: LanguageServerId,
+    cx: &mut AsyncApp,
+) -> Result<CallHierarchyItem> {
+    let buffer = lsp_store
+        .update(cx, |lsp_store, cx| {
+            lsp_store.open_local_buffer_via_lsp(item.uri, server_id, cx)
+        })?
+        .await?;
+    let (range, selection_range) = buffer.read_with(cx, |buffer, _| {
+        (
+            anchor_range_from_lsp(buffer, item.range),
+            anchor_range_from_lsp(buffer, item.selection_range),
+        )
+    })?;
+    Ok(CallHierarchyItem {
+        name: item.name,
+        kind: item.kind,
+        detail: item.detail,
+        location: Location { buffer, range },
+        selection_range,
+    })
+}
+
+fn call_hierarchy_item_to_proto(
+    item: CallHierarchyItem,
+    lsp_store: &mut LspStore,
+    peer_id: PeerId,
+    cx: &mut App,
+) -> proto::CallHierarchyItem {
+    proto::CallHierarchyItem {
+        name: item.name,
+        kind: unsafe { mem::transmute::<lsp::SymbolKind, i32>(item.kind) },
+        detail: item.detail,
+        location: Some(location_to_proto(item.location, lsp_store, peer_id, cx)),
+        selection_start: Some(serialize_anchor(&item.selection_range.start)),
+        selection_end: Some(serialize_anchor(&item.selection_range.end)),
+    }
+}
+
+async fn call_hierarchy_item_from_proto(
+    item: proto::CallHierarchyItem,
+    lsp_store: &Entity<LspStore>,
+    cx: &mut AsyncApp,
+) -> Result<CallHierarchyItem> {
+    let location = item.location.context("missing call hierarchy location")?;
+    let location = location_from_proto(location, lsp_store, cx).await?;
+    let selection_start = item
+        .selection_start
+        .and_then(deserialize_anchor)
+        .context("missing selection start")?;
+    let selection_end = item
+        .selection_end
+        .and_then(deserialize_anchor)
+        .context("missing selection end")?;
+    location
+        .buffer
+        .update(cx, |buffer, _| {
+            buffer.wait_for_anchors([selection_start, selection_end])
+        })?
+        .await?;
+    Ok(CallHierarchyItem {
+        name: item.name,
+        kind: unsafe { mem::transmute::<i32, lsp::SymbolKind>(item.kind) },
+        detail: item.detail,
+        location,
+        selection_range: selection_start..selection_end,
+    })
+}
+
+async fn type_hierarchy_item_from_lsp(
+    item: lsp::TypeHierarchyItem,
+    lsp_store: &Entity<LspStore>,
+    server_id: LanguageServerId,
+    cx: &mut AsyncApp,
+) -> Result<TypeHierarchyItem> {
+    let buffer = lsp_store
+        .update(cx, |lsp_store, cx| {
+            lsp_store.open_local_buffer_via_lsp(item.uri, server_id, cx)
+        })?
+        .await?;
+    let (range, selection_range) = buffer.read_with(cx, |buffer, _| {
+        (
+            anchor_range_from_lsp(buffer, item.range),
+            anchor_range_from_lsp(buffer, item.selection_range),
+        )
+    })?;
+    Ok(TypeHierarchyItem {
+        name: item.name,
+        kind: item.kind,
+        detail: item.detail,
+        location: Location { buffer, range },
+        selection_range,
+    })
+}
+
+fn type_hierarchy_item_to_proto(
+    item: TypeHierarchyItem,
+    lsp_store: &mut LspStore,
+    peer_id: PeerId,
+    cx: &mut App,
+) -> proto::TypeHierarchyItem {
+    proto::TypeHierarchyItem {
+        name: item.name,
+        kind: unsafe { mem::transmute::<lsp::SymbolKind, i32>(item.kind) },
+        detail: item.detail,
+        location: Some(location_to_proto(item.location, lsp_store, peer_id, cx)),
+        selection_start: Some(serialize_anchor(&item.selection_range.start)),
+        selection_end: Some(serialize_anchor(&item.selection_range.end)),
+    }
+}
+
+async fn type_hierarchy_item_from_proto(
+    item: proto::TypeHierarchyItem,
+    lsp_store: &Entity<LspStore>,
+    cx: &mut AsyncApp,
+) -> Result<TypeHierarchyItem> {
+    let location = item.location.context("missing type hierarchy location")?;
+    let location = location_from_proto(location, lsp_store, cx).await?;
+    let selection_start = item
+        .selection_start
+        .and_then(deserialize_anchor)
+        .context("missing selection start")?;
+    let selection_end = item
+        .selection_end
+        .and_then(deserialize_anchor)
+        .context("missing selection end")?;
+    location
+        .buffer
+        .update(cx, |buffer, _| {
+            buffer.wait_for_anchors([selection_start, selection_end])
+        })?
+        .await?;
+    Ok(TypeHierarchyItem {
+        name: item.name,
+        kind: unsafe { mem::transmute::<i32, lsp::SymbolKind>(item.kind) },
+        detail: item.detail,
+        location,
+        selection_range: selection_start..selection_end,
+    })
+}
+
+fn location_to_proto(
+    location: Location,
+    lsp_store: &mut LspStore,
+    peer_id: PeerId,
+    cx: &mut App,
+) -> proto::Location {
+    lsp_store
+        .buffer_store()
+        .update(cx, |buffer_store, cx| {
+            buffer_store.create_buffer_for_peer(&location.buffer, peer_id, cx)
+        })
+        .detach_and_log_err(cx);
+    proto::Location {
+        start: Some(serialize_anchor(&location.range.start)),
+        end: Some(serialize_anchor(&location.range.end)),
+        buffer_id: location.buffer.read(cx).remote_id().into(),
+    }
+}
+
+async fn location_from_proto(
+    location: proto::Location,
+    lsp_store: &Entity<LspStore>,
+    cx: &mut AsyncApp,
+) -> Result<Location> {
+    let buffer_id = BufferId::new(location.buffer_id)?;
+    let buffer = lsp_store
+        .update(cx, |lsp_store, cx| {
+            lsp_store.wait_for_remote_buffer(buffer_id, cx)
+        })?
+        .await?;
+    let start = location
+        .start
+        .and_then(deserialize_anchor)
+        .context("missing location start")?;
+    let end = location
+        .end
+        .and_then(deserialize_anchor)
+        .context("missing location end")?;
+    buffer
+        .update(cx, |buffer, _| buffer.wait_for_anchors([start, end]))?
+        .await?;
+    Ok(Location {
+        buffer,
+        range: start..end,
+    })
+}
+
 #[async_trait(?Send)]
 impl LspCommand for GetSignatureHelp {
     type Response = Option<SignatureHelp>;
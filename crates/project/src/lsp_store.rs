@@ -17,8 +17,10 @@ pub mod rust_analyzer_ext;
 pub mod vue_language_server_ext;
 
 mod inlay_hint_cache;
+mod semantic_tokens;
 
 use self::inlay_hint_cache::BufferInlayHints;
+use self::semantic_tokens::{SemanticTokensData, SemanticTokensResult};
 use crate::{
     CodeAction, ColorPresentation, Completion, CompletionDisplayOptions, CompletionResponse,
     CompletionSource, CoreCompletion, DocumentColor, Hover, InlayHint, InlayId, LocationLink,
@@ -66,7 +68,7 @@ use language::{
     ManifestName, Patch, PointUtf16, TextBufferSnapshot, ToOffset, ToPointUtf16, Toolchain,
     Transaction, Unclipped,
     language_settings::{FormatOnSave, Formatter, LanguageSettings, language_settings},
-    point_to_lsp,
+    point_from_lsp, point_to_lsp,
     proto::{
         deserialize_anchor, deserialize_lsp_edit, deserialize_version, serialize_anchor,
         serialize_lsp_edit, serialize_version,
@@ -131,6 +133,9 @@ use util::{
 pub use fs::*;
 pub use language::Location;
 pub use lsp_store::inlay_hint_cache::{CacheInlayHints, InvalidationStrategy};
+pub use lsp_store::semantic_tokens::{
+    BufferSemanticTokens, SemanticToken, SemanticTokens, SemanticTokensLegend,
+};
 #[cfg(any(test, feature = "test-support"))]
 pub use prettier::FORMAT_SUFFIX as TEST_PRETTIER_FORMAT_SUFFIX;
 pub use worktree::{
@@ -293,6 +298,7 @@ pub struct LocalLspStore {
         LanguageServerId,
         HashMap<Option<SharedString>, HashMap<PathBuf, Option<SharedString>>>,
     >,
+    semantic_tokens_results: HashMap<BufferId, HashMap<LanguageServerId, SemanticTokensResult>>,
 }
 
 impl LocalLspStore {
@@ -960,6 +966,28 @@ impl LocalLspStore {
             })
             .detach();
 
+        language_server
+            .on_request::<lsp::request::SemanticTokensRefresh, _, _>({
+                let this = lsp_store.clone();
+                move |(), cx| {
+                    let this = this.clone();
+                    let mut cx = cx.clone();
+                    async move {
+                        this.update(&mut cx, |this, cx| {
+                            this.invalidate_semantic_tokens(cx);
+                            this.downstream_client.as_ref().map(|(client, project_id)| {
+                                client.send(proto::RefreshSemanticTokens {
+                                    project_id: *project_id,
+                                })
+                            })
+                        })?
+                        .transpose()?;
+                        Ok(())
+                    }
+                }
+            })
+            .detach();
+
         language_server
             .on_request::<lsp::request::WorkspaceDiagnosticRefresh, _, _>({
                 let this = lsp_store.clone();
@@ -2999,28 +3027,8 @@ impl LocalLspStore {
     ) -> Result<ProjectTransaction> {
         let fs = this.read_with(cx, |this, _| this.as_local().unwrap().fs.clone())?;
 
-        let mut operations = Vec::new();
-        if let Some(document_changes) = edit.document_changes {
-            match document_changes {
-                lsp::DocumentChanges::Edits(edits) => {
-                    operations.extend(edits.into_iter().map(lsp::DocumentChangeOperation::Edit))
-                }
-                lsp::DocumentChanges::Operations(ops) => operations = ops,
-            }
-        } else if let Some(changes) = edit.changes {
-            operations.extend(changes.into_iter().map(|(uri, edits)| {
-                lsp::DocumentChangeOperation::Edit(lsp::TextDocumentEdit {
-                    text_document: lsp::OptionalVersionedTextDocumentIdentifier {
-                        uri,
-                        version: None,
-                    },
-                    edits: edits.into_iter().map(Edit::Plain).collect(),
-                })
-            }));
-        }
-
         let mut project_transaction = ProjectTransaction::default();
-        for operation in operations {
+        for operation in workspace_edit_operations(edit) {
             match operation {
                 lsp::DocumentChangeOperation::Op(lsp::ResourceOp::Create(op)) => {
                     let abs_path = op
@@ -3217,6 +3225,161 @@ impl LocalLspStore {
         Ok(project_transaction)
     }
 
+    /// Resolves the operations of a workspace edit against the buffers they edit,
+    /// without applying them.
+    pub(crate) async fn resolve_workspace_edit(
+        this: Entity<LspStore>,
+        edit: lsp::WorkspaceEdit,
+        language_server: Arc<LanguageServer>,
+        cx: &mut AsyncApp,
+    ) -> Result<ResolvedWorkspaceEdit> {
+        let mut resolved = ResolvedWorkspaceEdit::default();
+        for operation in workspace_edit_operations(edit) {
+            match operation {
+                lsp::DocumentChangeOperation::Op(lsp::ResourceOp::Create(op)) => {
+                    let abs_path = op
+                        .uri
+                        .to_file_path()
+                        .map_err(|()| anyhow!("can't convert URI to path"))?;
+                    let options = op
+                        .options
+                        .map(|options| fs::CreateOptions {
+                            overwrite: options.overwrite.unwrap_or(false),
+                            ignore_if_exists: options.ignore_if_exists.unwrap_or(false),
+                        })
+                        .unwrap_or_default();
+                    resolved
+                        .operations
+                        .push(WorkspaceEditOperation::CreateFile {
+                            abs_path,
+                            options,
+                            text: String::new(),
+                        });
+                }
+
+                lsp::DocumentChangeOperation::Op(lsp::ResourceOp::Rename(op)) => {
+                    let old_abs_path = op
+                        .old_uri
+                        .to_file_path()
+                        .map_err(|()| anyhow!("can't convert URI to path"))?;
+                    let new_abs_path = op
+                        .new_uri
+                        .to_file_path()
+                        .map_err(|()| anyhow!("can't convert URI to path"))?;
+                    let options = op.options.as_ref();
+                    resolved
+                        .operations
+                        .push(WorkspaceEditOperation::RenameFile {
+                            old_abs_path,
+                            new_abs_path,
+                            options: fs::RenameOptions {
+                                overwrite: options
+                                    .and_then(|options| options.overwrite)
+                                    .unwrap_or(false),
+                                ignore_if_exists: options
+                                    .and_then(|options| options.ignore_if_exists)
+                                    .unwrap_or(false),
+                                create_parents: true,
+                            },
+                        });
+                }
+
+                lsp::DocumentChangeOperation::Op(lsp::ResourceOp::Delete(op)) => {
+                    let abs_path = op
+                        .uri
+                        .to_file_path()
+                        .map_err(|()| anyhow!("can't convert URI to path"))?;
+                    let options = op
+                        .options
+                        .map(|options| fs::RemoveOptions {
+                            recursive: options.recursive.unwrap_or(false),
+                            ignore_if_not_exists: options.ignore_if_not_exists.unwrap_or(false),
+                        })
+                        .unwrap_or_default();
+                    resolved
+                        .operations
+                        .push(WorkspaceEditOperation::DeleteFile { abs_path, options });
+                }
+
+                lsp::DocumentChangeOperation::Edit(op) => {
+                    let abs_path = op
+                        .text_document
+                        .uri
+                        .to_file_path()
+                        .map_err(|()| anyhow!("can't convert URI to path"))?;
+                    let mut edits = Vec::new();
+                    for edit in op.edits {
+                        let edit = match edit {
+                            Edit::Plain(edit) => edit,
+                            Edit::Annotated(edit) => edit.text_edit,
+                            Edit::Snippet(edit) => {
+                                let Ok(snippet) = Snippet::parse(&edit.snippet.value) else {
+                                    continue;
+                                };
+                                TextEdit {
+                                    range: edit.range,
+                                    new_text: snippet.text,
+                                }
+                            }
+                        };
+                        if !edits.contains(&edit) {
+                            edits.push(edit);
+                        }
+                    }
+
+                    // Files created by this workspace edit have no buffer yet, so their
+                    // edits become the contents they are created with.
+                    if let Some(WorkspaceEditOperation::CreateFile { text, .. }) =
+                        resolved.operations.iter_mut().rev().find(|operation| {
+                            matches!(
+                                operation,
+                                WorkspaceEditOperation::CreateFile { abs_path: created_path, .. }
+                                    if *created_path == abs_path
+                            )
+                        })
+                    {
+                        apply_lsp_edits_to_text(text, edits);
+                        continue;
+                    }
+
+                    // Files renamed by this workspace edit are edited through the buffer
+                    // of their current path, which follows the file once it is renamed.
+                    let (uri, version) = match resolved.renamed_from(&abs_path) {
+                        Some(old_abs_path) => (
+                            lsp::Uri::from_file_path(&old_abs_path)
+                                .map_err(|()| anyhow!("can't convert path to URI"))?,
+                            None,
+                        ),
+                        None => (op.text_document.uri, op.text_document.version),
+                    };
+                    let buffer = this
+                        .update(cx, |this, cx| {
+                            this.open_local_buffer_via_lsp(uri, language_server.server_id(), cx)
+                        })?
+                        .await?;
+                    let edits = this
+                        .update(cx, |this, cx| {
+                            this.as_local_mut().unwrap().edits_from_lsp(
+                                &buffer,
+                                edits,
+                                language_server.server_id(),
+                                version,
+                                cx,
+                            )
+                        })?
+                        .await?;
+                    if !edits.is_empty() {
+                        resolved
+                            .operations
+                            .push(WorkspaceEditOperation::EditBuffer { buffer, edits });
+                    }
+                }
+            }
+        }
+
+        Ok(resolved)
+    }
+
     async fn on_lsp_workspace_edit(
         this: WeakEntity<LspStore>,
         params: lsp::ApplyWorkspaceEditParams,
@@ -3676,6 +3839,7 @@ pub struct BufferLspData {
     buffer_version: Global,
     document_colors: Option<DocumentColorData>,
     code_lens: Option<CodeLensData>,
+    semantic_tokens: Option<SemanticTokensData>,
     inlay_hints: BufferInlayHints,
     lsp_requests: HashMap<LspKey, HashMap<LspRequestId, Task<()>>>,
     chunk_lsp_requests: HashMap<LspKey, HashMap<RowChunk, LspRequestId>>,
@@ -3693,6 +3857,7 @@ impl BufferLspData {
             buffer_version: buffer.read(cx).version(),
             document_colors: None,
             code_lens: None,
+            semantic_tokens: None,
             inlay_hints: BufferInlayHints::new(buffer, cx),
             lsp_requests: HashMap::default(),
             chunk_lsp_requests: HashMap::default(),
@@ -3709,6 +3874,10 @@ impl BufferLspData {
             code_lens.lens.remove(&for_server);
         }
 
+        if let Some(semantic_tokens) = &mut self.semantic_tokens {
+            semantic_tokens.remove_server_data(for_server);
+        }
+
         self.inlay_hints.remove_server_data(for_server);
     }
 
@@ -3761,6 +3930,7 @@ pub enum LspStoreEvent {
         request_id: Option<usize>,
     },
     RefreshCodeLens,
+    RefreshSemanticTokens,
     DiagnosticsUpdated {
         server_id: LanguageServerId,
         paths: Vec<ProjectPath>,
@@ -3840,6 +4010,8 @@ impl LspStore {
         client.add_entity_request_handler(Self::handle_open_buffer_for_symbol);
         client.add_entity_request_handler(Self::handle_refresh_inlay_hints);
         client.add_entity_request_handler(Self::handle_refresh_code_lens);
+        client.add_entity_request_handler(Self::handle_refresh_semantic_tokens);
+        client.add_entity_request_handler(Self::handle_get_semantic_tokens);
         client.add_entity_request_handler(Self::handle_on_type_formatting);
         client.add_entity_request_handler(Self::handle_apply_additional_edits_for_completion);
         client.add_entity_request_handler(Self::handle_register_buffer_with_language_servers);
@@ -3848,6 +4020,11 @@ impl LspStore {
         client.add_entity_request_handler(Self::handle_lsp_get_completions);
         client.add_entity_request_handler(Self::handle_lsp_command::<GetDocumentHighlights>);
         client.add_entity_request_handler(Self::handle_lsp_command::<GetDocumentSymbols>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetCallHierarchy>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetTypeHierarchy>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetFoldingRanges>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetSelectionRanges>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetInlineCompletions>);
         client.add_entity_request_handler(Self::handle_lsp_command::<PrepareRename>);
         client.add_entity_request_handler(Self::handle_lsp_command::<PerformRename>);
         client.add_entity_request_handler(Self::handle_lsp_command::<LinkedEditingRange>);
@@ -3972,6 +4149,7 @@ impl LspStore {
                 buffers_opened_in_servers: HashMap::default(),
                 buffer_pull_diagnostics_result_ids: HashMap::default(),
                 workspace_pull_diagnostics_result_ids: HashMap::default(),
+                semantic_tokens_results: HashMap::default(),
                 watched_manifest_filenames: ManifestProvidersStore::global(cx)
                     .manifest_file_names(),
             }),
@@ -4247,6 +4425,7 @@ impl LspStore {
                         local.registered_buffers.remove(&buffer_id);
 
                         local.buffers_opened_in_servers.remove(&buffer_id);
+                        local.semantic_tokens_results.remove(&buffer_id);
                         if let Some(file) = File::from_dyn(buffer.read(cx).file()).cloned() {
                             local.unregister_old_buffer_from_language_servers(buffer, &file, cx);
 
@@ -4547,6 +4726,23 @@ impl LspStore {
         }
     }
 
+    /// Whether any language server of the buffer provides inline completions.
+    pub fn has_inline_completion_provider(&self, buffer: &Entity<Buffer>, cx: &App) -> bool {
+        self.check_if_capable_for_proto_request(
+            buffer,
+            |capabilities| {
+                capabilities
+                    .inline_completion_provider
+                    .as_ref()
+                    .is_some_and(|capability| match capability {
+                        OneOf::Left(supported) => *supported,
+                        OneOf::Right(_options) => true,
+                    })
+            },
+            cx,
+        )
+    }
+
     fn is_capable_for_proto_request<R>(
         &self,
         buffer: &Entity<Buffer>,
@@ -5440,6 +5636,133 @@ impl LspStore {
         }
     }
 
+    /// Requests the workspace edit renaming the symbol at a position, without applying it.
+    ///
+    /// Returns `None` when the project is remote, as its workspace edits can only be
+    /// applied by the host.
+    pub fn rename_edits<T: ToPointUtf16>(
+        &mut self,
+        buffer: Entity<Buffer>,
+        position: T,
+        new_name: String,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Option<ResolvedWorkspaceEdit>>> {
+        let Some(local) = self.as_local() else {
+            return Task::ready(Ok(None));
+        };
+        let rename = PerformRename {
+            position: position.to_point_utf16(buffer.read(cx)),
+            new_name,
+            push_to_history: false,
+        };
+        let Some(language_server) = buffer.update(cx, |buffer, cx| {
+            local
+                .language_servers_for_buffer(buffer, cx)
+                .find(|(_, server)| rename.check_capabilities(server.adapter_server_capabilities()))
+                .map(|(_, server)| server.clone())
+        }) else {
+            return Task::ready(Ok(Some(ResolvedWorkspaceEdit::default())));
+        };
+        let Some(file) = File::from_dyn(buffer.read(cx).file()).and_then(File::as_local) else {
+            return Task::ready(Ok(Some(ResolvedWorkspaceEdit::default())));
+        };
+        let params = match rename.to_lsp(&file.abs_path(cx), buffer.read(cx), &language_server, cx)
+        {
+            Ok(params) => params,
+            Err(error) => return Task::ready(Err(error)),
+        };
+
+        cx.spawn(async move |this, cx| {
+            let edit = language_server
+                .request::<lsp::request::Rename>(params)
+                .await
+                .into_response()
+                .with_context(|| format!("Rename via {} failed", language_server.name()))?;
+            let Some(edit) = edit else {
+                return Ok(Some(ResolvedWorkspaceEdit::default()));
+            };
+            let this = this.upgrade().context("no app context")?;
+            LocalLspStore::resolve_workspace_edit(this, edit, language_server, cx)
+                .await
+                .map(Some)
+        })
+    }
+
+    /// Applies the operations of a resolved workspace edit in order, editing each buffer
+    /// in a single transaction.
+    pub fn apply_workspace_edit(
+        &mut self,
+        edit: ResolvedWorkspaceEdit,
+        push_to_history: bool,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<ProjectTransaction>> {
+        let Some(local) = self.as_local() else {
+            return Task::ready(Err(anyhow!(
+                "workspace edits can only be applied in local projects"
+            )));
+        };
+        let fs = local.fs.clone();
+        cx.spawn(async move |_, cx| {
+            let mut project_transaction = ProjectTransaction::default();
+            for operation in edit.operations {
+                match operation {
+                    WorkspaceEditOperation::CreateFile {
+                        abs_path,
+                        options,
+                        text,
+                    } => {
+                        if let Some(parent_path) = abs_path.parent() {
+                            fs.create_dir(parent_path).await?;
+                        }
+                        if abs_path.ends_with("/") {
+                            fs.create_dir(&abs_path).await?;
+                        } else {
+                            fs.create_file(&abs_path, options).await?;
+                            if !text.is_empty() {
+                                fs.write(&abs_path, text.as_bytes()).await?;
+                            }
+                        }
+                    }
+                    WorkspaceEditOperation::RenameFile {
+                        old_abs_path,
+                        new_abs_path,
+                        options,
+                    } => {
+                        fs.rename(&old_abs_path, &new_abs_path, options).await?;
+                    }
+                    WorkspaceEditOperation::DeleteFile { abs_path, options } => {
+                        if abs_path.ends_with("/") {
+                            fs.remove_dir(&abs_path, options).await?;
+                        } else {
+                            fs.remove_file(&abs_path, options).await?;
+                        }
+                    }
+                    WorkspaceEditOperation::EditBuffer { buffer, edits } => {
+                        let transaction = buffer.update(cx, |buffer, cx| {
+                            buffer.finalize_last_transaction();
+                            buffer.start_transaction();
+                            for (range, text) in edits {
+                                buffer.edit([(range, text)], None, cx);
+                            }
+                            buffer.end_transaction(cx).and_then(|transaction_id| {
+                                if push_to_history {
+                                    buffer.finalize_last_transaction();
+                                    buffer.get_transaction(transaction_id).cloned()
+                                } else {
+                                    buffer.forget_transaction(transaction_id)
+                                }
+                            })
+                        })?;
+                        if let Some(transaction) = transaction {
+                            project_transaction.0.insert(buffer, transaction);
+                        }
+                    }
+                }
+            }
+            Ok(project_transaction)
+        })
+    }
+
     pub fn on_type_format<T: ToPointUtf16>(
         &mut self,
         buffer: Entity<Buffer>,
@@ -11957,6 +12280,9 @@ impl LspStore {
             for buffer_servers in local.buffers_opened_in_servers.values_mut() {
                 buffer_servers.remove(&for_server);
             }
+            for buffer_results in local.semantic_tokens_results.values_mut() {
+                buffer_results.remove(&for_server);
+            }
         }
     }
 
@@ -12289,6 +12615,19 @@ impl LspStore {
                     });
                     notify_server_capabilities_updated(&server, cx);
                 }
+                "textDocument/prepareCallHierarchy" => {
+                    let options = parse_register_capabilities(reg)?;
+                    let provider = match options {
+                        OneOf::Left(value) => lsp::CallHierarchyServerCapability::Simple(value),
+                        OneOf::Right(options) => {
+                            lsp::CallHierarchyServerCapability::Options(options)
+                        }
+                    };
+                    server.update_capabilities(|capabilities| {
+                        capabilities.call_hierarchy_provider = Some(provider);
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
                 "textDocument/codeAction" => {
                     let options = parse_register_capabilities(reg)?;
                     let provider = match options {
@@ -12494,6 +12833,40 @@ impl LspStore {
                     });
                     notify_server_capabilities_updated(&server, cx);
                 }
+                "textDocument/semanticTokens" => {
+                    if let Some(caps) = reg
+                        .register_options
+                        .map(serde_json::from_value)
+                        .transpose()?
+                    {
+                        server.update_capabilities(|capabilities| {
+                            capabilities.semantic_tokens_provider = Some(
+                                lsp::SemanticTokensServerCapabilities::SemanticTokensRegistrationOptions(caps),
+                            );
+                        });
+                        notify_server_capabilities_updated(&server, cx);
+                    }
+                }
+                "textDocument/foldingRange" => {
+                    server.update_capabilities(|capabilities| {
+                        capabilities.folding_range_provider =
+                            Some(lsp::FoldingRangeProviderCapability::Simple(true));
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
+                "textDocument/selectionRange" => {
+                    server.update_capabilities(|capabilities| {
+                        capabilities.selection_range_provider =
+                            Some(lsp::SelectionRangeProviderCapability::Simple(true));
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
+                "textDocument/inlineCompletion" => {
+                    server.update_capabilities(|capabilities| {
+                        capabilities.inline_completion_provider = Some(OneOf::Left(true));
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
                 _ => log::warn!("unhandled capability registration: {reg:?}"),
             }
         }
@@ -12585,6 +12958,12 @@ impl LspStore {
                     server.update_capabilities(|capabilities| capabilities.rename_provider = None);
                     notify_server_capabilities_updated(&server, cx);
                 }
+                "textDocument/prepareCallHierarchy" => {
+                    server.update_capabilities(|capabilities| {
+                        capabilities.call_hierarchy_provider = None;
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
                 "textDocument/codeAction" => {
                     server.update_capabilities(|capabilities| {
                         capabilities.code_action_provider = None;
@@ -12685,6 +13064,30 @@ impl LspStore {
                     });
                     notify_server_capabilities_updated(&server, cx);
                 }
+                "textDocument/semanticTokens" => {
+                    server.update_capabilities(|capabilities| {
+                        capabilities.semantic_tokens_provider = None;
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
+                "textDocument/foldingRange" => {
+                    server.update_capabilities(|capabilities| {
+                        capabilities.folding_range_provider = None;
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
+                "textDocument/selectionRange" => {
+                    server.update_capabilities(|capabilities| {
+                        capabilities.selection_range_provider = None;
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
+                "textDocument/inlineCompletion" => {
+                    server.update_capabilities(|capabilities| {
+                        capabilities.inline_completion_provider = None;
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
                 _ => log::warn!("unhandled capability unregistration: {unreg:?}"),
             }
         }
@@ -13240,6 +13643,99 @@ async fn populate_labels_for_completions(
     completions
 }
 
+/// A workspace edit of a language server, resolved against the buffers it edits
+/// but not applied yet, so that it can be previewed and applied in part.
+#[derive(Clone, Default)]
+pub struct ResolvedWorkspaceEdit {
+    /// The operations of the edit, in the order the language server sent them.
+    pub operations: Vec<WorkspaceEditOperation>,
+}
+
+#[derive(Clone)]
+pub enum WorkspaceEditOperation {
+    /// Creates a file, or a directory when the path ends with a separator.
+    CreateFile {
+        abs_path: PathBuf,
+        options: fs::CreateOptions,
+        /// The contents of the file, from the edits of the workspace edit to it.
+        text: String,
+    },
+    RenameFile {
+        old_abs_path: PathBuf,
+        new_abs_path: PathBuf,
+        options: fs::RenameOptions,
+    },
+    /// Deletes a file, or a directory when the path ends with a separator.
+    DeleteFile {
+        abs_path: PathBuf,
+        options: fs::RemoveOptions,
+    },
+    EditBuffer {
+        buffer: Entity<Buffer>,
+        edits: Vec<(Range<Anchor>, Arc<str>)>,
+    },
+}
+
+impl ResolvedWorkspaceEdit {
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    /// The path that a path renamed by this edit had before it.
+    fn renamed_from(&self, abs_path: &Path) -> Option<PathBuf> {
+        self.operations
+            .iter()
+            .rev()
+            .find_map(|operation| match operation {
+                WorkspaceEditOperation::RenameFile {
+                    old_abs_path,
+                    new_abs_path,
+                    ..
+                } => {
+                    let suffix = abs_path.strip_prefix(new_abs_path).ok()?;
+                    Some(if suffix.as_os_str().is_empty() {
+                        old_abs_path.clone()
+                    } else {
+                        old_abs_path.join(suffix)
+                    })
+                }
+                _ => None,
+            })
+    }
+}
+
+fn workspace_edit_operations(edit: lsp::WorkspaceEdit) -> Vec<lsp::DocumentChangeOperation> {
+    let mut operations = Vec::new();
+    if let Some(document_changes) = edit.document_changes {
+        match document_changes {
+            lsp::DocumentChanges::Edits(edits) => {
+                operations.extend(edits.into_iter().map(lsp::DocumentChangeOperation::Edit))
+            }
+            lsp::DocumentChanges::Operations(ops) => operations = ops,
+        }
+    } else if let Some(changes) = edit.changes {
+        operations.extend(changes.into_iter().map(|(uri, edits)| {
+            lsp::DocumentChangeOperation::Edit(lsp::TextDocumentEdit {
+                text_document: lsp::OptionalVersionedTextDocumentIdentifier { uri, version: None },
+                edits: edits.into_iter().map(Edit::Plain).collect(),
+            })
+        }));
+    }
+    operations
+}
+
+fn apply_lsp_edits_to_text(text: &mut String, mut edits: Vec<lsp::TextEdit>) {
+    let mut rope = text::Rope::from(text.as_str());
+    // The ranges of the edits refer to the text before any of them is applied.
+    edits.sort_by_key(|edit| Reverse((edit.range.start.line, edit.range.start.character)));
+    for edit in edits {
+        let start = rope.unclipped_point_utf16_to_offset(point_from_lsp(edit.range.start));
+        let end = rope.unclipped_point_utf16_to_offset(point_from_lsp(edit.range.end));
+        rope.replace(start..end.max(start), &edit.new_text);
+    }
+    *text = rope.to_string();
+}
+
 #[derive(Debug)]
 pub enum LanguageServerToQuery {
     /// Query language servers in order of users preference, up until one capable of handling the request is found.
@@ -0,0 +1,617 @@
+//! Semantic tokens of language servers, which tell apart the kinds of symbols tree-sitter can't see,
+//! like mutable variables, statics or tokens produced by macros.
+//!
+//! Tokens are requested for the whole buffer, and as a delta against the previous response
+//! when the server supports it. The previous response of each server is kept on the host only:
+//! remote clients get decoded tokens of the buffer version they asked for.
+
+use std::{ops::Range, sync::Arc, time::Duration};
+
+use anyhow::{Context as _, Result, anyhow};
+use client::{TypedEnvelope, proto};
+use clock::Global;
+use collections::HashMap;
+use futures::{
+    FutureExt as _,
+    future::{Shared, join_all},
+};
+use gpui::{AsyncApp, Context, Entity, SharedString, Task};
+use language::{
+    Bias, Buffer, PointUtf16, Unclipped,
+    proto::{deserialize_version, serialize_version},
+};
+use lsp::{LanguageServer, LanguageServerId};
+use text::{Anchor, BufferId, TextBufferSnapshot, ToPointUtf16 as _};
+use util::ResultExt as _;
+
+use super::{File, LspStore, LspStoreEvent};
+
+/// The token types and modifiers of a language server, which its tokens refer to by index.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SemanticTokensLegend {
+    pub token_types: Vec<SharedString>,
+    pub token_modifiers: Vec<SharedString>,
+}
+
+impl SemanticTokensLegend {
+    fn from_lsp(legend: &lsp::SemanticTokensLegend) -> Self {
+        Self {
+            token_types: legend
+                .token_types
+                .iter()
+                .map(|token_type| SharedString::from(token_type.as_str().to_string()))
+                .collect(),
+            token_modifiers: legend
+                .token_modifiers
+                .iter()
+                .map(|modifier| SharedString::from(modifier.as_str().to_string()))
+                .collect(),
+        }
+    }
+
+    /// The type of the token, if the legend has one at its index.
+    pub fn token_type(&self, token: &SemanticToken) -> Option<&SharedString> {
+        self.token_types.get(token.token_type as usize)
+    }
+
+    /// The modifiers set on the token, in the order of the legend.
+    pub fn token_modifiers<'a>(
+        &'a self,
+        token: &SemanticToken,
+    ) -> impl Iterator<Item = &'a SharedString> + 'a {
+        let modifiers = token.token_modifiers;
+        self.token_modifiers
+            .iter()
+            .take(u32::BITS as usize)
+            .enumerate()
+            .filter(move |(ix, _)| modifiers & (1 << ix) != 0)
+            .map(|(_, modifier)| modifier)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SemanticToken {
+    pub range: Range<Anchor>,
+    /// The index of the type of the token in the legend.
+    pub token_type: u32,
+    /// A bit set of the indices of the modifiers of the token in the legend.
+    pub token_modifiers: u32,
+}
+
+/// The semantic tokens of a buffer from one language server.
+#[derive(Debug, Default, Clone)]
+pub struct SemanticTokens {
+    pub legend: Arc<SemanticTokensLegend>,
+    pub tokens: Arc<[SemanticToken]>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct BufferSemanticTokens {
+    pub servers: HashMap<LanguageServerId, SemanticTokens>,
+    pub cache_version: Option<usize>,
+}
+
+pub(super) type SemanticTokensTask =
+    Shared<Task<std::result::Result<BufferSemanticTokens, Arc<anyhow::Error>>>>;
+
+#[derive(Debug, Default)]
+pub(super) struct SemanticTokensData {
+    servers: HashMap<LanguageServerId, SemanticTokens>,
+    cache_version: usize,
+    update: Option<(Global, SemanticTokensTask)>,
+}
+
+impl SemanticTokensData {
+    pub(super) fn remove_server_data(&mut self, for_server: LanguageServerId) {
+        if self.servers.remove(&for_server).is_some() {
+            self.cache_version += 1;
+        }
+    }
+}
+
+/// The latest response of a language server for a buffer, which its delta responses apply to.
+#[derive(Debug)]
+pub(super) struct SemanticTokensResult {
+    result_id: Option<String>,
+    data: Vec<lsp::SemanticToken>,
+}
+
+impl LspStore {
+    /// Returns the semantic tokens of the buffer, from the cache when they are up to date,
+    /// or `None` when the caller already has the tokens of `known_cache_version`.
+    pub fn semantic_tokens(
+        &mut self,
+        known_cache_version: Option<usize>,
+        buffer: Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Option<SemanticTokensTask> {
+        let version_queried_for = buffer.read(cx).version();
+        let buffer_id = buffer.read(cx).remote_id();
+
+        if let Some(lsp_data) = self.current_lsp_data(buffer_id)
+            && let Some(cached_tokens) = &lsp_data.semantic_tokens
+            && cached_tokens.update.is_none()
+            && !version_queried_for.changed_since(&lsp_data.buffer_version)
+        {
+            let cache_version = cached_tokens.cache_version;
+            if Some(cache_version) == known_cache_version {
+                return None;
+            }
+            return Some(
+                Task::ready(Ok(BufferSemanticTokens {
+                    servers: cached_tokens.servers.clone(),
+                    cache_version: Some(cache_version),
+                }))
+                .shared(),
+            );
+        }
+
+        let tokens_lsp_data = self
+            .latest_lsp_data(&buffer, cx)
+            .semantic_tokens
+            .get_or_insert_default();
+        if let Some((updating_for, running_update)) = &tokens_lsp_data.update
+            && !version_queried_for.changed_since(updating_for)
+        {
+            return Some(running_update.clone());
+        }
+
+        let buffer_version_queried_for = version_queried_for.clone();
+        let new_task = cx
+            .spawn(async move |lsp_store, cx| {
+                cx.background_executor()
+                    .timer(Duration::from_millis(30))
+                    .await;
+                let fetched_tokens = lsp_store
+                    .update(cx, |lsp_store, cx| {
+                        lsp_store.fetch_semantic_tokens_for_buffer(&buffer, cx)
+                    })?
+                    .await
+                    .context("fetching semantic tokens")
+                    .map_err(Arc::new);
+                let fetched_tokens = match fetched_tokens {
+                    Ok(fetched_tokens) => fetched_tokens,
+                    Err(e) => {
+                        lsp_store
+                            .update(cx, |lsp_store, _| {
+                                if let Some(lsp_data) = lsp_store.lsp_data.get_mut(&buffer_id)
+                                    && let Some(semantic_tokens) = &mut lsp_data.semantic_tokens
+                                {
+                                    semantic_tokens.update = None;
+                                }
+                            })
+                            .ok();
+                        return Err(e);
+                    }
+                };
+
+                lsp_store
+                    .update(cx, |lsp_store, cx| {
+                        let lsp_data = lsp_store.latest_lsp_data(&buffer, cx);
+                        let buffer_version = lsp_data.buffer_version.clone();
+                        let semantic_tokens = lsp_data.semantic_tokens.get_or_insert_default();
+                        if let Some(fetched_tokens) = fetched_tokens
+                            && buffer_version == buffer_version_queried_for
+                        {
+                            semantic_tokens.servers = fetched_tokens;
+                            semantic_tokens.cache_version += 1;
+                        }
+                        semantic_tokens.update = None;
+                        BufferSemanticTokens {
+                            servers: semantic_tokens.servers.clone(),
+                            cache_version: Some(semantic_tokens.cache_version),
+                        }
+                    })
+                    .map_err(Arc::new)
+            })
+            .shared();
+        tokens_lsp_data.update = Some((version_queried_for, new_task.clone()));
+        Some(new_task)
+    }
+
+    /// Drops the semantic tokens of all buffers, after a language server asked to refresh them.
+    pub(super) fn invalidate_semantic_tokens(&mut self, cx: &mut Context<Self>) {
+        for lsp_data in self.lsp_data.values_mut() {
+            lsp_data.semantic_tokens = None;
+        }
+        cx.emit(LspStoreEvent::RefreshSemanticTokens);
+    }
+
+    /// Fetches the tokens of every language server of the buffer, or `None` if the buffer
+    /// changed while they were fetched.
+    fn fetch_semantic_tokens_for_buffer(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Option<HashMap<LanguageServerId, SemanticTokens>>>> {
+        if let Some((client, project_id)) = self.upstream_client() {
+            let request = client.request(proto::GetSemanticTokens {
+                project_id,
+                buffer_id: buffer.read(cx).remote_id().into(),
+                version: serialize_version(&buffer.read(cx).version()),
+            });
+            let buffer = buffer.clone();
+            return cx.spawn(async move |_, cx| {
+                let response = request.await?;
+                let version = deserialize_version(&response.version);
+                buffer.read_with(cx, |buffer, _| {
+                    if buffer.version() != version {
+                        return None;
+                    }
+                    let snapshot = buffer.text_snapshot();
+                    Some(
+                        response
+                            .servers
+                            .into_iter()
+                            .map(|server| {
+                                let legend = SemanticTokensLegend {
+                                    token_types: server
+                                        .token_types
+                                        .into_iter()
+                                        .map(SharedString::from)
+                                        .collect(),
+                                    token_modifiers: server
+                                        .token_modifiers
+                                        .into_iter()
+                                        .map(SharedString::from)
+                                        .collect(),
+                                };
+                                let data = server
+                                    .data
+                                    .chunks_exact(5)
+                                    .map(|token| lsp::SemanticToken {
+                                        delta_line: token[0],
+                                        delta_start: token[1],
+                                        length: token[2],
+                                        token_type: token[3],
+                                        token_modifiers_bitset: token[4],
+                                    })
+                                    .collect::<Vec<_>>();
+                                (
+                                    LanguageServerId::from_proto(server.server_id),
+                                    SemanticTokens {
+                                        legend: Arc::new(legend),
+                                        tokens: decode_semantic_tokens(&data, &snapshot).into(),
+                                    },
+                                )
+                            })
+                            .collect(),
+                    )
+                })
+            });
+        }
+
+        let buffer_id = buffer.read(cx).remote_id();
+        let version = buffer.read(cx).version();
+        let Some(abs_path) = File::from_dyn(buffer.read(cx).file())
+            .and_then(|file| Some(file.as_local()?.abs_path(cx)))
+        else {
+            return Task::ready(Ok(None));
+        };
+        let uri = match lsp::Uri::from_file_path(&abs_path)
+            .ok()
+            .with_context(|| format!("Failed to convert path to URI: {}", abs_path.display()))
+        {
+            Ok(uri) => uri,
+            Err(e) => return Task::ready(Err(e)),
+        };
+        let servers = buffer.update(cx, |buffer, cx| {
+            let Some(local) = self.as_local() else {
+                return Vec::new();
+            };
+            local
+                .language_servers_for_buffer(buffer, cx)
+                .filter_map(|(_, server)| {
+                    let (legend, supports_delta) = semantic_tokens_options(server)?;
+                    let previous_result = supports_delta
+                        .then(|| {
+                            let result = local
+                                .semantic_tokens_results
+                                .get(&buffer_id)?
+                                .get(&server.server_id())?;
+                            Some((result.result_id.clone()?, result.data.clone()))
+                        })
+                        .flatten();
+                    Some((server.clone(), legend, previous_result))
+                })
+                .collect::<Vec<_>>()
+        });
+        if servers.is_empty() {
+            return Task::ready(Ok(Some(HashMap::default())));
+        }
+
+        let buffer = buffer.clone();
+        cx.spawn(async move |lsp_store, cx| {
+            let responses = join_all(servers.into_iter().map(
+                |(server, legend, previous_result)| {
+                    let uri = uri.clone();
+                    async move {
+                        let response = request_semantic_tokens(&server, uri, previous_result).await;
+                        (server.server_id(), legend, response)
+                    }
+                },
+            ))
+            .await;
+
+            lsp_store.update(cx, |lsp_store, cx| {
+                let snapshot = buffer.read(cx).text_snapshot();
+                let buffer_changed = snapshot.version() != &version;
+                let mut tokens = HashMap::default();
+                for (server_id, legend, response) in responses {
+                    let Some(result) = response
+                        .with_context(|| format!("semantic tokens of server {server_id}"))
+                        .log_err()
+                    else {
+                        // Keep an entry for the server, so that it is not asked again until the buffer changes.
+                        tokens.insert(
+                            server_id,
+                            SemanticTokens {
+                                legend: Arc::new(legend),
+                                tokens: Arc::default(),
+                            },
+                        );
+                        continue;
+                    };
+                    if !buffer_changed {
+                        tokens.insert(
+                            server_id,
+                            SemanticTokens {
+                                legend: Arc::new(legend),
+                                tokens: decode_semantic_tokens(&result.data, &snapshot).into(),
+                            },
+                        );
+                    }
+                    if let Some(local) = lsp_store.as_local_mut() {
+                        local
+                            .semantic_tokens_results
+                            .entry(buffer_id)
+                            .or_default()
+                            .insert(server_id, result);
+                    }
+                }
+                (!buffer_changed).then_some(tokens)
+            })
+        })
+    }
+
+    pub(super) async fn handle_get_semantic_tokens(
+        lsp_store: Entity<Self>,
+        envelope: TypedEnvelope<proto::GetSemanticTokens>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GetSemanticTokensResponse> {
+        let buffer_id = BufferId::new(envelope.payload.buffer_id)?;
+        let version = deserialize_version(&envelope.payload.version);
+        let buffer = lsp_store.update(&mut cx, |lsp_store, cx| {
+            lsp_store.buffer_store.read(cx).get_existing(buffer_id)
+        })??;
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(version.clone())
+            })?
+            .await?;
+        let tokens_task = lsp_store.update(&mut cx, |lsp_store, cx| {
+            lsp_store.semantic_tokens(None, buffer.clone(), cx)
+        })?;
+        let tokens = match tokens_task {
+            Some(tokens_task) => tokens_task.await.map_err(|e| anyhow!("{e:#}"))?,
+            None => BufferSemanticTokens::default(),
+        };
+
+        buffer.read_with(&cx, |buffer, _| {
+            let snapshot = buffer.text_snapshot();
+            proto::GetSemanticTokensResponse {
+                servers: tokens
+                    .servers
+                    .into_iter()
+                    .map(|(server_id, tokens)| proto::LanguageServerSemanticTokens {
+                        server_id: server_id.to_proto(),
+                        token_types: tokens
+                            .legend
+                            .token_types
+                            .iter()
+                            .map(|token_type| token_type.to_string())
+                            .collect(),
+                        token_modifiers: tokens
+                            .legend
+                            .token_modifiers
+                            .iter()
+                            .map(|modifier| modifier.to_string())
+                            .collect(),
+                        data: encode_semantic_tokens(&tokens.tokens, &snapshot)
+                            .into_iter()
+                            .flat_map(|token| {
+                                [
+                                    token.delta_line,
+                                    token.delta_start,
+                                    token.length,
+                                    token.token_type,
+                                    token.token_modifiers_bitset,
+                                ]
+                            })
+                            .collect(),
+                    })
+                    .collect(),
+                version: serialize_version(&snapshot.version()),
+            }
+        })
+    }
+
+    pub(super) async fn handle_refresh_semantic_tokens(
+        lsp_store: Entity<Self>,
+        _: TypedEnvelope<proto::RefreshSemanticTokens>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        lsp_store.update(&mut cx, |lsp_store, cx| {
+            lsp_store.invalidate_semantic_tokens(cx);
+        })?;
+        Ok(proto::Ack {})
+    }
+}
+
+/// The legend of the server and whether it can send deltas, if it provides semantic tokens
+/// for whole documents.
+fn semantic_tokens_options(server: &LanguageServer) -> Option<(SemanticTokensLegend, bool)> {
+    let options = match server.capabilities().semantic_tokens_provider? {
+        lsp::SemanticTokensServerCapabilities::SemanticTokensOptions(options) => options,
+        lsp::SemanticTokensServerCapabilities::SemanticTokensRegistrationOptions(options) => {
+            options.semantic_tokens_options
+        }
+    };
+    let supports_delta = match options.full? {
+        lsp::SemanticTokensFullOptions::Bool(false) => return None,
+        lsp::SemanticTokensFullOptions::Bool(true) => false,
+        lsp::SemanticTokensFullOptions::Delta { delta } => delta.unwrap_or(false),
+    };
+    Some((
+        SemanticTokensLegend::from_lsp(&options.legend),
+        supports_delta,
+    ))
+}
+
+/// Requests the tokens of the document, as a delta against the previous result if there is one.
+/// Falls back to the full tokens when the delta does not apply.
+async fn request_semantic_tokens(
+    server: &LanguageServer,
+    uri: lsp::Uri,
+    previous_result: Option<(String, Vec<lsp::SemanticToken>)>,
+) -> Result<SemanticTokensResult> {
+    let text_document = lsp::TextDocumentIdentifier { uri };
+    if let Some((previous_result_id, mut data)) = previous_result {
+        let response = server
+            .request::<lsp::request::SemanticTokensFullDeltaRequest>(
+                lsp::SemanticTokensDeltaParams {
+                    text_document: text_document.clone(),
+                    previous_result_id,
+                    work_done_progress_params: Default::default(),
+                    partial_result_params: Default::default(),
+                },
+            )
+            .await
+            .into_response()?;
+        match response {
+            Some(lsp::SemanticTokensFullDeltaResult::Tokens(tokens)) => {
+                return Ok(SemanticTokensResult {
+                    result_id: tokens.result_id,
+                    data: tokens.data,
+                });
+            }
+            Some(lsp::SemanticTokensFullDeltaResult::TokensDelta(delta)) => {
+                if apply_semantic_tokens_edits(&mut data, delta.edits)
+                    .log_err()
+                    .is_some()
+                {
+                    return Ok(SemanticTokensResult {
+                        result_id: delta.result_id,
+                        data,
+                    });
+                }
+            }
+            Some(lsp::SemanticTokensFullDeltaResult::PartialTokensDelta { .. }) | None => {}
+        }
+    }
+
+    let response = server
+        .request::<lsp::request::SemanticTokensFullRequest>(lsp::SemanticTokensParams {
+            text_document,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+        .await
+        .into_response()?;
+    Ok(match response {
+        Some(lsp::SemanticTokensResult::Tokens(tokens)) => SemanticTokensResult {
+            result_id: tokens.result_id,
+            data: tokens.data,
+        },
+        Some(lsp::SemanticTokensResult::Partial(partial)) => SemanticTokensResult {
+            result_id: None,
+            data: partial.data,
+        },
+        None => SemanticTokensResult {
+            result_id: None,
+            data: Vec::new(),
+        },
+    })
+}
+
+/// Applies the edits of a delta response, whose offsets count integers of the encoded tokens.
+fn apply_semantic_tokens_edits(
+    data: &mut Vec<lsp::SemanticToken>,
+    mut edits: Vec<lsp::SemanticTokensEdit>,
+) -> Result<()> {
+    const TOKEN_LEN: u32 = 5;
+
+    edits.sort_by_key(|edit| edit.start);
+    for edit in edits.into_iter().rev() {
+        anyhow::ensure!(
+            edit.start % TOKEN_LEN == 0 && edit.delete_count % TOKEN_LEN == 0,
+            "semantic tokens edit does not align with tokens: {edit:?}"
+        );
+        let start = (edit.start / TOKEN_LEN) as usize;
+        let end = start + (edit.delete_count / TOKEN_LEN) as usize;
+        anyhow::ensure!(
+            end <= data.len(),
+            "semantic tokens edit is out of bounds: {edit:?}"
+        );
+        data.splice(start..end, edit.data.unwrap_or_default());
+    }
+    Ok(())
+}
+
+fn decode_semantic_tokens(
+    data: &[lsp::SemanticToken],
+    snapshot: &TextBufferSnapshot,
+) -> Vec<SemanticToken> {
+    let mut row = 0;
+    let mut column = 0;
+    data.iter()
+        .filter_map(|token| {
+            if token.delta_line > 0 {
+                row += token.delta_line;
+                column = 0;
+            }
+            column += token.delta_start;
+            let start =
+                snapshot.clip_point_utf16(Unclipped(PointUtf16::new(row, column)), Bias::Left);
+            let end = snapshot.clip_point_utf16(
+                Unclipped(PointUtf16::new(row, column + token.length)),
+                Bias::Right,
+            );
+            (start < end).then(|| SemanticToken {
+                range: snapshot.anchor_after(start)..snapshot.anchor_before(end),
+                token_type: token.token_type,
+                token_modifiers: token.token_modifiers_bitset,
+            })
+        })
+        .collect()
+}
+
+fn encode_semantic_tokens(
+    tokens: &[SemanticToken],
+    snapshot: &TextBufferSnapshot,
+) -> Vec<lsp::SemanticToken> {
+    let mut previous = PointUtf16::zero();
+    tokens
+        .iter()
+        .filter_map(|token| {
+            let start = token.range.start.to_point_utf16(snapshot);
+            let end = token.range.end.to_point_utf16(snapshot);
+            if start < previous || end.row != start.row {
+                return None;
+            }
+            let encoded = lsp::SemanticToken {
+                delta_line: start.row - previous.row,
+                delta_start: if start.row == previous.row {
+                    start.column - previous.column
+                } else {
+                    start.column
+                },
+                length: end.column - start.column,
+                token_type: token.token_type,
+                token_modifiers_bitset: token.token_modifiers,
+            };
+            previous = start;
+            Some(encoded)
+        })
+        .collect()
+}
@@ -5,6 +5,9 @@ pub mod connection_manager;
 pub mod context_server_store;
 pub mod debounced_delay;
 pub mod debugger;
+pub mod dotnet_build_diagnostics;
+pub mod dotnet_configurations;
+pub mod dotnet_startup_projects;
 pub mod git_store;
 pub mod image_store;
 pub mod lsp_command;
@@ -36,6 +39,9 @@ mod yarn;
 use dap::inline_value::{InlineValueLocation, VariableLookupKind, VariableScope};
 
 use crate::{
+    dotnet_build_diagnostics::DotNetBuildDiagnostics,
+    dotnet_configurations::DotNetConfigurations,
+    dotnet_startup_projects::DotNetStartupProjects,
     git_store::GitStore,
     lsp_store::{SymbolLocation, log_store::LogKind},
     project_search::SearchResultsHandle,
@@ -150,7 +156,8 @@ pub use buffer_store::ProjectTransaction;
 pub use lsp_store::{
     DiagnosticSummary, InvalidationStrategy, LanguageServerLogType, LanguageServerProgress,
     LanguageServerPromptRequest, LanguageServerStatus, LanguageServerToQuery, LspStore,
-    LspStoreEvent, ProgressToken, SERVER_PROGRESS_THROTTLE_TIMEOUT,
+    LspStoreEvent, ProgressToken, ResolvedWorkspaceEdit, SERVER_PROGRESS_THROTTLE_TIMEOUT,
+    SemanticToken, SemanticTokens, SemanticTokensLegend, WorkspaceEditOperation,
 };
 pub use toolchain_store::{ToolchainStore, Toolchains};
 const MAX_PROJECT_SEARCH_HISTORY_SIZE: usize = 500;
@@ -217,6 +224,9 @@ pub struct Project {
     settings_observer: Entity<SettingsObserver>,
     toolchain_store: Option<Entity<ToolchainStore>>,
     agent_location: Option<AgentLocation>,
+    dotnet_configurations: DotNetConfigurations,
+    dotnet_startup_projects: DotNetStartupProjects,
+    dotnet_build_diagnostics: DotNetBuildDiagnostics,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -344,6 +354,7 @@ pub enum Event {
         request_id: Option<usize>,
     },
     RefreshCodeLens,
+    RefreshSemanticTokens,
     RevealInProjectPanel(ProjectEntryId),
     SnippetEdit(BufferId, Vec<(lsp::Range, Snippet)>),
     ExpandedAllForEntry(WorktreeId, ProjectEntryId),
@@ -410,6 +421,7 @@ pub enum PrepareRenameResponse {
 pub enum InlayId {
     EditPrediction(usize),
     DebuggerValue(usize),
+    TestResult(usize),
     // LSP
     Hint(usize),
     Color(usize),
@@ -420,6 +432,7 @@ impl InlayId {
         match self {
             Self::EditPrediction(id) => *id,
             Self::DebuggerValue(id) => *id,
+            Self::TestResult(id) => *id,
             Self::Hint(id) => *id,
             Self::Color(id) => *id,
         }
@@ -793,6 +806,86 @@ pub struct DocumentSymbol {
     pub children: Vec<DocumentSymbol>,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CallHierarchyDirection {
+    /// The symbols calling a symbol
+    Incoming,
+    /// The symbols a symbol calls
+    Outgoing,
+}
+
+/// A symbol in a call hierarchy, such as a function or a method.
+#[derive(Clone, Debug)]
+pub struct CallHierarchyItem {
+    pub name: String,
+    pub kind: lsp::SymbolKind,
+    pub detail: Option<String>,
+    /// The whole symbol, including its body.
+    pub location: Location,
+    /// The part of the symbol to reveal when it is selected, such as its name.
+    pub selection_range: Range<Anchor>,
+}
+
+/// A symbol calling, or called by, the symbol of a call hierarchy.
+#[derive(Clone, Debug)]
+pub struct CallHierarchyCall {
+    pub item: CallHierarchyItem,
+    /// Where the calls are made: in the caller for incoming calls, and in the
+    /// symbol of the hierarchy for outgoing ones.
+    pub call_sites: Vec<Location>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CallHierarchy {
+    pub item: CallHierarchyItem,
+    pub calls: Vec<CallHierarchyCall>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TypeHierarchyDirection {
+    /// The types a type derives from or implements
+    Supertypes,
+    /// The types deriving from or implementing a type
+    Subtypes,
+}
+
+/// A type in a type hierarchy, such as a class or an interface.
+#[derive(Clone, Debug)]
+pub struct TypeHierarchyItem {
+    pub name: String,
+    pub kind: lsp::SymbolKind,
+    pub detail: Option<String>,
+    /// The whole declaration of the type.
+    pub location: Location,
+    /// The part of the type to reveal when it is selected, such as its name.
+    pub selection_range: Range<Anchor>,
+}
+
+#[derive(Clone, Debug)]
+pub struct TypeHierarchy {
+    pub item: TypeHierarchyItem,
+    /// The direct supertypes or subtypes of the item.
+    pub types: Vec<TypeHierarchyItem>,
+}
+
+/// How an inline completion request was triggered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InlineCompletionTrigger {
+    /// Explicitly requested by the user
+    Invoked,
+    /// Requested while typing
+    Automatic,
+}
+
+/// A completion a language server suggests showing inline at a position.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InlineCompletion {
+    /// The text the completion replaces, empty when it is inserted at the position.
+    pub range: Range<Anchor>,
+    /// The text to insert, with the tabstops of snippets removed.
+    pub text: String,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct HoverBlock {
     pub text: String,
@@ -1098,6 +1191,8 @@ impl Project {
             let breakpoint_store =
                 cx.new(|_| BreakpointStore::local(worktree_store.clone(), buffer_store.clone()));
 
+            let dotnet_startup_projects = DotNetStartupProjects::default();
+            let dotnet_build_diagnostics = DotNetBuildDiagnostics::default();
             let dap_store = cx.new(|cx| {
                 DapStore::new_local(
                     client.http_client(),
@@ -1107,6 +1202,8 @@ impl Project {
                     toolchain_store.read(cx).as_language_toolchain_store(),
                     worktree_store.clone(),
                     breakpoint_store.clone(),
+                    dotnet_startup_projects.clone(),
+                    dotnet_build_diagnostics.clone(),
                     false,
                     cx,
                 )
@@ -1127,12 +1224,14 @@ impl Project {
                 )
             });
 
+            let dotnet_configurations = DotNetConfigurations::default();
             let task_store = cx.new(|cx| {
                 TaskStore::local(
                     buffer_store.downgrade(),
                     worktree_store.clone(),
                     toolchain_store.read(cx).as_language_toolchain_store(),
                     environment.clone(),
+                    dotnet_configurations.clone(),
                     cx,
                 )
             });
@@ -1188,6 +1287,9 @@ impl Project {
             });
 
             cx.subscribe(&lsp_store, Self::on_lsp_store_event).detach();
+            dotnet_build_diagnostics
+                .maintain_diagnostics(lsp_store.downgrade(), cx)
+                .detach();
 
             Self {
                 buffer_ordered_messages_tx: tx,
@@ -1231,6 +1333,10 @@ impl Project {
                 toolchain_store: Some(toolchain_store),
 
                 agent_location: None,
+
+                dotnet_configurations,
+                dotnet_startup_projects,
+                dotnet_build_diagnostics,
             }
         })
     }
@@ -1288,6 +1394,7 @@ impl Project {
             let toolchain_store = cx.new(|cx| {
                 ToolchainStore::remote(REMOTE_SERVER_PROJECT_ID, remote.read(cx).proto_client(), cx)
             });
+            let dotnet_configurations = DotNetConfigurations::default();
             let task_store = cx.new(|cx| {
                 TaskStore::remote(
                     buffer_store.downgrade(),
@@ -1295,6 +1402,7 @@ impl Project {
                     toolchain_store.read(cx).as_language_toolchain_store(),
                     remote.read(cx).proto_client(),
                     REMOTE_SERVER_PROJECT_ID,
+                    dotnet_configurations.clone(),
                     cx,
                 )
             });
@@ -1422,6 +1530,9 @@ impl Project {
 
                 toolchain_store: Some(toolchain_store),
                 agent_location: None,
+                dotnet_configurations,
+                dotnet_startup_projects: DotNetStartupProjects::default(),
+                dotnet_build_diagnostics: DotNetBuildDiagnostics::default(),
             };
 
             // remote server -> local machine handlers
@@ -1559,6 +1670,7 @@ impl Project {
             )
         })?;
 
+        let dotnet_configurations = DotNetConfigurations::default();
         let task_store = cx.new(|cx| {
             if run_tasks {
                 TaskStore::remote(
@@ -1567,6 +1679,7 @@ impl Project {
                     Arc::new(EmptyToolchainStore),
                     client.clone().into(),
                     remote_id,
+                    dotnet_configurations.clone(),
                     cx,
                 )
             } else {
@@ -1676,6 +1789,9 @@ impl Project {
                 remotely_created_models: Arc::new(Mutex::new(RemotelyCreatedModels::default())),
                 toolchain_store: None,
                 agent_location: None,
+                dotnet_configurations,
+                dotnet_startup_projects: DotNetStartupProjects::default(),
+                dotnet_build_diagnostics: DotNetBuildDiagnostics::default(),
             };
             project.set_role(role, cx);
             for worktree in worktrees {
@@ -2018,6 +2134,24 @@ impl Project {
         &self.task_store
     }
 
+    /// The configurations .NET projects are built and evaluated with, as
+    /// chosen in the solution explorer.
+    pub fn dotnet_configurations(&self) -> &DotNetConfigurations {
+        &self.dotnet_configurations
+    }
+
+    /// The startup projects of .NET solutions, which the .NET debug locator
+    /// launches for builds of a whole solution.
+    pub fn dotnet_startup_projects(&self) -> &DotNetStartupProjects {
+        &self.dotnet_startup_projects
+    }
+
+    /// Where .NET builds of this project publish their errors and warnings,
+    /// which it shows as diagnostics when it's local.
+    pub fn dotnet_build_diagnostics(&self) -> &DotNetBuildDiagnostics {
+        &self.dotnet_build_diagnostics
+    }
+
     #[inline]
     pub fn snippets(&self) -> &Entity<SnippetProvider> {
         &self.snippets
@@ -3082,6 +3216,7 @@ impl Project {
                 request_id: *request_id,
             }),
             LspStoreEvent::RefreshCodeLens => cx.emit(Event::RefreshCodeLens),
+            LspStoreEvent::RefreshSemanticTokens => cx.emit(Event::RefreshSemanticTokens),
             LspStoreEvent::LanguageServerPrompt(prompt) => {
                 cx.emit(Event::LanguageServerPrompt(prompt.clone()))
             }
@@ -3776,6 +3911,102 @@ impl Project {
         )
     }
 
+    /// The symbol at a position, with the symbols calling it or called by it.
+    pub fn call_hierarchy<T: ToPointUtf16>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: T,
+        direction: CallHierarchyDirection,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Option<CallHierarchy>>> {
+        let position = position.to_point_utf16(buffer.read(cx));
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            GetCallHierarchy {
+                position,
+                direction,
+            },
+            cx,
+        )
+    }
+
+    /// The type at a position, with its direct supertypes or subtypes.
+    pub fn type_hierarchy<T: ToPointUtf16>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: T,
+        direction: TypeHierarchyDirection,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Option<TypeHierarchy>>> {
+        let position = position.to_point_utf16(buffer.read(cx));
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            GetTypeHierarchy {
+                position,
+                direction,
+            },
+            cx,
+        )
+    }
+
+    /// The ranges the language server folds, each starting at the end of its first line.
+    /// Empty when no language server of the buffer provides folding ranges.
+    pub fn folding_ranges(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<Range<Anchor>>>> {
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            GetFoldingRanges,
+            cx,
+        )
+    }
+
+    /// The selection ranges around each position, from the innermost to the outermost.
+    /// Empty when no language server of the buffer provides selection ranges.
+    pub fn selection_ranges<T: ToPointUtf16>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        positions: impl IntoIterator<Item = T>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<Vec<Range<Anchor>>>>> {
+        let positions = {
+            let buffer = buffer.read(cx);
+            positions
+                .into_iter()
+                .map(|position| position.to_point_utf16(buffer))
+                .collect()
+        };
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            GetSelectionRanges { positions },
+            cx,
+        )
+    }
+
+    /// The completions the first language server providing them suggests showing inline
+    /// at a position, following the LSP 3.18 `textDocument/inlineCompletion` request.
+    pub fn inline_completions<T: ToPointUtf16>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: T,
+        trigger: InlineCompletionTrigger,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<InlineCompletion>>> {
+        let position = position.to_point_utf16(buffer.read(cx));
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            GetInlineCompletions { position, trigger },
+            cx,
+        )
+    }
+
     pub fn symbols(&self, query: &str, cx: &mut Context<Self>) -> Task<Result<Vec<Symbol>>> {
         self.lsp_store
             .update(cx, |lsp_store, cx| lsp_store.symbols(query, cx))
@@ -3981,6 +4212,30 @@ impl Project {
         )
     }
 
+    /// Requests the workspace edit renaming the symbol at a position without applying it,
+    /// so that it can be previewed. Returns `None` in remote projects.
+    pub fn rename_edits<T: ToPointUtf16>(
+        &mut self,
+        buffer: Entity<Buffer>,
+        position: T,
+        new_name: String,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Option<ResolvedWorkspaceEdit>>> {
+        self.lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store.rename_edits(buffer, position, new_name, cx)
+        })
+    }
+
+    pub fn apply_workspace_edit(
+        &mut self,
+        edit: ResolvedWorkspaceEdit,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<ProjectTransaction>> {
+        self.lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store.apply_workspace_edit(edit, true, cx)
+        })
+    }
+
     pub fn on_type_format<T: ToPointUtf16>(
         &mut self,
         buffer: Entity<Buffer>,
@@ -2,11 +2,15 @@
 
 use crate::{
     Event,
-    git_store::{GitStoreEvent, RepositoryEvent, StatusEntry, pending_op},
+    dotnet_build_diagnostics::{
+        DOTNET_BUILD_DIAGNOSTICS_ID, DOTNET_BUILD_DIAGNOSTICS_SOURCE, DotNetBuildLog,
+    },
+    git_store::{GitStoreEvent, Repository, RepositoryEvent, StatusEntry, pending_op},
     task_inventory::TaskContexts,
     task_store::TaskSettingsLocation,
     *,
 };
+use askpass::AskPassDelegate;
 use async_trait::async_trait;
 use buffer_diff::{
     BufferDiffEvent, CALCULATE_DIFF_TASK, DiffHunkSecondaryStatus, DiffHunkStatus,
@@ -15,9 +19,13 @@ use buffer_diff::{
 use fs::FakeFs;
 use futures::{StreamExt, future};
 use git::{
-    GitHostingProviderRegistry,
-    repository::{RepoPath, repo_path},
+    GitHostingProviderRegistry, Oid,
+    repository::{
+        CommitSummary, RebaseControl, RebaseOutcome, RebaseTodoAction, RepoPath,
+        SequencerOperation, repo_path,
+    },
     status::{StatusCode, TrackedStatus},
+    submodule::{Submodule, SubmoduleOperation, SubmoduleStatus},
 };
 use git2::RepositoryInitOptions;
 use gpui::{App, BackgroundExecutor, FutureExt, UpdateGlobal};
@@ -51,7 +59,7 @@ use std::{
     task::Poll,
 };
 use sum_tree::SumTree;
-use task::{ResolvedTask, ShellKind, TaskContext};
+use task::{ResolvedTask, ShellKind, SpawnInTerminal, TaskContext};
 use unindent::Unindent as _;
 use util::{
     TryFutureExt as _, assert_set_eq, maybe, path,
@@ -2878,6 +2886,212 @@ async fn test_diagnostics_from_multiple_language_servers(cx: &mut gpui::TestAppC
     });
 }
 
+#[gpui::test]
+async fn test_dotnet_build_diagnostics(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "App.csproj": "<Project Sdk=\"Microsoft.NET.Sdk\" />",
+            "Program.cs": "Console.WriteLine(1)\n",
+        }),
+    )
+    .await;
+    fs.insert_tree(
+        path!("/other"),
+        json!({
+            "Other.csproj": "<Project Sdk=\"Microsoft.NET.Sdk\" />",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), [Path::new(path!("/dir"))], cx).await;
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/dir/Program.cs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let build_output = format!(
+        "{program}(1,21): error CS1002: ; expected [{project}]\n\
+         {project} : warning NU1603: Newtonsoft.Json 13.0.1 was not found. [{project}]\n\
+         {outside}(1,1): error CS0103: The name 'x' does not exist [{project}]\n",
+        program = path!("/dir/Program.cs"),
+        project = path!("/dir/App.csproj"),
+        outside = path!("/elsewhere/Other.cs"),
+    );
+    let build_diagnostics =
+        project.read_with(cx, |project, _| project.dotnet_build_diagnostics().clone());
+    build_diagnostics.publish(dotnet::diagnostics::parse_build_output(
+        &build_output,
+        Path::new(path!("/dir")),
+    ));
+    cx.run_until_parked();
+
+    // Files outside of the project are left out
+    project.update(cx, |project, cx| {
+        assert_eq!(
+            project.diagnostic_summary(false, cx),
+            DiagnosticSummary {
+                error_count: 1,
+                warning_count: 1,
+            }
+        );
+    });
+    buffer.update(cx, |buffer, _| {
+        let diagnostics = buffer
+            .snapshot()
+            .diagnostics_in_range::<_, Point>(0..buffer.len(), false)
+            .map(|entry| {
+                (
+                    entry.range,
+                    entry.diagnostic.source.clone(),
+                    entry.diagnostic.code.clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            diagnostics,
+            [(
+                Point::new(0, 19)..Point::new(0, 20),
+                Some(DOTNET_BUILD_DIAGNOSTICS_SOURCE.to_string()),
+                Some(NumberOrString::String("CS1002".to_string())),
+            )]
+        );
+    });
+
+    // Builds of another project leave them alone
+    let other_project = Project::test(fs, [Path::new(path!("/other"))], cx).await;
+    other_project.read_with(cx, |other_project, _| {
+        other_project.dotnet_build_diagnostics().publish(Vec::new())
+    });
+    cx.run_until_parked();
+    project.update(cx, |project, cx| {
+        assert_eq!(
+            project.diagnostic_summary(false, cx),
+            DiagnosticSummary {
+                error_count: 1,
+                warning_count: 1,
+            }
+        );
+    });
+
+    // The next build replaces the diagnostics of the previous one
+    build_diagnostics.publish(Vec::new());
+    cx.run_until_parked();
+    project.update(cx, |project, cx| {
+        assert_eq!(
+            project.diagnostic_summary(false, cx),
+            DiagnosticSummary::default()
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_dotnet_build_log_diagnostics(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "App.csproj": "<Project Sdk=\"Microsoft.NET.Sdk\" />",
+            "Program.cs": "Console.WriteLine(1)\n",
+            "Util.cs": "class Util { int x }\n",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [Path::new(path!("/dir"))], cx).await;
+    let build_diagnostics =
+        project.read_with(cx, |project, _| project.dotnet_build_diagnostics().clone());
+    // Runs a `dotnet build` task whose MSBuild file logger writes `output`
+    let run_build = |output: String| {
+        let mut spawn_in_terminal = SpawnInTerminal {
+            command: Some("dotnet".to_string()),
+            args: vec!["build".to_string()],
+            cwd: Some(PathBuf::from(path!("/dir"))),
+            ..SpawnInTerminal::default()
+        };
+        let build_log = DotNetBuildLog::attach(&mut spawn_in_terminal).unwrap();
+        let log_path = spawn_in_terminal
+            .args
+            .iter()
+            .find_map(|arg| arg.strip_prefix("-flp:LogFile="))
+            .and_then(|options| options.split(';').next())
+            .unwrap();
+        std::fs::write(log_path, output).unwrap();
+        build_log.publish(&build_diagnostics);
+    };
+    let paths_with_diagnostics = |cx: &mut gpui::TestAppContext| {
+        project.read_with(cx, |project, cx| {
+            project
+                .diagnostic_summaries(false, cx)
+                .map(|(path, server_id, summary)| {
+                    (path.path.as_unix_str().to_string(), server_id, summary)
+                })
+                .sorted_by(|(a, _, _), (b, _, _)| a.cmp(b))
+                .collect::<Vec<_>>()
+        })
+    };
+
+    run_build(format!(
+        "{program}(1,21): error CS1002: ; expected [{project}]\n\
+         {util}(1,20): warning CS0169: The field 'Util.x' is never used [{project}]\n",
+        program = path!("/dir/Program.cs"),
+        util = path!("/dir/Util.cs"),
+        project = path!("/dir/App.csproj"),
+    ));
+    cx.run_until_parked();
+    assert_eq!(
+        paths_with_diagnostics(cx),
+        [
+            (
+                "Program.cs".to_string(),
+                DOTNET_BUILD_DIAGNOSTICS_ID,
+                DiagnosticSummary {
+                    error_count: 1,
+                    warning_count: 0,
+                }
+            ),
+            (
+                "Util.cs".to_string(),
+                DOTNET_BUILD_DIAGNOSTICS_ID,
+                DiagnosticSummary {
+                    error_count: 0,
+                    warning_count: 1,
+                }
+            ),
+        ]
+    );
+
+    // Files without diagnostics in the next build are cleared
+    run_build(format!(
+        "{util}(1,20): warning CS0169: The field 'Util.x' is never used [{project}]\n",
+        util = path!("/dir/Util.cs"),
+        project = path!("/dir/App.csproj"),
+    ));
+    cx.run_until_parked();
+    assert_eq!(
+        paths_with_diagnostics(cx),
+        [(
+            "Util.cs".to_string(),
+            DOTNET_BUILD_DIAGNOSTICS_ID,
+            DiagnosticSummary {
+                error_count: 0,
+                warning_count: 1,
+            }
+        )]
+    );
+
+    run_build(String::new());
+    cx.run_until_parked();
+    assert!(paths_with_diagnostics(cx).is_empty());
+}
+
 #[gpui::test]
 async fn test_edits_from_lsp2_with_past_version(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -3424,6 +3638,520 @@ async fn test_definition(cx: &mut gpui::TestAppContext) {
     }
 }
 
+#[gpui::test]
+async fn test_call_hierarchy(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.rs": "fn a() {\n    b();\n}\n",
+            "b.rs": "pub fn b() {}\n",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                call_hierarchy_provider: Some(lsp::CallHierarchyServerCapability::Simple(true)),
+                ..lsp::ServerCapabilities::default()
+            },
+            ..FakeLspAdapter::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/b.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let fake_server = fake_servers.next().await.unwrap();
+    let item = |name: &str, path: &str, range: lsp::Range, selection_range: lsp::Range| {
+        lsp::CallHierarchyItem {
+            name: name.to_string(),
+            kind: lsp::SymbolKind::FUNCTION,
+            tags: None,
+            detail: None,
+            uri: lsp::Uri::from_file_path(path).unwrap(),
+            range,
+            selection_range,
+            data: Some(json!({ "id": name })),
+        }
+    };
+    let b_item = item(
+        "b",
+        path!("/dir/b.rs"),
+        lsp::Range::new(lsp::Position::new(0, 0), lsp::Position::new(0, 13)),
+        lsp::Range::new(lsp::Position::new(0, 7), lsp::Position::new(0, 8)),
+    );
+    let a_item = item(
+        "a",
+        path!("/dir/a.rs"),
+        lsp::Range::new(lsp::Position::new(0, 0), lsp::Position::new(2, 1)),
+        lsp::Range::new(lsp::Position::new(0, 3), lsp::Position::new(0, 4)),
+    );
+    fake_server.set_request_handler::<lsp::request::CallHierarchyPrepare, _, _>({
+        let b_item = b_item.clone();
+        move |params, _| {
+            let b_item = b_item.clone();
+            async move {
+                assert_eq!(
+                    params.text_document_position_params.position,
+                    lsp::Position::new(0, 7)
+                );
+                Ok(Some(vec![b_item]))
+            }
+        }
+    });
+    fake_server.set_request_handler::<lsp::request::CallHierarchyIncomingCalls, _, _>(
+        move |params, _| {
+            let a_item = a_item.clone();
+            async move {
+                // The prepared item is passed back as is
+                assert_eq!(params.item.data, Some(json!({ "id": "b" })));
+                Ok(Some(vec![lsp::CallHierarchyIncomingCall {
+                    from: a_item,
+                    from_ranges: vec![lsp::Range::new(
+                        lsp::Position::new(1, 4),
+                        lsp::Position::new(1, 5),
+                    )],
+                }]))
+            }
+        },
+    );
+
+    let hierarchy = project
+        .update(cx, |project, cx| {
+            project.call_hierarchy(&buffer, 7, CallHierarchyDirection::Incoming, cx)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+
+    cx.update(|cx| {
+        assert_eq!(hierarchy.item.name, "b");
+        assert_eq!(hierarchy.item.location.buffer, buffer);
+        assert_eq!(
+            hierarchy
+                .item
+                .selection_range
+                .to_offset(hierarchy.item.location.buffer.read(cx)),
+            7..8
+        );
+
+        assert_eq!(hierarchy.calls.len(), 1);
+        let call = &hierarchy.calls[0];
+        assert_eq!(call.item.name, "a");
+        let caller_buffer = call.item.location.buffer.read(cx);
+        assert_eq!(
+            caller_buffer
+                .file()
+                .unwrap()
+                .as_local()
+                .unwrap()
+                .abs_path(cx),
+            Path::new(path!("/dir/a.rs")),
+        );
+        assert_eq!(call.call_sites.len(), 1);
+        assert_eq!(call.call_sites[0].buffer, call.item.location.buffer);
+        assert_eq!(call.call_sites[0].range.to_offset(caller_buffer), 13..14);
+    });
+}
+
+#[gpui::test]
+async fn test_type_hierarchy(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "shape.rs": "pub trait Shape {}\n",
+            "circle.rs": "struct Circle;\nimpl Shape for Circle {}\n",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp("Rust", FakeLspAdapter::default());
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/circle.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let fake_server = fake_servers.next().await.unwrap();
+    let item = |name: &str, kind, path: &str, range: lsp::Range, selection_range: lsp::Range| {
+        lsp::TypeHierarchyItem {
+            name: name.to_string(),
+            kind,
+            tags: None,
+            detail: None,
+            uri: lsp::Uri::from_file_path(path).unwrap(),
+            range,
+            selection_range,
+            data: Some(json!({ "id": name })),
+        }
+    };
+    let circle_item = item(
+        "Circle",
+        lsp::SymbolKind::STRUCT,
+        path!("/dir/circle.rs"),
+        lsp::Range::new(lsp::Position::new(0, 0), lsp::Position::new(0, 14)),
+        lsp::Range::new(lsp::Position::new(0, 7), lsp::Position::new(0, 13)),
+    );
+    let shape_item = item(
+        "Shape",
+        lsp::SymbolKind::INTERFACE,
+        path!("/dir/shape.rs"),
+        lsp::Range::new(lsp::Position::new(0, 0), lsp::Position::new(0, 18)),
+        lsp::Range::new(lsp::Position::new(0, 10), lsp::Position::new(0, 15)),
+    );
+    fake_server.set_request_handler::<lsp::request::TypeHierarchyPrepare, _, _>(
+        move |params, _| {
+            let circle_item = circle_item.clone();
+            async move {
+                assert_eq!(
+                    params.text_document_position_params.position,
+                    lsp::Position::new(0, 8)
+                );
+                Ok(Some(vec![circle_item]))
+            }
+        },
+    );
+    fake_server.set_request_handler::<lsp::request::TypeHierarchySupertypes, _, _>(
+        move |params, _| {
+            let shape_item = shape_item.clone();
+            async move {
+                assert_eq!(params.item.data, Some(json!({ "id": "Circle" })));
+                Ok(Some(vec![shape_item]))
+            }
+        },
+    );
+
+    let hierarchy = project
+        .update(cx, |project, cx| {
+            project.type_hierarchy(&buffer, 8, TypeHierarchyDirection::Supertypes, cx)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+
+    cx.update(|cx| {
+        assert_eq!(hierarchy.item.name, "Circle");
+        assert_eq!(hierarchy.item.location.buffer, buffer);
+        assert_eq!(hierarchy.types.len(), 1);
+        let supertype = &hierarchy.types[0];
+        assert_eq!(supertype.name, "Shape");
+        assert_eq!(supertype.kind, lsp::SymbolKind::INTERFACE);
+        let supertype_buffer = supertype.location.buffer.read(cx);
+        assert_eq!(
+            supertype_buffer
+                .file()
+                .unwrap()
+                .as_local()
+                .unwrap()
+                .abs_path(cx),
+            Path::new(path!("/dir/shape.rs")),
+        );
+        assert_eq!(
+            supertype.selection_range.to_offset(supertype_buffer),
+            10..15
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_semantic_tokens(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "main.rs": "fn main() {\n    let mut x = 1;\n    x += 1;\n}\n",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                semantic_tokens_provider: Some(
+                    lsp::SemanticTokensServerCapabilities::SemanticTokensOptions(
+                        lsp::SemanticTokensOptions {
+                            legend: lsp::SemanticTokensLegend {
+                                token_types: vec![
+                                    lsp::SemanticTokenType::VARIABLE,
+                                    lsp::SemanticTokenType::FUNCTION,
+                                ],
+                                token_modifiers: vec![lsp::SemanticTokenModifier::new("mutable")],
+                            },
+                            full: Some(lsp::SemanticTokensFullOptions::Delta { delta: Some(true) }),
+                            ..Default::default()
+                        },
+                    ),
+                ),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/main.rs"), cx)
+        })
+        .await
+        .unwrap();
+    let fake_server = fake_servers.next().await.unwrap();
+
+    let token =
+        |delta_line, delta_start, length, token_type, token_modifiers_bitset| lsp::SemanticToken {
+            delta_line,
+            delta_start,
+            length,
+            token_type,
+            token_modifiers_bitset,
+        };
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    fake_server.set_request_handler::<lsp::request::SemanticTokensFullRequest, _, _>({
+        let requests = requests.clone();
+        move |_, _| {
+            requests.lock().push("full".to_string());
+            async move {
+                Ok(Some(lsp::SemanticTokensResult::Tokens(
+                    lsp::SemanticTokens {
+                        result_id: Some("1".to_string()),
+                        data: vec![
+                            token(0, 3, 4, 1, 0),
+                            token(1, 12, 1, 0, 1),
+                            token(1, 4, 1, 0, 1),
+                        ],
+                    },
+                )))
+            }
+        }
+    });
+    fake_server.set_request_handler::<lsp::request::SemanticTokensFullDeltaRequest, _, _>({
+        let requests = requests.clone();
+        move |params, _| {
+            requests
+                .lock()
+                .push(format!("delta {}", params.previous_result_id));
+            async move {
+                Ok(Some(lsp::SemanticTokensFullDeltaResult::TokensDelta(
+                    lsp::SemanticTokensDelta {
+                        result_id: Some("2".to_string()),
+                        edits: vec![lsp::SemanticTokensEdit {
+                            start: 15,
+                            delete_count: 0,
+                            data: Some(vec![token(1, 4, 1, 0, 1)]),
+                        }],
+                    },
+                )))
+            }
+        }
+    });
+
+    let lsp_store = project.read_with(cx, |project, _| project.lsp_store());
+    let fetch_tokens = |cx: &mut gpui::TestAppContext| {
+        let task = lsp_store
+            .update(cx, |lsp_store, cx| {
+                lsp_store.semantic_tokens(None, buffer.clone(), cx)
+            })
+            .unwrap();
+        cx.executor().advance_clock(Duration::from_millis(30));
+        cx.run_until_parked();
+        task
+    };
+    let describe_tokens = |tokens: &BufferSemanticTokens, cx: &mut gpui::TestAppContext| {
+        buffer.read_with(cx, |buffer, _| {
+            let tokens = &tokens.servers[&fake_server.server.server_id()];
+            tokens
+                .tokens
+                .iter()
+                .map(|token| {
+                    let modifiers = tokens.legend.token_modifiers(token).join(",");
+                    format!(
+                        "{:?} {} {modifiers}",
+                        token.range.to_offset(buffer),
+                        tokens.legend.token_type(token).unwrap(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        })
+    };
+
+    let tokens = fetch_tokens(cx).await.unwrap();
+    assert_eq!(
+        describe_tokens(&tokens, cx),
+        [
+            "3..7 function ",
+            "24..25 variable mutable",
+            "35..36 variable mutable"
+        ]
+    );
+    assert_eq!(*requests.lock(), ["full"]);
+
+    let cached_tokens = fetch_tokens(cx).await.unwrap();
+    assert_eq!(cached_tokens.cache_version, tokens.cache_version);
+    assert_eq!(*requests.lock(), ["full"]);
+
+    buffer.update(cx, |buffer, cx| {
+        buffer.edit([(43..43, "    x += 2;\n")], None, cx)
+    });
+    let tokens = fetch_tokens(cx).await.unwrap();
+    assert_eq!(
+        describe_tokens(&tokens, cx),
+        [
+            "3..7 function ",
+            "24..25 variable mutable",
+            "35..36 variable mutable",
+            "47..48 variable mutable",
+        ]
+    );
+    assert_eq!(*requests.lock(), ["full", "delta 1"]);
+}
+
+#[gpui::test]
+async fn test_folding_and_selection_ranges(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "main.rs": "fn main() {\n    let x = 1;\n    x;\n}\n",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                folding_range_provider: Some(lsp::FoldingRangeProviderCapability::Simple(true)),
+                selection_range_provider: Some(lsp::SelectionRangeProviderCapability::Simple(true)),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/main.rs"), cx)
+        })
+        .await
+        .unwrap();
+    let fake_server = fake_servers.next().await.unwrap();
+
+    fake_server.set_request_handler::<lsp::request::FoldingRangeRequest, _, _>(|_, _| async move {
+        let folding_range = |start_line, end_line, end_character| lsp::FoldingRange {
+            start_line,
+            start_character: Some(4),
+            end_line,
+            end_character,
+            kind: None,
+            collapsed_text: None,
+        };
+        Ok(Some(vec![
+            folding_range(0, 2, None),
+            folding_range(0, 3, Some(0)),
+            folding_range(1, 1, None),
+            folding_range(0, 10, None),
+        ]))
+    });
+    fake_server.set_request_handler::<lsp::request::SelectionRangeRequest, _, _>(
+        |params, _| async move {
+            let range = |start_line, start_character, end_line, end_character| lsp::Range {
+                start: lsp::Position::new(start_line, start_character),
+                end: lsp::Position::new(end_line, end_character),
+            };
+            assert_eq!(
+                params.positions,
+                [lsp::Position::new(1, 8), lsp::Position::new(2, 4)]
+            );
+            Ok(Some(vec![
+                lsp::SelectionRange {
+                    range: range(1, 8, 1, 9),
+                    parent: Some(Box::new(lsp::SelectionRange {
+                        range: range(1, 4, 1, 14),
+                        parent: Some(Box::new(lsp::SelectionRange {
+                            range: range(0, 0, 3, 1),
+                            parent: None,
+                        })),
+                    })),
+                },
+                lsp::SelectionRange {
+                    range: range(2, 4, 2, 5),
+                    parent: None,
+                },
+            ]))
+        },
+    );
+
+    let folding_ranges = project
+        .update(cx, |project, cx| project.folding_ranges(&buffer, cx))
+        .await
+        .unwrap();
+    buffer.read_with(cx, |buffer, _| {
+        assert_eq!(
+            folding_ranges
+                .iter()
+                .map(|range| range.to_offset(buffer))
+                .collect::<Vec<_>>(),
+            [11..33, 11..34],
+            "folds start at the end of their first line, and single-line or invalid ranges are dropped"
+        );
+    });
+
+    let selection_ranges = project
+        .update(cx, |project, cx| {
+            project.selection_ranges(&buffer, [Point::new(1, 8), Point::new(2, 4)], cx)
+        })
+        .await
+        .unwrap();
+    buffer.read_with(cx, |buffer, _| {
+        assert_eq!(
+            selection_ranges
+                .iter()
+                .map(|ranges| {
+                    ranges
+                        .iter()
+                        .map(|range| range.to_offset(buffer))
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>(),
+            [vec![20..21, 16..26, 0..35], vec![31..32]]
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_completions_with_text_edit(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -5564,29 +6292,159 @@ async fn test_rename(cx: &mut gpui::TestAppContext) {
                     .into_iter()
                     .collect(),
                 ),
-                ..Default::default()
-            }))
+                ..Default::default()
+            }))
+        })
+        .next()
+        .await
+        .unwrap();
+    let mut transaction = response.await.unwrap().0;
+    assert_eq!(transaction.len(), 2);
+    assert_eq!(
+        transaction
+            .remove_entry(&buffer)
+            .unwrap()
+            .0
+            .update(cx, |buffer, _| buffer.text()),
+        "const THREE: usize = 1;"
+    );
+    assert_eq!(
+        transaction
+            .into_keys()
+            .next()
+            .unwrap()
+            .update(cx, |buffer, _| buffer.text()),
+        "const TWO: usize = one::THREE + one::THREE;"
+    );
+}
+
+#[gpui::test]
+async fn test_rename_edits(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "one.rs": "const ONE: usize = 1;",
+            "two.rs": "const TWO: usize = one::ONE + one::ONE;"
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                rename_provider: Some(lsp::OneOf::Left(true)),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/one.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let fake_server = fake_servers.next().await.unwrap();
+    fake_server.set_request_handler::<lsp::request::Rename, _, _>(|_, _| async move {
+        let edit = |path: &str, edits: Vec<lsp::TextEdit>| {
+            lsp::DocumentChangeOperation::Edit(lsp::TextDocumentEdit {
+                text_document: lsp::OptionalVersionedTextDocumentIdentifier {
+                    uri: lsp::Uri::from_file_path(path).unwrap(),
+                    version: None,
+                },
+                edits: edits.into_iter().map(lsp::Edit::Plain).collect(),
+            })
+        };
+        let text_edit = |start, end, new_text: &str| {
+            lsp::TextEdit::new(
+                lsp::Range::new(lsp::Position::new(0, start), lsp::Position::new(0, end)),
+                new_text.to_string(),
+            )
+        };
+        Ok(Some(lsp::WorkspaceEdit {
+            document_changes: Some(lsp::DocumentChanges::Operations(vec![
+                edit(path!("/dir/one.rs"), vec![text_edit(6, 9, "THREE")]),
+                lsp::DocumentChangeOperation::Op(lsp::ResourceOp::Rename(lsp::RenameFile {
+                    old_uri: lsp::Uri::from_file_path(path!("/dir/two.rs")).unwrap(),
+                    new_uri: lsp::Uri::from_file_path(path!("/dir/three.rs")).unwrap(),
+                    options: None,
+                    annotation_id: None,
+                })),
+                edit(
+                    path!("/dir/three.rs"),
+                    vec![text_edit(24, 27, "THREE"), text_edit(35, 38, "THREE")],
+                ),
+                lsp::DocumentChangeOperation::Op(lsp::ResourceOp::Create(lsp::CreateFile {
+                    uri: lsp::Uri::from_file_path(path!("/dir/four.rs")).unwrap(),
+                    options: None,
+                    annotation_id: None,
+                })),
+                edit(
+                    path!("/dir/four.rs"),
+                    vec![text_edit(0, 0, "const FOUR: usize = one::THREE;")],
+                ),
+            ])),
+            ..Default::default()
+        }))
+    });
+
+    let edit = project
+        .update(cx, |project, cx| {
+            project.rename_edits(buffer.clone(), 7, "THREE".to_string(), cx)
         })
-        .next()
         .await
+        .unwrap()
         .unwrap();
-    let mut transaction = response.await.unwrap().0;
-    assert_eq!(transaction.len(), 2);
+    let [
+        WorkspaceEditOperation::EditBuffer { buffer: one, .. },
+        WorkspaceEditOperation::RenameFile { .. },
+        WorkspaceEditOperation::EditBuffer { buffer: two, .. },
+        WorkspaceEditOperation::CreateFile { text, .. },
+    ] = edit.operations.as_slice()
+    else {
+        panic!("unexpected operations");
+    };
+    assert_eq!(*one, buffer);
+    assert_eq!(text, "const FOUR: usize = one::THREE;");
+    let two = two.clone();
     assert_eq!(
-        transaction
-            .remove_entry(&buffer)
-            .unwrap()
-            .0
-            .update(cx, |buffer, _| buffer.text()),
-        "const THREE: usize = 1;"
+        two.read_with(cx, |buffer, _| buffer.text()),
+        "const TWO: usize = one::ONE + one::ONE;",
+        "resolving the edits should not apply them"
+    );
+
+    // Leave out the edit of the first file.
+    let edit = ResolvedWorkspaceEdit {
+        operations: edit.operations[1..].to_vec(),
+    };
+    let transaction = project
+        .update(cx, |project, cx| project.apply_workspace_edit(edit, cx))
+        .await
+        .unwrap();
+    cx.run_until_parked();
+
+    assert_eq!(transaction.0.len(), 1);
+    assert_eq!(
+        buffer.read_with(cx, |buffer, _| buffer.text()),
+        "const ONE: usize = 1;"
     );
+    two.read_with(cx, |buffer, cx| {
+        assert_eq!(buffer.text(), "const TWO: usize = one::THREE + one::THREE;");
+        assert_eq!(buffer.file().unwrap().path().as_ref(), rel_path("three.rs"));
+    });
     assert_eq!(
-        transaction
-            .into_keys()
-            .next()
-            .unwrap()
-            .update(cx, |buffer, _| buffer.text()),
-        "const TWO: usize = one::THREE + one::THREE;"
+        fs.load(Path::new(path!("/dir/four.rs"))).await.unwrap(),
+        "const FOUR: usize = one::THREE;"
     );
 }
 
@@ -10232,6 +11090,127 @@ async fn test_git_worktrees_and_submodules(cx: &mut gpui::TestAppContext) {
             StatusCode::Modified.worktree(),
         );
     });
+
+    // The parent repository knows about its submodule, and where it's checked out.
+    let submodule = Submodule {
+        path: repo_path("subdir/some-submodule"),
+        status: SubmoduleStatus::OutOfDate,
+        is_dirty: true,
+        sha: Oid::from_bytes(&[1; 20]).unwrap(),
+        head_sha: Some(Oid::from_bytes(&[2; 20]).unwrap()),
+    };
+    fs.with_git_state(path!("/project/.git").as_ref(), true, {
+        let submodule = submodule.clone();
+        move |state| state.submodules = vec![submodule]
+    })
+    .unwrap();
+    cx.run_until_parked();
+
+    project.update(cx, |project, cx| {
+        let worktree_id = project.worktrees(cx).next().unwrap().read(cx).id();
+        let git_store = project.git_store().read(cx);
+        let (parent_repo, found_submodule) = git_store
+            .submodule_for_project_path(
+                &ProjectPath {
+                    worktree_id,
+                    path: rel_path("subdir/some-submodule").into(),
+                },
+                cx,
+            )
+            .unwrap();
+        pretty_assertions::assert_eq!(found_submodule, submodule);
+        pretty_assertions::assert_eq!(
+            parent_repo.read(cx).work_directory_abs_path,
+            Path::new(path!("/project")).into(),
+        );
+        assert_eq!(
+            git_store.submodule_repository(&parent_repo, &submodule.path, cx),
+            Some(submodule_repo.clone())
+        );
+        assert_eq!(
+            git_store.parent_repository(&submodule_repo, cx),
+            Some((parent_repo.clone(), submodule.clone()))
+        );
+        assert!(
+            git_store
+                .submodule_for_project_path(
+                    &ProjectPath {
+                        worktree_id,
+                        path: rel_path("subdir").into(),
+                    },
+                    cx,
+                )
+                .is_none()
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_submodule_operations(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+    let fs = FakeFs::new(cx.background_executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            ".git": {},
+            ".gitmodules": "",
+            "a.txt": "A",
+        }),
+    )
+    .await;
+    let submodules = ["first", "second"].map(|path| Submodule {
+        path: repo_path(path),
+        status: SubmoduleStatus::Uninitialized,
+        is_dirty: false,
+        sha: Oid::from_bytes(&[1; 20]).unwrap(),
+        head_sha: Some(Oid::from_bytes(&[1; 20]).unwrap()),
+    });
+    fs.with_git_state(path!("/project/.git").as_ref(), true, |state| {
+        state.submodules = submodules.to_vec()
+    })
+    .unwrap();
+
+    let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+    cx.run_until_parked();
+    let repository = project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+
+    for (operation, paths) in [
+        (SubmoduleOperation::Init, vec![]),
+        (SubmoduleOperation::Update, vec![repo_path("first")]),
+        (SubmoduleOperation::Sync, vec![]),
+    ] {
+        let askpass = cx.update(|cx| AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}));
+        repository
+            .update(cx, |repository, cx| {
+                repository.submodule_operation(operation, paths, askpass, cx)
+            })
+            .await
+            .unwrap()
+            .unwrap();
+    }
+    cx.run_until_parked();
+
+    fs.with_git_state(path!("/project/.git").as_ref(), false, |state| {
+        assert_eq!(
+            state.submodule_operations,
+            [
+                (SubmoduleOperation::Init, vec![]),
+                (SubmoduleOperation::Update, vec![repo_path("first")]),
+                (SubmoduleOperation::Sync, vec![]),
+            ]
+        );
+    })
+    .unwrap();
+    repository.read_with(cx, |repository, _| {
+        assert_eq!(
+            repository.submodule(&repo_path("first")).unwrap().status,
+            SubmoduleStatus::UpToDate
+        );
+        assert_eq!(
+            repository.submodule(&repo_path("second")).unwrap().status,
+            SubmoduleStatus::Uninitialized
+        );
+    });
 }
 
 #[gpui::test]
@@ -10281,6 +11260,282 @@ async fn test_repository_deduplication(cx: &mut gpui::TestAppContext) {
     pretty_assertions::assert_eq!(repos, [Path::new(path!("/root/project")).into()]);
 }
 
+#[gpui::test]
+async fn test_interactive_rebase(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+    let fs = FakeFs::new(cx.background_executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            ".git": {},
+            "a.txt": "A",
+        }),
+    )
+    .await;
+    fs.with_git_state(path!("/project/.git").as_ref(), true, |state| {
+        state.set_commits(fake_commits(&["Add d", "Add c", "Add b", "Add a"]))
+    })
+    .unwrap();
+
+    let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+    cx.run_until_parked();
+    let repository = project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+
+    let commits = repository
+        .update(cx, |repository, _| repository.recent_commits(10))
+        .await
+        .unwrap()
+        .unwrap();
+    let base = commits[3].sha.to_string();
+    let mut todo = repository
+        .update(cx, |repository, _| repository.rebase_todo(base.clone()))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        todo.iter()
+            .map(|entry| entry.subject.as_ref())
+            .collect::<Vec<_>>(),
+        ["Add b", "Add c", "Add d"]
+    );
+
+    // Stop at the first commit, and continue once it's edited.
+    todo[0].action = RebaseTodoAction::Edit;
+    todo[2].action = RebaseTodoAction::Drop;
+    let outcome = repository
+        .update(cx, |repository, _| repository.rebase(base.clone(), todo))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(outcome, RebaseOutcome::Paused);
+    assert_eq!(recent_subjects(&repository, cx).await, ["Add b", "Add a"]);
+    let outcome = repository
+        .update(cx, |repository, _| {
+            repository.rebase_control(RebaseControl::Continue)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(outcome, RebaseOutcome::Finished);
+    assert_eq!(
+        recent_subjects(&repository, cx).await,
+        ["Add c", "Add b", "Add a"]
+    );
+
+    // Aborting a paused rebase restores the commits it started from.
+    let mut todo = repository
+        .update(cx, |repository, _| repository.rebase_todo(base.clone()))
+        .await
+        .unwrap()
+        .unwrap();
+    todo.reverse();
+    todo[0].action = RebaseTodoAction::Edit;
+    let outcome = repository
+        .update(cx, |repository, _| repository.rebase(base.clone(), todo))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(outcome, RebaseOutcome::Paused);
+    assert_eq!(recent_subjects(&repository, cx).await, ["Add c", "Add a"]);
+    repository
+        .update(cx, |repository, _| {
+            repository.rebase_control(RebaseControl::Abort)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        recent_subjects(&repository, cx).await,
+        ["Add c", "Add b", "Add a"]
+    );
+    assert!(
+        repository
+            .update(cx, |repository, _| {
+                repository.rebase_control(RebaseControl::Continue)
+            })
+            .await
+            .unwrap()
+            .is_err()
+    );
+}
+
+#[gpui::test]
+async fn test_cherry_pick_revert_and_tags(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+    let fs = FakeFs::new(cx.background_executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            ".git": {},
+            "a.txt": "A",
+        }),
+    )
+    .await;
+    let mut feature_commits = fake_commits(&["Fix bug", "Add feature"]);
+    for commit in &mut feature_commits {
+        commit.sha = commit.sha.replace('0', "f").into();
+    }
+    let fix_sha = feature_commits[0].sha.to_string();
+    let feature_sha = feature_commits[1].sha.to_string();
+    fs.with_git_state(path!("/project/.git").as_ref(), true, |state| {
+        state.set_commits(fake_commits(&["Add b", "Add a"]));
+        state.refs.insert("feature".into(), fix_sha.clone());
+        state.other_commits = feature_commits;
+        state.conflicting_commits.insert(feature_sha.clone());
+        state
+            .remotes
+            .insert("origin".into(), "git@example.com:repo.git".into());
+    })
+    .unwrap();
+
+    let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+    cx.run_until_parked();
+    let repository = project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+
+    repository
+        .update(cx, |repository, _| repository.cherry_pick("feature".into()))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        recent_subjects(&repository, cx).await,
+        ["Fix bug", "Add b", "Add a"]
+    );
+    let head_sha = repository
+        .update(cx, |repository, _| repository.recent_commits(1))
+        .await
+        .unwrap()
+        .unwrap()[0]
+        .sha
+        .to_string();
+    assert_ne!(head_sha, fix_sha);
+    repository
+        .update(cx, |repository, _| repository.revert(head_sha.clone()))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        recent_subjects(&repository, cx).await,
+        ["Revert \"Fix bug\"", "Fix bug", "Add b", "Add a"]
+    );
+
+    // A conflicting cherry-pick leaves HEAD alone until it's continued, and can be aborted.
+    assert!(
+        repository
+            .update(cx, |repository, _| repository
+                .cherry_pick(feature_sha.clone()))
+            .await
+            .unwrap()
+            .is_err()
+    );
+    assert_eq!(recent_subjects(&repository, cx).await.len(), 4);
+    assert!(
+        repository
+            .update(cx, |repository, _| {
+                repository.sequencer_control(SequencerOperation::Revert, RebaseControl::Abort)
+            })
+            .await
+            .unwrap()
+            .is_err()
+    );
+    repository
+        .update(cx, |repository, _| {
+            repository.sequencer_control(SequencerOperation::CherryPick, RebaseControl::Abort)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(recent_subjects(&repository, cx).await.len(), 4);
+    fs.with_git_state(path!("/project/.git").as_ref(), true, |state| {
+        state.conflicting_commits.insert(feature_sha.clone());
+    })
+    .unwrap();
+    assert!(
+        repository
+            .update(cx, |repository, _| repository
+                .cherry_pick(feature_sha.clone()))
+            .await
+            .unwrap()
+            .is_err()
+    );
+    repository
+        .update(cx, |repository, _| {
+            repository.sequencer_control(SequencerOperation::CherryPick, RebaseControl::Continue)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        recent_subjects(&repository, cx).await,
+        [
+            "Add feature",
+            "Revert \"Fix bug\"",
+            "Fix bug",
+            "Add b",
+            "Add a"
+        ]
+    );
+
+    repository
+        .update(cx, |repository, _| {
+            repository.create_tag("v1".into(), None, None)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    let tags = repository
+        .update(cx, |repository, _| repository.tags())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(tags.len(), 1);
+    assert_eq!(tags[0].name.as_ref(), "v1");
+    let askpass = cx.update(|cx| AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}));
+    repository
+        .update(cx, |repository, cx| {
+            repository.push_tag("v1".into(), "origin".into(), askpass, cx)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    fs.with_git_state(path!("/project/.git").as_ref(), false, |state| {
+        assert_eq!(
+            state.pushed_tags,
+            [("origin".to_string(), "v1".to_string())]
+        );
+    })
+    .unwrap();
+}
+
+/// Builds a linear history of commits with the given subjects, newest first.
+fn fake_commits(subjects: &[&str]) -> Vec<CommitSummary> {
+    subjects
+        .iter()
+        .enumerate()
+        .map(|(ix, subject)| CommitSummary {
+            sha: format!("{:040x}", subjects.len() - ix).into(),
+            subject: subject.to_string().into(),
+            commit_timestamp: (subjects.len() - ix) as i64,
+            author_name: "Fake Author".into(),
+            has_parent: ix + 1 < subjects.len(),
+        })
+        .collect()
+}
+
+async fn recent_subjects(
+    repository: &Entity<Repository>,
+    cx: &mut gpui::TestAppContext,
+) -> Vec<String> {
+    repository
+        .update(cx, |repository, _| repository.recent_commits(usize::MAX))
+        .await
+        .unwrap()
+        .unwrap()
+        .into_iter()
+        .map(|commit| commit.subject.to_string())
+        .collect()
+}
+
 #[gpui::test]
 async fn test_buffer_changed_file_path_updates_git_diff(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -19,7 +19,7 @@ use util::ResultExt;
 
 use crate::{
     BasicContextProvider, Inventory, ProjectEnvironment, buffer_store::BufferStore,
-    worktree_store::WorktreeStore,
+    dotnet_configurations::DotNetConfigurations, worktree_store::WorktreeStore,
 };
 
 // platform-dependent warning
@@ -34,6 +34,7 @@ pub struct StoreState {
     buffer_store: WeakEntity<BufferStore>,
     worktree_store: Entity<WorktreeStore>,
     toolchain_store: Arc<dyn LanguageToolchainStore>,
+    dotnet_configurations: DotNetConfigurations,
 }
 
 enum StoreMode {
@@ -163,6 +164,7 @@ impl TaskStore {
         worktree_store: Entity<WorktreeStore>,
         toolchain_store: Arc<dyn LanguageToolchainStore>,
         environment: Entity<ProjectEnvironment>,
+        dotnet_configurations: DotNetConfigurations,
         cx: &mut Context<Self>,
     ) -> Self {
         Self::Functional(StoreState {
@@ -174,6 +176,7 @@ impl TaskStore {
             buffer_store,
             toolchain_store,
             worktree_store,
+            dotnet_configurations,
         })
     }
 
@@ -183,6 +186,7 @@ impl TaskStore {
         toolchain_store: Arc<dyn LanguageToolchainStore>,
         upstream_client: AnyProtoClient,
         project_id: u64,
+        dotnet_configurations: DotNetConfigurations,
         cx: &mut Context<Self>,
     ) -> Self {
         Self::Functional(StoreState {
@@ -194,38 +198,46 @@ impl TaskStore {
             buffer_store,
             toolchain_store,
             worktree_store,
+            dotnet_configurations,
         })
     }
 
     pub fn task_context_for_location(
         &self,
-        captured_variables: TaskVariables,
+        mut captured_variables: TaskVariables,
         location: Location,
         cx: &mut App,
     ) -> Task<Option<TaskContext>> {
         match self {
-            TaskStore::Functional(state) => match &state.mode {
-                StoreMode::Local { environment, .. } => local_task_context_for_location(
-                    state.worktree_store.clone(),
-                    state.toolchain_store.clone(),
-                    environment.clone(),
-                    captured_variables,
-                    location,
-                    cx,
-                ),
-                StoreMode::Remote {
-                    upstream_client,
-                    project_id,
-                } => remote_task_context_for_location(
-                    *project_id,
-                    upstream_client.clone(),
-                    state.worktree_store.clone(),
-                    captured_variables,
-                    location,
-                    state.toolchain_store.clone(),
-                    cx,
-                ),
-            },
+            TaskStore::Functional(state) => {
+                // Remote hosts get the configurations chosen here along with
+                // the other captured variables
+                state
+                    .dotnet_configurations
+                    .add_task_variables(&mut captured_variables);
+                match &state.mode {
+                    StoreMode::Local { environment, .. } => local_task_context_for_location(
+                        state.worktree_store.clone(),
+                        state.toolchain_store.clone(),
+                        environment.clone(),
+                        captured_variables,
+                        location,
+                        cx,
+                    ),
+                    StoreMode::Remote {
+                        upstream_client,
+                        project_id,
+                    } => remote_task_context_for_location(
+                        *project_id,
+                        upstream_client.clone(),
+                        state.worktree_store.clone(),
+                        captured_variables,
+                        location,
+                        state.toolchain_store.clone(),
+                        cx,
+                    ),
+                }
+            }
             TaskStore::Noop => Task::ready(None),
         }
     }
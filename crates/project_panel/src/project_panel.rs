@@ -16,6 +16,7 @@ use editor::{
 use file_icons::FileIcons;
 use git;
 use git::status::GitSummary;
+use git::submodule::Submodule;
 use git_ui;
 use git_ui::file_diff_view::FileDiffView;
 use gpui::{
@@ -230,6 +231,7 @@ struct EntryDetails {
     is_private: bool,
     worktree_id: WorktreeId,
     canonical_path: Option<Arc<Path>>,
+    submodule: Option<Submodule>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -587,7 +589,11 @@ impl ProjectPanel {
                 &git_store,
                 window,
                 |this, _, event, window, cx| match event {
-                    GitStoreEvent::RepositoryUpdated(_, RepositoryEvent::StatusesChanged, _)
+                    GitStoreEvent::RepositoryUpdated(
+                        _,
+                        RepositoryEvent::StatusesChanged | RepositoryEvent::SubmodulesChanged,
+                        _,
+                    )
                     | GitStoreEvent::RepositoryAdded
                     | GitStoreEvent::RepositoryRemoved(_) => {
                         this.update_visible_entries(None, false, false, window, cx);
@@ -4466,6 +4472,12 @@ impl ProjectPanel {
             .canonical_path
             .as_ref()
             .map(|f| f.to_string_lossy().into_owned());
+        let submodule_state = details.submodule.as_ref().and_then(|submodule| {
+            Some((
+                submodule.state_description()?,
+                submodule.sha.display_short(),
+            ))
+        });
         let path_style = self.project.read(cx).path_style(cx);
         let path = details.path.clone();
         let path_for_external_paths = path.clone();
@@ -4847,6 +4859,27 @@ impl ProjectPanel {
                         }
                     })
                     .selectable(false)
+                    .when_some(submodule_state, |this, (state, sha)| {
+                        this.end_slot::<AnyElement>(
+                            div()
+                                .id("submodule_state")
+                                .pr_3()
+                                .tooltip(move |_window, cx| {
+                                    Tooltip::with_meta(
+                                        "Submodule",
+                                        None,
+                                        format!("Checked out at {sha}"),
+                                        cx,
+                                    )
+                                })
+                                .child(
+                                    Label::new(state)
+                                        .size(LabelSize::Small)
+                                        .color(Color::Muted),
+                                )
+                                .into_any_element(),
+                        )
+                    })
                     .when_some(canonical_path, |this, path| {
                         this.end_slot::<AnyElement>(
                             div()
@@ -5127,9 +5160,13 @@ impl ProjectPanel {
         _window: &mut Window,
         cx: &mut Context<Self>,
     ) -> EntryDetails {
-        let (show_file_icons, show_folder_icons) = {
+        let (show_file_icons, show_folder_icons, show_git_status) = {
             let settings = ProjectPanelSettings::get_global(cx);
-            (settings.file_icons, settings.folder_icons)
+            (
+                settings.file_icons,
+                settings.folder_icons,
+                settings.git_status,
+            )
         };
 
         let expanded_entry_ids = self
@@ -5196,6 +5233,21 @@ impl ProjectPanel {
             .as_ref()
             .is_some_and(|e| e.is_cut() && e.items().contains(&selection));
 
+        let submodule = if show_git_status && entry.is_dir() {
+            let project_path = ProjectPath {
+                worktree_id,
+                path: entry.path.clone(),
+            };
+            self.project
+                .read(cx)
+                .git_store()
+                .read(cx)
+                .submodule_for_project_path(&project_path, cx)
+                .map(|(_, submodule)| submodule)
+        } else {
+            None
+        };
+
         EntryDetails {
             filename,
             icon,
@@ -5216,6 +5268,7 @@ impl ProjectPanel {
             is_private: entry.is_private,
             worktree_id,
             canonical_path: entry.canonical_path.clone(),
+            submodule,
         }
     }
 
@@ -85,6 +85,10 @@ messages!(
     (GetChannelMessages, Background),
     (GetChannelMessagesById, Background),
     (GetChannelMessagesResponse, Background),
+    (GetCallHierarchy, Background),
+    (GetCallHierarchyResponse, Background),
+    (GetTypeHierarchy, Background),
+    (GetTypeHierarchyResponse, Background),
     (GetCodeActions, Background),
     (GetCodeActionsResponse, Background),
     (GetCompletions, Background),
@@ -225,6 +229,15 @@ messages!(
     (GetColorPresentation, Background),
     (GetColorPresentationResponse, Background),
     (RefreshCodeLens, Background),
+    (GetSemanticTokens, Background),
+    (GetSemanticTokensResponse, Background),
+    (RefreshSemanticTokens, Background),
+    (GetFoldingRanges, Background),
+    (GetFoldingRangesResponse, Background),
+    (GetSelectionRanges, Background),
+    (GetSelectionRangesResponse, Background),
+    (GetInlineCompletions, Background),
+    (GetInlineCompletionsResponse, Background),
     (GetCodeLens, Background),
     (GetCodeLensResponse, Background),
     (RespondToChannelInvite, Foreground),
@@ -296,6 +309,25 @@ messages!(
     (GitCommitDetails, Background),
     (GitFileHistory, Background),
     (GitFileHistoryResponse, Background),
+    (GitRecentCommits, Background),
+    (GitRecentCommitsResponse, Background),
+    (GitRebaseTodo, Background),
+    (GitRebaseTodoResponse, Background),
+    (GitRebase, Background),
+    (GitRebaseControl, Background),
+    (GitRebaseResponse, Background),
+    (GitCherryPick, Background),
+    (GitRevert, Background),
+    (GitGetTags, Background),
+    (GitGetTagsResponse, Background),
+    (GitCreateTag, Background),
+    (GitDeleteTag, Background),
+    (GitPushTag, Background),
+    (GitCommitLog, Background),
+    (GitCommitLogResponse, Background),
+    (GitCheckoutCommit, Background),
+    (GitSubmoduleOperation, Background),
+    (GitSequencerControl, Background),
     (SetIndexText, Background),
     (Push, Background),
     (Fetch, Background),
@@ -372,6 +404,8 @@ request_messages!(
     (GetChannelMembers, GetChannelMembersResponse),
     (GetChannelMessages, GetChannelMessagesResponse),
     (GetChannelMessagesById, GetChannelMessagesResponse),
+    (GetCallHierarchy, GetCallHierarchyResponse),
+    (GetTypeHierarchy, GetTypeHierarchyResponse),
     (GetCodeActions, GetCodeActionsResponse),
     (GetCompletions, GetCompletionsResponse),
     (GetDefinition, GetDefinitionResponse),
@@ -417,6 +451,11 @@ request_messages!(
     (PrepareRename, PrepareRenameResponse),
     (RefreshInlayHints, Ack),
     (RefreshCodeLens, Ack),
+    (GetSemanticTokens, GetSemanticTokensResponse),
+    (RefreshSemanticTokens, Ack),
+    (GetFoldingRanges, GetFoldingRangesResponse),
+    (GetSelectionRanges, GetSelectionRangesResponse),
+    (GetInlineCompletions, GetInlineCompletionsResponse),
     (RejoinChannelBuffers, RejoinChannelBuffersResponse),
     (RejoinRoom, RejoinRoomResponse),
     (ReloadBuffers, ReloadBuffersResponse),
@@ -497,6 +536,20 @@ request_messages!(
     (RegisterBufferWithLanguageServers, Ack),
     (GitShow, GitCommitDetails),
     (GitFileHistory, GitFileHistoryResponse),
+    (GitRecentCommits, GitRecentCommitsResponse),
+    (GitRebaseTodo, GitRebaseTodoResponse),
+    (GitRebase, GitRebaseResponse),
+    (GitRebaseControl, GitRebaseResponse),
+    (GitCherryPick, Ack),
+    (GitRevert, Ack),
+    (GitGetTags, GitGetTagsResponse),
+    (GitCreateTag, Ack),
+    (GitDeleteTag, Ack),
+    (GitPushTag, RemoteMessageResponse),
+    (GitCommitLog, GitCommitLogResponse),
+    (GitCheckoutCommit, Ack),
+    (GitSubmoduleOperation, RemoteMessageResponse),
+    (GitSequencerControl, Ack),
     (GitReset, Ack),
     (GitDeleteBranch, Ack),
     (GitCheckoutFiles, Ack),
@@ -571,6 +624,8 @@ entity_messages!(
     FindSearchCandidates,
     ApplyCodeActionKind,
     FormatBuffers,
+    GetCallHierarchy,
+    GetTypeHierarchy,
     GetCodeActions,
     GetCodeLens,
     GetCompletions,
@@ -606,6 +661,11 @@ entity_messages!(
     PrepareRename,
     RefreshInlayHints,
     RefreshCodeLens,
+    GetSemanticTokens,
+    RefreshSemanticTokens,
+    GetFoldingRanges,
+    GetSelectionRanges,
+    GetInlineCompletions,
     ReloadBuffers,
     RemoveProjectCollaborator,
     RenameProjectEntry,
@@ -665,6 +725,20 @@ entity_messages!(
     RegisterBufferWithLanguageServers,
     GitShow,
     GitFileHistory,
+    GitRecentCommits,
+    GitRebaseTodo,
+    GitRebase,
+    GitRebaseControl,
+    GitCherryPick,
+    GitRevert,
+    GitGetTags,
+    GitCreateTag,
+    GitDeleteTag,
+    GitPushTag,
+    GitCommitLog,
+    GitCheckoutCommit,
+    GitSubmoduleOperation,
+    GitSequencerControl,
     GitReset,
     GitDeleteBranch,
     GitCheckoutFiles,
@@ -15,6 +15,9 @@ use project::{
     agent_server_store::AgentServerStore,
     buffer_store::{BufferStore, BufferStoreEvent},
     debugger::{breakpoint_store::BreakpointStore, dap_store::DapStore},
+    dotnet_build_diagnostics::DotNetBuildDiagnostics,
+    dotnet_configurations::DotNetConfigurations,
+    dotnet_startup_projects::DotNetStartupProjects,
     git_store::GitStore,
     image_store::ImageId,
     lsp_store::log_store::{self, GlobalLogStore, LanguageServerKind, LogKind},
@@ -129,6 +132,8 @@ impl HeadlessProject {
                 toolchain_store.read(cx).as_language_toolchain_store(),
                 worktree_store.clone(),
                 breakpoint_store.clone(),
+                DotNetStartupProjects::default(),
+                DotNetBuildDiagnostics::default(),
                 true,
                 cx,
             );
@@ -164,6 +169,7 @@ impl HeadlessProject {
                 worktree_store.clone(),
                 toolchain_store.read(cx).as_language_toolchain_store(),
                 environment.clone(),
+                DotNetConfigurations::default(),
                 cx,
             );
             task_store.shared(REMOTE_SERVER_PROJECT_ID, session.clone(), cx);
@@ -11,6 +11,9 @@ use prompt_store::ProjectContext;
 
 use extension::ExtensionHostProxy;
 use fs::{FakeFs, Fs};
+use git::repository::{
+    CommitSummary, RebaseControl, RebaseOutcome, RebaseTodoAction, SequencerOperation,
+};
 use gpui::{AppContext as _, Entity, SharedString, TestAppContext};
 use http_client::{BlockedHttpClient, FakeHttpClient};
 use language::{
@@ -1758,6 +1761,156 @@ async fn test_remote_git_branches(cx: &mut TestAppContext, server_cx: &mut TestA
     assert_eq!(server_branch.name(), "totally-new-branch");
 }
 
+#[gpui::test]
+async fn test_remote_git_rebase(cx: &mut TestAppContext, server_cx: &mut TestAppContext) {
+    let fs = FakeFs::new(server_cx.executor());
+    fs.insert_tree(
+        path!("/code"),
+        json!({
+            "project1": {
+                ".git": {},
+                "README.md": "# project 1",
+            },
+        }),
+    )
+    .await;
+    let commits = ["Add c", "Add b", "Add a"]
+        .into_iter()
+        .enumerate()
+        .map(|(ix, subject)| CommitSummary {
+            sha: format!("{:040x}", 3 - ix).into(),
+            subject: subject.into(),
+            commit_timestamp: 3 - ix as i64,
+            author_name: "Fake Author".into(),
+            has_parent: ix < 2,
+        })
+        .collect::<Vec<_>>();
+    fs.with_git_state(Path::new(path!("/code/project1/.git")), true, |state| {
+        state.set_commits(commits)
+    })
+    .unwrap();
+
+    let (project, _headless_project) = init_test(&fs, cx, server_cx).await;
+    project
+        .update(cx, |project, cx| {
+            project.find_or_create_worktree(path!("/code/project1"), true, cx)
+        })
+        .await
+        .unwrap();
+    cx.run_until_parked();
+
+    let repository = project.update(cx, |project, cx| project.active_repository(cx).unwrap());
+    let commits = repository
+        .update(cx, |repository, _| repository.recent_commits(10))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(commits.len(), 3);
+
+    let base = commits[2].sha.to_string();
+    let mut todo = repository
+        .update(cx, |repository, _| repository.rebase_todo(base.clone()))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(todo.len(), 2);
+    todo.reverse();
+    todo[0].action = RebaseTodoAction::Edit;
+    let outcome = repository
+        .update(cx, |repository, _| repository.rebase(base, todo))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(outcome, RebaseOutcome::Paused);
+
+    let outcome = repository
+        .update(cx, |repository, _| {
+            repository.rebase_control(RebaseControl::Continue)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(outcome, RebaseOutcome::Finished);
+    let subjects = repository
+        .update(cx, |repository, _| repository.recent_commits(10))
+        .await
+        .unwrap()
+        .unwrap()
+        .into_iter()
+        .map(|commit| commit.subject.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(subjects, ["Add b", "Add c", "Add a"]);
+}
+
+#[gpui::test]
+async fn test_remote_git_cherry_pick(cx: &mut TestAppContext, server_cx: &mut TestAppContext) {
+    let fs = FakeFs::new(server_cx.executor());
+    fs.insert_tree(
+        path!("/code"),
+        json!({
+            "project1": {
+                ".git": {},
+                "README.md": "# project 1",
+            },
+        }),
+    )
+    .await;
+    let feature_sha = "f".repeat(40);
+    fs.with_git_state(Path::new(path!("/code/project1/.git")), true, |state| {
+        state.set_commits(vec![CommitSummary {
+            sha: format!("{:040x}", 1).into(),
+            subject: "Add a".into(),
+            commit_timestamp: 1,
+            author_name: "Fake Author".into(),
+            has_parent: false,
+        }]);
+        state.other_commits = vec![CommitSummary {
+            sha: feature_sha.clone().into(),
+            subject: "Add feature".into(),
+            commit_timestamp: 2,
+            author_name: "Fake Author".into(),
+            has_parent: true,
+        }];
+        state.conflicting_commits.insert(feature_sha.clone());
+    })
+    .unwrap();
+
+    let (project, _headless_project) = init_test(&fs, cx, server_cx).await;
+    project
+        .update(cx, |project, cx| {
+            project.find_or_create_worktree(path!("/code/project1"), true, cx)
+        })
+        .await
+        .unwrap();
+    cx.run_until_parked();
+
+    let repository = project.update(cx, |project, cx| project.active_repository(cx).unwrap());
+    assert!(
+        repository
+            .update(cx, |repository, _| repository
+                .cherry_pick(feature_sha.clone()))
+            .await
+            .unwrap()
+            .is_err()
+    );
+    repository
+        .update(cx, |repository, _| {
+            repository.sequencer_control(SequencerOperation::CherryPick, RebaseControl::Continue)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    let subjects = repository
+        .update(cx, |repository, _| repository.recent_commits(10))
+        .await
+        .unwrap()
+        .unwrap()
+        .into_iter()
+        .map(|commit| commit.subject.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(subjects, ["Add feature", "Add a"]);
+}
+
 #[gpui::test]
 async fn test_remote_agent_fs_tool_calls(cx: &mut TestAppContext, server_cx: &mut TestAppContext) {
     let fs = FakeFs::new(server_cx.executor());
@@ -81,6 +81,9 @@ pub struct SettingsContent {
     /// Default: VSCode
     pub base_keymap: Option<BaseKeymapContent>,
 
+    /// Configuration for the call hierarchy panel.
+    pub call_hierarchy_panel: Option<PanelSettingsContent>,
+
     /// Configuration for the collab panel visual settings.
     pub collaboration_panel: Option<PanelSettingsContent>,
 
@@ -145,6 +148,9 @@ pub struct SettingsContent {
 
     pub title_bar: Option<TitleBarSettingsContent>,
 
+    /// Configuration for the type hierarchy panel.
+    pub type_hierarchy_panel: Option<PanelSettingsContent>,
+
     /// Whether or not to enable Vim mode.
     ///
     /// Default: false
@@ -76,6 +76,8 @@ pub enum EditPredictionProvider {
     Supermaven,
     Zed,
     Codestral,
+    /// Inline completions of the language servers, as per LSP 3.18 `textDocument/inlineCompletion`.
+    LanguageServer,
     Experimental(&'static str),
 }
 
@@ -96,6 +98,7 @@ impl<'de> Deserialize<'de> for EditPredictionProvider {
             Supermaven,
             Zed,
             Codestral,
+            LanguageServer,
             Experimental(String),
         }
 
@@ -105,6 +108,7 @@ impl<'de> Deserialize<'de> for EditPredictionProvider {
             Content::Supermaven => EditPredictionProvider::Supermaven,
             Content::Zed => EditPredictionProvider::Zed,
             Content::Codestral => EditPredictionProvider::Codestral,
+            Content::LanguageServer => EditPredictionProvider::LanguageServer,
             Content::Experimental(name)
                 if name == EXPERIMENTAL_SWEEP_EDIT_PREDICTION_PROVIDER_NAME =>
             {
@@ -144,6 +148,7 @@ impl EditPredictionProvider {
             | EditPredictionProvider::Copilot
             | EditPredictionProvider::Supermaven
             | EditPredictionProvider::Codestral
+            | EditPredictionProvider::LanguageServer
             | EditPredictionProvider::Experimental(_) => false,
         }
     }
@@ -439,6 +444,50 @@ pub struct LanguageSettingsContent {
     ///
     /// Default: false
     pub colorize_brackets: Option<bool>,
+    /// Whether to highlight the semantic tokens of language servers over the tree-sitter highlights.
+    ///
+    /// Default: false
+    pub semantic_tokens: Option<bool>,
+    /// The theme syntax styles used for semantic tokens, keyed by token type (`"variable"`),
+    /// or by token type and modifier (`"variable.mutable"`), which takes precedence.
+    /// An empty style leaves tokens of that kind to tree-sitter.
+    pub semantic_token_styles: Option<HashMap<String, String>>,
+    /// Which ranges the editor folds: the built-in ones, from the indentation,
+    /// or the folding ranges of the first language server that provides them.
+    ///
+    /// Default: tree_sitter
+    pub folding_ranges: Option<SyntaxRangesProvider>,
+    /// Which ranges `editor::SelectLargerSyntaxNode` expands the selections through:
+    /// the tree-sitter syntax nodes, or the selection ranges of the first language server
+    /// that provides them.
+    ///
+    /// Default: tree_sitter
+    pub selection_ranges: Option<SyntaxRangesProvider>,
+}
+
+/// Where the editor takes the syntactic ranges used for folding and expanding selections from.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    JsonSchema,
+    MergeFrom,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum SyntaxRangesProvider {
+    /// Use the tree-sitter grammar of the language, and the indentation where it has none.
+    #[default]
+    TreeSitter,
+    /// Use the ranges of the language server, falling back to tree-sitter
+    /// when no server provides them.
+    LanguageServer,
 }
 
 /// Controls how whitespace should be displayedin the editor.
@@ -176,6 +176,7 @@ impl VsCodeSettings {
             audio: None,
             auto_update: None,
             base_keymap: Some(BaseKeymapContent::VSCode),
+            call_hierarchy_panel: None,
             calls: None,
             collaboration_panel: None,
             debugger: None,
@@ -212,6 +213,7 @@ impl VsCodeSettings {
             terminal: self.terminal_settings_content(),
             theme: Box::new(self.theme_settings_content()),
             title_bar: None,
+            type_hierarchy_panel: None,
             vim: None,
             vim_mode: None,
             workspace: self.workspace_settings_content(),
@@ -451,6 +453,10 @@ impl VsCodeSettings {
             remove_trailing_whitespace_on_save: self.read_bool("editor.trimAutoWhitespace"),
             show_completion_documentation: None,
             colorize_brackets: self.read_bool("editor.bracketPairColorization.enabled"),
+            semantic_tokens: self.read_bool("editor.semanticHighlighting.enabled"),
+            semantic_token_styles: None,
+            folding_ranges: None,
+            selection_ranges: None,
             show_completions_on_input: self.read_bool("editor.suggestOnTriggerCharacters"),
             show_edit_predictions: self.read_bool("editor.inlineSuggest.enabled"),
             show_whitespaces: self.read_enum("editor.renderWhitespace", |s| {
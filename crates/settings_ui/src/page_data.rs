@@ -7177,6 +7177,63 @@ fn language_settings_data() -> Vec<SettingsPageItem> {
             metadata: None,
             files: USER | PROJECT,
         }),
+        SettingsPageItem::SettingItem(SettingItem {
+            title: "Semantic Tokens",
+            description: "Whether to highlight the semantic tokens of language servers over the tree-sitter highlights.",
+            field: Box::new(SettingField {
+                json_path: Some("languages.$(language).semantic_tokens"),
+                pick: |settings_content| {
+                    language_settings_field(settings_content, |language| {
+                        language.semantic_tokens.as_ref()
+                    })
+                },
+                write: |settings_content, value| {
+                    language_settings_field_mut(settings_content, value, |language, value| {
+                        language.semantic_tokens = value;
+                    })
+                },
+            }),
+            metadata: None,
+            files: USER | PROJECT,
+        }),
+        SettingsPageItem::SettingItem(SettingItem {
+            title: "Folding Ranges",
+            description: "Whether to fold the ranges of the tree-sitter grammar or of the language server.",
+            field: Box::new(SettingField {
+                json_path: Some("languages.$(language).folding_ranges"),
+                pick: |settings_content| {
+                    language_settings_field(settings_content, |language| {
+                        language.folding_ranges.as_ref()
+                    })
+                },
+                write: |settings_content, value| {
+                    language_settings_field_mut(settings_content, value, |language, value| {
+                        language.folding_ranges = value;
+                    })
+                },
+            }),
+            metadata: None,
+            files: USER | PROJECT,
+        }),
+        SettingsPageItem::SettingItem(SettingItem {
+            title: "Selection Ranges",
+            description: "Whether to expand selections through the tree-sitter syntax nodes or the selection ranges of the language server.",
+            field: Box::new(SettingField {
+                json_path: Some("languages.$(language).selection_ranges"),
+                pick: |settings_content| {
+                    language_settings_field(settings_content, |language| {
+                        language.selection_ranges.as_ref()
+                    })
+                },
+                write: |settings_content, value| {
+                    language_settings_field_mut(settings_content, value, |language, value| {
+                        language.selection_ranges = value;
+                    })
+                },
+            }),
+            metadata: None,
+            files: USER | PROJECT,
+        }),
     ]);
 
     if current_language().is_none() {
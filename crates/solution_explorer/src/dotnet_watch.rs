@@ -0,0 +1,512 @@
+use anyhow::{Context as _, Result};
+use collections::BTreeMap;
+use dotnet::watch::{RestartAnswer, WATCH_ENV, WatchEvent, WatchOutput, WatchStatus};
+use futures::{AsyncBufReadExt as _, AsyncWriteExt as _, StreamExt as _, channel::mpsc};
+use gpui::{App, Corner, Entity, Global, Subscription, Task, WeakEntity};
+use std::{
+    io::Write as _,
+    path::{Path, PathBuf},
+    process::Stdio,
+    time::Duration,
+};
+use ui::{ContextMenu, PopoverMenu, Tooltip, prelude::*};
+use util::ResultExt as _;
+use workspace::{
+    OpenOptions, StatusItemView, Workspace,
+    item::ItemHandle,
+    notifications::{NotificationId, simple_message_notification::MessageNotification},
+};
+
+/// How long `dotnet watch` gets to stop the app it runs before it is killed.
+const STOP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The `dotnet watch` sessions of projects, started from the solution
+/// explorer.
+///
+/// Each session runs `dotnet watch` without a terminal, so that its output
+/// can be read: its status is shown in the status bar, its build errors
+/// become project diagnostics, and its prompt to restart the app after a rude
+/// edit becomes a notification.
+pub struct DotNetWatch {
+    sessions: BTreeMap<PathBuf, WatchSession>,
+    next_session_id: usize,
+}
+
+struct WatchSession {
+    id: usize,
+    /// The name of the watched project.
+    name: SharedString,
+    /// The arguments passed to `dotnet run`, kept to restart the session.
+    run_args: Vec<String>,
+    status: WatchStatus,
+    /// Whether `dotnet watch` waits for an answer to its restart prompt.
+    prompting: bool,
+    /// The workspace the session was started from, which shows its
+    /// notifications.
+    workspace: WeakEntity<Workspace>,
+    /// The output of `dotnet watch` and of the app.
+    output_log: tempfile::TempPath,
+    child: smol::process::Child,
+    input: mpsc::UnboundedSender<char>,
+    _output_task: Task<()>,
+    _input_task: Task<()>,
+}
+
+struct GlobalDotNetWatch(Entity<DotNetWatch>);
+
+impl Global for GlobalDotNetWatch {}
+
+impl DotNetWatch {
+    /// Returns the global [`DotNetWatch`].
+    ///
+    /// Inserts one without sessions if none exists yet.
+    pub fn global(cx: &mut App) -> Entity<Self> {
+        if let Some(global) = cx.try_global::<GlobalDotNetWatch>() {
+            return global.0.clone();
+        }
+        let watch = cx.new(|_| Self {
+            sessions: BTreeMap::default(),
+            next_session_id: 0,
+        });
+        cx.set_global(GlobalDotNetWatch(watch.clone()));
+        watch
+    }
+
+    pub fn is_watching(&self, project_path: &Path) -> bool {
+        self.sessions.contains_key(project_path)
+    }
+
+    /// Starts `dotnet watch` for the project file at `project_path`, replacing
+    /// any session it has already.
+    pub fn start(
+        &mut self,
+        project_path: PathBuf,
+        name: SharedString,
+        run_args: Vec<String>,
+        workspace: WeakEntity<Workspace>,
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        self.stop(&project_path, cx);
+        // Build errors are shown in the project the session was started from
+        let build_diagnostics = workspace.read_with(cx, |workspace, cx| {
+            workspace
+                .project()
+                .read(cx)
+                .dotnet_build_diagnostics()
+                .clone()
+        })?;
+
+        let working_dir = project_path
+            .parent()
+            .context("project file has no directory")?
+            .to_path_buf();
+        let mut child = util::command::new_smol_command("dotnet")
+            .arg("watch")
+            .arg("--project")
+            .arg(&project_path)
+            .arg("run")
+            // Build diagnostics name files by their absolute paths
+            .arg("--property:GenerateFullPaths=true")
+            .args(&run_args)
+            .envs(WATCH_ENV.iter().copied())
+            .current_dir(&working_dir)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .context("starting dotnet watch")?;
+        let stdin = child.stdin.take().context("no stdin for dotnet watch")?;
+        let stdout = child.stdout.take().context("no stdout for dotnet watch")?;
+        let stderr = child.stderr.take().context("no stderr for dotnet watch")?;
+        let output_log = tempfile::Builder::new()
+            .prefix("dotnet-watch-")
+            .suffix(".log")
+            .tempfile()?;
+        let mut log_file = output_log.reopen()?;
+
+        let id = self.next_session_id;
+        self.next_session_id += 1;
+        let output_task = cx.spawn({
+            let project_path = project_path.clone();
+            async move |this, cx| {
+                let stdout = smol::io::BufReader::new(stdout).lines();
+                let stderr = smol::io::BufReader::new(stderr).lines();
+                let mut lines = futures::stream::select(stdout, stderr);
+                let mut output = WatchOutput::new(&working_dir);
+                while let Some(Ok(line)) = lines.next().await {
+                    writeln!(log_file, "{line}").log_err();
+                    let (event, diagnostics) = output.push_line(&line);
+                    if let Some(diagnostics) = diagnostics {
+                        build_diagnostics.publish(diagnostics);
+                    }
+                    let Some(event) = event else {
+                        continue;
+                    };
+                    let handled = this.update(cx, |this, cx| {
+                        this.handle_event(&project_path, id, event, cx)
+                    });
+                    if handled.is_err() {
+                        return;
+                    }
+                }
+                this.update(cx, |this, cx| {
+                    if let Some(session) = this.sessions.get_mut(&project_path)
+                        && session.id == id
+                    {
+                        session.status = WatchStatus::Exited;
+                        session.prompting = false;
+                        cx.notify();
+                    }
+                })
+                .ok();
+            }
+        });
+        let (input, mut keys) = mpsc::unbounded::<char>();
+        let input_task = cx.background_spawn(async move {
+            let mut stdin = stdin;
+            while let Some(key) = keys.next().await {
+                let mut buffer = [0; 4];
+                let written = stdin
+                    .write_all(key.encode_utf8(&mut buffer).as_bytes())
+                    .await;
+                if written.is_err() || stdin.flush().await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        self.sessions.insert(
+            project_path,
+            WatchSession {
+                id,
+                name,
+                run_args,
+                status: WatchStatus::Building,
+                prompting: false,
+                workspace,
+                output_log: output_log.into_temp_path(),
+                child,
+                input,
+                _output_task: output_task,
+                _input_task: input_task,
+            },
+        );
+        cx.notify();
+        Ok(())
+    }
+
+    /// Stops the session of the project at `project_path`, and the app it
+    /// runs.
+    pub fn stop(&mut self, project_path: &Path, cx: &mut Context<Self>) {
+        let Some(session) = self.sessions.remove(project_path) else {
+            return;
+        };
+        cx.notify();
+        let mut child = session.child;
+        cx.background_spawn(async move {
+            // Killing dotnet watch would leave the app running, so let it
+            // stop the app on Ctrl+C first
+            #[cfg(unix)]
+            unsafe {
+                libc::kill(child.id() as libc::pid_t, libc::SIGINT);
+            }
+            let exited = smol::future::or(
+                async {
+                    child.status().await.ok();
+                    true
+                },
+                async {
+                    smol::Timer::after(STOP_TIMEOUT).await;
+                    false
+                },
+            )
+            .await;
+            if !exited {
+                child.kill().log_err();
+            }
+        })
+        .detach();
+    }
+
+    /// Stops the session of the project at `project_path` and starts it again.
+    pub fn restart(&mut self, project_path: &Path, cx: &mut Context<Self>) -> Result<()> {
+        let session = self
+            .sessions
+            .get(project_path)
+            .context("project is not watched")?;
+        let (name, run_args, workspace) = (
+            session.name.clone(),
+            session.run_args.clone(),
+            session.workspace.clone(),
+        );
+        self.start(project_path.to_path_buf(), name, run_args, workspace, cx)
+    }
+
+    /// Answers the prompt of `dotnet watch` to restart the app after a rude
+    /// edit.
+    pub fn answer_restart(
+        &mut self,
+        project_path: &Path,
+        answer: RestartAnswer,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(session) = self.sessions.get_mut(project_path) else {
+            return;
+        };
+        if !session.prompting {
+            return;
+        }
+        session.prompting = false;
+        session.input.unbounded_send(answer.key()).log_err();
+        if matches!(answer, RestartAnswer::Yes | RestartAnswer::Always) {
+            session.status = WatchStatus::Building;
+        }
+        cx.notify();
+    }
+
+    fn handle_event(
+        &mut self,
+        project_path: &Path,
+        id: usize,
+        event: WatchEvent,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(session) = self
+            .sessions
+            .get_mut(project_path)
+            .filter(|session| session.id == id)
+        else {
+            return;
+        };
+        if let Some(status) = event.status() {
+            session.status = status;
+            cx.notify();
+        }
+        if event == WatchEvent::RestartPrompt {
+            session.prompting = true;
+            show_restart_prompt(project_path, &session.name, &session.workspace, cx);
+        }
+    }
+}
+
+/// Asks whether to restart the app of a watched project after a rude edit,
+/// as `dotnet watch` does in a terminal.
+fn show_restart_prompt(
+    project_path: &Path,
+    name: &SharedString,
+    workspace: &WeakEntity<Workspace>,
+    cx: &mut App,
+) {
+    let message =
+        format!("Changes to {name} can't be hot reloaded. Restart the app to apply them?");
+    let answer = {
+        let project_path = project_path.to_path_buf();
+        move |answer: RestartAnswer, cx: &mut App| {
+            DotNetWatch::global(cx).update(cx, |watch, cx| {
+                watch.answer_restart(&project_path, answer, cx)
+            });
+        }
+    };
+    workspace
+        .update(cx, |workspace, cx| {
+            workspace.show_notification(
+                NotificationId::composite::<DotNetWatch>(
+                    project_path.to_string_lossy().into_owned(),
+                ),
+                cx,
+                |cx| {
+                    cx.new(|cx| {
+                        MessageNotification::new(message, cx)
+                            .primary_message("Restart")
+                            .primary_icon(IconName::RotateCw)
+                            .primary_on_click({
+                                let answer = answer.clone();
+                                move |_, cx| answer(RestartAnswer::Yes, cx)
+                            })
+                            .secondary_message("Always Restart")
+                            .secondary_on_click(move |_, cx| answer(RestartAnswer::Always, cx))
+                            .show_suppress_button(false)
+                    })
+                },
+            );
+        })
+        .ok();
+}
+
+/// The status of the `dotnet watch` sessions started from a workspace, in the
+/// status bar.
+pub struct DotNetWatchIndicator {
+    watch: Entity<DotNetWatch>,
+    workspace: WeakEntity<Workspace>,
+    _observe_watch: Subscription,
+}
+
+impl DotNetWatchIndicator {
+    pub fn new(workspace: &Workspace, cx: &mut Context<Self>) -> Self {
+        let watch = DotNetWatch::global(cx);
+        Self {
+            _observe_watch: cx.observe(&watch, |_, _, cx| cx.notify()),
+            watch,
+            workspace: workspace.weak_handle(),
+        }
+    }
+}
+
+/// The actions on the sessions started from `workspace`.
+fn build_menu(
+    watch: &Entity<DotNetWatch>,
+    workspace: &WeakEntity<Workspace>,
+    window: &mut Window,
+    cx: &mut App,
+) -> Entity<ContextMenu> {
+    let sessions = watch
+        .read(cx)
+        .sessions
+        .iter()
+        .filter(|(_, session)| session.workspace == *workspace)
+        .map(|(path, session)| {
+            (
+                path.clone(),
+                session.name.clone(),
+                session.status,
+                session.prompting,
+                session.output_log.to_path_buf(),
+            )
+        })
+        .collect::<Vec<_>>();
+    let watch = watch.clone();
+    let workspace = workspace.clone();
+    ContextMenu::build(window, cx, move |mut menu, _, _| {
+        for (index, (project_path, name, status, prompting, output_log)) in
+            sessions.into_iter().enumerate()
+        {
+            if index > 0 {
+                menu = menu.separator();
+            }
+            menu = menu.header(format!("{name}: {}", status.label()));
+            if prompting {
+                menu = menu.entry("Restart App", None, {
+                    let (watch, project_path) = (watch.clone(), project_path.clone());
+                    move |_, cx| {
+                        watch.update(cx, |watch, cx| {
+                            watch.answer_restart(&project_path, RestartAnswer::Yes, cx)
+                        })
+                    }
+                });
+                menu = menu.entry("Don't Restart", None, {
+                    let (watch, project_path) = (watch.clone(), project_path.clone());
+                    move |_, cx| {
+                        watch.update(cx, |watch, cx| {
+                            watch.answer_restart(&project_path, RestartAnswer::No, cx)
+                        })
+                    }
+                });
+            } else {
+                menu = menu.entry("Restart", None, {
+                    let (watch, project_path) = (watch.clone(), project_path.clone());
+                    move |_, cx| {
+                        watch
+                            .update(cx, |watch, cx| watch.restart(&project_path, cx))
+                            .log_err();
+                    }
+                });
+            }
+            menu = menu
+                .entry("Show Output", None, {
+                    let workspace = workspace.clone();
+                    move |window, cx| {
+                        workspace
+                            .update(cx, |workspace, cx| {
+                                workspace
+                                    .open_abs_path(
+                                        output_log.clone(),
+                                        OpenOptions::default(),
+                                        window,
+                                        cx,
+                                    )
+                                    .detach_and_log_err(cx);
+                            })
+                            .ok();
+                    }
+                })
+                .entry("Stop Watching", None, {
+                    let watch = watch.clone();
+                    move |_, cx| watch.update(cx, |watch, cx| watch.stop(&project_path, cx))
+                });
+        }
+        menu
+    })
+}
+
+impl Render for DotNetWatchIndicator {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let sessions = self
+            .watch
+            .read(cx)
+            .sessions
+            .values()
+            .filter(|session| session.workspace == self.workspace)
+            .collect::<Vec<_>>();
+        let Some(first) = sessions.first() else {
+            return div().into_any_element();
+        };
+        let (label, tooltip) = if sessions.len() == 1 {
+            (
+                format!("{}: {}", first.name, first.status.label()),
+                "dotnet watch".to_string(),
+            )
+        } else {
+            (
+                format!("{} projects watched", sessions.len()),
+                sessions
+                    .iter()
+                    .map(|session| format!("{}: {}", session.name, session.status.label()))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            )
+        };
+        let (icon, color) = if sessions.iter().any(|session| session.status.is_error()) {
+            (IconName::Warning, Color::Warning)
+        } else if sessions.iter().any(|session| {
+            matches!(
+                session.status,
+                WatchStatus::Building | WatchStatus::Applying
+            )
+        }) {
+            (IconName::ArrowCircle, Color::Muted)
+        } else if sessions
+            .iter()
+            .all(|session| session.status == WatchStatus::Exited)
+        {
+            (IconName::Stop, Color::Muted)
+        } else {
+            (IconName::Flame, Color::Muted)
+        };
+
+        let (watch, workspace) = (self.watch.clone(), self.workspace.clone());
+        PopoverMenu::new("dotnet-watch-menu")
+            .menu(move |window, cx| Some(build_menu(&watch, &workspace, window, cx)))
+            .anchor(Corner::BottomLeft)
+            .trigger_with_tooltip(
+                Button::new("dotnet-watch-status", label)
+                    .label_size(LabelSize::Small)
+                    .icon(icon)
+                    .icon_size(IconSize::Small)
+                    .icon_color(color)
+                    .icon_position(IconPosition::Start),
+                Tooltip::text(tooltip),
+            )
+            .into_any_element()
+    }
+}
+
+impl StatusItemView for DotNetWatchIndicator {
+    fn set_active_pane_item(
+        &mut self,
+        _: Option<&dyn ItemHandle>,
+        _: &mut Window,
+        _: &mut Context<Self>,
+    ) {
+    }
+}
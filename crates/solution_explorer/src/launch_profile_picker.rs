@@ -0,0 +1,182 @@
+use dotnet::LaunchProfile;
+use fuzzy::{StringMatch, StringMatchCandidate, match_strings};
+use gpui::{DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Task, WeakEntity};
+use picker::{Picker, PickerDelegate};
+use std::sync::Arc;
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt;
+use workspace::ModalView;
+
+type OnConfirm = Box<dyn FnOnce(LaunchProfile, &mut Window, &mut App)>;
+
+/// A modal for picking one of the launch profiles of a project.
+pub struct LaunchProfilePicker {
+    picker: Entity<Picker<LaunchProfilePickerDelegate>>,
+}
+
+impl LaunchProfilePicker {
+    pub fn new(
+        placeholder: impl Into<Arc<str>>,
+        profiles: Vec<LaunchProfile>,
+        on_confirm: impl FnOnce(LaunchProfile, &mut Window, &mut App) + 'static,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let candidates = profiles
+            .iter()
+            .enumerate()
+            .map(|(ix, profile)| StringMatchCandidate::new(ix, &profile.name))
+            .collect();
+        let delegate = LaunchProfilePickerDelegate {
+            profile_picker: cx.entity().downgrade(),
+            placeholder: placeholder.into(),
+            profiles,
+            candidates,
+            matches: Vec::new(),
+            selected_index: 0,
+            on_confirm: Some(Box::new(on_confirm)),
+        };
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+        Self { picker }
+    }
+}
+
+impl Render for LaunchProfilePicker {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+impl Focusable for LaunchProfilePicker {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for LaunchProfilePicker {}
+impl ModalView for LaunchProfilePicker {}
+
+struct LaunchProfilePickerDelegate {
+    profile_picker: WeakEntity<LaunchProfilePicker>,
+    placeholder: Arc<str>,
+    profiles: Vec<LaunchProfile>,
+    candidates: Vec<StringMatchCandidate>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+    on_confirm: Option<OnConfirm>,
+}
+
+impl PickerDelegate for LaunchProfilePickerDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        self.placeholder.clone()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(&mut self, ix: usize, _: &mut Window, _: &mut Context<Picker<Self>>) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let background = cx.background_executor().clone();
+        let candidates = self.candidates.clone();
+        cx.spawn_in(window, async move |this, cx| {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                // Keep the order of the launch settings file.
+                let mut matches = match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    true,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await;
+                matches.sort_by_key(|mat| mat.candidate_id);
+                matches
+            };
+
+            this.update(cx, |this, cx| {
+                let delegate = &mut this.delegate;
+                delegate.matches = matches;
+                delegate.selected_index = delegate
+                    .selected_index
+                    .min(delegate.matches.len().saturating_sub(1));
+                cx.notify();
+            })
+            .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(profile) = self
+            .matches
+            .get(self.selected_index)
+            .and_then(|mat| self.profiles.get(mat.candidate_id))
+            .cloned()
+        else {
+            return;
+        };
+        if let Some(on_confirm) = self.on_confirm.take() {
+            window.defer(cx, move |window, cx| on_confirm(profile, window, cx));
+        }
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.profile_picker
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let mat = self.matches.get(ix)?;
+        let profile = self.profiles.get(mat.candidate_id)?;
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(HighlightedLabel::new(
+                    profile.name.clone(),
+                    mat.positions.clone(),
+                ))
+                .end_slot::<Label>(profile.summary().map(|summary| {
+                    Label::new(summary.to_string())
+                        .size(LabelSize::Small)
+                        .color(Color::Muted)
+                        .truncate()
+                })),
+        )
+    }
+}
@@ -0,0 +1,144 @@
+use gpui::{DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Task, WeakEntity};
+use picker::{Picker, PickerDelegate};
+use std::sync::Arc;
+use ui::{ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt;
+use workspace::ModalView;
+
+type OnConfirm = Box<dyn FnOnce(String, &mut Window, &mut App)>;
+
+/// A modal asking for the name of a new or renamed solution item.
+pub struct NamePrompt {
+    picker: Entity<Picker<NamePromptDelegate>>,
+}
+
+impl NamePrompt {
+    pub fn new(
+        placeholder: impl Into<Arc<str>>,
+        action_label: impl Into<SharedString>,
+        initial_name: Option<String>,
+        on_confirm: impl FnOnce(String, &mut Window, &mut App) + 'static,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let delegate = NamePromptDelegate {
+            prompt: cx.entity().downgrade(),
+            placeholder: placeholder.into(),
+            action_label: action_label.into(),
+            name: String::new(),
+            on_confirm: Some(Box::new(on_confirm)),
+        };
+        let picker = cx.new(|cx| {
+            let picker = Picker::uniform_list(delegate, window, cx);
+            if let Some(initial_name) = initial_name {
+                picker.set_query(initial_name, window, cx);
+            }
+            picker
+        });
+        Self { picker }
+    }
+}
+
+impl Render for NamePrompt {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+impl Focusable for NamePrompt {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for NamePrompt {}
+impl ModalView for NamePrompt {}
+
+struct NamePromptDelegate {
+    prompt: WeakEntity<NamePrompt>,
+    placeholder: Arc<str>,
+    action_label: SharedString,
+    name: String,
+    on_confirm: Option<OnConfirm>,
+}
+
+impl NamePromptDelegate {
+    fn is_valid(&self) -> bool {
+        !self.name.is_empty() && !self.name.contains(['/', '\\'])
+    }
+}
+
+impl PickerDelegate for NamePromptDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        self.placeholder.clone()
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        if self.name.contains(['/', '\\']) {
+            Some("Names cannot contain path separators".into())
+        } else {
+            Some("Type a name".into())
+        }
+    }
+
+    fn match_count(&self) -> usize {
+        if self.is_valid() { 1 } else { 0 }
+    }
+
+    fn selected_index(&self) -> usize {
+        0
+    }
+
+    fn set_selected_index(&mut self, _: usize, _: &mut Window, _: &mut Context<Picker<Self>>) {}
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        self.name = query.trim().to_string();
+        Task::ready(())
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        if !self.is_valid() {
+            return;
+        }
+        let name = self.name.clone();
+        if let Some(on_confirm) = self.on_confirm.take() {
+            // Run the callback once this modal is gone, so it may open another one.
+            window.defer(cx, move |window, cx| on_confirm(name, window, cx));
+        }
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.prompt
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .child(Label::new(self.action_label.clone()).color(Color::Muted))
+                        .child(Label::new(self.name.clone())),
+                ),
+        )
+    }
+}
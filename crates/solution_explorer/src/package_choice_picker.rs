@@ -0,0 +1,187 @@
+use fuzzy::{StringMatch, StringMatchCandidate, match_strings};
+use gpui::{DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Task, WeakEntity};
+use picker::{Picker, PickerDelegate};
+use std::sync::Arc;
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt;
+use workspace::ModalView;
+
+type OnConfirm = Box<dyn FnOnce(usize, &mut Window, &mut App)>;
+
+/// An entry of a [`PackageChoicePicker`], such as a package version.
+pub struct PackageChoice {
+    pub label: String,
+    pub detail: Option<String>,
+}
+
+/// A modal for picking one of a list of package versions or package updates.
+///
+/// `on_confirm` receives the index of the chosen entry.
+pub struct PackageChoicePicker {
+    picker: Entity<Picker<PackageChoicePickerDelegate>>,
+}
+
+impl PackageChoicePicker {
+    pub fn new(
+        placeholder: impl Into<Arc<str>>,
+        choices: Vec<PackageChoice>,
+        on_confirm: impl FnOnce(usize, &mut Window, &mut App) + 'static,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let candidates = choices
+            .iter()
+            .enumerate()
+            .map(|(ix, choice)| StringMatchCandidate::new(ix, &choice.label))
+            .collect();
+        let delegate = PackageChoicePickerDelegate {
+            choice_picker: cx.entity().downgrade(),
+            placeholder: placeholder.into(),
+            choices,
+            candidates,
+            matches: Vec::new(),
+            selected_index: 0,
+            on_confirm: Some(Box::new(on_confirm)),
+        };
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+        Self { picker }
+    }
+}
+
+impl Render for PackageChoicePicker {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+impl Focusable for PackageChoicePicker {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for PackageChoicePicker {}
+impl ModalView for PackageChoicePicker {}
+
+struct PackageChoicePickerDelegate {
+    choice_picker: WeakEntity<PackageChoicePicker>,
+    placeholder: Arc<str>,
+    choices: Vec<PackageChoice>,
+    candidates: Vec<StringMatchCandidate>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+    on_confirm: Option<OnConfirm>,
+}
+
+impl PickerDelegate for PackageChoicePickerDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        self.placeholder.clone()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(&mut self, ix: usize, _: &mut Window, _: &mut Context<Picker<Self>>) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let background = cx.background_executor().clone();
+        let candidates = self.candidates.clone();
+        cx.spawn_in(window, async move |this, cx| {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                // Keep the order of the choices, e.g. newest versions first.
+                let mut matches = match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    true,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await;
+                matches.sort_by_key(|mat| mat.candidate_id);
+                matches
+            };
+
+            this.update(cx, |this, cx| {
+                let delegate = &mut this.delegate;
+                delegate.matches = matches;
+                delegate.selected_index = delegate
+                    .selected_index
+                    .min(delegate.matches.len().saturating_sub(1));
+                cx.notify();
+            })
+            .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(ix) = self
+            .matches
+            .get(self.selected_index)
+            .map(|mat| mat.candidate_id)
+        else {
+            return;
+        };
+        if let Some(on_confirm) = self.on_confirm.take() {
+            window.defer(cx, move |window, cx| on_confirm(ix, window, cx));
+        }
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.choice_picker
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let mat = self.matches.get(ix)?;
+        let choice = self.choices.get(mat.candidate_id)?;
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(HighlightedLabel::new(
+                    choice.label.clone(),
+                    mat.positions.clone(),
+                ))
+                .end_slot::<Label>(choice.detail.clone().map(|detail| {
+                    Label::new(detail)
+                        .size(LabelSize::Small)
+                        .color(Color::Muted)
+                })),
+        )
+    }
+}
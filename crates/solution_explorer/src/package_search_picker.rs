@@ -0,0 +1,390 @@
+use anyhow::{Context as _, Result};
+use dotnet::{
+    NuGetVersion, OutdatedPackage, PackageSearchResult, PackageSource, PackageSourceKind,
+    nuget::{
+        ServiceIndex, is_local_resource, merge_search_results, parse_outdated_packages,
+        parse_search_response, parse_versions_response, search_local_flat_container,
+        search_local_folder, search_url, versions_url,
+    },
+};
+use futures::AsyncReadExt as _;
+use gpui::{DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Task, WeakEntity};
+use http_client::{AsyncBody, HttpClient, StatusCode};
+use picker::{Picker, PickerDelegate};
+use std::{path::Path, sync::Arc, time::Duration};
+use ui::{Checkbox, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt;
+use workspace::ModalView;
+
+const SEARCH_DEBOUNCE: Duration = Duration::from_millis(300);
+const SEARCH_RESULT_COUNT: usize = 30;
+
+type OnConfirm = Box<dyn FnOnce(PackageSearchResult, bool, &mut Window, &mut App)>;
+
+/// Searches all package sources, merging packages found in several of them.
+///
+/// Sources that cannot be searched are logged and skipped.
+pub async fn search_packages(
+    http: Arc<dyn HttpClient>,
+    sources: Vec<PackageSource>,
+    query: String,
+    include_prerelease: bool,
+) -> Vec<PackageSearchResult> {
+    let mut results = Vec::new();
+    for source in &sources {
+        match search_source(http.as_ref(), source, &query, include_prerelease).await {
+            Ok(source_results) => results.extend(source_results),
+            Err(error) => log::warn!("Failed to search package source {}: {error:#}", source.name),
+        }
+    }
+    merge_search_results(results)
+}
+
+async fn search_source(
+    http: &dyn HttpClient,
+    source: &PackageSource,
+    query: &str,
+    include_prerelease: bool,
+) -> Result<Vec<PackageSearchResult>> {
+    match &source.kind {
+        PackageSourceKind::LocalFolder(folder) => {
+            search_local_folder(&source.name, folder, query, include_prerelease)
+        }
+        PackageSourceKind::ServiceIndex(location) => {
+            let index = load_service_index(http, location).await?;
+            if let Some(search) = index.search.filter(|search| !is_local_resource(search)) {
+                let url = search_url(&search, query, include_prerelease, SEARCH_RESULT_COUNT);
+                let response = fetch(http, &url)
+                    .await?
+                    .with_context(|| format!("{url} was not found"))?;
+                parse_search_response(&response, &source.name, include_prerelease)
+            } else if let Some(base_address) = index
+                .package_base_address
+                .filter(|base_address| is_local_resource(base_address))
+            {
+                search_local_flat_container(
+                    &source.name,
+                    Path::new(&base_address),
+                    query,
+                    include_prerelease,
+                )
+            } else {
+                anyhow::bail!("{location} has no search resource")
+            }
+        }
+    }
+}
+
+/// Lists the versions of a package available in any of the package sources,
+/// newest first.
+pub async fn package_versions(
+    http: Arc<dyn HttpClient>,
+    sources: Vec<PackageSource>,
+    package_id: String,
+    include_prerelease: bool,
+) -> Vec<NuGetVersion> {
+    let mut versions = Vec::new();
+    for source in &sources {
+        let source_versions = match &source.kind {
+            PackageSourceKind::LocalFolder(folder) => {
+                search_local_folder(&source.name, folder, &package_id, include_prerelease).map(
+                    |results| {
+                        results
+                            .into_iter()
+                            .filter(|result| result.id.eq_ignore_ascii_case(&package_id))
+                            .flat_map(|result| result.versions)
+                            .collect()
+                    },
+                )
+            }
+            PackageSourceKind::ServiceIndex(location) => {
+                flat_container_versions(http.as_ref(), location, &package_id, include_prerelease)
+                    .await
+            }
+        };
+        match source_versions {
+            Ok(source_versions) => versions.extend(source_versions),
+            Err(error) => log::warn!(
+                "Failed to list versions of {package_id} in {}: {error:#}",
+                source.name
+            ),
+        }
+    }
+    versions.sort_by(|a, b| b.cmp(a));
+    versions.dedup();
+    versions
+}
+
+async fn flat_container_versions(
+    http: &dyn HttpClient,
+    location: &str,
+    package_id: &str,
+    include_prerelease: bool,
+) -> Result<Vec<NuGetVersion>> {
+    let index = load_service_index(http, location).await?;
+    let base_address = index
+        .package_base_address
+        .with_context(|| format!("{location} has no package base address"))?;
+    // A feed without the package answers with 404.
+    match fetch(http, &versions_url(&base_address, package_id)).await? {
+        Some(response) => parse_versions_response(&response, include_prerelease),
+        None => Ok(Vec::new()),
+    }
+}
+
+async fn load_service_index(http: &dyn HttpClient, location: &str) -> Result<ServiceIndex> {
+    let index = fetch(http, location)
+        .await?
+        .with_context(|| format!("service index {location} was not found"))?;
+    ServiceIndex::parse(&index, location)
+}
+
+/// Reads a feed resource from a URL or, for local feeds, from disk.
+///
+/// Returns `None` when the resource does not exist.
+async fn fetch(http: &dyn HttpClient, location: &str) -> Result<Option<Vec<u8>>> {
+    if is_local_resource(location) {
+        return match std::fs::read(location) {
+            Ok(content) => Ok(Some(content)),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error).with_context(|| format!("reading {location}")),
+        };
+    }
+    let mut response = http.get(location, AsyncBody::default(), true).await?;
+    if response.status() == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    anyhow::ensure!(
+        response.status().is_success(),
+        "{location} responded with {}",
+        response.status()
+    );
+    let mut body = Vec::new();
+    response.body_mut().read_to_end(&mut body).await?;
+    Ok(Some(body))
+}
+
+/// Lists the packages of a solution or project that have newer versions.
+pub async fn load_outdated_packages(
+    path: &Path,
+    include_prerelease: bool,
+) -> Result<Vec<OutdatedPackage>> {
+    let mut command = util::command::new_smol_command("dotnet");
+    command
+        .arg("list")
+        .arg(path)
+        .args(["package", "--outdated", "--format", "json"]);
+    if include_prerelease {
+        command.arg("--include-prerelease");
+    }
+    let output = command
+        .output()
+        .await
+        .context("running `dotnet list package --outdated`")?;
+    anyhow::ensure!(
+        output.status.success(),
+        "`dotnet list package --outdated` failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    parse_outdated_packages(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// A modal for searching the configured package sources.
+pub struct PackageSearchPicker {
+    picker: Entity<Picker<PackageSearchPickerDelegate>>,
+}
+
+impl PackageSearchPicker {
+    /// `on_confirm` receives the chosen package and whether prerelease versions
+    /// were included when it was found.
+    pub fn new(
+        http: Arc<dyn HttpClient>,
+        sources: Vec<PackageSource>,
+        include_prerelease: bool,
+        on_confirm: impl FnOnce(PackageSearchResult, bool, &mut Window, &mut App) + 'static,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let delegate = PackageSearchPickerDelegate {
+            search_picker: cx.entity().downgrade(),
+            http,
+            sources,
+            include_prerelease,
+            results: Vec::new(),
+            searching: false,
+            selected_index: 0,
+            on_confirm: Some(Box::new(on_confirm)),
+        };
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+        Self { picker }
+    }
+}
+
+impl Render for PackageSearchPicker {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+impl Focusable for PackageSearchPicker {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for PackageSearchPicker {}
+impl ModalView for PackageSearchPicker {}
+
+struct PackageSearchPickerDelegate {
+    search_picker: WeakEntity<PackageSearchPicker>,
+    http: Arc<dyn HttpClient>,
+    sources: Vec<PackageSource>,
+    include_prerelease: bool,
+    results: Vec<PackageSearchResult>,
+    searching: bool,
+    selected_index: usize,
+    on_confirm: Option<OnConfirm>,
+}
+
+impl PickerDelegate for PackageSearchPickerDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Search NuGet packages…".into()
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some(if self.searching {
+            "Searching…".into()
+        } else {
+            "No packages found".into()
+        })
+    }
+
+    fn match_count(&self) -> usize {
+        self.results.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(&mut self, ix: usize, _: &mut Window, _: &mut Context<Picker<Self>>) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let http = self.http.clone();
+        let sources = self.sources.clone();
+        let include_prerelease = self.include_prerelease;
+        self.searching = true;
+        cx.spawn_in(window, async move |this, cx| {
+            // Typing replaces this task, so only the last query reaches the sources.
+            cx.background_executor().timer(SEARCH_DEBOUNCE).await;
+            let results = cx
+                .background_spawn(search_packages(
+                    http,
+                    sources,
+                    query.trim().to_string(),
+                    include_prerelease,
+                ))
+                .await;
+
+            this.update(cx, |this, cx| {
+                let delegate = &mut this.delegate;
+                delegate.results = results;
+                delegate.searching = false;
+                delegate.selected_index = 0;
+                cx.notify();
+            })
+            .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(result) = self.results.get(self.selected_index).cloned() else {
+            return;
+        };
+        let include_prerelease = self.include_prerelease;
+        if let Some(on_confirm) = self.on_confirm.take() {
+            window.defer(cx, move |window, cx| {
+                on_confirm(result, include_prerelease, window, cx)
+            });
+        }
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.search_picker
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let result = self.results.get(ix)?;
+        let latest_version = result.latest_version(self.include_prerelease)?;
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(
+                    v_flex()
+                        .child(
+                            h_flex().gap_2().child(Label::new(result.id.clone())).child(
+                                Label::new(result.source.clone())
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            ),
+                        )
+                        .children(result.description.clone().map(|description| {
+                            Label::new(description)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted)
+                                .single_line()
+                                .truncate()
+                        })),
+                )
+                .end_slot(
+                    Label::new(latest_version.to_string())
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                ),
+        )
+    }
+
+    fn render_footer(&self, _: &mut Window, cx: &mut Context<Picker<Self>>) -> Option<AnyElement> {
+        Some(
+            h_flex()
+                .w_full()
+                .p_2()
+                .border_t_1()
+                .border_color(cx.theme().colors().border_variant)
+                .child(
+                    Checkbox::new(
+                        "include-prerelease",
+                        ToggleState::from(self.include_prerelease),
+                    )
+                    .label("Include prerelease")
+                    .on_click(cx.listener(
+                        |picker, state: &ToggleState, window, cx| {
+                            picker.delegate.include_prerelease = *state == ToggleState::Selected;
+                            picker.refresh(window, cx);
+                        },
+                    )),
+                )
+                .into_any(),
+        )
+    }
+}
@@ -0,0 +1,189 @@
+use fuzzy::{StringMatch, StringMatchCandidate, match_strings};
+use gpui::{DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Task, WeakEntity};
+use picker::{Picker, PickerDelegate};
+use std::{path::PathBuf, sync::Arc};
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt;
+use workspace::ModalView;
+
+type OnConfirm = Box<dyn FnOnce(PathBuf, &mut Window, &mut App)>;
+
+/// A project that may be referenced, with its path relative to the solution.
+pub struct ReferenceCandidate {
+    pub name: String,
+    pub path: PathBuf,
+    pub relative_path: String,
+}
+
+/// A modal for picking the project a new `<ProjectReference>` points to.
+pub struct ProjectReferencePicker {
+    picker: Entity<Picker<ProjectReferencePickerDelegate>>,
+}
+
+impl ProjectReferencePicker {
+    pub fn new(
+        projects: Vec<ReferenceCandidate>,
+        on_confirm: impl FnOnce(PathBuf, &mut Window, &mut App) + 'static,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let candidates = projects
+            .iter()
+            .enumerate()
+            .map(|(ix, project)| StringMatchCandidate::new(ix, &project.name))
+            .collect();
+        let delegate = ProjectReferencePickerDelegate {
+            reference_picker: cx.entity().downgrade(),
+            projects,
+            candidates,
+            matches: Vec::new(),
+            selected_index: 0,
+            on_confirm: Some(Box::new(on_confirm)),
+        };
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+        Self { picker }
+    }
+}
+
+impl Render for ProjectReferencePicker {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+impl Focusable for ProjectReferencePicker {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for ProjectReferencePicker {}
+impl ModalView for ProjectReferencePicker {}
+
+struct ProjectReferencePickerDelegate {
+    reference_picker: WeakEntity<ProjectReferencePicker>,
+    projects: Vec<ReferenceCandidate>,
+    candidates: Vec<StringMatchCandidate>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+    on_confirm: Option<OnConfirm>,
+}
+
+impl PickerDelegate for ProjectReferencePickerDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Add a reference to project…".into()
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some("No other projects can be referenced without creating a cycle".into())
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(&mut self, ix: usize, _: &mut Window, _: &mut Context<Picker<Self>>) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let background = cx.background_executor().clone();
+        let candidates = self.candidates.clone();
+        cx.spawn_in(window, async move |this, cx| {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    true,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+            };
+
+            this.update(cx, |this, cx| {
+                let delegate = &mut this.delegate;
+                delegate.matches = matches;
+                delegate.selected_index = delegate
+                    .selected_index
+                    .min(delegate.matches.len().saturating_sub(1));
+                cx.notify();
+            })
+            .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(path) = self
+            .matches
+            .get(self.selected_index)
+            .and_then(|mat| self.projects.get(mat.candidate_id))
+            .map(|project| project.path.clone())
+        else {
+            return;
+        };
+        if let Some(on_confirm) = self.on_confirm.take() {
+            window.defer(cx, move |window, cx| on_confirm(path, window, cx));
+        }
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.reference_picker
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let mat = self.matches.get(ix)?;
+        let project = self.projects.get(mat.candidate_id)?;
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(
+                    h_flex()
+                        .gap_2()
+                        .child(HighlightedLabel::new(
+                            project.name.clone(),
+                            mat.positions.clone(),
+                        ))
+                        .child(
+                            Label::new(project.relative_path.clone())
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        ),
+                ),
+        )
+    }
+}
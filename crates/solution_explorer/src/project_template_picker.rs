@@ -0,0 +1,210 @@
+use anyhow::{Context as _, Result};
+use dotnet::{ProjectTemplate, parse_template_list};
+use fuzzy::{StringMatch, StringMatchCandidate, match_strings};
+use gpui::{DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Task, WeakEntity};
+use picker::{Picker, PickerDelegate};
+use std::sync::Arc;
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt;
+use workspace::ModalView;
+
+type OnConfirm = Box<dyn FnOnce(ProjectTemplate, &mut Window, &mut App)>;
+
+/// Lists the project templates installed for `dotnet new`.
+pub async fn load_project_templates() -> Result<Vec<ProjectTemplate>> {
+    let output = util::command::new_smol_command("dotnet")
+        .args(["new", "list", "--type", "project"])
+        .output()
+        .await
+        .context("running `dotnet new list`")?;
+    anyhow::ensure!(
+        output.status.success(),
+        "`dotnet new list` failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(parse_template_list(&stdout))
+}
+
+/// A modal for picking the `dotnet new` template of a new project.
+pub struct ProjectTemplatePicker {
+    picker: Entity<Picker<ProjectTemplatePickerDelegate>>,
+}
+
+impl ProjectTemplatePicker {
+    pub fn new(
+        templates: Vec<ProjectTemplate>,
+        on_confirm: impl FnOnce(ProjectTemplate, &mut Window, &mut App) + 'static,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let candidates = templates
+            .iter()
+            .enumerate()
+            .map(|(ix, template)| {
+                StringMatchCandidate::new(
+                    ix,
+                    &format!("{} {}", template.name, template.short_names.join(" ")),
+                )
+            })
+            .collect();
+        let delegate = ProjectTemplatePickerDelegate {
+            template_picker: cx.entity().downgrade(),
+            templates,
+            candidates,
+            matches: Vec::new(),
+            selected_index: 0,
+            on_confirm: Some(Box::new(on_confirm)),
+        };
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+        Self { picker }
+    }
+}
+
+impl Render for ProjectTemplatePicker {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+impl Focusable for ProjectTemplatePicker {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for ProjectTemplatePicker {}
+impl ModalView for ProjectTemplatePicker {}
+
+struct ProjectTemplatePickerDelegate {
+    template_picker: WeakEntity<ProjectTemplatePicker>,
+    templates: Vec<ProjectTemplate>,
+    candidates: Vec<StringMatchCandidate>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+    on_confirm: Option<OnConfirm>,
+}
+
+impl PickerDelegate for ProjectTemplatePickerDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Select a project template…".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(&mut self, ix: usize, _: &mut Window, _: &mut Context<Picker<Self>>) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let background = cx.background_executor().clone();
+        let candidates = self.candidates.clone();
+        cx.spawn_in(window, async move |this, cx| {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    true,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+            };
+
+            this.update(cx, |this, cx| {
+                let delegate = &mut this.delegate;
+                delegate.matches = matches;
+                delegate.selected_index = delegate
+                    .selected_index
+                    .min(delegate.matches.len().saturating_sub(1));
+                cx.notify();
+            })
+            .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(template) = self
+            .matches
+            .get(self.selected_index)
+            .and_then(|mat| self.templates.get(mat.candidate_id))
+            .cloned()
+        else {
+            return;
+        };
+        if let Some(on_confirm) = self.on_confirm.take() {
+            window.defer(cx, move |window, cx| on_confirm(template, window, cx));
+        }
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.template_picker
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let mat = self.matches.get(ix)?;
+        let template = self.templates.get(mat.candidate_id)?;
+        // Only highlight positions that fall within the template name.
+        let positions = mat
+            .positions
+            .iter()
+            .copied()
+            .filter(|position| *position < template.name.len())
+            .collect();
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(
+                    h_flex()
+                        .gap_2()
+                        .child(HighlightedLabel::new(template.name.clone(), positions))
+                        .child(
+                            Label::new(template.short_name().to_string())
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        ),
+                )
+                .end_slot::<Label>(template.default_language.clone().map(|language| {
+                    Label::new(language)
+                        .size(LabelSize::Small)
+                        .color(Color::Muted)
+                })),
+        )
+    }
+}
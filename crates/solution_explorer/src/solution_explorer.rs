@@ -1,28 +1,77 @@
-use anyhow::Result;
-use collections::HashSet;
+mod dotnet_watch;
+mod launch_profile_picker;
+mod name_prompt;
+mod package_choice_picker;
+mod package_search_picker;
+mod project_reference_picker;
+mod project_template_picker;
+mod test_explorer;
+
+use anyhow::{Context as _, Result};
+use collections::{HashMap, HashSet};
+use db::kvp::KEY_VALUE_STORE;
+use dotnet::{
+    Dependency, DependencyKind, EvaluationOptions, LaunchSettings, NuGetVersion, OutdatedPackage,
+    ProjectGraph, ProjectItemNode, ProjectModel, ProjectTemplate, RealProjectFiles,
+    SolutionConfiguration, SolutionFile, StartupAction, StartupProjects, add_package_reference,
+    add_project_reference, find_project_file,
+    nuget::{package_sources, set_central_package_version, version_conflicts},
+    project_dependencies, project_item_tree,
+    project_items::{rename_item_references, renamed_nested_file},
+    remove_package_reference, remove_project_reference, set_package_reference_version,
+};
+use dotnet_watch::DotNetWatch;
 use gpui::{
-    Action, App, AsyncWindowContext, Context, DismissEvent, Entity, EventEmitter, FocusHandle,
-    Focusable, IntoElement, Point, Pixels, Render, Subscription, Task, UniformListScrollHandle,
-    WeakEntity, Window, actions, anchored, deferred, div, px, uniform_list,
+    Action, AnyElement, App, AsyncWindowContext, ClickEvent, Context, DismissEvent, Entity,
+    EventEmitter, FocusHandle, Focusable, FontWeight, IntoElement, MouseDownEvent,
+    PathPromptOptions, Pixels, Point, PromptLevel, Render, Subscription, Task,
+    UniformListScrollHandle, WeakEntity, Window, actions, anchored, deferred, div, px,
+    uniform_list,
 };
-use languages::csharp::{SolutionFile, parse_csproj_packages};
-use project::{Fs, Project};
+use launch_profile_picker::LaunchProfilePicker;
+use name_prompt::NamePrompt;
+use package_choice_picker::{PackageChoice, PackageChoicePicker};
+use package_search_picker::{PackageSearchPicker, load_outdated_packages, package_versions};
+use project::{
+    DirectoryLister, Fs, PathChange, Project, RenameOptions, UpdatedEntriesSet, WorktreeId,
+    dotnet_startup_projects::dotnet_debug_adapter,
+};
+use project_reference_picker::{ProjectReferencePicker, ReferenceCandidate};
+use project_template_picker::{ProjectTemplatePicker, load_project_templates};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use settings::{DockSide, Settings, SettingsStore, update_settings_file};
-use std::path::{Path, PathBuf};
+use std::{
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use ui::{
-    Color, ContextMenu, Icon, IconName, Label, LabelSize, ListItem, ListItemSpacing, ScrollAxes,
-    Scrollbars, WithScrollbar, prelude::*,
+    Color, ContextMenu, DropdownMenu, DropdownStyle, Icon, IconName, Label, LabelSize, ListItem,
+    ListItemSpacing, ScrollAxes, Scrollbars, Tooltip, WithScrollbar, prelude::*,
 };
+use util::ResultExt;
+pub use dotnet_watch::DotNetWatchIndicator;
+pub use test_explorer::TestExplorerPanel;
 use workspace::{
     OpenOptions, Workspace,
     dock::{DockPosition, Panel, PanelEvent},
 };
 use zed_actions::{solution_explorer::ToggleFocus, task::Spawn};
-use task::SpawnInTerminal;
+use task::{BuildTaskDefinition, DebugScenario, SpawnInTerminal, TaskContext, TaskTemplate};
 
 const SOLUTION_EXPLORER_PANEL_KEY: &str = "SolutionExplorerPanel";
+/// The debug locator that turns `dotnet` tasks into debug requests
+const DEBUG_LOCATOR: &str = "dotnet-locator";
+
+#[derive(Serialize, Deserialize)]
+struct SerializedSolutionExplorerPanel {
+    width: Option<Pixels>,
+    /// The active solution configuration, as `Configuration|Platform`
+    configuration: Option<String>,
+    /// The startup projects, if chosen
+    startup_projects: Option<StartupProjects>,
+}
 
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct SolutionExplorerSettings {
@@ -62,14 +111,37 @@ impl Settings for SolutionExplorerSettings {
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 enum SolutionTreeNode {
     Solution { path: PathBuf },
-    Project { name: String, path: PathBuf, guid: String },
-    Package { project_guid: String, package_id: String, version: Option<String> },
+    Folder { id: String, name: String },
+    SolutionItem { path: PathBuf },
+    Project { name: String, path: PathBuf, id: String },
+    Dependencies { project_id: String },
+    DependencyGroup { project_id: String, kind: DependencyKind },
+    Dependency { project_id: String, kind: DependencyKind, include: String, path: Option<PathBuf> },
+    Package { project_id: String, package_id: String, version: Option<String> },
+    /// A folder of a project, by its absolute path
+    ItemFolder { project_id: String, path: PathBuf },
+    /// A file of a project, by its absolute path
+    Item { project_id: String, path: PathBuf, is_linked: bool },
 }
 
 struct SolutionTreeState {
     solution: Option<SolutionFile>,
-    expanded_projects: HashSet<String>, // Project GUIDs
-    expanded_packages: HashSet<String>, // Project GUIDs that have packages expanded
+    /// Dependencies of each project, keyed by project id
+    dependencies: HashMap<String, Vec<Dependency>>,
+    /// Project references between the projects of the solution
+    graph: ProjectGraph,
+    /// Project reference cycles, reported on the projects involved
+    cycles: Vec<Vec<PathBuf>>,
+    /// `Directory.Packages.props` of each project using Central Package Management
+    central_package_files: HashMap<String, PathBuf>,
+    /// The chosen startup projects, or the default ones
+    startup_projects: StartupProjects,
+    /// Folders and files of the projects expanded so far, keyed by project id
+    project_items: HashMap<String, Vec<ProjectItemNode>>,
+    collapsed_folders: HashSet<String>, // Solution folder ids
+    expanded_projects: HashSet<String>, // Project ids
+    expanded_dependencies: HashSet<SolutionTreeNode>, // Dependencies nodes and groups
+    expanded_items: HashSet<SolutionTreeNode>, // Item folders and files with nested files
     selected_nodes: HashSet<SolutionTreeNode>, // Support multi-selection
 }
 
@@ -77,8 +149,16 @@ impl Default for SolutionTreeState {
     fn default() -> Self {
         Self {
             solution: None,
+            dependencies: HashMap::default(),
+            graph: ProjectGraph::default(),
+            cycles: Vec::new(),
+            central_package_files: HashMap::default(),
+            startup_projects: StartupProjects::default(),
+            project_items: HashMap::default(),
+            collapsed_folders: HashSet::default(),
             expanded_projects: HashSet::new(),
-            expanded_packages: HashSet::new(),
+            expanded_dependencies: HashSet::default(),
+            expanded_items: HashSet::default(),
             selected_nodes: HashSet::new(),
         }
     }
@@ -92,8 +172,19 @@ pub struct SolutionExplorerPanel {
     scroll_handle: UniformListScrollHandle,
     width: Option<Pixels>,
     state: SolutionTreeState,
+    visible_items: Vec<TreeItem>,
     solution_load_task: Task<()>,
+    /// Listings of project files in progress, keyed by project id
+    item_load_tasks: HashMap<String, Task<()>>,
     context_menu: Option<(Entity<ContextMenu>, Point<Pixels>, Subscription)>,
+    /// Whether package searches and updates offer prerelease versions
+    include_prerelease: bool,
+    /// The solution configuration that builds, tasks and debugging use
+    configuration: Option<SolutionConfiguration>,
+    /// The startup projects chosen by the user, `None` until they choose any
+    startup_projects: Option<StartupProjects>,
+    pending_serialization: Task<Option<()>>,
+    _project_subscription: Subscription,
 }
 
 actions!(
@@ -111,6 +202,18 @@ actions!(
         CollapseAllProjects,
         /// Opens the selected project file.
         OpenSelectedProject,
+        /// Creates a project from a `dotnet new` template and adds it to the solution.
+        AddNewProject,
+        /// Adds existing project files to the solution.
+        AddExistingProject,
+        /// Creates a solution folder.
+        NewSolutionFolder,
+        /// Renames the selected project or solution folder.
+        RenameSolutionItem,
+        /// Removes the selected project or solution folder from the solution.
+        RemoveFromSolution,
+        /// Builds the solution and starts its startup projects.
+        StartStartupProjects,
     ]
 );
 
@@ -151,7 +254,7 @@ pub struct CleanProject {
 #[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize, schemars::JsonSchema, Action)]
 #[action(namespace = solution_explorer)]
 pub struct SetStartupProject {
-    pub project_guid: String,
+    pub project_id: String,
 }
 
 #[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize, schemars::JsonSchema, Action)]
@@ -180,6 +283,11 @@ impl SolutionExplorerPanel {
         let fs = project.read(cx).fs().clone();
         let focus_handle = cx.focus_handle();
         let scroll_handle = UniformListScrollHandle::default();
+        let project_subscription = cx.subscribe(&project, |this, _, event, cx| {
+            if let project::Event::WorktreeUpdatedEntries(worktree_id, changes) = event {
+                this.worktree_entries_changed(*worktree_id, changes, cx);
+            }
+        });
 
         let mut panel = Self {
             project,
@@ -189,8 +297,15 @@ impl SolutionExplorerPanel {
             scroll_handle,
             width: None,
             state: SolutionTreeState::default(),
+            visible_items: Vec::new(),
             solution_load_task: Task::ready(()),
+            item_load_tasks: HashMap::default(),
             context_menu: None,
+            include_prerelease: false,
+            configuration: None,
+            startup_projects: None,
+            pending_serialization: Task::ready(None),
+            _project_subscription: project_subscription,
         };
 
         panel.load_solution(window, cx);
@@ -198,242 +313,1996 @@ impl SolutionExplorerPanel {
     }
 
     fn load_solution(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        let project = self.project.clone();
-        let fs = self.fs.clone();
-        let panel_entity = window.entity();
-
-        self.solution_load_task = cx.spawn(|mut cx| async move {
-            let solution_path = project
-                .update(&mut cx, |project, cx| {
-                    // Find solution file in project
-                    project
-                        .worktrees()
-                        .find_map(|worktree| {
-                            let root = worktree.read(cx).abs_path();
-                            find_solution_file(&root, &fs)
-                        })
-                })
-                .ok()
-                .flatten();
-
-            if let Some(solution_path) = solution_path {
-                if let Ok(content) = std::fs::read_to_string(&solution_path) {
-                    let base_dir = solution_path.parent().unwrap_or(Path::new("."));
-                    if let Ok(mut solution) = SolutionFile::parse(&content, base_dir) {
-                        // Load packages for each project
-                        for project in &mut solution.projects {
-                            let project_path = base_dir.join(&project.path);
-                            if let Ok(csproj_content) = std::fs::read_to_string(&project_path) {
-                                if let Ok(packages) = parse_csproj_packages(&csproj_content) {
-                                    project.packages = packages;
+        // Reload the solution we already show, so that edits don't switch to another file.
+        let known_path = self
+            .state
+            .solution
+            .as_ref()
+            .map(|solution| solution.path.clone());
+        let roots = self
+            .project
+            .read(cx)
+            .worktrees(cx)
+            .map(|worktree| worktree.read(cx).abs_path().to_path_buf())
+            .collect::<Vec<_>>();
+        let configuration = self.configuration.clone();
+
+        self.solution_load_task = cx.spawn_in(window, async move |this, cx| {
+            let loaded = cx
+                .background_spawn(async move {
+                    let solution_path = known_path
+                        .or_else(|| roots.iter().find_map(|root| find_solution_file(root)))?;
+                    let solution = match SolutionFile::load(&solution_path) {
+                        Ok(solution) => solution,
+                        Err(error) => {
+                            log::error!("Failed to load solution: {error:#}");
+                            return None;
+                        }
+                    };
+                    let configuration = configuration
+                        .filter(|configuration| solution.configurations.contains(configuration))
+                        .or_else(|| solution.default_configuration().cloned());
+                    // Evaluate each project to find its dependencies
+                    let mut graph = ProjectGraph::new();
+                    let mut dependencies = HashMap::default();
+                    let mut central_package_files = HashMap::default();
+                    let mut launchable_projects = HashSet::default();
+                    for project in &solution.projects {
+                        let path = solution.project_abs_path(project);
+                        let options = configuration
+                            .as_ref()
+                            .map(|configuration| {
+                                solution.evaluation_options(project, configuration)
+                            })
+                            .unwrap_or_default();
+                        let project_dependencies =
+                            match ProjectModel::load(&path, &options, &RealProjectFiles) {
+                                Ok(model) => {
+                                    if model.manages_package_versions_centrally
+                                        && let Some(props) = model.central_packages_file()
+                                    {
+                                        central_package_files
+                                            .insert(project.id.clone(), props.to_path_buf());
+                                    }
+                                    if model.is_executable() && !model.is_test_project {
+                                        launchable_projects.insert(project.id.clone());
+                                    }
+                                    project_dependencies(&model)
                                 }
-                            }
+                                Err(error) => {
+                                    log::warn!("Failed to evaluate {}: {error:#}", path.display());
+                                    Vec::new()
+                                }
+                            };
+                        graph.add_project(
+                            path,
+                            project_dependencies
+                                .iter()
+                                .filter(|dependency| dependency.kind == DependencyKind::Project)
+                                .filter_map(|dependency| dependency.path.clone()),
+                        );
+                        dependencies.insert(project.id.clone(), project_dependencies);
+                    }
+                    let cycles = graph.solution_build_order().cycles;
+                    let default_startup_projects =
+                        StartupProjects::default_for(&solution, |project| {
+                            launchable_projects.contains(&project.id)
+                        });
+                    Some((
+                        solution,
+                        configuration,
+                        default_startup_projects,
+                        dependencies,
+                        central_package_files,
+                        graph,
+                        cycles,
+                    ))
+                })
+                .await;
+
+            if let Some((
+                solution,
+                configuration,
+                default_startup_projects,
+                dependencies,
+                central_package_files,
+                graph,
+                cycles,
+            )) = loaded
+            {
+                this.update(cx, |this, cx| {
+                    if !cycles.is_empty() {
+                        log::warn!("Project reference cycles: {}", describe_cycles(&cycles));
+                    }
+                    if let Some(configuration) = &configuration {
+                        this.project
+                            .read(cx)
+                            .dotnet_configurations()
+                            .set_solution_configuration(&solution, configuration);
+                    }
+                    this.configuration = configuration;
+                    let startup_projects = match this.startup_projects.as_mut() {
+                        Some(startup_projects) => {
+                            startup_projects.retain_solution_projects(&solution);
+                            startup_projects.clone()
                         }
-                        
-                        panel_entity.update(&mut cx, |panel, cx| {
-                            panel.state.solution = Some(solution);
-                            cx.notify();
-                        })
-                        .ok();
+                        None => default_startup_projects,
+                    };
+                    this.project
+                        .read(cx)
+                        .dotnet_startup_projects()
+                        .set_startup_projects(&solution, &startup_projects);
+                    this.state.startup_projects = startup_projects;
+                    this.state.solution = Some(solution);
+                    this.state.dependencies = dependencies;
+                    this.state.central_package_files = central_package_files;
+                    this.state.graph = graph;
+                    this.state.cycles = cycles;
+                    // Items may differ between configurations, and projects may be gone
+                    let listed_projects =
+                        this.state.project_items.keys().cloned().collect::<Vec<_>>();
+                    for project_id in listed_projects {
+                        this.load_project_items(&project_id, cx);
                     }
+                    cx.notify();
+                })
+                .ok();
+            }
+        });
+    }
+
+    /// Lists the folders and files of a project, evaluating it with its
+    /// wildcards expanded.
+    fn load_project_items(&mut self, project_id: &str, cx: &mut Context<Self>) {
+        let Some(path) = self.project_abs_path(project_id) else {
+            self.state.project_items.remove(project_id);
+            self.item_load_tasks.remove(project_id);
+            return;
+        };
+        let options = self
+            .state
+            .solution
+            .as_ref()
+            .zip(self.configuration.as_ref())
+            .and_then(|(solution, configuration)| {
+                let project = solution.project_by_id(project_id)?;
+                Some(solution.evaluation_options(project, configuration))
+            })
+            .unwrap_or_default();
+        let project_id = project_id.to_string();
+        let task = cx.spawn({
+            let project_id = project_id.clone();
+            async move |this, cx| {
+                let items = cx
+                    .background_spawn(async move {
+                        let options = EvaluationOptions {
+                            expand_wildcards: true,
+                            ..options
+                        };
+                        ProjectModel::load(&path, &options, &RealProjectFiles)
+                            .map(|model| project_item_tree(&model))
+                            .with_context(|| format!("listing the files of {}", path.display()))
+                    })
+                    .await;
+                this.update(cx, |this, cx| {
+                    match items {
+                        Ok(items) => {
+                            this.state.project_items.insert(project_id, items);
+                        }
+                        Err(error) => log::warn!("{error:#}"),
+                    }
+                    cx.notify();
+                })
+                .ok();
+            }
+        });
+        self.item_load_tasks.insert(project_id, task);
+    }
+
+    /// Lists the files of projects again when files are added, removed or
+    /// renamed in their directories, or when MSBuild files there change.
+    fn worktree_entries_changed(
+        &mut self,
+        worktree_id: WorktreeId,
+        changes: &UpdatedEntriesSet,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(solution) = &self.state.solution else {
+            return;
+        };
+        let Some(worktree) = self.project.read(cx).worktree_for_id(worktree_id, cx) else {
+            return;
+        };
+        let worktree = worktree.read(cx);
+        let changed_paths = changes
+            .iter()
+            .map(|(path, _, change)| (worktree.absolutize(path), change))
+            .filter(|(path, change)| {
+                !matches!(change, PathChange::Updated | PathChange::Loaded)
+                    || path.extension().is_some_and(|extension| {
+                        ["csproj", "fsproj", "vbproj", "props", "targets"]
+                            .iter()
+                            .any(|known| extension.eq_ignore_ascii_case(known))
+                    })
+            })
+            .map(|(path, _)| path)
+            .collect::<Vec<_>>();
+        let stale_projects = solution
+            .projects
+            .iter()
+            .filter(|project| self.state.project_items.contains_key(&project.id))
+            .filter(|project| {
+                let path = solution.project_abs_path(project);
+                let directory = path.parent().unwrap_or(Path::new(""));
+                changed_paths
+                    .iter()
+                    .any(|changed| changed.starts_with(directory))
+            })
+            .map(|project| project.id.clone())
+            .collect::<Vec<_>>();
+        for project_id in stale_projects {
+            self.load_project_items(&project_id, cx);
+        }
+    }
+
+    fn show_error(&self, error: &anyhow::Error, cx: &mut Context<Self>) {
+        log::error!("{error:#}");
+        self.workspace
+            .update(cx, |workspace, cx| workspace.show_error(error, cx))
+            .ok();
+    }
+
+    /// Switches the solution configuration used by builds, tasks and debug launches.
+    ///
+    /// Projects are re-evaluated, since their dependencies may differ between
+    /// configurations.
+    fn set_configuration(
+        &mut self,
+        configuration: SolutionConfiguration,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(solution) = &self.state.solution {
+            self.project
+                .read(cx)
+                .dotnet_configurations()
+                .set_solution_configuration(solution, &configuration);
+        }
+        self.configuration = Some(configuration);
+        self.serialize(cx);
+        self.load_solution(window, cx);
+        cx.notify();
+    }
+
+    /// Arguments selecting the active configuration for `dotnet` commands on the
+    /// solution, or on the project at `project_path`.
+    fn configuration_args(&self, project_path: Option<&Path>) -> Vec<String> {
+        let (Some(solution), Some(configuration)) = (&self.state.solution, &self.configuration)
+        else {
+            return Vec::new();
+        };
+        let project = project_path.and_then(|project_path| {
+            solution
+                .projects
+                .iter()
+                .find(|project| solution.project_abs_path(project) == project_path)
+        });
+        let (configuration, platform) = match project {
+            Some(project) => {
+                let options = solution.evaluation_options(project, configuration);
+                (options.configuration, options.platform)
+            }
+            None => (
+                configuration.configuration.clone(),
+                Some(configuration.platform.clone()),
+            ),
+        };
+        let mut args = vec!["--configuration".to_string(), configuration];
+        args.extend(platform.map(|platform| format!("-p:Platform={platform}")));
+        args
+    }
+
+    /// Replaces the startup projects with the user's choice.
+    fn set_startup_projects(&mut self, startup_projects: StartupProjects, cx: &mut Context<Self>) {
+        if let Some(solution) = &self.state.solution {
+            self.project
+                .read(cx)
+                .dotnet_startup_projects()
+                .set_startup_projects(solution, &startup_projects);
+        }
+        self.state.startup_projects = startup_projects.clone();
+        self.startup_projects = Some(startup_projects);
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    /// Changes what starting the solution does with the project at `project_path`.
+    fn set_startup_action(
+        &mut self,
+        project_path: &Path,
+        action: StartupAction,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(solution) = &self.state.solution else {
+            return;
+        };
+        let mut startup_projects = self.state.startup_projects.clone();
+        startup_projects.set_action(&solution.relative_path(project_path), action);
+        self.set_startup_projects(startup_projects, cx);
+    }
+
+    fn start_startup_projects_action(
+        &mut self,
+        _: &StartStartupProjects,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.start_startup_projects(window, cx);
+    }
+
+    /// Builds the solution, then starts each startup project: in a debug session,
+    /// or in a terminal when started without debugging.
+    fn start_startup_projects(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(solution) = &self.state.solution else {
+            return;
+        };
+        if self.state.startup_projects.is_empty() {
+            self.show_error(
+                &anyhow::anyhow!(
+                    "{} has no startup projects, choose them from a project's context menu",
+                    solution.name()
+                ),
+                cx,
+            );
+            return;
+        }
+        let launches = self
+            .state
+            .startup_projects
+            .iter()
+            .filter_map(|startup| {
+                let project = solution.project_by_path(&startup.path)?;
+                let path = solution.project_abs_path(project);
+                Some(ProjectLaunch {
+                    name: project.name.clone(),
+                    args: self.configuration_args(Some(&path)),
+                    path,
+                    action: startup.action,
+                    prebuilt: true,
+                })
+            })
+            .collect::<Vec<_>>();
+        if !self.check_build_order(&[], cx) {
+            return;
+        }
+
+        // Build once up front, so that launches don't race to build shared projects.
+        let mut args = vec![
+            "build".to_string(),
+            solution.path.to_string_lossy().into_owned(),
+        ];
+        args.extend(self.configuration_args(None));
+        let build = SpawnInTerminal {
+            label: format!("dotnet: build {}", solution.name()),
+            full_label: format!("dotnet: build {}", solution.name()),
+            command: Some("dotnet".to_string()),
+            args,
+            cwd: Some(solution.directory().to_path_buf()),
+            ..Default::default()
+        };
+        let Ok(build) = self.workspace.update(cx, |workspace, cx| {
+            workspace.spawn_in_terminal(build, window, cx)
+        }) else {
+            return;
+        };
+
+        cx.spawn_in(window, async move |this, cx| {
+            match build.await {
+                Some(Ok(status)) if status.success() => {}
+                Some(Ok(_)) | None => return,
+                Some(Err(error)) => {
+                    this.update(cx, |this, cx| this.show_error(&error, cx)).ok();
+                    return;
                 }
             }
+            this.update_in(cx, |this, window, cx| {
+                this.workspace
+                    .update(cx, |workspace, cx| {
+                        for launch in launches {
+                            launch.start(workspace, window, cx);
+                        }
+                    })
+                    .ok();
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    /// Starts `dotnet watch` for a project, or stops it if it runs already.
+    fn toggle_watch(&mut self, project_path: PathBuf, name: String, cx: &mut Context<Self>) {
+        let watch = DotNetWatch::global(cx);
+        if watch.read(cx).is_watching(&project_path) {
+            watch.update(cx, |watch, cx| watch.stop(&project_path, cx));
+            return;
+        }
+        if !self.project.read(cx).is_local() {
+            self.show_error(
+                &anyhow::anyhow!("dotnet watch only runs in local projects"),
+                cx,
+            );
+            return;
+        }
+        let run_args = self.configuration_args(Some(&project_path));
+        let workspace = self.workspace.clone();
+        let started = watch.update(cx, |watch, cx| {
+            watch.start(project_path, name.into(), run_args, workspace, cx)
         });
+        if let Err(error) = started {
+            self.show_error(&error, cx);
+        }
     }
 
-    fn find_solution_file(&self, cx: &mut Context<Self>) -> Option<PathBuf> {
-        self.project
-            .read(cx)
-            .worktrees()
-            .find_map(|worktree| {
-                let root = worktree.read(cx).abs_path();
-                find_solution_file(&root, &self.fs)
+    /// Runs or debugs a project with a launch profile picked from its launch
+    /// settings, as `dotnet run --launch-profile` does.
+    fn launch_with_profile(
+        &mut self,
+        project_path: PathBuf,
+        action: StartupAction,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(project_dir) = project_path.parent().map(Path::to_path_buf) else {
+            return;
+        };
+        let name = project_name(&project_path);
+        let args = self.configuration_args(Some(&project_path));
+        cx.spawn_in(window, async move |this, cx| {
+            let settings = cx
+                .background_spawn(async move { LaunchSettings::load(&project_dir) })
+                .await;
+            this.update_in(cx, |this, window, cx| {
+                let profiles = match settings {
+                    Ok(settings) => settings
+                        .iter()
+                        .flat_map(|settings| settings.project_profiles())
+                        .cloned()
+                        .collect::<Vec<_>>(),
+                    Err(error) => {
+                        this.show_error(&error, cx);
+                        return;
+                    }
+                };
+                if profiles.is_empty() {
+                    this.show_error(
+                        &anyhow::anyhow!(
+                            "{name} has no launch profiles that `dotnet run` can start"
+                        ),
+                        cx,
+                    );
+                    return;
+                }
+                let placeholder = format!("Select a launch profile of {name}…");
+                this.workspace
+                    .update(cx, |workspace, cx| {
+                        let workspace_handle = cx.entity().downgrade();
+                        workspace.toggle_modal(window, cx, |window, cx| {
+                            LaunchProfilePicker::new(
+                                placeholder,
+                                profiles,
+                                move |profile, window, cx| {
+                                    let mut args = args;
+                                    args.extend(["--launch-profile".to_string(), profile.name]);
+                                    let launch = ProjectLaunch {
+                                        name,
+                                        path: project_path,
+                                        action,
+                                        args,
+                                        prebuilt: false,
+                                    };
+                                    workspace_handle
+                                        .update(cx, |workspace, cx| {
+                                            launch.start(workspace, window, cx)
+                                        })
+                                        .ok();
+                                },
+                                window,
+                                cx,
+                            )
+                        });
+                    })
+                    .ok();
             })
+            .ok();
+        })
+        .detach();
     }
 
-    fn render_tree(&mut self, cx: &mut Context<Self>) -> impl IntoElement {
-        let items: Vec<_> = if let Some(ref solution) = self.state.solution {
-            let mut items = Vec::new();
-            let expanded_projects = self.state.expanded_projects.clone();
-            let selected_nodes = self.state.selected_nodes.clone();
+    fn serialization_key(workspace: &Workspace) -> Option<String> {
+        workspace
+            .database_id()
+            .map(|id| i64::from(id).to_string())
+            .or(workspace.session_id())
+            .map(|id| format!("{}-{:?}", SOLUTION_EXPLORER_PANEL_KEY, id))
+    }
 
-            // Solution root node
-            items.push(TreeItem {
-                node: SolutionTreeNode::Solution {
-                    path: solution.path.clone(),
-                },
-                label: solution
-                    .path
-                    .file_name()
-                    .and_then(|n| n.to_str())
-                    .unwrap_or("Solution")
-                    .to_string(),
-                icon: Some(IconName::FileCode),
-                depth: 0,
-                is_expanded: true,
-                has_children: !solution.projects.is_empty(),
-            });
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let Some(serialization_key) = self
+            .workspace
+            .read_with(cx, |workspace, _| Self::serialization_key(workspace))
+            .ok()
+            .flatten()
+        else {
+            return;
+        };
+        let width = self.width;
+        let configuration = self.configuration.as_ref().map(ToString::to_string);
+        let startup_projects = self.startup_projects.clone();
+        self.pending_serialization = cx.background_spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        serialization_key,
+                        serde_json::to_string(&SerializedSolutionExplorerPanel {
+                            width,
+                            configuration,
+                            startup_projects,
+                        })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
 
-            // Project nodes
-            let expanded_packages = self.state.expanded_packages.clone();
-            for project in &solution.projects {
-                let is_expanded = expanded_projects.contains(&project.guid);
-                let packages_expanded = expanded_packages.contains(&project.guid);
-                items.push(TreeItem {
-                    node: SolutionTreeNode::Project {
-                        name: project.name.clone(),
-                        path: project.path.clone(),
-                        guid: project.guid.clone(),
+    fn render_configuration_switcher(
+        &self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<impl IntoElement> {
+        let solution = self.state.solution.as_ref()?;
+        let active = self.configuration.clone()?;
+        let configurations = solution.configurations.clone();
+        let panel = cx.entity().downgrade();
+        Some(
+            h_flex()
+                .w_full()
+                .p_1()
+                .border_b_1()
+                .border_color(cx.theme().colors().border_variant)
+                .child(
+                    DropdownMenu::new(
+                        "solution-configuration",
+                        active.to_string(),
+                        ContextMenu::build(window, cx, move |mut menu, _, _| {
+                            for configuration in configurations {
+                                let panel = panel.clone();
+                                menu = menu.toggleable_entry(
+                                    configuration.to_string(),
+                                    configuration == active,
+                                    IconPosition::Start,
+                                    None,
+                                    move |window, cx| {
+                                        panel
+                                            .update(cx, |this, cx| {
+                                                this.set_configuration(
+                                                    configuration.clone(),
+                                                    window,
+                                                    cx,
+                                                )
+                                            })
+                                            .ok();
+                                    },
+                                );
+                            }
+                            menu
+                        }),
+                    )
+                    .style(DropdownStyle::Ghost)
+                    .full_width(true)
+                    .trigger_tooltip(Tooltip::text("Solution Configuration")),
+                )
+                .child(
+                    IconButton::new("start-startup-projects", IconName::PlayFilled)
+                        .icon_size(IconSize::Small)
+                        .icon_color(Color::Success)
+                        .tooltip(Tooltip::text("Start Startup Projects"))
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.start_startup_projects(window, cx)
+                        })),
+                ),
+        )
+    }
+
+    /// Applies a structural edit to the solution file on disk and reloads the tree.
+    ///
+    /// The file is re-read before editing, so changes made outside of Zed since the
+    /// last load are preserved.
+    fn edit_solution(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        edit: impl FnOnce(&mut SolutionFile) -> Result<()> + 'static,
+    ) {
+        let Some(path) = self
+            .state
+            .solution
+            .as_ref()
+            .map(|solution| solution.path.clone())
+        else {
+            return;
+        };
+        let fs = self.fs.clone();
+        cx.spawn_in(window, async move |this, cx| {
+            let result = async {
+                let content = fs.load(&path).await?;
+                let mut solution = SolutionFile::parse(&path, &content)?;
+                edit(&mut solution)?;
+                fs.atomic_write(path.clone(), solution.to_text()).await
+            }
+            .await;
+            this.update_in(cx, |this, window, cx| {
+                if let Err(error) = result {
+                    this.show_error(&error, cx);
+                }
+                this.load_solution(window, cx);
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    /// The single selected node, if exactly one node is selected.
+    fn selected_node(&self) -> Option<SolutionTreeNode> {
+        if self.state.selected_nodes.len() == 1 {
+            self.state.selected_nodes.iter().next().cloned()
+        } else {
+            None
+        }
+    }
+
+    /// The solution folder new items should be added to when `node` is selected.
+    fn target_folder(&self, node: Option<&SolutionTreeNode>) -> Option<String> {
+        match node? {
+            SolutionTreeNode::Folder { id, .. } => Some(id.clone()),
+            SolutionTreeNode::Project { id, .. } => self
+                .state
+                .solution
+                .as_ref()?
+                .project_by_id(id)?
+                .parent
+                .clone(),
+            _ => None,
+        }
+    }
+
+    fn add_new_project_action(
+        &mut self,
+        _: &AddNewProject,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let parent = self.target_folder(self.selected_node().as_ref());
+        self.add_new_project(parent, window, cx);
+    }
+
+    fn add_existing_project_action(
+        &mut self,
+        _: &AddExistingProject,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let parent = self.target_folder(self.selected_node().as_ref());
+        self.add_existing_project(parent, window, cx);
+    }
+
+    fn new_solution_folder_action(
+        &mut self,
+        _: &NewSolutionFolder,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let parent = self.target_folder(self.selected_node().as_ref());
+        self.new_solution_folder(parent, window, cx);
+    }
+
+    fn rename_action(
+        &mut self,
+        _: &RenameSolutionItem,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(node) = self.selected_node() {
+            self.rename_node(&node, window, cx);
+        }
+    }
+
+    fn remove_action(
+        &mut self,
+        _: &RemoveFromSolution,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(node) = self.selected_node() {
+            self.remove_node(&node, window, cx);
+        }
+    }
+
+    fn add_new_project(
+        &mut self,
+        parent: Option<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.state.solution.is_none() {
+            return;
+        }
+        let workspace = self.workspace.clone();
+        cx.spawn_in(window, async move |this, cx| {
+            let templates = match load_project_templates().await {
+                Ok(templates) => templates,
+                Err(error) => {
+                    this.update(cx, |this, cx| this.show_error(&error, cx)).ok();
+                    return;
+                }
+            };
+            workspace
+                .update_in(cx, |workspace, window, cx| {
+                    workspace.toggle_modal(window, cx, |window, cx| {
+                        ProjectTemplatePicker::new(
+                            templates,
+                            move |template, window, cx| {
+                                this.update(cx, |this, cx| {
+                                    this.prompt_for_project_name(template, parent, window, cx)
+                                })
+                                .ok();
+                            },
+                            window,
+                            cx,
+                        )
+                    });
+                })
+                .ok();
+        })
+        .detach();
+    }
+
+    fn prompt_for_project_name(
+        &mut self,
+        template: ProjectTemplate,
+        parent: Option<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let panel = cx.entity().downgrade();
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.toggle_modal(window, cx, |window, cx| {
+                    NamePrompt::new(
+                        format!("Name of the new {} project…", template.name),
+                        "Create project",
+                        None,
+                        move |name, window, cx| {
+                            panel
+                                .update(cx, |this, cx| {
+                                    this.create_project(template, name, parent, window, cx)
+                                })
+                                .ok();
+                        },
+                        window,
+                        cx,
+                    )
+                });
+            })
+            .ok();
+    }
+
+    /// Runs `dotnet new` next to the solution file and adds the created project.
+    fn create_project(
+        &mut self,
+        template: ProjectTemplate,
+        name: String,
+        parent: Option<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(directory) = self
+            .state
+            .solution
+            .as_ref()
+            .map(|solution| solution.directory().to_path_buf())
+        else {
+            return;
+        };
+        let output_directory = directory.join(&name);
+        cx.spawn_in(window, async move |this, cx| {
+            let result = async {
+                let output = util::command::new_smol_command("dotnet")
+                    .args(["new", template.short_name(), "--name", &name, "--output"])
+                    .arg(&output_directory)
+                    .current_dir(&directory)
+                    .output()
+                    .await
+                    .context("running `dotnet new`")?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "`dotnet new {}` failed: {}",
+                    template.short_name(),
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                find_project_file(&output_directory).with_context(|| {
+                    format!(
+                        "`dotnet new {}` did not create a project file",
+                        template.short_name()
+                    )
+                })
+            }
+            .await;
+            this.update_in(cx, |this, window, cx| match result {
+                Ok(project_file) => this.edit_solution(window, cx, move |solution| {
+                    let relative_path = solution.relative_path(&project_file);
+                    solution.add_project(&relative_path, parent.as_deref())?;
+                    Ok(())
+                }),
+                Err(error) => this.show_error(&error, cx),
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    fn add_existing_project(
+        &mut self,
+        parent: Option<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.state.solution.is_none() {
+            return;
+        }
+        let Some(prompt) = self
+            .workspace
+            .update(cx, |workspace, cx| {
+                workspace.prompt_for_open_path(
+                    PathPromptOptions {
+                        files: true,
+                        directories: false,
+                        multiple: true,
+                        prompt: Some("Add to Solution".into()),
                     },
-                    label: project.name.clone(),
-                    icon: Some(IconName::FileCode),
-                    depth: 1,
-                    is_expanded,
-                    has_children: !project.packages.is_empty(),
+                    DirectoryLister::Local(
+                        workspace.project().clone(),
+                        workspace.app_state().fs.clone(),
+                    ),
+                    window,
+                    cx,
+                )
+            })
+            .ok()
+        else {
+            return;
+        };
+        cx.spawn_in(window, async move |this, cx| {
+            let Some(paths) = prompt.await.ok().flatten() else {
+                return;
+            };
+            this.update_in(cx, |this, window, cx| {
+                this.edit_solution(window, cx, move |solution| {
+                    for path in paths {
+                        let relative_path = solution.relative_path(&path);
+                        solution.add_project(&relative_path, parent.as_deref())?;
+                    }
+                    Ok(())
+                })
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    fn new_solution_folder(
+        &mut self,
+        parent: Option<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.state.solution.is_none() {
+            return;
+        }
+        let panel = cx.entity().downgrade();
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.toggle_modal(window, cx, |window, cx| {
+                    NamePrompt::new(
+                        "Name of the new solution folder…",
+                        "Create folder",
+                        None,
+                        move |name, window, cx| {
+                            panel
+                                .update(cx, |this, cx| {
+                                    this.edit_solution(window, cx, move |solution| {
+                                        solution.add_folder(&name, parent.as_deref())?;
+                                        Ok(())
+                                    })
+                                })
+                                .ok();
+                        },
+                        window,
+                        cx,
+                    )
                 });
-                
-                // Add package nodes if project is expanded and packages are expanded
-                if is_expanded && packages_expanded {
-                    for package in &project.packages {
-                        items.push(TreeItem {
-                            node: SolutionTreeNode::Package {
-                                project_guid: project.guid.clone(),
-                                package_id: package.id.clone(),
-                                version: package.version.clone(),
-                            },
-                            label: if let Some(ref version) = package.version {
-                                format!("{} ({})", package.id, version)
-                            } else {
-                                package.id.clone()
-                            },
-                            icon: Some(IconName::Box),
-                            depth: 2,
-                            is_expanded: false,
-                            has_children: false,
+            })
+            .ok();
+    }
+
+    fn rename_node(
+        &mut self,
+        node: &SolutionTreeNode,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let (placeholder, current_name) = match node {
+            SolutionTreeNode::Folder { name, .. } => ("New solution folder name…", name.clone()),
+            SolutionTreeNode::Project { name, .. } => ("New project name…", name.clone()),
+            SolutionTreeNode::ItemFolder { path, .. } => ("New folder name…", file_name(path)),
+            SolutionTreeNode::Item {
+                path,
+                is_linked: false,
+                ..
+            } => ("New file name…", file_name(path)),
+            _ => return,
+        };
+        let node = node.clone();
+        let panel = cx.entity().downgrade();
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.toggle_modal(window, cx, |window, cx| {
+                    NamePrompt::new(
+                        placeholder,
+                        "Rename to",
+                        Some(current_name),
+                        move |name, window, cx| {
+                            panel
+                                .update(cx, |this, cx| match node {
+                                    SolutionTreeNode::Folder { id, .. } => {
+                                        this.edit_solution(window, cx, move |solution| {
+                                            solution.rename_folder(&id, &name)
+                                        })
+                                    }
+                                    SolutionTreeNode::Project { id, path, .. } => {
+                                        this.rename_project(id, path, name, window, cx)
+                                    }
+                                    SolutionTreeNode::ItemFolder { project_id, path }
+                                    | SolutionTreeNode::Item {
+                                        project_id, path, ..
+                                    } => this.rename_project_item(
+                                        &project_id,
+                                        &path,
+                                        &name,
+                                        window,
+                                        cx,
+                                    ),
+                                    _ => {}
+                                })
+                                .ok();
+                        },
+                        window,
+                        cx,
+                    )
+                });
+            })
+            .ok();
+    }
+
+    /// Renames the project file on disk, keeping its extension, and updates the solution.
+    fn rename_project(
+        &mut self,
+        id: String,
+        path: PathBuf,
+        name: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let extension = path
+            .extension()
+            .map(|extension| extension.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "csproj".to_string());
+        let new_path = path.with_file_name(format!("{name}.{extension}"));
+        let fs = self.fs.clone();
+        cx.spawn_in(window, async move |this, cx| {
+            let result = if new_path == path {
+                Ok(())
+            } else {
+                fs.rename(&path, &new_path, RenameOptions::default()).await
+            };
+            this.update_in(cx, |this, window, cx| match result {
+                Ok(()) => this.edit_solution(window, cx, move |solution| {
+                    let relative_path = solution.relative_path(&new_path);
+                    solution.rename_project(&id, &name, &relative_path)
+                }),
+                Err(error) => this.show_error(&error, cx),
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    /// Renames a file or folder of a project, along with the files nested
+    /// under it that are named after it.
+    fn rename_project_item(
+        &mut self,
+        project_id: &str,
+        path: &Path,
+        name: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let old_name = file_name(path);
+        if name.is_empty() || name == old_name {
+            return;
+        }
+        let mut renames = vec![(path.to_path_buf(), path.with_file_name(name))];
+        for nested in self.nested_files(project_id, path) {
+            if let Some(new_name) = renamed_nested_file(&old_name, name, &file_name(&nested)) {
+                let new_path = nested.with_file_name(new_name);
+                renames.push((nested, new_path));
+            }
+        }
+        self.rename_project_items(project_id, renames, window, cx);
+    }
+
+    /// Moves a dragged file or folder of a project into a folder of the same
+    /// project, along with the files nested under it.
+    fn move_project_item(
+        &mut self,
+        project_id: &str,
+        path: &Path,
+        target: &SolutionTreeNode,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let target_directory = match target {
+            SolutionTreeNode::ItemFolder {
+                project_id: target_project_id,
+                path,
+            } if target_project_id == project_id => path.clone(),
+            SolutionTreeNode::Project { id, path, .. } if id == project_id => {
+                path.parent().unwrap_or(Path::new("")).to_path_buf()
+            }
+            _ => return,
+        };
+        if path.parent() == Some(target_directory.as_path()) || target_directory.starts_with(path) {
+            return;
+        }
+        let renames = std::iter::once(path.to_path_buf())
+            .chain(self.nested_files(project_id, path))
+            .map(|old_path| {
+                let new_path = target_directory.join(file_name(&old_path));
+                (old_path, new_path)
+            })
+            .collect();
+        self.rename_project_items(project_id, renames, window, cx);
+    }
+
+    /// The files nested under a file of a project, at any depth.
+    fn nested_files(&self, project_id: &str, path: &Path) -> Vec<PathBuf> {
+        let mut nested = Vec::new();
+        let node = self
+            .state
+            .project_items
+            .get(project_id)
+            .and_then(|nodes| find_item_node(nodes, path));
+        if let Some(ProjectItemNode::File { children, .. }) = node {
+            push_nested_files(children, &mut nested);
+        }
+        nested
+    }
+
+    /// Renames or moves files and folders of a project through the project, so
+    /// that language servers can update the code referring to them, such as
+    /// the namespaces of moved C# files, then updates the items of the project
+    /// file that refer to them.
+    fn rename_project_items(
+        &mut self,
+        project_id: &str,
+        renames: Vec<(PathBuf, PathBuf)>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(project_path) = self.project_abs_path(project_id) else {
+            return;
+        };
+        let project = self.project.clone();
+        cx.spawn_in(window, async move |this, cx| {
+            let mut renamed = Vec::new();
+            let mut result = Ok(());
+            for (old_path, new_path) in renames {
+                let rename = project.update(cx, |project, cx| {
+                    let entry_id = project
+                        .project_path_for_absolute_path(&old_path, cx)
+                        .and_then(|path| project.entry_for_path(&path, cx))
+                        .map(|entry| entry.id)
+                        .with_context(|| {
+                            format!("{} is not in the workspace", old_path.display())
+                        })?;
+                    let new_project_path = project
+                        .project_path_for_absolute_path(&new_path, cx)
+                        .with_context(|| {
+                            format!("{} is not in the workspace", new_path.display())
+                        })?;
+                    anyhow::Ok(project.rename_entry(entry_id, new_project_path, cx))
+                });
+                result = match rename {
+                    Ok(Ok(rename)) => rename.await.map(|_| ()),
+                    Ok(Err(error)) | Err(error) => Err(error),
+                };
+                if result.is_err() {
+                    break;
+                }
+                renamed.push((old_path, new_path));
+            }
+            this.update_in(cx, |this, window, cx| {
+                if let Err(error) = &result {
+                    this.show_error(error, cx);
+                }
+                if renamed.is_empty() {
+                    return;
+                }
+                let project_directory =
+                    project_path.parent().unwrap_or(Path::new("")).to_path_buf();
+                this.edit_project_file(project_path, window, cx, move |content| {
+                    rename_item_references(content, &project_directory, &renamed)
+                });
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    fn remove_node(
+        &mut self,
+        node: &SolutionTreeNode,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let (id, message, detail, is_folder) = match node {
+            SolutionTreeNode::Folder { id, name } => (
+                id.clone(),
+                format!("Remove solution folder \"{name}\"?"),
+                "Projects in this folder are removed from the solution as well. No files are deleted.",
+                true,
+            ),
+            SolutionTreeNode::Project { id, name, .. } => (
+                id.clone(),
+                format!("Remove \"{name}\" from the solution?"),
+                "The project files are not deleted.",
+                false,
+            ),
+            _ => return,
+        };
+        let answer = window.prompt(
+            PromptLevel::Warning,
+            &message,
+            Some(detail),
+            &["Remove", "Cancel"],
+            cx,
+        );
+        cx.spawn_in(window, async move |this, cx| {
+            if answer.await != Ok(0) {
+                return;
+            }
+            this.update_in(cx, |this, window, cx| {
+                this.state.selected_nodes.retain(|node| match node {
+                    SolutionTreeNode::Folder { id: selected, .. }
+                    | SolutionTreeNode::Project { id: selected, .. } => *selected != id,
+                    _ => true,
+                });
+                this.edit_solution(window, cx, move |solution| {
+                    if is_folder {
+                        solution.remove_folder(&id)
+                    } else {
+                        solution.remove_project(&id)
+                    }
+                })
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    /// Moves a dragged project or solution folder onto `target`.
+    fn move_node(
+        &mut self,
+        dragged: &SolutionTreeNode,
+        target: &SolutionTreeNode,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let SolutionTreeNode::ItemFolder { project_id, path }
+        | SolutionTreeNode::Item {
+            project_id, path, ..
+        } = dragged
+        {
+            let (project_id, path) = (project_id.clone(), path.clone());
+            self.move_project_item(&project_id, &path, target, window, cx);
+            return;
+        }
+        let Some(solution) = &self.state.solution else {
+            return;
+        };
+        let (id, current_parent) = match dragged {
+            SolutionTreeNode::Folder { id, .. } => (
+                id.clone(),
+                solution.folder_by_id(id).and_then(|f| f.parent.clone()),
+            ),
+            SolutionTreeNode::Project { id, .. } => (
+                id.clone(),
+                solution.project_by_id(id).and_then(|p| p.parent.clone()),
+            ),
+            _ => return,
+        };
+        let parent = match target {
+            SolutionTreeNode::Solution { .. } => None,
+            // Dropping onto a project moves the item next to that project.
+            SolutionTreeNode::Folder { .. } | SolutionTreeNode::Project { .. } => {
+                self.target_folder(Some(target))
+            }
+            _ => return,
+        };
+        if parent == current_parent || parent.as_ref() == Some(&id) {
+            return;
+        }
+        self.edit_solution(window, cx, move |solution| {
+            solution.move_item(&id, parent.as_deref())
+        });
+    }
+
+    /// Applies an edit to a project file on disk and reloads the tree.
+    fn edit_project_file(
+        &mut self,
+        path: PathBuf,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        edit: impl FnOnce(&str) -> Result<String> + 'static,
+    ) {
+        let fs = self.fs.clone();
+        cx.spawn_in(window, async move |this, cx| {
+            let result = async {
+                let content = fs.load(&path).await?;
+                let edited = edit(&content)?;
+                fs.atomic_write(path.clone(), edited).await
+            }
+            .await;
+            this.update_in(cx, |this, window, cx| {
+                if let Err(error) = result {
+                    this.show_error(&error, cx);
+                }
+                this.load_solution(window, cx);
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    fn add_project_reference(
+        &mut self,
+        project_id: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(solution) = &self.state.solution else {
+            return;
+        };
+        let Some(project) = solution.project_by_id(project_id) else {
+            return;
+        };
+        let project_path = solution.project_abs_path(project);
+        let graph = &self.state.graph;
+        // Offer only projects that are not referenced yet and would not introduce a cycle.
+        let candidates = solution
+            .projects
+            .iter()
+            .filter_map(|candidate| {
+                let path = solution.project_abs_path(candidate);
+                let is_valid = !graph.references(&project_path).contains(&path)
+                    && !graph.would_create_cycle(&project_path, &path);
+                is_valid.then(|| ReferenceCandidate {
+                    name: candidate.name.clone(),
+                    relative_path: candidate.path.to_string_lossy().into_owned(),
+                    path,
+                })
+            })
+            .collect::<Vec<_>>();
+        let panel = cx.entity().downgrade();
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.toggle_modal(window, cx, |window, cx| {
+                    ProjectReferencePicker::new(
+                        candidates,
+                        move |reference, window, cx| {
+                            panel
+                                .update(cx, |this, cx| {
+                                    this.edit_project_file(
+                                        project_path.clone(),
+                                        window,
+                                        cx,
+                                        move |content| {
+                                            add_project_reference(
+                                                content,
+                                                &project_path,
+                                                &reference,
+                                            )
+                                        },
+                                    )
+                                })
+                                .ok();
+                        },
+                        window,
+                        cx,
+                    )
+                });
+            })
+            .ok();
+    }
+
+    fn remove_project_reference(
+        &mut self,
+        project_id: &str,
+        reference: PathBuf,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(project_path) = self.project_abs_path(project_id) else {
+            return;
+        };
+        self.edit_project_file(project_path.clone(), window, cx, move |content| {
+            remove_project_reference(content, &project_path, &reference)
+        });
+    }
+
+    fn project_abs_path(&self, project_id: &str) -> Option<PathBuf> {
+        let solution = self.state.solution.as_ref()?;
+        let project = solution.project_by_id(project_id)?;
+        Some(solution.project_abs_path(project))
+    }
+
+    /// The `<PackageReference>` of a project to `package_id`, if any.
+    fn package_dependency(&self, project_id: &str, package_id: &str) -> Option<&Dependency> {
+        self.state
+            .dependencies
+            .get(project_id)?
+            .iter()
+            .find(|dependency| {
+                dependency.kind == DependencyKind::Package
+                    && dependency.include.eq_ignore_ascii_case(package_id)
+            })
+    }
+
+    /// Searches the package sources of a project for a package to install.
+    fn manage_packages(&mut self, project_id: &str, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(project_path) = self.project_abs_path(project_id) else {
+            return;
+        };
+        let project_id = project_id.to_string();
+        let http = cx.http_client();
+        let include_prerelease = self.include_prerelease;
+        cx.spawn_in(window, async move |this, cx| {
+            let sources = cx
+                .background_spawn(async move {
+                    package_sources(project_path.parent().unwrap_or(Path::new("")))
+                })
+                .await;
+            this.update_in(cx, |this, window, cx| {
+                let panel = cx.entity().downgrade();
+                this.workspace
+                    .update(cx, |workspace, cx| {
+                        workspace.toggle_modal(window, cx, |window, cx| {
+                            PackageSearchPicker::new(
+                                http,
+                                sources,
+                                include_prerelease,
+                                move |package, include_prerelease, window, cx| {
+                                    panel
+                                        .update(cx, |this, cx| {
+                                            this.include_prerelease = include_prerelease;
+                                            this.pick_package_version(
+                                                project_id,
+                                                package.id,
+                                                package.versions,
+                                                window,
+                                                cx,
+                                            );
+                                        })
+                                        .ok();
+                                },
+                                window,
+                                cx,
+                            )
                         });
+                    })
+                    .ok();
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    /// Offers the versions of a package that is installed in a project, or is about to be.
+    fn update_package(
+        &mut self,
+        project_id: &str,
+        package_id: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(project_path) = self.project_abs_path(project_id) else {
+            return;
+        };
+        let project_id = project_id.to_string();
+        let package_id = package_id.to_string();
+        let http = cx.http_client();
+        let include_prerelease = self.include_prerelease;
+        cx.spawn_in(window, async move |this, cx| {
+            let versions = cx
+                .background_spawn({
+                    let package_id = package_id.clone();
+                    async move {
+                        let sources =
+                            package_sources(project_path.parent().unwrap_or(Path::new("")));
+                        package_versions(http, sources, package_id, include_prerelease).await
+                    }
+                })
+                .await;
+            this.update_in(cx, |this, window, cx| {
+                this.pick_package_version(project_id, package_id, versions, window, cx);
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    fn pick_package_version(
+        &mut self,
+        project_id: String,
+        package_id: String,
+        versions: Vec<NuGetVersion>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if versions.is_empty() {
+            self.show_error(
+                &anyhow::anyhow!("No versions of {package_id} were found in the package sources"),
+                cx,
+            );
+            return;
+        }
+        let installed = self
+            .package_dependency(&project_id, &package_id)
+            .and_then(|dependency| NuGetVersion::parse(dependency.version.as_deref()?));
+        let choices = versions
+            .iter()
+            .enumerate()
+            .map(|(ix, version)| PackageChoice {
+                label: version.to_string(),
+                detail: if installed.as_ref() == Some(version) {
+                    Some("installed".to_string())
+                } else if ix == 0 {
+                    Some("latest".to_string())
+                } else {
+                    None
+                },
+            })
+            .collect();
+        let placeholder = format!("Select a version of {package_id}…");
+        let panel = cx.entity().downgrade();
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.toggle_modal(window, cx, |window, cx| {
+                    PackageChoicePicker::new(
+                        placeholder,
+                        choices,
+                        move |ix, window, cx| {
+                            let version = versions[ix].to_string();
+                            panel
+                                .update(cx, |this, cx| {
+                                    this.set_package_version(
+                                        &project_id,
+                                        &package_id,
+                                        &version,
+                                        window,
+                                        cx,
+                                    )
+                                })
+                                .ok();
+                        },
+                        window,
+                        cx,
+                    )
+                });
+            })
+            .ok();
+    }
+
+    /// Installs a version of a package in a project, or updates the project to it.
+    ///
+    /// Centrally managed versions are changed in `Directory.Packages.props`, which
+    /// affects every project using the package.
+    fn set_package_version(
+        &mut self,
+        project_id: &str,
+        package_id: &str,
+        version: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(project_path) = self.project_abs_path(project_id) else {
+            return;
+        };
+        let installed = self
+            .package_dependency(project_id, package_id)
+            .map(|dependency| dependency.is_central_version);
+        let central_file = self.state.central_package_files.get(project_id).cloned();
+        let package_id = package_id.to_string();
+        let version = version.to_string();
+        match (installed, central_file) {
+            (Some(true), Some(props_path)) => {
+                self.edit_project_file(props_path.clone(), window, cx, move |content| {
+                    set_central_package_version(content, &props_path, &package_id, &version)
+                });
+            }
+            (Some(_), _) => {
+                self.edit_project_file(project_path.clone(), window, cx, move |content| {
+                    set_package_reference_version(content, &project_path, &package_id, &version)
+                });
+            }
+            (None, Some(props_path)) => {
+                self.edit_project_file(props_path.clone(), window, cx, {
+                    let package_id = package_id.clone();
+                    move |content| {
+                        set_central_package_version(content, &props_path, &package_id, &version)
+                    }
+                });
+                self.edit_project_file(project_path.clone(), window, cx, move |content| {
+                    add_package_reference(content, &project_path, &package_id, None)
+                });
+            }
+            (None, None) => {
+                self.edit_project_file(project_path.clone(), window, cx, move |content| {
+                    add_package_reference(content, &project_path, &package_id, Some(&version))
+                });
+            }
+        }
+    }
+
+    fn remove_package(
+        &mut self,
+        project_id: &str,
+        package_id: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(project_path) = self.project_abs_path(project_id) else {
+            return;
+        };
+        let package_id = package_id.to_string();
+        self.edit_project_file(project_path.clone(), window, cx, move |content| {
+            remove_package_reference(content, &project_path, &package_id)
+        });
+    }
+
+    /// Lists the packages of the solution with newer versions, updating the one picked.
+    fn show_outdated_packages(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(solution_path) = self
+            .state
+            .solution
+            .as_ref()
+            .map(|solution| solution.path.clone())
+        else {
+            return;
+        };
+        let include_prerelease = self.include_prerelease;
+        cx.spawn_in(window, async move |this, cx| {
+            let outdated = load_outdated_packages(&solution_path, include_prerelease).await;
+            this.update_in(cx, |this, window, cx| match outdated {
+                Ok(outdated) => this.pick_outdated_package(outdated, window, cx),
+                Err(error) => this.show_error(&error, cx),
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    fn pick_outdated_package(
+        &mut self,
+        outdated: Vec<OutdatedPackage>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(solution) = &self.state.solution else {
+            return;
+        };
+        // Multi-targeting projects report a package once per target framework.
+        let mut updates = Vec::<(String, OutdatedPackage)>::new();
+        for package in outdated {
+            let Some(project) = solution
+                .projects
+                .iter()
+                .find(|project| solution.project_abs_path(project) == package.project_path)
+            else {
+                continue;
+            };
+            if !updates.iter().any(|(project_id, existing)| {
+                *project_id == project.id && existing.id.eq_ignore_ascii_case(&package.id)
+            }) {
+                updates.push((project.id.clone(), package));
+            }
+        }
+        if updates.is_empty() {
+            window
+                .prompt(
+                    PromptLevel::Info,
+                    "All packages are up to date",
+                    None,
+                    &["OK"],
+                    cx,
+                )
+                .detach();
+            return;
+        }
+        let choices = updates
+            .iter()
+            .map(|(_, package)| PackageChoice {
+                label: format!(
+                    "{} {} → {}",
+                    package.id,
+                    package
+                        .requested_version
+                        .as_deref()
+                        .unwrap_or(&package.resolved_version),
+                    package.latest_version
+                ),
+                detail: Some(project_name(&package.project_path)),
+            })
+            .collect();
+        let panel = cx.entity().downgrade();
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.toggle_modal(window, cx, |window, cx| {
+                    PackageChoicePicker::new(
+                        "Update an outdated package…",
+                        choices,
+                        move |ix, window, cx| {
+                            let (project_id, package) = &updates[ix];
+                            panel
+                                .update(cx, |this, cx| {
+                                    this.set_package_version(
+                                        project_id,
+                                        &package.id,
+                                        &package.latest_version,
+                                        window,
+                                        cx,
+                                    )
+                                })
+                                .ok();
+                        },
+                        window,
+                        cx,
+                    )
+                });
+            })
+            .ok();
+    }
+
+    /// Lists packages referenced with different versions across the solution and
+    /// updates the projects of the one picked to its highest version.
+    fn consolidate_packages(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(solution) = &self.state.solution else {
+            return;
+        };
+        let conflicts = version_conflicts(solution.projects.iter().filter_map(|project| {
+            let dependencies = self.state.dependencies.get(&project.id)?;
+            Some((project.id.as_str(), dependencies.as_slice()))
+        }));
+        if conflicts.is_empty() {
+            window
+                .prompt(
+                    PromptLevel::Info,
+                    "All projects use the same package versions",
+                    None,
+                    &["OK"],
+                    cx,
+                )
+                .detach();
+            return;
+        }
+        let choices = conflicts
+            .iter()
+            .map(|conflict| PackageChoice {
+                label: format!("{} → {}", conflict.package_id, conflict.highest_version),
+                detail: Some(
+                    conflict
+                        .projects
+                        .iter()
+                        .map(|(project_id, version)| {
+                            let name = solution
+                                .project_by_id(project_id)
+                                .map_or(project_id.as_str(), |project| project.name.as_str());
+                            format!("{name} {version}")
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                ),
+            })
+            .collect();
+        let panel = cx.entity().downgrade();
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.toggle_modal(window, cx, |window, cx| {
+                    PackageChoicePicker::new(
+                        "Consolidate a package version…",
+                        choices,
+                        move |ix, window, cx| {
+                            let conflict = &conflicts[ix];
+                            let highest_version = NuGetVersion::parse(&conflict.highest_version);
+                            panel
+                                .update(cx, |this, cx| {
+                                    for (project_id, version) in &conflict.projects {
+                                        if NuGetVersion::parse(version) != highest_version {
+                                            this.set_package_version(
+                                                project_id,
+                                                &conflict.package_id,
+                                                &conflict.highest_version,
+                                                window,
+                                                cx,
+                                            );
+                                        }
+                                    }
+                                })
+                                .ok();
+                        },
+                        window,
+                        cx,
+                    )
+                });
+            })
+            .ok();
+    }
+
+    /// Reports project reference cycles that would make building `roots` fail.
+    ///
+    /// Returns whether the projects can be built.
+    fn check_build_order(&self, roots: &[PathBuf], cx: &mut Context<Self>) -> bool {
+        let order = if roots.is_empty() {
+            self.state.graph.solution_build_order()
+        } else {
+            self.state
+                .graph
+                .build_order(roots.iter().map(PathBuf::as_path))
+        };
+        if order.cycles.is_empty() {
+            return true;
+        }
+        self.show_error(
+            &anyhow::anyhow!(
+                "Cannot build because of project reference cycles: {}",
+                describe_cycles(&order.cycles)
+            ),
+            cx,
+        );
+        false
+    }
+
+    /// Shows the order in which the projects of the solution are built.
+    fn show_build_order(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let order = self.state.graph.solution_build_order();
+        let projects = order
+            .projects
+            .iter()
+            .enumerate()
+            .map(|(ix, path)| format!("{}. {}", ix + 1, project_name(path)))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let (level, message) = if order.cycles.is_empty() {
+            (PromptLevel::Info, "Project Build Order".to_string())
+        } else {
+            (
+                PromptLevel::Warning,
+                format!(
+                    "Project reference cycles: {}",
+                    describe_cycles(&order.cycles)
+                ),
+            )
+        };
+        window
+            .prompt(level, &message, Some(&projects), &["OK"], cx)
+            .detach();
+    }
+
+    fn toggle_expanded(&mut self, node: &SolutionTreeNode, cx: &mut Context<Self>) {
+        match node {
+            SolutionTreeNode::Folder { id, .. } => {
+                if !self.state.collapsed_folders.remove(id) {
+                    self.state.collapsed_folders.insert(id.clone());
+                }
+            }
+            SolutionTreeNode::Project { id, .. } => {
+                if !self.state.expanded_projects.remove(id) {
+                    self.state.expanded_projects.insert(id.clone());
+                    if !self.state.project_items.contains_key(id) {
+                        self.load_project_items(id, cx);
                     }
                 }
             }
+            SolutionTreeNode::Dependencies { .. } | SolutionTreeNode::DependencyGroup { .. } => {
+                if !self.state.expanded_dependencies.remove(node) {
+                    self.state.expanded_dependencies.insert(node.clone());
+                }
+            }
+            SolutionTreeNode::ItemFolder { .. } | SolutionTreeNode::Item { .. } => {
+                if !self.state.expanded_items.remove(node) {
+                    self.state.expanded_items.insert(node.clone());
+                }
+            }
+            SolutionTreeNode::Solution { .. }
+            | SolutionTreeNode::SolutionItem { .. }
+            | SolutionTreeNode::Dependency { .. }
+            | SolutionTreeNode::Package { .. } => {}
+        }
+        cx.notify();
+    }
 
-            items
-        } else {
-            vec![]
-        };
-
-        let item_count = items.len();
-        let expanded_projects = self.state.expanded_projects.clone();
-        let expanded_packages = self.state.expanded_packages.clone();
-        let selected_nodes = self.state.selected_nodes.clone();
+    fn on_entry_click(
+        &mut self,
+        node: &SolutionTreeNode,
+        event: &ClickEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let modifiers = event.modifiers();
+        // Toggle selection: if Ctrl/Cmd is held, add/remove from selection; otherwise, replace selection
+        if modifiers.control || modifiers.platform {
+            if !self.state.selected_nodes.remove(node) {
+                self.state.selected_nodes.insert(node.clone());
+            }
+            cx.notify();
+            return;
+        }
+        self.state.selected_nodes.clear();
+        self.state.selected_nodes.insert(node.clone());
+        if let SolutionTreeNode::SolutionItem { path } | SolutionTreeNode::Item { path, .. } = node
+        {
+            self.workspace
+                .update(cx, |workspace, cx| {
+                    workspace
+                        .open_abs_path(path.clone(), OpenOptions::default(), window, cx)
+                        .detach_and_log_err(cx);
+                })
+                .ok();
+        }
+        self.toggle_expanded(node, cx);
+    }
 
+    fn render_tree(&mut self, cx: &mut Context<Self>) -> impl IntoElement {
+        self.visible_items = tree_items(&self.state);
         uniform_list(
-            cx,
             "solution_explorer_tree",
-            item_count,
-            move |cx, range, _| {
-                items[range]
-                    .iter()
-                    .enumerate()
-                    .map(|(index, item)| {
-                        let is_selected = selected_nodes.contains(&item.node);
-                        let (item_guid, is_package_node) = match &item.node {
-                            SolutionTreeNode::Project { guid, .. } => (Some(guid.clone()), false),
-                            SolutionTreeNode::Package { project_guid, .. } => (Some(project_guid.clone()), true),
-                            _ => (None, false),
-                        };
-                        let is_expanded = item_guid
-                            .as_ref()
-                            .map(|g| expanded_projects.contains(g))
-                            .unwrap_or(false);
-                        let packages_expanded = item_guid
-                            .as_ref()
-                            .map(|g| expanded_packages.contains(g))
-                            .unwrap_or(false);
-
-                        ListItem::new(index)
-                            .spacing(ListItemSpacing::Sparse)
-                            .selected(is_selected)
-                            .on_click(cx.listener(move |this, event: &gpui::ClickEvent, window, cx| {
-                                if event.is_right_click() {
-                                    this.deploy_context_menu(event.position, &item.node, window, cx);
-                                    return;
-                                }
-                                // Toggle selection: if Ctrl/Cmd is held, add/remove from selection; otherwise, replace selection
-                                if event.modifiers.control || event.modifiers.command {
-                                    if this.state.selected_nodes.contains(&item.node) {
-                                        this.state.selected_nodes.remove(&item.node);
-                                    } else {
-                                        this.state.selected_nodes.insert(item.node.clone());
-                                    }
-                                } else {
-                                    // Single selection - clear and set
-                                    this.state.selected_nodes.clear();
-                                    this.state.selected_nodes.insert(item.node.clone());
-                                }
-                                match &item.node {
-                                    SolutionTreeNode::Project { guid, .. } => {
-                                        // Toggle project expansion
-                                        if this.state.expanded_projects.contains(guid) {
-                                            this.state.expanded_projects.remove(guid);
-                                            this.state.expanded_packages.remove(guid);
-                                        } else {
-                                            this.state.expanded_projects.insert(guid.clone());
-                                            // Auto-expand packages if project has packages
-                                            if let Some(ref solution) = this.state.solution {
-                                                if let Some(proj) = solution.projects.iter().find(|p| p.guid == *guid) {
-                                                    if !proj.packages.is_empty() {
-                                                        this.state.expanded_packages.insert(guid.clone());
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    }
-                                    SolutionTreeNode::Package { project_guid, .. } => {
-                                        // Toggle package expansion for the parent project
-                                        if this.state.expanded_packages.contains(project_guid) {
-                                            this.state.expanded_packages.remove(project_guid);
-                                        } else {
-                                            this.state.expanded_packages.insert(project_guid.clone());
-                                        }
-                                    }
-                                    _ => {}
-                                }
-                                cx.notify();
-                            }))
-                            .child(
-                                h_flex()
-                                    .gap_1()
-                                    .items_center()
-                                    .pl(px(item.depth as f32 * 16.0))
-                                    .when(item.has_children, |div| {
-                                        let chevron_expanded = match &item.node {
-                                            SolutionTreeNode::Project { .. } => is_expanded && packages_expanded,
-                                            _ => is_expanded,
-                                        };
-                                        div.child(
-                                            Icon::new(if chevron_expanded {
-                                                IconName::ChevronDown
-                                            } else {
-                                                IconName::ChevronRight
-                                            })
-                                            .size(ui::IconSize::Small)
-                                            .color(Color::Muted),
-                                        )
-                                    })
-                                    .when(!item.has_children, |div| div.w(px(16.0)))
-                                    .when_some(item.icon.clone(), |div, icon| {
-                                        div.child(Icon::new(icon).size(ui::IconSize::Small))
-                                    })
-                                    .child(Label::new(item.label.clone()).size(LabelSize::Small)),
-                            )
-                            .into_any_element()
+            self.visible_items.len(),
+            cx.processor(|this, range: Range<usize>, window, cx| {
+                range
+                    .filter_map(|index| {
+                        let item = this.visible_items.get(index)?.clone();
+                        Some(this.render_entry(index, item, window, cx))
                     })
                     .collect()
-            },
+            }),
         )
         .track_scroll(&self.scroll_handle)
         .size_full()
     }
 
+    fn render_entry(
+        &self,
+        index: usize,
+        item: TreeItem,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let is_selected = self.state.selected_nodes.contains(&item.node);
+        let is_draggable = match &item.node {
+            SolutionTreeNode::Folder { .. }
+            | SolutionTreeNode::Project { .. }
+            | SolutionTreeNode::ItemFolder { .. } => true,
+            SolutionTreeNode::Item { is_linked, .. } => !*is_linked,
+            _ => false,
+        };
+        let is_drop_target = matches!(
+            item.node,
+            SolutionTreeNode::Solution { .. }
+                | SolutionTreeNode::Folder { .. }
+                | SolutionTreeNode::Project { .. }
+                | SolutionTreeNode::ItemFolder { .. }
+        );
+        let dragged = DraggedSolutionNode {
+            node: item.node.clone(),
+            label: item.label.clone(),
+            icon: item.icon,
+        };
+
+        div()
+            .id(("solution-explorer-entry", index))
+            .when(is_draggable, |div| {
+                div.on_drag(dragged, |dragged, _, _, cx| cx.new(|_| dragged.clone()))
+            })
+            .when(is_drop_target, |div| {
+                let target = item.node.clone();
+                div.drag_over::<DraggedSolutionNode>(|style, _, _, cx| {
+                    style.bg(cx.theme().colors().drop_target_background)
+                })
+                .on_drop(cx.listener(
+                    move |this, dragged: &DraggedSolutionNode, window, cx| {
+                        this.move_node(&dragged.node, &target, window, cx);
+                    },
+                ))
+            })
+            .child(
+                ListItem::new(index)
+                    .spacing(ListItemSpacing::Sparse)
+                    .toggle_state(is_selected)
+                    .on_click(cx.listener({
+                        let node = item.node.clone();
+                        move |this, event: &ClickEvent, window, cx| {
+                            this.on_entry_click(&node, event, window, cx)
+                        }
+                    }))
+                    .on_secondary_mouse_down(cx.listener({
+                        let node = item.node.clone();
+                        move |this, event: &MouseDownEvent, window, cx| {
+                            this.deploy_context_menu(event.position, &node, window, cx)
+                        }
+                    }))
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .items_center()
+                            .pl(px(item.depth as f32 * 16.0))
+                            .when(item.has_children, |div| {
+                                div.child(
+                                    Icon::new(if item.is_expanded {
+                                        IconName::ChevronDown
+                                    } else {
+                                        IconName::ChevronRight
+                                    })
+                                    .size(ui::IconSize::Small)
+                                    .color(Color::Muted),
+                                )
+                            })
+                            .when(!item.has_children, |this| this.child(div().w(px(16.0))))
+                            .when_some(item.icon, |div, icon| {
+                                div.child(Icon::new(icon).size(ui::IconSize::Small))
+                            })
+                            .child(
+                                Label::new(item.label.clone())
+                                    .size(LabelSize::Small)
+                                    .when(item.is_startup, |label| label.weight(FontWeight::BOLD)),
+                            )
+                            .when_some(item.warning.clone(), |this, warning| {
+                                this.child(
+                                    div()
+                                        .id(("solution-explorer-warning", index))
+                                        .child(
+                                            Icon::new(IconName::Warning)
+                                                .size(ui::IconSize::Small)
+                                                .color(Color::Warning),
+                                        )
+                                        .tooltip(Tooltip::text(warning)),
+                                )
+                            }),
+                    ),
+            )
+            .into_any_element()
+    }
+
     fn deploy_context_menu(
         &mut self,
         position: Point<Pixels>,
@@ -445,9 +2314,10 @@ impl SolutionExplorerPanel {
         let project = self.project.clone();
         let node_clone = node.clone();
         let solution = self.state.solution.clone();
+        let startup_projects = self.state.startup_projects.clone();
         let selected_nodes = self.state.selected_nodes.clone();
         let focus_handle = self.focus_handle.clone();
-        let panel_entity = window.entity();
+        let panel_entity = cx.entity();
 
         let context_menu = ContextMenu::build(window, cx, move |menu, window, cx| {
             match &node_clone {
@@ -455,15 +2325,24 @@ impl SolutionExplorerPanel {
                     let solution_path = path.clone();
                     menu.context(focus_handle.clone())
                         .entry("Build Solution", None, window.handler_for(&panel_entity, move |this, window, cx| {
+                            if !this.check_build_order(&[], cx) {
+                                return;
+                            }
                             window.dispatch_action(Spawn::ByName { task_name: "dotnet: build".to_string(), reveal_target: None }.boxed_clone(), cx);
                         }))
                         .entry("Rebuild Solution", None, window.handler_for(&panel_entity, move |this, window, cx| {
+                            if !this.check_build_order(&[], cx) {
+                                return;
+                            }
+                            let configuration_args = this.configuration_args(None);
                             workspace.update(window, |workspace, cx| {
                                 if let Some(worktree) = workspace.project().read(cx).worktrees().next() {
                                     let root = worktree.read(cx).abs_path().to_path_buf();
+                                    let mut args = vec!["build".to_string(), "--no-incremental".to_string()];
+                                    args.extend(configuration_args);
                                     let task = SpawnInTerminal {
                                         command: Some("dotnet".to_string()),
-                                        args: vec!["build".to_string(), "--no-incremental".to_string()],
+                                        args,
                                         cwd: Some(root),
                                         ..Default::default()
                                     };
@@ -474,6 +2353,29 @@ impl SolutionExplorerPanel {
                         .entry("Clean Solution", None, window.handler_for(&panel_entity, move |this, window, cx| {
                             window.dispatch_action(Spawn::ByName { task_name: "dotnet: clean".to_string(), reveal_target: None }.boxed_clone(), cx);
                         }))
+                        .entry("Project Build Order…", None, window.handler_for(&panel_entity, |this, window, cx| {
+                            this.show_build_order(window, cx);
+                        }))
+                        .entry("Start Startup Projects", None, window.handler_for(&panel_entity, |this, window, cx| {
+                            this.start_startup_projects(window, cx);
+                        }))
+                        .separator()
+                        .entry("Show Outdated Packages…", None, window.handler_for(&panel_entity, |this, window, cx| {
+                            this.show_outdated_packages(window, cx);
+                        }))
+                        .entry("Consolidate Packages…", None, window.handler_for(&panel_entity, |this, window, cx| {
+                            this.consolidate_packages(window, cx);
+                        }))
+                        .separator()
+                        .entry("Add New Project…", None, window.handler_for(&panel_entity, |this, window, cx| {
+                            this.add_new_project(None, window, cx);
+                        }))
+                        .entry("Add Existing Project…", None, window.handler_for(&panel_entity, |this, window, cx| {
+                            this.add_existing_project(None, window, cx);
+                        }))
+                        .entry("New Solution Folder…", None, window.handler_for(&panel_entity, |this, window, cx| {
+                            this.new_solution_folder(None, window, cx);
+                        }))
                         .separator()
                         .entry("Open Solution File", None, window.handler_for(&panel_entity, move |this, window, cx| {
                             workspace.update(window, |workspace, cx| {
@@ -485,25 +2387,71 @@ impl SolutionExplorerPanel {
                             }).ok();
                         }))
                 }
-                SolutionTreeNode::Project { name, path, guid } => {
-                    let project_name = name.clone();
+                SolutionTreeNode::Folder { id, .. } => {
+                    let folder_id = id.clone();
+                    let folder_node = node_clone.clone();
+                    menu.context(focus_handle.clone())
+                        .entry("Add New Project…", None, window.handler_for(&panel_entity, {
+                            let folder_id = folder_id.clone();
+                            move |this, window, cx| this.add_new_project(Some(folder_id.clone()), window, cx)
+                        }))
+                        .entry("Add Existing Project…", None, window.handler_for(&panel_entity, {
+                            let folder_id = folder_id.clone();
+                            move |this, window, cx| this.add_existing_project(Some(folder_id.clone()), window, cx)
+                        }))
+                        .entry("New Solution Folder…", None, window.handler_for(&panel_entity, {
+                            let folder_id = folder_id.clone();
+                            move |this, window, cx| this.new_solution_folder(Some(folder_id.clone()), window, cx)
+                        }))
+                        .separator()
+                        .entry("Rename…", None, window.handler_for(&panel_entity, {
+                            let folder_node = folder_node.clone();
+                            move |this, window, cx| this.rename_node(&folder_node, window, cx)
+                        }))
+                        .entry("Remove from Solution", None, window.handler_for(&panel_entity, move |this, window, cx| {
+                            this.remove_node(&folder_node, window, cx);
+                        }))
+                }
+                SolutionTreeNode::SolutionItem { path } => {
+                    let item_path = path.clone();
+                    menu.context(focus_handle.clone())
+                        .entry("Open", None, window.handler_for(&panel_entity, move |this, window, cx| {
+                            this.workspace
+                                .update(cx, |workspace, cx| {
+                                    workspace
+                                        .open_abs_path(item_path.clone(), OpenOptions::default(), window, cx)
+                                        .detach_and_log_err(cx);
+                                })
+                                .ok();
+                        }))
+                }
+                SolutionTreeNode::Project { name, path, id } => {
                     let project_path = path.clone();
-                    let project_guid = guid.clone();
-                    let is_startup = solution
-                        .as_ref()
-                        .and_then(|s| s.startup_project.as_ref())
-                        .map(|sp| sp == guid)
-                        .unwrap_or(false);
+                    let project_id = id.clone();
+                    let startup_path = path.clone();
+                    let project_name = name.clone();
+                    let is_watching = DotNetWatch::global(cx).read(cx).is_watching(path);
+                    let startup_action = solution.as_ref().map_or(StartupAction::None, |solution| {
+                        startup_projects.action(&solution.relative_path(path))
+                    });
+                    let is_only_startup = startup_action == StartupAction::Start
+                        && startup_projects.iter().count() == 1;
 
                     menu.context(focus_handle.clone())
                         .entry("Build", None, window.handler_for(&panel_entity, move |this, window, cx| {
+                            if !this.check_build_order(std::slice::from_ref(&project_path), cx) {
+                                return;
+                            }
+                            let configuration_args = this.configuration_args(Some(&project_path));
                             workspace.update(window, |workspace, cx| {
                                 if let Some(worktree) = workspace.project().read(cx).worktrees().next() {
                                     let root = worktree.read(cx).abs_path().to_path_buf();
                                     let full_path = root.join(&project_path);
+                                    let mut args = vec!["build".to_string(), full_path.to_string_lossy().to_string()];
+                                    args.extend(configuration_args);
                                     let task = SpawnInTerminal {
                                         command: Some("dotnet".to_string()),
-                                        args: vec!["build".to_string(), full_path.to_string_lossy().to_string()],
+                                        args,
                                         cwd: Some(root),
                                         ..Default::default()
                                     };
@@ -512,13 +2460,19 @@ impl SolutionExplorerPanel {
                             }).ok();
                         }))
                         .entry("Rebuild", None, window.handler_for(&panel_entity, move |this, window, cx| {
+                            if !this.check_build_order(std::slice::from_ref(&project_path), cx) {
+                                return;
+                            }
+                            let configuration_args = this.configuration_args(Some(&project_path));
                             workspace.update(window, |workspace, cx| {
                                 if let Some(worktree) = workspace.project().read(cx).worktrees().next() {
                                     let root = worktree.read(cx).abs_path().to_path_buf();
                                     let full_path = root.join(&project_path);
+                                    let mut args = vec!["build".to_string(), "--no-incremental".to_string(), full_path.to_string_lossy().to_string()];
+                                    args.extend(configuration_args);
                                     let task = SpawnInTerminal {
                                         command: Some("dotnet".to_string()),
-                                        args: vec!["build".to_string(), "--no-incremental".to_string(), full_path.to_string_lossy().to_string()],
+                                        args,
                                         cwd: Some(root),
                                         ..Default::default()
                                     };
@@ -527,13 +2481,16 @@ impl SolutionExplorerPanel {
                             }).ok();
                         }))
                         .entry("Clean", None, window.handler_for(&panel_entity, move |this, window, cx| {
+                            let configuration_args = this.configuration_args(Some(&project_path));
                             workspace.update(window, |workspace, cx| {
                                 if let Some(worktree) = workspace.project().read(cx).worktrees().next() {
                                     let root = worktree.read(cx).abs_path().to_path_buf();
                                     let full_path = root.join(&project_path);
+                                    let mut args = vec!["clean".to_string(), full_path.to_string_lossy().to_string()];
+                                    args.extend(configuration_args);
                                     let task = SpawnInTerminal {
                                         command: Some("dotnet".to_string()),
-                                        args: vec!["clean".to_string(), full_path.to_string_lossy().to_string()],
+                                        args,
                                         cwd: Some(root),
                                         ..Default::default()
                                     };
@@ -541,26 +2498,37 @@ impl SolutionExplorerPanel {
                                 }
                             }).ok();
                         }))
+                        .entry(if is_watching { "Stop Watching" } else { "Watch" }, None, window.handler_for(&panel_entity, {
+                            let project_path = startup_path.clone();
+                            move |this, _, cx| this.toggle_watch(project_path.clone(), project_name.clone(), cx)
+                        }))
                         .separator()
-                        .when(!is_startup, |menu| {
-                            menu.entry("Set as Startup Project", None, window.handler_for(&panel_entity, move |this, window, cx| {
-                                panel_entity.update(cx, |panel, cx| {
-                                    if let Some(ref mut sol) = panel.state.solution {
-                                        sol.startup_project = Some(project_guid.clone());
-                                        cx.notify();
-                                    }
-                                });
+                        .entry("Run with Launch Profile…", None, window.handler_for(&panel_entity, {
+                            let project_path = startup_path.clone();
+                            move |this, window, cx| this.launch_with_profile(project_path.clone(), StartupAction::StartWithoutDebugging, window, cx)
+                        }))
+                        .entry("Debug with Launch Profile…", None, window.handler_for(&panel_entity, {
+                            let project_path = startup_path.clone();
+                            move |this, window, cx| this.launch_with_profile(project_path.clone(), StartupAction::Start, window, cx)
+                        }))
+                        .when(!is_only_startup, |menu| {
+                            let project_path = startup_path.clone();
+                            menu.entry("Set as Startup Project", None, window.handler_for(&panel_entity, move |this, _, cx| {
+                                if let Some(solution) = &this.state.solution {
+                                    let path = solution.relative_path(&project_path);
+                                    this.set_startup_projects(StartupProjects::single(path), cx);
+                                }
                             }))
                         })
-                        .when(is_startup, |menu| {
-                            menu.entry("Unset Startup Project", None, window.handler_for(&panel_entity, move |this, window, cx| {
-                                panel_entity.update(cx, |panel, cx| {
-                                    if let Some(ref mut sol) = panel.state.solution {
-                                        sol.startup_project = None;
-                                        cx.notify();
-                                    }
-                                });
-                            }))
+                        .header("When Starting the Solution")
+                        .map(|mut menu| {
+                            for action in StartupAction::ALL {
+                                let project_path = startup_path.clone();
+                                menu = menu.toggleable_entry(action.label(), action == startup_action, IconPosition::Start, None, window.handler_for(&panel_entity, move |this, _, cx| {
+                                    this.set_startup_action(&project_path, action, cx);
+                                }));
+                            }
+                            menu
                         })
                         .separator()
                         .entry("Open Project File", None, window.handler_for(&panel_entity, move |this, window, cx| {
@@ -584,6 +2552,20 @@ impl SolutionExplorerPanel {
                             }).ok();
                         }))
                         .separator()
+                        .entry("Rename…", None, window.handler_for(&panel_entity, {
+                            let project_node = node_clone.clone();
+                            move |this, window, cx| this.rename_node(&project_node, window, cx)
+                        }))
+                        .entry("Remove from Solution", None, window.handler_for(&panel_entity, {
+                            let project_node = node_clone.clone();
+                            move |this, window, cx| this.remove_node(&project_node, window, cx)
+                        }))
+                        .separator()
+                        .entry("Add Project Reference…", None, window.handler_for(&panel_entity, {
+                            let project_id = project_id.clone();
+                            move |this, window, cx| this.add_project_reference(&project_id, window, cx)
+                        }))
+                        .separator()
                         .entry("Restore Packages", None, window.handler_for(&panel_entity, move |this, window, cx| {
                             workspace.update(window, |workspace, cx| {
                                 if let Some(worktree) = workspace.project().read(cx).worktrees().next() {
@@ -599,18 +2581,9 @@ impl SolutionExplorerPanel {
                                 }
                             }).ok();
                         }))
-                        .entry("Add Package...", None, window.handler_for(&panel_entity, move |this, window, cx| {
-                            // TODO: Show package search dialog
-                            // For now, just show a placeholder message
-                            workspace.update(window, |workspace, cx| {
-                                if let Some(worktree) = workspace.project().read(cx).worktrees().next() {
-                                    let root = worktree.read(cx).abs_path().to_path_buf();
-                                    let full_path = root.join(&project_path);
-                                    // This would normally open a package search dialog
-                                    // For now, we'll just show a message that this feature needs a dialog
-                                    log::info!("Add Package dialog not yet implemented for project: {}", project_name);
-                                }
-                            }).ok();
+                        .entry("Manage NuGet Packages…", None, window.handler_for(&panel_entity, {
+                            let project_id = project_id.clone();
+                            move |this, window, cx| this.manage_packages(&project_id, window, cx)
                         }))
                         .separator()
                         .entry("Pack", None, window.handler_for(&panel_entity, move |this, window, cx| {
@@ -619,22 +2592,26 @@ impl SolutionExplorerPanel {
                                 let selected_projects: Vec<_> = selected_nodes
                                     .iter()
                                     .filter_map(|n| {
-                                        if let SolutionTreeNode::Project { guid, .. } = n {
-                                            sol.projects.iter().find(|p| p.guid == *guid)
+                                        if let SolutionTreeNode::Project { id, .. } = n {
+                                            sol.project_by_id(id)
                                         } else {
                                             None
                                         }
                                     })
                                     .collect();
                                 
-                                if !selected_projects.is_empty() {
+                                let projects: Vec<_> = if !selected_projects.is_empty() {
                                     selected_projects
-                                } else if let SolutionTreeNode::Project { guid, .. } = &node_clone {
+                                } else if let SolutionTreeNode::Project { id, .. } = &node_clone {
                                     // Fall back to clicked project if no multi-selection
-                                    sol.projects.iter().filter(|p| p.guid == *guid).collect()
+                                    sol.project_by_id(id).into_iter().collect()
                                 } else {
                                     Vec::new()
-                                }
+                                };
+                                projects
+                                    .into_iter()
+                                    .map(|project| (sol.project_abs_path(project), project.name.clone()))
+                                    .collect()
                             } else {
                                 Vec::new()
                             };
@@ -650,9 +2627,7 @@ impl SolutionExplorerPanel {
                                 if let Some(worktree) = workspace.project().read(cx).worktrees().next() {
                                     let root = worktree.read(cx).abs_path().to_path_buf();
                                     
-                                    for project in projects_to_pack {
-                                        let project_path = root.join(&project.path);
-                                        let project_name = project.name.clone();
+                                    for (project_path, project_name) in projects_to_pack {
                                         
                                         // Build pack command arguments
                                         let mut pack_args = vec!["pack".to_string(), project_path.to_string_lossy().to_string()];
@@ -743,48 +2718,91 @@ impl SolutionExplorerPanel {
                             }).ok();
                         }))
                 }
-                SolutionTreeNode::Package { project_guid, package_id, version } => {
-                    let package_id_clone = package_id.clone();
-                    let project_guid_clone = project_guid.clone();
-                    let project_path = solution
-                        .as_ref()
-                        .and_then(|s| s.projects.iter().find(|p| p.guid == *project_guid))
-                        .map(|p| p.path.clone());
-                    
-                    if let Some(ref proj_path) = project_path {
-                        let project_path_clone = proj_path.clone();
-                        menu.context(focus_handle.clone())
-                            .entry("Update Package", None, window.handler_for(&panel_entity, move |this, window, cx| {
-                                workspace.update(window, |workspace, cx| {
-                                    if let Some(worktree) = workspace.project().read(cx).worktrees().next() {
-                                        let root = worktree.read(cx).abs_path().to_path_buf();
-                                        let full_path = root.join(&project_path_clone);
-                                        let task = SpawnInTerminal {
-                                            command: Some("dotnet".to_string()),
-                                            args: vec!["add".to_string(), full_path.to_string_lossy().to_string(), "package".to_string(), package_id_clone.clone(), "--version".to_string(), "latest".to_string()],
-                                            cwd: Some(root),
-                                            ..Default::default()
-                                        };
-                                        workspace.spawn_in_terminal(task, window, cx).detach();
-                                    }
-                                }).ok();
-                            }))
-                            .entry("Remove Package", None, window.handler_for(&panel_entity, move |this, window, cx| {
-                                workspace.update(window, |workspace, cx| {
-                                    if let Some(worktree) = workspace.project().read(cx).worktrees().next() {
-                                        let root = worktree.read(cx).abs_path().to_path_buf();
-                                        let full_path = root.join(&project_path_clone);
-                                        let task = SpawnInTerminal {
-                                            command: Some("dotnet".to_string()),
-                                            args: vec!["remove".to_string(), full_path.to_string_lossy().to_string(), "package".to_string(), package_id_clone.clone()],
-                                            cwd: Some(root),
-                                            ..Default::default()
-                                        };
-                                        workspace.spawn_in_terminal(task, window, cx).detach();
-                                    }
-                                }).ok();
-                            }))
-                    }
+                SolutionTreeNode::Dependencies { project_id } => {
+                    let project_id = project_id.clone();
+                    menu.context(focus_handle.clone())
+                        .entry("Add Project Reference…", None, window.handler_for(&panel_entity, {
+                            let project_id = project_id.clone();
+                            move |this, window, cx| this.add_project_reference(&project_id, window, cx)
+                        }))
+                        .entry("Manage NuGet Packages…", None, window.handler_for(&panel_entity, move |this, window, cx| {
+                            this.manage_packages(&project_id, window, cx);
+                        }))
+                }
+                SolutionTreeNode::DependencyGroup { project_id, kind: DependencyKind::Project } => {
+                    let project_id = project_id.clone();
+                    menu.context(focus_handle.clone())
+                        .entry("Add Project Reference…", None, window.handler_for(&panel_entity, move |this, window, cx| {
+                            this.add_project_reference(&project_id, window, cx);
+                        }))
+                }
+                SolutionTreeNode::DependencyGroup { project_id, kind: DependencyKind::Package } => {
+                    let project_id = project_id.clone();
+                    menu.context(focus_handle.clone())
+                        .entry("Manage NuGet Packages…", None, window.handler_for(&panel_entity, move |this, window, cx| {
+                            this.manage_packages(&project_id, window, cx);
+                        }))
+                }
+                SolutionTreeNode::Dependency { project_id, kind: DependencyKind::Project, path: Some(reference_path), .. } => {
+                    let project_id = project_id.clone();
+                    let reference_path = reference_path.clone();
+                    menu.context(focus_handle.clone())
+                        .entry("Open Project File", None, window.handler_for(&panel_entity, {
+                            let reference_path = reference_path.clone();
+                            move |this, window, cx| {
+                                this.workspace
+                                    .update(cx, |workspace, cx| {
+                                        workspace
+                                            .open_abs_path(reference_path.clone(), OpenOptions::default(), window, cx)
+                                            .detach_and_log_err(cx);
+                                    })
+                                    .ok();
+                            }
+                        }))
+                        .entry("Remove Project Reference", None, window.handler_for(&panel_entity, move |this, window, cx| {
+                            this.remove_project_reference(&project_id, reference_path.clone(), window, cx);
+                        }))
+                }
+                SolutionTreeNode::DependencyGroup { .. } | SolutionTreeNode::Dependency { .. } => menu,
+                SolutionTreeNode::Package { project_id, package_id, .. } => {
+                    let project_id = project_id.clone();
+                    let package_id = package_id.clone();
+                    menu.context(focus_handle.clone())
+                        .entry("Update Package…", None, window.handler_for(&panel_entity, {
+                            let project_id = project_id.clone();
+                            let package_id = package_id.clone();
+                            move |this, window, cx| this.update_package(&project_id, &package_id, window, cx)
+                        }))
+                        .entry("Remove Package", None, window.handler_for(&panel_entity, move |this, window, cx| {
+                            this.remove_package(&project_id, &package_id, window, cx);
+                        }))
+                }
+                SolutionTreeNode::ItemFolder { .. } => {
+                    let folder_node = node_clone.clone();
+                    menu.context(focus_handle.clone())
+                        .entry("Rename…", None, window.handler_for(&panel_entity, move |this, window, cx| {
+                            this.rename_node(&folder_node, window, cx);
+                        }))
+                }
+                SolutionTreeNode::Item { path, is_linked, .. } => {
+                    let item_path = path.clone();
+                    let item_node = node_clone.clone();
+                    menu.context(focus_handle.clone())
+                        .entry("Open", None, window.handler_for(&panel_entity, move |this, window, cx| {
+                            this.workspace
+                                .update(cx, |workspace, cx| {
+                                    workspace
+                                        .open_abs_path(item_path.clone(), OpenOptions::default(), window, cx)
+                                        .detach_and_log_err(cx);
+                                })
+                                .ok();
+                        }))
+                        .when(!*is_linked, |menu| {
+                            menu.separator()
+                                .entry("Rename…", None, window.handler_for(&panel_entity, move |this, window, cx| {
+                                    this.rename_node(&item_node, window, cx);
+                                }))
+                        })
                 }
             }
         });
@@ -800,52 +2818,455 @@ impl SolutionExplorerPanel {
     }
 }
 
-fn find_solution_file(root: &Path, fs: &dyn Fs) -> Option<PathBuf> {
-    // Check current directory
-    if let Ok(entries) = fs.read_dir(root) {
-        for entry in entries {
-            if let Ok(entry) = entry {
-                if let Some(name) = entry.file_name().to_str() {
-                    if name.ends_with(".slnx") || name.ends_with(".sln") {
-                        return Some(root.join(name));
-                    }
-                }
+fn find_solution_file(root: &Path) -> Option<PathBuf> {
+    // Check the worktree root and up to 3 parent directories
+    root.ancestors().take(4).find_map(|directory| {
+        std::fs::read_dir(directory)
+            .ok()?
+            .flatten()
+            .map(|entry| entry.path())
+            .find(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension == "sln" || extension == "slnx")
+            })
+    })
+}
+
+#[derive(Clone)]
+struct TreeItem {
+    node: SolutionTreeNode,
+    label: String,
+    icon: Option<IconName>,
+    depth: usize,
+    is_expanded: bool,
+    has_children: bool,
+    /// A problem shown next to the entry, such as a reference cycle.
+    warning: Option<String>,
+    /// Whether starting the solution starts this project
+    is_startup: bool,
+}
+
+/// Flattens the visible part of the solution tree into rows.
+fn tree_items(state: &SolutionTreeState) -> Vec<TreeItem> {
+    let Some(solution) = &state.solution else {
+        return Vec::new();
+    };
+    let mut items = vec![TreeItem {
+        node: SolutionTreeNode::Solution {
+            path: solution.path.clone(),
+        },
+        label: solution
+            .path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("Solution")
+            .to_string(),
+        icon: Some(IconName::FileCode),
+        depth: 0,
+        is_expanded: true,
+        has_children: !solution.projects.is_empty() || !solution.folders.is_empty(),
+        warning: None,
+        is_startup: false,
+    }];
+    push_child_items(state, solution, None, 1, &mut items);
+    items
+}
+
+fn push_child_items(
+    state: &SolutionTreeState,
+    solution: &SolutionFile,
+    parent: Option<&str>,
+    depth: usize,
+    items: &mut Vec<TreeItem>,
+) {
+    for folder in solution.child_folders(parent) {
+        let is_expanded = !state.collapsed_folders.contains(&folder.id);
+        items.push(TreeItem {
+            node: SolutionTreeNode::Folder {
+                id: folder.id.clone(),
+                name: folder.name.clone(),
+            },
+            label: folder.name.clone(),
+            icon: Some(if is_expanded {
+                IconName::FolderOpen
+            } else {
+                IconName::Folder
+            }),
+            depth,
+            is_expanded,
+            has_children: !folder.items.is_empty()
+                || solution.child_folders(Some(&folder.id)).next().is_some()
+                || solution.child_projects(Some(&folder.id)).next().is_some(),
+            warning: None,
+            is_startup: false,
+        });
+        if is_expanded {
+            push_child_items(state, solution, Some(&folder.id), depth + 1, items);
+            for item in &folder.items {
+                items.push(TreeItem {
+                    node: SolutionTreeNode::SolutionItem {
+                        path: solution.directory().join(item),
+                    },
+                    label: item
+                        .file_name()
+                        .map(|name| name.to_string_lossy().into_owned())
+                        .unwrap_or_default(),
+                    icon: Some(IconName::File),
+                    depth: depth + 1,
+                    is_expanded: false,
+                    has_children: false,
+                    warning: None,
+                    is_startup: false,
+                });
             }
         }
     }
 
-    // Check parent directories (up to 3 levels)
-    let mut current = root;
-    for _ in 0..3 {
-        if let Some(parent) = current.parent() {
-            if let Ok(entries) = fs.read_dir(parent) {
-                for entry in entries {
-                    if let Ok(entry) = entry {
-                        if let Some(name) = entry.file_name().to_str() {
-                            if name.ends_with(".slnx") || name.ends_with(".sln") {
-                                return Some(parent.join(name));
-                            }
-                        }
-                    }
-                }
+    for project in solution.child_projects(parent) {
+        let path = solution.project_abs_path(project);
+        let dependencies = state
+            .dependencies
+            .get(&project.id)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let is_expanded = state.expanded_projects.contains(&project.id);
+        let warning = state
+            .cycles
+            .iter()
+            .find(|cycle| cycle.contains(&path))
+            .map(|cycle| {
+                format!(
+                    "Project reference cycle: {}",
+                    describe_cycles(std::slice::from_ref(cycle))
+                )
+            });
+        let directory = path.parent().unwrap_or(Path::new("")).to_path_buf();
+        items.push(TreeItem {
+            node: SolutionTreeNode::Project {
+                name: project.name.clone(),
+                path,
+                id: project.id.clone(),
+            },
+            label: project.name.clone(),
+            icon: Some(IconName::FileCode),
+            depth,
+            is_expanded,
+            has_children: true,
+            warning,
+            is_startup: state.startup_projects.action(&project.path) != StartupAction::None,
+        });
+        if is_expanded {
+            push_dependency_items(state, &project.id, dependencies, depth + 1, items);
+            if let Some(project_items) = state.project_items.get(&project.id) {
+                push_project_items(
+                    state,
+                    &project.id,
+                    &directory,
+                    project_items,
+                    depth + 1,
+                    items,
+                );
             }
-            current = parent;
-        } else {
-            break;
         }
     }
+}
+
+/// Adds the "Dependencies" node of a project, grouped by dependency kind.
+fn push_dependency_items(
+    state: &SolutionTreeState,
+    project_id: &str,
+    dependencies: &[Dependency],
+    depth: usize,
+    items: &mut Vec<TreeItem>,
+) {
+    let dependencies_node = SolutionTreeNode::Dependencies {
+        project_id: project_id.to_string(),
+    };
+    let is_expanded = state.expanded_dependencies.contains(&dependencies_node);
+    items.push(TreeItem {
+        node: dependencies_node,
+        label: "Dependencies".to_string(),
+        icon: Some(IconName::Library),
+        depth,
+        is_expanded,
+        has_children: !dependencies.is_empty(),
+        warning: None,
+        is_startup: false,
+    });
+    if !is_expanded {
+        return;
+    }
+
+    for kind in DependencyKind::ALL {
+        let group = dependencies
+            .iter()
+            .filter(|dependency| dependency.kind == kind)
+            .collect::<Vec<_>>();
+        if group.is_empty() {
+            continue;
+        }
+        let group_node = SolutionTreeNode::DependencyGroup {
+            project_id: project_id.to_string(),
+            kind,
+        };
+        let is_expanded = state.expanded_dependencies.contains(&group_node);
+        items.push(TreeItem {
+            node: group_node,
+            label: kind.group_label().to_string(),
+            icon: Some(if is_expanded {
+                IconName::FolderOpen
+            } else {
+                IconName::Folder
+            }),
+            depth: depth + 1,
+            is_expanded,
+            has_children: true,
+            warning: None,
+            is_startup: false,
+        });
+        if !is_expanded {
+            continue;
+        }
+
+        for dependency in group {
+            let label = match &dependency.version {
+                Some(version) => format!("{} ({version})", dependency.name),
+                None => dependency.name.clone(),
+            };
+            let node = if kind == DependencyKind::Package {
+                SolutionTreeNode::Package {
+                    project_id: project_id.to_string(),
+                    package_id: dependency.include.clone(),
+                    version: dependency.version.clone(),
+                }
+            } else {
+                SolutionTreeNode::Dependency {
+                    project_id: project_id.to_string(),
+                    kind,
+                    include: dependency.include.clone(),
+                    path: dependency.path.clone(),
+                }
+            };
+            items.push(TreeItem {
+                node,
+                label,
+                icon: Some(match kind {
+                    DependencyKind::Project => IconName::FileCode,
+                    DependencyKind::Package => IconName::Box,
+                    DependencyKind::Framework => IconName::Library,
+                    DependencyKind::Assembly | DependencyKind::Analyzer => IconName::File,
+                }),
+                depth: depth + 2,
+                is_expanded: false,
+                has_children: false,
+                warning: None,
+                is_startup: false,
+            });
+        }
+    }
+}
+
+/// Adds the folders and files of a project, with nested files under the file
+/// they belong to.
+fn push_project_items(
+    state: &SolutionTreeState,
+    project_id: &str,
+    project_directory: &Path,
+    nodes: &[ProjectItemNode],
+    depth: usize,
+    items: &mut Vec<TreeItem>,
+) {
+    for node in nodes {
+        let (tree_node, is_linked) = match node {
+            ProjectItemNode::Folder { path, .. } => (
+                SolutionTreeNode::ItemFolder {
+                    project_id: project_id.to_string(),
+                    path: project_directory.join(path),
+                },
+                false,
+            ),
+            ProjectItemNode::File { file, .. } => (
+                SolutionTreeNode::Item {
+                    project_id: project_id.to_string(),
+                    path: file.path.clone(),
+                    is_linked: file.is_linked,
+                },
+                file.is_linked,
+            ),
+        };
+        let is_expanded = state.expanded_items.contains(&tree_node);
+        let icon = match node {
+            ProjectItemNode::Folder { .. } if is_expanded => IconName::FolderOpen,
+            ProjectItemNode::Folder { .. } => IconName::Folder,
+            ProjectItemNode::File { .. } if is_linked => IconName::Link,
+            ProjectItemNode::File { .. } => IconName::File,
+        };
+        items.push(TreeItem {
+            node: tree_node,
+            label: node.name(),
+            icon: Some(icon),
+            depth,
+            is_expanded,
+            has_children: !node.children().is_empty(),
+            warning: None,
+            is_startup: false,
+        });
+        if is_expanded {
+            push_project_items(
+                state,
+                project_id,
+                project_directory,
+                node.children(),
+                depth + 1,
+                items,
+            );
+        }
+    }
+}
+
+fn find_item_node<'a>(nodes: &'a [ProjectItemNode], path: &Path) -> Option<&'a ProjectItemNode> {
+    nodes.iter().find_map(|node| match node {
+        ProjectItemNode::File { file, .. } if file.path == path => Some(node),
+        _ => find_item_node(node.children(), path),
+    })
+}
+
+fn push_nested_files(nodes: &[ProjectItemNode], paths: &mut Vec<PathBuf>) {
+    for node in nodes {
+        if let ProjectItemNode::File { file, children } = node
+            && !file.is_linked
+        {
+            paths.push(file.path.clone());
+            push_nested_files(children, paths);
+        }
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn project_name(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Describes reference cycles as `A → B → A; C → C`.
+fn describe_cycles(cycles: &[Vec<PathBuf>]) -> String {
+    cycles
+        .iter()
+        .map(|cycle| {
+            cycle
+                .iter()
+                .chain(cycle.first())
+                .map(|path| project_name(path))
+                .collect::<Vec<_>>()
+                .join(" → ")
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
 
-    None
+/// A project to start with or without debugging.
+struct ProjectLaunch {
+    name: String,
+    path: PathBuf,
+    action: StartupAction,
+    /// Arguments for `dotnet build` and `dotnet run`, such as the configuration
+    args: Vec<String>,
+    /// Whether the solution was built already, so that the project only needs starting
+    prebuilt: bool,
+}
+
+impl ProjectLaunch {
+    fn start(self, workspace: &mut Workspace, window: &mut Window, cx: &mut Context<Workspace>) {
+        let cwd = self.path.parent().map(Path::to_path_buf);
+        let project_path = self.path.to_string_lossy().into_owned();
+        match self.action {
+            StartupAction::Start => {
+                // The debug locator builds the project and launches its output.
+                let mut args = vec!["build".to_string(), project_path];
+                if self.prebuilt {
+                    args.push("--no-dependencies".to_string());
+                }
+                args.extend(self.args);
+                let scenario = DebugScenario {
+                    adapter: dotnet_debug_adapter(
+                        workspace.project().read(cx),
+                        Some(&self.path),
+                        cx,
+                    ),
+                    label: self.name.clone().into(),
+                    build: Some(BuildTaskDefinition::Template {
+                        task_template: TaskTemplate {
+                            label: format!("dotnet: build {}", self.name),
+                            command: "dotnet".to_string(),
+                            args,
+                            cwd: cwd.map(|cwd| cwd.to_string_lossy().into_owned()),
+                            ..TaskTemplate::default()
+                        },
+                        locator_name: Some(DEBUG_LOCATOR.into()),
+                    }),
+                    // The locator turns the build into a launch request
+                    config: serde_json::json!({
+                        "type": "coreclr"
+                    }),
+                    tcp_connection: None,
+                };
+                workspace.start_debug_session(
+                    scenario,
+                    TaskContext::default(),
+                    None,
+                    None,
+                    window,
+                    cx,
+                );
+            }
+            StartupAction::StartWithoutDebugging => {
+                let mut args = vec!["run".to_string(), "--project".to_string(), project_path];
+                if self.prebuilt {
+                    args.push("--no-build".to_string());
+                }
+                args.extend(self.args);
+                let run = SpawnInTerminal {
+                    label: format!("dotnet: run {}", self.name),
+                    full_label: format!("dotnet: run {}", self.name),
+                    command: Some("dotnet".to_string()),
+                    args,
+                    cwd,
+                    ..Default::default()
+                };
+                workspace.spawn_in_terminal(run, window, cx).detach();
+            }
+            StartupAction::None => {}
+        }
+    }
 }
 
+/// A project or solution folder being dragged onto another solution folder.
 #[derive(Clone)]
-struct TreeItem {
+struct DraggedSolutionNode {
     node: SolutionTreeNode,
     label: String,
     icon: Option<IconName>,
-    depth: usize,
-    is_expanded: bool,
-    has_children: bool,
+}
+
+impl Render for DraggedSolutionNode {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .gap_1()
+            .py_1()
+            .px_2()
+            .rounded_lg()
+            .bg(cx.theme().colors().background)
+            .when_some(self.icon, |this, icon| {
+                this.child(Icon::new(icon).size(ui::IconSize::Small))
+            })
+            .child(Label::new(self.label.clone()).size(LabelSize::Small))
+    }
 }
 
 impl Render for SolutionExplorerPanel {
@@ -855,8 +3276,16 @@ impl Render for SolutionExplorerPanel {
         if has_solution {
             v_flex()
                 .id("solution_explorer_panel")
+                .key_context("SolutionExplorer")
                 .size_full()
                 .track_focus(&self.focus_handle)
+                .on_action(cx.listener(Self::add_new_project_action))
+                .on_action(cx.listener(Self::add_existing_project_action))
+                .on_action(cx.listener(Self::new_solution_folder_action))
+                .on_action(cx.listener(Self::rename_action))
+                .on_action(cx.listener(Self::remove_action))
+                .on_action(cx.listener(Self::start_startup_projects_action))
+                .children(self.render_configuration_switcher(window, cx))
                 .child(
                     self.render_tree(cx)
                         .custom_scrollbars(
@@ -934,6 +3363,7 @@ impl Panel for SolutionExplorerPanel {
 
     fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
         self.width = size;
+        self.serialize(cx);
         cx.notify();
     }
 
@@ -977,9 +3407,42 @@ impl SolutionExplorerPanel {
         workspace: WeakEntity<Workspace>,
         mut cx: AsyncWindowContext,
     ) -> Result<gpui::Entity<Self>> {
+        let serialized_panel = match workspace
+            .read_with(&cx, |workspace, _| Self::serialization_key(workspace))
+            .ok()
+            .flatten()
+        {
+            Some(serialization_key) => cx
+                .background_spawn(async move { KEY_VALUE_STORE.read_kvp(&serialization_key) })
+                .await
+                .context("loading solution explorer panel")
+                .log_err()
+                .flatten()
+                .map(|panel| serde_json::from_str::<SerializedSolutionExplorerPanel>(&panel))
+                .transpose()
+                .log_err()
+                .flatten(),
+            None => None,
+        };
+
         workspace.update_in(&mut cx, |workspace, window, cx| {
             let project = workspace.project().clone();
-            let panel = SolutionExplorerPanel::new(workspace.clone(), project, window, cx);
+            let workspace = cx.entity().downgrade();
+            let panel = cx.new(|cx| {
+                let mut panel = SolutionExplorerPanel::new(workspace, project, window, cx);
+                if let Some(serialized_panel) = serialized_panel {
+                    panel.width = serialized_panel.width.map(|px| px.round());
+                    // Applied once the solution is loaded, if it still has this configuration
+                    panel.configuration = serialized_panel
+                        .configuration
+                        .as_deref()
+                        .and_then(SolutionConfiguration::parse);
+                    panel.startup_projects = serialized_panel.startup_projects;
+                    // Reload with the restored choices
+                    panel.load_solution(window, cx);
+                }
+                panel
+            });
             Ok(panel)
         })
     }
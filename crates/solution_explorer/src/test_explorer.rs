@@ -0,0 +1,1176 @@
+use crate::{DEBUG_LOCATOR, SolutionExplorerSettings, find_solution_file};
+use anyhow::{Context as _, Result};
+use collections::{BTreeMap, HashMap, HashSet};
+use db::kvp::KEY_VALUE_STORE;
+use dotnet::{
+    EvaluationOptions, ProjectModel, RealProjectFiles, SolutionFile, TestOutcome, TestResult,
+    testing::{
+        find_test_methods, parse_test_list, parse_trx, split_test_name, test_name_filter,
+        test_prefix_filter, trx_logger_args,
+    },
+};
+use editor::Editor;
+use gpui::{
+    Action, AnyElement, App, AsyncWindowContext, ClickEvent, Context, DismissEvent, Entity,
+    EventEmitter, FocusHandle, Focusable, IntoElement, MouseDownEvent, Pixels, Point, Render,
+    Subscription, Task, UniformListScrollHandle, WeakEntity, Window, actions, anchored, deferred,
+    div, px, uniform_list,
+};
+use project::{Fs, Project, dotnet_startup_projects::dotnet_debug_adapter};
+use serde::{Deserialize, Serialize};
+use settings::{DockSide, Settings};
+use std::{
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+use task::{BuildTaskDefinition, DebugScenario, SpawnInTerminal, TaskContext, TaskTemplate};
+use ui::{
+    Color, ContextMenu, Icon, IconButton, IconName, IconSize, Label, LabelSize, ListItem,
+    ListItemSpacing, ScrollAxes, Scrollbars, Tooltip, WithScrollbar, prelude::*,
+};
+use util::{ResultExt, command::new_smol_command};
+use workspace::{
+    OpenOptions, Workspace,
+    dock::{DockPosition, Panel, PanelEvent},
+};
+use zed_actions::test_explorer::ToggleFocus;
+
+const TEST_EXPLORER_PANEL_KEY: &str = "TestExplorerPanel";
+/// How often to look for the results of tests being debugged
+const REPORT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+actions!(
+    test_explorer,
+    [
+        /// Discovers tests again by building the test projects and listing their tests.
+        RefreshTests,
+        /// Runs all tests of all test projects.
+        RunAllTests,
+    ]
+);
+
+#[derive(Serialize, Deserialize)]
+struct SerializedTestExplorerPanel {
+    width: Option<Pixels>,
+}
+
+/// A test project of the solution.
+struct TestProject {
+    name: String,
+    path: PathBuf,
+    /// Tests by fully qualified name, with where they are declared if known
+    tests: BTreeMap<String, Option<TestLocation>>,
+}
+
+#[derive(Clone, Debug)]
+struct TestLocation {
+    path: PathBuf,
+    /// Zero-based line of the test method
+    line: u32,
+}
+
+/// The results of the latest run of a test, over all of its cases.
+#[derive(Clone, Debug)]
+struct TestSummary {
+    outcome: TestOutcome,
+    duration: Duration,
+    /// The failure message of the first failed case
+    message: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum TestTreeNode {
+    Project { path: PathBuf },
+    Namespace { project: PathBuf, name: String },
+    Class { project: PathBuf, name: String },
+    Test { project: PathBuf, name: String },
+}
+
+impl TestTreeNode {
+    fn project_path(&self) -> &Path {
+        match self {
+            TestTreeNode::Project { path } => path,
+            TestTreeNode::Namespace { project, .. }
+            | TestTreeNode::Class { project, .. }
+            | TestTreeNode::Test { project, .. } => project,
+        }
+    }
+
+    /// Whether the test named `test` belongs to this node.
+    fn contains(&self, test: &str) -> bool {
+        match self {
+            TestTreeNode::Project { .. } => true,
+            TestTreeNode::Namespace { name, .. } => {
+                split_test_name(test).is_some_and(|(class, _)| namespace_of(class) == name.as_str())
+            }
+            TestTreeNode::Class { name, .. } => {
+                split_test_name(test).is_some_and(|(class, _)| class == name.as_str())
+            }
+            TestTreeNode::Test { name, .. } => test == name.as_str(),
+        }
+    }
+}
+
+pub struct TestExplorerPanel {
+    project: Entity<Project>,
+    fs: Arc<dyn Fs>,
+    workspace: WeakEntity<Workspace>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    width: Option<Pixels>,
+    projects: Vec<TestProject>,
+    /// Results of the latest run of each test, by fully qualified name
+    results: HashMap<String, TestSummary>,
+    /// Tests of runs that haven't reported their results yet
+    running_tests: HashSet<String>,
+    collapsed_nodes: HashSet<TestTreeNode>,
+    selected_node: Option<TestTreeNode>,
+    visible_items: Vec<TestTreeItem>,
+    discovery_task: Task<()>,
+    /// Waits for the results of the tests being debugged
+    debug_run: Task<()>,
+    context_menu: Option<(Entity<ContextMenu>, Point<Pixels>, Subscription)>,
+    pending_serialization: Task<Option<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl TestExplorerPanel {
+    pub fn new(
+        workspace: &Entity<Workspace>,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let fs = project.read(cx).fs().clone();
+        let subscriptions = vec![
+            cx.subscribe(&project, |this, _, event: &project::Event, cx| {
+                if let project::Event::WorktreeAdded(_) | project::Event::WorktreeRemoved(_) = event
+                {
+                    this.discover_tests(cx);
+                }
+            }),
+            // Show results in editors as they open
+            cx.subscribe_in(
+                workspace,
+                window,
+                |this, _, event: &workspace::Event, _, cx| {
+                    if let workspace::Event::ItemAdded { .. }
+                    | workspace::Event::ActiveItemChanged = event
+                    {
+                        this.update_editors(cx);
+                    }
+                },
+            ),
+        ];
+
+        let mut panel = Self {
+            project,
+            fs,
+            workspace: workspace.downgrade(),
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::default(),
+            width: None,
+            projects: Vec::new(),
+            results: HashMap::default(),
+            running_tests: HashSet::default(),
+            collapsed_nodes: HashSet::default(),
+            selected_node: None,
+            visible_items: Vec::new(),
+            discovery_task: Task::ready(()),
+            debug_run: Task::ready(()),
+            context_menu: None,
+            pending_serialization: Task::ready(None),
+            _subscriptions: subscriptions,
+        };
+        panel.discover_tests(cx);
+        panel
+    }
+
+    /// Finds the test projects of the solution and the tests declared in
+    /// their sources, without building them.
+    fn discover_tests(&mut self, cx: &mut Context<Self>) {
+        let roots = self
+            .project
+            .read(cx)
+            .worktrees(cx)
+            .map(|worktree| worktree.read(cx).abs_path().to_path_buf())
+            .collect::<Vec<_>>();
+        let configurations = self.project.read(cx).dotnet_configurations().clone();
+
+        self.discovery_task = cx.spawn(async move |this, cx| {
+            let projects = cx
+                .background_spawn(async move {
+                    let solution_path = roots.iter().find_map(|root| find_solution_file(root))?;
+                    let solution = SolutionFile::load(&solution_path)
+                        .inspect_err(|error| log::error!("Failed to load solution: {error:#}"))
+                        .ok()?;
+                    let projects = solution
+                        .projects
+                        .iter()
+                        .filter_map(|project| {
+                            let path = solution.project_abs_path(project);
+                            let options = configurations.evaluation_options(&path);
+                            let model = ProjectModel::load(&path, &options, &RealProjectFiles)
+                                .inspect_err(|error| {
+                                    log::warn!("Failed to evaluate {}: {error:#}", path.display())
+                                })
+                                .ok()?;
+                            model.is_test_project.then(|| TestProject {
+                                name: model.name.clone(),
+                                tests: declared_tests(model.directory()),
+                                path,
+                            })
+                        })
+                        .collect::<Vec<_>>();
+                    Some(projects)
+                })
+                .await
+                .unwrap_or_default();
+
+            this.update(cx, |this, cx| {
+                this.projects = projects;
+                this.update_editors(cx);
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    /// Builds the test projects and lists their tests with
+    /// `dotnet test --list-tests`, which also finds tests that the source scan
+    /// misses, such as those declared in base classes.
+    fn refresh_tests(&mut self, _: &RefreshTests, _: &mut Window, cx: &mut Context<Self>) {
+        let configurations = self.project.read(cx).dotnet_configurations().clone();
+        let projects = self
+            .projects
+            .iter()
+            .map(|project| {
+                let mut args = vec![
+                    "test".to_string(),
+                    project.path.to_string_lossy().into_owned(),
+                    "--list-tests".to_string(),
+                ];
+                args.extend(configuration_args(
+                    &configurations.evaluation_options(&project.path),
+                ));
+                (project.path.clone(), args)
+            })
+            .collect::<Vec<_>>();
+
+        self.discovery_task = cx.spawn(async move |this, cx| {
+            for (project_path, args) in projects {
+                let directory = project_path.parent().unwrap_or(&project_path).to_path_buf();
+                let output = new_smol_command("dotnet")
+                    .args(&args)
+                    .current_dir(&directory)
+                    .output()
+                    .await
+                    .with_context(|| format!("listing the tests of {}", project_path.display()));
+                let tests = match output {
+                    Ok(output) if output.status.success() => {
+                        parse_test_list(&String::from_utf8_lossy(&output.stdout))
+                    }
+                    Ok(output) => {
+                        log::error!(
+                            "Failed to list the tests of {}:\n{}",
+                            project_path.display(),
+                            String::from_utf8_lossy(&output.stdout)
+                        );
+                        continue;
+                    }
+                    Err(error) => {
+                        log::error!("{error:#}");
+                        continue;
+                    }
+                };
+                let updated = this.update(cx, |this, cx| {
+                    if let Some(project) = this
+                        .projects
+                        .iter_mut()
+                        .find(|project| project.path == project_path)
+                    {
+                        // The list is complete, but only the sources know
+                        // where tests are declared
+                        let mut declared = std::mem::take(&mut project.tests);
+                        project.tests = tests
+                            .into_iter()
+                            .map(|test| {
+                                let location = declared.remove(&test).flatten();
+                                (test, location)
+                            })
+                            .collect();
+                    }
+                    cx.notify();
+                });
+                if updated.is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    fn run_all_tests(&mut self, _: &RunAllTests, window: &mut Window, cx: &mut Context<Self>) {
+        let nodes = self
+            .projects
+            .iter()
+            .map(|project| TestTreeNode::Project {
+                path: project.path.clone(),
+            })
+            .collect::<Vec<_>>();
+        for node in nodes {
+            self.run_tests(&node, false, window, cx);
+        }
+    }
+
+    /// The tests below `node`.
+    fn tests_of(&self, node: &TestTreeNode) -> Vec<String> {
+        self.projects
+            .iter()
+            .filter(|project| project.path == node.project_path())
+            .flat_map(|project| project.tests.keys())
+            .filter(|test| node.contains(test))
+            .cloned()
+            .collect()
+    }
+
+    /// Runs the tests below `node` with `dotnet test`, in a terminal or under
+    /// the debugger, and shows their results once they finish.
+    fn run_tests(
+        &mut self,
+        node: &TestTreeNode,
+        debug: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(project) = self
+            .projects
+            .iter()
+            .find(|project| project.path == node.project_path())
+        else {
+            return;
+        };
+        let tests = self.tests_of(node);
+        let filter = match node {
+            TestTreeNode::Project { .. } => None,
+            TestTreeNode::Namespace { name, .. } | TestTreeNode::Class { name, .. }
+                if !name.is_empty() =>
+            {
+                Some(test_prefix_filter(name))
+            }
+            _ => Some(test_name_filter(tests.iter().map(String::as_str))),
+        };
+        let Some(report_dir) = tempfile::tempdir().log_err() else {
+            return;
+        };
+        let report_path = report_dir.path().join("results.trx");
+
+        let label = format!("dotnet: test {}", node_label(node, &project.name));
+        let cwd = project.path.parent().map(Path::to_path_buf);
+        let mut args = vec![
+            "test".to_string(),
+            project.path.to_string_lossy().into_owned(),
+        ];
+        args.extend(configuration_args(
+            &self
+                .project
+                .read(cx)
+                .dotnet_configurations()
+                .evaluation_options(&project.path),
+        ));
+        if let Some(filter) = filter {
+            args.extend(["--filter".to_string(), filter]);
+        }
+        args.extend(trx_logger_args(&report_path));
+
+        self.running_tests.extend(tests.iter().cloned());
+        cx.notify();
+
+        if debug {
+            // The test task lists the tests instead of running them, and the
+            // debug locator runs them in a test host the debugger attaches to
+            args.push("--list-tests".to_string());
+            let scenario = DebugScenario {
+                adapter: dotnet_debug_adapter(self.project.read(cx), Some(&project.path), cx),
+                label: label.clone().into(),
+                build: Some(BuildTaskDefinition::Template {
+                    task_template: TaskTemplate {
+                        label,
+                        command: "dotnet".to_string(),
+                        args,
+                        cwd: cwd.map(|cwd| cwd.to_string_lossy().into_owned()),
+                        ..TaskTemplate::default()
+                    },
+                    locator_name: Some(DEBUG_LOCATOR.into()),
+                }),
+                config: serde_json::json!({
+                    "type": "coreclr"
+                }),
+                tcp_connection: None,
+            };
+            self.workspace
+                .update(cx, |workspace, cx| {
+                    workspace.start_debug_session(
+                        scenario,
+                        TaskContext::default(),
+                        None,
+                        None,
+                        window,
+                        cx,
+                    );
+                })
+                .ok();
+            self.debug_run = cx.spawn(async move |this, cx| {
+                let results = loop {
+                    cx.background_executor().timer(REPORT_POLL_INTERVAL).await;
+                    let report_path = report_path.clone();
+                    // The report is written once all tests finished
+                    let results = cx
+                        .background_spawn(async move {
+                            report_path.exists().then(|| read_report(&report_path))
+                        })
+                        .await;
+                    if let Some(Ok(results)) = results {
+                        break Ok(results);
+                    }
+                };
+                drop(report_dir);
+                this.update(cx, |this, cx| this.finish_run(&tests, results, cx))
+                    .ok();
+            });
+        } else {
+            let spawn = SpawnInTerminal {
+                label: label.clone(),
+                full_label: label,
+                command: Some("dotnet".to_string()),
+                args,
+                cwd,
+                ..Default::default()
+            };
+            let Ok(run) = self.workspace.update(cx, |workspace, cx| {
+                workspace.spawn_in_terminal(spawn, window, cx)
+            }) else {
+                return;
+            };
+            cx.spawn(async move |this, cx| {
+                let status = run.await;
+                let results = match status {
+                    Some(Err(error)) => Err(error),
+                    // Failing tests fail the run, but still report results
+                    Some(Ok(_)) | None => {
+                        cx.background_spawn(async move { read_report(&report_path) })
+                            .await
+                    }
+                };
+                drop(report_dir);
+                this.update(cx, |this, cx| this.finish_run(&tests, results, cx))
+                    .ok();
+            })
+            .detach();
+        }
+    }
+
+    fn finish_run(
+        &mut self,
+        tests: &[String],
+        results: Result<Vec<TestResult>>,
+        cx: &mut Context<Self>,
+    ) {
+        for test in tests {
+            self.running_tests.remove(test);
+        }
+        match results {
+            Ok(results) => {
+                let mut summaries = HashMap::<String, TestSummary>::default();
+                for result in results {
+                    let duration = result.duration.unwrap_or_default();
+                    summaries
+                        .entry(result.fully_qualified_name)
+                        .and_modify(|summary| {
+                            summary.outcome = summary.outcome.max(result.outcome);
+                            summary.duration += duration;
+                            if summary.message.is_none() {
+                                summary.message = result.message.clone();
+                            }
+                        })
+                        .or_insert(TestSummary {
+                            outcome: result.outcome,
+                            duration,
+                            message: result.message,
+                        });
+                }
+                self.results.extend(summaries);
+            }
+            // The build failed, or the run was cancelled
+            Err(error) => log::warn!("No test results: {error:#}"),
+        }
+        self.update_editors(cx);
+        cx.notify();
+    }
+
+    /// Shows the results of the tests declared in open C# editors next to
+    /// their methods.
+    fn update_editors(&self, cx: &mut Context<Self>) {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let editors = workspace
+            .read(cx)
+            .items_of_type::<Editor>(cx)
+            .collect::<Vec<_>>();
+        for editor in editors {
+            editor.update(cx, |editor, cx| {
+                show_test_results(editor, &self.results, cx)
+            });
+        }
+    }
+
+    fn go_to_test(&self, test: &str, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(location) = self
+            .projects
+            .iter()
+            .find_map(|project| project.tests.get(test).cloned().flatten())
+        else {
+            return;
+        };
+        let Ok(open) = self.workspace.update(cx, |workspace, cx| {
+            workspace.open_abs_path(location.path.clone(), OpenOptions::default(), window, cx)
+        }) else {
+            return;
+        };
+        cx.spawn_in(window, async move |_, cx| {
+            let item = open.await?;
+            if let Some(editor) = item.downcast::<Editor>() {
+                editor.update_in(cx, |editor, window, cx| {
+                    editor.go_to_singleton_buffer_point(
+                        language::Point::new(location.line, 0),
+                        window,
+                        cx,
+                    );
+                })?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn serialization_key(workspace: &Workspace) -> Option<String> {
+        workspace
+            .database_id()
+            .map(|id| i64::from(id).to_string())
+            .or(workspace.session_id())
+            .map(|id| format!("{}-{:?}", TEST_EXPLORER_PANEL_KEY, id))
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let Some(serialization_key) = self
+            .workspace
+            .read_with(cx, |workspace, _| Self::serialization_key(workspace))
+            .ok()
+            .flatten()
+        else {
+            return;
+        };
+        let width = self.width;
+        self.pending_serialization = cx.background_spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        serialization_key,
+                        serde_json::to_string(&SerializedTestExplorerPanel { width })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    fn on_entry_click(&mut self, node: &TestTreeNode, window: &mut Window, cx: &mut Context<Self>) {
+        self.selected_node = Some(node.clone());
+        match node {
+            TestTreeNode::Test { name, .. } => self.go_to_test(name, window, cx),
+            _ => {
+                if !self.collapsed_nodes.remove(node) {
+                    self.collapsed_nodes.insert(node.clone());
+                }
+            }
+        }
+        cx.notify();
+    }
+
+    fn render_tree(&mut self, cx: &mut Context<Self>) -> impl IntoElement {
+        self.visible_items = tree_items(
+            &self.projects,
+            &self.results,
+            &self.running_tests,
+            &self.collapsed_nodes,
+        );
+        uniform_list(
+            "test_explorer_tree",
+            self.visible_items.len(),
+            cx.processor(|this, range: Range<usize>, window, cx| {
+                range
+                    .filter_map(|index| {
+                        let item = this.visible_items.get(index)?.clone();
+                        Some(this.render_entry(index, item, window, cx))
+                    })
+                    .collect()
+            }),
+        )
+        .track_scroll(&self.scroll_handle)
+        .size_full()
+    }
+
+    fn render_entry(
+        &self,
+        index: usize,
+        item: TestTreeItem,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let is_selected = self.selected_node.as_ref() == Some(&item.node);
+        let (icon, color) = match (item.is_running, item.outcome) {
+            (true, _) => (IconName::ArrowCircle, Color::Accent),
+            (false, Some(TestOutcome::Passed)) => (IconName::Check, Color::Success),
+            (false, Some(TestOutcome::Failed)) => (IconName::XCircle, Color::Error),
+            (false, Some(TestOutcome::Skipped)) => (IconName::Dash, Color::Warning),
+            (false, None) => (IconName::Circle, Color::Muted),
+        };
+
+        ListItem::new(index)
+            .spacing(ListItemSpacing::Sparse)
+            .toggle_state(is_selected)
+            .on_click(cx.listener({
+                let node = item.node.clone();
+                move |this, _: &ClickEvent, window, cx| this.on_entry_click(&node, window, cx)
+            }))
+            .on_secondary_mouse_down(cx.listener({
+                let node = item.node.clone();
+                move |this, event: &MouseDownEvent, window, cx| {
+                    this.deploy_context_menu(event.position, &node, window, cx)
+                }
+            }))
+            .child(
+                h_flex()
+                    .gap_1()
+                    .items_center()
+                    .pl(px(item.depth as f32 * 16.0))
+                    .when(item.has_children, |div| {
+                        div.child(
+                            Icon::new(if item.is_expanded {
+                                IconName::ChevronDown
+                            } else {
+                                IconName::ChevronRight
+                            })
+                            .size(IconSize::Small)
+                            .color(Color::Muted),
+                        )
+                    })
+                    .when(!item.has_children, |this| this.child(div().w(px(16.0))))
+                    .child(Icon::new(icon).size(IconSize::Small).color(color))
+                    .child(
+                        div()
+                            .id(("test-explorer-label", index))
+                            .child(Label::new(item.label.clone()).size(LabelSize::Small))
+                            .when_some(item.message.clone(), |this, message| {
+                                this.tooltip(Tooltip::text(message))
+                            }),
+                    )
+                    .when_some(item.duration, |this, duration| {
+                        this.child(
+                            Label::new(format_duration(duration))
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                    }),
+            )
+            .into_any_element()
+    }
+
+    fn deploy_context_menu(
+        &mut self,
+        position: Point<Pixels>,
+        node: &TestTreeNode,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let node = node.clone();
+        let focus_handle = self.focus_handle.clone();
+        let panel_entity = cx.entity();
+        self.selected_node = Some(node.clone());
+
+        let context_menu = ContextMenu::build(window, cx, move |menu, window, _| {
+            let menu = menu
+                .context(focus_handle.clone())
+                .entry(
+                    "Run",
+                    None,
+                    window.handler_for(&panel_entity, {
+                        let node = node.clone();
+                        move |this, window, cx| this.run_tests(&node, false, window, cx)
+                    }),
+                )
+                .entry(
+                    "Debug",
+                    None,
+                    window.handler_for(&panel_entity, {
+                        let node = node.clone();
+                        move |this, window, cx| this.run_tests(&node, true, window, cx)
+                    }),
+                );
+            match &node {
+                TestTreeNode::Test { name, .. } => {
+                    let name = name.clone();
+                    menu.separator().entry(
+                        "Go to Test",
+                        None,
+                        window.handler_for(&panel_entity, move |this, window, cx| {
+                            this.go_to_test(&name, window, cx);
+                        }),
+                    )
+                }
+                _ => menu,
+            }
+        });
+
+        window.focus(&context_menu.focus_handle(cx));
+        let subscription = cx.subscribe(&context_menu, |this, _, _: &DismissEvent, cx| {
+            this.context_menu.take();
+            cx.notify();
+        });
+
+        self.context_menu = Some((context_menu, position, subscription));
+        cx.notify();
+    }
+
+    fn render_toolbar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .px_2()
+            .py_1()
+            .gap_1()
+            .justify_end()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                IconButton::new("run-all-tests", IconName::PlayFilled)
+                    .icon_size(IconSize::Small)
+                    .tooltip(Tooltip::text("Run All Tests"))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.run_all_tests(&RunAllTests, window, cx)
+                    })),
+            )
+            .child(
+                IconButton::new("refresh-tests", IconName::RotateCw)
+                    .icon_size(IconSize::Small)
+                    .tooltip(Tooltip::text("Build and Refresh Tests"))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.refresh_tests(&RefreshTests, window, cx)
+                    })),
+            )
+    }
+}
+
+/// Arguments selecting a project's configuration for `dotnet test`.
+fn configuration_args(options: &EvaluationOptions) -> Vec<String> {
+    let mut args = vec!["--configuration".to_string(), options.configuration.clone()];
+    args.extend(
+        options
+            .platform
+            .as_ref()
+            .map(|platform| format!("-p:Platform={platform}")),
+    );
+    args
+}
+
+/// Scans the C# sources of the project in `project_dir` for test methods.
+fn declared_tests(project_dir: &Path) -> BTreeMap<String, Option<TestLocation>> {
+    let mut tests = BTreeMap::new();
+    let mut directories = vec![project_dir.to_path_buf()];
+    while let Some(directory) = directories.pop() {
+        let Ok(entries) = std::fs::read_dir(&directory) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if path.is_dir() {
+                // Build output and tool directories hold no sources of the project
+                if !matches!(name.as_ref(), "bin" | "obj") && !name.starts_with('.') {
+                    directories.push(path);
+                }
+            } else if name.ends_with(".cs")
+                && let Some(source) = std::fs::read_to_string(&path).log_err()
+            {
+                for method in find_test_methods(&source) {
+                    tests.insert(
+                        method.fully_qualified_name(),
+                        Some(TestLocation {
+                            path: path.clone(),
+                            line: method.line,
+                        }),
+                    );
+                }
+            }
+        }
+    }
+    tests
+}
+
+fn read_report(report_path: &Path) -> Result<Vec<TestResult>> {
+    let content = std::fs::read_to_string(report_path)
+        .with_context(|| format!("reading test results from {}", report_path.display()))?;
+    parse_trx(&content)
+}
+
+/// Shows the results of the tests declared in `editor`'s C# file: a colored
+/// gutter next to each test method, and its outcome and duration after it.
+fn show_test_results(
+    editor: &mut Editor,
+    results: &HashMap<String, TestSummary>,
+    cx: &mut Context<Editor>,
+) {
+    enum PassedTestGutter {}
+    enum FailedTestGutter {}
+    enum SkippedTestGutter {}
+
+    let Some(buffer) = editor.buffer().read(cx).as_singleton() else {
+        return;
+    };
+    let is_csharp = buffer
+        .read(cx)
+        .file()
+        .is_some_and(|file| file.file_name(cx).ends_with(".cs"));
+    if !is_csharp {
+        return;
+    }
+    let text = buffer.read(cx).text();
+    let lines = text.lines().collect::<Vec<_>>();
+    let snapshot = editor.buffer().read(cx).snapshot(cx);
+
+    let mut passed = Vec::new();
+    let mut failed = Vec::new();
+    let mut skipped = Vec::new();
+    let mut inlays = Vec::new();
+    for method in find_test_methods(&text) {
+        let Some(summary) = results.get(&method.fully_qualified_name()) else {
+            continue;
+        };
+        let line_len = lines.get(method.line as usize).map_or(0, |line| line.len());
+        let start = snapshot.anchor_before(language::Point::new(method.line, 0));
+        let end = snapshot.anchor_after(language::Point::new(method.line, line_len as u32));
+        let (ranges, label) = match summary.outcome {
+            TestOutcome::Passed => (
+                &mut passed,
+                format!("  ✓ {}", format_duration(summary.duration)),
+            ),
+            TestOutcome::Failed => (
+                &mut failed,
+                format!("  ✗ {}", format_duration(summary.duration)),
+            ),
+            TestOutcome::Skipped => (&mut skipped, "  skipped".to_string()),
+        };
+        ranges.push(start..end);
+        inlays.push((end, label));
+    }
+
+    editor.highlight_gutter::<PassedTestGutter>(passed, |cx| cx.theme().status().success, cx);
+    editor.highlight_gutter::<FailedTestGutter>(failed, |cx| cx.theme().status().error, cx);
+    editor.highlight_gutter::<SkippedTestGutter>(skipped, |cx| cx.theme().status().warning, cx);
+    editor.set_test_results(inlays, cx);
+}
+
+fn format_duration(duration: Duration) -> String {
+    if duration < Duration::from_secs(1) {
+        format!("{} ms", duration.as_millis())
+    } else {
+        format!("{:.1} s", duration.as_secs_f64())
+    }
+}
+
+/// The namespace part of a fully qualified class name.
+fn namespace_of(class_name: &str) -> &str {
+    class_name
+        .rsplit_once('.')
+        .map_or("", |(namespace, _)| namespace)
+}
+
+fn node_label(node: &TestTreeNode, project_name: &str) -> String {
+    match node {
+        TestTreeNode::Project { .. } => project_name.to_string(),
+        TestTreeNode::Namespace { name, .. }
+        | TestTreeNode::Class { name, .. }
+        | TestTreeNode::Test { name, .. } => name.clone(),
+    }
+}
+
+#[derive(Clone)]
+struct TestTreeItem {
+    node: TestTreeNode,
+    label: String,
+    depth: usize,
+    is_expanded: bool,
+    has_children: bool,
+    /// The worst outcome of the tests below, if any of them ran
+    outcome: Option<TestOutcome>,
+    /// The total duration of the tests below that ran
+    duration: Option<Duration>,
+    /// The failure message of a test
+    message: Option<String>,
+    is_running: bool,
+}
+
+/// Flattens the visible part of the test tree into rows: projects, then
+/// namespaces, classes and tests.
+fn tree_items(
+    projects: &[TestProject],
+    results: &HashMap<String, TestSummary>,
+    running_tests: &HashSet<String>,
+    collapsed_nodes: &HashSet<TestTreeNode>,
+) -> Vec<TestTreeItem> {
+    let mut items = Vec::new();
+    let mut push_item = |node: TestTreeNode, label: &str, depth: usize, tests: &[&String]| {
+        let ran = tests
+            .iter()
+            .filter_map(|test| results.get(*test))
+            .collect::<Vec<_>>();
+        let is_test = matches!(node, TestTreeNode::Test { .. });
+        let is_expanded = !is_test && !collapsed_nodes.contains(&node);
+        items.push(TestTreeItem {
+            label: label.to_string(),
+            depth,
+            is_expanded,
+            has_children: !is_test,
+            outcome: ran.iter().map(|summary| summary.outcome).max(),
+            duration: (!ran.is_empty()).then(|| ran.iter().map(|summary| summary.duration).sum()),
+            message: ran
+                .iter()
+                .find_map(|summary| summary.message.clone())
+                .filter(|_| is_test),
+            is_running: tests.iter().any(|test| running_tests.contains(*test)),
+            node,
+        });
+        is_expanded
+    };
+
+    for project in projects {
+        let mut namespaces = BTreeMap::<&str, BTreeMap<&str, Vec<&String>>>::new();
+        for test in project.tests.keys() {
+            let (class_name, _) = split_test_name(test).unwrap_or(("", test.as_str()));
+            namespaces
+                .entry(namespace_of(class_name))
+                .or_default()
+                .entry(class_name)
+                .or_default()
+                .push(test);
+        }
+
+        let project_tests = project.tests.keys().collect::<Vec<_>>();
+        let project_node = TestTreeNode::Project {
+            path: project.path.clone(),
+        };
+        if !push_item(project_node, &project.name, 0, &project_tests) {
+            continue;
+        }
+        for (namespace, classes) in namespaces {
+            let namespace_tests = classes.values().flatten().copied().collect::<Vec<_>>();
+            let namespace_node = TestTreeNode::Namespace {
+                project: project.path.clone(),
+                name: namespace.to_string(),
+            };
+            let label = if namespace.is_empty() {
+                "<global namespace>"
+            } else {
+                namespace
+            };
+            if !push_item(namespace_node, label, 1, &namespace_tests) {
+                continue;
+            }
+            for (class_name, tests) in classes {
+                let class_node = TestTreeNode::Class {
+                    project: project.path.clone(),
+                    name: class_name.to_string(),
+                };
+                let label = class_name
+                    .strip_prefix(namespace)
+                    .map_or(class_name, |name| name.trim_start_matches('.'));
+                if !push_item(class_node, label, 2, &tests) {
+                    continue;
+                }
+                for test in tests {
+                    let test_node = TestTreeNode::Test {
+                        project: project.path.clone(),
+                        name: test.clone(),
+                    };
+                    let label = split_test_name(test).map_or(test.as_str(), |(_, method)| method);
+                    push_item(test_node, label, 3, &[test]);
+                }
+            }
+        }
+    }
+    items
+}
+
+impl Render for TestExplorerPanel {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if self.projects.is_empty() {
+            return v_flex()
+                .id("empty-test_explorer_panel")
+                .p_4()
+                .size_full()
+                .items_center()
+                .justify_center()
+                .gap_1()
+                .track_focus(&self.focus_handle)
+                .child(
+                    Label::new("No .NET test projects found")
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                );
+        }
+
+        v_flex()
+            .id("test_explorer_panel")
+            .key_context("TestExplorer")
+            .size_full()
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::refresh_tests))
+            .on_action(cx.listener(Self::run_all_tests))
+            .child(self.render_toolbar(cx))
+            .child(
+                self.render_tree(cx)
+                    .custom_scrollbars(
+                        Scrollbars::default()
+                            .tracked_scroll_handle(&self.scroll_handle)
+                            .with_track_along(
+                                ScrollAxes::Horizontal,
+                                cx.theme().colors().panel_background,
+                            )
+                            .notify_content(),
+                        window,
+                        cx,
+                    )
+                    .size_full(),
+            )
+            .children(self.context_menu.as_ref().map(|(menu, position, _)| {
+                deferred(
+                    anchored()
+                        .position(*position)
+                        .anchor(gpui::Corner::TopLeft)
+                        .child(menu.clone()),
+                )
+                .with_priority(3)
+            }))
+    }
+}
+
+impl EventEmitter<PanelEvent> for TestExplorerPanel {}
+
+impl Panel for TestExplorerPanel {
+    // Docks with the solution explorer
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        match SolutionExplorerSettings::get_global(cx).dock {
+            Some(DockSide::Right) => DockPosition::Right,
+            Some(DockSide::Left) | None => DockPosition::Left,
+        }
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file(self.fs.clone(), cx, move |settings, _| {
+            let dock = match position {
+                DockPosition::Left | DockPosition::Bottom => DockSide::Left,
+                DockPosition::Right => DockSide::Right,
+            };
+            settings.solution_explorer.get_or_insert_default().dock = Some(dock);
+        });
+    }
+
+    fn size(&self, _: &Window, cx: &App) -> Pixels {
+        self.width
+            .unwrap_or_else(|| SolutionExplorerSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.width = size;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, _: &Window, _cx: &App) -> Option<IconName> {
+        Some(IconName::ListTree)
+    }
+
+    fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<&'static str> {
+        Some("Test Explorer")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn persistent_name() -> &'static str {
+        "Test Explorer"
+    }
+
+    fn panel_key() -> &'static str {
+        TEST_EXPLORER_PANEL_KEY
+    }
+
+    fn starts_open(&self, _: &Window, _: &App) -> bool {
+        false
+    }
+
+    fn activation_priority(&self) -> u32 {
+        11
+    }
+}
+
+impl Focusable for TestExplorerPanel {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl TestExplorerPanel {
+    pub async fn load(
+        workspace: WeakEntity<Workspace>,
+        mut cx: AsyncWindowContext,
+    ) -> Result<Entity<Self>> {
+        let serialized_panel = match workspace
+            .read_with(&cx, |workspace, _| Self::serialization_key(workspace))
+            .ok()
+            .flatten()
+        {
+            Some(serialization_key) => cx
+                .background_spawn(async move { KEY_VALUE_STORE.read_kvp(&serialization_key) })
+                .await
+                .context("loading test explorer panel")
+                .log_err()
+                .flatten()
+                .map(|panel| serde_json::from_str::<SerializedTestExplorerPanel>(&panel))
+                .transpose()
+                .log_err()
+                .flatten(),
+            None => None,
+        };
+
+        workspace.update_in(&mut cx, |workspace, window, cx| {
+            let project = workspace.project().clone();
+            let workspace = cx.entity();
+            let panel = cx.new(|cx| {
+                let mut panel = TestExplorerPanel::new(&workspace, project, window, cx);
+                if let Some(serialized_panel) = serialized_panel {
+                    panel.width = serialized_panel.width.map(|px| px.round());
+                }
+                panel
+            });
+            Ok(panel)
+        })
+    }
+}
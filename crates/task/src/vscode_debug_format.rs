@@ -30,6 +30,11 @@ impl VsCodeDebugTaskDefinition {
             if let Some(port) = self.port.take() {
                 config.insert("port".to_owned(), port.into());
             }
+        } else if let Some(config) = config.as_object_mut()
+            && self.r#type == "coreclr"
+        {
+            // .NET configurations are told apart from other vsdbg ones by their type
+            config.insert("type".to_owned(), self.r#type.clone().into());
         }
         let definition = DebugScenario {
             label: label.into(),
@@ -69,10 +74,16 @@ impl TryFrom<VsCodeDebugTaskFile> for DebugTaskFile {
             ),
             ("file".to_owned(), VariableName::File.to_string()),
         ]))
-        .with_commands([(
-            "pickMyProcess".to_owned(),
-            VariableName::PickProcessId.to_string(),
-        )]);
+        .with_commands([
+            (
+                "pickMyProcess".to_owned(),
+                VariableName::PickProcessId.to_string(),
+            ),
+            (
+                "pickDotnetProcess".to_owned(),
+                VariableName::PickDotNetProcessId.to_string(),
+            ),
+        ]);
         let templates = file
             .configurations
             .into_iter()
@@ -91,6 +102,7 @@ fn task_type_to_adapter_name(task_type: &str) -> String {
         "cppdbg" | "lldb" => "CodeLLDB",
         "debugpy" => "Debugpy",
         "rdbg" => "rdbg",
+        "coreclr" => "vsdbg",
         _ => task_type,
     }
     .to_owned()
@@ -191,4 +203,40 @@ mod tests {
             }])
         );
     }
+
+    #[test]
+    fn test_command_pickdotnetprocess_replacement() {
+        let raw = r#"
+            {
+                "version": "0.2.0",
+                "configurations": [
+                    {
+                        "name": ".NET Attach",
+                        "request": "attach",
+                        "type": "coreclr",
+                        "processId": "${command:pickDotnetProcess}"
+                    }
+                ]
+            }
+        "#;
+        let parsed: VsCodeDebugTaskFile =
+            serde_json_lenient::from_str(raw).expect("deserializing launch.json");
+        let zed = DebugTaskFile::try_from(parsed).expect("converting to Zed debug templates");
+
+        let expected_placeholder = format!("${{{}}}", VariableName::PickDotNetProcessId);
+        pretty_assertions::assert_eq!(
+            zed,
+            DebugTaskFile(vec![DebugScenario {
+                label: ".NET Attach".into(),
+                adapter: "vsdbg".into(),
+                config: json!({
+                    "request": "attach",
+                    "type": "coreclr",
+                    "processId": expected_placeholder,
+                }),
+                tcp_connection: None,
+                build: None
+            }])
+        );
+    }
 }
@@ -3,7 +3,7 @@ use std::process::ExitStatus;
 use anyhow::Result;
 use gpui::{AppContext, Context, Entity, Task};
 use language::Buffer;
-use project::{TaskSourceKind, WorktreeId};
+use project::{TaskSourceKind, WorktreeId, dotnet_build_diagnostics::DotNetBuildLog};
 use remote::ConnectionState;
 use task::{DebugScenario, ResolvedTask, SpawnInTerminal, TaskContext, TaskTemplate};
 use ui::Window;
@@ -71,8 +71,8 @@ impl Workspace {
             });
         }
 
-        if let Some(terminal_provider) = self.terminal_provider.as_ref() {
-            let task_status = terminal_provider.spawn(spawn_in_terminal, window, cx);
+        if self.terminal_provider.is_some() {
+            let task_status = self.spawn_in_terminal(spawn_in_terminal, window, cx);
 
             let task = cx.spawn(async |w, cx| {
                 let res = cx.background_spawn(task_status).await;
@@ -121,14 +121,31 @@ impl Workspace {
 
     pub fn spawn_in_terminal(
         self: &mut Workspace,
-        spawn_in_terminal: SpawnInTerminal,
+        mut spawn_in_terminal: SpawnInTerminal,
         window: &mut Window,
         cx: &mut Context<Workspace>,
     ) -> Task<Option<Result<ExitStatus>>> {
-        if let Some(terminal_provider) = self.terminal_provider.as_ref() {
-            terminal_provider.spawn(spawn_in_terminal, window, cx)
+        let Some(terminal_provider) = self.terminal_provider.as_ref() else {
+            return Task::ready(None);
+        };
+
+        // .NET builds report their errors and warnings as project diagnostics
+        let build_log = if self.project.read(cx).is_local() {
+            DotNetBuildLog::attach(&mut spawn_in_terminal)
         } else {
-            Task::ready(None)
-        }
+            None
+        };
+        let task_status = terminal_provider.spawn(spawn_in_terminal, window, cx);
+        let Some(build_log) = build_log else {
+            return task_status;
+        };
+        let build_diagnostics = self.project.read(cx).dotnet_build_diagnostics().clone();
+        cx.background_spawn(async move {
+            let status = task_status.await;
+            if status.is_some() {
+                build_log.publish(&build_diagnostics);
+            }
+            status
+        })
     }
 }
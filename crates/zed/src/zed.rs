@@ -15,6 +15,7 @@ pub use app_menus::*;
 use assets::Assets;
 use audio::{AudioSettings, REPLAY_DURATION};
 use breadcrumbs::Breadcrumbs;
+use call_hierarchy::{CallHierarchyPanel, TypeHierarchyPanel};
 use client::zed_urls;
 use collections::VecDeque;
 use debugger_ui::debugger_panel::DebugPanel;
@@ -51,7 +52,7 @@ use paths::{
 };
 use project::{DirectoryLister, DisableAiSettings, ProjectItem};
 use project_panel::ProjectPanel;
-use solution_explorer::SolutionExplorerPanel;
+use solution_explorer::{SolutionExplorerPanel, TestExplorerPanel};
 use prompt_store::PromptBuilder;
 use quick_action_bar::QuickActionBar;
 use recent_projects::open_remote_project;
@@ -426,6 +427,8 @@ pub fn initialize_workspace(
             window,
             cx,
         );
+        let dotnet_watch_indicator =
+            cx.new(|cx| solution_explorer::DotNetWatchIndicator::new(workspace, cx));
         let active_buffer_language =
             cx.new(|_| language_selector::ActiveBufferLanguage::new(workspace));
         let active_toolchain_language =
@@ -451,6 +454,7 @@ pub fn initialize_workspace(
             status_bar.add_left_item(lsp_button, window, cx);
             status_bar.add_left_item(diagnostic_summary, window, cx);
             status_bar.add_left_item(activity_indicator, window, cx);
+            status_bar.add_left_item(dotnet_watch_indicator, window, cx);
             status_bar.add_right_item(edit_prediction_ui, window, cx);
             status_bar.add_right_item(active_buffer_language, window, cx);
             status_bar.add_right_item(active_toolchain_language, window, cx);
@@ -657,6 +661,9 @@ fn initialize_panels(
         );
         let debug_panel = DebugPanel::load(workspace_handle.clone(), cx);
         let solution_explorer_panel = SolutionExplorerPanel::load(workspace_handle.clone(), cx.clone());
+        let test_explorer_panel = TestExplorerPanel::load(workspace_handle.clone(), cx.clone());
+        let call_hierarchy_panel = CallHierarchyPanel::load(workspace_handle.clone(), cx.clone());
+        let type_hierarchy_panel = TypeHierarchyPanel::load(workspace_handle.clone(), cx.clone());
 
         async fn add_panel_when_ready(
             panel_task: impl Future<Output = anyhow::Result<Entity<impl workspace::Panel>>> + 'static,
@@ -682,6 +689,9 @@ fn initialize_panels(
             add_panel_when_ready(notification_panel, workspace_handle.clone(), cx.clone()),
             add_panel_when_ready(debug_panel, workspace_handle.clone(), cx.clone()),
             add_panel_when_ready(solution_explorer_panel, workspace_handle.clone(), cx.clone()),
+            add_panel_when_ready(test_explorer_panel, workspace_handle.clone(), cx.clone()),
+            add_panel_when_ready(call_hierarchy_panel, workspace_handle.clone(), cx.clone()),
+            add_panel_when_ready(type_hierarchy_panel, workspace_handle.clone(), cx.clone()),
             initialize_agent_panel(workspace_handle, prompt_builder, cx.clone()).map(|r| r.log_err())
         );
 
@@ -1037,6 +1047,14 @@ fn register_actions(
                 workspace.toggle_panel_focus::<SolutionExplorerPanel>(window, cx);
             },
         )
+        .register_action(
+            |workspace: &mut Workspace,
+             _: &zed_actions::test_explorer::ToggleFocus,
+             window: &mut Window,
+             cx: &mut Context<Workspace>| {
+                workspace.toggle_panel_focus::<TestExplorerPanel>(window, cx);
+            },
+        )
         .register_action(
             |workspace: &mut Workspace,
              _: &collab_ui::collab_panel::ToggleFocus,
@@ -4931,6 +4949,7 @@ mod tests {
             git_ui::init(cx);
             project_panel::init(cx);
             outline_panel::init(cx);
+            call_hierarchy::init(cx);
             terminal_view::init(cx);
             copilot::copilot_chat::init(
                 app_state.fs.clone(),
@@ -2,7 +2,9 @@ use client::{Client, UserStore};
 use codestral::CodestralEditPredictionDelegate;
 use collections::HashMap;
 use copilot::{Copilot, CopilotEditPredictionDelegate};
-use edit_prediction::{SweepFeatureFlag, ZedEditPredictionDelegate, Zeta2FeatureFlag};
+use edit_prediction::{
+    LspEditPredictionDelegate, SweepFeatureFlag, ZedEditPredictionDelegate, Zeta2FeatureFlag,
+};
 use editor::Editor;
 use feature_flags::FeatureFlagAppExt;
 use gpui::{AnyWindowHandle, App, AppContext as _, Context, Entity, WeakEntity};
@@ -203,6 +205,12 @@ fn assign_edit_prediction_provider(
             let provider = cx.new(|_| CodestralEditPredictionDelegate::new(http_client));
             editor.set_edit_prediction_provider(Some(provider), window, cx);
         }
+        EditPredictionProvider::LanguageServer => {
+            if let Some(project) = editor.project() {
+                let provider = cx.new(|_| LspEditPredictionDelegate::new(project.clone()));
+                editor.set_edit_prediction_provider(Some(provider), window, cx);
+            }
+        }
         value @ (EditPredictionProvider::Experimental(_) | EditPredictionProvider::Zed) => {
             let ep_store = edit_prediction::EditPredictionStore::global(client, &user_store, cx);
 
@@ -224,6 +224,8 @@ pub mod git {
             Branch,
             /// Opens the git stash selector.
             ViewStash,
+            /// Opens the git tag selector.
+            Tag,
             /// Opens the git worktree selector.
             Worktree
         ]
@@ -265,6 +267,36 @@ pub mod project_panel {
         ]
     );
 }
+pub mod call_hierarchy {
+    use gpui::actions;
+
+    actions!(
+        call_hierarchy,
+        [
+            /// Toggles focus on the call hierarchy panel.
+            ToggleFocus,
+            /// Shows the symbols calling the symbol under the cursor.
+            ShowIncomingCalls,
+            /// Shows the symbols called by the symbol under the cursor.
+            ShowOutgoingCalls,
+        ]
+    );
+}
+pub mod type_hierarchy {
+    use gpui::actions;
+
+    actions!(
+        type_hierarchy,
+        [
+            /// Toggles focus on the type hierarchy panel.
+            ToggleFocus,
+            /// Shows the types the type under the cursor derives from or implements.
+            ShowSupertypes,
+            /// Shows the types deriving from or implementing the type under the cursor.
+            ShowSubtypes,
+        ]
+    );
+}
 pub mod solution_explorer {
     use gpui::actions;
 
@@ -276,6 +308,17 @@ pub mod solution_explorer {
         ]
     );
 }
+pub mod test_explorer {
+    use gpui::actions;
+
+    actions!(
+        test_explorer,
+        [
+            /// Toggles focus on the test explorer panel.
+            ToggleFocus
+        ]
+    );
+}
 pub mod feedback {
     use gpui::actions;
 